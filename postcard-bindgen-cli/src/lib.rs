@@ -0,0 +1,316 @@
+//! The `postcard-bindgen` and `cargo postcard-bindgen` commands, which generate bindings from
+//! a JSON document written by `to_json()`, without compiling the Rust types. See the
+//! `json_ir` module of `postcard-bindgen-core` for the format.
+//!
+//! ```text
+//! postcard-bindgen javascript --ir schema.json --out bindings
+//! postcard-bindgen python --out bindings -- cargo run --quiet --example export
+//! ```
+//!
+//! Instead of a file, a command can be passed after `--`, e.g. an exporter of the crate which
+//! defines the types. It has to print the document to stdout.
+//!
+//! With `--watch`, the file is checked for changes until the command is stopped and the
+//! bindings are written again after each change.
+//!
+//! The options are read from `postcard-bindgen.toml` in the working directory if it exists,
+//! or the file passed with `--config`. Options passed as arguments take precedence. Without
+//! an input, the exporter of the configuration is run with `cargo run`.
+//!
+//! `cargo postcard-bindgen` takes the same arguments, but reads the configuration from the
+//! root of the workspace it's run in.
+
+use std::{
+    ffi::OsString,
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+    process::{self, ExitCode, Stdio},
+    thread,
+    time::Duration,
+};
+
+use clap::{ArgGroup, CommandFactory, FromArgMatches, Parser, ValueEnum};
+use postcard_bindgen::{
+    backend::ContainerCollection,
+    config::{self, Config, Exporter},
+    json_ir, Version,
+};
+
+/// How the tool is invoked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// As `postcard-bindgen`, relative to the working directory.
+    Standalone,
+    /// As `cargo postcard-bindgen`, relative to the root of the workspace.
+    Cargo,
+}
+
+#[derive(Parser)]
+#[command(name = "postcard-bindgen", version, about)]
+#[command(group(ArgGroup::new("input").args(["ir", "exporter"])))]
+struct Cli {
+    /// Language of the bindings, `javascript` if neither passed nor configured.
+    language: Option<Language>,
+    /// JSON document which describes the types.
+    #[arg(long, value_name = "FILE")]
+    ir: Option<PathBuf>,
+    /// Directory the bindings are written to, `out-dir` of the configuration by default.
+    #[arg(long, value_name = "DIR")]
+    out: Option<PathBuf>,
+    /// Configuration file, `postcard-bindgen.toml` is used if it exists.
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+    /// Name of the package, module or source file.
+    #[arg(long)]
+    name: Option<String>,
+    /// Version of the package, only used by packages with a manifest.
+    #[arg(long = "package-version")]
+    package_version: Option<Version>,
+    /// Only generates the code to serialize values.
+    #[arg(long, conflicts_with = "deserialize_only")]
+    serialize_only: bool,
+    /// Only generates the code to deserialize values.
+    #[arg(long)]
+    deserialize_only: bool,
+    /// Writes the bindings again whenever the JSON document changes.
+    #[arg(long, requires = "ir")]
+    watch: bool,
+    /// Command which prints the JSON document to stdout.
+    #[arg(last = true, value_name = "EXPORTER")]
+    exporter: Vec<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Language {
+    /// npm package
+    Javascript,
+    /// TypeScript module
+    Typescript,
+    /// Deno module
+    Deno,
+    /// JavaScript module for browsers
+    Web,
+    /// pip package
+    Python,
+    C,
+    Cpp,
+    Kotlin,
+    Swift,
+    Dart,
+    Csharp,
+    Go,
+    Java,
+    Lua,
+}
+
+impl From<Language> for config::Language {
+    fn from(language: Language) -> Self {
+        match language {
+            Language::Javascript => Self::Javascript,
+            Language::Typescript => Self::Typescript,
+            Language::Deno => Self::Deno,
+            Language::Web => Self::Web,
+            Language::Python => Self::Python,
+            Language::C => Self::C,
+            Language::Cpp => Self::Cpp,
+            Language::Kotlin => Self::Kotlin,
+            Language::Swift => Self::Swift,
+            Language::Dart => Self::Dart,
+            Language::Csharp => Self::Csharp,
+            Language::Go => Self::Go,
+            Language::Java => Self::Java,
+            Language::Lua => Self::Lua,
+        }
+    }
+}
+
+/// Parses the arguments of the process and runs the command.
+pub fn main(command: Command) -> ExitCode {
+    let mut args = std::env::args_os().collect::<Vec<_>>();
+    let cli = match command {
+        Command::Standalone => Cli::parse_from(args),
+        Command::Cargo => {
+            // cargo passes the name of the subcommand as first argument
+            if args.get(1).is_some_and(|arg| arg == "postcard-bindgen") {
+                args.remove(1);
+            }
+            let matches = Cli::command()
+                .bin_name("cargo postcard-bindgen")
+                .get_matches_from(args);
+            Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit())
+        }
+    };
+
+    match run(&cli, command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// How often the JSON document is checked for changes in watch mode.
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+fn run(cli: &Cli, command: Command) -> io::Result<()> {
+    let config_path = match &cli.config {
+        Some(path) => Some(path.clone()),
+        None => {
+            let dir = match command {
+                Command::Standalone => PathBuf::new(),
+                Command::Cargo => workspace_root()?,
+            };
+            Some(dir.join(Config::FILE_NAME)).filter(|path| path.is_file())
+        }
+    };
+    // relative paths of the configuration are relative to its directory
+    let config_dir = config_path
+        .as_deref()
+        .and_then(Path::parent)
+        .unwrap_or(Path::new(""))
+        .to_path_buf();
+    let config = config(cli, config_path.as_deref())?;
+
+    let out = match (&cli.out, &config.out_dir) {
+        (Some(out), _) => out.clone(),
+        (None, Some(out_dir)) => config_dir.join(out_dir),
+        (None, None) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "no output directory, pass `--out` or configure `out-dir`",
+            ))
+        }
+    };
+
+    match (&cli.ir, &config.exporter) {
+        (Some(path), _) if cli.watch => watch(&config, path, &out),
+        (Some(path), _) => config.build(&out, json_ir::from_json(File::open(path)?)?),
+        (None, _) if !cli.exporter.is_empty() => config.build(&out, export(&cli.exporter, None)?),
+        (None, Some(exporter)) => {
+            config.build(&out, export(&cargo_run(exporter), Some(&config_dir))?)
+        }
+        (None, None) => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "no input, pass `--ir`, an exporter after `--` or configure `[exporter]`",
+        )),
+    }
+}
+
+/// Reads the configuration file and overrides it with the passed options.
+fn config(cli: &Cli, path: Option<&Path>) -> io::Result<Config> {
+    let mut config = match path {
+        Some(path) => Config::load(path)?,
+        None => Config::default(),
+    };
+
+    if let Some(language) = cli.language {
+        config.language = language.into();
+    }
+    if let Some(name) = &cli.name {
+        config.name = name.clone();
+    }
+    if let Some(version) = cli.package_version {
+        config.version = version;
+    }
+    if cli.serialize_only {
+        (config.serialization, config.deserialization) = (true, false);
+    }
+    if cli.deserialize_only {
+        (config.serialization, config.deserialization) = (false, true);
+    }
+
+    Ok(config)
+}
+
+/// Locates the root of the workspace in the working directory with cargo.
+fn workspace_root() -> io::Result<PathBuf> {
+    let output = process::Command::new(cargo())
+        .args(["locate-project", "--workspace", "--message-format", "plain"])
+        .stderr(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other("no cargo workspace found"));
+    }
+
+    let manifest = String::from_utf8_lossy(&output.stdout);
+    Ok(Path::new(manifest.trim())
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default())
+}
+
+/// The cargo which runs the subcommand, or the one on the path.
+fn cargo() -> OsString {
+    std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into())
+}
+
+/// The `cargo run` command of the exporter.
+fn cargo_run(exporter: &Exporter) -> Vec<String> {
+    let mut command = vec![
+        cargo().to_string_lossy().into_owned(),
+        "run".into(),
+        "--quiet".into(),
+    ];
+    let options = [
+        ("--package", &exporter.package),
+        ("--bin", &exporter.bin),
+        ("--example", &exporter.example),
+    ];
+    for (option, value) in options {
+        if let Some(value) = value {
+            command.extend([option.into(), value.clone()]);
+        }
+    }
+    if !exporter.features.is_empty() {
+        command.extend(["--features".into(), exporter.features.join(",")]);
+    }
+    command
+}
+
+/// Writes the bindings each time the content of the document changes.
+///
+/// Errors are reported and the document is watched further, as it may be saved in an invalid
+/// state while it's edited.
+fn watch(config: &Config, path: &Path, out: &Path) -> io::Result<()> {
+    let mut last = None;
+    loop {
+        // a missing file is skipped, editors may replace it by a new one on save
+        if let Ok(content) = fs::read(path) {
+            if last.as_ref() != Some(&content) {
+                match json_ir::from_json(content.as_slice())
+                    .and_then(|bindings| config.build(out, bindings))
+                {
+                    Ok(()) => eprintln!("wrote bindings to {}", out.display()),
+                    Err(e) => eprintln!("error: {e}"),
+                }
+                last = Some(content);
+            }
+        }
+        thread::sleep(WATCH_INTERVAL);
+    }
+}
+
+/// Runs the exporter in `dir` and reads the document it prints.
+fn export(exporter: &[String], dir: Option<&Path>) -> io::Result<ContainerCollection> {
+    let (program, args) = exporter
+        .split_first()
+        .expect("the exporter has at least a program");
+    let mut command = process::Command::new(program);
+    command.args(args).stderr(Stdio::inherit());
+    if let Some(dir) = dir.filter(|dir| !dir.as_os_str().is_empty()) {
+        command.current_dir(dir);
+    }
+
+    let output = command.output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "exporter `{}` failed with {}",
+            exporter.join(" "),
+            output.status
+        )));
+    }
+
+    json_ir::from_json(output.stdout.as_slice())
+}
@@ -0,0 +1,7 @@
+use std::process::ExitCode;
+
+use postcard_bindgen_cli::Command;
+
+fn main() -> ExitCode {
+    postcard_bindgen_cli::main(Command::Standalone)
+}
@@ -0,0 +1,209 @@
+use std::{
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use postcard_bindgen::{generate_bindings, PostcardBindings};
+use serde::Serialize;
+
+#[derive(Serialize, PostcardBindings)]
+struct A {
+    a: u8,
+    b: Vec<u16>,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize, PostcardBindings)]
+enum B {
+    A,
+    B(A),
+}
+
+/// Writes the document of the test types into a fresh directory.
+fn write_ir(test: &str) -> (PathBuf, PathBuf) {
+    let dir = std::env::temp_dir().join("postcard-bindgen-cli").join(test);
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let ir = dir.join("schema.json");
+    std::fs::write(&ir, generate_bindings!(A, B).to_json()).unwrap();
+    (dir, ir)
+}
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_postcard-bindgen"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn path(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+#[test]
+fn test_generate_from_ir_file() {
+    let (dir, ir) = write_ir("ir_file");
+    let out = dir.join("out");
+
+    let output = run(&["javascript", "--ir", path(&ir), "--out", path(&out)]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(out.join("bindings").join("package.json").is_file());
+
+    let output = run(&[
+        "go",
+        "--ir",
+        path(&ir),
+        "--out",
+        path(&out),
+        "--name",
+        "types",
+    ]);
+    assert!(output.status.success(), "{output:?}");
+    let source = std::fs::read_to_string(out.join("types.go")).unwrap();
+    assert!(source.contains("func EncodeA("));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_generate_from_exporter() {
+    let (dir, ir) = write_ir("exporter");
+    let out = dir.join("out");
+
+    let output = run(&["lua", "--out", path(&out), "--", "cat", path(&ir)]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(out.join("bindings.lua").is_file());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_failing_exporter() {
+    let (dir, _) = write_ir("failing_exporter");
+
+    let output = run(&["lua", "--out", path(&dir.join("out")), "--", "false"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("exporter `false` failed"));
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct C(u8);
+
+#[test]
+fn test_watch_rewrites_on_change() {
+    let (dir, ir) = write_ir("watch");
+    let out = dir.join("out");
+    let source = out.join("bindings.lua");
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_postcard-bindgen"))
+        .args(["lua", "--watch", "--ir", path(&ir), "--out", path(&out)])
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let wait_for = |check: &dyn Fn(&str) -> bool| {
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_secs(20) {
+            if std::fs::read_to_string(&source).is_ok_and(|s| check(&s)) {
+                return true;
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+        false
+    };
+
+    let written = wait_for(&|s| !s.contains("encoders.C("));
+    std::fs::write(&ir, generate_bindings!(A, B, C).to_json()).unwrap();
+    let rewritten = written && wait_for(&|s| s.contains("encoders.C("));
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+    assert!(written && rewritten);
+}
+
+#[test]
+fn test_watch_requires_ir_file() {
+    let output = run(&["lua", "--watch", "--out", "out", "--", "cat", "schema.json"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_options_from_config_file() {
+    let (dir, ir) = write_ir("config");
+    let out = dir.join("out");
+    let config = dir.join("bindgen.toml");
+    std::fs::write(
+        &config,
+        "language = \"lua\"\nname = \"types\"\nrename = { A = \"Point\" }\n",
+    )
+    .unwrap();
+
+    let output = run(&[
+        "--config",
+        path(&config),
+        "--ir",
+        path(&ir),
+        "--out",
+        path(&out),
+    ]);
+    assert!(output.status.success(), "{output:?}");
+    let source = std::fs::read_to_string(out.join("types.lua")).unwrap();
+    assert!(source.contains("encoders.Point("));
+
+    // arguments take precedence
+    let output = run(&[
+        "go",
+        "--config",
+        path(&config),
+        "--ir",
+        path(&ir),
+        "--out",
+        path(&out),
+    ]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(out.join("types.go").is_file());
+}
+
+#[test]
+fn test_cargo_subcommand_runs_configured_exporter() {
+    let (workspace, ir) = write_ir("cargo");
+    let crate_dir = workspace.join("exporter");
+    std::fs::create_dir_all(crate_dir.join("src")).unwrap();
+    std::fs::write(
+        workspace.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"exporter\"]\nresolver = \"2\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        crate_dir.join("Cargo.toml"),
+        "[package]\nname = \"exporter\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        crate_dir.join("src").join("main.rs"),
+        format!("fn main() {{ print!(\"{{}}\", include_str!({ir:?})); }}"),
+    )
+    .unwrap();
+    std::fs::write(
+        workspace.join("postcard-bindgen.toml"),
+        "language = \"lua\"\nout-dir = \"generated\"\n\n[exporter]\npackage = \"exporter\"\n",
+    )
+    .unwrap();
+
+    // run in a member of the workspace, the configuration is read from the root
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-postcard-bindgen"))
+        .arg("postcard-bindgen")
+        .current_dir(&crate_dir)
+        .env("CARGO_TARGET_DIR", workspace.join("target"))
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    assert!(workspace.join("generated").join("bindings.lua").is_file());
+}
+
+#[test]
+fn test_requires_input() {
+    let output = run(&["lua", "--out", "out"]);
+    assert!(!output.status.success());
+}
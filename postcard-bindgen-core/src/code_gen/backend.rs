@@ -0,0 +1,114 @@
+use genco::lang::Lang;
+
+use crate::{
+    registry::{
+        BindingType, Container, ContainerCollection, EnumType, StructType, TupleStructType,
+    },
+    Exports,
+};
+
+/// A code generator for a target language.
+///
+/// [`generate_with`] prepares the containers, visits each of them in registration order with
+/// the method matching its kind and finally collects the generated files with
+/// [`LanguageBackend::finish`]. The backend keeps the generated code between the calls, so
+/// it can group it freely, e.g. put all encoders in one file and all decoders in another.
+///
+/// Backends for other languages can be implemented outside of this crate, the bundled
+/// backends like [`go`](super::go) implement this trait as well.
+///
+/// # Example
+/// ```
+/// # use postcard_bindgen_core::{
+/// #     code_gen::backend::{generate_with, LanguageBackend},
+/// #     lang::Lang,
+/// #     registry::{Container, ContainerCollection, EnumType, StructType, TupleStructType},
+/// #     ExportFile, Exports,
+/// # };
+/// /// Lists the names of the registered types.
+/// #[derive(Default)]
+/// struct TypeList(genco::Tokens<()>);
+///
+/// impl LanguageBackend for TypeList {
+///     type Lang = ();
+///
+///     fn visit_struct(&mut self, container: &Container, _: &StructType) {
+///         genco::quote_in!(self.0 => struct $(&container.name)$['\n']);
+///     }
+///
+///     fn visit_tuple_struct(&mut self, container: &Container, _: &TupleStructType) {
+///         genco::quote_in!(self.0 => tuple struct $(&container.name)$['\n']);
+///     }
+///
+///     fn visit_enum(&mut self, container: &Container, _: &EnumType) {
+///         genco::quote_in!(self.0 => enum $(&container.name)$['\n']);
+///     }
+///
+///     fn visit_unit_struct(&mut self, container: &Container) {
+///         genco::quote_in!(self.0 => unit struct $(&container.name)$['\n']);
+///     }
+///
+///     fn finish(self) -> Exports<()> {
+///         Exports {
+///             files: vec![ExportFile {
+///                 content_type: "source".to_owned(),
+///                 content: self.0,
+///             }],
+///         }
+///     }
+/// }
+///
+/// # fn list(containers: ContainerCollection) -> Exports<()> {
+/// generate_with(TypeList::default(), containers)
+/// # }
+/// ```
+pub trait LanguageBackend {
+    /// Language of the generated tokens.
+    type Lang: Lang;
+
+    /// Prepares the containers before they are visited.
+    ///
+    /// This is the place to flatten the module paths with [`ContainerCollection::flatten`],
+    /// to resolve `usize` with [`ContainerCollection::resolve_pointer_width`], to encode sets
+    /// as lists with [`ContainerCollection::sets_as_sequences`] or to reject types the language
+    /// can't express. Does nothing by default.
+    fn prepare(&mut self, containers: &mut ContainerCollection) {
+        let _ = containers;
+    }
+
+    /// Generates the code of a struct with named fields.
+    fn visit_struct(&mut self, container: &Container, struct_type: &StructType);
+
+    /// Generates the code of a tuple struct.
+    fn visit_tuple_struct(&mut self, container: &Container, tuple_struct_type: &TupleStructType);
+
+    /// Generates the code of an enum.
+    fn visit_enum(&mut self, container: &Container, enum_type: &EnumType);
+
+    /// Generates the code of a unit struct.
+    fn visit_unit_struct(&mut self, container: &Container);
+
+    /// Collects the generated code into the exported files.
+    fn finish(self) -> Exports<Self::Lang>;
+}
+
+/// Generates the bindings of the containers with the given backend.
+pub fn generate_with<B: LanguageBackend>(
+    mut backend: B,
+    mut containers: ContainerCollection,
+) -> Exports<B::Lang> {
+    backend.prepare(&mut containers);
+
+    for container in containers.all_containers() {
+        match &container.r#type {
+            BindingType::Struct(struct_type) => backend.visit_struct(&container, struct_type),
+            BindingType::TupleStruct(tuple_struct_type) => {
+                backend.visit_tuple_struct(&container, tuple_struct_type)
+            }
+            BindingType::Enum(enum_type) => backend.visit_enum(&container, enum_type),
+            BindingType::UnitStruct(_) => backend.visit_unit_struct(&container),
+        }
+    }
+
+    backend.finish()
+}
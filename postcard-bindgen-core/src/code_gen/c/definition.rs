@@ -0,0 +1,705 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use genco::{quote, quote_in};
+
+use crate::{
+    registry::{BindingType, Container, EnumVariantType, StructField},
+    type_info::{ArrayMeta, MapMeta, NumberMeta, ValueType},
+};
+
+use super::Tokens;
+
+const C_KEYWORDS: &[&str] = &[
+    "auto", "bool", "break", "case", "char", "const", "continue", "default", "do", "double",
+    "else", "enum", "extern", "false", "float", "for", "goto", "if", "inline", "int", "long",
+    "register", "restrict", "return", "short", "signed", "sizeof", "static", "struct", "switch",
+    "true", "typedef", "union", "unsigned", "void", "volatile", "while",
+];
+
+/// A C struct which is generated together with its encode and decode functions.
+pub enum Definition {
+    Container(Container),
+    /// Value types without a name in rust, like `Option<u32>` or `Vec<u8>`.
+    Value(ValueType),
+    /// Key and value pair of a map type.
+    MapEntry(MapMeta),
+}
+
+impl Definition {
+    pub fn name(&self) -> String {
+        match self {
+            Definition::Container(container) => container.name.to_string(),
+            Definition::Value(value_type) => c_type(value_type),
+            Definition::MapEntry(meta) => map_entry_name(meta),
+        }
+    }
+
+    /// Value types which are embedded by value and have to be defined before this one.
+    fn value_dependencies(&self) -> Vec<&ValueType> {
+        match self {
+            Definition::Container(container) => match &container.r#type {
+                BindingType::Struct(struct_type) => encoded_fields(&struct_type.fields)
+                    .map(|field| &field.v_type)
+                    .collect(),
+                BindingType::TupleStruct(tuple_struct) => tuple_struct.fields.iter().collect(),
+                BindingType::UnitStruct(_) => Vec::new(),
+                BindingType::Enum(enum_type) => enum_type
+                    .variants
+                    .iter()
+                    .flat_map(|variant| match &variant.inner_type {
+                        EnumVariantType::Empty => Vec::new(),
+                        EnumVariantType::Tuple(fields) => fields.iter().collect(),
+                        EnumVariantType::NewType(fields) => {
+                            encoded_fields(fields).map(|field| &field.v_type).collect()
+                        }
+                    })
+                    .collect(),
+            },
+            Definition::Value(value_type) => match value_type {
+                ValueType::Optional(meta) => vec![meta.inner.as_ref()],
+                ValueType::Range(meta) => vec![meta.bounds_type.as_ref()],
+                ValueType::Array(ArrayMeta {
+                    items_type,
+                    length: Some(_),
+                    ..
+                }) => vec![items_type.as_ref()],
+                ValueType::Tuple(meta) => meta.items_types.iter().collect(),
+                // vectors and maps only hold a pointer to their items
+                _ => Vec::new(),
+            },
+            Definition::MapEntry(meta) => vec![meta.key_type.as_ref(), meta.value_type.as_ref()],
+        }
+    }
+
+    pub fn gen_forward_decl(&self) -> Tokens {
+        let name = self.name();
+        quote!(typedef struct $(&name) $name;)
+    }
+
+    pub fn gen_type(&self) -> Tokens {
+        let name = self.name();
+        let mut members = Tokens::new();
+        let mut prefix = Tokens::new();
+
+        match self {
+            Definition::Container(container) => match &container.r#type {
+                BindingType::Struct(struct_type) => {
+                    gen_named_members(&mut members, &struct_type.fields)
+                }
+                BindingType::TupleStruct(tuple_struct) => {
+                    gen_tuple_members(&mut members, &tuple_struct.fields)
+                }
+                BindingType::UnitStruct(_) => (),
+                BindingType::Enum(enum_type) => {
+                    let tags = enum_type
+                        .variants
+                        .iter()
+                        .map(|variant| quote!($(&name)_$(&variant.name) = $(variant.index)));
+                    quote_in! { prefix =>
+                        typedef enum {
+                            $(for tag in tags join (,$['\r']) => $tag)
+                        } $(&name)_Tag;
+                        $['\n']
+                    };
+
+                    let mut variants = Tokens::new();
+                    for variant in enum_type.variants.iter() {
+                        let mut variant_members = Tokens::new();
+                        match &variant.inner_type {
+                            EnumVariantType::Empty => continue,
+                            EnumVariantType::Tuple(fields) if fields.is_empty() => continue,
+                            EnumVariantType::Tuple(fields) if fields.len() == 1 => {
+                                quote_in!(variants => $(c_type(&fields[0])) $(&variant.name););
+                                variants.push();
+                                continue;
+                            }
+                            EnumVariantType::Tuple(fields) => {
+                                gen_tuple_members(&mut variant_members, fields)
+                            }
+                            EnumVariantType::NewType(fields) => {
+                                if encoded_fields(fields).next().is_none() {
+                                    continue;
+                                }
+                                gen_named_members(&mut variant_members, fields)
+                            }
+                        }
+                        quote_in! { variants =>
+                            struct {
+                                $variant_members
+                            } $(&variant.name);
+                        };
+                        variants.push();
+                    }
+
+                    quote_in!(members => $(&name)_Tag tag;);
+                    members.push();
+                    if !variants.is_empty() {
+                        quote_in! { members =>
+                            union {
+                                $variants
+                            } value;
+                        };
+                        members.push();
+                    }
+                }
+            },
+            Definition::Value(value_type) => match value_type {
+                ValueType::Optional(meta) => {
+                    quote_in! { members =>
+                        bool is_some;
+                        $(c_type(&meta.inner)) value;
+                    }
+                }
+                ValueType::Range(meta) => {
+                    let bounds = c_type(&meta.bounds_type);
+                    quote_in! { members =>
+                        $(&bounds) start;
+                        $bounds end;
+                    }
+                }
+                ValueType::Array(ArrayMeta {
+                    items_type,
+                    length: Some(length),
+                    ..
+                }) => quote_in!(members => $(c_type(items_type)) items[$(*length)];),
+                ValueType::Array(meta) => {
+                    quote_in! { members =>
+                        const $(c_type(&meta.items_type)) *items;
+                        size_t len;
+                    }
+                }
+                ValueType::Map(meta) => {
+                    quote_in! { members =>
+                        const $(map_entry_name(meta)) *entries;
+                        size_t len;
+                    }
+                }
+                ValueType::Tuple(meta) => gen_tuple_members(&mut members, &meta.items_types),
+                _ => unreachable!("only compound value types are defined"),
+            },
+            Definition::MapEntry(meta) => {
+                quote_in! { members =>
+                    $(c_type(&meta.key_type)) key;
+                    $(c_type(&meta.value_type)) value;
+                }
+            }
+        }
+
+        if members.is_empty() {
+            // C doesn't allow empty structs
+            quote_in!(members => char _unused;);
+        }
+
+        quote! {
+            $prefix
+            struct $name {
+                $members
+            };
+        }
+    }
+
+    pub fn gen_encode_decl(&self) -> Tokens {
+        let name = self.name();
+        quote!(static bool pc_encode_$(&name)(pc_writer *w, const $name *v))
+    }
+
+    pub fn gen_decode_decl(&self) -> Tokens {
+        let name = self.name();
+        quote!(static bool pc_decode_$(&name)(pc_reader *r, $name *v))
+    }
+
+    pub fn gen_encode_function(&self) -> Tokens {
+        let mut body = Tokens::new();
+
+        match self {
+            Definition::Container(container) => match &container.r#type {
+                BindingType::Struct(struct_type) => {
+                    for field in encoded_fields(&struct_type.fields) {
+                        gen_encode(&mut body, &field.v_type, quote!(v->$(c_ident(&field.name))));
+                    }
+                }
+                BindingType::TupleStruct(tuple_struct) => {
+                    for (index, field) in tuple_struct.fields.iter().enumerate() {
+                        gen_encode(&mut body, field, quote!(v->_$index));
+                    }
+                }
+                BindingType::UnitStruct(_) => (),
+                BindingType::Enum(enum_type) => {
+                    let name = self.name();
+                    let mut cases = Tokens::new();
+                    for variant in enum_type.variants.iter() {
+                        let mut case_body = Tokens::new();
+                        gen_variant(
+                            &mut case_body,
+                            &variant.inner_type,
+                            &variant.name,
+                            gen_encode,
+                        );
+                        quote_in! { cases =>
+                            case $(&name)_$(&variant.name):
+                                $case_body
+                                break;
+                        };
+                        cases.push();
+                    }
+
+                    quote_in! { body =>
+                        uint32_t tag = (uint32_t)v->tag;
+                        PC_TRY(pc_encode_u32(w, &tag));
+                        switch (v->tag) {
+                        $cases
+                        default:
+                            return false;
+                        }
+                    };
+                    body.push();
+                }
+            },
+            Definition::Value(value_type) => match value_type {
+                ValueType::Optional(meta) => {
+                    let mut inner = Tokens::new();
+                    gen_encode(&mut inner, &meta.inner, quote!(v->value));
+                    quote_in! { body =>
+                        PC_TRY(pc_encode_bool(w, &v->is_some));
+                        if (v->is_some) {
+                            $inner
+                        }
+                    };
+                    body.push();
+                }
+                ValueType::Range(meta) => {
+                    gen_encode(&mut body, &meta.bounds_type, quote!(v->start));
+                    gen_encode(&mut body, &meta.bounds_type, quote!(v->end));
+                }
+                ValueType::Array(ArrayMeta {
+                    items_type,
+                    length: Some(length),
+                    ..
+                }) => {
+                    let mut item = Tokens::new();
+                    gen_encode(&mut item, items_type, quote!(v->items[i]));
+                    quote_in! { body =>
+                        for (size_t i = 0; i < $(*length); i++) {
+                            $item
+                        }
+                    };
+                    body.push();
+                }
+                ValueType::Array(meta) => {
+                    let mut item = Tokens::new();
+                    gen_encode(&mut item, &meta.items_type, quote!(v->items[i]));
+                    quote_in! { body =>
+                        PC_TRY(pc_write_len(w, v->len));
+                        for (size_t i = 0; i < v->len; i++) {
+                            $item
+                        }
+                    };
+                    body.push();
+                }
+                ValueType::Map(meta) => {
+                    quote_in! { body =>
+                        PC_TRY(pc_write_len(w, v->len));
+                        for (size_t i = 0; i < v->len; i++) {
+                            PC_TRY(pc_encode_$(map_entry_name(meta))(w, &v->entries[i]));
+                        }
+                    };
+                    body.push();
+                }
+                ValueType::Tuple(meta) => {
+                    for (index, item) in meta.items_types.iter().enumerate() {
+                        gen_encode(&mut body, item, quote!(v->_$index));
+                    }
+                }
+                _ => unreachable!("only compound value types are defined"),
+            },
+            Definition::MapEntry(meta) => {
+                gen_encode(&mut body, &meta.key_type, quote!(v->key));
+                gen_encode(&mut body, &meta.value_type, quote!(v->value));
+            }
+        }
+
+        if body.is_empty() {
+            quote_in! { body =>
+                (void)w;
+                (void)v;
+            };
+            body.push();
+        }
+
+        quote! {
+            $(self.gen_encode_decl()) {
+                $body
+                return true;
+            }
+        }
+    }
+
+    pub fn gen_decode_function(&self) -> Tokens {
+        let mut body = Tokens::new();
+
+        match self {
+            Definition::Container(container) => match &container.r#type {
+                BindingType::Struct(struct_type) => {
+                    for field in encoded_fields(&struct_type.fields) {
+                        gen_decode(&mut body, &field.v_type, quote!(v->$(c_ident(&field.name))));
+                    }
+                }
+                BindingType::TupleStruct(tuple_struct) => {
+                    for (index, field) in tuple_struct.fields.iter().enumerate() {
+                        gen_decode(&mut body, field, quote!(v->_$index));
+                    }
+                }
+                BindingType::UnitStruct(_) => (),
+                BindingType::Enum(enum_type) => {
+                    let name = self.name();
+                    let mut cases = Tokens::new();
+                    for variant in enum_type.variants.iter() {
+                        let mut case_body = Tokens::new();
+                        gen_variant(
+                            &mut case_body,
+                            &variant.inner_type,
+                            &variant.name,
+                            gen_decode,
+                        );
+                        quote_in! { cases =>
+                            case $(&name)_$(&variant.name):
+                                $case_body
+                                break;
+                        };
+                        cases.push();
+                    }
+
+                    quote_in! { body =>
+                        uint32_t tag;
+                        PC_TRY(pc_decode_u32(r, &tag));
+                        switch (tag) {
+                        $cases
+                        default:
+                            return false;
+                        }
+                        v->tag = ($(&name)_Tag)tag;
+                    };
+                    body.push();
+                }
+            },
+            Definition::Value(value_type) => match value_type {
+                ValueType::Optional(meta) => {
+                    let mut inner = Tokens::new();
+                    gen_decode(&mut inner, &meta.inner, quote!(v->value));
+                    quote_in! { body =>
+                        PC_TRY(pc_decode_bool(r, &v->is_some));
+                        if (v->is_some) {
+                            $inner
+                        }
+                    };
+                    body.push();
+                }
+                ValueType::Range(meta) => {
+                    gen_decode(&mut body, &meta.bounds_type, quote!(v->start));
+                    gen_decode(&mut body, &meta.bounds_type, quote!(v->end));
+                }
+                ValueType::Array(ArrayMeta {
+                    items_type,
+                    length: Some(length),
+                    ..
+                }) => {
+                    let mut item = Tokens::new();
+                    gen_decode(&mut item, items_type, quote!(v->items[i]));
+                    quote_in! { body =>
+                        for (size_t i = 0; i < $(*length); i++) {
+                            $item
+                        }
+                    };
+                    body.push();
+                }
+                ValueType::Array(meta) if is_byte(&meta.items_type) => {
+                    // bytes are not copied, the items point into the input buffer
+                    quote_in! { body =>
+                        PC_TRY(pc_read_len(r, &v->len));
+                        PC_TRY(pc_read(r, &v->items, v->len));
+                    };
+                    body.push();
+                }
+                ValueType::Array(meta) => {
+                    let items_type = c_type(&meta.items_type);
+                    let mut item = Tokens::new();
+                    gen_decode(&mut item, &meta.items_type, quote!(items[i]));
+                    quote_in! { body =>
+                        size_t len;
+                        void *scratch;
+                        PC_TRY(pc_read_len(r, &len));
+                        PC_TRY(pc_alloc(r, len, sizeof($(&items_type)), PC_ALIGNOF($(&items_type)), &scratch));
+                        $(&items_type) *items = scratch;
+                        for (size_t i = 0; i < len; i++) {
+                            $item
+                        }
+                        v->items = items;
+                        v->len = len;
+                    };
+                    body.push();
+                }
+                ValueType::Map(meta) => {
+                    let entry_type = map_entry_name(meta);
+                    quote_in! { body =>
+                        size_t len;
+                        void *scratch;
+                        PC_TRY(pc_read_len(r, &len));
+                        PC_TRY(pc_alloc(r, len, sizeof($(&entry_type)), PC_ALIGNOF($(&entry_type)), &scratch));
+                        $(&entry_type) *entries = scratch;
+                        for (size_t i = 0; i < len; i++) {
+                            PC_TRY(pc_decode_$(&entry_type)(r, &entries[i]));
+                        }
+                        v->entries = entries;
+                        v->len = len;
+                    };
+                    body.push();
+                }
+                ValueType::Tuple(meta) => {
+                    for (index, item) in meta.items_types.iter().enumerate() {
+                        gen_decode(&mut body, item, quote!(v->_$index));
+                    }
+                }
+                _ => unreachable!("only compound value types are defined"),
+            },
+            Definition::MapEntry(meta) => {
+                gen_decode(&mut body, &meta.key_type, quote!(v->key));
+                gen_decode(&mut body, &meta.value_type, quote!(v->value));
+            }
+        }
+
+        if body.is_empty() {
+            quote_in! { body =>
+                (void)r;
+                (void)v;
+            };
+            body.push();
+        }
+
+        quote! {
+            $(self.gen_decode_decl()) {
+                $body
+                return true;
+            }
+        }
+    }
+}
+
+/// Collects the definitions of all containers and of the value types used by them, ordered
+/// so that each struct is defined before the structs embedding it by value.
+///
+/// Panics if a container embeds itself by value, e.g. `struct A { a: Option<Box<A>> }`,
+/// which has no finite size in C.
+pub fn collect_definitions(containers: impl Iterator<Item = Container>) -> Vec<Definition> {
+    let mut definitions = Vec::new();
+    let mut names = BTreeSet::new();
+
+    for container in containers {
+        assert!(
+            names.insert(container.name.to_string()),
+            "the C bindings hold all types in one namespace, but `{}` is defined twice",
+            container.name
+        );
+        definitions.push(Definition::Container(container));
+    }
+
+    let mut value_types = Vec::new();
+    for definition in definitions.iter() {
+        for value_type in definition.value_dependencies() {
+            collect_value_types(value_type, &mut value_types, &mut names);
+        }
+    }
+    definitions.extend(value_types);
+
+    sort_definitions(definitions)
+}
+
+fn collect_value_types(
+    value_type: &ValueType,
+    definitions: &mut Vec<Definition>,
+    names: &mut BTreeSet<String>,
+) {
+    match value_type {
+        ValueType::Optional(meta) => collect_value_types(&meta.inner, definitions, names),
+        ValueType::Range(meta) => collect_value_types(&meta.bounds_type, definitions, names),
+        ValueType::Array(meta) => collect_value_types(&meta.items_type, definitions, names),
+        ValueType::Set(_) => unreachable!("sets are replaced by sequences"),
+        ValueType::Unit(_) => panic!("`()` is only supported as field by the C bindings"),
+        ValueType::Map(meta) => {
+            collect_value_types(&meta.key_type, definitions, names);
+            collect_value_types(&meta.value_type, definitions, names);
+            if names.insert(map_entry_name(meta)) {
+                definitions.push(Definition::MapEntry(meta.clone()));
+            }
+        }
+        ValueType::Tuple(meta) => {
+            for item in meta.items_types.iter() {
+                collect_value_types(item, definitions, names);
+            }
+        }
+        ValueType::Number(_) | ValueType::String(_) | ValueType::Bool(_) | ValueType::Object(_) => {
+            return
+        }
+    }
+
+    if names.insert(c_type(value_type)) {
+        definitions.push(Definition::Value(value_type.clone()));
+    }
+}
+
+fn sort_definitions(definitions: Vec<Definition>) -> Vec<Definition> {
+    let indices = definitions
+        .iter()
+        .enumerate()
+        .map(|(index, definition)| (definition.name(), index))
+        .collect::<BTreeMap<_, _>>();
+
+    fn visit(
+        index: usize,
+        definitions: &[Definition],
+        indices: &BTreeMap<String, usize>,
+        visiting: &mut Vec<usize>,
+        order: &mut Vec<usize>,
+    ) {
+        if order.contains(&index) {
+            return;
+        }
+        if visiting.contains(&index) {
+            panic!(
+                "`{}` contains itself by value, which can't be represented in C. Only recursion through a `Vec` or a map is supported",
+                definitions[index].name()
+            );
+        }
+
+        visiting.push(index);
+        for dependency in definitions[index].value_dependencies() {
+            if let Some(dependency) = indices.get(&c_type(dependency)) {
+                visit(*dependency, definitions, indices, visiting, order);
+            }
+        }
+        visiting.pop();
+
+        order.push(index);
+    }
+
+    let mut order = Vec::new();
+    for index in 0..definitions.len() {
+        visit(index, &definitions, &indices, &mut Vec::new(), &mut order);
+    }
+
+    let mut definitions = definitions.into_iter().map(Some).collect::<Vec<_>>();
+    order
+        .into_iter()
+        .map(|index| definitions[index].take().unwrap())
+        .collect()
+}
+
+fn encoded_fields(fields: &[StructField]) -> impl Iterator<Item = &StructField> {
+    fields.iter().filter(|field| !field.skip)
+}
+
+fn gen_named_members(tokens: &mut Tokens, fields: &[StructField]) {
+    for field in encoded_fields(fields) {
+        quote_in!(*tokens => $(c_type(&field.v_type)) $(c_ident(&field.name)););
+        tokens.push();
+    }
+}
+
+fn gen_tuple_members(tokens: &mut Tokens, fields: &[ValueType]) {
+    for (index, field) in fields.iter().enumerate() {
+        quote_in!(*tokens => $(c_type(field)) _$index;);
+        tokens.push();
+    }
+}
+
+fn gen_variant(
+    tokens: &mut Tokens,
+    inner_type: &EnumVariantType,
+    variant_name: &str,
+    gen: fn(&mut Tokens, &ValueType, Tokens),
+) {
+    match inner_type {
+        EnumVariantType::Empty => (),
+        EnumVariantType::Tuple(fields) if fields.len() == 1 => {
+            gen(tokens, &fields[0], quote!(v->value.$variant_name))
+        }
+        EnumVariantType::Tuple(fields) => {
+            for (index, field) in fields.iter().enumerate() {
+                gen(tokens, field, quote!(v->value.$variant_name._$index))
+            }
+        }
+        EnumVariantType::NewType(fields) => {
+            for field in encoded_fields(fields) {
+                gen(
+                    tokens,
+                    &field.v_type,
+                    quote!(v->value.$variant_name.$(c_ident(&field.name))),
+                )
+            }
+        }
+    }
+}
+
+fn gen_encode(tokens: &mut Tokens, value_type: &ValueType, lvalue: Tokens) {
+    quote_in!(*tokens => PC_TRY(pc_encode_$(fn_suffix(value_type))(w, &$lvalue)););
+    tokens.push();
+}
+
+fn gen_decode(tokens: &mut Tokens, value_type: &ValueType, lvalue: Tokens) {
+    quote_in!(*tokens => PC_TRY(pc_decode_$(fn_suffix(value_type))(r, &$lvalue)););
+    tokens.push();
+}
+
+fn is_byte(value_type: &ValueType) -> bool {
+    matches!(
+        value_type,
+        ValueType::Number(NumberMeta::Integer {
+            bytes: 1,
+            signed: false,
+            ..
+        })
+    )
+}
+
+/// Name of the C type of a value.
+pub fn c_type(value_type: &ValueType) -> String {
+    match value_type {
+        ValueType::Number(NumberMeta::Integer { bytes, signed, .. }) => {
+            assert_ne!(
+                *bytes, 16,
+                "128 bit integers are not supported by the C bindings"
+            );
+            format!("{}int{}_t", if *signed { "" } else { "u" }, bytes * 8)
+        }
+        ValueType::Number(NumberMeta::FloatingPoint { bytes: 4 }) => "float".to_owned(),
+        ValueType::Number(NumberMeta::FloatingPoint { .. }) => "double".to_owned(),
+        ValueType::Bool(_) => "bool".to_owned(),
+        ValueType::String(_) => "pc_str".to_owned(),
+        ValueType::Object(meta) => meta.name.to_string(),
+        _ => value_type.name_part(),
+    }
+}
+
+/// Suffix of the `pc_encode_*` and `pc_decode_*` functions of a value.
+fn fn_suffix(value_type: &ValueType) -> String {
+    match value_type {
+        ValueType::Number(NumberMeta::Integer { bytes, signed, .. }) => {
+            format!("{}{}", if *signed { "i" } else { "u" }, bytes * 8)
+        }
+        ValueType::Number(NumberMeta::FloatingPoint { bytes }) => format!("f{}", bytes * 8),
+        ValueType::Bool(_) => "bool".to_owned(),
+        ValueType::String(_) => "str".to_owned(),
+        _ => c_type(value_type),
+    }
+}
+
+fn map_entry_name(meta: &MapMeta) -> String {
+    format!("{}_Entry", c_type(&ValueType::Map(meta.clone())))
+}
+
+fn c_ident(name: &str) -> String {
+    if C_KEYWORDS.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_owned()
+    }
+}
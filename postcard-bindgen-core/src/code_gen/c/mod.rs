@@ -0,0 +1,191 @@
+mod definition;
+mod runtime;
+
+use core::borrow::Borrow;
+
+use convert_case::{Case, Casing};
+use genco::{lang::c::C, quote, tokens::quoted};
+
+use definition::{collect_definitions, Definition};
+use runtime::{gen_des_runtime, gen_header_runtime, gen_runtime, gen_ser_runtime};
+
+use crate::{registry::ContainerCollection, Exports};
+
+pub use super::settings::{GenerationSettings, PointerWidth};
+
+type Tokens = genco::lang::c::Tokens;
+type ExportFile = crate::ExportFile<C>;
+
+/// Generates a C header and source file, exported as `header` and `source`.
+///
+/// The header defines a struct per type and declares the functions
+///
+/// ```c
+/// bool Type_encode(const Type *value, uint8_t *buf, size_t len, size_t *written);
+/// bool Type_decode(Type *value, const uint8_t *buf, size_t len, uint8_t *scratch, size_t scratch_len, size_t *consumed);
+/// ```
+///
+/// which return `false` if the buffer is too small or the input is invalid. Nothing is
+/// allocated: strings and byte vectors point into the decoded input, all other vectors and
+/// maps are placed into the caller provided scratch buffer. Vectors and maps are a pointer
+/// to the items together with the length, options hold an `is_some` flag and enums a `tag`
+/// with the variant data in the `value` union.
+///
+/// All types share one namespace, as C has no modules. Generating panics for 128 bit
+/// integers, which C doesn't support, and for types containing themselves by value.
+pub fn generate(
+    containers: ContainerCollection,
+    gen_settings: impl Borrow<GenerationSettings>,
+    module_name: String,
+) -> Exports<C> {
+    let module_name = module_name.to_case(Case::Snake);
+    let gen_settings = gen_settings.borrow();
+
+    let mut containers = containers;
+    containers.assert_no_internally_tagged_enums("C");
+    containers.flatten();
+    containers.resolve_pointer_width(gen_settings.pointer_width.bytes());
+    containers.sets_as_sequences();
+    containers.remove_unit_fields();
+
+    let definitions = collect_definitions(containers.all_containers());
+    let containers = definitions
+        .iter()
+        .filter(|definition| matches!(definition, Definition::Container(_)))
+        .collect::<Vec<_>>();
+
+    let guard = format!("{}_H", module_name.to_case(Case::UpperSnake));
+
+    let mut header = quote! {
+        #ifndef $(&guard)
+        #define $(&guard)
+
+        $(gen_header_runtime())
+
+        #ifdef __cplusplus
+        $(r#"extern "C" {"#)
+        #endif
+
+        $(for definition in definitions.iter() => $(definition.gen_forward_decl())$['\r'])
+
+        $(for definition in definitions.iter() join ($['\n']) => $(definition.gen_type()))
+    };
+
+    if gen_settings.ser {
+        header.line();
+        for container in containers.iter() {
+            header.append(gen_encode_decl(container));
+            header.push();
+        }
+    }
+
+    if gen_settings.des {
+        header.line();
+        for container in containers.iter() {
+            header.append(gen_decode_decl(container));
+            header.push();
+        }
+    }
+
+    header.line();
+    header.append(quote! {
+        #ifdef __cplusplus
+        $("}")
+        #endif
+
+        #endif
+    });
+
+    let mut source = quote! {
+        #include $(quoted(format!("{module_name}.h")))
+
+        $(gen_runtime())
+    };
+
+    if gen_settings.ser {
+        source.line();
+        source.append(gen_ser_runtime());
+        source.line();
+        for definition in definitions.iter() {
+            source.append(quote!($(definition.gen_encode_decl());));
+            source.push();
+        }
+        for definition in definitions.iter() {
+            source.line();
+            source.append(definition.gen_encode_function());
+        }
+        for container in containers.iter() {
+            source.line();
+            source.append(gen_encode_function(container));
+        }
+    }
+
+    if gen_settings.des {
+        source.line();
+        source.append(gen_des_runtime());
+        source.line();
+        for definition in definitions.iter() {
+            source.append(quote!($(definition.gen_decode_decl());));
+            source.push();
+        }
+        for definition in definitions.iter() {
+            source.line();
+            source.append(definition.gen_decode_function());
+        }
+        for container in containers.iter() {
+            source.line();
+            source.append(gen_decode_function(container));
+        }
+    }
+
+    Exports {
+        files: vec![
+            ExportFile {
+                content_type: "header".to_owned(),
+                content: header,
+            },
+            ExportFile {
+                content_type: "source".to_owned(),
+                content: source,
+            },
+        ],
+    }
+}
+
+fn gen_encode_decl(container: &Definition) -> Tokens {
+    let name = container.name();
+    quote!(bool $(&name)_encode(const $name *value, uint8_t *buf, size_t len, size_t *written);)
+}
+
+fn gen_decode_decl(container: &Definition) -> Tokens {
+    let name = container.name();
+    quote!(bool $(&name)_decode($name *value, const uint8_t *buf, size_t len, uint8_t *scratch, size_t scratch_len, size_t *consumed);)
+}
+
+fn gen_encode_function(container: &Definition) -> Tokens {
+    let name = container.name();
+    quote! {
+        bool $(&name)_encode(const $(&name) *value, uint8_t *buf, size_t len, size_t *written) {
+            pc_writer w = { buf, len, 0 };
+            PC_TRY(pc_encode_$(&name)(&w, value));
+            if (written != NULL) {
+                *written = w.pos;
+            }
+            return true;
+        }
+    }
+}
+
+fn gen_decode_function(container: &Definition) -> Tokens {
+    let name = container.name();
+    quote! {
+        bool $(&name)_decode($(&name) *value, const uint8_t *buf, size_t len, uint8_t *scratch, size_t scratch_len, size_t *consumed) {
+            pc_reader r = { buf, len, 0, scratch, scratch_len, 0 };
+            PC_TRY(pc_decode_$(&name)(&r, value));
+            if (consumed != NULL) {
+                *consumed = r.pos;
+            }
+            return true;
+        }
+    }
+}
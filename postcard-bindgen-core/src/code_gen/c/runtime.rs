@@ -0,0 +1,359 @@
+use genco::quote;
+
+use super::Tokens;
+
+/// Types of the runtime which are part of the public interface.
+pub fn gen_header_runtime() -> Tokens {
+    quote!($(HEADER_RUNTIME))
+}
+
+/// Shared helpers of the encode and decode functions.
+pub fn gen_runtime() -> Tokens {
+    quote!($(RUNTIME))
+}
+
+pub fn gen_ser_runtime() -> Tokens {
+    quote!($(SER_RUNTIME))
+}
+
+pub fn gen_des_runtime() -> Tokens {
+    quote!($(DES_RUNTIME))
+}
+
+const HEADER_RUNTIME: &str = "\
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+/* utf-8 string, not null terminated */
+typedef struct {
+    const char *ptr;
+    size_t len;
+} pc_str;";
+
+const RUNTIME: &str = "\
+#include <string.h>
+
+#define PC_TRY(expr) do { if (!(expr)) { return false; } } while (0)
+#define PC_ALIGNOF(type) offsetof(struct { char c; type t; }, t)";
+
+const SER_RUNTIME: &str = "\
+typedef struct {
+    uint8_t *buf;
+    size_t len;
+    size_t pos;
+} pc_writer;
+
+static inline bool pc_write(pc_writer *w, const uint8_t *bytes, size_t n) {
+    if (w->len - w->pos < n) {
+        return false;
+    }
+    if (n > 0) {
+        memcpy(w->buf + w->pos, bytes, n);
+    }
+    w->pos += n;
+    return true;
+}
+
+static inline bool pc_write_varint(pc_writer *w, uint64_t v) {
+    uint8_t bytes[10];
+    size_t n = 0;
+    do {
+        bytes[n] = (uint8_t)(v & 0x7F);
+        v >>= 7;
+        if (v != 0) {
+            bytes[n] |= 0x80;
+        }
+        n++;
+    } while (v != 0);
+    return pc_write(w, bytes, n);
+}
+
+static inline bool pc_write_zigzag(pc_writer *w, int64_t v) {
+    return pc_write_varint(w, ((uint64_t)v << 1) ^ (v < 0 ? UINT64_MAX : 0));
+}
+
+static inline bool pc_write_len(pc_writer *w, size_t len) {
+    return pc_write_varint(w, (uint64_t)len);
+}
+
+static inline bool pc_encode_bool(pc_writer *w, const bool *v) {
+    const uint8_t byte = *v ? 1 : 0;
+    return pc_write(w, &byte, 1);
+}
+
+static inline bool pc_encode_u8(pc_writer *w, const uint8_t *v) {
+    return pc_write(w, v, 1);
+}
+
+static inline bool pc_encode_i8(pc_writer *w, const int8_t *v) {
+    const uint8_t byte = (uint8_t)*v;
+    return pc_write(w, &byte, 1);
+}
+
+static inline bool pc_encode_u16(pc_writer *w, const uint16_t *v) {
+    return pc_write_varint(w, *v);
+}
+
+static inline bool pc_encode_u32(pc_writer *w, const uint32_t *v) {
+    return pc_write_varint(w, *v);
+}
+
+static inline bool pc_encode_u64(pc_writer *w, const uint64_t *v) {
+    return pc_write_varint(w, *v);
+}
+
+static inline bool pc_encode_i16(pc_writer *w, const int16_t *v) {
+    return pc_write_zigzag(w, *v);
+}
+
+static inline bool pc_encode_i32(pc_writer *w, const int32_t *v) {
+    return pc_write_zigzag(w, *v);
+}
+
+static inline bool pc_encode_i64(pc_writer *w, const int64_t *v) {
+    return pc_write_zigzag(w, *v);
+}
+
+static inline bool pc_encode_f32(pc_writer *w, const float *v) {
+    uint32_t bits;
+    uint8_t bytes[4];
+    memcpy(&bits, v, sizeof(bits));
+    for (size_t i = 0; i < sizeof(bytes); i++) {
+        bytes[i] = (uint8_t)(bits >> (8 * i));
+    }
+    return pc_write(w, bytes, sizeof(bytes));
+}
+
+static inline bool pc_encode_f64(pc_writer *w, const double *v) {
+    uint64_t bits;
+    uint8_t bytes[8];
+    memcpy(&bits, v, sizeof(bits));
+    for (size_t i = 0; i < sizeof(bytes); i++) {
+        bytes[i] = (uint8_t)(bits >> (8 * i));
+    }
+    return pc_write(w, bytes, sizeof(bytes));
+}
+
+static inline bool pc_encode_str(pc_writer *w, const pc_str *v) {
+    PC_TRY(pc_write_len(w, v->len));
+    return pc_write(w, (const uint8_t *)v->ptr, v->len);
+}";
+
+const DES_RUNTIME: &str = "\
+typedef struct {
+    const uint8_t *buf;
+    size_t len;
+    size_t pos;
+    uint8_t *scratch;
+    size_t scratch_len;
+    size_t scratch_pos;
+} pc_reader;
+
+static inline bool pc_read(pc_reader *r, const uint8_t **bytes, size_t n) {
+    if (r->len - r->pos < n) {
+        return false;
+    }
+    *bytes = r->buf + r->pos;
+    r->pos += n;
+    return true;
+}
+
+/* rejects varints longer than `max_bytes` and values exceeding 64 bits */
+static inline bool pc_read_varint(pc_reader *r, uint64_t *v, size_t max_bytes) {
+    uint64_t out = 0;
+    for (size_t i = 0; i < max_bytes; i++) {
+        const uint8_t *byte;
+        PC_TRY(pc_read(r, &byte, 1));
+        if (i == 9 && *byte > 1) {
+            return false;
+        }
+        out |= (uint64_t)(*byte & 0x7F) << (7 * i);
+        if ((*byte & 0x80) == 0) {
+            *v = out;
+            return true;
+        }
+    }
+    return false;
+}
+
+static inline bool pc_read_zigzag(pc_reader *r, int64_t *v, size_t max_bytes) {
+    uint64_t raw;
+    PC_TRY(pc_read_varint(r, &raw, max_bytes));
+    *v = (int64_t)(raw >> 1) ^ -(int64_t)(raw & 1);
+    return true;
+}
+
+static inline bool pc_read_len(pc_reader *r, size_t *len) {
+    uint64_t raw;
+    PC_TRY(pc_read_varint(r, &raw, 10));
+#if SIZE_MAX < UINT64_MAX
+    if (raw > SIZE_MAX) {
+        return false;
+    }
+#endif
+    *len = (size_t)raw;
+    return true;
+}
+
+/* takes `count` items of `size` bytes from the scratch buffer */
+static inline bool pc_alloc(pc_reader *r, size_t count, size_t size, size_t align, void **out) {
+    if (count == 0) {
+        *out = NULL;
+        return true;
+    }
+    const size_t pad = (align - ((uintptr_t)r->scratch + r->scratch_pos) % align) % align;
+    if (r->scratch_len - r->scratch_pos < pad) {
+        return false;
+    }
+    const size_t start = r->scratch_pos + pad;
+    if (count > (r->scratch_len - start) / size) {
+        return false;
+    }
+    *out = r->scratch + start;
+    r->scratch_pos = start + count * size;
+    return true;
+}
+
+static inline bool pc_is_utf8(const uint8_t *s, size_t len) {
+    size_t i = 0;
+    while (i < len) {
+        const uint8_t c = s[i];
+        size_t n;
+        uint32_t min, cp;
+        if (c < 0x80) {
+            i++;
+            continue;
+        } else if ((c & 0xE0) == 0xC0) {
+            n = 1; min = 0x80; cp = c & 0x1F;
+        } else if ((c & 0xF0) == 0xE0) {
+            n = 2; min = 0x800; cp = c & 0x0F;
+        } else if ((c & 0xF8) == 0xF0) {
+            n = 3; min = 0x10000; cp = c & 0x07;
+        } else {
+            return false;
+        }
+        if (len - i - 1 < n) {
+            return false;
+        }
+        for (size_t j = 1; j <= n; j++) {
+            if ((s[i + j] & 0xC0) != 0x80) {
+                return false;
+            }
+            cp = (cp << 6) | (s[i + j] & 0x3F);
+        }
+        if (cp < min || cp > 0x10FFFF || (cp >= 0xD800 && cp <= 0xDFFF)) {
+            return false;
+        }
+        i += n + 1;
+    }
+    return true;
+}
+
+static inline bool pc_decode_bool(pc_reader *r, bool *v) {
+    const uint8_t *byte;
+    PC_TRY(pc_read(r, &byte, 1));
+    if (*byte > 1) {
+        return false;
+    }
+    *v = *byte == 1;
+    return true;
+}
+
+static inline bool pc_decode_u8(pc_reader *r, uint8_t *v) {
+    const uint8_t *byte;
+    PC_TRY(pc_read(r, &byte, 1));
+    *v = *byte;
+    return true;
+}
+
+static inline bool pc_decode_i8(pc_reader *r, int8_t *v) {
+    const uint8_t *byte;
+    PC_TRY(pc_read(r, &byte, 1));
+    *v = (int8_t)*byte;
+    return true;
+}
+
+static inline bool pc_decode_u16(pc_reader *r, uint16_t *v) {
+    uint64_t raw;
+    PC_TRY(pc_read_varint(r, &raw, 3));
+    if (raw > UINT16_MAX) {
+        return false;
+    }
+    *v = (uint16_t)raw;
+    return true;
+}
+
+static inline bool pc_decode_u32(pc_reader *r, uint32_t *v) {
+    uint64_t raw;
+    PC_TRY(pc_read_varint(r, &raw, 5));
+    if (raw > UINT32_MAX) {
+        return false;
+    }
+    *v = (uint32_t)raw;
+    return true;
+}
+
+static inline bool pc_decode_u64(pc_reader *r, uint64_t *v) {
+    return pc_read_varint(r, v, 10);
+}
+
+static inline bool pc_decode_i16(pc_reader *r, int16_t *v) {
+    int64_t raw;
+    PC_TRY(pc_read_zigzag(r, &raw, 3));
+    if (raw < INT16_MIN || raw > INT16_MAX) {
+        return false;
+    }
+    *v = (int16_t)raw;
+    return true;
+}
+
+static inline bool pc_decode_i32(pc_reader *r, int32_t *v) {
+    int64_t raw;
+    PC_TRY(pc_read_zigzag(r, &raw, 5));
+    if (raw < INT32_MIN || raw > INT32_MAX) {
+        return false;
+    }
+    *v = (int32_t)raw;
+    return true;
+}
+
+static inline bool pc_decode_i64(pc_reader *r, int64_t *v) {
+    return pc_read_zigzag(r, v, 10);
+}
+
+static inline bool pc_decode_f32(pc_reader *r, float *v) {
+    const uint8_t *bytes;
+    uint32_t bits = 0;
+    PC_TRY(pc_read(r, &bytes, sizeof(bits)));
+    for (size_t i = 0; i < sizeof(bits); i++) {
+        bits |= (uint32_t)bytes[i] << (8 * i);
+    }
+    memcpy(v, &bits, sizeof(bits));
+    return true;
+}
+
+static inline bool pc_decode_f64(pc_reader *r, double *v) {
+    const uint8_t *bytes;
+    uint64_t bits = 0;
+    PC_TRY(pc_read(r, &bytes, sizeof(bits)));
+    for (size_t i = 0; i < sizeof(bits); i++) {
+        bits |= (uint64_t)bytes[i] << (8 * i);
+    }
+    memcpy(v, &bits, sizeof(bits));
+    return true;
+}
+
+/* the string points into the input buffer */
+static inline bool pc_decode_str(pc_reader *r, pc_str *v) {
+    size_t len;
+    const uint8_t *bytes;
+    PC_TRY(pc_read_len(r, &len));
+    PC_TRY(pc_read(r, &bytes, len));
+    if (!pc_is_utf8(bytes, len)) {
+        return false;
+    }
+    v->ptr = (const char *)bytes;
+    v->len = len;
+    return true;
+}";
@@ -0,0 +1,527 @@
+mod runtime;
+
+use core::borrow::Borrow;
+use std::collections::{BTreeMap, BTreeSet};
+
+use convert_case::{Case, Casing};
+use genco::{lang::c::C, quote, quote_in};
+
+use runtime::gen_runtime;
+
+use crate::{
+    registry::{BindingType, Container, ContainerCollection, EnumVariantType, StructField},
+    type_info::{ArrayMeta, NumberMeta, ValueType},
+    Exports,
+};
+
+pub use super::settings::{GenerationSettings, PointerWidth};
+
+type Tokens = genco::lang::c::Tokens;
+type ExportFile = crate::ExportFile<C>;
+
+const CPP_KEYWORDS: &[&str] = &[
+    "alignas",
+    "alignof",
+    "and",
+    "asm",
+    "auto",
+    "bool",
+    "break",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "constexpr",
+    "continue",
+    "decltype",
+    "default",
+    "delete",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "explicit",
+    "export",
+    "extern",
+    "false",
+    "float",
+    "for",
+    "friend",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "mutable",
+    "namespace",
+    "new",
+    "noexcept",
+    "not",
+    "nullptr",
+    "operator",
+    "or",
+    "private",
+    "protected",
+    "public",
+    "register",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "struct",
+    "switch",
+    "template",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typedef",
+    "typeid",
+    "typename",
+    "union",
+    "unsigned",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "while",
+    "xor",
+];
+
+/// Generates a header only C++17 library, exported as `header`.
+///
+/// Structs become plain structs, options `std::optional` and enums a struct with a
+/// nested struct per variant, held in the `std::variant` member `value`. The types are
+/// placed in a namespace named after the module, together with the overloads
+///
+/// ```cpp
+/// void encode(postcard::Writer& w, const Type& v);
+/// bool decode(postcard::Reader& r, Type& v);
+/// ```
+///
+/// which are wrapped by `postcard::to_bytes(value)` and `postcard::from_bytes<Type>(bytes)`.
+/// Decoding returns `false` or an empty optional if the input is invalid.
+///
+/// All types share the namespace. Generating panics for 128 bit integers, which have no
+/// standard C++ type, and for types containing themselves by value.
+pub fn generate(
+    containers: ContainerCollection,
+    gen_settings: impl Borrow<GenerationSettings>,
+    module_name: String,
+) -> Exports<C> {
+    let module_name = module_name.to_case(Case::Snake);
+    let gen_settings = gen_settings.borrow();
+
+    let mut containers = containers;
+    containers.assert_no_internally_tagged_enums("C++");
+    containers.flatten();
+    containers.resolve_pointer_width(gen_settings.pointer_width.bytes());
+    containers.sets_as_sequences();
+    containers.remove_unit_fields();
+
+    let containers = sort_containers(containers.all_containers().collect());
+
+    let guard = format!("{}_HPP", module_name.to_case(Case::UpperSnake));
+
+    let mut header = quote! {
+        #ifndef $(&guard)
+        #define $(&guard)
+
+        $(gen_runtime())
+
+        $(format!("namespace {module_name} {{"))
+
+        using postcard::decode;
+        using postcard::encode;
+
+        $(for container in containers.iter() => struct $(&container.name);$['\r'])
+
+        $(for container in containers.iter() join ($['\n']) => $(gen_type(container, &module_name)))
+    };
+
+    if gen_settings.ser {
+        header.line();
+        for container in containers.iter() {
+            for name in function_types(container) {
+                header.append(quote!(inline void encode(postcard::Writer& w, const $name& v);));
+                header.push();
+            }
+        }
+        for container in containers.iter() {
+            header.line();
+            header.append(gen_encode_functions(container));
+        }
+    }
+
+    if gen_settings.des {
+        header.line();
+        for container in containers.iter() {
+            for name in function_types(container) {
+                header.append(quote!(inline bool decode(postcard::Reader& r, $name& v);));
+                header.push();
+            }
+        }
+        for container in containers.iter() {
+            header.line();
+            header.append(gen_decode_functions(container));
+        }
+    }
+
+    header.line();
+    header.append(quote! {
+        $(format!("}} // namespace {module_name}"))
+
+        #endif
+    });
+
+    Exports {
+        files: vec![ExportFile {
+            content_type: "header".to_owned(),
+            content: header,
+        }],
+    }
+}
+
+/// Fields of a struct or an enum variant.
+enum Fields<'a> {
+    Named(&'a [StructField]),
+    Unnamed(&'a [ValueType]),
+    Unit,
+}
+
+impl Fields<'_> {
+    fn members(&self) -> Vec<(String, &ValueType)> {
+        match self {
+            Fields::Named(fields) => encoded_fields(fields)
+                .map(|field| (cpp_ident(&field.name), &field.v_type))
+                .collect(),
+            Fields::Unnamed(fields) => fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| (format!("_{index}"), field))
+                .collect(),
+            Fields::Unit => Vec::new(),
+        }
+    }
+}
+
+impl<'a> From<&'a EnumVariantType> for Fields<'a> {
+    fn from(variant_type: &'a EnumVariantType) -> Self {
+        match variant_type {
+            EnumVariantType::Empty => Fields::Unit,
+            EnumVariantType::Tuple(fields) => Fields::Unnamed(fields),
+            EnumVariantType::NewType(fields) => Fields::Named(fields),
+        }
+    }
+}
+
+/// The structs of a container with their fields: the container itself or, for enums,
+/// the structs of the variants.
+fn nested_types(container: &Container) -> Vec<(String, Fields<'_>)> {
+    match &container.r#type {
+        BindingType::Struct(struct_type) => vec![(
+            container.name.to_string(),
+            Fields::Named(&struct_type.fields),
+        )],
+        BindingType::TupleStruct(tuple_struct) => vec![(
+            container.name.to_string(),
+            Fields::Unnamed(&tuple_struct.fields),
+        )],
+        BindingType::UnitStruct(_) => vec![(container.name.to_string(), Fields::Unit)],
+        BindingType::Enum(enum_type) => enum_type
+            .variants
+            .iter()
+            .map(|variant| {
+                (
+                    format!("{}::{}", container.name, variant.name),
+                    Fields::from(&variant.inner_type),
+                )
+            })
+            .collect(),
+    }
+}
+
+/// Types with an `encode` and `decode` overload.
+fn function_types(container: &Container) -> Vec<String> {
+    let mut names = nested_types(container)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+    if matches!(container.r#type, BindingType::Enum(_)) {
+        names.push(container.name.to_string());
+    }
+    names
+}
+
+fn gen_type(container: &Container, namespace: &str) -> Tokens {
+    let mut tokens = Tokens::new();
+    match &container.r#type {
+        BindingType::Enum(enum_type) => {
+            let mut body = Tokens::new();
+            for variant in enum_type.variants.iter() {
+                gen_struct(
+                    &mut body,
+                    &variant.name,
+                    &Fields::from(&variant.inner_type),
+                    namespace,
+                );
+                body.line();
+            }
+            let variants = enum_type.variants.iter().map(|variant| &variant.name);
+            quote_in! { tokens =>
+                struct $(&container.name) {
+                    $body
+                    std::variant<$(for variant in variants join (, ) => $variant)> value;
+                };
+            }
+        }
+        _ => {
+            for (name, fields) in nested_types(container) {
+                gen_struct(&mut tokens, &name, &fields, namespace);
+            }
+        }
+    }
+    tokens
+}
+
+fn gen_struct(tokens: &mut Tokens, name: &str, fields: &Fields, namespace: &str) {
+    let members = fields.members();
+    if members.is_empty() {
+        quote_in!(*tokens => struct $name {};);
+        return;
+    }
+    quote_in! { *tokens =>
+        struct $name {
+            $(for (member, v_type) in members => $(cpp_type(v_type, namespace)) $member{};$['\r'])
+        };
+    }
+}
+
+fn gen_encode_functions(container: &Container) -> Tokens {
+    let mut tokens = Tokens::new();
+    for (name, fields) in nested_types(container) {
+        if !tokens.is_empty() {
+            tokens.line();
+        }
+        gen_encode_struct(&mut tokens, &name, &fields);
+    }
+
+    if let BindingType::Enum(enum_type) = &container.r#type {
+        tokens.line();
+        let tags = enum_type.variants.iter().map(|variant| variant.index);
+        quote_in! { tokens =>
+            inline void encode(postcard::Writer& w, const $(&container.name)& v) {
+                static constexpr std::uint32_t TAGS[] = {$(for tag in tags join (, ) => $tag)};
+                encode(w, TAGS[v.value.index()]);
+                std::visit([&w](const auto& variant) { encode(w, variant); }, v.value);
+            }
+        }
+    }
+    tokens
+}
+
+fn gen_encode_struct(tokens: &mut Tokens, name: &str, fields: &Fields) {
+    let members = fields.members();
+    if members.is_empty() {
+        quote_in!(*tokens => inline void encode(postcard::Writer&, const $name&) {});
+        return;
+    }
+    quote_in! { *tokens =>
+        inline void encode(postcard::Writer& w, const $name& v) {
+            $(for (member, _) in members => encode(w, v.$member);$['\r'])
+        }
+    }
+}
+
+fn gen_decode_functions(container: &Container) -> Tokens {
+    let mut tokens = Tokens::new();
+    for (name, fields) in nested_types(container) {
+        if !tokens.is_empty() {
+            tokens.line();
+        }
+        gen_decode_struct(&mut tokens, &name, &fields);
+    }
+
+    if let BindingType::Enum(enum_type) = &container.r#type {
+        tokens.line();
+        let mut cases = Tokens::new();
+        for variant in enum_type.variants.iter() {
+            quote_in! { cases =>
+                case $(variant.index):
+                    return postcard::decode_variant<$(&container.name)::$(&variant.name)>(r, v.value);
+            }
+            cases.push();
+        }
+        quote_in! { tokens =>
+            inline bool decode(postcard::Reader& r, $(&container.name)& v) {
+                std::uint32_t tag;
+                if (!decode(r, tag)) {
+                    return false;
+                }
+                switch (tag) {
+                $cases
+                default:
+                    return false;
+                }
+            }
+        }
+    }
+    tokens
+}
+
+fn gen_decode_struct(tokens: &mut Tokens, name: &str, fields: &Fields) {
+    let members = fields.members();
+    if members.is_empty() {
+        quote_in!(*tokens => inline bool decode(postcard::Reader&, $name&) { return true; });
+        return;
+    }
+    quote_in! { *tokens =>
+        inline bool decode(postcard::Reader& r, $name& v) {
+            return $(for (member, _) in members join ( && ) => decode(r, v.$member));
+        }
+    }
+}
+
+/// Orders the containers so that types embedded by value are defined first.
+fn sort_containers(containers: Vec<Container>) -> Vec<Container> {
+    let mut names = BTreeSet::new();
+    for container in containers.iter() {
+        assert!(
+            names.insert(&container.name),
+            "the C++ bindings hold all types in one namespace, but `{}` is defined twice",
+            container.name
+        );
+    }
+
+    let indices = containers
+        .iter()
+        .enumerate()
+        .map(|(index, container)| (container.name.as_ref(), index))
+        .collect::<BTreeMap<_, _>>();
+
+    fn visit(
+        index: usize,
+        containers: &[Container],
+        indices: &BTreeMap<&str, usize>,
+        visiting: &mut Vec<usize>,
+        order: &mut Vec<usize>,
+    ) {
+        if order.contains(&index) {
+            return;
+        }
+        if visiting.contains(&index) {
+            panic!(
+                "`{}` contains itself by value, which can't be represented in C++. Only recursion through a `Vec` or a map is supported",
+                containers[index].name
+            );
+        }
+
+        visiting.push(index);
+        for (_, fields) in nested_types(&containers[index]) {
+            for (_, v_type) in fields.members() {
+                let mut dependencies = Vec::new();
+                value_dependencies(v_type, &mut dependencies);
+                for dependency in dependencies {
+                    if let Some(dependency) = indices.get(dependency) {
+                        visit(*dependency, containers, indices, visiting, order);
+                    }
+                }
+            }
+        }
+        visiting.pop();
+
+        order.push(index);
+    }
+
+    let mut order = Vec::new();
+    for index in 0..containers.len() {
+        visit(index, &containers, &indices, &mut Vec::new(), &mut order);
+    }
+
+    let mut containers = containers.into_iter().map(Some).collect::<Vec<_>>();
+    order
+        .into_iter()
+        .map(|index| containers[index].take().unwrap())
+        .collect()
+}
+
+/// Names of the types which are embedded by value and have to be complete before the value.
+fn value_dependencies<'a>(value_type: &'a ValueType, dependencies: &mut Vec<&'a str>) {
+    match value_type {
+        ValueType::Object(meta) => dependencies.push(&meta.name),
+        ValueType::Optional(meta) => value_dependencies(&meta.inner, dependencies),
+        ValueType::Range(meta) => value_dependencies(&meta.bounds_type, dependencies),
+        ValueType::Array(ArrayMeta {
+            items_type,
+            length: Some(_),
+            ..
+        }) => value_dependencies(items_type, dependencies),
+        ValueType::Tuple(meta) => meta
+            .items_types
+            .iter()
+            .for_each(|item| value_dependencies(item, dependencies)),
+        // vectors and maps may hold incomplete types
+        _ => (),
+    }
+}
+
+/// The C++ type of a value, types of the bindings are qualified with the namespace as
+/// the structs of enum variants may shadow them.
+fn cpp_type(value_type: &ValueType, namespace: &str) -> String {
+    match value_type {
+        ValueType::Number(NumberMeta::Integer { bytes, signed, .. }) => {
+            assert_ne!(
+                *bytes, 16,
+                "128 bit integers are not supported by the C++ bindings"
+            );
+            format!("std::{}int{}_t", if *signed { "" } else { "u" }, bytes * 8)
+        }
+        ValueType::Number(NumberMeta::FloatingPoint { bytes: 4 }) => "float".to_owned(),
+        ValueType::Number(NumberMeta::FloatingPoint { .. }) => "double".to_owned(),
+        ValueType::Bool(_) => "bool".to_owned(),
+        ValueType::String(_) => "std::string".to_owned(),
+        ValueType::Object(meta) => format!("::{namespace}::{}", meta.name),
+        ValueType::Optional(meta) => format!("std::optional<{}>", cpp_type(&meta.inner, namespace)),
+        ValueType::Range(meta) => format!(
+            "postcard::Range<{}>",
+            cpp_type(&meta.bounds_type, namespace)
+        ),
+        ValueType::Array(ArrayMeta {
+            items_type,
+            length: Some(length),
+            ..
+        }) => format!("std::array<{}, {length}>", cpp_type(items_type, namespace)),
+        ValueType::Array(ArrayMeta { items_type, .. }) => {
+            format!("std::vector<{}>", cpp_type(items_type, namespace))
+        }
+        ValueType::Set(_) => unreachable!("sets are replaced by sequences"),
+        ValueType::Unit(_) => panic!("`()` is only supported as field by the C++ bindings"),
+        ValueType::Map(meta) => format!(
+            "std::map<{}, {}>",
+            cpp_type(&meta.key_type, namespace),
+            cpp_type(&meta.value_type, namespace)
+        ),
+        ValueType::Tuple(meta) => format!(
+            "std::tuple<{}>",
+            meta.items_types
+                .iter()
+                .map(|item| cpp_type(item, namespace))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn encoded_fields(fields: &[StructField]) -> impl Iterator<Item = &StructField> {
+    fields.iter().filter(|field| !field.skip)
+}
+
+fn cpp_ident(name: &str) -> String {
+    if CPP_KEYWORDS.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_owned()
+    }
+}
@@ -0,0 +1,452 @@
+use genco::quote;
+
+use super::Tokens;
+
+/// The `postcard` namespace with the writer, the reader and the encode and decode overloads
+/// of the primitive and standard library types.
+pub fn gen_runtime() -> Tokens {
+    quote!($(RUNTIME))
+}
+
+const RUNTIME: &str = r#"#include <array>
+#include <cstddef>
+#include <cstdint>
+#include <cstring>
+#include <limits>
+#include <map>
+#include <optional>
+#include <string>
+#include <tuple>
+#include <utility>
+#include <variant>
+#include <vector>
+
+#ifndef POSTCARD_BINDGEN_RUNTIME
+#define POSTCARD_BINDGEN_RUNTIME
+
+namespace postcard {
+
+// rust `Range` and `RangeInclusive`
+template <typename T>
+struct Range {
+    T start{};
+    T end{};
+};
+
+class Writer {
+public:
+    std::vector<std::uint8_t> bytes;
+
+    void push(std::uint8_t byte) { bytes.push_back(byte); }
+
+    void push_n(const std::uint8_t* data, std::size_t n) { bytes.insert(bytes.end(), data, data + n); }
+
+    void varint(std::uint64_t v) {
+        do {
+            std::uint8_t byte = static_cast<std::uint8_t>(v & 0x7F);
+            v >>= 7;
+            if (v != 0) {
+                byte |= 0x80;
+            }
+            push(byte);
+        } while (v != 0);
+    }
+
+    void zigzag(std::int64_t v) {
+        varint((static_cast<std::uint64_t>(v) << 1) ^ (v < 0 ? UINT64_MAX : 0));
+    }
+};
+
+class Reader {
+public:
+    Reader(const std::uint8_t* data, std::size_t len) : data_(data), len_(len) {}
+
+    std::size_t position() const { return pos_; }
+
+    bool take(const std::uint8_t** out, std::size_t n) {
+        if (len_ - pos_ < n) {
+            return false;
+        }
+        *out = data_ + pos_;
+        pos_ += n;
+        return true;
+    }
+
+    // rejects varints longer than `max_bytes` and values exceeding 64 bits
+    bool varint(std::uint64_t& v, std::size_t max_bytes) {
+        std::uint64_t out = 0;
+        for (std::size_t i = 0; i < max_bytes; i++) {
+            const std::uint8_t* byte;
+            if (!take(&byte, 1) || (i == 9 && *byte > 1)) {
+                return false;
+            }
+            out |= static_cast<std::uint64_t>(*byte & 0x7F) << (7 * i);
+            if ((*byte & 0x80) == 0) {
+                v = out;
+                return true;
+            }
+        }
+        return false;
+    }
+
+    bool zigzag(std::int64_t& v, std::size_t max_bytes) {
+        std::uint64_t raw;
+        if (!varint(raw, max_bytes)) {
+            return false;
+        }
+        v = static_cast<std::int64_t>(raw >> 1) ^ -static_cast<std::int64_t>(raw & 1);
+        return true;
+    }
+
+    bool len(std::size_t& len) {
+        std::uint64_t raw;
+        if (!varint(raw, 10)) {
+            return false;
+        }
+#if SIZE_MAX < UINT64_MAX
+        if (raw > SIZE_MAX) {
+            return false;
+        }
+#endif
+        len = static_cast<std::size_t>(raw);
+        return true;
+    }
+
+private:
+    const std::uint8_t* data_;
+    std::size_t len_;
+    std::size_t pos_ = 0;
+};
+
+inline bool is_utf8(const std::uint8_t* s, std::size_t len) {
+    std::size_t i = 0;
+    while (i < len) {
+        const std::uint8_t c = s[i];
+        std::size_t n;
+        std::uint32_t min, cp;
+        if (c < 0x80) {
+            i++;
+            continue;
+        } else if ((c & 0xE0) == 0xC0) {
+            n = 1; min = 0x80; cp = c & 0x1F;
+        } else if ((c & 0xF0) == 0xE0) {
+            n = 2; min = 0x800; cp = c & 0x0F;
+        } else if ((c & 0xF8) == 0xF0) {
+            n = 3; min = 0x10000; cp = c & 0x07;
+        } else {
+            return false;
+        }
+        if (len - i - 1 < n) {
+            return false;
+        }
+        for (std::size_t j = 1; j <= n; j++) {
+            if ((s[i + j] & 0xC0) != 0x80) {
+                return false;
+            }
+            cp = (cp << 6) | (s[i + j] & 0x3F);
+        }
+        if (cp < min || cp > 0x10FFFF || (cp >= 0xD800 && cp <= 0xDFFF)) {
+            return false;
+        }
+        i += n + 1;
+    }
+    return true;
+}
+
+template <typename T>
+inline bool decode_unsigned(Reader& r, T& v, std::size_t max_bytes) {
+    std::uint64_t raw;
+    if (!r.varint(raw, max_bytes)) {
+        return false;
+    }
+    if constexpr (sizeof(T) < sizeof(raw)) {
+        if (raw > std::numeric_limits<T>::max()) {
+            return false;
+        }
+    }
+    v = static_cast<T>(raw);
+    return true;
+}
+
+template <typename T>
+inline bool decode_signed(Reader& r, T& v, std::size_t max_bytes) {
+    std::int64_t raw;
+    if (!r.zigzag(raw, max_bytes)) {
+        return false;
+    }
+    if constexpr (sizeof(T) < sizeof(raw)) {
+        if (raw < std::numeric_limits<T>::min() || raw > std::numeric_limits<T>::max()) {
+            return false;
+        }
+    }
+    v = static_cast<T>(raw);
+    return true;
+}
+
+template <typename T, typename Bits>
+inline void encode_float(Writer& w, T v) {
+    Bits bits;
+    std::memcpy(&bits, &v, sizeof(bits));
+    for (std::size_t i = 0; i < sizeof(bits); i++) {
+        w.push(static_cast<std::uint8_t>(bits >> (8 * i)));
+    }
+}
+
+template <typename T, typename Bits>
+inline bool decode_float(Reader& r, T& v) {
+    const std::uint8_t* bytes;
+    if (!r.take(&bytes, sizeof(Bits))) {
+        return false;
+    }
+    Bits bits = 0;
+    for (std::size_t i = 0; i < sizeof(bits); i++) {
+        bits |= static_cast<Bits>(bytes[i]) << (8 * i);
+    }
+    std::memcpy(&v, &bits, sizeof(bits));
+    return true;
+}
+
+inline void encode(Writer& w, bool v) { w.push(v ? 1 : 0); }
+inline void encode(Writer& w, std::uint8_t v) { w.push(v); }
+inline void encode(Writer& w, std::int8_t v) { w.push(static_cast<std::uint8_t>(v)); }
+inline void encode(Writer& w, std::uint16_t v) { w.varint(v); }
+inline void encode(Writer& w, std::uint32_t v) { w.varint(v); }
+inline void encode(Writer& w, std::uint64_t v) { w.varint(v); }
+inline void encode(Writer& w, std::int16_t v) { w.zigzag(v); }
+inline void encode(Writer& w, std::int32_t v) { w.zigzag(v); }
+inline void encode(Writer& w, std::int64_t v) { w.zigzag(v); }
+inline void encode(Writer& w, float v) { encode_float<float, std::uint32_t>(w, v); }
+inline void encode(Writer& w, double v) { encode_float<double, std::uint64_t>(w, v); }
+
+inline void encode(Writer& w, const std::string& v) {
+    w.varint(v.size());
+    w.push_n(reinterpret_cast<const std::uint8_t*>(v.data()), v.size());
+}
+
+inline bool decode(Reader& r, bool& v) {
+    const std::uint8_t* byte;
+    if (!r.take(&byte, 1) || *byte > 1) {
+        return false;
+    }
+    v = *byte == 1;
+    return true;
+}
+
+inline bool decode(Reader& r, std::uint8_t& v) {
+    const std::uint8_t* byte;
+    if (!r.take(&byte, 1)) {
+        return false;
+    }
+    v = *byte;
+    return true;
+}
+
+inline bool decode(Reader& r, std::int8_t& v) {
+    const std::uint8_t* byte;
+    if (!r.take(&byte, 1)) {
+        return false;
+    }
+    v = static_cast<std::int8_t>(*byte);
+    return true;
+}
+
+inline bool decode(Reader& r, std::uint16_t& v) { return decode_unsigned(r, v, 3); }
+inline bool decode(Reader& r, std::uint32_t& v) { return decode_unsigned(r, v, 5); }
+inline bool decode(Reader& r, std::uint64_t& v) { return decode_unsigned(r, v, 10); }
+inline bool decode(Reader& r, std::int16_t& v) { return decode_signed(r, v, 3); }
+inline bool decode(Reader& r, std::int32_t& v) { return decode_signed(r, v, 5); }
+inline bool decode(Reader& r, std::int64_t& v) { return decode_signed(r, v, 10); }
+inline bool decode(Reader& r, float& v) { return decode_float<float, std::uint32_t>(r, v); }
+inline bool decode(Reader& r, double& v) { return decode_float<double, std::uint64_t>(r, v); }
+
+inline bool decode(Reader& r, std::string& v) {
+    std::size_t len;
+    const std::uint8_t* bytes;
+    if (!r.len(len) || !r.take(&bytes, len) || !is_utf8(bytes, len)) {
+        return false;
+    }
+    v.assign(reinterpret_cast<const char*>(bytes), len);
+    return true;
+}
+
+template <typename T>
+void encode(Writer& w, const std::optional<T>& v);
+template <typename T>
+void encode(Writer& w, const std::vector<T>& v);
+template <typename T, std::size_t N>
+void encode(Writer& w, const std::array<T, N>& v);
+template <typename K, typename V>
+void encode(Writer& w, const std::map<K, V>& v);
+template <typename... T>
+void encode(Writer& w, const std::tuple<T...>& v);
+template <typename T>
+void encode(Writer& w, const Range<T>& v);
+
+template <typename T>
+bool decode(Reader& r, std::optional<T>& v);
+template <typename T>
+bool decode(Reader& r, std::vector<T>& v);
+template <typename T, std::size_t N>
+bool decode(Reader& r, std::array<T, N>& v);
+template <typename K, typename V>
+bool decode(Reader& r, std::map<K, V>& v);
+template <typename... T>
+bool decode(Reader& r, std::tuple<T...>& v);
+template <typename T>
+bool decode(Reader& r, Range<T>& v);
+
+template <typename T>
+void encode(Writer& w, const std::optional<T>& v) {
+    encode(w, v.has_value());
+    if (v.has_value()) {
+        encode(w, *v);
+    }
+}
+
+template <typename T>
+void encode(Writer& w, const std::vector<T>& v) {
+    w.varint(v.size());
+    for (std::size_t i = 0; i < v.size(); i++) {
+        // the element type is spelled out for the proxies of `std::vector<bool>`
+        encode(w, static_cast<const T&>(v[i]));
+    }
+}
+
+template <typename T, std::size_t N>
+void encode(Writer& w, const std::array<T, N>& v) {
+    for (const T& item : v) {
+        encode(w, item);
+    }
+}
+
+template <typename K, typename V>
+void encode(Writer& w, const std::map<K, V>& v) {
+    w.varint(v.size());
+    for (const auto& entry : v) {
+        encode(w, entry.first);
+        encode(w, entry.second);
+    }
+}
+
+template <typename... T>
+void encode(Writer& w, const std::tuple<T...>& v) {
+    std::apply([&w](const auto&... items) { (encode(w, items), ...); }, v);
+}
+
+template <typename T>
+void encode(Writer& w, const Range<T>& v) {
+    encode(w, v.start);
+    encode(w, v.end);
+}
+
+template <typename T>
+bool decode(Reader& r, std::optional<T>& v) {
+    bool is_some;
+    if (!decode(r, is_some)) {
+        return false;
+    }
+    if (!is_some) {
+        v.reset();
+        return true;
+    }
+    T value{};
+    if (!decode(r, value)) {
+        return false;
+    }
+    v = std::move(value);
+    return true;
+}
+
+template <typename T>
+bool decode(Reader& r, std::vector<T>& v) {
+    std::size_t len;
+    if (!r.len(len)) {
+        return false;
+    }
+    v.clear();
+    for (std::size_t i = 0; i < len; i++) {
+        T item{};
+        if (!decode(r, item)) {
+            return false;
+        }
+        v.push_back(std::move(item));
+    }
+    return true;
+}
+
+template <typename T, std::size_t N>
+bool decode(Reader& r, std::array<T, N>& v) {
+    for (T& item : v) {
+        if (!decode(r, item)) {
+            return false;
+        }
+    }
+    return true;
+}
+
+template <typename K, typename V>
+bool decode(Reader& r, std::map<K, V>& v) {
+    std::size_t len;
+    if (!r.len(len)) {
+        return false;
+    }
+    v.clear();
+    for (std::size_t i = 0; i < len; i++) {
+        K key{};
+        V value{};
+        if (!decode(r, key) || !decode(r, value)) {
+            return false;
+        }
+        v.insert_or_assign(std::move(key), std::move(value));
+    }
+    return true;
+}
+
+template <typename... T>
+bool decode(Reader& r, std::tuple<T...>& v) {
+    return std::apply([&r](auto&... items) { return (decode(r, items) && ...); }, v);
+}
+
+template <typename T>
+bool decode(Reader& r, Range<T>& v) {
+    return decode(r, v.start) && decode(r, v.end);
+}
+
+// decodes the alternative `T` of an enum and stores it in the variant
+template <typename T, typename V>
+bool decode_variant(Reader& r, V& v) {
+    T value{};
+    if (!decode(r, value)) {
+        return false;
+    }
+    v = std::move(value);
+    return true;
+}
+
+// serializes the value to postcard bytes
+template <typename T>
+std::vector<std::uint8_t> to_bytes(const T& value) {
+    Writer w;
+    encode(w, value);
+    return std::move(w.bytes);
+}
+
+// deserializes a value from postcard bytes, empty if the bytes are invalid or not fully consumed
+template <typename T>
+std::optional<T> from_bytes(const std::uint8_t* data, std::size_t len) {
+    Reader r(data, len);
+    T value{};
+    if (!decode(r, value) || r.position() != len) {
+        return std::nullopt;
+    }
+    return value;
+}
+
+template <typename T>
+std::optional<T> from_bytes(const std::vector<std::uint8_t>& bytes) {
+    return from_bytes<T>(bytes.data(), bytes.size());
+}
+
+} // namespace postcard
+
+#endif"#;
@@ -0,0 +1,494 @@
+mod runtime;
+
+use core::borrow::Borrow;
+use std::collections::BTreeSet;
+
+use convert_case::{Case, Casing};
+use genco::{lang::csharp::Csharp, quote, quote_in};
+
+use runtime::{gen_des_runtime, gen_runtime, gen_ser_runtime};
+
+use super::backend::{generate_with, LanguageBackend};
+use crate::{
+    registry::{
+        BindingType, Container, ContainerCollection, EnumType, EnumVariantType, StructField,
+        StructType, TupleStructType,
+    },
+    type_info::{ArrayMeta, NumberMeta, ValueType},
+    Exports,
+};
+
+pub use super::settings::{GenerationSettings, PointerWidth};
+
+type Tokens = genco::lang::csharp::Tokens;
+type ExportFile = crate::ExportFile<Csharp>;
+
+/// Members every generated record has, which can't be used as property or variant names.
+const RECORD_MEMBERS: &[&str] = &[
+    "Decode",
+    "Deconstruct",
+    "Encode",
+    "EqualityContract",
+    "Equals",
+    "Finalize",
+    "GetHashCode",
+    "GetType",
+    "MemberwiseClone",
+    "PrintMembers",
+    "ToString",
+];
+
+/// Generates a C# source file, exported as `source`.
+///
+/// The types are placed in the namespace `namespace`, converted to pascal case. Structs
+/// become sealed records with pascal case properties and enums abstract records with a
+/// nested record per variant. Encoding and decoding is done with
+///
+/// ```csharp
+/// byte[] Postcard.Serialize<T>(T value) where T : IPostcardEncodable
+/// T Postcard.Deserialize<T>(ReadOnlySpan<byte> bytes) where T : IPostcardDecodable<T>
+/// ```
+///
+/// where decoding throws a `PostcardException` if the input is invalid. The decoders
+/// read from a `ReadOnlySpan<byte>` without copying it.
+///
+/// The code needs C# 11 and .NET 7, for `UInt128` and static abstract interface members.
+/// Vectors become a `List`, arrays an array, maps a `Dictionary` and tuples value tuples.
+/// Generating panics for tuples with less than two items and for nested optionals.
+pub fn generate(
+    containers: ContainerCollection,
+    gen_settings: impl Borrow<GenerationSettings>,
+    namespace: String,
+) -> Exports<Csharp> {
+    let namespace = namespace.to_case(Case::Pascal);
+    let backend = CsharpBackend {
+        gen_settings: gen_settings.borrow(),
+        qualifier: format!("global::{namespace}."),
+        namespace,
+        records: Tokens::new(),
+    };
+    generate_with(backend, containers)
+}
+
+struct CsharpBackend<'a> {
+    gen_settings: &'a GenerationSettings,
+    namespace: String,
+    qualifier: String,
+    records: Tokens,
+}
+
+impl CsharpBackend<'_> {
+    fn push_record(&mut self, container: &Container) {
+        self.records.line();
+        self.records
+            .append(gen_record(container, self.gen_settings, &self.qualifier));
+    }
+}
+
+impl LanguageBackend for CsharpBackend<'_> {
+    type Lang = Csharp;
+
+    fn prepare(&mut self, containers: &mut ContainerCollection) {
+        containers.assert_no_internally_tagged_enums("C#");
+        containers.flatten();
+        containers.resolve_pointer_width(self.gen_settings.pointer_width.bytes());
+        containers.sets_as_sequences();
+        containers.remove_unit_fields();
+
+        let mut names = BTreeSet::new();
+        for container in containers.all_containers() {
+            assert!(
+                names.insert(container.name.clone()),
+                "the C# bindings hold all types in one namespace, but `{}` is defined twice",
+                container.name
+            );
+        }
+    }
+
+    fn visit_struct(&mut self, container: &Container, _: &StructType) {
+        self.push_record(container);
+    }
+
+    fn visit_tuple_struct(&mut self, container: &Container, _: &TupleStructType) {
+        self.push_record(container);
+    }
+
+    fn visit_enum(&mut self, container: &Container, _: &EnumType) {
+        self.push_record(container);
+    }
+
+    fn visit_unit_struct(&mut self, container: &Container) {
+        self.push_record(container);
+    }
+
+    fn finish(self) -> Exports<Csharp> {
+        let mut source = quote! {
+            #nullable enable
+
+            using System;
+            using System.Buffers.Binary;
+            using System.Collections.Generic;
+            using System.Text;
+
+            namespace $(&self.namespace);
+
+            $(gen_runtime())
+        };
+
+        if self.gen_settings.ser {
+            source.line();
+            source.append(gen_ser_runtime());
+        }
+
+        if self.gen_settings.des {
+            source.line();
+            source.append(gen_des_runtime());
+        }
+
+        source.append(self.records);
+
+        Exports {
+            files: vec![ExportFile {
+                content_type: "source".to_owned(),
+                content: source,
+            }],
+        }
+    }
+}
+
+/// Fields of a struct or an enum variant.
+enum Fields<'a> {
+    Named(&'a [StructField]),
+    Unnamed(&'a [ValueType]),
+    Unit,
+}
+
+impl<'a> Fields<'a> {
+    /// Property names, which must differ from the name of the `record`.
+    fn members(&self, record: &str) -> Vec<(String, &'a ValueType)> {
+        match self {
+            Fields::Named(fields) => fields
+                .iter()
+                .filter(|field| !field.skip)
+                .map(|field| {
+                    (
+                        member_ident(&field.name.to_case(Case::Pascal), record),
+                        &field.v_type,
+                    )
+                })
+                .collect(),
+            Fields::Unnamed(fields) => fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| (format!("Field{index}"), field))
+                .collect(),
+            Fields::Unit => Vec::new(),
+        }
+    }
+}
+
+impl<'a> From<&'a EnumVariantType> for Fields<'a> {
+    fn from(variant_type: &'a EnumVariantType) -> Self {
+        match variant_type {
+            EnumVariantType::Empty => Fields::Unit,
+            EnumVariantType::Tuple(fields) => Fields::Unnamed(fields),
+            EnumVariantType::NewType(fields) => Fields::Named(fields),
+        }
+    }
+}
+
+fn struct_fields(container: &Container) -> Option<Fields<'_>> {
+    match &container.r#type {
+        BindingType::Struct(struct_type) => Some(Fields::Named(&struct_type.fields)),
+        BindingType::TupleStruct(tuple_struct) => Some(Fields::Unnamed(&tuple_struct.fields)),
+        BindingType::UnitStruct(_) => Some(Fields::Unit),
+        BindingType::Enum(_) => None,
+    }
+}
+
+fn gen_record(container: &Container, gen_settings: &GenerationSettings, qualifier: &str) -> Tokens {
+    let name = container.name.as_ref();
+    let mut interfaces = Vec::new();
+    if gen_settings.ser {
+        interfaces.push("IPostcardEncodable".to_owned());
+    }
+    if gen_settings.des {
+        interfaces.push(format!("IPostcardDecodable<{name}>"));
+    }
+    let interfaces = if interfaces.is_empty() {
+        String::new()
+    } else {
+        format!(" : {}", interfaces.join(", "))
+    };
+
+    match &container.r#type {
+        BindingType::Enum(enum_type) => {
+            let mut body = quote!(private $name() { });
+            for variant in enum_type.variants.iter() {
+                let fields = Fields::from(&variant.inner_type);
+                let variant_name = member_ident(&variant.name, name);
+                let members = fields.members(&variant_name);
+                let params = gen_params(&members, qualifier);
+
+                body.line();
+                if gen_settings.ser {
+                    quote_in! { body =>
+                        public sealed record $(&variant_name)($params) : $name
+                        {
+                            public override void Encode(PostcardWriter w)
+                            {
+                                w.WriteU32($(variant.index));
+                                $(for (member, v_type) in members.iter() => $(gen_encode(v_type, quote!($member), 0))$['\r'])
+                            }
+                        }
+                    }
+                } else {
+                    quote_in!(body => public sealed record $(&variant_name)($params) : $name;);
+                }
+            }
+
+            if gen_settings.ser {
+                body.line();
+                quote_in!(body => public abstract void Encode(PostcardWriter w););
+            }
+
+            if gen_settings.des {
+                let mut arms = Tokens::new();
+                for variant in enum_type.variants.iter() {
+                    let fields = Fields::from(&variant.inner_type);
+                    let variant_name = member_ident(&variant.name, name);
+                    let members = fields.members(&variant_name);
+                    quote_in! { arms =>
+                        $(variant.index) => new $(&variant_name)($(for (_, v_type) in members.iter() join (, ) => $(gen_decode(v_type, "r", 0, qualifier)))),
+                    }
+                    arms.push();
+                }
+                body.line();
+                quote_in! { body =>
+                    public static $name Decode(ref PostcardReader r)
+                    {
+                        var tag = r.ReadU32();
+                        return tag switch
+                        {
+                            $arms
+                            _ => throw new PostcardException($(format!("$\"unknown variant {{tag}} of {name}\""))),
+                        };
+                    }
+                }
+            }
+
+            quote! {
+                public abstract record $name$interfaces
+                {
+                    $body
+                }
+            }
+        }
+        _ => {
+            let members = struct_fields(container).unwrap().members(name);
+            // properties may shadow type names in expressions, but not in types
+            let params = gen_params(&members, "");
+
+            let mut body = Tokens::new();
+            if gen_settings.ser {
+                quote_in! { body =>
+                    public void Encode(PostcardWriter w)
+                    {
+                        $(for (member, v_type) in members.iter() => $(gen_encode(v_type, quote!($member), 0))$['\r'])
+                    }
+                }
+            }
+            if gen_settings.des {
+                body.line();
+                quote_in! { body =>
+                    public static $name Decode(ref PostcardReader r) => new($(for (_, v_type) in members.iter() join (, ) => $(gen_decode(v_type, "r", 0, qualifier))));
+                }
+            }
+
+            if body.is_empty() {
+                return quote!(public sealed record $name($params););
+            }
+            quote! {
+                public sealed record $name($params)$interfaces
+                {
+                    $body
+                }
+            }
+        }
+    }
+}
+
+fn gen_params(members: &[(String, &ValueType)], qualifier: &str) -> Tokens {
+    quote!($(for (member, v_type) in members join (, ) => $(cs_type(v_type, qualifier)) $member))
+}
+
+/// Encodes `value` as statements, variables are numbered with `depth`.
+fn gen_encode(v_type: &ValueType, value: Tokens, depth: usize) -> Tokens {
+    let item = format!("x{depth}");
+    match v_type {
+        ValueType::Object(_) => quote!($value.Encode(w);),
+        ValueType::Optional(meta) => {
+            let some = format!("v{depth}");
+            quote!(if ($value is { } $(&some)) { w.Push(1); $(gen_encode(&meta.inner, quote!($(&some)), depth + 1)) } else { w.Push(0); })
+        }
+        ValueType::Array(ArrayMeta {
+            items_type,
+            length: Some(length),
+            ..
+        }) => {
+            quote!(w.CheckLength($(value.clone()).Length, $(*length)); foreach (var $(&item) in $value) { $(gen_encode(items_type, quote!($(&item)), depth + 1)) })
+        }
+        ValueType::Array(ArrayMeta { items_type, .. }) => {
+            quote!(w.WriteLength($(value.clone()).Count); foreach (var $(&item) in $value) { $(gen_encode(items_type, quote!($(&item)), depth + 1)) })
+        }
+        ValueType::Map(meta) => {
+            let key = format!("k{depth}");
+            quote!(w.WriteLength($(value.clone()).Count); foreach (var ($(&key), $(&item)) in $value) { $(gen_encode(&meta.key_type, quote!($(&key)), depth + 1)) $(gen_encode(&meta.value_type, quote!($(&item)), depth + 1)) })
+        }
+        ValueType::Range(meta) => {
+            quote!($(gen_encode(&meta.bounds_type, quote!($(value.clone()).Start), depth)) $(gen_encode(&meta.bounds_type, quote!($value.End), depth)))
+        }
+        ValueType::Tuple(meta) => {
+            let items = meta
+                .items_types
+                .iter()
+                .enumerate()
+                .map(|(index, item_type)| {
+                    gen_encode(
+                        item_type,
+                        quote!($(value.clone()).$(format!("Item{}", index + 1))),
+                        depth,
+                    )
+                })
+                .collect::<Vec<_>>();
+            quote!($(for item in items join ( ) => $item))
+        }
+        _ => quote!(w.$(format!("Write{}", primitive_fn(v_type)))($value);),
+    }
+}
+
+/// Decodes a value from `reader`, nested decoders are static lambdas whose reader is
+/// numbered with `depth`.
+fn gen_decode(v_type: &ValueType, reader: &str, depth: usize, qualifier: &str) -> Tokens {
+    let decoder = |v_type: &ValueType| {
+        let inner = format!("r{}", depth + 1);
+        quote!(static (ref PostcardReader $(&inner)) => $(gen_decode(v_type, &inner, depth + 1, qualifier)))
+    };
+    match v_type {
+        ValueType::Object(meta) => {
+            quote!($(format!("{qualifier}{}", meta.name)).Decode(ref $reader))
+        }
+        ValueType::Optional(meta) if is_value_type(&meta.inner) => {
+            quote!($reader.ReadOptionValue($(decoder(&meta.inner))))
+        }
+        ValueType::Optional(meta) => quote!($reader.ReadOptionRef($(decoder(&meta.inner)))),
+        ValueType::Array(ArrayMeta {
+            items_type,
+            length: Some(length),
+            ..
+        }) => quote!($reader.ReadArray($(*length), $(decoder(items_type)))),
+        ValueType::Array(ArrayMeta { items_type, .. }) => {
+            quote!($reader.ReadList($(decoder(items_type))))
+        }
+        ValueType::Map(meta) => {
+            quote!($reader.ReadMap($(decoder(&meta.key_type)), $(decoder(&meta.value_type))))
+        }
+        ValueType::Range(meta) => quote!($reader.ReadRange($(decoder(&meta.bounds_type)))),
+        ValueType::Tuple(meta) => {
+            quote!(($(for item in meta.items_types.iter() join (, ) => $(gen_decode(item, reader, depth, qualifier)))))
+        }
+        _ => quote!($reader.$(format!("Read{}", primitive_fn(v_type)))()),
+    }
+}
+
+/// Suffix of the `PostcardWriter` and `PostcardReader` method of a primitive.
+fn primitive_fn(v_type: &ValueType) -> String {
+    match v_type {
+        ValueType::Number(NumberMeta::Integer { bytes, signed, .. }) => {
+            format!("{}{}", if *signed { "I" } else { "U" }, bytes * 8)
+        }
+        ValueType::Number(NumberMeta::FloatingPoint { bytes }) => format!("F{}", bytes * 8),
+        ValueType::Bool(_) => "Bool".to_owned(),
+        ValueType::String(_) => "String".to_owned(),
+        _ => unreachable!("not a primitive type"),
+    }
+}
+
+/// Whether the C# type is a struct, so an optional is a `Nullable<T>`.
+fn is_value_type(v_type: &ValueType) -> bool {
+    matches!(
+        v_type,
+        ValueType::Number(_) | ValueType::Bool(_) | ValueType::Tuple(_) | ValueType::Range(_)
+    )
+}
+
+/// The C# type, objects are prefixed with `qualifier`.
+fn cs_type(v_type: &ValueType, qualifier: &str) -> String {
+    match v_type {
+        ValueType::Number(NumberMeta::Integer { bytes, signed, .. }) => match (bytes, signed) {
+            (1, false) => "byte",
+            (1, true) => "sbyte",
+            (2, false) => "ushort",
+            (2, true) => "short",
+            (4, false) => "uint",
+            (4, true) => "int",
+            (8, false) => "ulong",
+            (8, true) => "long",
+            (_, false) => "UInt128",
+            (_, true) => "Int128",
+        }
+        .to_owned(),
+        ValueType::Number(NumberMeta::FloatingPoint { bytes: 4 }) => "float".to_owned(),
+        ValueType::Number(NumberMeta::FloatingPoint { .. }) => "double".to_owned(),
+        ValueType::Bool(_) => "bool".to_owned(),
+        ValueType::String(_) => "string".to_owned(),
+        ValueType::Object(meta) => format!("{qualifier}{}", meta.name),
+        ValueType::Optional(meta) => {
+            assert!(
+                !matches!(*meta.inner, ValueType::Optional(_)),
+                "nested optionals are not supported by the C# bindings"
+            );
+            format!("{}?", cs_type(&meta.inner, qualifier))
+        }
+        ValueType::Range(meta) => {
+            format!("PostcardRange<{}>", cs_type(&meta.bounds_type, qualifier))
+        }
+        ValueType::Array(ArrayMeta {
+            items_type,
+            length: Some(_),
+            ..
+        }) => format!("{}[]", cs_type(items_type, qualifier)),
+        ValueType::Array(meta) => format!("List<{}>", cs_type(&meta.items_type, qualifier)),
+        ValueType::Set(_) => unreachable!("sets are replaced by sequences"),
+        ValueType::Unit(_) => panic!("`()` is only supported as field by the C# bindings"),
+        ValueType::Map(meta) => format!(
+            "Dictionary<{}, {}>",
+            cs_type(&meta.key_type, qualifier),
+            cs_type(&meta.value_type, qualifier)
+        ),
+        ValueType::Tuple(meta) => {
+            assert!(
+                meta.items_types.len() >= 2,
+                "tuples with less than two items are not supported by the C# bindings"
+            );
+            format!(
+                "({})",
+                meta.items_types
+                    .iter()
+                    .map(|item| cs_type(item, qualifier))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }
+    }
+}
+
+/// Escapes a property or nested type name, which must differ from the record members and
+/// the name of the `record` it is declared in.
+fn member_ident(name: &str, record: &str) -> String {
+    if name == record || RECORD_MEMBERS.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_owned()
+    }
+}
@@ -0,0 +1,328 @@
+use genco::quote;
+
+use super::Tokens;
+
+/// Types shared by the encode and decode functions.
+pub fn gen_runtime() -> Tokens {
+    quote!($(RUNTIME))
+}
+
+pub fn gen_ser_runtime() -> Tokens {
+    quote!($(SER_RUNTIME))
+}
+
+pub fn gen_des_runtime() -> Tokens {
+    quote!($(DES_RUNTIME))
+}
+
+const RUNTIME: &str = r#"public sealed class PostcardException : Exception
+{
+    public PostcardException(string message) : base(message) { }
+}
+
+/// <summary>rust <c>Range</c> and <c>RangeInclusive</c></summary>
+public readonly record struct PostcardRange<T>(T Start, T End);"#;
+
+const SER_RUNTIME: &str = r#"public interface IPostcardEncodable
+{
+    void Encode(PostcardWriter w);
+}
+
+public static partial class Postcard
+{
+    public static byte[] Serialize<T>(T value) where T : IPostcardEncodable
+    {
+        var w = new PostcardWriter();
+        value.Encode(w);
+        return w.ToArray();
+    }
+}
+
+public sealed class PostcardWriter
+{
+    private byte[] _buffer = new byte[64];
+    private int _length;
+
+    public ReadOnlySpan<byte> WrittenSpan => _buffer.AsSpan(0, _length);
+
+    public byte[] ToArray() => WrittenSpan.ToArray();
+
+    private Span<byte> Reserve(int n)
+    {
+        if (_buffer.Length - _length < n)
+        {
+            Array.Resize(ref _buffer, Math.Max(_buffer.Length * 2, _length + n));
+        }
+        var span = _buffer.AsSpan(_length, n);
+        _length += n;
+        return span;
+    }
+
+    public void Push(byte value) => Reserve(1)[0] = value;
+
+    public void WriteBytes(ReadOnlySpan<byte> bytes) => bytes.CopyTo(Reserve(bytes.Length));
+
+    public void WriteVarint(ulong value)
+    {
+        while (value >= 0x80)
+        {
+            Push((byte)(value | 0x80));
+            value >>= 7;
+        }
+        Push((byte)value);
+    }
+
+    public void WriteZigzag(long value) => WriteVarint((ulong)((value << 1) ^ (value >> 63)));
+
+    public void WriteLength(int n) => WriteVarint((ulong)n);
+
+    public void CheckLength(int actual, int expected)
+    {
+        if (actual != expected)
+        {
+            throw new PostcardException($"expected {expected} items, got {actual}");
+        }
+    }
+
+    public void WriteBool(bool value) => Push(value ? (byte)1 : (byte)0);
+    public void WriteU8(byte value) => Push(value);
+    public void WriteI8(sbyte value) => Push((byte)value);
+    public void WriteU16(ushort value) => WriteVarint(value);
+    public void WriteU32(uint value) => WriteVarint(value);
+    public void WriteU64(ulong value) => WriteVarint(value);
+    public void WriteI16(short value) => WriteZigzag(value);
+    public void WriteI32(int value) => WriteZigzag(value);
+    public void WriteI64(long value) => WriteZigzag(value);
+
+    public void WriteU128(UInt128 value)
+    {
+        while (value >= 0x80u)
+        {
+            Push((byte)((byte)value | 0x80));
+            value >>= 7;
+        }
+        Push((byte)value);
+    }
+
+    public void WriteI128(Int128 value) => WriteU128((UInt128)((value << 1) ^ (value >> 127)));
+
+    public void WriteF32(float value) => BinaryPrimitives.WriteSingleLittleEndian(Reserve(4), value);
+
+    public void WriteF64(double value) => BinaryPrimitives.WriteDoubleLittleEndian(Reserve(8), value);
+
+    public void WriteString(string value)
+    {
+        var bytes = Encoding.UTF8.GetBytes(value);
+        WriteLength(bytes.Length);
+        WriteBytes(bytes);
+    }
+}"#;
+
+const DES_RUNTIME: &str = r#"public interface IPostcardDecodable<TSelf> where TSelf : IPostcardDecodable<TSelf>
+{
+    static abstract TSelf Decode(ref PostcardReader r);
+}
+
+public static partial class Postcard
+{
+    public static T Deserialize<T>(ReadOnlySpan<byte> bytes) where T : IPostcardDecodable<T>
+    {
+        var r = new PostcardReader(bytes);
+        var value = T.Decode(ref r);
+        r.Finish();
+        return value;
+    }
+}
+
+public ref struct PostcardReader
+{
+    public delegate T Decoder<T>(ref PostcardReader r);
+
+    private static readonly UTF8Encoding StrictUtf8 = new(false, true);
+
+    private readonly ReadOnlySpan<byte> _bytes;
+    private int _index;
+
+    public PostcardReader(ReadOnlySpan<byte> bytes)
+    {
+        _bytes = bytes;
+        _index = 0;
+    }
+
+    public byte Pop()
+    {
+        if (_index >= _bytes.Length)
+        {
+            throw new PostcardException("unexpected end of input");
+        }
+        return _bytes[_index++];
+    }
+
+    private ReadOnlySpan<byte> Take(int n)
+    {
+        if (_bytes.Length - _index < n)
+        {
+            throw new PostcardException("unexpected end of input");
+        }
+        var span = _bytes.Slice(_index, n);
+        _index += n;
+        return span;
+    }
+
+    /// <summary>rejects varints longer than <c>maxBytes</c> and values exceeding 64 bits</summary>
+    public ulong ReadVarint(int maxBytes)
+    {
+        ulong value = 0;
+        for (var i = 0; i < maxBytes; i++)
+        {
+            var b = Pop();
+            if (i == 9 && b > 1)
+            {
+                throw new PostcardException("varint exceeds 64 bits");
+            }
+            value |= (ulong)(b & 0x7F) << (7 * i);
+            if ((b & 0x80) == 0)
+            {
+                return value;
+            }
+        }
+        throw new PostcardException($"varint exceeds {maxBytes} bytes");
+    }
+
+    public long ReadZigzag(int maxBytes)
+    {
+        var raw = ReadVarint(maxBytes);
+        return (long)(raw >> 1) ^ -(long)(raw & 1);
+    }
+
+    private ulong Unsigned(int maxBytes, ulong max)
+    {
+        var value = ReadVarint(maxBytes);
+        if (value > max)
+        {
+            throw new PostcardException($"{value} is out of range");
+        }
+        return value;
+    }
+
+    private long Signed(int maxBytes, long min, long max)
+    {
+        var value = ReadZigzag(maxBytes);
+        if (value < min || value > max)
+        {
+            throw new PostcardException($"{value} is out of range");
+        }
+        return value;
+    }
+
+    public int ReadLength() => (int)Unsigned(10, int.MaxValue);
+
+    public bool ReadBool() => Pop() switch
+    {
+        0 => false,
+        1 => true,
+        _ => throw new PostcardException("invalid bool"),
+    };
+
+    public byte ReadU8() => Pop();
+    public sbyte ReadI8() => (sbyte)Pop();
+    public ushort ReadU16() => (ushort)Unsigned(3, ushort.MaxValue);
+    public uint ReadU32() => (uint)Unsigned(5, uint.MaxValue);
+    public ulong ReadU64() => ReadVarint(10);
+    public short ReadI16() => (short)Signed(3, short.MinValue, short.MaxValue);
+    public int ReadI32() => (int)Signed(5, int.MinValue, int.MaxValue);
+    public long ReadI64() => ReadZigzag(10);
+
+    public UInt128 ReadU128()
+    {
+        var value = UInt128.Zero;
+        for (var i = 0; i < 19; i++)
+        {
+            var b = Pop();
+            if (i == 18 && b > 3)
+            {
+                throw new PostcardException("varint exceeds 128 bits");
+            }
+            value |= (UInt128)(b & 0x7F) << (7 * i);
+            if ((b & 0x80) == 0)
+            {
+                return value;
+            }
+        }
+        throw new PostcardException("varint exceeds 19 bytes");
+    }
+
+    public Int128 ReadI128()
+    {
+        var raw = ReadU128();
+        return (Int128)(raw >> 1) ^ -(Int128)(raw & UInt128.One);
+    }
+
+    public float ReadF32() => BinaryPrimitives.ReadSingleLittleEndian(Take(4));
+
+    public double ReadF64() => BinaryPrimitives.ReadDoubleLittleEndian(Take(8));
+
+    public string ReadString()
+    {
+        var bytes = Take(ReadLength());
+        try
+        {
+            return StrictUtf8.GetString(bytes);
+        }
+        catch (DecoderFallbackException)
+        {
+            throw new PostcardException("invalid utf-8");
+        }
+    }
+
+    public T? ReadOptionValue<T>(Decoder<T> item) where T : struct => ReadBool() ? item(ref this) : null;
+
+    public T? ReadOptionRef<T>(Decoder<T> item) where T : class => ReadBool() ? item(ref this) : null;
+
+    public List<T> ReadList<T>(Decoder<T> item)
+    {
+        var n = ReadLength();
+        var list = new List<T>(Math.Min(n, _bytes.Length - _index));
+        for (var i = 0; i < n; i++)
+        {
+            list.Add(item(ref this));
+        }
+        return list;
+    }
+
+    public T[] ReadArray<T>(int length, Decoder<T> item)
+    {
+        var array = new T[length];
+        for (var i = 0; i < length; i++)
+        {
+            array[i] = item(ref this);
+        }
+        return array;
+    }
+
+    public Dictionary<K, V> ReadMap<K, V>(Decoder<K> key, Decoder<V> value) where K : notnull
+    {
+        var n = ReadLength();
+        var map = new Dictionary<K, V>();
+        for (var i = 0; i < n; i++)
+        {
+            var k = key(ref this);
+            map[k] = value(ref this);
+        }
+        return map;
+    }
+
+    public PostcardRange<T> ReadRange<T>(Decoder<T> item)
+    {
+        var start = item(ref this);
+        return new PostcardRange<T>(start, item(ref this));
+    }
+
+    public void Finish()
+    {
+        if (_index != _bytes.Length)
+        {
+            throw new PostcardException($"{_bytes.Length - _index} trailing bytes");
+        }
+    }
+}"#;
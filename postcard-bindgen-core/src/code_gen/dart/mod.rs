@@ -0,0 +1,500 @@
+mod runtime;
+
+use core::borrow::Borrow;
+use std::collections::BTreeSet;
+
+use convert_case::{Case, Casing};
+use genco::{lang::dart::Dart, quote, quote_in};
+
+use runtime::{gen_des_runtime, gen_runtime, gen_ser_runtime};
+
+use super::backend::{generate_with, LanguageBackend};
+use crate::{
+    registry::{
+        BindingType, Container, ContainerCollection, EnumType, EnumVariantType, StructField,
+        StructType, TupleStructType,
+    },
+    type_info::{ArrayMeta, NumberMeta, ValueType},
+    Exports,
+};
+
+pub use super::settings::{GenerationSettings, PointerWidth};
+
+type Tokens = genco::lang::dart::Tokens;
+type ExportFile = crate::ExportFile<Dart>;
+
+/// Dart keywords and the members every generated class has, which can't be used as field
+/// names.
+const DART_RESERVED: &[&str] = &[
+    "assert",
+    "async",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "else",
+    "encode",
+    "enum",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "for",
+    "hashCode",
+    "if",
+    "in",
+    "is",
+    "new",
+    "noSuchMethod",
+    "null",
+    "required",
+    "rethrow",
+    "return",
+    "runtimeType",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "toString",
+    "true",
+    "try",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+/// Generates a Dart library, exported as `source`.
+///
+/// Structs become classes with camel case fields and enums sealed classes with a subclass
+/// per variant, named `<Enum><Variant>`. Encoding and decoding is done with
+///
+/// ```dart
+/// Uint8List serialize(PostcardEncodable value)
+/// T deserialize<T>(Uint8List bytes, T Function(PostcardReader r) decode)
+/// ```
+///
+/// e.g. `deserialize(bytes, Message.decode)`, where decoding throws a `PostcardException`
+/// if the input is invalid.
+///
+/// Integers up to 64 bits are an `int`, where `u64` values above `i64::MAX` are stored as
+/// their bit pattern, and 128 bit integers a `BigInt`. Tuples become records. All types
+/// share one library. Generating panics for nested optionals, which Dart can't tell apart.
+pub fn generate(
+    containers: ContainerCollection,
+    gen_settings: impl Borrow<GenerationSettings>,
+) -> Exports<Dart> {
+    let backend = DartBackend {
+        gen_settings: gen_settings.borrow(),
+        classes: Tokens::new(),
+    };
+    generate_with(backend, containers)
+}
+
+struct DartBackend<'a> {
+    gen_settings: &'a GenerationSettings,
+    classes: Tokens,
+}
+
+impl DartBackend<'_> {
+    fn push_class(&mut self, container: &Container) {
+        self.classes.line();
+        self.classes.append(gen_class(container, self.gen_settings));
+    }
+}
+
+impl LanguageBackend for DartBackend<'_> {
+    type Lang = Dart;
+
+    fn prepare(&mut self, containers: &mut ContainerCollection) {
+        containers.assert_no_internally_tagged_enums("Dart");
+        containers.flatten();
+        containers.resolve_pointer_width(self.gen_settings.pointer_width.bytes());
+        containers.sets_as_sequences();
+        containers.remove_unit_fields();
+
+        let mut names = BTreeSet::new();
+        for container in containers.all_containers() {
+            let mut class_names = vec![container.name.to_string()];
+            if let BindingType::Enum(enum_type) = &container.r#type {
+                class_names.extend(
+                    enum_type
+                        .variants
+                        .iter()
+                        .map(|variant| variant_class(&container.name, &variant.name)),
+                );
+            }
+            for class_name in class_names {
+                assert!(
+                    names.insert(class_name.clone()),
+                    "the Dart bindings hold all types in one library, but `{class_name}` is defined twice"
+                );
+            }
+        }
+    }
+
+    fn visit_struct(&mut self, container: &Container, _: &StructType) {
+        self.push_class(container);
+    }
+
+    fn visit_tuple_struct(&mut self, container: &Container, _: &TupleStructType) {
+        self.push_class(container);
+    }
+
+    fn visit_enum(&mut self, container: &Container, _: &EnumType) {
+        self.push_class(container);
+    }
+
+    fn visit_unit_struct(&mut self, container: &Container) {
+        self.push_class(container);
+    }
+
+    fn finish(self) -> Exports<Dart> {
+        let mut source = quote! {
+            import "dart:convert";
+            import "dart:typed_data";
+
+            $(gen_runtime())
+        };
+
+        if self.gen_settings.ser {
+            source.line();
+            source.append(gen_ser_runtime());
+        }
+
+        if self.gen_settings.des {
+            source.line();
+            source.append(gen_des_runtime());
+        }
+
+        source.append(self.classes);
+
+        Exports {
+            files: vec![ExportFile {
+                content_type: "source".to_owned(),
+                content: source,
+            }],
+        }
+    }
+}
+
+/// Fields of a struct or an enum variant.
+enum Fields<'a> {
+    Named(&'a [StructField]),
+    Unnamed(&'a [ValueType]),
+    Unit,
+}
+
+impl<'a> Fields<'a> {
+    fn members(&self) -> Vec<(String, &'a ValueType)> {
+        match self {
+            Fields::Named(fields) => fields
+                .iter()
+                .filter(|field| !field.skip)
+                .map(|field| (dart_ident(&field.name.to_case(Case::Camel)), &field.v_type))
+                .collect(),
+            Fields::Unnamed(fields) => fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| (format!("field{index}"), field))
+                .collect(),
+            Fields::Unit => Vec::new(),
+        }
+    }
+}
+
+impl<'a> From<&'a EnumVariantType> for Fields<'a> {
+    fn from(variant_type: &'a EnumVariantType) -> Self {
+        match variant_type {
+            EnumVariantType::Empty => Fields::Unit,
+            EnumVariantType::Tuple(fields) => Fields::Unnamed(fields),
+            EnumVariantType::NewType(fields) => Fields::Named(fields),
+        }
+    }
+}
+
+fn struct_fields(container: &Container) -> Option<Fields<'_>> {
+    match &container.r#type {
+        BindingType::Struct(struct_type) => Some(Fields::Named(&struct_type.fields)),
+        BindingType::TupleStruct(tuple_struct) => Some(Fields::Unnamed(&tuple_struct.fields)),
+        BindingType::UnitStruct(_) => Some(Fields::Unit),
+        BindingType::Enum(_) => None,
+    }
+}
+
+fn variant_class(enum_name: &str, variant_name: &str) -> String {
+    format!("{enum_name}{variant_name}")
+}
+
+fn gen_class(container: &Container, gen_settings: &GenerationSettings) -> Tokens {
+    let name = container.name.as_ref();
+    match &container.r#type {
+        BindingType::Enum(enum_type) => {
+            let implements = if gen_settings.ser {
+                " implements PostcardEncodable"
+            } else {
+                ""
+            };
+
+            let mut decode = Tokens::new();
+            if gen_settings.des {
+                let mut cases = Tokens::new();
+                for variant in enum_type.variants.iter() {
+                    let fields = Fields::from(&variant.inner_type);
+                    quote_in! { cases =>
+                        case $(variant.index):
+                            return $(gen_construct(&variant_class(name, &variant.name), &fields));
+                    }
+                    cases.push();
+                }
+                quote_in! { decode =>
+                    factory $name.decode(PostcardReader r) {
+                        final tag = r.u32();
+                        switch (tag) {
+                            $cases
+                            default:
+                                throw PostcardException($(format!("'unknown variant $tag of {name}'")));
+                        }
+                    }
+                }
+            }
+
+            let mut out = quote! {
+                sealed class $name$implements {
+                    const $name();
+                    $(if !decode.is_empty() {
+                        $['\n']
+                        $decode
+                    })
+                }
+            };
+            for variant in enum_type.variants.iter() {
+                let fields = Fields::from(&variant.inner_type);
+                let encode = gen_settings.ser.then(|| {
+                    quote! {
+                        @override
+                        void encode(PostcardWriter w) {
+                            w.u32($(variant.index));
+                            $(for (member, v_type) in fields.members() => $(gen_encode(v_type, quote!(this.$member), 0))$['\r'])
+                        }
+                    }
+                });
+                out.line();
+                out.append(gen_data_class(
+                    &variant_class(name, &variant.name),
+                    &format!(" extends {name}"),
+                    &fields,
+                    encode,
+                    None,
+                ));
+            }
+            out
+        }
+        _ => {
+            let fields = struct_fields(container).unwrap();
+            let implements = if gen_settings.ser {
+                " implements PostcardEncodable"
+            } else {
+                ""
+            };
+            let encode = gen_settings.ser.then(|| {
+                quote! {
+                    @override
+                    void encode(PostcardWriter w) {
+                        $(for (member, v_type) in fields.members() => $(gen_encode(v_type, quote!(this.$member), 0))$['\r'])
+                    }
+                }
+            });
+            let decode = gen_settings.des.then(|| {
+                quote!(factory $name.decode(PostcardReader r) => $(gen_construct(name, &fields));)
+            });
+            gen_data_class(name, implements, &fields, encode, decode)
+        }
+    }
+}
+
+/// A class holding `fields` with an `encode` method and a `decode` constructor.
+fn gen_data_class(
+    name: &str,
+    supertypes: &str,
+    fields: &Fields,
+    encode: Option<Tokens>,
+    decode: Option<Tokens>,
+) -> Tokens {
+    let members = fields.members();
+    let params = match fields {
+        Fields::Named(_) if !members.is_empty() => {
+            quote!({$(for (member, _) in members.iter() join (, ) => required this.$member)})
+        }
+        _ => quote!($(for (member, _) in members.iter() join (, ) => this.$member)),
+    };
+
+    let mut body = Tokens::new();
+    for (member, v_type) in members.iter() {
+        quote_in!(body => final $(dart_type(v_type)) $member;);
+        body.push();
+    }
+    if !members.is_empty() {
+        body.line();
+    }
+    quote_in!(body => const $name($params););
+    for method in encode.into_iter().chain(decode) {
+        body.line();
+        body.append(method);
+    }
+
+    quote! {
+        final class $name$supertypes {
+            $body
+        }
+    }
+}
+
+/// Encodes `value` as a statement, closures are nested with `depth`.
+fn gen_encode(v_type: &ValueType, value: Tokens, depth: usize) -> Tokens {
+    let item = format!("x{depth}");
+    match v_type {
+        ValueType::Object(_) => quote!($value.encode(w);),
+        ValueType::Optional(meta) => {
+            quote!(w.option($value, ($(&item)) { $(gen_encode(&meta.inner, quote!($(&item)), depth + 1)) });)
+        }
+        ValueType::Array(ArrayMeta {
+            items_type,
+            length: Some(length),
+            ..
+        }) => {
+            quote!(w.array($value, $(*length), ($(&item)) { $(gen_encode(items_type, quote!($(&item)), depth + 1)) });)
+        }
+        ValueType::Array(ArrayMeta { items_type, .. }) => {
+            quote!(w.list($value, ($(&item)) { $(gen_encode(items_type, quote!($(&item)), depth + 1)) });)
+        }
+        ValueType::Map(meta) => {
+            let key = format!("k{depth}");
+            quote!(w.map($value, ($(&key)) { $(gen_encode(&meta.key_type, quote!($(&key)), depth + 1)) }, ($(&item)) { $(gen_encode(&meta.value_type, quote!($(&item)), depth + 1)) });)
+        }
+        ValueType::Range(meta) => {
+            quote!(w.range($value, ($(&item)) { $(gen_encode(&meta.bounds_type, quote!($(&item)), depth + 1)) });)
+        }
+        ValueType::Tuple(meta) => {
+            let items = meta
+                .items_types
+                .iter()
+                .enumerate()
+                .map(|(index, item_type)| {
+                    gen_encode(
+                        item_type,
+                        quote!($(value.clone()).$(format!("${}", index + 1))),
+                        depth,
+                    )
+                })
+                .collect::<Vec<_>>();
+            quote!($(for item in items join ( ) => $item))
+        }
+        _ => quote!(w.$(primitive_fn(v_type))($value);),
+    }
+}
+
+/// Constructs the class `name` from the decoded `fields`.
+fn gen_construct(name: &str, fields: &Fields) -> Tokens {
+    let members = fields.members();
+    match fields {
+        Fields::Named(_) => {
+            quote!($name($(for (member, v_type) in members join (, ) => $member: $(gen_decode(v_type)))))
+        }
+        Fields::Unnamed(_) => {
+            quote!($name($(for (_, v_type) in members join (, ) => $(gen_decode(v_type)))))
+        }
+        Fields::Unit => quote!(const $name()),
+    }
+}
+
+fn gen_decode(v_type: &ValueType) -> Tokens {
+    match v_type {
+        ValueType::Object(meta) => quote!($(&meta.name).decode(r)),
+        ValueType::Optional(meta) => quote!(r.option(() => $(gen_decode(&meta.inner)))),
+        ValueType::Array(ArrayMeta {
+            items_type,
+            length: Some(length),
+            ..
+        }) => quote!(r.array($(*length), () => $(gen_decode(items_type)))),
+        ValueType::Array(ArrayMeta { items_type, .. }) => {
+            quote!(r.list(() => $(gen_decode(items_type))))
+        }
+        ValueType::Map(meta) => {
+            quote!(r.map(() => $(gen_decode(&meta.key_type)), () => $(gen_decode(&meta.value_type))))
+        }
+        ValueType::Range(meta) => quote!(r.range(() => $(gen_decode(&meta.bounds_type)))),
+        ValueType::Tuple(meta) if meta.items_types.len() == 1 => {
+            quote!(($(gen_decode(&meta.items_types[0])),))
+        }
+        ValueType::Tuple(meta) => {
+            quote!(($(for item in meta.items_types.iter() join (, ) => $(gen_decode(item)))))
+        }
+        _ => quote!(r.$(primitive_fn(v_type))()),
+    }
+}
+
+/// Name of the `PostcardWriter` and `PostcardReader` method of a primitive.
+fn primitive_fn(v_type: &ValueType) -> String {
+    match v_type {
+        ValueType::Number(NumberMeta::Integer { bytes, signed, .. }) => {
+            format!("{}{}", if *signed { "i" } else { "u" }, bytes * 8)
+        }
+        ValueType::Number(NumberMeta::FloatingPoint { bytes }) => format!("f{}", bytes * 8),
+        ValueType::Bool(_) => "boolean".to_owned(),
+        ValueType::String(_) => "string".to_owned(),
+        _ => unreachable!("not a primitive type"),
+    }
+}
+
+fn dart_type(v_type: &ValueType) -> String {
+    match v_type {
+        ValueType::Number(NumberMeta::Integer { bytes: 16, .. }) => "BigInt".to_owned(),
+        ValueType::Number(NumberMeta::Integer { .. }) => "int".to_owned(),
+        ValueType::Number(NumberMeta::FloatingPoint { .. }) => "double".to_owned(),
+        ValueType::Bool(_) => "bool".to_owned(),
+        ValueType::String(_) => "String".to_owned(),
+        ValueType::Object(meta) => meta.name.to_string(),
+        ValueType::Optional(meta) => {
+            assert!(
+                !matches!(*meta.inner, ValueType::Optional(_)),
+                "nested optionals are not supported by the Dart bindings"
+            );
+            format!("{}?", dart_type(&meta.inner))
+        }
+        ValueType::Range(meta) => format!("PostcardRange<{}>", dart_type(&meta.bounds_type)),
+        ValueType::Array(meta) => format!("List<{}>", dart_type(&meta.items_type)),
+        ValueType::Set(_) => unreachable!("sets are replaced by sequences"),
+        ValueType::Unit(_) => panic!("`()` is only supported as field by the Dart bindings"),
+        ValueType::Map(meta) => format!(
+            "Map<{}, {}>",
+            dart_type(&meta.key_type),
+            dart_type(&meta.value_type)
+        ),
+        ValueType::Tuple(meta) if meta.items_types.len() == 1 => {
+            format!("({},)", dart_type(&meta.items_types[0]))
+        }
+        ValueType::Tuple(meta) => format!(
+            "({})",
+            meta.items_types
+                .iter()
+                .map(dart_type)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn dart_ident(name: &str) -> String {
+    if DART_RESERVED.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_owned()
+    }
+}
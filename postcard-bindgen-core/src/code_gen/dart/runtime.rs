@@ -0,0 +1,311 @@
+use genco::quote;
+
+use super::Tokens;
+
+/// Types shared by the encode and decode functions.
+pub fn gen_runtime() -> Tokens {
+    quote!($(RUNTIME))
+}
+
+pub fn gen_ser_runtime() -> Tokens {
+    quote!($(SER_RUNTIME))
+}
+
+pub fn gen_des_runtime() -> Tokens {
+    quote!($(DES_RUNTIME))
+}
+
+const RUNTIME: &str = r#"class PostcardException implements Exception {
+    final String message;
+
+    const PostcardException(this.message);
+
+    @override
+    String toString() => 'PostcardException: $message';
+}
+
+/// rust `Range` and `RangeInclusive`
+class PostcardRange<T> {
+    final T start;
+    final T end;
+
+    const PostcardRange(this.start, this.end);
+}"#;
+
+const SER_RUNTIME: &str = r#"abstract interface class PostcardEncodable {
+    void encode(PostcardWriter w);
+}
+
+Uint8List serialize(PostcardEncodable value) {
+    final w = PostcardWriter();
+    value.encode(w);
+    return w.finish();
+}
+
+class PostcardWriter {
+    final BytesBuilder _bytes = BytesBuilder(copy: false);
+
+    void push(int byte) => _bytes.addByte(byte);
+
+    /// `value` is taken as the bit pattern of an unsigned 64 bit integer
+    void varint(int value) {
+        var v = value;
+        while ((v & ~0x7F) != 0) {
+            push((v & 0x7F) | 0x80);
+            v = v >>> 7;
+        }
+        push(v);
+    }
+
+    void zigzag(int value) => varint((value << 1) ^ (value >> 63));
+
+    void len(int n) => varint(n);
+
+    int _checked(int v, int min, int max) {
+        if (v < min || v > max) {
+            throw PostcardException('$v is out of range');
+        }
+        return v;
+    }
+
+    void boolean(bool v) => push(v ? 1 : 0);
+    void u8(int v) => push(_checked(v, 0, 0xFF));
+    void i8(int v) => push(_checked(v, -0x80, 0x7F) & 0xFF);
+    void u16(int v) => varint(_checked(v, 0, 0xFFFF));
+    void u32(int v) => varint(_checked(v, 0, 0xFFFFFFFF));
+    void u64(int v) => varint(v);
+    void i16(int v) => zigzag(_checked(v, -0x8000, 0x7FFF));
+    void i32(int v) => zigzag(_checked(v, -0x80000000, 0x7FFFFFFF));
+    void i64(int v) => zigzag(v);
+
+    void u128(BigInt v) {
+        if (v.isNegative || v.bitLength > 128) {
+            throw PostcardException('$v is out of range of u128');
+        }
+        var rest = v;
+        while (rest >= _byteLimit) {
+            push((rest & _lowBits).toInt() | 0x80);
+            rest = rest >> 7;
+        }
+        push(rest.toInt());
+    }
+
+    void i128(BigInt v) {
+        if (v.bitLength > 127) {
+            throw PostcardException('$v is out of range of i128');
+        }
+        u128(v.isNegative ? ((-v) << 1) - BigInt.one : v << 1);
+    }
+
+    void f32(double v) {
+        _bytes.add((ByteData(4)..setFloat32(0, v, Endian.little)).buffer.asUint8List());
+    }
+
+    void f64(double v) {
+        _bytes.add((ByteData(8)..setFloat64(0, v, Endian.little)).buffer.asUint8List());
+    }
+
+    void string(String v) {
+        final utf8Bytes = utf8.encode(v);
+        len(utf8Bytes.length);
+        _bytes.add(utf8Bytes);
+    }
+
+    void option<T>(T? v, void Function(T) item) {
+        if (v == null) {
+            push(0);
+        } else {
+            push(1);
+            item(v);
+        }
+    }
+
+    void list<T>(List<T> v, void Function(T) item) {
+        len(v.length);
+        v.forEach(item);
+    }
+
+    void array<T>(List<T> v, int length, void Function(T) item) {
+        if (v.length != length) {
+            throw PostcardException('expected $length items, got ${v.length}');
+        }
+        v.forEach(item);
+    }
+
+    void map<K, V>(Map<K, V> v, void Function(K) key, void Function(V) value) {
+        len(v.length);
+        v.forEach((k, x) {
+            key(k);
+            value(x);
+        });
+    }
+
+    void range<T>(PostcardRange<T> v, void Function(T) item) {
+        item(v.start);
+        item(v.end);
+    }
+
+    Uint8List finish() => _bytes.takeBytes();
+
+    static final BigInt _byteLimit = BigInt.from(0x80);
+    static final BigInt _lowBits = BigInt.from(0x7F);
+}"#;
+
+const DES_RUNTIME: &str = r#"T deserialize<T>(Uint8List bytes, T Function(PostcardReader r) decode) {
+    final r = PostcardReader(bytes);
+    final value = decode(r);
+    r.finish();
+    return value;
+}
+
+class PostcardReader {
+    final Uint8List _bytes;
+    int _index = 0;
+
+    PostcardReader(this._bytes);
+
+    int pop() {
+        if (_index >= _bytes.length) {
+            throw const PostcardException('unexpected end of input');
+        }
+        return _bytes[_index++];
+    }
+
+    Uint8List _take(int n) {
+        if (_bytes.length - _index < n) {
+            throw const PostcardException('unexpected end of input');
+        }
+        final out = Uint8List.sublistView(_bytes, _index, _index + n);
+        _index += n;
+        return out;
+    }
+
+    /// rejects varints longer than `maxBytes` and values exceeding 64 bits, unsigned 64 bit
+    /// values are returned as their bit pattern
+    int varint(int maxBytes) {
+        var out = 0;
+        for (var i = 0; i < maxBytes; i++) {
+            final byte = pop();
+            if (i == 9 && byte > 1) {
+                throw const PostcardException('varint exceeds 64 bits');
+            }
+            out |= (byte & 0x7F) << (7 * i);
+            if ((byte & 0x80) == 0) {
+                return out;
+            }
+        }
+        throw PostcardException('varint exceeds $maxBytes bytes');
+    }
+
+    int zigzag(int maxBytes) {
+        final raw = varint(maxBytes);
+        return (raw >>> 1) ^ -(raw & 1);
+    }
+
+    int _unsigned(int maxBytes, int max) {
+        final v = varint(maxBytes);
+        if (v < 0 || v > max) {
+            throw PostcardException('$v is out of range');
+        }
+        return v;
+    }
+
+    int _signed(int maxBytes, int min, int max) {
+        final v = zigzag(maxBytes);
+        if (v < min || v > max) {
+            throw PostcardException('$v is out of range');
+        }
+        return v;
+    }
+
+    int len() {
+        final v = varint(10);
+        if (v < 0) {
+            throw const PostcardException('length exceeds 63 bits');
+        }
+        return v;
+    }
+
+    bool boolean() {
+        switch (pop()) {
+            case 0:
+                return false;
+            case 1:
+                return true;
+            default:
+                throw const PostcardException('invalid bool');
+        }
+    }
+
+    int u8() => pop();
+    int i8() => pop().toSigned(8);
+    int u16() => _unsigned(3, 0xFFFF);
+    int u32() => _unsigned(5, 0xFFFFFFFF);
+    int u64() => varint(10);
+    int i16() => _signed(3, -0x8000, 0x7FFF);
+    int i32() => _signed(5, -0x80000000, 0x7FFFFFFF);
+    int i64() => zigzag(10);
+
+    BigInt u128() {
+        var out = BigInt.zero;
+        for (var i = 0; i < 19; i++) {
+            final byte = pop();
+            out |= BigInt.from(byte & 0x7F) << (7 * i);
+            if ((byte & 0x80) == 0) {
+                if (out.bitLength > 128) {
+                    throw const PostcardException('varint exceeds 128 bits');
+                }
+                return out;
+            }
+        }
+        throw const PostcardException('varint exceeds 19 bytes');
+    }
+
+    BigInt i128() {
+        final raw = u128();
+        return raw.isOdd ? -(raw >> 1) - BigInt.one : raw >> 1;
+    }
+
+    double f32() => ByteData.sublistView(_take(4)).getFloat32(0, Endian.little);
+
+    double f64() => ByteData.sublistView(_take(8)).getFloat64(0, Endian.little);
+
+    String string() {
+        final n = len();
+        try {
+            return utf8.decode(_take(n));
+        } on FormatException {
+            throw const PostcardException('invalid utf-8');
+        }
+    }
+
+    T? option<T>(T Function() item) => boolean() ? item() : null;
+
+    List<T> list<T>(T Function() item) {
+        final n = len();
+        return [for (var i = 0; i < n; i++) item()];
+    }
+
+    List<T> array<T>(int length, T Function() item) => [for (var i = 0; i < length; i++) item()];
+
+    Map<K, V> map<K, V>(K Function() key, V Function() value) {
+        final n = len();
+        final out = <K, V>{};
+        for (var i = 0; i < n; i++) {
+            final k = key();
+            out[k] = value();
+        }
+        return out;
+    }
+
+    PostcardRange<T> range<T>(T Function() item) {
+        final start = item();
+        return PostcardRange(start, item());
+    }
+
+    void finish() {
+        if (_index != _bytes.length) {
+            throw PostcardException('${_bytes.length - _index} trailing bytes');
+        }
+    }
+}"#;
@@ -0,0 +1,471 @@
+mod runtime;
+
+use core::borrow::Borrow;
+use std::collections::BTreeSet;
+
+use convert_case::{Case, Casing};
+use genco::{lang::go::Go, quote, quote_in};
+
+use runtime::{gen_des_runtime, gen_runtime, gen_ser_runtime};
+
+use super::backend::{generate_with, LanguageBackend};
+use crate::{
+    registry::{
+        BindingType, Container, ContainerCollection, EnumType, EnumVariantType, StructField,
+        StructType, TupleStructType,
+    },
+    type_info::{ArrayMeta, NumberMeta, ValueType},
+    Exports,
+};
+
+pub use super::settings::{GenerationSettings, PointerWidth};
+
+type Tokens = genco::lang::go::Tokens;
+type ExportFile = crate::ExportFile<Go>;
+
+/// Generates a Go source file, exported as `source`.
+///
+/// The file declares the package `package_name`, lowercased without separators. Structs
+/// become structs with exported pascal case fields and enums interfaces, which are
+/// implemented by a struct per variant, named `<Enum><Variant>`. Every type gets the
+/// functions
+///
+/// ```go
+/// func EncodeType(v Type) ([]byte, error)
+/// func DecodeType(data []byte) (Type, error)
+/// ```
+///
+/// where decoding fails if the input is invalid.
+///
+/// The code needs Go 1.21. Optionals become pointers, 128 bit integers a `*big.Int`, maps
+/// a `map`, which is encoded in random order, and tuples anonymous structs with the fields
+/// `F0`, `F1`, ...
+pub fn generate(
+    containers: ContainerCollection,
+    gen_settings: impl Borrow<GenerationSettings>,
+    package_name: String,
+) -> Exports<Go> {
+    let backend = GoBackend {
+        gen_settings: gen_settings.borrow(),
+        package_name: package_name.to_case(Case::Flat),
+        types: Tokens::new(),
+    };
+    generate_with(backend, containers)
+}
+
+struct GoBackend<'a> {
+    gen_settings: &'a GenerationSettings,
+    package_name: String,
+    types: Tokens,
+}
+
+impl GoBackend<'_> {
+    fn push_type(&mut self, container: &Container) {
+        self.types.line();
+        self.types.append(gen_type(container));
+        if self.gen_settings.ser {
+            self.types.line();
+            self.types.append(gen_encode_fns(container));
+        }
+        if self.gen_settings.des {
+            self.types.line();
+            self.types.append(gen_decode_fns(container));
+        }
+    }
+}
+
+impl LanguageBackend for GoBackend<'_> {
+    type Lang = Go;
+
+    fn prepare(&mut self, containers: &mut ContainerCollection) {
+        containers.assert_no_internally_tagged_enums("Go");
+        containers.flatten();
+        containers.resolve_pointer_width(self.gen_settings.pointer_width.bytes());
+        containers.sets_as_sequences();
+        containers.remove_unit_fields();
+
+        let mut names = BTreeSet::new();
+        for container in containers.all_containers() {
+            let mut type_names = vec![container.name.to_string()];
+            if let BindingType::Enum(enum_type) = &container.r#type {
+                type_names.extend(
+                    enum_type
+                        .variants
+                        .iter()
+                        .map(|variant| variant_struct(&container.name, &variant.name)),
+                );
+            }
+            for type_name in type_names {
+                assert!(
+                    names.insert(type_name.clone()),
+                    "the Go bindings hold all types in one package, but `{type_name}` is defined twice"
+                );
+            }
+        }
+    }
+
+    fn visit_struct(&mut self, container: &Container, _: &StructType) {
+        self.push_type(container);
+    }
+
+    fn visit_tuple_struct(&mut self, container: &Container, _: &TupleStructType) {
+        self.push_type(container);
+    }
+
+    fn visit_enum(&mut self, container: &Container, _: &EnumType) {
+        self.push_type(container);
+    }
+
+    fn visit_unit_struct(&mut self, container: &Container) {
+        self.push_type(container);
+    }
+
+    fn finish(self) -> Exports<Go> {
+        let gen_settings = self.gen_settings;
+
+        let mut imports = BTreeSet::new();
+        if gen_settings.ser {
+            imports.extend(["fmt", "math", "math/big"]);
+        }
+        if gen_settings.des {
+            imports.extend(["errors", "fmt", "math", "math/big", "unicode/utf8"]);
+        }
+
+        let mut source = quote! {
+            package $(&self.package_name)
+            $(if !imports.is_empty() {
+                $['\n']
+                import (
+                    $(for import in imports => $(quoted(import))$['\r'])
+                )
+            })
+
+            $(gen_runtime())
+        };
+
+        if gen_settings.ser {
+            source.line();
+            source.append(gen_ser_runtime());
+        }
+
+        if gen_settings.des {
+            source.line();
+            source.append(gen_des_runtime());
+        }
+
+        source.append(self.types);
+
+        Exports {
+            files: vec![ExportFile {
+                content_type: "source".to_owned(),
+                content: source,
+            }],
+        }
+    }
+}
+
+/// Fields of a struct or an enum variant.
+enum Fields<'a> {
+    Named(&'a [StructField]),
+    Unnamed(&'a [ValueType]),
+    Unit,
+}
+
+impl<'a> Fields<'a> {
+    fn members(&self) -> Vec<(String, &'a ValueType)> {
+        match self {
+            Fields::Named(fields) => fields
+                .iter()
+                .filter(|field| !field.skip)
+                .map(|field| (field.name.to_case(Case::Pascal), &field.v_type))
+                .collect(),
+            Fields::Unnamed(fields) => fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| (format!("Field{index}"), field))
+                .collect(),
+            Fields::Unit => Vec::new(),
+        }
+    }
+}
+
+impl<'a> From<&'a EnumVariantType> for Fields<'a> {
+    fn from(variant_type: &'a EnumVariantType) -> Self {
+        match variant_type {
+            EnumVariantType::Empty => Fields::Unit,
+            EnumVariantType::Tuple(fields) => Fields::Unnamed(fields),
+            EnumVariantType::NewType(fields) => Fields::Named(fields),
+        }
+    }
+}
+
+fn struct_fields(container: &Container) -> Option<Fields<'_>> {
+    match &container.r#type {
+        BindingType::Struct(struct_type) => Some(Fields::Named(&struct_type.fields)),
+        BindingType::TupleStruct(tuple_struct) => Some(Fields::Unnamed(&tuple_struct.fields)),
+        BindingType::UnitStruct(_) => Some(Fields::Unit),
+        BindingType::Enum(_) => None,
+    }
+}
+
+fn variant_struct(enum_name: &str, variant_name: &str) -> String {
+    format!("{enum_name}{variant_name}")
+}
+
+fn gen_struct(name: &str, fields: &Fields) -> Tokens {
+    let members = fields.members();
+    if members.is_empty() {
+        return quote!(type $name struct{});
+    }
+    quote! {
+        type $name struct {
+            $(for (member, v_type) in members => $member $(go_type(v_type))$['\r'])
+        }
+    }
+}
+
+fn gen_type(container: &Container) -> Tokens {
+    let name = container.name.as_ref();
+    match &container.r#type {
+        BindingType::Enum(enum_type) => {
+            let marker = format!("is{name}");
+            let mut out = quote! {
+                type $name interface {
+                    $(&marker)()
+                }
+            };
+            for variant in enum_type.variants.iter() {
+                let variant_name = variant_struct(name, &variant.name);
+                out.line();
+                out.append(gen_struct(
+                    &variant_name,
+                    &Fields::from(&variant.inner_type),
+                ));
+                out.line();
+                quote_in!(out => func ($(&variant_name)) $(&marker)() {});
+            }
+            out
+        }
+        _ => gen_struct(name, &struct_fields(container).unwrap()),
+    }
+}
+
+fn gen_encode_fns(container: &Container) -> Tokens {
+    let name = container.name.as_ref();
+    let mut body = Tokens::new();
+    match &container.r#type {
+        BindingType::Enum(enum_type) => {
+            let mut cases = Tokens::new();
+            for variant in enum_type.variants.iter() {
+                let members = Fields::from(&variant.inner_type).members();
+                quote_in! { cases =>
+                    case $(variant_struct(name, &variant.name)):
+                        w.U32($(variant.index))
+                        $(for (member, v_type) in members => $(gen_encode(v_type, quote!(v.$member), 0))$['\r'])
+                }
+                cases.push();
+            }
+            quote_in! { body =>
+                switch v := v.(type) {
+                $cases
+                default:
+                    w.fail(fmt.Errorf($(quoted(format!("unknown variant %T of {name}"))), v))
+                }
+            }
+        }
+        _ => {
+            for (member, v_type) in struct_fields(container).unwrap().members() {
+                body.append(gen_encode(v_type, quote!(v.$member), 0));
+                body.push();
+            }
+        }
+    }
+
+    let encode_fn = format!("encode{name}");
+    let public_fn = format!("Encode{name}");
+    quote! {
+        func $(&encode_fn)(w *Writer, v $name) {
+            $body
+        }
+
+        $(format!("// {public_fn} encodes v in the postcard format."))
+        func $(&public_fn)(v $name) ([]byte, error) {
+            return encode(v, $(&encode_fn))
+        }
+    }
+}
+
+/// Encodes `value` as statements, loop variables are numbered with `depth`.
+fn gen_encode(v_type: &ValueType, value: Tokens, depth: usize) -> Tokens {
+    let item = format!("x{depth}");
+    match v_type {
+        ValueType::Object(meta) => quote!($(format!("encode{}", meta.name))(w, $value)),
+        ValueType::Optional(meta) => {
+            quote!(if $(value.clone()) == nil { w.U8(0) } else { w.U8(1); $(gen_encode(&meta.inner, quote!((*$value)), depth)) })
+        }
+        ValueType::Array(ArrayMeta {
+            items_type,
+            length: Some(_),
+            ..
+        }) => {
+            quote!(for _, $(&item) := range $value { $(gen_encode(items_type, quote!($(&item)), depth + 1)) })
+        }
+        ValueType::Array(ArrayMeta { items_type, .. }) => {
+            quote!(w.Len(len($(value.clone()))); for _, $(&item) := range $value { $(gen_encode(items_type, quote!($(&item)), depth + 1)) })
+        }
+        ValueType::Map(meta) => {
+            let key = format!("k{depth}");
+            quote!(w.Len(len($(value.clone()))); for $(&key), $(&item) := range $value { $(gen_encode(&meta.key_type, quote!($(&key)), depth + 1)); $(gen_encode(&meta.value_type, quote!($(&item)), depth + 1)) })
+        }
+        ValueType::Range(meta) => {
+            quote!($(gen_encode(&meta.bounds_type, quote!($(value.clone()).Start), depth)); $(gen_encode(&meta.bounds_type, quote!($value.End), depth)))
+        }
+        ValueType::Tuple(meta) => {
+            let items = meta
+                .items_types
+                .iter()
+                .enumerate()
+                .map(|(index, item_type)| {
+                    gen_encode(
+                        item_type,
+                        quote!($(value.clone()).$(format!("F{index}"))),
+                        depth,
+                    )
+                })
+                .collect::<Vec<_>>();
+            quote!($(for item in items join (; ) => $item))
+        }
+        _ => quote!(w.$(primitive_fn(v_type))($value)),
+    }
+}
+
+fn gen_decode_fns(container: &Container) -> Tokens {
+    let name = container.name.as_ref();
+    let body = match &container.r#type {
+        BindingType::Enum(enum_type) => {
+            let mut cases = Tokens::new();
+            for variant in enum_type.variants.iter() {
+                let fields = Fields::from(&variant.inner_type);
+                quote_in! { cases =>
+                    case $(variant.index):
+                        return $(gen_construct(&variant_struct(name, &variant.name), &fields))
+                }
+                cases.push();
+            }
+            quote! {
+                switch tag := r.U32(); tag {
+                $cases
+                default:
+                    r.fail(fmt.Errorf($(quoted(format!("unknown variant %d of {name}"))), tag))
+                    return nil
+                }
+            }
+        }
+        _ => quote!(return $(gen_construct(name, &struct_fields(container).unwrap()))),
+    };
+
+    let decode_fn = format!("decode{name}");
+    let public_fn = format!("Decode{name}");
+    quote! {
+        func $(&decode_fn)(r *Reader) $name {
+            $body
+        }
+
+        $(format!("// {public_fn} decodes data, which must not contain trailing bytes."))
+        func $(&public_fn)(data []byte) ($name, error) {
+            return decode(data, $(&decode_fn))
+        }
+    }
+}
+
+/// A composite literal of the struct `name` with the decoded `fields`.
+fn gen_construct(name: &str, fields: &Fields) -> Tokens {
+    quote!($name{$(for (member, v_type) in fields.members() join (, ) => $member: $(gen_decode(v_type)))})
+}
+
+fn gen_decode(v_type: &ValueType) -> Tokens {
+    let decoder =
+        |v_type: &ValueType| quote!(func() $(go_type(v_type)) { return $(gen_decode(v_type)) });
+    match v_type {
+        ValueType::Object(meta) => quote!($(format!("decode{}", meta.name))(r)),
+        ValueType::Optional(meta) => quote!(readOption(r, $(decoder(&meta.inner)))),
+        ValueType::Array(ArrayMeta {
+            items_type,
+            length: Some(length),
+            ..
+        }) => {
+            quote!(func() (a [$(*length)]$(go_type(items_type))) { for i := range a { a[i] = $(gen_decode(items_type)) }; return }())
+        }
+        ValueType::Array(ArrayMeta { items_type, .. }) => {
+            quote!(readList(r, $(decoder(items_type))))
+        }
+        ValueType::Map(meta) => {
+            quote!(readMap(r, $(decoder(&meta.key_type)), $(decoder(&meta.value_type))))
+        }
+        ValueType::Range(meta) => {
+            quote!($(go_type(v_type)){Start: $(gen_decode(&meta.bounds_type)), End: $(gen_decode(&meta.bounds_type))})
+        }
+        ValueType::Tuple(meta) => {
+            let items =
+                meta.items_types.iter().enumerate().map(
+                    |(index, item_type)| quote!($(format!("F{index}")): $(gen_decode(item_type))),
+                );
+            quote!($(go_type(v_type)){$(for item in items join (, ) => $item)})
+        }
+        _ => quote!(r.$(primitive_fn(v_type))()),
+    }
+}
+
+/// Name of the `Writer` and `Reader` method of a primitive.
+fn primitive_fn(v_type: &ValueType) -> String {
+    match v_type {
+        ValueType::Number(NumberMeta::Integer { bytes, signed, .. }) => {
+            format!("{}{}", if *signed { "I" } else { "U" }, bytes * 8)
+        }
+        ValueType::Number(NumberMeta::FloatingPoint { bytes }) => format!("F{}", bytes * 8),
+        ValueType::Bool(_) => "Bool".to_owned(),
+        ValueType::String(_) => "Str".to_owned(),
+        _ => unreachable!("not a primitive type"),
+    }
+}
+
+fn go_type(v_type: &ValueType) -> String {
+    match v_type {
+        ValueType::Number(NumberMeta::Integer { bytes: 16, .. }) => "*big.Int".to_owned(),
+        ValueType::Number(NumberMeta::Integer { bytes, signed, .. }) => {
+            format!("{}int{}", if *signed { "" } else { "u" }, bytes * 8)
+        }
+        ValueType::Number(NumberMeta::FloatingPoint { bytes }) => format!("float{}", bytes * 8),
+        ValueType::Bool(_) => "bool".to_owned(),
+        ValueType::String(_) => "string".to_owned(),
+        ValueType::Object(meta) => meta.name.to_string(),
+        ValueType::Optional(meta) => format!("*{}", go_type(&meta.inner)),
+        ValueType::Range(meta) => format!("PostcardRange[{}]", go_type(&meta.bounds_type)),
+        ValueType::Array(ArrayMeta {
+            items_type,
+            length: Some(length),
+            ..
+        }) => format!("[{length}]{}", go_type(items_type)),
+        ValueType::Array(meta) => format!("[]{}", go_type(&meta.items_type)),
+        ValueType::Set(_) => unreachable!("sets are replaced by sequences"),
+        ValueType::Unit(_) => panic!("`()` is only supported as field by the Go bindings"),
+        ValueType::Map(meta) => format!(
+            "map[{}]{}",
+            go_type(&meta.key_type),
+            go_type(&meta.value_type)
+        ),
+        ValueType::Tuple(meta) => format!(
+            "struct{{ {} }}",
+            meta.items_types
+                .iter()
+                .enumerate()
+                .map(|(index, item)| format!("F{index} {}", go_type(item)))
+                .collect::<Vec<_>>()
+                .join("; ")
+        ),
+    }
+}
+
+fn quoted(value: impl AsRef<str>) -> String {
+    format!("\"{}\"", value.as_ref())
+}
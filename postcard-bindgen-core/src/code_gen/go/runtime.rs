@@ -0,0 +1,323 @@
+use genco::quote;
+
+use super::Tokens;
+
+/// Types shared by the encode and decode functions.
+pub fn gen_runtime() -> Tokens {
+    quote!($(RUNTIME))
+}
+
+pub fn gen_ser_runtime() -> Tokens {
+    quote!($(SER_RUNTIME))
+}
+
+pub fn gen_des_runtime() -> Tokens {
+    quote!($(DES_RUNTIME))
+}
+
+const RUNTIME: &str = r#"// PostcardRange is a rust `Range` or `RangeInclusive`.
+type PostcardRange[T any] struct {
+    Start T
+    End   T
+}"#;
+
+const SER_RUNTIME: &str = r#"// Writer encodes values, the first error is kept and returned by finish.
+type Writer struct {
+    buf []byte
+    err error
+}
+
+func (w *Writer) fail(err error) {
+    if w.err == nil {
+        w.err = err
+    }
+}
+
+func (w *Writer) finish() ([]byte, error) {
+    if w.err != nil {
+        return nil, w.err
+    }
+    return w.buf, nil
+}
+
+func encode[T any](v T, f func(*Writer, T)) ([]byte, error) {
+    w := &Writer{}
+    f(w, v)
+    return w.finish()
+}
+
+func (w *Writer) Varint(v uint64) {
+    for v >= 0x80 {
+        w.buf = append(w.buf, byte(v)|0x80)
+        v >>= 7
+    }
+    w.buf = append(w.buf, byte(v))
+}
+
+func (w *Writer) Zigzag(v int64) {
+    w.Varint(uint64((v << 1) ^ (v >> 63)))
+}
+
+func (w *Writer) Len(n int) {
+    w.Varint(uint64(n))
+}
+
+func (w *Writer) Bool(v bool) {
+    if v {
+        w.buf = append(w.buf, 1)
+    } else {
+        w.buf = append(w.buf, 0)
+    }
+}
+
+func (w *Writer) U8(v uint8)   { w.buf = append(w.buf, v) }
+func (w *Writer) I8(v int8)    { w.buf = append(w.buf, byte(v)) }
+func (w *Writer) U16(v uint16) { w.Varint(uint64(v)) }
+func (w *Writer) U32(v uint32) { w.Varint(uint64(v)) }
+func (w *Writer) U64(v uint64) { w.Varint(v) }
+func (w *Writer) I16(v int16)  { w.Zigzag(int64(v)) }
+func (w *Writer) I32(v int32)  { w.Zigzag(int64(v)) }
+func (w *Writer) I64(v int64)  { w.Zigzag(v) }
+
+func (w *Writer) U128(v *big.Int) {
+    if v == nil || v.Sign() < 0 || v.BitLen() > 128 {
+        w.fail(fmt.Errorf("%v is out of range of u128", v))
+        return
+    }
+    rest := new(big.Int).Set(v)
+    for rest.BitLen() > 7 {
+        w.buf = append(w.buf, byte(rest.Uint64())|0x80)
+        rest.Rsh(rest, 7)
+    }
+    w.buf = append(w.buf, byte(rest.Uint64()))
+}
+
+func (w *Writer) I128(v *big.Int) {
+    if v == nil || v.BitLen() > 127 {
+        w.fail(fmt.Errorf("%v is out of range of i128", v))
+        return
+    }
+    zigzag := new(big.Int).Lsh(v, 1)
+    if v.Sign() < 0 {
+        zigzag.Neg(zigzag).Sub(zigzag, big.NewInt(1))
+    }
+    w.U128(zigzag)
+}
+
+func (w *Writer) F32(v float32) {
+    bits := math.Float32bits(v)
+    w.buf = append(w.buf, byte(bits), byte(bits>>8), byte(bits>>16), byte(bits>>24))
+}
+
+func (w *Writer) F64(v float64) {
+    bits := math.Float64bits(v)
+    for i := 0; i < 8; i++ {
+        w.buf = append(w.buf, byte(bits>>(8*i)))
+    }
+}
+
+func (w *Writer) Str(v string) {
+    w.Len(len(v))
+    w.buf = append(w.buf, v...)
+}"#;
+
+const DES_RUNTIME: &str = r#"// ErrUnexpectedEnd is returned if the input ends in the middle of a value.
+var ErrUnexpectedEnd = errors.New("unexpected end of input")
+
+// Reader decodes values, after the first error all reads return zero values and the error
+// is returned by finish.
+type Reader struct {
+    data []byte
+    pos  int
+    err  error
+}
+
+func (r *Reader) fail(err error) {
+    if r.err == nil {
+        r.err = err
+    }
+}
+
+func (r *Reader) finish() error {
+    if r.err == nil && r.pos != len(r.data) {
+        r.err = fmt.Errorf("%d trailing bytes", len(r.data)-r.pos)
+    }
+    return r.err
+}
+
+func decode[T any](data []byte, f func(*Reader) T) (T, error) {
+    r := &Reader{data: data}
+    v := f(r)
+    if err := r.finish(); err != nil {
+        var zero T
+        return zero, err
+    }
+    return v, nil
+}
+
+func (r *Reader) take(n int) []byte {
+    if r.err != nil {
+        return nil
+    }
+    if len(r.data)-r.pos < n {
+        r.fail(ErrUnexpectedEnd)
+        return nil
+    }
+    out := r.data[r.pos : r.pos+n]
+    r.pos += n
+    return out
+}
+
+func (r *Reader) pop() byte {
+    if b := r.take(1); b != nil {
+        return b[0]
+    }
+    return 0
+}
+
+// Varint rejects varints longer than maxBytes and values exceeding 64 bits.
+func (r *Reader) Varint(maxBytes int) uint64 {
+    var v uint64
+    for i := 0; i < maxBytes && r.err == nil; i++ {
+        b := r.pop()
+        if i == 9 && b > 1 {
+            r.fail(errors.New("varint exceeds 64 bits"))
+            return 0
+        }
+        v |= uint64(b&0x7F) << (7 * i)
+        if b&0x80 == 0 {
+            return v
+        }
+    }
+    r.fail(fmt.Errorf("varint exceeds %d bytes", maxBytes))
+    return 0
+}
+
+func (r *Reader) Zigzag(maxBytes int) int64 {
+    raw := r.Varint(maxBytes)
+    return int64(raw>>1) ^ -int64(raw&1)
+}
+
+func (r *Reader) unsigned(maxBytes int, max uint64) uint64 {
+    v := r.Varint(maxBytes)
+    if v > max {
+        r.fail(fmt.Errorf("%d is out of range", v))
+        return 0
+    }
+    return v
+}
+
+func (r *Reader) signed(maxBytes int, min, max int64) int64 {
+    v := r.Zigzag(maxBytes)
+    if v < min || v > max {
+        r.fail(fmt.Errorf("%d is out of range", v))
+        return 0
+    }
+    return v
+}
+
+func (r *Reader) Len() int {
+    return int(r.unsigned(10, math.MaxInt))
+}
+
+func (r *Reader) Bool() bool {
+    switch b := r.pop(); b {
+    case 0:
+        return false
+    case 1:
+        return true
+    default:
+        r.fail(errors.New("invalid bool"))
+        return false
+    }
+}
+
+func (r *Reader) U8() uint8   { return r.pop() }
+func (r *Reader) I8() int8    { return int8(r.pop()) }
+func (r *Reader) U16() uint16 { return uint16(r.unsigned(3, math.MaxUint16)) }
+func (r *Reader) U32() uint32 { return uint32(r.unsigned(5, math.MaxUint32)) }
+func (r *Reader) U64() uint64 { return r.Varint(10) }
+func (r *Reader) I16() int16  { return int16(r.signed(3, math.MinInt16, math.MaxInt16)) }
+func (r *Reader) I32() int32  { return int32(r.signed(5, math.MinInt32, math.MaxInt32)) }
+func (r *Reader) I64() int64  { return r.Zigzag(10) }
+
+func (r *Reader) U128() *big.Int {
+    v := new(big.Int)
+    for i := 0; i < 19 && r.err == nil; i++ {
+        b := r.pop()
+        v.Or(v, new(big.Int).Lsh(big.NewInt(int64(b&0x7F)), uint(7*i)))
+        if b&0x80 == 0 {
+            if v.BitLen() > 128 {
+                r.fail(errors.New("varint exceeds 128 bits"))
+            }
+            return v
+        }
+    }
+    r.fail(errors.New("varint exceeds 19 bytes"))
+    return v
+}
+
+func (r *Reader) I128() *big.Int {
+    raw := r.U128()
+    v := new(big.Int).Rsh(raw, 1)
+    if raw.Bit(0) == 1 {
+        v.Add(v, big.NewInt(1)).Neg(v)
+    }
+    return v
+}
+
+func (r *Reader) F32() float32 {
+    b := r.take(4)
+    if b == nil {
+        return 0
+    }
+    return math.Float32frombits(uint32(b[0]) | uint32(b[1])<<8 | uint32(b[2])<<16 | uint32(b[3])<<24)
+}
+
+func (r *Reader) F64() float64 {
+    b := r.take(8)
+    if b == nil {
+        return 0
+    }
+    var bits uint64
+    for i := 7; i >= 0; i-- {
+        bits = bits<<8 | uint64(b[i])
+    }
+    return math.Float64frombits(bits)
+}
+
+func (r *Reader) Str() string {
+    b := r.take(r.Len())
+    if !utf8.Valid(b) {
+        r.fail(errors.New("invalid utf-8"))
+        return ""
+    }
+    return string(b)
+}
+
+func readOption[T any](r *Reader, item func() T) *T {
+    if !r.Bool() {
+        return nil
+    }
+    v := item()
+    return &v
+}
+
+func readList[T any](r *Reader, item func() T) []T {
+    n := r.Len()
+    out := make([]T, 0, min(n, len(r.data)-r.pos))
+    for i := 0; i < n && r.err == nil; i++ {
+        out = append(out, item())
+    }
+    return out
+}
+
+func readMap[K comparable, V any](r *Reader, key func() K, value func() V) map[K]V {
+    n := r.Len()
+    out := make(map[K]V, min(n, len(r.data)-r.pos))
+    for i := 0; i < n && r.err == nil; i++ {
+        k := key()
+        out[k] = value()
+    }
+    return out
+}"#;
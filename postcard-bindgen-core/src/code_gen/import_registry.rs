@@ -130,6 +130,17 @@ impl ImportRegistry {
             });
     }
 
+    /// Moves the imports of the packages `f` returns `true` for into a new registry.
+    pub fn split_off(&mut self, f: impl Fn(&Package) -> bool) -> Self {
+        let (split, kept) = self.imports.drain().partition(|(package, _)| f(package));
+        self.imports = kept;
+
+        Self {
+            imports: split,
+            base_path: self.base_path.clone(),
+        }
+    }
+
     pub(super) fn into_items_sorted(self) -> (String, Vec<(Package, ImportMode)>) {
         let mut items = self.imports.into_iter().collect::<Vec<_>>();
         items.sort_by_key(|(package, ..)| package.to_owned());
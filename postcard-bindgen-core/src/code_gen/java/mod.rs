@@ -0,0 +1,608 @@
+mod runtime;
+
+use core::borrow::Borrow;
+use std::collections::BTreeSet;
+
+use convert_case::{Case, Casing};
+use genco::{lang::java::Java, quote, quote_in};
+
+use runtime::{gen_des_runtime, gen_runtime, gen_ser_runtime, gen_tuple};
+
+use super::backend::{generate_with, LanguageBackend};
+use crate::{
+    registry::{
+        BindingType, Container, ContainerCollection, EnumType, EnumVariantType, StructField,
+        StructType, TupleStructType,
+    },
+    type_info::{ArrayMeta, NumberMeta, ValueType},
+    Exports,
+};
+
+pub use super::settings::{GenerationSettings, PointerWidth};
+
+type Tokens = genco::lang::java::Tokens;
+type ExportFile = crate::ExportFile<Java>;
+
+/// Java keywords and the methods of `Object` and the generated records, which can't be
+/// used as record components.
+const JAVA_RESERVED: &[&str] = &[
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "clone",
+    "const",
+    "continue",
+    "decode",
+    "default",
+    "do",
+    "double",
+    "else",
+    "encode",
+    "enum",
+    "extends",
+    "false",
+    "final",
+    "finalize",
+    "finally",
+    "float",
+    "for",
+    "getClass",
+    "goto",
+    "hashCode",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "notify",
+    "notifyAll",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "toString",
+    "transient",
+    "true",
+    "try",
+    "void",
+    "volatile",
+    "wait",
+    "while",
+];
+
+/// Generates a Java source file, exported as `source`.
+///
+/// The file declares the package `module_name` in flat case, holding the final class
+/// `module_name` in pascal case. All types are nested in this class, structs as records
+/// with camel case components and enums as sealed interfaces with a record per variant.
+/// Encoding and decoding is done with
+///
+/// ```java
+/// static byte[] serialize(PostcardEncodable value)
+/// static <T> T deserialize(ByteBuffer bytes, Function<PostcardReader, T> decode)
+/// ```
+///
+/// e.g. `deserialize(bytes, Message::decode)`, which throw a `PostcardException` if a value
+/// is out of range or the input is invalid.
+///
+/// Java has no unsigned integers, so `u8` and `u16` are an `int`, `u32` and `u64` a `long`,
+/// where `u64` values above `i64::MAX` are stored as their bit pattern. 128 bit integers
+/// become a `BigInteger`, optionals an `Optional`, vectors and arrays a `List` and tuples
+/// a generated `PostcardTuple<N>` record.
+pub fn generate(
+    containers: ContainerCollection,
+    gen_settings: impl Borrow<GenerationSettings>,
+    module_name: String,
+) -> Exports<Java> {
+    let backend = JavaBackend {
+        gen_settings: gen_settings.borrow(),
+        package: module_name.to_case(Case::Flat),
+        class_name: class_name(&module_name),
+        tuples: BTreeSet::new(),
+        types: Tokens::new(),
+    };
+    generate_with(backend, containers)
+}
+
+struct JavaBackend<'a> {
+    gen_settings: &'a GenerationSettings,
+    package: String,
+    class_name: String,
+    tuples: BTreeSet<usize>,
+    types: Tokens,
+}
+
+impl JavaBackend<'_> {
+    fn push_type(&mut self, container: &Container) {
+        let v_types = match struct_fields(container) {
+            Some(fields) => fields.members(),
+            None => match &container.r#type {
+                BindingType::Enum(enum_type) => enum_type
+                    .variants
+                    .iter()
+                    .flat_map(|variant| Fields::from(&variant.inner_type).members())
+                    .collect(),
+                _ => unreachable!(),
+            },
+        };
+        for (_, v_type) in v_types {
+            collect_tuples(v_type, &mut self.tuples);
+        }
+        self.types.line();
+        self.types
+            .append(gen_type(container, &self.class_name, self.gen_settings));
+    }
+}
+
+impl LanguageBackend for JavaBackend<'_> {
+    type Lang = Java;
+
+    fn prepare(&mut self, containers: &mut ContainerCollection) {
+        containers.assert_no_internally_tagged_enums("Java");
+        containers.flatten();
+        containers.resolve_pointer_width(self.gen_settings.pointer_width.bytes());
+        containers.sets_as_sequences();
+        containers.remove_unit_fields();
+
+        let class_name = &self.class_name;
+        let mut names = BTreeSet::from([class_name.clone()]);
+        for container in containers.all_containers() {
+            assert!(
+                names.insert(container.name.to_string()),
+                "the Java bindings nest all types in the class `{class_name}`, but `{}` is defined twice",
+                container.name
+            );
+        }
+    }
+
+    fn visit_struct(&mut self, container: &Container, _: &StructType) {
+        self.push_type(container);
+    }
+
+    fn visit_tuple_struct(&mut self, container: &Container, _: &TupleStructType) {
+        self.push_type(container);
+    }
+
+    fn visit_enum(&mut self, container: &Container, _: &EnumType) {
+        self.push_type(container);
+    }
+
+    fn visit_unit_struct(&mut self, container: &Container) {
+        self.push_type(container);
+    }
+
+    fn finish(self) -> Exports<Java> {
+        let gen_settings = self.gen_settings;
+        let class_name = &self.class_name;
+
+        let mut body = gen_runtime();
+        if gen_settings.ser {
+            body.line();
+            body.append(gen_ser_runtime());
+        }
+        if gen_settings.des {
+            body.line();
+            body.append(gen_des_runtime());
+        }
+        if !self.tuples.is_empty() {
+            body.line();
+            for arity in self.tuples {
+                body.append(gen_tuple(arity));
+                body.push();
+            }
+        }
+        body.append(self.types);
+
+        let source = quote! {
+            package $(&self.package);
+
+            import java.math.BigInteger;
+            import java.nio.ByteBuffer;
+            import java.nio.ByteOrder;
+            $(if gen_settings.des => import java.nio.charset.CharacterCodingException;)
+            import java.nio.charset.StandardCharsets;
+            $(if gen_settings.des => import java.util.ArrayList;)
+            $(if gen_settings.ser => import java.util.Arrays;)
+            $(if gen_settings.des => import java.util.LinkedHashMap;)
+            import java.util.List;
+            import java.util.Map;
+            import java.util.Optional;
+            $(if gen_settings.ser => import java.util.function.Consumer;)
+            $(if gen_settings.des => import java.util.function.Function;)
+            $(if gen_settings.des => import java.util.function.Supplier;)
+
+            public final class $class_name {
+                private $class_name() {}
+
+                $body
+            }
+        };
+
+        Exports {
+            files: vec![ExportFile {
+                content_type: "source".to_owned(),
+                content: source,
+            }],
+        }
+    }
+}
+
+/// Name of the class generated for `module_name`, which is also the name of the file.
+pub fn class_name(module_name: &str) -> String {
+    module_name.to_case(Case::Pascal)
+}
+
+/// Fields of a struct or an enum variant.
+enum Fields<'a> {
+    Named(&'a [StructField]),
+    Unnamed(&'a [ValueType]),
+    Unit,
+}
+
+impl<'a> Fields<'a> {
+    fn members(&self) -> Vec<(String, &'a ValueType)> {
+        match self {
+            Fields::Named(fields) => fields
+                .iter()
+                .filter(|field| !field.skip)
+                .map(|field| (java_ident(&field.name.to_case(Case::Camel)), &field.v_type))
+                .collect(),
+            Fields::Unnamed(fields) => fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| (format!("item{index}"), field))
+                .collect(),
+            Fields::Unit => Vec::new(),
+        }
+    }
+}
+
+impl<'a> From<&'a EnumVariantType> for Fields<'a> {
+    fn from(variant_type: &'a EnumVariantType) -> Self {
+        match variant_type {
+            EnumVariantType::Empty => Fields::Unit,
+            EnumVariantType::Tuple(fields) => Fields::Unnamed(fields),
+            EnumVariantType::NewType(fields) => Fields::Named(fields),
+        }
+    }
+}
+
+fn struct_fields(container: &Container) -> Option<Fields<'_>> {
+    match &container.r#type {
+        BindingType::Struct(struct_type) => Some(Fields::Named(&struct_type.fields)),
+        BindingType::TupleStruct(tuple_struct) => Some(Fields::Unnamed(&tuple_struct.fields)),
+        BindingType::UnitStruct(_) => Some(Fields::Unit),
+        BindingType::Enum(_) => None,
+    }
+}
+
+/// Collects the lengths of all tuples in `v_type`, a record is generated for each.
+fn collect_tuples(v_type: &ValueType, tuples: &mut BTreeSet<usize>) {
+    match v_type {
+        ValueType::Optional(meta) => collect_tuples(&meta.inner, tuples),
+        ValueType::Range(meta) => collect_tuples(&meta.bounds_type, tuples),
+        ValueType::Array(meta) => collect_tuples(&meta.items_type, tuples),
+        ValueType::Map(meta) => {
+            collect_tuples(&meta.key_type, tuples);
+            collect_tuples(&meta.value_type, tuples);
+        }
+        ValueType::Tuple(meta) => {
+            tuples.insert(meta.items_types.len());
+            for item in meta.items_types.iter() {
+                collect_tuples(item, tuples);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Nested types can't share the name of the enclosing one.
+fn variant_record(enum_name: &str, variant_name: &str) -> String {
+    if enum_name == variant_name {
+        format!("{variant_name}_")
+    } else {
+        variant_name.to_owned()
+    }
+}
+
+fn gen_type(container: &Container, class_name: &str, gen_settings: &GenerationSettings) -> Tokens {
+    let name = container.name.as_ref();
+    match &container.r#type {
+        BindingType::Enum(enum_type) => {
+            // the variants may shadow other types of the bindings
+            let qualifier = format!("{class_name}.");
+            let extends = if gen_settings.ser {
+                " extends PostcardEncodable"
+            } else {
+                ""
+            };
+
+            let mut body = Tokens::new();
+            for variant in enum_type.variants.iter() {
+                let fields = Fields::from(&variant.inner_type);
+                let encode = gen_settings.ser.then(|| {
+                    gen_encode_method(quote!(w.u32($(variant.index));), &fields, &qualifier)
+                });
+                body.append(gen_record(
+                    &variant_record(name, &variant.name),
+                    &format!(" implements {name}"),
+                    &fields,
+                    &qualifier,
+                    encode,
+                    None,
+                ));
+                body.line();
+            }
+
+            if gen_settings.des {
+                let mut cases = Tokens::new();
+                for variant in enum_type.variants.iter() {
+                    let construct = gen_construct(
+                        &variant_record(name, &variant.name),
+                        &Fields::from(&variant.inner_type),
+                        &qualifier,
+                    );
+                    quote_in!(cases => case $(variant.index) -> $construct;);
+                    cases.push();
+                }
+                quote_in! { body =>
+                    static $name decode(PostcardReader r) {
+                        int tag = r.tag();
+                        return switch (tag) {
+                            $cases
+                            default -> throw new PostcardException($(format!("\"unknown variant \" + tag + \" of {name}\"")));
+                        };
+                    }
+                }
+            }
+
+            quote! {
+                public sealed interface $name$extends {
+                    $body
+                }
+            }
+        }
+        _ => {
+            let fields = struct_fields(container).unwrap();
+            let implements = if gen_settings.ser {
+                " implements PostcardEncodable"
+            } else {
+                ""
+            };
+            let encode = gen_settings
+                .ser
+                .then(|| gen_encode_method(Tokens::new(), &fields, ""));
+            let decode = gen_settings.des.then(|| {
+                quote! {
+                    public static $name decode(PostcardReader r) {
+                        return $(gen_construct(name, &fields, ""));
+                    }
+                }
+            });
+            quote!(public $(gen_record(name, implements, &fields, "", encode, decode)))
+        }
+    }
+}
+
+/// A record holding `fields` with an `encode` method and a static `decode` method.
+fn gen_record(
+    name: &str,
+    supertypes: &str,
+    fields: &Fields,
+    qualifier: &str,
+    encode: Option<Tokens>,
+    decode: Option<Tokens>,
+) -> Tokens {
+    let components = fields
+        .members()
+        .into_iter()
+        .map(|(member, v_type)| format!("{} {member}", java_type(v_type, qualifier, false)))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut body = Tokens::new();
+    for method in encode.into_iter().chain(decode) {
+        if !body.is_empty() {
+            body.line();
+        }
+        body.append(method);
+    }
+
+    if body.is_empty() {
+        return quote!(record $name($components)$supertypes {});
+    }
+    quote! {
+        record $name($components)$supertypes {
+            $body
+        }
+    }
+}
+
+fn gen_encode_method(tag: Tokens, fields: &Fields, qualifier: &str) -> Tokens {
+    quote! {
+        @Override
+        public void encode(PostcardWriter w) {
+            $tag
+            $(for (member, v_type) in fields.members() => $(gen_encode(v_type, quote!(this.$member), qualifier, 0))$['\r'])
+        }
+    }
+}
+
+/// Encodes `value` as statements, lambdas are nested with `depth`.
+fn gen_encode(v_type: &ValueType, value: Tokens, qualifier: &str, depth: usize) -> Tokens {
+    match v_type {
+        ValueType::Tuple(meta) => {
+            let items = meta
+                .items_types
+                .iter()
+                .enumerate()
+                .map(|(index, item_type)| {
+                    gen_encode(
+                        item_type,
+                        quote!($(value.clone()).item$(index.to_string())()),
+                        qualifier,
+                        depth,
+                    )
+                })
+                .collect::<Vec<_>>();
+            quote!($(for item in items join ( ) => $item))
+        }
+        _ => quote!($(gen_encode_call(v_type, value, qualifier, depth));),
+    }
+}
+
+/// Encodes `value` with a single call, which isn't possible for tuples.
+fn gen_encode_call(v_type: &ValueType, value: Tokens, qualifier: &str, depth: usize) -> Tokens {
+    let item = format!("x{depth}");
+    let lambda = |v_type: &ValueType, item: &str| {
+        let param = quote!($item);
+        match v_type {
+            ValueType::Tuple(_) => {
+                quote!($item -> { $(gen_encode(v_type, param, qualifier, depth + 1)) })
+            }
+            _ => quote!($item -> $(gen_encode_call(v_type, param, qualifier, depth + 1))),
+        }
+    };
+    match v_type {
+        ValueType::Object(_) => quote!($value.encode(w)),
+        ValueType::Optional(meta) => quote!(w.option($value, $(lambda(&meta.inner, &item)))),
+        ValueType::Array(ArrayMeta {
+            items_type,
+            length: Some(length),
+            ..
+        }) => quote!(w.array($value, $(*length), $(lambda(items_type, &item)))),
+        ValueType::Array(ArrayMeta { items_type, .. }) => {
+            quote!(w.list($value, $(lambda(items_type, &item))))
+        }
+        ValueType::Map(meta) => {
+            let key = format!("k{depth}");
+            quote!(w.map($value, $(lambda(&meta.key_type, &key)), $(lambda(&meta.value_type, &item))))
+        }
+        ValueType::Range(meta) => quote!(w.range($value, $(lambda(&meta.bounds_type, &item)))),
+        ValueType::Tuple(_) => unreachable!("tuples are encoded item by item"),
+        _ => quote!(w.$(primitive_fn(v_type))($value)),
+    }
+}
+
+/// Constructs the record `name` from the decoded `fields`.
+fn gen_construct(name: &str, fields: &Fields, qualifier: &str) -> Tokens {
+    quote!(new $name($(for (_, v_type) in fields.members() join (, ) => $(gen_decode(v_type, qualifier)))))
+}
+
+fn gen_decode(v_type: &ValueType, qualifier: &str) -> Tokens {
+    match v_type {
+        ValueType::Object(meta) => quote!($qualifier$(&meta.name).decode(r)),
+        ValueType::Optional(meta) => quote!(r.option(() -> $(gen_decode(&meta.inner, qualifier)))),
+        ValueType::Array(ArrayMeta {
+            items_type,
+            length: Some(length),
+            ..
+        }) => quote!(r.array($(*length), () -> $(gen_decode(items_type, qualifier)))),
+        ValueType::Array(ArrayMeta { items_type, .. }) => {
+            quote!(r.list(() -> $(gen_decode(items_type, qualifier))))
+        }
+        ValueType::Map(meta) => {
+            quote!(r.map(() -> $(gen_decode(&meta.key_type, qualifier)), () -> $(gen_decode(&meta.value_type, qualifier))))
+        }
+        ValueType::Range(meta) => {
+            quote!(r.range(() -> $(gen_decode(&meta.bounds_type, qualifier))))
+        }
+        ValueType::Tuple(meta) => {
+            quote!(new PostcardTuple$(meta.items_types.len().to_string())<>($(for item in meta.items_types.iter() join (, ) => $(gen_decode(item, qualifier)))))
+        }
+        _ => quote!(r.$(primitive_fn(v_type))()),
+    }
+}
+
+/// Name of the `PostcardWriter` and `PostcardReader` method of a primitive.
+fn primitive_fn(v_type: &ValueType) -> String {
+    match v_type {
+        ValueType::Number(NumberMeta::Integer { bytes, signed, .. }) => {
+            format!("{}{}", if *signed { "i" } else { "u" }, bytes * 8)
+        }
+        ValueType::Number(NumberMeta::FloatingPoint { bytes }) => format!("f{}", bytes * 8),
+        ValueType::Bool(_) => "bool".to_owned(),
+        ValueType::String(_) => "string".to_owned(),
+        _ => unreachable!("not a primitive type"),
+    }
+}
+
+/// Type of `v_type`, `boxed` when used as a type argument.
+fn java_type(v_type: &ValueType, qualifier: &str, boxed: bool) -> String {
+    let (primitive, boxed_type) = match v_type {
+        ValueType::Number(NumberMeta::Integer { bytes: 16, .. }) => return "BigInteger".to_owned(),
+        ValueType::Number(NumberMeta::Integer { bytes, signed, .. }) => match (bytes, signed) {
+            (1, true) => ("byte", "Byte"),
+            (2, true) => ("short", "Short"),
+            (1 | 2, false) | (4, true) => ("int", "Integer"),
+            _ => ("long", "Long"),
+        },
+        ValueType::Number(NumberMeta::FloatingPoint { bytes: 4 }) => ("float", "Float"),
+        ValueType::Number(NumberMeta::FloatingPoint { .. }) => ("double", "Double"),
+        ValueType::Bool(_) => ("boolean", "Boolean"),
+        ValueType::String(_) => return "String".to_owned(),
+        ValueType::Object(meta) => return format!("{qualifier}{}", meta.name),
+        ValueType::Optional(meta) => {
+            return format!("Optional<{}>", java_type(&meta.inner, qualifier, true))
+        }
+        ValueType::Range(meta) => {
+            return format!(
+                "PostcardRange<{}>",
+                java_type(&meta.bounds_type, qualifier, true)
+            )
+        }
+        ValueType::Array(meta) => {
+            return format!("List<{}>", java_type(&meta.items_type, qualifier, true))
+        }
+        ValueType::Set(_) => unreachable!("sets are replaced by sequences"),
+        ValueType::Unit(_) => panic!("`()` is only supported as field by the Java bindings"),
+        ValueType::Map(meta) => {
+            return format!(
+                "Map<{}, {}>",
+                java_type(&meta.key_type, qualifier, true),
+                java_type(&meta.value_type, qualifier, true)
+            )
+        }
+        ValueType::Tuple(meta) => {
+            return format!(
+                "PostcardTuple{}<{}>",
+                meta.items_types.len(),
+                meta.items_types
+                    .iter()
+                    .map(|item| java_type(item, qualifier, true))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }
+    };
+    if boxed { boxed_type } else { primitive }.to_owned()
+}
+
+fn java_ident(name: &str) -> String {
+    if JAVA_RESERVED.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_owned()
+    }
+}
@@ -0,0 +1,375 @@
+use genco::quote_in;
+
+use super::Tokens;
+
+/// Types shared by the encode and decode functions.
+pub fn gen_runtime() -> Tokens {
+    nested(RUNTIME)
+}
+
+pub fn gen_ser_runtime() -> Tokens {
+    nested(SER_RUNTIME)
+}
+
+pub fn gen_des_runtime() -> Tokens {
+    nested(DES_RUNTIME)
+}
+
+/// Generic records holding the items of rust tuples with `arity` items.
+pub fn gen_tuple(arity: usize) -> Tokens {
+    let params = (0..arity)
+        .map(|index| format!("T{index}"))
+        .collect::<Vec<_>>();
+    let components = (0..arity)
+        .map(|index| format!("T{index} item{index}"))
+        .collect::<Vec<_>>();
+    let mut out = Tokens::new();
+    quote_in!(out => public record $(format!("PostcardTuple{arity}<{}>({}) {{}}", params.join(", "), components.join(", "))));
+    out
+}
+
+/// Splits `source` into lines, so it is indented in the enclosing class.
+fn nested(source: &str) -> Tokens {
+    let mut out = Tokens::new();
+    for line in source.lines() {
+        if line.is_empty() {
+            out.line();
+        } else {
+            out.append(line);
+            out.push();
+        }
+    }
+    out
+}
+
+const RUNTIME: &str = r#"public static final class PostcardException extends RuntimeException {
+    private static final long serialVersionUID = 1L;
+
+    public PostcardException(String message) {
+        super(message);
+    }
+}
+
+/** rust {@code Range} and {@code RangeInclusive} */
+public record PostcardRange<T>(T start, T end) {}"#;
+
+const SER_RUNTIME: &str = r#"public interface PostcardEncodable {
+    void encode(PostcardWriter w);
+}
+
+public static byte[] serialize(PostcardEncodable value) {
+    PostcardWriter w = new PostcardWriter();
+    value.encode(w);
+    return w.toByteArray();
+}
+
+public static final class PostcardWriter {
+    private static final BigInteger BYTE_LIMIT = BigInteger.valueOf(0x80);
+
+    private ByteBuffer buffer = ByteBuffer.allocate(64).order(ByteOrder.LITTLE_ENDIAN);
+
+    private void reserve(int n) {
+        if (buffer.remaining() < n) {
+            ByteBuffer grown = ByteBuffer.allocate(Math.max(buffer.capacity() * 2, buffer.position() + n));
+            buffer.flip();
+            buffer = grown.put(buffer).order(ByteOrder.LITTLE_ENDIAN);
+        }
+    }
+
+    public void push(int b) {
+        reserve(1);
+        buffer.put((byte) b);
+    }
+
+    /** {@code value} is taken as the bit pattern of an unsigned 64 bit integer */
+    public void varint(long value) {
+        while ((value & ~0x7FL) != 0) {
+            push((int) (value & 0x7F) | 0x80);
+            value >>>= 7;
+        }
+        push((int) value);
+    }
+
+    public void zigzag(long value) {
+        varint((value << 1) ^ (value >> 63));
+    }
+
+    public void len(int n) {
+        varint(n);
+    }
+
+    private static long checked(long v, long min, long max) {
+        if (v < min || v > max) {
+            throw new PostcardException(v + " is out of range");
+        }
+        return v;
+    }
+
+    public void bool(boolean v) { push(v ? 1 : 0); }
+    public void u8(int v) { push((int) checked(v, 0, 0xFF)); }
+    public void i8(byte v) { push(v); }
+    public void u16(int v) { varint(checked(v, 0, 0xFFFF)); }
+    public void u32(long v) { varint(checked(v, 0, 0xFFFFFFFFL)); }
+    public void u64(long v) { varint(v); }
+    public void i16(short v) { zigzag(v); }
+    public void i32(int v) { zigzag(v); }
+    public void i64(long v) { zigzag(v); }
+
+    public void u128(BigInteger v) {
+        if (v.signum() < 0 || v.bitLength() > 128) {
+            throw new PostcardException(v + " is out of range of u128");
+        }
+        while (v.compareTo(BYTE_LIMIT) >= 0) {
+            push(v.intValue() & 0x7F | 0x80);
+            v = v.shiftRight(7);
+        }
+        push(v.intValue());
+    }
+
+    public void i128(BigInteger v) {
+        if (v.bitLength() > 127) {
+            throw new PostcardException(v + " is out of range of i128");
+        }
+        u128(v.signum() < 0 ? v.negate().shiftLeft(1).subtract(BigInteger.ONE) : v.shiftLeft(1));
+    }
+
+    public void f32(float v) {
+        reserve(4);
+        buffer.putFloat(v);
+    }
+
+    public void f64(double v) {
+        reserve(8);
+        buffer.putDouble(v);
+    }
+
+    public void string(String v) {
+        byte[] utf8 = v.getBytes(StandardCharsets.UTF_8);
+        len(utf8.length);
+        reserve(utf8.length);
+        buffer.put(utf8);
+    }
+
+    public <T> void option(Optional<T> v, Consumer<T> item) {
+        if (v.isPresent()) {
+            push(1);
+            item.accept(v.get());
+        } else {
+            push(0);
+        }
+    }
+
+    public <T> void list(List<T> v, Consumer<T> item) {
+        len(v.size());
+        v.forEach(item);
+    }
+
+    public <T> void array(List<T> v, int length, Consumer<T> item) {
+        if (v.size() != length) {
+            throw new PostcardException("expected " + length + " items, got " + v.size());
+        }
+        v.forEach(item);
+    }
+
+    public <K, V> void map(Map<K, V> v, Consumer<K> key, Consumer<V> value) {
+        len(v.size());
+        v.forEach((k, x) -> {
+            key.accept(k);
+            value.accept(x);
+        });
+    }
+
+    public <T> void range(PostcardRange<T> v, Consumer<T> item) {
+        item.accept(v.start());
+        item.accept(v.end());
+    }
+
+    public ByteBuffer toByteBuffer() {
+        return buffer.duplicate().flip().order(ByteOrder.LITTLE_ENDIAN);
+    }
+
+    public byte[] toByteArray() {
+        return Arrays.copyOf(buffer.array(), buffer.position());
+    }
+}"#;
+
+const DES_RUNTIME: &str = r#"public static <T> T deserialize(ByteBuffer bytes, Function<PostcardReader, T> decode) {
+    PostcardReader r = new PostcardReader(bytes);
+    T value = decode.apply(r);
+    r.finish();
+    return value;
+}
+
+public static <T> T deserialize(byte[] bytes, Function<PostcardReader, T> decode) {
+    return deserialize(ByteBuffer.wrap(bytes), decode);
+}
+
+public static final class PostcardReader {
+    private final ByteBuffer buffer;
+
+    /** reads the remaining bytes of {@code buffer}, without changing its position */
+    public PostcardReader(ByteBuffer buffer) {
+        this.buffer = buffer.slice().order(ByteOrder.LITTLE_ENDIAN);
+    }
+
+    private void require(int n) {
+        if (buffer.remaining() < n) {
+            throw new PostcardException("unexpected end of input");
+        }
+    }
+
+    public int pop() {
+        require(1);
+        return buffer.get() & 0xFF;
+    }
+
+    /** rejects varints longer than {@code maxBytes} and values exceeding 64 bits */
+    public long varint(int maxBytes) {
+        long out = 0;
+        for (int i = 0; i < maxBytes; i++) {
+            int b = pop();
+            if (i == 9 && b > 1) {
+                throw new PostcardException("varint exceeds 64 bits");
+            }
+            out |= (long) (b & 0x7F) << (7 * i);
+            if ((b & 0x80) == 0) {
+                return out;
+            }
+        }
+        throw new PostcardException("varint exceeds " + maxBytes + " bytes");
+    }
+
+    public long zigzag(int maxBytes) {
+        long raw = varint(maxBytes);
+        return (raw >>> 1) ^ -(raw & 1);
+    }
+
+    private long unsigned(int maxBytes, long max) {
+        long v = varint(maxBytes);
+        if (v < 0 || v > max) {
+            throw new PostcardException(Long.toUnsignedString(v) + " is out of range");
+        }
+        return v;
+    }
+
+    private long signed(int maxBytes, long min, long max) {
+        long v = zigzag(maxBytes);
+        if (v < min || v > max) {
+            throw new PostcardException(v + " is out of range");
+        }
+        return v;
+    }
+
+    public int len() {
+        return (int) unsigned(10, Integer.MAX_VALUE);
+    }
+
+    /** reads the variant index of an enum */
+    public int tag() {
+        return (int) unsigned(5, Integer.MAX_VALUE);
+    }
+
+    public boolean bool() {
+        switch (pop()) {
+            case 0:
+                return false;
+            case 1:
+                return true;
+            default:
+                throw new PostcardException("invalid bool");
+        }
+    }
+
+    public int u8() { return pop(); }
+    public byte i8() { return (byte) pop(); }
+    public int u16() { return (int) unsigned(3, 0xFFFF); }
+    public long u32() { return unsigned(5, 0xFFFFFFFFL); }
+    public long u64() { return varint(10); }
+    public short i16() { return (short) signed(3, Short.MIN_VALUE, Short.MAX_VALUE); }
+    public int i32() { return (int) signed(5, Integer.MIN_VALUE, Integer.MAX_VALUE); }
+    public long i64() { return zigzag(10); }
+
+    public BigInteger u128() {
+        BigInteger out = BigInteger.ZERO;
+        for (int i = 0; i < 19; i++) {
+            int b = pop();
+            out = out.or(BigInteger.valueOf(b & 0x7F).shiftLeft(7 * i));
+            if ((b & 0x80) == 0) {
+                if (out.bitLength() > 128) {
+                    throw new PostcardException("varint exceeds 128 bits");
+                }
+                return out;
+            }
+        }
+        throw new PostcardException("varint exceeds 19 bytes");
+    }
+
+    public BigInteger i128() {
+        BigInteger raw = u128();
+        return raw.testBit(0) ? raw.shiftRight(1).add(BigInteger.ONE).negate() : raw.shiftRight(1);
+    }
+
+    public float f32() {
+        require(4);
+        return buffer.getFloat();
+    }
+
+    public double f64() {
+        require(8);
+        return buffer.getDouble();
+    }
+
+    public String string() {
+        int n = len();
+        require(n);
+        ByteBuffer utf8 = buffer.slice().limit(n);
+        buffer.position(buffer.position() + n);
+        try {
+            return StandardCharsets.UTF_8.newDecoder().decode(utf8).toString();
+        } catch (CharacterCodingException e) {
+            throw new PostcardException("invalid utf-8");
+        }
+    }
+
+    public <T> Optional<T> option(Supplier<T> item) {
+        return bool() ? Optional.of(item.get()) : Optional.empty();
+    }
+
+    public <T> List<T> list(Supplier<T> item) {
+        int n = len();
+        List<T> out = new ArrayList<>(Math.min(n, buffer.remaining()));
+        for (int i = 0; i < n; i++) {
+            out.add(item.get());
+        }
+        return out;
+    }
+
+    public <T> List<T> array(int length, Supplier<T> item) {
+        List<T> out = new ArrayList<>(length);
+        for (int i = 0; i < length; i++) {
+            out.add(item.get());
+        }
+        return out;
+    }
+
+    public <K, V> Map<K, V> map(Supplier<K> key, Supplier<V> value) {
+        int n = len();
+        Map<K, V> out = new LinkedHashMap<>();
+        for (int i = 0; i < n; i++) {
+            K k = key.get();
+            out.put(k, value.get());
+        }
+        return out;
+    }
+
+    public <T> PostcardRange<T> range(Supplier<T> item) {
+        T start = item.get();
+        return new PostcardRange<>(start, item.get());
+    }
+
+    public void finish() {
+        if (buffer.hasRemaining()) {
+            throw new PostcardException(buffer.remaining() + " trailing bytes");
+        }
+    }
+}"#;
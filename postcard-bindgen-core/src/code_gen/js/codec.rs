@@ -0,0 +1,217 @@
+use genco::{quote, tokens::quoted};
+
+use crate::registry::Container;
+
+use super::{Tokens, VariablePath};
+
+pub const JS_CODECS: &str = "CODECS";
+
+/// Custom javascript representation of a struct field.
+///
+/// The field is still encoded as its rust type, the codec only converts between the encoded
+/// value and the value exposed in javascript. All three snippets are javascript function
+/// expressions, e.g. a `u64` timestamp in milliseconds could be exposed as `Date` with
+///
+/// ```
+/// # use postcard_bindgen_core::code_gen::js::Codec;
+/// let codec = Codec::new(
+///     "(date) => date.getTime()",
+///     "(ms) => new Date(Number(ms))",
+///     "(date) => date instanceof Date",
+/// )
+/// .ts_type("Date");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Codec {
+    serialize: String,
+    deserialize: String,
+    check: String,
+    ts_type: String,
+}
+
+impl Codec {
+    /// Constructs a [`Codec`] from the javascript snippets.
+    ///
+    /// * `serialize` converts the javascript value into a value of the encoded type.
+    /// * `deserialize` converts the decoded value into the javascript value.
+    /// * `check` returns if a value is a valid javascript value, it's used by the runtime
+    ///   type checks.
+    pub fn new(
+        serialize: impl Into<String>,
+        deserialize: impl Into<String>,
+        check: impl Into<String>,
+    ) -> Self {
+        Self {
+            serialize: serialize.into(),
+            deserialize: deserialize.into(),
+            check: check.into(),
+            ts_type: "any".into(),
+        }
+    }
+
+    /// Exposes a date and time encoded as RFC 3339 string, e.g. a chrono `DateTime<Utc>`,
+    /// as `Date`.
+    pub fn date_time() -> Self {
+        Self::new(
+            "(date) => date.toISOString()",
+            "(str) => new Date(str)",
+            "(date) => date instanceof Date && !isNaN(date)",
+        )
+        .ts_type("Date")
+    }
+
+    /// Exposes a date without time zone, e.g. a chrono `NaiveDate`, as `Date` at midnight UTC.
+    pub fn naive_date() -> Self {
+        Self::new(
+            "(date) => date.toISOString().slice(0, 10)",
+            "(str) => new Date(str + \"T00:00:00Z\")",
+            "(date) => date instanceof Date && !isNaN(date)",
+        )
+        .ts_type("Date")
+    }
+
+    /// Exposes a date and time without time zone, e.g. a chrono `NaiveDateTime`, as `Date`
+    /// in UTC.
+    pub fn naive_date_time() -> Self {
+        Self::new(
+            "(date) => date.toISOString().slice(0, -1)",
+            "(str) => new Date(str + \"Z\")",
+            "(date) => date instanceof Date && !isNaN(date)",
+        )
+        .ts_type("Date")
+    }
+
+    /// Exposes the bytes of a `uuid::Uuid` as hyphenated string, e.g.
+    /// `67e55044-10b1-426f-9247-bb680e5fe0c8`. Without codec, the bytes are an array.
+    pub fn uuid() -> Self {
+        Self::new(
+            "(str) => str.replace(/-/g, \"\").match(/../g).map((b) => parseInt(b, 16))",
+            "(bytes) => Array.from(bytes, (b) => b.toString(16).padStart(2, \"0\")).join(\"\").replace(/^(.{8})(.{4})(.{4})(.{4})(.{12})$/, \"$1-$2-$3-$4-$5\")",
+            "(str) => typeof str === \"string\" && /^[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$/i.test(str)",
+        )
+        .ts_type("string")
+    }
+
+    /// Exposes the bytes of a `uuid::Uuid` as `Uint8Array` of length 16.
+    pub fn uuid_bytes() -> Self {
+        Self::new(
+            "(bytes) => Array.from(bytes)",
+            "(bytes) => Uint8Array.from(bytes)",
+            "(bytes) => bytes instanceof Uint8Array && bytes.length === 16",
+        )
+        .ts_type("Uint8Array")
+    }
+
+    /// Exposes the octets of an `Ipv4Addr` as dotted string, e.g. `192.168.0.1`. Without
+    /// codec, the octets are an array.
+    pub fn ipv4_addr() -> Self {
+        Self::new(IPV4_SERIALIZE, IPV4_DESERIALIZE, IPV4_CHECK).ts_type("string")
+    }
+
+    /// Exposes the octets of an `Ipv6Addr` as string, e.g. `2001:db8::1`. Addresses with an
+    /// embedded IPv4 address like `::ffff:192.168.0.1` are not accepted.
+    pub fn ipv6_addr() -> Self {
+        Self::new(IPV6_SERIALIZE, IPV6_DESERIALIZE, IPV6_CHECK).ts_type("string")
+    }
+
+    /// Exposes an `IpAddr` as string of either version instead of the enum.
+    pub fn ip_addr() -> Self {
+        Self::new(
+            format!("(str) => str.includes(\":\") ? {{ tag: \"V6\", value: ({IPV6_SERIALIZE})(str) }} : {{ tag: \"V4\", value: ({IPV4_SERIALIZE})(str) }}"),
+            format!("(ip) => ip.tag === \"V6\" ? ({IPV6_DESERIALIZE})(ip.value) : ({IPV4_DESERIALIZE})(ip.value)"),
+            format!("(str) => ({IPV4_CHECK})(str) || ({IPV6_CHECK})(str)"),
+        )
+        .ts_type("string")
+    }
+
+    /// Exposes a `SocketAddr` as string of the address and the port, e.g. `192.168.0.1:8080`
+    /// or `[2001:db8::1]:8080`, instead of the enum.
+    pub fn socket_addr() -> Self {
+        Self::new(
+            format!("(str) => {{ const i = str.lastIndexOf(\":\"); const [ip, port] = [str.slice(0, i), Number(str.slice(i + 1))]; return ip.startsWith(\"[\") ? {{ tag: \"V6\", value: {{ ip: ({IPV6_SERIALIZE})(ip.slice(1, -1)), port }} }} : {{ tag: \"V4\", value: {{ ip: ({IPV4_SERIALIZE})(ip), port }} }} }}"),
+            format!("(addr) => addr.tag === \"V6\" ? `[${{({IPV6_DESERIALIZE})(addr.value.ip)}}]:${{addr.value.port}}` : `${{({IPV4_DESERIALIZE})(addr.value.ip)}}:${{addr.value.port}}`"),
+            format!("(str) => {{ if (typeof str !== \"string\") return false; const i = str.lastIndexOf(\":\"); const [ip, port] = [str.slice(0, i), str.slice(i + 1)]; return /^\\d{{1,5}}$/.test(port) && Number(port) < 65536 && (ip.startsWith(\"[\") && ip.endsWith(\"]\") ? ({IPV6_CHECK})(ip.slice(1, -1)) : ({IPV4_CHECK})(ip)) }}"),
+        )
+        .ts_type("string")
+    }
+
+    /// Sets the typescript type of the javascript value. Defaults to `any`.
+    pub fn ts_type(mut self, ts_type: impl Into<String>) -> Self {
+        self.ts_type = ts_type.into();
+        self
+    }
+}
+
+// Snippets shared by the codecs of the address types.
+const IPV4_SERIALIZE: &str = "(str) => str.split(\".\").map(Number)";
+const IPV4_DESERIALIZE: &str = "(octets) => Array.from(octets).join(\".\")";
+const IPV4_CHECK: &str = r#"(str) => typeof str === "string" && /^(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)(\.(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)){3}$/.test(str)"#;
+const IPV6_SERIALIZE: &str = r#"(str) => { const groups = (s) => s ? s.split(":").map((g) => parseInt(g, 16)) : []; const [head, tail] = str.split("::").map(groups); const all = tail === undefined ? head : [...head, ...Array(8 - head.length - tail.length).fill(0), ...tail]; return all.flatMap((g) => [g >> 8, g & 255]) }"#;
+const IPV6_DESERIALIZE: &str = r#"(octets) => { const g = Array.from({ length: 8 }, (_, i) => ((octets[2 * i] << 8) | octets[2 * i + 1]).toString(16)); let [start, len] = [-1, 1]; for (let i = 0; i < 8; i++) { let j = i; while (j < 8 && g[j] === "0") j++; if (j - i > len) [start, len] = [i, j - i] } return start < 0 ? g.join(":") : `${g.slice(0, start).join(":")}::${g.slice(start + len).join(":")}` }"#;
+const IPV6_CHECK: &str = r#"(str) => { if (typeof str !== "string") return false; const parts = str.split("::"); const groups = parts.flatMap((s) => s ? s.split(":") : []); return parts.length <= 2 && groups.every((g) => /^[0-9a-f]{1,4}$/i.test(g)) && (parts.length === 2 ? groups.length < 8 : groups.length === 8) }"#;
+
+/// Generates the table holding the snippets of all codecs.
+pub fn gen_codecs(codecs: &[(String, Codec)]) -> Tokens {
+    if codecs.is_empty() {
+        return Tokens::new();
+    }
+
+    let entries = codecs.iter().map(|(name, codec)| {
+        quote!($(quoted(name)): { serialize: $(&codec.serialize), deserialize: $(&codec.deserialize), check: $(&codec.check) })
+    });
+    quote!(const $JS_CODECS = { $(for entry in entries join (, ) => $entry) })
+}
+
+/// Generates the typescript declaration mapping the codec names to their javascript types.
+pub fn gen_codecs_decl(codecs: &[(String, Codec)]) -> Tokens {
+    if codecs.is_empty() {
+        return Tokens::new();
+    }
+
+    let entries = codecs
+        .iter()
+        .map(|(name, codec)| quote!($(quoted(name)): $(&codec.ts_type)));
+    quote!(declare type Codecs = { $(for entry in entries join (, ) => $entry) })
+}
+
+/// Path to the encoded value of a field with a codec.
+pub fn gen_encoded_path(codec: &str, variable_path: VariablePath) -> VariablePath {
+    let value = quote!($variable_path);
+    VariablePath::new(format!(
+        "{JS_CODECS}[{codec:?}].serialize({})",
+        value
+            .to_string()
+            .expect("variable path is valid javascript")
+    ))
+}
+
+pub fn gen_decoded_value(codec: &str, decoded: Tokens) -> Tokens {
+    quote!($JS_CODECS[$(quoted(codec))].deserialize($decoded))
+}
+
+pub fn gen_check(codec: &str, variable_path: VariablePath) -> Tokens {
+    quote!($JS_CODECS[$(quoted(codec))].check($variable_path))
+}
+
+pub fn gen_ts_type(codec: &str) -> Tokens {
+    quote!(Codecs[$(quoted(codec))])
+}
+
+/// Panics if a field of the containers uses a codec which is not registered.
+pub fn assert_codecs_registered(
+    containers: impl Iterator<Item = Container>,
+    codecs: &[(String, Codec)],
+) {
+    for container in containers {
+        for field in container.named_fields() {
+            if let Some(codec) = &field.codec {
+                assert!(
+                    codecs.iter().any(|(name, _)| *name == *codec),
+                    "codec `{codec}` of field `{}` in `{}` is not registered",
+                    field.name,
+                    container.name
+                );
+            }
+        }
+    }
+}
@@ -7,7 +7,10 @@ use genco::{
 use crate::{
     code_gen::{
         js::{generateable::container::BindingTypeGenerateable, Tokens},
-        utils::{ContainerFullQualifiedTypeBuilder, ContainerIdentifierBuilder, TokensIterExt},
+        utils::{
+            ContainerFullQualifiedTypeBuilder, ContainerIdentifierBuilder, IdentifierCase,
+            TokensIterExt,
+        },
     },
     function_args,
     registry::Container,
@@ -17,16 +20,25 @@ use super::{Case, DefaultCase, ExportRegistry, Function, SwitchCase};
 
 pub fn gen_deserializer_code() -> Tokens {
     quote! {
+        class DeserializationError extends Error {
+            constructor(kind, message) { super(message); this.name = "DeserializationError"; this.kind = kind }
+        }
+
         class Deserializer {
-            constructor(bytes_in) { this.bytes = Array.from(bytes_in) }
-            pop_next = () => { const next = this.bytes.shift(); if (next === undefined) { throw "input buffer too small" } return next }
-            pop_n = (n) => { const bytes = Array(); for (let i = 0; i < n; i++) { bytes.push(this.bytes.shift()) } return bytes }
+            constructor(bytes_in) { this.bytes = Array.from(bytes_in); this.index = 0 }
+            at_end = () => this.index >= this.bytes.length
+            pop_next = () => { if (this.index >= this.bytes.length) { throw "input buffer too small" } return this.bytes[this.index++] }
+            pop_n = (n) => { if (this.index + n > this.bytes.length) { throw "input buffer too small" } const bytes = this.bytes.slice(this.index, this.index + n); this.index += n; return bytes }
             get_uint8 = () => this.pop_next()
-            try_take = (n_bytes) => { let out = 0n, v_max = varint_max(n_bytes); for (let i = 0; i < v_max; i++) { const val = this.pop_next(), carry = BigInt(val & 0x7F); out |= carry << BigInt(7 * i); if ((val & 0x80) === 0) { if (i === v_max - 1 && val > max_of_last_byte(n_bytes)) { throw "Bad Variant" } else return Number(out) } } throw "Bad Variant"; }
+            take_varint = (n_bytes) => { let out = 0n, v_max = varint_max(n_bytes); for (let i = 0; i < v_max; i++) { const val = this.pop_next(), carry = BigInt(val & 0x7F); out |= carry << BigInt(7 * i); if ((val & 0x80) === 0) { if (i === v_max - 1 && val > max_of_last_byte(n_bytes)) { throw "Bad Variant" } else return out } } throw "Bad Variant"; }
+            try_take = (n_bytes) => Number(this.take_varint(n_bytes))
             deserialize_bool = () => { const byte = this.pop_next(); return byte === undefined ? undefined : byte > 0 ? true : false }
-            deserialize_number = (n_bytes, signed) => { if (n_bytes === U8_BYTES) { return this.get_uint8() } else if (n_bytes === U16_BYTES || n_bytes === U32_BYTES || n_bytes === U64_BYTES || n_bytes === U128_BYTES) { const val = this.try_take(n_bytes); return to_number_if_safe(signed ? de_zig_zag_signed(val) : val) } else { throw "byte count not supported" } }
+            deserialize_number = (n_bytes, signed) => { if (n_bytes === U8_BYTES) { const byte = this.get_uint8(); return signed ? (byte << 24) >> 24 : byte } else if (n_bytes === U16_BYTES || n_bytes === U32_BYTES || n_bytes === U64_BYTES || n_bytes === U128_BYTES) { const val = this.take_varint(n_bytes); return to_number_if_safe(signed ? de_zig_zag_signed(val) : val) } else { throw "byte count not supported" } }
             deserialize_number_float = (n_bytes) => { const b_buffer = new ArrayBuffer(n_bytes), b_view = new DataView(b_buffer); this.pop_n(n_bytes).forEach((b, i) => b_view.setUint8(i, b)); if (n_bytes === U32_BYTES) { return b_view.getFloat32(0, true) } else if (n_bytes === U64_BYTES) { return b_view.getFloat64(0, true) } else { throw "byte count not supported" } }
-            deserialize_string = () => { const str = this.pop_n(this.try_take(U32_BYTES)); return String.fromCharCode(...str) }
+            deserialize_number_array = (n_bytes, signed, len, array_type) => { const n = len === undefined ? this.try_take(U32_BYTES) : len, out = array_type === undefined ? new Array(n) : new array_type(n); if (n_bytes > U32_BYTES) { for (let i = 0; i < n; i++) { out[i] = this.deserialize_number(n_bytes, signed) } return out } const v_max = varint_max(n_bytes); for (let i = 0; i < n; i++) { if (n_bytes === U8_BYTES) { const byte = this.pop_next(); out[i] = signed ? (byte << 24) >> 24 : byte; continue } let value = 0; for (let j = 0; ; j++) { if (j === v_max) { throw "Bad Variant" } const byte = this.pop_next(); value += (byte & 0x7F) * 2 ** (7 * j); if ((byte & 0x80) === 0) { if (j === v_max - 1 && byte > max_of_last_byte(n_bytes)) { throw "Bad Variant" } break } } out[i] = signed ? (value >>> 1) ^ -(value & 1) : value } return out }
+            deserialize_number_float_array = (n_bytes, len, array_type) => { const n = len === undefined ? this.try_take(U32_BYTES) : len, b_view = new DataView(new Uint8Array(this.pop_n(n * n_bytes)).buffer); return (array_type === undefined ? Array : array_type).from({length: n}, (v, i) => { if (n_bytes === U32_BYTES) { return b_view.getFloat32(i * n_bytes, true) } else if (n_bytes === U64_BYTES) { return b_view.getFloat64(i * n_bytes, true) } else { throw "byte count not supported" } }) }
+            deserialize_string = () => { const str = this.pop_n(this.try_take(U32_BYTES)); return new TextDecoder("utf-8", { fatal: true }).decode(new Uint8Array(str)) }
+            deserialize_bytes = () => Uint8Array.from(this.pop_n(this.try_take(U32_BYTES)))
             deserialize_array = (des, len) => Array.from({length: len === undefined ? this.try_take(U32_BYTES) : len}, (v, i) => des(this))
             deserialize_string_key_map = (des) => { return [...Array(this.try_take(U32_BYTES))].reduce((prev) => { prev[this.deserialize_string()] = des(this); return prev }, {}) }
             deserialize_map = (des) => { return [...Array(this.try_take(U32_BYTES))].reduce((prev) => { const d = des(this); prev.set(d[0], d[1]); return prev }, new Map()) }
@@ -34,15 +46,23 @@ pub fn gen_deserializer_code() -> Tokens {
     }
 }
 
-pub fn gen_des_functions(bindings: impl Iterator<Item = Container>) -> Tokens {
+pub fn gen_des_functions(
+    bindings: impl Iterator<Item = Container>,
+    ident_case: IdentifierCase,
+) -> Tokens {
     bindings
-        .map(gen_des_function_for_type)
+        .map(|container| gen_des_function_for_type(container, ident_case))
         .join_with_empty_line()
 }
 
-fn gen_des_function_for_type(container: Container) -> impl FormatInto<JavaScript> {
-    let container_ident = ContainerIdentifierBuilder::from(&container).build();
-    let des_body = container.r#type.gen_des_body();
+pub fn gen_des_function_for_type(
+    container: Container,
+    ident_case: IdentifierCase,
+) -> impl FormatInto<JavaScript> {
+    let container_ident = ContainerIdentifierBuilder::from(&container)
+        .ident_case(ident_case)
+        .build();
+    let des_body = container.r#type.gen_des_body(ident_case);
 
     Function::new_untyped(
         quote!(deserialize_$container_ident),
@@ -51,35 +71,174 @@ fn gen_des_function_for_type(container: Container) -> impl FormatInto<JavaScript
     )
 }
 
+/// Generates the `deserialize` and `deserializeLenient` functions taking the type name.
+///
+/// With `validate`, [`gen_check_deserialized`] has to be in scope.
 pub fn gen_deserialize_func(
     defines: impl Iterator<Item = Container>,
+    type_script: bool,
+    validate: bool,
+    ident_case: IdentifierCase,
     export_registry: &mut ExportRegistry,
 ) -> impl FormatInto<JavaScript> {
     let mut switch_case = SwitchCase::new("type");
-    switch_case.extend_cases(defines.map(gen_des_case));
+    switch_case
+        .extend_cases(defines.map(|container| gen_des_case(container, validate, ident_case)));
     switch_case.default_case(DefaultCase::new_without_break(
         quote!(throw "type not implemented";),
     ));
 
-    let body = quote! {
-        if (!(typeof type === "string")) {
-            throw "type must be a string";
-        }
-        const d = new Deserializer(bytes);
-        $switch_case
+    let deserialize_type = Function::new_untyped(
+        "deserialize_type",
+        function_args!["type", "d"],
+        quote! {
+            if (!(typeof type === "string")) {
+                throw "type must be a string";
+            }
+            $switch_case
+        },
+    );
+
+    let strict_body = gen_strict_body(
+        quote!(deserialize_type(type, d)),
+        quote!(" bytes left after deserializing " + type),
+    );
+    let lenient_body = quote!(return deserialize_type(type, new Deserializer(bytes)););
+
+    export_registry.push("DeserializationError");
+    export_registry.push("deserialize");
+    export_registry.push("deserializeLenient");
+
+    let (deserialize, deserialize_lenient) = if type_script {
+        let args = || function_args![("type", "T"), ("bytes", "Uint8Array | u8[]")];
+        (
+            Function::new(
+                "deserialize<T extends Type>",
+                args(),
+                strict_body,
+                "ValueType<T>",
+            ),
+            Function::new(
+                "deserializeLenient<T extends Type>",
+                args(),
+                lenient_body,
+                "ValueType<T>",
+            ),
+        )
+    } else {
+        (
+            Function::new_untyped("deserialize", function_args!("type", "bytes"), strict_body),
+            Function::new_untyped(
+                "deserializeLenient",
+                function_args!("type", "bytes"),
+                lenient_body,
+            ),
+        )
     };
 
+    quote! {
+        $deserialize_type
+
+        $deserialize
+
+        $deserialize_lenient
+    }
+}
+
+/// Generates `deserialize(bytes)` and `deserializeLenient(bytes)` for a single type, which
+/// behave like the functions taking the type name.
+///
+/// With `validate`, `check_deserialized` has to be in scope.
+pub fn gen_deserialize_type_funcs(
+    container: Container,
+    validate: bool,
+    ident_case: IdentifierCase,
+    export_registry: &mut ExportRegistry,
+) -> Tokens {
+    let fully_qualified = ContainerFullQualifiedTypeBuilder::from(&container).build();
+    let value = gen_des_value(
+        &container,
+        validate,
+        quote!($(quoted(&fully_qualified))),
+        ident_case,
+    );
+
+    let deserialize = Function::new_untyped(
+        "deserialize",
+        function_args!["bytes"],
+        gen_strict_body(
+            value.clone(),
+            quote!($(quoted(format!(" bytes left after deserializing {fully_qualified}")))),
+        ),
+    );
+    let deserialize_lenient = Function::new_untyped(
+        "deserializeLenient",
+        function_args!["bytes"],
+        quote! {
+            const d = new Deserializer(bytes);
+            return $value;
+        },
+    );
+
     export_registry.push("deserialize");
+    export_registry.push("deserializeLenient");
 
-    Function::new_untyped("deserialize", function_args!("type", "bytes"), body)
+    quote! {
+        $deserialize
+
+        $deserialize_lenient
+    }
 }
 
-fn gen_des_case(container: Container) -> Case {
+/// Throws if the decoded value doesn't pass the runtime type check of its type.
+pub fn gen_check_deserialized() -> Tokens {
+    quote! {
+        $(Function::new_untyped(
+            "check_deserialized",
+            function_args!["type", "value", "check"],
+            quote! {
+                if (!check(value)) {
+                    throw new DeserializationError("invalid_value", "deserialized value doesn't pass the type check of " + type);
+                }
+                return value;
+            },
+        ))
+    }
+}
+
+/// Decodes `value` from `d` and throws if bytes are left, the message is the number of bytes
+/// left followed by `message`.
+fn gen_strict_body(value: Tokens, message: Tokens) -> Tokens {
+    quote! {
+        const d = new Deserializer(bytes), value = $value;
+        if (d.index !== bytes.length) {
+            throw new DeserializationError("trailing_bytes", (bytes.length - d.index) + $message);
+        }
+        return value;
+    }
+}
+
+fn gen_des_case(container: Container, validate: bool, ident_case: IdentifierCase) -> Case {
     let fully_qualified = ContainerFullQualifiedTypeBuilder::from(&container).build();
-    let container_ident = ContainerIdentifierBuilder::from(&container).build();
+    let value = gen_des_value(&container, validate, quote!(type), ident_case);
 
-    Case::new_without_break(
-        quoted(fully_qualified),
-        quote!(return deserialize_$container_ident(d);),
-    )
+    Case::new_without_break(quoted(fully_qualified), quote!(return $value;))
+}
+
+/// Decodes a value of the container from `d`, which has to be in scope.
+fn gen_des_value(
+    container: &Container,
+    validate: bool,
+    type_name: Tokens,
+    ident_case: IdentifierCase,
+) -> Tokens {
+    let container_ident = ContainerIdentifierBuilder::from(container)
+        .ident_case(ident_case)
+        .build();
+
+    if validate {
+        quote!(check_deserialized($type_name, deserialize_$(&container_ident)(d), is_$container_ident))
+    } else {
+        quote!(deserialize_$container_ident(d))
+    }
 }
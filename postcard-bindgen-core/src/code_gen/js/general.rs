@@ -1,8 +1,33 @@
-use genco::quote;
+use genco::{quote, tokens::quoted};
 
-use super::Tokens;
+use crate::{
+    code_gen::utils::{ContainerFullQualifiedTypeBuilder, TokensIterExt},
+    function_args,
+    registry::Container,
+};
+
+use super::{
+    codec::gen_codecs, ExportRegistry, Function, GenerationSettings, OptionalNone, Tokens,
+};
+
+pub fn gen_util(gen_settings: &GenerationSettings) -> Tokens {
+    let none = match gen_settings.optional_none {
+        OptionalNone::Undefined | OptionalNone::Either => "undefined",
+        OptionalNone::Null => "null",
+    };
+    let is_none = match gen_settings.optional_none {
+        OptionalNone::Undefined => quote!(v === undefined),
+        OptionalNone::Null => quote!(v === null),
+        OptionalNone::Either => quote!(v === undefined || v === null),
+    };
+    // Anything else than an array is only accepted if iterable inputs are enabled, the items
+    // of those are not checked to not consume generators.
+    let is_other_iterable = if gen_settings.iterable_inputs {
+        quote!(v !== null && typeof v === "object" && typeof v[Symbol.iterator] === "function")
+    } else {
+        quote!(false)
+    };
 
-pub fn gen_util() -> Tokens {
     quote! {
         const BITS_PER_BYTE = 8, BITS_PER_VARINT_BYTE = 7, U8_BYTES = 1, U16_BYTES = 2, U32_BYTES = 4, U64_BYTES = 8, U128_BYTES = 16
 
@@ -12,5 +37,41 @@ pub fn gen_util() -> Tokens {
         const max_of_last_byte = (n_bytes) => (1 << (n_bytes * BITS_PER_BYTE) % 7) - 1
         const to_number_if_safe = (n) => Number.MAX_SAFE_INTEGER < ((n < 0n) ? -n : n) ? n : Number(n)
         const varint = (n_bytes, n) => { let value = BigInt(n), out = []; for (let i = 0; i < varint_max(n_bytes); i++) { out.push(Number(value & 0xFFn)); if (value < 128n) { return out } out[i] |= 0x80; value >>= 7n } }
+        const NONE = $none, is_none = (v) => $is_none
+        const is_well_formed_string = (str) => { for (let i = 0; i < str.length; i++) { const c = str.charCodeAt(i); if (c >= 0xD800 && c <= 0xDBFF) { const next = str.charCodeAt(i + 1); if (!(next >= 0xDC00 && next <= 0xDFFF)) { return false } i++ } else if (c >= 0xDC00 && c <= 0xDFFF) { return false } } return true }
+        const to_array = (v) => Array.isArray(v) || ArrayBuffer.isView(v) ? v : Array.from(v)
+        const is_array_of = (v, is_item) => Array.isArray(v) ? v.every((item) => is_item(item)) : $is_other_iterable
+        $(gen_codecs(&gen_settings.codecs))
+    }
+}
+
+/// Generates the frozen `TYPES` list of all type names and `isKnownType(name)`, which checks
+/// if a name can be passed to `serialize` and `deserialize`.
+pub fn gen_known_types(
+    defines: impl Iterator<Item = Container>,
+    type_script: bool,
+    export_registry: &mut ExportRegistry,
+) -> Tokens {
+    let names = defines
+        .map(|container| quote!($(quoted(ContainerFullQualifiedTypeBuilder::from(&container).build()))))
+        .join_with_comma();
+
+    export_registry.push("TYPES");
+    export_registry.push("isKnownType");
+
+    let body = quote!(return TYPES.includes(name););
+
+    if type_script {
+        quote! {
+            const TYPES: readonly Type[] = Object.freeze([$names])
+
+            $(Function::new("isKnownType", function_args![("name", "string")], body, "name is Type"))
+        }
+    } else {
+        quote! {
+            const TYPES = Object.freeze([$names])
+
+            $(Function::new_untyped("isKnownType", function_args!["name"], body))
+        }
     }
 }
@@ -1,24 +1,63 @@
 use genco::quote;
 
-use crate::{code_gen::js::Tokens, registry::EnumType};
+use crate::{
+    code_gen::js::{IdentifierCase, Tokens},
+    registry::EnumType,
+};
 
 use super::BindingTypeGenerateable;
 
+// An enum without variants is uninhabited, so there is no value which could be encoded
+// and no bytes which could be decoded.
 impl BindingTypeGenerateable for EnumType {
-    fn gen_ser_body(&self) -> Tokens {
-        quote!($(ser::gen_function(&self.variants)))
+    fn gen_ser_body(&self, ident_case: IdentifierCase) -> Tokens {
+        if self.variants.is_empty() {
+            return quote!(throw "uninhabited enum";);
+        }
+        match &self.tag {
+            Some(tag) => internally_tagged::gen_ser_function(tag, &self.variants, ident_case),
+            None => quote!($(ser::gen_function(&self.variants, ident_case))),
+        }
     }
 
-    fn gen_des_body(&self) -> Tokens {
-        quote!($(des::gen_function(&self.variants)))
+    fn gen_des_body(&self, ident_case: IdentifierCase) -> Tokens {
+        if self.variants.is_empty() {
+            return quote!(throw "uninhabited enum";);
+        }
+        match &self.tag {
+            Some(tag) => internally_tagged::gen_des_function(tag, &self.variants, ident_case),
+            None => quote!($(des::gen_function(&self.variants, ident_case))),
+        }
     }
 
-    fn gen_ty_check_body(&self) -> Tokens {
-        ty_check::gen_check_func(&self.variants)
+    fn gen_ty_check_body(&self, ident_case: IdentifierCase) -> Tokens {
+        if self.variants.is_empty() {
+            return quote!(return false;);
+        }
+        match &self.tag {
+            Some(tag) => internally_tagged::gen_check_func(tag, &self.variants, ident_case),
+            None => ty_check::gen_check_func(&self.variants, ident_case),
+        }
     }
 
     fn gen_ts_typings_body(&self) -> Tokens {
-        ts::gen_typings(&self.variants)
+        if self.variants.is_empty() {
+            return quote!(never);
+        }
+        match &self.tag {
+            Some(tag) => internally_tagged::gen_typings(tag, &self.variants),
+            None => ts::gen_typings(&self.variants),
+        }
+    }
+
+    fn gen_size_hint_body(&self, ident_case: IdentifierCase) -> Tokens {
+        if self.variants.is_empty() {
+            return quote!(return 0;);
+        }
+        match &self.tag {
+            Some(tag) => internally_tagged::gen_size_hint_function(tag, &self.variants, ident_case),
+            None => size_hint::gen_function(&self.variants, ident_case),
+        }
     }
 }
 
@@ -35,20 +74,24 @@ pub mod ser {
         code_gen::{
             js::{
                 generateable::{container::ser, types::JsTypeGenerateable},
-                Case, SwitchCase, VariableAccess, VariablePath, JS_ENUM_VARIANT_KEY,
-                JS_ENUM_VARIANT_VALUE,
+                Case, IdentifierCase, SwitchCase, VariableAccess, VariablePath,
+                JS_ENUM_VARIANT_KEY, JS_ENUM_VARIANT_VALUE,
             },
             switch_case::DefaultCase,
         },
         registry::{EnumVariant, EnumVariantType},
     };
 
-    pub fn gen_function(variants: impl AsRef<[EnumVariant]>) -> impl FormatInto<JavaScript> {
-        let enumerated_variants = variants.as_ref().iter().enumerate();
-
+    pub fn gen_function(
+        variants: impl AsRef<[EnumVariant]>,
+        ident_case: IdentifierCase,
+    ) -> impl FormatInto<JavaScript> {
         let mut switch_case = SwitchCase::new(quote!(v.$JS_ENUM_VARIANT_KEY));
         switch_case.extend_cases(
-            enumerated_variants.map(|(index, variant)| gen_case_for_variant(index, variant)),
+            variants
+                .as_ref()
+                .iter()
+                .map(|variant| gen_case_for_variant(variant, ident_case)),
         );
         switch_case.default_case(DefaultCase::new_without_break(
             quote!(throw "variant not implemented"),
@@ -73,25 +116,25 @@ pub mod ser {
         }
     }
 
-    fn gen_case_for_variant(index: usize, variant: &EnumVariant) -> Case {
-        let variant_name = quoted(variant.name);
+    fn gen_case_for_variant(variant: &EnumVariant, ident_case: IdentifierCase) -> Case {
+        let variant_name = quoted(&variant.name);
         let variable_path = VariablePath::default()
             .modify_push(VariableAccess::Field(JS_ENUM_VARIANT_VALUE.into()));
         let body = match &variant.inner_type {
             EnumVariantType::Empty => CaseBody::None,
             EnumVariantType::Tuple(fields) => CaseBody::Body(match fields.len() {
-                1 => quote!($(fields[0].gen_ser_accessor(variable_path));),
-                _ => ser::gen_accessors_indexed(fields, variable_path),
+                1 => quote!($(fields[0].gen_ser_accessor(variable_path, ident_case));),
+                _ => ser::gen_accessors_indexed(fields, variable_path, ident_case),
             }),
             EnumVariantType::NewType(fields) => {
-                CaseBody::Body(ser::gen_accessors_fields(fields, variable_path))
+                CaseBody::Body(ser::gen_accessors_fields(fields, variable_path, ident_case))
             }
         };
 
         Case::new(
             variant_name,
             quote! {
-                s.serialize_number(U32_BYTES, false, $index);
+                s.serialize_number(U32_BYTES, false, $(variant.index));
                 $body
             },
         )
@@ -110,7 +153,7 @@ pub mod des {
         code_gen::{
             js::{
                 generateable::{container::des, types::JsTypeGenerateable},
-                Case, DefaultCase, FieldAccessor, SwitchCase, JS_ENUM_VARIANT_KEY,
+                Case, DefaultCase, FieldAccessor, IdentifierCase, SwitchCase, JS_ENUM_VARIANT_KEY,
                 JS_ENUM_VARIANT_VALUE,
             },
             utils::{JoinType, TokensIterExt},
@@ -118,12 +161,16 @@ pub mod des {
         registry::{EnumVariant, EnumVariantType},
     };
 
-    pub fn gen_function(variants: impl AsRef<[EnumVariant]>) -> impl FormatInto<JavaScript> {
-        let enumerated_variants = variants.as_ref().iter().enumerate();
-
+    pub fn gen_function(
+        variants: impl AsRef<[EnumVariant]>,
+        ident_case: IdentifierCase,
+    ) -> impl FormatInto<JavaScript> {
         let mut switch_case = SwitchCase::new(quote!(d.deserialize_number(U32_BYTES, false)));
         switch_case.extend_cases(
-            enumerated_variants.map(|(index, variant)| gen_case_for_variant(index, variant)),
+            variants
+                .as_ref()
+                .iter()
+                .map(|variant| gen_case_for_variant(variant, ident_case)),
         );
         switch_case.default_case(DefaultCase::new_without_break(
             quote!(throw "variant not implemented"),
@@ -148,14 +195,16 @@ pub mod des {
         }
     }
 
-    fn gen_case_for_variant(index: usize, variant: &EnumVariant) -> Case {
-        let variant_name = quoted(variant.name);
+    fn gen_case_for_variant(variant: &EnumVariant, ident_case: IdentifierCase) -> Case {
+        let variant_name = quoted(&variant.name);
         let body = match &variant.inner_type {
             EnumVariantType::Empty => CaseBody::None,
-            EnumVariantType::NewType(fields) => CaseBody::Body(des::gen_accessors_fields(fields)),
+            EnumVariantType::NewType(fields) => {
+                CaseBody::Body(des::gen_accessors_fields(fields, ident_case))
+            }
             EnumVariantType::Tuple(fields) => CaseBody::Body(match fields.len() {
-                1 => fields[0].gen_des_accessor(FieldAccessor::None),
-                _ => des::gen_accessors_indexed(fields),
+                1 => fields[0].gen_des_accessor(FieldAccessor::None, ident_case),
+                _ => des::gen_accessors_indexed(fields, ident_case),
             }),
         };
 
@@ -164,7 +213,7 @@ pub mod des {
             .join_with([JoinType::Comma, JoinType::LineBreak]);
 
         Case::new_without_break(
-            index,
+            variant.index,
             quote! {
                 return {
                     $body
@@ -181,80 +230,108 @@ pub mod ty_check {
         code_gen::{
             js::{
                 generateable::{container::ty_check, types::JsTypeGenerateable},
-                VariableAccess, VariablePath, JS_ENUM_VARIANT_KEY, JS_ENUM_VARIANT_VALUE,
-                JS_OBJECT_VARIABLE,
+                Case, DefaultCase, IdentifierCase, SwitchCase, VariableAccess, VariablePath,
+                JS_ENUM_VARIANT_KEY, JS_ENUM_VARIANT_VALUE, JS_OBJECT_VARIABLE,
             },
-            utils::TokensIterExt,
+            utils::break_long_logical_lines,
         },
         registry::{EnumVariant, EnumVariantType},
     };
 
-    pub fn gen_check_func(variants: impl AsRef<[EnumVariant]>) -> Tokens {
-        let enumerated_variants = variants.as_ref().iter().enumerate();
-        let simple_variants = enumerated_variants
-            .to_owned()
-            .filter(|(_, v)| matches!(v.inner_type, EnumVariantType::Empty));
-        let complex_variants = enumerated_variants
-            .to_owned()
-            .filter(|(_, v)| !matches!(v.inner_type, EnumVariantType::Empty));
-
-        let simple_variant_checks = gen_simple_type_checks(simple_variants);
-        let complex_variant_checks = gen_complex_type_checks(complex_variants);
-
-        [simple_variant_checks, complex_variant_checks]
-            .into_iter()
-            .flatten()
-            .join_logic_or()
-    }
-
-    fn gen_simple_type_checks<'a>(
-        variants: impl Iterator<Item = (usize, &'a EnumVariant)> + Clone,
-    ) -> Option<Tokens> {
-        if variants.to_owned().count() == 0 {
-            None
-        } else {
-            let variant_checks = variants
-                .map(|(_, variant)| quote!(v.$JS_ENUM_VARIANT_KEY === $(quoted(variant.name))))
-                .join_logic_or();
-            let type_check = simple_enum_type_check();
-            Some(quote!(($type_check && $variant_checks)))
-        }
-    }
+    pub fn gen_check_func(
+        variants: impl AsRef<[EnumVariant]>,
+        ident_case: IdentifierCase,
+    ) -> Tokens {
+        let mut switch_case = SwitchCase::new(quote!($JS_OBJECT_VARIABLE.$JS_ENUM_VARIANT_KEY));
+        switch_case.extend_cases(
+            variants
+                .as_ref()
+                .iter()
+                .map(|variant| gen_case_for_variant(variant, ident_case)),
+        );
+        switch_case.default_case(DefaultCase::new_without_break(quote!(return false;)));
 
-    fn gen_complex_type_checks<'a>(
-        variants: impl Iterator<Item = (usize, &'a EnumVariant)> + Clone,
-    ) -> Option<Tokens> {
-        if variants.to_owned().count() == 0 {
-            None
-        } else {
-            let variant_checks = variants.map(|(_, variant)| {
-                let inner_type_checks = gen_variant_check(variant);
-                quote!((v.$JS_ENUM_VARIANT_KEY === $(quoted(variant.name)) && $inner_type_checks))
-            }).join_logic_or();
-            let type_check = complex_enum_type_check();
-            Some(quote!(($type_check && $variant_checks)))
+        quote! {
+            if (!($(enum_type_check()))) {
+                return false;
+            }
+            $switch_case
         }
     }
 
-    fn gen_variant_check(variant: &EnumVariant) -> Tokens {
-        let variable_path = VariablePath::new("v".into())
+    fn gen_case_for_variant(variant: &EnumVariant, ident_case: IdentifierCase) -> Case {
+        let variable_path = VariablePath::default()
             .modify_push(VariableAccess::Field(JS_ENUM_VARIANT_VALUE.into()));
-        match &variant.inner_type {
-            EnumVariantType::Empty => unreachable!(),
-            EnumVariantType::NewType(fields) => ty_check::gen_object_checks(fields, variable_path),
-            EnumVariantType::Tuple(fields) => match fields.len() {
-                1 => fields[0].gen_ty_check(variable_path),
-                _ => ty_check::gen_array_checks(fields, variable_path),
-            },
-        }
+        let check = match &variant.inner_type {
+            EnumVariantType::Empty => quote!($variable_path === undefined),
+            EnumVariantType::NewType(fields) => {
+                let checks = ty_check::gen_object_checks(fields, variable_path, ident_case);
+                quote!($(quoted(JS_ENUM_VARIANT_VALUE)) in $JS_OBJECT_VARIABLE && $checks)
+            }
+            EnumVariantType::Tuple(fields) => {
+                let checks = match fields.len() {
+                    1 => fields[0].gen_ty_check(variable_path, ident_case),
+                    _ => ty_check::gen_array_checks(fields, variable_path, ident_case),
+                };
+                quote!($(quoted(JS_ENUM_VARIANT_VALUE)) in $JS_OBJECT_VARIABLE && $checks)
+            }
+        };
+
+        Case::new_without_break(
+            quoted(&variant.name),
+            quote!(return $(break_long_logical_lines(check))),
+        )
     }
 
-    fn simple_enum_type_check() -> Tokens {
+    fn enum_type_check() -> Tokens {
         quote!(typeof $JS_OBJECT_VARIABLE === "object" && $(quoted(JS_ENUM_VARIANT_KEY)) in $JS_OBJECT_VARIABLE)
     }
+}
+
+pub mod size_hint {
+    use genco::{lang::js::Tokens, quote, tokens::quoted};
+
+    use crate::{
+        code_gen::js::{
+            generateable::{container::size_hint, types::JsTypeGenerateable},
+            Case, DefaultCase, IdentifierCase, SwitchCase, VariableAccess, VariablePath,
+            JS_ENUM_VARIANT_KEY, JS_ENUM_VARIANT_VALUE, JS_OBJECT_VARIABLE,
+        },
+        registry::{EnumVariant, EnumVariantType},
+    };
+
+    pub fn gen_function(variants: impl AsRef<[EnumVariant]>, ident_case: IdentifierCase) -> Tokens {
+        let mut switch_case = SwitchCase::new(quote!($JS_OBJECT_VARIABLE.$JS_ENUM_VARIANT_KEY));
+        switch_case.extend_cases(
+            variants
+                .as_ref()
+                .iter()
+                .map(|variant| gen_case_for_variant(variant, ident_case)),
+        );
+        switch_case.default_case(DefaultCase::new_without_break(quote!(return 0;)));
+
+        quote!($switch_case)
+    }
+
+    fn gen_case_for_variant(variant: &EnumVariant, ident_case: IdentifierCase) -> Case {
+        let variable_path = VariablePath::default()
+            .modify_push(VariableAccess::Field(JS_ENUM_VARIANT_VALUE.into()));
+        // The variant index is a varint of at most five bytes.
+        let body = match &variant.inner_type {
+            EnumVariantType::Empty => quote!(return 5;),
+            EnumVariantType::Tuple(fields) => {
+                let hint = match fields.len() {
+                    1 => fields[0].gen_size_hint(variable_path, ident_case),
+                    _ => size_hint::gen_hints_indexed(fields, variable_path, ident_case),
+                };
+                quote!(return 5 + $hint;)
+            }
+            EnumVariantType::NewType(fields) => {
+                quote!(return 5 + $(size_hint::gen_hints_fields(fields, variable_path, ident_case));)
+            }
+        };
 
-    fn complex_enum_type_check() -> Tokens {
-        quote!(typeof $JS_OBJECT_VARIABLE === "object" && $(quoted(JS_ENUM_VARIANT_KEY)) in $JS_OBJECT_VARIABLE && $(quoted(JS_ENUM_VARIANT_VALUE)) in $JS_OBJECT_VARIABLE)
+        Case::new_without_break(quoted(&variant.name), body)
     }
 }
 
@@ -282,7 +359,7 @@ pub mod ts {
     }
 
     fn gen_variant_typings(variant: &EnumVariant) -> Tokens {
-        let name = quoted(variant.name);
+        let name = quoted(&variant.name);
         match &variant.inner_type {
             EnumVariantType::Empty => quote!({ $JS_ENUM_VARIANT_KEY: $name }),
             t => {
@@ -299,3 +376,196 @@ pub mod ts {
         }
     }
 }
+
+/// Enums with `#[serde(tag = "...")]`, whose values are the fields of the variant next to the
+/// tag field with the variant name, e.g. `{ type: "Moved", x: 1 }`.
+///
+/// Serde encodes the variant name as string, followed by the fields. The fields of a newtype
+/// variant are the ones of the struct it contains.
+pub mod internally_tagged {
+    use genco::{prelude::js::Tokens, quote, tokens::quoted};
+
+    use crate::{
+        code_gen::{
+            js::{
+                generateable::{container, types::JsTypeGenerateable},
+                Case, DefaultCase, FieldAccessor, IdentifierCase, PropertyKey, SwitchCase,
+                VariableAccess, VariablePath, JS_OBJECT_VARIABLE,
+            },
+            utils::{break_long_logical_lines, TokensIterExt},
+        },
+        registry::{EnumVariant, EnumVariantType, StructField},
+        type_info::ValueType,
+    };
+
+    /// The fields of a variant next to the tag.
+    pub enum VariantFields<'a> {
+        None,
+        Fields(&'a [StructField]),
+        /// The fields of the struct of a newtype variant.
+        Struct(&'a ValueType),
+    }
+
+    pub fn variant_fields(variant: &EnumVariant) -> VariantFields<'_> {
+        match &variant.inner_type {
+            EnumVariantType::Empty => VariantFields::None,
+            EnumVariantType::NewType(fields) => VariantFields::Fields(fields),
+            EnumVariantType::Tuple(fields) => match fields.as_slice() {
+                [v_type @ ValueType::Object(_)] => VariantFields::Struct(v_type),
+                _ => panic!(
+                    "variant `{}` of an internally tagged enum has to be a unit, struct or newtype variant of a struct",
+                    variant.name
+                ),
+            },
+        }
+    }
+
+    fn tag_path(tag: &str) -> VariablePath {
+        VariablePath::default().modify_push(VariableAccess::Field(tag.into()))
+    }
+
+    pub fn gen_ser_function(
+        tag: &str,
+        variants: &[EnumVariant],
+        ident_case: IdentifierCase,
+    ) -> Tokens {
+        let mut switch_case = SwitchCase::new(tag_path(tag));
+        switch_case.extend_cases(variants.iter().map(|variant| {
+            let body = match variant_fields(variant) {
+                VariantFields::None => quote!(),
+                VariantFields::Fields(fields) => container::ser::gen_accessors_fields(
+                    fields,
+                    VariablePath::default(),
+                    ident_case,
+                ),
+                VariantFields::Struct(v_type) => {
+                    quote!($(v_type.gen_ser_accessor(VariablePath::default(), ident_case));)
+                }
+            };
+            Case::new(
+                quoted(&variant.name),
+                quote! {
+                    s.serialize_string($(quoted(&variant.name)));
+                    $body
+                },
+            )
+        }));
+        switch_case.default_case(DefaultCase::new_without_break(
+            quote!(throw "variant not implemented"),
+        ));
+
+        quote!($switch_case)
+    }
+
+    pub fn gen_des_function(
+        tag: &str,
+        variants: &[EnumVariant],
+        ident_case: IdentifierCase,
+    ) -> Tokens {
+        let mut switch_case = SwitchCase::new(quote!(d.deserialize_string()));
+        switch_case.extend_cases(variants.iter().map(|variant| {
+            let fields = match variant_fields(variant) {
+                VariantFields::None => None,
+                VariantFields::Fields(fields) => {
+                    Some(container::des::gen_accessors_fields(fields, ident_case))
+                }
+                VariantFields::Struct(v_type) => {
+                    Some(v_type.gen_des_accessor(FieldAccessor::None, ident_case))
+                }
+            };
+            let tag = quote!($(PropertyKey(tag)): $(quoted(&variant.name)));
+            Case::new_without_break(
+                quoted(&variant.name),
+                match fields {
+                    None => quote!(return { $tag };),
+                    Some(fields) => quote!(return { $tag, ...$fields };),
+                },
+            )
+        }));
+        switch_case.default_case(DefaultCase::new_without_break(
+            quote!(throw "variant not implemented"),
+        ));
+
+        quote!($switch_case)
+    }
+
+    pub fn gen_check_func(
+        tag: &str,
+        variants: &[EnumVariant],
+        ident_case: IdentifierCase,
+    ) -> Tokens {
+        let mut switch_case = SwitchCase::new(tag_path(tag));
+        switch_case.extend_cases(variants.iter().map(|variant| {
+            let check = match variant_fields(variant) {
+                VariantFields::None => quote!(true),
+                VariantFields::Fields(fields) => container::ty_check::gen_object_checks(
+                    fields,
+                    VariablePath::default(),
+                    ident_case,
+                ),
+                VariantFields::Struct(v_type) => {
+                    v_type.gen_ty_check(VariablePath::default(), ident_case)
+                }
+            };
+            Case::new_without_break(
+                quoted(&variant.name),
+                quote!(return $(break_long_logical_lines(check))),
+            )
+        }));
+        switch_case.default_case(DefaultCase::new_without_break(quote!(return false;)));
+
+        quote! {
+            if (!(typeof $JS_OBJECT_VARIABLE === "object" && $JS_OBJECT_VARIABLE !== null && $(quoted(tag)) in $JS_OBJECT_VARIABLE)) {
+                return false;
+            }
+            $switch_case
+        }
+    }
+
+    pub fn gen_size_hint_function(
+        tag: &str,
+        variants: &[EnumVariant],
+        ident_case: IdentifierCase,
+    ) -> Tokens {
+        let mut switch_case = SwitchCase::new(tag_path(tag));
+        switch_case.extend_cases(variants.iter().map(|variant| {
+            // The name is a string of at most five bytes length prefix and its utf-8 bytes.
+            let name_hint = 5 + variant.name.len();
+            let body = match variant_fields(variant) {
+                VariantFields::None => quote!(return $name_hint;),
+                VariantFields::Fields(fields) => {
+                    let hints = container::size_hint::gen_hints_fields(
+                        fields,
+                        VariablePath::default(),
+                        ident_case,
+                    );
+                    quote!(return $name_hint + $hints;)
+                }
+                VariantFields::Struct(v_type) => {
+                    let hint = v_type.gen_size_hint(VariablePath::default(), ident_case);
+                    quote!(return $name_hint + $hint;)
+                }
+            };
+            Case::new_without_break(quoted(&variant.name), body)
+        }));
+        switch_case.default_case(DefaultCase::new_without_break(quote!(return 0;)));
+
+        quote!($switch_case)
+    }
+
+    pub fn gen_typings(tag: &str, variants: &[EnumVariant]) -> Tokens {
+        variants
+            .iter()
+            .map(|variant| {
+                let tag = quote!({ $(PropertyKey(tag)): $(quoted(&variant.name)) });
+                match variant_fields(variant) {
+                    VariantFields::None => tag,
+                    VariantFields::Fields(fields) => {
+                        quote!($tag & $(container::ts::gen_typings_fields(fields)))
+                    }
+                    VariantFields::Struct(v_type) => quote!($tag & $(v_type.gen_ts_type())),
+                }
+            })
+            .join_with_vertical_line()
+    }
+}
@@ -5,43 +5,45 @@ mod unit_structs;
 
 use genco::prelude::js::Tokens;
 
-use crate::registry::BindingType;
+use crate::{code_gen::js::IdentifierCase, registry::BindingType};
 
 pub trait BindingTypeGenerateable {
-    fn gen_ser_body(&self) -> Tokens;
+    fn gen_ser_body(&self, ident_case: IdentifierCase) -> Tokens;
 
-    fn gen_des_body(&self) -> Tokens;
+    fn gen_des_body(&self, ident_case: IdentifierCase) -> Tokens;
 
-    fn gen_ty_check_body(&self) -> Tokens;
+    fn gen_ty_check_body(&self, ident_case: IdentifierCase) -> Tokens;
 
     fn gen_ts_typings_body(&self) -> Tokens;
+
+    fn gen_size_hint_body(&self, ident_case: IdentifierCase) -> Tokens;
 }
 
 impl BindingTypeGenerateable for BindingType {
-    fn gen_ser_body(&self) -> Tokens {
+    fn gen_ser_body(&self, ident_case: IdentifierCase) -> Tokens {
         match self {
-            Self::Struct(struct_type) => struct_type.gen_ser_body(),
-            Self::UnitStruct(unit_struct_type) => unit_struct_type.gen_ser_body(),
-            Self::TupleStruct(tuple_struct_type) => tuple_struct_type.gen_ser_body(),
-            Self::Enum(enum_type) => enum_type.gen_ser_body(),
+            Self::Struct(struct_type) => struct_type.gen_ser_body(ident_case),
+            Self::UnitStruct(unit_struct_type) => unit_struct_type.gen_ser_body(ident_case),
+            Self::TupleStruct(tuple_struct_type) => tuple_struct_type.gen_ser_body(ident_case),
+            Self::Enum(enum_type) => enum_type.gen_ser_body(ident_case),
         }
     }
 
-    fn gen_des_body(&self) -> Tokens {
+    fn gen_des_body(&self, ident_case: IdentifierCase) -> Tokens {
         match self {
-            Self::Struct(struct_type) => struct_type.gen_des_body(),
-            Self::UnitStruct(unit_struct_type) => unit_struct_type.gen_des_body(),
-            Self::TupleStruct(tuple_struct_type) => tuple_struct_type.gen_des_body(),
-            Self::Enum(enum_type) => enum_type.gen_des_body(),
+            Self::Struct(struct_type) => struct_type.gen_des_body(ident_case),
+            Self::UnitStruct(unit_struct_type) => unit_struct_type.gen_des_body(ident_case),
+            Self::TupleStruct(tuple_struct_type) => tuple_struct_type.gen_des_body(ident_case),
+            Self::Enum(enum_type) => enum_type.gen_des_body(ident_case),
         }
     }
 
-    fn gen_ty_check_body(&self) -> Tokens {
+    fn gen_ty_check_body(&self, ident_case: IdentifierCase) -> Tokens {
         match self {
-            Self::Struct(struct_type) => struct_type.gen_ty_check_body(),
-            Self::UnitStruct(unit_struct_type) => unit_struct_type.gen_ty_check_body(),
-            Self::TupleStruct(tuple_struct_type) => tuple_struct_type.gen_ty_check_body(),
-            Self::Enum(enum_type) => enum_type.gen_ty_check_body(),
+            Self::Struct(struct_type) => struct_type.gen_ty_check_body(ident_case),
+            Self::UnitStruct(unit_struct_type) => unit_struct_type.gen_ty_check_body(ident_case),
+            Self::TupleStruct(tuple_struct_type) => tuple_struct_type.gen_ty_check_body(ident_case),
+            Self::Enum(enum_type) => enum_type.gen_ty_check_body(ident_case),
         }
     }
 
@@ -53,6 +55,17 @@ impl BindingTypeGenerateable for BindingType {
             Self::Enum(enum_type) => enum_type.gen_ts_typings_body(),
         }
     }
+
+    fn gen_size_hint_body(&self, ident_case: IdentifierCase) -> Tokens {
+        match self {
+            Self::Struct(struct_type) => struct_type.gen_size_hint_body(ident_case),
+            Self::UnitStruct(unit_struct_type) => unit_struct_type.gen_size_hint_body(ident_case),
+            Self::TupleStruct(tuple_struct_type) => {
+                tuple_struct_type.gen_size_hint_body(ident_case)
+            }
+            Self::Enum(enum_type) => enum_type.gen_size_hint_body(ident_case),
+        }
+    }
 }
 
 mod ser {
@@ -60,7 +73,10 @@ mod ser {
 
     use crate::{
         code_gen::{
-            js::{generateable::types::JsTypeGenerateable, VariableAccess, VariablePath},
+            js::{
+                codec, generateable::types::JsTypeGenerateable, IdentifierCase, VariableAccess,
+                VariablePath,
+            },
             utils::{JoinType, TokensIterExt},
         },
         registry::StructField,
@@ -70,6 +86,7 @@ mod ser {
     pub fn gen_accessors_indexed(
         fields: impl AsRef<[ValueType]>,
         variable_path: VariablePath,
+        ident_case: IdentifierCase,
     ) -> Tokens {
         let mut body = fields
             .as_ref()
@@ -79,7 +96,7 @@ mod ser {
                 let path = variable_path
                     .to_owned()
                     .modify_push(VariableAccess::Indexed(index));
-                field.gen_ser_accessor(path)
+                field.gen_ser_accessor(path, ident_case)
             })
             .join_with([JoinType::Semicolon, JoinType::LineBreak]);
 
@@ -90,15 +107,22 @@ mod ser {
     pub fn gen_accessors_fields(
         fields: impl AsRef<[StructField]>,
         variable_path: VariablePath,
+        ident_case: IdentifierCase,
     ) -> Tokens {
         let mut body = fields
             .as_ref()
             .iter()
+            .filter(|field| !field.skip)
             .map(|field| {
                 let path = variable_path
                     .to_owned()
-                    .modify_push(VariableAccess::Field(field.name.into()));
-                field.v_type.gen_ser_accessor(path)
+                    .modify_push(VariableAccess::Field(field.name.to_string()));
+                match &field.codec {
+                    Some(codec) => field
+                        .v_type
+                        .gen_ser_accessor(codec::gen_encoded_path(codec, path), ident_case),
+                    None => field.v_type.gen_ser_accessor(path, ident_case),
+                }
             })
             .join_with([JoinType::Semicolon, JoinType::LineBreak]);
 
@@ -112,21 +136,46 @@ mod des {
 
     use crate::{
         code_gen::{
-            js::{generateable::types::JsTypeGenerateable, FieldAccessor},
+            js::{
+                codec,
+                generateable::types::{js_type::gen_default_value, JsTypeGenerateable},
+                FieldAccessor, IdentifierCase,
+            },
             utils::{JoinType, TokensIterExt},
         },
         registry::StructField,
         type_info::ValueType,
     };
 
-    pub fn gen_accessors_fields(fields: impl AsRef<[StructField]>) -> Tokens {
+    pub fn gen_accessors_fields(
+        fields: impl AsRef<[StructField]>,
+        ident_case: IdentifierCase,
+    ) -> Tokens {
         let body = fields
             .as_ref()
             .iter()
             .map(|field| {
-                field
-                    .v_type
-                    .gen_des_accessor(FieldAccessor::Object(field.name))
+                // the value of a field which may be missing at the end of the bytes
+                let missing_value = match &field.default {
+                    _ if !field.has_default => None,
+                    Some(default) => Some(quote!($default)),
+                    None => gen_default_value(&field.v_type),
+                };
+                if field.skip {
+                    let default = field.default.as_deref().unwrap_or("undefined");
+                    quote!($(FieldAccessor::Object(&field.name))$default)
+                } else if let Some(codec) = &field.codec {
+                    let decoded = field.v_type.gen_des_accessor(FieldAccessor::None, ident_case);
+                    quote!($(FieldAccessor::Object(&field.name))$(codec::gen_decoded_value(codec, decoded)))
+                } else if let Some(default) = missing_value {
+                    let value = field.v_type.gen_des_accessor(FieldAccessor::None, ident_case);
+                    quote!($(FieldAccessor::Object(&field.name))d.at_end() ? ($default) : $value)
+                } else {
+                    // without a known default the field has to be encoded, like postcard expects
+                    field
+                        .v_type
+                        .gen_des_accessor(FieldAccessor::Object(&field.name), ident_case)
+                }
             })
             .join_with([JoinType::Comma, JoinType::LineBreak]);
         quote! {
@@ -136,11 +185,14 @@ mod des {
         }
     }
 
-    pub fn gen_accessors_indexed(fields: impl AsRef<[ValueType]>) -> Tokens {
+    pub fn gen_accessors_indexed(
+        fields: impl AsRef<[ValueType]>,
+        ident_case: IdentifierCase,
+    ) -> Tokens {
         let body = fields
             .as_ref()
             .iter()
-            .map(|v_type| v_type.gen_des_accessor(FieldAccessor::Array))
+            .map(|v_type| v_type.gen_des_accessor(FieldAccessor::Array, ident_case))
             .join_with([JoinType::Comma, JoinType::LineBreak]);
         quote! {
             [
@@ -155,7 +207,10 @@ mod ty_check {
 
     use crate::{
         code_gen::{
-            js::{generateable::types::JsTypeGenerateable, VariableAccess, VariablePath},
+            js::{
+                codec, generateable::types::JsTypeGenerateable, IdentifierCase, VariableAccess,
+                VariablePath,
+            },
             utils::TokensIterExt,
         },
         registry::StructField,
@@ -165,23 +220,33 @@ mod ty_check {
     pub fn gen_object_checks(
         fields: impl AsRef<[StructField]>,
         variable_path: VariablePath,
+        ident_case: IdentifierCase,
     ) -> Tokens {
         let field_checks = fields
             .as_ref()
             .iter()
+            .filter(|field| !field.skip)
             .map(|field| {
                 let path = variable_path
                     .to_owned()
-                    .modify_push(VariableAccess::Field(field.name.into()));
-                field.v_type.gen_ty_check(path)
+                    .modify_push(VariableAccess::Field(field.name.to_string()));
+                match &field.codec {
+                    Some(codec) => codec::gen_check(codec, path),
+                    None => field.v_type.gen_ty_check(path, ident_case),
+                }
             })
             .join_logic_and();
-        quote!(typeof $variable_path === "object" && $field_checks)
+        if field_checks.is_empty() {
+            quote!(typeof $variable_path === "object")
+        } else {
+            quote!(typeof $variable_path === "object" && $field_checks)
+        }
     }
 
     pub fn gen_array_checks(
         fields: impl AsRef<[ValueType]>,
         variable_path: VariablePath,
+        ident_case: IdentifierCase,
     ) -> Tokens {
         let arr_len = fields.as_ref().len();
         let field_checks = fields
@@ -192,7 +257,7 @@ mod ty_check {
                 let path = variable_path
                     .to_owned()
                     .modify_push(VariableAccess::Indexed(index));
-                field.gen_ty_check(path)
+                field.gen_ty_check(path, ident_case)
             })
             .join_logic_and();
 
@@ -200,11 +265,81 @@ mod ty_check {
     }
 }
 
+mod size_hint {
+    use genco::{prelude::js::Tokens, quote};
+
+    use crate::{
+        code_gen::{
+            js::{
+                codec, generateable::types::JsTypeGenerateable, IdentifierCase, VariableAccess,
+                VariablePath,
+            },
+            utils::TokensIterExt,
+        },
+        registry::StructField,
+        type_info::ValueType,
+    };
+
+    pub fn gen_hints_indexed(
+        fields: impl AsRef<[ValueType]>,
+        variable_path: VariablePath,
+        ident_case: IdentifierCase,
+    ) -> Tokens {
+        let hints = fields
+            .as_ref()
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let path = variable_path
+                    .to_owned()
+                    .modify_push(VariableAccess::Indexed(index));
+                field.gen_size_hint(path, ident_case)
+            })
+            .join_with_plus();
+        sum_or_zero(hints)
+    }
+
+    pub fn gen_hints_fields(
+        fields: impl AsRef<[StructField]>,
+        variable_path: VariablePath,
+        ident_case: IdentifierCase,
+    ) -> Tokens {
+        let hints = fields
+            .as_ref()
+            .iter()
+            .filter(|field| !field.skip)
+            .map(|field| {
+                let path = variable_path
+                    .to_owned()
+                    .modify_push(VariableAccess::Field(field.name.to_string()));
+                match &field.codec {
+                    Some(codec) => field
+                        .v_type
+                        .gen_size_hint(codec::gen_encoded_path(codec, path), ident_case),
+                    None => field.v_type.gen_size_hint(path, ident_case),
+                }
+            })
+            .join_with_plus();
+        sum_or_zero(hints)
+    }
+
+    fn sum_or_zero(hints: Tokens) -> Tokens {
+        if hints.is_empty() {
+            quote!(0)
+        } else {
+            hints
+        }
+    }
+}
+
 pub mod ts {
     use genco::{prelude::js::Tokens, quote};
 
     use crate::{
-        code_gen::{js::generateable::types::JsTypeGenerateable, utils::TokensIterExt},
+        code_gen::{
+            js::{codec, generateable::types::JsTypeGenerateable, PropertyKey},
+            utils::TokensIterExt,
+        },
         registry::StructField,
         type_info::ValueType,
     };
@@ -222,7 +357,18 @@ pub mod ts {
         let body = fields
             .as_ref()
             .iter()
-            .map(|f| quote!($(f.name): $(f.v_type.gen_ts_type())))
+            .map(|f| {
+                let ts_type = match (f.codec.as_deref(), f.alias.as_deref()) {
+                    (Some(codec), _) => codec::gen_ts_type(codec),
+                    (None, Some(alias)) => quote!($alias),
+                    (None, None) => f.v_type.gen_ts_type(),
+                };
+                if f.skip {
+                    quote!($(PropertyKey(&f.name))?: $ts_type)
+                } else {
+                    quote!($(PropertyKey(&f.name)): $ts_type)
+                }
+            })
             .join_with_comma();
         quote!({ $body })
     }
@@ -1,27 +1,40 @@
 use genco::quote;
 
 use crate::{
-    code_gen::js::{Tokens, VariablePath},
+    code_gen::{
+        js::{IdentifierCase, Tokens, VariablePath},
+        utils::break_long_logical_lines,
+    },
     registry::StructType,
 };
 
-use super::{des, ser, ts, ty_check, BindingTypeGenerateable};
+use super::{des, ser, size_hint, ts, ty_check, BindingTypeGenerateable};
 
 impl BindingTypeGenerateable for StructType {
-    fn gen_ser_body(&self) -> Tokens {
-        ser::gen_accessors_fields(&self.fields, VariablePath::default())
+    fn gen_ser_body(&self, ident_case: IdentifierCase) -> Tokens {
+        ser::gen_accessors_fields(&self.fields, VariablePath::default(), ident_case)
     }
 
-    fn gen_des_body(&self) -> Tokens {
-        let body = des::gen_accessors_fields(&self.fields);
+    fn gen_des_body(&self, ident_case: IdentifierCase) -> Tokens {
+        let body = des::gen_accessors_fields(&self.fields, ident_case);
         quote!(return $body;)
     }
 
-    fn gen_ty_check_body(&self) -> Tokens {
-        ty_check::gen_object_checks(&self.fields, VariablePath::default())
+    fn gen_ty_check_body(&self, ident_case: IdentifierCase) -> Tokens {
+        let body = break_long_logical_lines(ty_check::gen_object_checks(
+            &self.fields,
+            VariablePath::default(),
+            ident_case,
+        ));
+        quote!(return $body)
     }
 
     fn gen_ts_typings_body(&self) -> Tokens {
         ts::gen_typings_fields(&self.fields)
     }
+
+    fn gen_size_hint_body(&self, ident_case: IdentifierCase) -> Tokens {
+        let body = size_hint::gen_hints_fields(&self.fields, VariablePath::default(), ident_case);
+        quote!(return $body;)
+    }
 }
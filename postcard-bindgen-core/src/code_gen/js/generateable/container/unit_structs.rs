@@ -1,27 +1,37 @@
 use genco::quote;
 
 use crate::{
-    code_gen::js::{Tokens, JS_OBJECT_VARIABLE},
+    code_gen::{
+        js::{IdentifierCase, Tokens, JS_OBJECT_VARIABLE},
+        utils::break_long_logical_lines,
+    },
     registry::UnitStructType,
 };
 
 use super::{des, ts, BindingTypeGenerateable};
 
 impl BindingTypeGenerateable for UnitStructType {
-    fn gen_ser_body(&self) -> Tokens {
+    fn gen_ser_body(&self, _ident_case: IdentifierCase) -> Tokens {
         quote!()
     }
 
-    fn gen_des_body(&self) -> Tokens {
-        let body = des::gen_accessors_fields([]);
+    fn gen_des_body(&self, ident_case: IdentifierCase) -> Tokens {
+        let body = des::gen_accessors_fields([], ident_case);
         quote!(return $body;)
     }
 
-    fn gen_ty_check_body(&self) -> Tokens {
-        quote!(typeof $JS_OBJECT_VARIABLE === "object" && Object.keys($JS_OBJECT_VARIABLE).length === 0)
+    fn gen_ty_check_body(&self, _ident_case: IdentifierCase) -> Tokens {
+        let body = break_long_logical_lines(
+            quote!(typeof $JS_OBJECT_VARIABLE === "object" && Object.keys($JS_OBJECT_VARIABLE).length === 0),
+        );
+        quote!(return $body)
     }
 
     fn gen_ts_typings_body(&self) -> Tokens {
         ts::gen_typings_fields([])
     }
+
+    fn gen_size_hint_body(&self, _ident_case: IdentifierCase) -> Tokens {
+        quote!(return 0;)
+    }
 }
@@ -1,49 +1,87 @@
 pub mod container;
 pub mod types;
 
+use std::collections::BTreeMap;
+
 use container::BindingTypeGenerateable;
 use genco::{quote, quote_in, tokens::quoted};
+use types::JsTypeGenerateable;
 
 use crate::{
     code_gen::{
-        js::Tokens,
+        js::{codec::gen_codecs_decl, GenerationSettings, OptionalNone, Tokens},
         utils::{ContainerFullQualifiedTypeBuilder, TokensIterExt},
     },
     registry::{Container, ContainerCollection, Module},
+    type_info::ValueType,
 };
 
-pub fn gen_ts_typings(containers: &ContainerCollection) -> Tokens {
+pub fn gen_ts_typings(
+    containers: &ContainerCollection,
+    gen_settings: &GenerationSettings,
+) -> Tokens {
+    quote!(
+        $(gen_ts_type_decls(containers, gen_settings))
+
+        $(gen_ser_des_decls(gen_settings))
+        $(if gen_settings.ser && gen_settings.runtime_type_checks {
+            export function serializeAny(value: ValueType<Type>): Uint8Array
+        })
+        $(if gen_settings.ser && gen_settings.des && gen_settings.self_test {
+            export function __selfTest(): void
+        })
+    )
+}
+
+/// Generates the type declarations of all containers without the declarations
+/// of the `serialize` and `deserialize` functions.
+pub fn gen_ts_type_decls(
+    containers: &ContainerCollection,
+    gen_settings: &GenerationSettings,
+) -> Tokens {
     quote!(
         $(gen_number_decls())
+        $(gen_none_decl(gen_settings.optional_none))
+        $(gen_codecs_decl(&gen_settings.codecs))
 
         $(gen_extra_types_decls())
 
+        $(gen_alias_decls(containers))
         $(gen_bindings_types(containers))
 
         $(gen_type_decl(containers.all_containers()))
         $(gen_value_type_decl(containers.all_containers()))
-
-        $(gen_ser_des_decls())
     )
 }
 
 fn gen_number_decls() -> Tokens {
+    // Integers beyond `Number.MAX_SAFE_INTEGER` are deserialized as `BigInt`, they can be
+    // passed as `BigInt` to the serializer as well.
     quote!(
         declare type u8 = number
         declare type u16 = number
         declare type u32 = number
-        declare type u64 = number
-        declare type u128 = number
-        declare type usize = number
+        declare type u64 = number | bigint
+        declare type u128 = number | bigint
+        declare type usize = number | bigint
         declare type i8 = number
         declare type i16 = number
         declare type i32 = number
-        declare type i64 = number
-        declare type i128 = number
-        declare type isize = number
+        declare type i64 = number | bigint
+        declare type i128 = number | bigint
+        declare type isize = number | bigint
     )
 }
 
+fn gen_none_decl(optional_none: OptionalNone) -> Tokens {
+    let none = match optional_none {
+        OptionalNone::Undefined => quote!(undefined),
+        OptionalNone::Null => quote!(null),
+        OptionalNone::Either => quote!(undefined | null),
+    };
+    quote!(declare type none = $none)
+}
+
 fn gen_extra_types_decls() -> Tokens {
     quote!(
         declare type ArrayLengthMutationKeys = "splice" | "push" | "pop" | "shift" |  "unshift"
@@ -57,6 +95,47 @@ fn gen_extra_types_decls() -> Tokens {
     )
 }
 
+/// Declarations of the type aliases fields are registered with, e.g. `export type NodeId = u32`.
+///
+/// Panics if an alias is used for different types or has the name of a container.
+fn gen_alias_decls(containers: &ContainerCollection) -> Tokens {
+    let mut aliases = BTreeMap::<String, ValueType>::new();
+    for container in containers.all_containers() {
+        for field in container.named_fields() {
+            let Some(alias) = &field.alias else {
+                continue;
+            };
+            let v_type = aliases
+                .entry(alias.to_string())
+                .or_insert(field.v_type.clone());
+            assert!(
+                *v_type == field.v_type,
+                "type alias `{alias}` of field `{}` in `{}` is used for different types",
+                field.name,
+                container.name
+            );
+        }
+    }
+
+    // the aliases are declared at the root, next to the containers of the root module
+    let (root_containers, _) = containers.containers_per_module();
+    for container in root_containers {
+        assert!(
+            !aliases.contains_key(container.name.as_ref()),
+            "type alias `{}` has the name of a type",
+            container.name
+        );
+    }
+
+    if aliases.is_empty() {
+        return Tokens::new();
+    }
+    aliases
+        .into_iter()
+        .map(|(alias, v_type)| quote!(export type $alias = $(v_type.gen_ts_type())))
+        .join_with_line_breaks()
+}
+
 fn gen_type_decl(bindings: impl Iterator<Item = Container>) -> Tokens {
     let type_cases = bindings
         .map(|container| quote!($(quoted(ContainerFullQualifiedTypeBuilder::from(&container).build()))))
@@ -74,10 +153,23 @@ fn gen_value_type_decl(bindings: impl Iterator<Item = Container>) -> Tokens {
     quote!(declare type ValueType<T extends Type> = $if_cases : void)
 }
 
-fn gen_ser_des_decls() -> Tokens {
+/// Declarations of the functions exported by the generated module, only the enabled ones
+/// are declared.
+fn gen_ser_des_decls(gen_settings: &GenerationSettings) -> Tokens {
     quote!(
-        export function serialize<T extends Type>(type: T, value: ValueType<T>): u8[]
-        export function deserialize<T extends Type>(type: T, bytes: u8[]): ValueType<T>
+        $(if gen_settings.ser {
+            export function serialize<T extends Type>(type: T, value: ValueType<T>): Uint8Array
+            export function encodedLength<T extends Type>(type: T, value: ValueType<T>): number
+        })
+        $(if gen_settings.des {
+            export class DeserializationError extends Error {
+                kind: "trailing_bytes" | "invalid_value"
+            }
+            export function deserialize<T extends Type>(type: T, bytes: Uint8Array | u8[]): ValueType<T>
+            export function deserializeLenient<T extends Type>(type: T, bytes: Uint8Array | u8[]): ValueType<T>
+        })
+        export const TYPES: readonly Type[]
+        export function isKnownType(name: string): name is Type
     )
 }
 
@@ -128,7 +220,7 @@ fn create_namespace(tokens: &mut Tokens, r#mod: Module<'_>) {
 }
 
 fn gen_binding_type(binding: &Container) -> Tokens {
-    let name = binding.name;
+    let name = &binding.name;
     let body = binding.r#type.gen_ts_typings_body();
     quote!(export type $name = $body)
 }
@@ -139,17 +231,21 @@ mod test {
 
     use crate::{
         code_gen::{
-            js::generateable::{container::BindingTypeGenerateable, types::JsTypeGenerateable},
-            utils::assert_tokens,
+            js::{
+                generateable::{container::BindingTypeGenerateable, types::JsTypeGenerateable},
+                IdentifierCase,
+            },
+            utils::{assert_tokens, break_long_logical_lines},
         },
         path::Path,
         registry::{
-            BindingType, Container, EnumType, EnumVariant, EnumVariantType, StructField, StructType,
+            BindingType, BindingsRegistry, Container, EnumType, EnumVariant, EnumVariantType,
+            StructField, StructFields, StructType, TupleFields,
         },
         type_info::{ArrayMeta, NumberMeta, ObjectMeta, OptionalMeta, StringMeta, ValueType},
     };
 
-    use super::gen_binding_type;
+    use super::{gen_alias_decls, gen_binding_type};
 
     #[test]
     fn test_js_type_with_number_typings() {
@@ -183,6 +279,8 @@ mod test {
             let ty = ValueType::Number(NumberMeta::Integer {
                 bytes: assertion.0 .0,
                 signed: assertion.0 .1,
+                pointer_sized: false,
+                non_zero: false,
             });
             assert_tokens(quote!($(ty.gen_ts_type())), assertion.1);
         }
@@ -192,11 +290,20 @@ mod test {
                 items_type: Box::new(ValueType::Number(NumberMeta::Integer {
                     bytes: assertion.0 .0,
                     signed: assertion.0 .1,
+                    pointer_sized: false,
+                    non_zero: false,
                 })),
                 length: None,
+                capacity: None,
+                format: None,
             });
 
-            assert_tokens(quote!($(ty.gen_ts_type())), quote!($(assertion.1)[]));
+            // byte vectors are exposed as typed array
+            if *assertion.0 == (1, false) {
+                assert_tokens(quote!($(ty.gen_ts_type())), quote!(Uint8Array));
+            } else {
+                assert_tokens(quote!($(ty.gen_ts_type())), quote!($(assertion.1)[]));
+            }
         }
 
         for assertion in assert_combs {
@@ -204,25 +311,28 @@ mod test {
                 inner: Box::new(ValueType::Number(NumberMeta::Integer {
                     bytes: assertion.0 .0,
                     signed: assertion.0 .1,
+                    pointer_sized: false,
+                    non_zero: false,
                 })),
             });
 
-            assert_tokens(
-                quote!($(ty.gen_ts_type())),
-                quote!($(assertion.1) | undefined),
-            );
+            assert_tokens(quote!($(ty.gen_ts_type())), quote!($(assertion.1) | none));
         }
     }
 
     #[test]
     fn test_js_type_without_number_typings() {
         let ty = ValueType::Object(ObjectMeta {
-            name: "A",
+            name: "A".into(),
             path: Path::new("", "::"),
         });
         assert_tokens(quote!($(ty.gen_ts_type())), quote!(A));
 
-        let ty = ValueType::String(StringMeta {});
+        let ty = ValueType::String(StringMeta {
+            char: false,
+            capacity: None,
+            format: None,
+        });
         assert_tokens(quote!($(ty.gen_ts_type())), quote!(string));
     }
 
@@ -231,41 +341,78 @@ mod test {
         let tokens = StructType {
             fields: vec![
                 StructField {
-                    name: "a",
+                    name: "a".into(),
                     v_type: ValueType::Number(NumberMeta::Integer {
                         bytes: 1,
                         signed: false,
+                        pointer_sized: false,
+                        non_zero: false,
                     }),
+                    skip: false,
+                    default: None,
+                    codec: None,
+                    alias: None,
+                    has_default: false,
                 },
                 StructField {
-                    name: "b",
+                    name: "b".into(),
                     v_type: ValueType::Object(ObjectMeta {
-                        name: "B",
+                        name: "B".into(),
                         path: Path::new("", "::"),
                     }),
+                    skip: false,
+                    default: None,
+                    codec: None,
+                    alias: None,
+                    has_default: false,
                 },
                 StructField {
-                    name: "c",
-                    v_type: ValueType::String(StringMeta {}),
+                    name: "c".into(),
+                    v_type: ValueType::String(StringMeta {
+                        char: false,
+                        capacity: None,
+                        format: None,
+                    }),
+                    skip: false,
+                    default: None,
+                    codec: None,
+                    alias: None,
+                    has_default: false,
                 },
                 StructField {
-                    name: "d",
+                    name: "d".into(),
                     v_type: ValueType::Array(ArrayMeta {
                         items_type: Box::new(ValueType::Number(NumberMeta::Integer {
                             bytes: 1,
                             signed: false,
+                            pointer_sized: false,
+                            non_zero: false,
                         })),
                         length: None,
+                        capacity: None,
+                        format: None,
                     }),
+                    skip: false,
+                    default: None,
+                    codec: None,
+                    alias: None,
+                    has_default: false,
                 },
                 StructField {
-                    name: "e",
+                    name: "e".into(),
                     v_type: ValueType::Optional(OptionalMeta {
                         inner: Box::new(ValueType::Number(NumberMeta::Integer {
                             bytes: 1,
                             signed: false,
+                            pointer_sized: false,
+                            non_zero: false,
                         })),
                     }),
+                    skip: false,
+                    default: None,
+                    codec: None,
+                    alias: None,
+                    has_default: false,
                 },
             ],
         }
@@ -273,22 +420,103 @@ mod test {
 
         assert_tokens(
             tokens,
-            quote!({ a: u8, b: B, c: string, d: u8[], e: u8 | undefined }),
+            quote!({ a: u8, b: B, c: string, d: Uint8Array, e: u8 | none }),
         )
     }
 
+    #[test]
+    fn test_struct_skipped_field() {
+        let struct_type = StructType {
+            fields: vec![
+                StructField {
+                    name: "a".into(),
+                    v_type: ValueType::Number(NumberMeta::Integer {
+                        bytes: 1,
+                        signed: false,
+                        pointer_sized: false,
+                        non_zero: false,
+                    }),
+                    skip: false,
+                    default: None,
+                    codec: None,
+                    alias: None,
+                    has_default: false,
+                },
+                StructField {
+                    name: "b".into(),
+                    v_type: ValueType::String(StringMeta {
+                        char: false,
+                        capacity: None,
+                        format: None,
+                    }),
+                    skip: true,
+                    default: None,
+                    codec: None,
+                    alias: None,
+                    has_default: false,
+                },
+            ],
+        };
+
+        assert_tokens(
+            struct_type.gen_ser_body(IdentifierCase::default()),
+            quote!(s.serialize_number(U8_BYTES, false, v.a);),
+        );
+        assert_tokens(
+            struct_type.gen_des_body(IdentifierCase::default()),
+            quote! {
+                return {
+                    a: d.deserialize_number(U8_BYTES, false),
+                    b: undefined
+                };
+            },
+        );
+        assert_tokens(
+            struct_type.gen_ty_check_body(IdentifierCase::default()),
+            quote!(return $(break_long_logical_lines(quote!(typeof v === "object" && check_integer_type(v.a, U8_BYTES, false))))),
+        );
+        assert_tokens(
+            struct_type.gen_ts_typings_body(),
+            quote!({ a: u8, b?: string }),
+        );
+    }
+
+    #[test]
+    fn test_struct_skipped_field_default() {
+        let mut struct_type = StructType::new();
+        struct_type.register_field::<Option<u8>>("a");
+        struct_type.register_skipped_field_with_default::<u8>("b", "42");
+
+        assert_tokens(
+            struct_type.gen_des_body(IdentifierCase::default()),
+            quote! {
+                return {
+                    a: (d.deserialize_number(U32_BYTES, false) === 0) ? NONE : d.deserialize_number(U8_BYTES, false),
+                    b: 42
+                };
+            },
+        );
+    }
+
     #[test]
     fn test_struct_typings() {
         let test_binding = gen_binding_type(&Container {
-            name: "A",
+            name: "A".into(),
             path: Path::new("", "::"),
             r#type: BindingType::Struct(StructType {
                 fields: vec![StructField {
-                    name: "a",
+                    name: "a".into(),
                     v_type: ValueType::Number(NumberMeta::Integer {
                         bytes: 1,
                         signed: false,
+                        pointer_sized: false,
+                        non_zero: false,
                     }),
+                    skip: false,
+                    default: None,
+                    codec: None,
+                    alias: None,
+                    has_default: false,
                 }],
             }),
         });
@@ -299,26 +527,29 @@ mod test {
     #[test]
     fn test_enum_typings() {
         let test_binding = gen_binding_type(&Container {
-            name: "A",
+            name: "A".into(),
             path: Path::new("", "::"),
             r#type: BindingType::Enum(EnumType {
                 variants: vec![
                     EnumVariant {
-                        name: "A",
+                        name: "A".into(),
                         index: 0,
                         inner_type: EnumVariantType::Empty,
                     },
                     EnumVariant {
-                        name: "B",
+                        name: "B".into(),
                         index: 1,
                         inner_type: EnumVariantType::Tuple(vec![ValueType::Number(
                             NumberMeta::Integer {
                                 bytes: 1,
                                 signed: false,
+                                pointer_sized: false,
+                                non_zero: false,
                             },
                         )]),
                     },
                 ],
+                tag: None,
             }),
         });
 
@@ -327,4 +558,200 @@ mod test {
             quote!(export type A = { tag: "A" } | { tag: "B", value: u8 }),
         )
     }
+
+    #[test]
+    fn test_enum_union_typings() {
+        let mut fields = StructFields::default();
+        fields.register_field::<&str>("a");
+        fields.register_field::<u16>("b");
+
+        let mut enum_type = EnumType::new();
+        enum_type.register_variant("A");
+        enum_type.register_variant_tuple("B", {
+            let mut fields = TupleFields::default();
+            fields.register_field::<u8>();
+            fields
+        });
+        enum_type.register_variant_tuple("C", {
+            let mut fields = TupleFields::default();
+            fields.register_field::<u8>();
+            fields.register_field::<&str>();
+            fields
+        });
+        enum_type.register_unnamed_struct("D", fields);
+
+        assert_tokens(
+            enum_type.gen_ts_typings_body(),
+            quote!({ tag: "A" } | { tag: "B", value: u8 } | { tag: "C", value: [u8, string] } | { tag: "D", value: { a: string, b: u16 } }),
+        );
+    }
+
+    #[test]
+    fn test_enum_with_nested_enum() {
+        let enum_type = EnumType {
+            variants: vec![EnumVariant {
+                name: "Wrap".into(),
+                index: 0,
+                inner_type: EnumVariantType::Tuple(vec![ValueType::Object(ObjectMeta {
+                    name: "Inner".into(),
+                    path: Path::new("", "::"),
+                })]),
+            }],
+            tag: None,
+        };
+
+        assert_tokens(
+            enum_type.gen_ser_body(IdentifierCase::default()),
+            quote! {
+                switch (v.tag) {
+                case "Wrap":
+                    s.serialize_number(U32_BYTES, false, 0);
+                    serialize_INNER(s, v.value);
+                    break;
+                default:
+                    throw "variant not implemented"
+                }
+            },
+        );
+        assert_tokens(
+            enum_type.gen_des_body(IdentifierCase::default()),
+            quote! {
+                switch (d.deserialize_number(U32_BYTES, false)) {
+                case 0:
+                    return {
+                        tag: "Wrap",
+                        value: deserialize_INNER(d)
+                    };
+                default:
+                    throw "variant not implemented"
+                }
+            },
+        );
+    }
+
+    #[test]
+    fn test_struct_size_hint() {
+        let mut struct_type = StructType::new();
+        struct_type.register_field::<u8>("a");
+        struct_type.register_field::<u32>("b");
+        struct_type.register_field::<&str>("c");
+        struct_type.register_field::<Vec<u16>>("d");
+        struct_type.register_field::<Option<[f32; 2]>>("e");
+        struct_type.register_skipped_field::<u64>("f");
+
+        assert_tokens(
+            struct_type.gen_size_hint_body(IdentifierCase::default()),
+            quote!(return 1 + 5 + (5 + v.c.length * 3) + (5 + (v.d.length ?? 0) * 3) + (1 + (is_none(v.e) ? 0 : 8));),
+        );
+    }
+
+    #[test]
+    fn test_enum_size_hint() {
+        let mut enum_type = EnumType::new();
+        enum_type.register_variant("A");
+        enum_type.register_variant_tuple("B", {
+            let mut fields = TupleFields::default();
+            fields.register_field::<u64>();
+            fields.register_field::<bool>();
+            fields
+        });
+
+        assert_tokens(
+            enum_type.gen_size_hint_body(IdentifierCase::default()),
+            quote! {
+                switch (v.tag) {
+                case "A":
+                    return 5;
+                case "B":
+                    return 5 + 10 + 1;
+                default:
+                    return 0;
+                }
+            },
+        );
+    }
+
+    #[test]
+    fn test_empty_enum() {
+        let enum_type = EnumType::new();
+
+        assert_tokens(
+            enum_type.gen_ser_body(IdentifierCase::default()),
+            quote!(throw "uninhabited enum";),
+        );
+        assert_tokens(
+            enum_type.gen_des_body(IdentifierCase::default()),
+            quote!(throw "uninhabited enum";),
+        );
+        assert_tokens(
+            enum_type.gen_ty_check_body(IdentifierCase::default()),
+            quote!(return false;),
+        );
+
+        let test_binding = gen_binding_type(&Container {
+            name: "Never".into(),
+            path: Path::new("", "::"),
+            r#type: BindingType::Enum(enum_type),
+        });
+        assert_tokens(test_binding, quote!(export type Never = never));
+    }
+
+    #[test]
+    fn test_struct_field_codec() {
+        let mut struct_type = StructType::new();
+        struct_type.register_field_with_codec::<u64>("a", "date");
+
+        assert_tokens(
+            struct_type.gen_ser_body(IdentifierCase::default()),
+            quote!(s.serialize_number(U64_BYTES, false, CODECS["date"].serialize(v.a));),
+        );
+        assert_tokens(
+            struct_type.gen_des_body(IdentifierCase::default()),
+            quote! {
+                return {
+                    a: CODECS["date"].deserialize(d.deserialize_number(U64_BYTES, false))
+                };
+            },
+        );
+        assert_tokens(
+            struct_type.gen_ty_check_body(IdentifierCase::default()),
+            quote!(return $(break_long_logical_lines(quote!(typeof v === "object" && CODECS["date"].check(v.a))))),
+        );
+        assert_tokens(
+            struct_type.gen_ts_typings_body(),
+            quote!({ a: Codecs["date"] }),
+        );
+    }
+
+    #[test]
+    fn test_struct_field_alias() {
+        let mut struct_type = StructType::new();
+        struct_type.register_field_with_alias::<u32>("from", "NodeId");
+        struct_type.register_field_with_alias::<u32>("to", "NodeId");
+        struct_type.register_field::<u32>("weight");
+
+        assert_tokens(
+            struct_type.gen_ts_typings_body(),
+            quote!({ from: NodeId, to: NodeId, weight: u32 }),
+        );
+
+        let mut registry = BindingsRegistry::default();
+        registry.register_struct_binding("Edge", "", struct_type);
+        assert_tokens(
+            gen_alias_decls(&registry.into_entries()),
+            quote!(export type NodeId = u32),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "type alias `Id` of field `b` in `A` is used for different types")]
+    fn test_struct_field_alias_of_different_types() {
+        let mut struct_type = StructType::new();
+        struct_type.register_field_with_alias::<u32>("a", "Id");
+        struct_type.register_field_with_alias::<String>("b", "Id");
+
+        let mut registry = BindingsRegistry::default();
+        registry.register_struct_binding("A", "", struct_type);
+        gen_alias_decls(&registry.into_entries());
+    }
 }
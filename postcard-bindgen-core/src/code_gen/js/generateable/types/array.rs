@@ -1,45 +1,179 @@
-use genco::{prelude::js::Tokens, quote};
-
-use crate::{
-    code_gen::js::{FieldAccessor, VariablePath, JS_OBJECT_VARIABLE},
-    type_info::ArrayMeta,
-};
-
-use super::JsTypeGenerateable;
-
-impl JsTypeGenerateable for ArrayMeta {
-    fn gen_ser_accessor(&self, variable_path: VariablePath) -> Tokens {
-        let inner_type_accessor = self.items_type.gen_ser_accessor(VariablePath::default());
-        if let Some(len) = self.length {
-            quote!(s.serialize_array((s, $JS_OBJECT_VARIABLE) => $inner_type_accessor, $variable_path, $len))
-        } else {
-            quote!(s.serialize_array((s, $JS_OBJECT_VARIABLE) => $inner_type_accessor, $variable_path))
-        }
-    }
-
-    fn gen_des_accessor(&self, field_accessor: FieldAccessor) -> Tokens {
-        let inner_type_accessor = self.items_type.gen_des_accessor(FieldAccessor::Array);
-        if let Some(len) = self.length {
-            quote!($(field_accessor)d.deserialize_array(() => $inner_type_accessor, $len))
-        } else {
-            quote!($(field_accessor)d.deserialize_array(() => $inner_type_accessor))
-        }
-    }
-
-    fn gen_ty_check(&self, variable_path: VariablePath) -> Tokens {
-        let item_ty_check = quote!($(variable_path.clone()).every(($JS_OBJECT_VARIABLE) => $(self.items_type.gen_ty_check(VariablePath::default()))));
-        if let Some(len) = self.length {
-            quote!(Array.isArray($(variable_path.clone())) && $item_ty_check && $variable_path.length === $len)
-        } else {
-            quote!(Array.isArray($variable_path) && $item_ty_check)
-        }
-    }
-
-    fn gen_ts_type(&self) -> Tokens {
-        if let Some(len) = self.length {
-            quote!(FixedLengthArray<$(self.items_type.gen_ts_type()), $len>)
-        } else {
-            quote!($(self.items_type.gen_ts_type())[])
-        }
-    }
-}
+use genco::{prelude::js::Tokens, quote};
+
+use crate::{
+    code_gen::js::{FieldAccessor, IdentifierCase, VariablePath, JS_OBJECT_VARIABLE},
+    type_info::{ArrayMeta, NumberMeta, ValueType},
+};
+
+use super::{bool::bool_to_js_bool, JsTypeGenerateable};
+
+impl JsTypeGenerateable for ArrayMeta {
+    fn gen_ser_accessor(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
+        if self.is_bytes() {
+            return quote!(s.serialize_bytes($variable_path));
+        }
+
+        // Arrays of numbers are written in a single loop inside the serializer
+        // to avoid a closure call per element.
+        if let ValueType::Number(number_meta) = self.items_type.as_ref() {
+            let byte_amount_str = number_meta.as_byte_string();
+            let len = self.length.map(|len| quote!(, $len));
+            return match number_meta {
+                NumberMeta::FloatingPoint { .. } => {
+                    quote!(s.serialize_number_float_array($byte_amount_str, $variable_path$len))
+                }
+                NumberMeta::Integer { signed, .. } => {
+                    let signed = bool_to_js_bool(*signed);
+                    quote!(s.serialize_number_array($byte_amount_str, $signed, $variable_path$len))
+                }
+            };
+        }
+
+        // The accessor of optionals and tuples are statements, so the closure needs a block.
+        let inner_type_accessor = self
+            .items_type
+            .gen_ser_accessor(VariablePath::default(), ident_case);
+        let len = self.length.map(|len| quote!(, $len));
+        quote! {
+            s.serialize_array((s, $JS_OBJECT_VARIABLE) => {
+                $inner_type_accessor
+            }, $variable_path$len)
+        }
+    }
+
+    fn gen_des_accessor(
+        &self,
+        field_accessor: FieldAccessor,
+        ident_case: IdentifierCase,
+    ) -> Tokens {
+        if self.is_bytes() {
+            return quote!($(field_accessor)d.deserialize_bytes());
+        }
+
+        if let ValueType::Number(number_meta) = self.items_type.as_ref() {
+            let byte_amount_str = number_meta.as_byte_string();
+            let len = self.length.map(|len| quote!(, $len));
+            let typed_array = self.typed_array().map(|t| quote!(, $t));
+            return match number_meta {
+                NumberMeta::FloatingPoint { .. } => {
+                    quote!($(field_accessor)d.deserialize_number_float_array($byte_amount_str$len$typed_array))
+                }
+                NumberMeta::Integer { signed, .. } => {
+                    let signed = bool_to_js_bool(*signed);
+                    quote!($(field_accessor)d.deserialize_number_array($byte_amount_str, $signed$len$typed_array))
+                }
+            };
+        }
+
+        let inner_type_accessor = self
+            .items_type
+            .gen_des_accessor(FieldAccessor::Array, ident_case);
+        if let Some(len) = self.length {
+            quote!($(field_accessor)d.deserialize_array(() => $inner_type_accessor, $len))
+        } else {
+            quote!($(field_accessor)d.deserialize_array(() => $inner_type_accessor))
+        }
+    }
+
+    fn gen_ty_check(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
+        if self.is_bytes() {
+            let length = match (self.format, self.capacity) {
+                (Some(format), _) => {
+                    Some(quote!( && $(variable_path.clone()).length === $(format.length())))
+                }
+                (None, Some(capacity)) => {
+                    Some(quote!( && $(variable_path.clone()).length <= $capacity))
+                }
+                (None, None) => None,
+            };
+            return quote!(($(variable_path.clone()) instanceof Uint8Array || is_array_of($(variable_path.clone()), ($JS_OBJECT_VARIABLE) => $(self.items_type.gen_ty_check(VariablePath::default(), ident_case))))$length);
+        }
+
+        let item_ty_check = quote!($(variable_path.clone()).every(($JS_OBJECT_VARIABLE) => $(self.items_type.gen_ty_check(VariablePath::default(), ident_case))));
+        if let Some(typed_array) = self.typed_array() {
+            quote!((Array.isArray($(variable_path.clone())) || $(variable_path.clone()) instanceof $typed_array) && $item_ty_check && $variable_path.length === $(self.length))
+        } else if let Some(len) = self.length {
+            quote!(Array.isArray($(variable_path.clone())) && $item_ty_check && $variable_path.length === $len)
+        } else if let Some(capacity) = self.capacity {
+            quote!(is_array_of($(variable_path.clone()), ($JS_OBJECT_VARIABLE) => $(self.items_type.gen_ty_check(VariablePath::default(), ident_case))) && $variable_path.length <= $capacity)
+        } else {
+            quote!(is_array_of($variable_path, ($JS_OBJECT_VARIABLE) => $(self.items_type.gen_ty_check(VariablePath::default(), ident_case))))
+        }
+    }
+
+    fn gen_size_hint(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
+        // Fixed size arrays have no length prefix. Iterables without a length, which are
+        // converted at serialization, count as empty so generators are not consumed.
+        let item_bytes = match self.items_type.as_ref() {
+            ValueType::Number(number_meta) => Some(number_meta.max_encoded_len()),
+            ValueType::Bool(_) => Some(1),
+            _ => None,
+        };
+        match (self.length, item_bytes) {
+            (Some(len), Some(item_bytes)) => quote!($(len * item_bytes)),
+            (None, Some(item_bytes)) => quote!((5 + ($variable_path.length ?? 0) * $item_bytes)),
+            (len, None) => {
+                let prefix = if len.is_some() { 0 } else { 5 };
+                let item_size_hint = self
+                    .items_type
+                    .gen_size_hint(VariablePath::default(), ident_case);
+                quote!(($prefix + Array.prototype.reduce.call($variable_path, (acc, $JS_OBJECT_VARIABLE) => acc + $item_size_hint, 0)))
+            }
+        }
+    }
+
+    fn gen_ts_type(&self) -> Tokens {
+        if self.is_bytes() {
+            return quote!(Uint8Array);
+        }
+
+        if let Some(typed_array) = self.typed_array() {
+            quote!(FixedLengthArray<$(self.items_type.gen_ts_type()), $(self.length)> | $typed_array)
+        } else if let Some(len) = self.length {
+            quote!(FixedLengthArray<$(self.items_type.gen_ts_type()), $len>)
+        } else {
+            quote!($(self.items_type.gen_ts_type())[])
+        }
+    }
+}
+
+impl ArrayMeta {
+    // Byte vectors are copied at once and exposed as `Uint8Array`. Arrays of numbers are
+    // accepted for serialization as well.
+    pub(super) fn is_bytes(&self) -> bool {
+        self.length.is_none()
+            && matches!(
+                self.items_type.as_ref(),
+                ValueType::Number(NumberMeta::Integer {
+                    bytes: 1,
+                    signed: false,
+                    pointer_sized: false,
+                    non_zero: false,
+                })
+            )
+    }
+
+    // Fixed size arrays of numbers are deserialized into the typed array matching the number type.
+    // There are no typed arrays for 128 bit numbers, 64 bit numbers would be forced into BigInts.
+    fn typed_array(&self) -> Option<&'static str> {
+        self.length?;
+        let typed_array = match self.items_type.as_ref() {
+            ValueType::Number(NumberMeta::Integer { bytes, signed, .. }) => match (bytes, signed) {
+                (1, false) => "Uint8Array",
+                (1, true) => "Int8Array",
+                (2, false) => "Uint16Array",
+                (2, true) => "Int16Array",
+                (4, false) => "Uint32Array",
+                (4, true) => "Int32Array",
+                _ => return None,
+            },
+            ValueType::Number(NumberMeta::FloatingPoint { bytes }) => match bytes {
+                4 => "Float32Array",
+                8 => "Float64Array",
+                _ => return None,
+            },
+            _ => return None,
+        };
+        Some(typed_array)
+    }
+}
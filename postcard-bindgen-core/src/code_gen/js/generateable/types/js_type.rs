@@ -1,52 +1,85 @@
-use genco::prelude::js::Tokens;
+use genco::{prelude::js::Tokens, quote, tokens::quoted};
 
 use crate::{
-    code_gen::js::{FieldAccessor, VariablePath},
-    type_info::ValueType,
+    code_gen::{
+        js::{FieldAccessor, IdentifierCase, VariablePath},
+        utils::TokensIterExt,
+    },
+    type_info::{NumberMeta, ValueType},
 };
 
 use super::JsTypeGenerateable;
 
 impl JsTypeGenerateable for ValueType {
-    fn gen_ser_accessor(&self, variable_path: VariablePath) -> Tokens {
+    fn gen_ser_accessor(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
         match self {
-            Self::Number(number_meta) => number_meta.gen_ser_accessor(variable_path),
-            Self::Array(array_meta) => array_meta.gen_ser_accessor(variable_path),
-            Self::Object(object_meta) => object_meta.gen_ser_accessor(variable_path),
-            Self::Optional(optional_meta) => optional_meta.gen_ser_accessor(variable_path),
-            Self::String(string_meta) => string_meta.gen_ser_accessor(variable_path),
-            Self::Range(range_meta) => range_meta.gen_ser_accessor(variable_path),
-            Self::Map(map_meta) => map_meta.gen_ser_accessor(variable_path),
-            Self::Tuple(tuple_meta) => tuple_meta.gen_ser_accessor(variable_path),
-            Self::Bool(bool_meta) => bool_meta.gen_ser_accessor(variable_path),
+            Self::Number(number_meta) => number_meta.gen_ser_accessor(variable_path, ident_case),
+            Self::Array(array_meta) => array_meta.gen_ser_accessor(variable_path, ident_case),
+            Self::Object(object_meta) => object_meta.gen_ser_accessor(variable_path, ident_case),
+            Self::Optional(optional_meta) => {
+                optional_meta.gen_ser_accessor(variable_path, ident_case)
+            }
+            Self::String(string_meta) => string_meta.gen_ser_accessor(variable_path, ident_case),
+            Self::Range(range_meta) => range_meta.gen_ser_accessor(variable_path, ident_case),
+            Self::Map(map_meta) => map_meta.gen_ser_accessor(variable_path, ident_case),
+            Self::Set(set_meta) => set_meta.gen_ser_accessor(variable_path, ident_case),
+            Self::Tuple(tuple_meta) => tuple_meta.gen_ser_accessor(variable_path, ident_case),
+            Self::Bool(bool_meta) => bool_meta.gen_ser_accessor(variable_path, ident_case),
+            Self::Unit(unit_meta) => unit_meta.gen_ser_accessor(variable_path, ident_case),
         }
     }
 
-    fn gen_des_accessor(&self, field_accessor: FieldAccessor) -> Tokens {
+    fn gen_des_accessor(
+        &self,
+        field_accessor: FieldAccessor,
+        ident_case: IdentifierCase,
+    ) -> Tokens {
         match self {
-            Self::Number(number_meta) => number_meta.gen_des_accessor(field_accessor),
-            Self::Array(array_meta) => array_meta.gen_des_accessor(field_accessor),
-            Self::Object(object_meta) => object_meta.gen_des_accessor(field_accessor),
-            Self::Optional(optional_meta) => optional_meta.gen_des_accessor(field_accessor),
-            Self::String(string_meta) => string_meta.gen_des_accessor(field_accessor),
-            Self::Range(range_meta) => range_meta.gen_des_accessor(field_accessor),
-            Self::Map(map_meta) => map_meta.gen_des_accessor(field_accessor),
-            Self::Tuple(tuple_meta) => tuple_meta.gen_des_accessor(field_accessor),
-            Self::Bool(bool_meta) => bool_meta.gen_des_accessor(field_accessor),
+            Self::Number(number_meta) => number_meta.gen_des_accessor(field_accessor, ident_case),
+            Self::Array(array_meta) => array_meta.gen_des_accessor(field_accessor, ident_case),
+            Self::Object(object_meta) => object_meta.gen_des_accessor(field_accessor, ident_case),
+            Self::Optional(optional_meta) => {
+                optional_meta.gen_des_accessor(field_accessor, ident_case)
+            }
+            Self::String(string_meta) => string_meta.gen_des_accessor(field_accessor, ident_case),
+            Self::Range(range_meta) => range_meta.gen_des_accessor(field_accessor, ident_case),
+            Self::Map(map_meta) => map_meta.gen_des_accessor(field_accessor, ident_case),
+            Self::Set(set_meta) => set_meta.gen_des_accessor(field_accessor, ident_case),
+            Self::Tuple(tuple_meta) => tuple_meta.gen_des_accessor(field_accessor, ident_case),
+            Self::Bool(bool_meta) => bool_meta.gen_des_accessor(field_accessor, ident_case),
+            Self::Unit(unit_meta) => unit_meta.gen_des_accessor(field_accessor, ident_case),
         }
     }
 
-    fn gen_ty_check(&self, variable_path: VariablePath) -> Tokens {
+    fn gen_ty_check(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
         match self {
-            Self::Number(number_meta) => number_meta.gen_ty_check(variable_path),
-            Self::Array(array_meta) => array_meta.gen_ty_check(variable_path),
-            Self::Object(object_meta) => object_meta.gen_ty_check(variable_path),
-            Self::Optional(optional_meta) => optional_meta.gen_ty_check(variable_path),
-            Self::String(string_meta) => string_meta.gen_ty_check(variable_path),
-            Self::Range(range_meta) => range_meta.gen_ty_check(variable_path),
-            Self::Map(map_meta) => map_meta.gen_ty_check(variable_path),
-            Self::Tuple(tuple_meta) => tuple_meta.gen_ty_check(variable_path),
-            Self::Bool(bool_meta) => bool_meta.gen_ty_check(variable_path),
+            Self::Number(number_meta) => number_meta.gen_ty_check(variable_path, ident_case),
+            Self::Array(array_meta) => array_meta.gen_ty_check(variable_path, ident_case),
+            Self::Object(object_meta) => object_meta.gen_ty_check(variable_path, ident_case),
+            Self::Optional(optional_meta) => optional_meta.gen_ty_check(variable_path, ident_case),
+            Self::String(string_meta) => string_meta.gen_ty_check(variable_path, ident_case),
+            Self::Range(range_meta) => range_meta.gen_ty_check(variable_path, ident_case),
+            Self::Map(map_meta) => map_meta.gen_ty_check(variable_path, ident_case),
+            Self::Set(set_meta) => set_meta.gen_ty_check(variable_path, ident_case),
+            Self::Tuple(tuple_meta) => tuple_meta.gen_ty_check(variable_path, ident_case),
+            Self::Bool(bool_meta) => bool_meta.gen_ty_check(variable_path, ident_case),
+            Self::Unit(unit_meta) => unit_meta.gen_ty_check(variable_path, ident_case),
+        }
+    }
+
+    fn gen_size_hint(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
+        match self {
+            Self::Number(number_meta) => number_meta.gen_size_hint(variable_path, ident_case),
+            Self::Array(array_meta) => array_meta.gen_size_hint(variable_path, ident_case),
+            Self::Object(object_meta) => object_meta.gen_size_hint(variable_path, ident_case),
+            Self::Optional(optional_meta) => optional_meta.gen_size_hint(variable_path, ident_case),
+            Self::String(string_meta) => string_meta.gen_size_hint(variable_path, ident_case),
+            Self::Range(range_meta) => range_meta.gen_size_hint(variable_path, ident_case),
+            Self::Map(map_meta) => map_meta.gen_size_hint(variable_path, ident_case),
+            Self::Set(set_meta) => set_meta.gen_size_hint(variable_path, ident_case),
+            Self::Tuple(tuple_meta) => tuple_meta.gen_size_hint(variable_path, ident_case),
+            Self::Bool(bool_meta) => bool_meta.gen_size_hint(variable_path, ident_case),
+            Self::Unit(unit_meta) => unit_meta.gen_size_hint(variable_path, ident_case),
         }
     }
 
@@ -59,8 +92,44 @@ impl JsTypeGenerateable for ValueType {
             Self::String(string_meta) => string_meta.gen_ts_type(),
             Self::Range(range_meta) => range_meta.gen_ts_type(),
             Self::Map(map_meta) => map_meta.gen_ts_type(),
+            Self::Set(set_meta) => set_meta.gen_ts_type(),
             Self::Tuple(tuple_meta) => tuple_meta.gen_ts_type(),
             Self::Bool(bool_meta) => bool_meta.gen_ts_type(),
+            Self::Unit(unit_meta) => unit_meta.gen_ts_type(),
         }
     }
 }
+
+/// The value `Default::default()` of the type is deserialized as, e.g. for a missing field with
+/// `#[serde(default)]`.
+///
+/// Returns [`None`] for types whose default is not known from the type info, like the
+/// containers, fixed size arrays or non zero numbers.
+pub fn gen_default_value(v_type: &ValueType) -> Option<Tokens> {
+    let value = match v_type {
+        ValueType::Number(NumberMeta::Integer { non_zero: true, .. }) => return None,
+        ValueType::Number(_) => quote!(0),
+        ValueType::Bool(_) => quote!(false),
+        ValueType::String(meta) if meta.format.is_some() => return None,
+        ValueType::String(meta) if meta.char => quote!($(quoted("\0"))),
+        ValueType::String(_) => quote!(""),
+        ValueType::Optional(_) => quote!(NONE),
+        ValueType::Unit(_) => quote!(null),
+        ValueType::Array(meta) if meta.length.is_some() => return None,
+        ValueType::Array(meta) if meta.is_bytes() => quote!(new Uint8Array()),
+        ValueType::Array(_) => quote!([]),
+        ValueType::Map(meta) if matches!(*meta.key_type, ValueType::String(_)) => quote!({}),
+        ValueType::Map(_) => quote!(new Map()),
+        ValueType::Set(_) => quote!(new Set()),
+        ValueType::Tuple(meta) => {
+            let items = meta
+                .items_types
+                .iter()
+                .map(gen_default_value)
+                .collect::<Option<Vec<_>>>()?;
+            quote!([$(items.into_iter().join_with_comma())])
+        }
+        ValueType::Object(_) | ValueType::Range(_) => return None,
+    };
+    Some(value)
+}
@@ -4,7 +4,7 @@ use genco::{prelude::js::Tokens, quote};
 
 use crate::{
     code_gen::{
-        js::{FieldAccessor, VariablePath, JS_OBJECT_VARIABLE},
+        js::{FieldAccessor, IdentifierCase, VariablePath, JS_OBJECT_VARIABLE},
         utils::TokensIterExt,
     },
     type_info::{MapMeta, ValueType},
@@ -13,39 +13,54 @@ use crate::{
 use super::JsTypeGenerateable;
 
 impl JsTypeGenerateable for MapMeta {
-    fn gen_ser_accessor(&self, variable_path: VariablePath) -> Tokens {
+    fn gen_ser_accessor(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
         match self.key_type.deref() {
             &ValueType::String(_) => {
-                let inner_type_accessor = self.value_type.gen_ser_accessor(VariablePath::default());
-                quote!(s.serialize_string_key_map((s, v) => $inner_type_accessor, $variable_path))
+                let inner_type_accessor = self
+                    .value_type
+                    .gen_ser_accessor(VariablePath::default(), ident_case);
+                quote! {
+                    s.serialize_string_key_map((s, v) => {
+                        $inner_type_accessor
+                    }, $variable_path)
+                }
             }
             _ => {
                 let inner_type_key_accessor = self
                     .key_type
-                    .gen_ser_accessor(VariablePath::new("k".into()));
+                    .gen_ser_accessor(VariablePath::new("k".into()), ident_case);
                 let inner_type_value_accessor = self
                     .value_type
-                    .gen_ser_accessor(VariablePath::new("v".into()));
+                    .gen_ser_accessor(VariablePath::new("v".into()), ident_case);
                 quote! {
-                    s.serialize_map((d, k, v) => [
-                        $inner_type_key_accessor,
+                    s.serialize_map((s, k, v) => {
+                        $inner_type_key_accessor;
                         $inner_type_value_accessor
-                    ], $variable_path)
+                    }, $variable_path)
                 }
             }
         }
     }
 
-    fn gen_des_accessor(&self, field_accessor: FieldAccessor) -> Tokens {
+    fn gen_des_accessor(
+        &self,
+        field_accessor: FieldAccessor,
+        ident_case: IdentifierCase,
+    ) -> Tokens {
         match self.key_type.deref() {
             &ValueType::String(_) => {
-                let inner_type_accessor = self.value_type.gen_des_accessor(FieldAccessor::None);
+                let inner_type_accessor = self
+                    .value_type
+                    .gen_des_accessor(FieldAccessor::None, ident_case);
                 quote!($(field_accessor)d.deserialize_string_key_map(((d) => $inner_type_accessor)))
             }
             _ => {
-                let inner_type_key_accessor = self.key_type.gen_des_accessor(FieldAccessor::None);
-                let inner_type_value_accessor =
-                    self.value_type.gen_des_accessor(FieldAccessor::None);
+                let inner_type_key_accessor = self
+                    .key_type
+                    .gen_des_accessor(FieldAccessor::None, ident_case);
+                let inner_type_value_accessor = self
+                    .value_type
+                    .gen_des_accessor(FieldAccessor::None, ident_case);
                 quote! {
                     $(field_accessor)d.deserialize_map(((d) => [
                         $inner_type_key_accessor,
@@ -56,12 +71,12 @@ impl JsTypeGenerateable for MapMeta {
         }
     }
 
-    fn gen_ty_check(&self, variable_path: VariablePath) -> Tokens {
+    fn gen_ty_check(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
         match self.key_type.deref() {
             &ValueType::String(_) => {
                 let inner_type_check = self
                     .value_type
-                    .gen_ty_check(VariablePath::new(JS_OBJECT_VARIABLE.into()));
+                    .gen_ty_check(VariablePath::new(JS_OBJECT_VARIABLE.into()), ident_case);
                 let inner_type_checks = quote!(Object.values($(variable_path.to_owned())).map((v) => $inner_type_check).every((v) => v));
                 [
                     quote!(typeof $variable_path === "object"),
@@ -74,6 +89,23 @@ impl JsTypeGenerateable for MapMeta {
         }
     }
 
+    fn gen_size_hint(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
+        let key_size_hint = self
+            .key_type
+            .gen_size_hint(VariablePath::new("k".into()), ident_case);
+        let value_size_hint = self
+            .value_type
+            .gen_size_hint(VariablePath::new("v".into()), ident_case);
+        match self.key_type.deref() {
+            &ValueType::String(_) => {
+                quote!((5 + Object.entries($variable_path).reduce((acc, [k, v]) => acc + $key_size_hint + $value_size_hint, 0)))
+            }
+            _ => {
+                quote!((5 + [...$variable_path].reduce((acc, [k, v]) => acc + $key_size_hint + $value_size_hint, 0)))
+            }
+        }
+    }
+
     fn gen_ts_type(&self) -> Tokens {
         match self.key_type.deref() {
             &ValueType::String(_) => {
@@ -5,21 +5,27 @@ pub mod number;
 pub mod object;
 pub mod optional;
 pub mod range;
+pub mod set;
 pub mod string;
 pub mod tuple;
+pub mod unit;
 
 pub mod js_type;
 
 use genco::prelude::js::Tokens;
 
-use crate::code_gen::js::{FieldAccessor, VariablePath};
+use crate::code_gen::js::{FieldAccessor, IdentifierCase, VariablePath};
 
 pub trait JsTypeGenerateable {
-    fn gen_ser_accessor(&self, variable_path: VariablePath) -> Tokens;
+    fn gen_ser_accessor(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens;
 
-    fn gen_des_accessor(&self, field_accessor: FieldAccessor) -> Tokens;
+    fn gen_des_accessor(&self, field_accessor: FieldAccessor, ident_case: IdentifierCase)
+        -> Tokens;
 
-    fn gen_ty_check(&self, variable_path: VariablePath) -> Tokens;
+    fn gen_ty_check(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens;
+
+    /// Expression for an upper bound of the encoded length of the value in bytes.
+    fn gen_size_hint(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens;
 
     fn gen_ts_type(&self) -> Tokens;
 }
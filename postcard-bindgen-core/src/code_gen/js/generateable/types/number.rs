@@ -1,14 +1,14 @@
 use genco::{prelude::js::Tokens, quote};
 
 use crate::{
-    code_gen::js::{FieldAccessor, VariablePath},
+    code_gen::js::{FieldAccessor, IdentifierCase, VariablePath},
     type_info::NumberMeta,
 };
 
 use super::{bool::bool_to_js_bool, JsTypeGenerateable};
 
 impl JsTypeGenerateable for NumberMeta {
-    fn gen_ser_accessor(&self, variable_path: VariablePath) -> Tokens {
+    fn gen_ser_accessor(&self, variable_path: VariablePath, _ident_case: IdentifierCase) -> Tokens {
         let byte_amount_str = self.as_byte_string();
         match self {
             NumberMeta::FloatingPoint { .. } => {
@@ -21,7 +21,11 @@ impl JsTypeGenerateable for NumberMeta {
         }
     }
 
-    fn gen_des_accessor(&self, field_accessor: FieldAccessor) -> Tokens {
+    fn gen_des_accessor(
+        &self,
+        field_accessor: FieldAccessor,
+        _ident_case: IdentifierCase,
+    ) -> Tokens {
         let byte_amount_str = self.as_byte_string();
         match self {
             NumberMeta::FloatingPoint { .. } => {
@@ -34,11 +38,21 @@ impl JsTypeGenerateable for NumberMeta {
         }
     }
 
-    fn gen_ty_check(&self, variable_path: VariablePath) -> Tokens {
+    fn gen_ty_check(&self, variable_path: VariablePath, _ident_case: IdentifierCase) -> Tokens {
         let byte_amount_str = self.as_byte_string();
         match self {
             NumberMeta::FloatingPoint { .. } => {
-                quote!(typeof $(variable_path.to_owned()) === "number" && Number.isFinite($(variable_path.to_owned())))
+                // NaN and the infinities are encoded too, like postcard does in rust
+                quote!(typeof $variable_path === "number")
+            }
+            // `!=` as the value may be a `BigInt`, which is never strictly equal to `0`.
+            NumberMeta::Integer {
+                signed,
+                non_zero: true,
+                ..
+            } => {
+                let signed = bool_to_js_bool(*signed);
+                quote!(check_integer_type($(variable_path.clone()), $byte_amount_str, $signed) && $variable_path != 0)
             }
             NumberMeta::Integer { signed, .. } => {
                 let signed = bool_to_js_bool(*signed);
@@ -47,6 +61,10 @@ impl JsTypeGenerateable for NumberMeta {
         }
     }
 
+    fn gen_size_hint(&self, _variable_path: VariablePath, _ident_case: IdentifierCase) -> Tokens {
+        quote!($(self.max_encoded_len()))
+    }
+
     fn gen_ts_type(&self) -> Tokens {
         match self {
             NumberMeta::FloatingPoint { bytes } => {
@@ -57,7 +75,7 @@ impl JsTypeGenerateable for NumberMeta {
                 };
                 quote!(f$bits)
             }
-            NumberMeta::Integer { bytes, signed } => {
+            NumberMeta::Integer { bytes, signed, .. } => {
                 let prefix = if *signed { "i" } else { "u" };
                 let bits = match bytes {
                     1 => "8",
@@ -3,7 +3,7 @@ use genco::{prelude::js::Tokens, quote};
 use crate::{
     code_gen::{
         js::{FieldAccessor, VariablePath},
-        utils::{ContainerFullQualifiedTypeBuilder, ContainerIdentifierBuilder},
+        utils::{ContainerFullQualifiedTypeBuilder, ContainerIdentifierBuilder, IdentifierCase},
     },
     type_info::ObjectMeta,
 };
@@ -11,21 +11,38 @@ use crate::{
 use super::JsTypeGenerateable;
 
 impl JsTypeGenerateable for ObjectMeta {
-    fn gen_ser_accessor(&self, variable_path: VariablePath) -> Tokens {
-        let container_ident = ContainerIdentifierBuilder::from(self).build();
+    fn gen_ser_accessor(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
+        let container_ident = ContainerIdentifierBuilder::from(self)
+            .ident_case(ident_case)
+            .build();
         quote!(serialize_$container_ident(s, $variable_path))
     }
 
-    fn gen_des_accessor(&self, field_accessor: FieldAccessor) -> Tokens {
-        let container_ident = ContainerIdentifierBuilder::from(self).build();
+    fn gen_des_accessor(
+        &self,
+        field_accessor: FieldAccessor,
+        ident_case: IdentifierCase,
+    ) -> Tokens {
+        let container_ident = ContainerIdentifierBuilder::from(self)
+            .ident_case(ident_case)
+            .build();
         quote!($(field_accessor)deserialize_$container_ident(d))
     }
 
-    fn gen_ty_check(&self, variable_path: VariablePath) -> Tokens {
-        let container_ident = ContainerIdentifierBuilder::from(self).build();
+    fn gen_ty_check(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
+        let container_ident = ContainerIdentifierBuilder::from(self)
+            .ident_case(ident_case)
+            .build();
         quote!(is_$container_ident($variable_path))
     }
 
+    fn gen_size_hint(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
+        let container_ident = ContainerIdentifierBuilder::from(self)
+            .ident_case(ident_case)
+            .build();
+        quote!(size_hint_$container_ident($variable_path))
+    }
+
     fn gen_ts_type(&self) -> Tokens {
         let full_qualified = ContainerFullQualifiedTypeBuilder::from(self).build();
         quote!($full_qualified)
@@ -1,17 +1,23 @@
 use genco::{prelude::js::Tokens, quote};
 
 use crate::{
-    code_gen::js::{AvailableCheck, FieldAccessor, VariablePath},
-    type_info::OptionalMeta,
+    code_gen::js::{FieldAccessor, IdentifierCase, VariableAccess, VariablePath},
+    type_info::{OptionalMeta, ValueType},
 };
 
 use super::JsTypeGenerateable;
 
+// `Some(None)` and `None` can't both be `NONE`, so the value of an optional which contains
+// another optional is wrapped as `{ value }`.
+const WRAPPED_VALUE: &str = "value";
+
 impl JsTypeGenerateable for OptionalMeta {
-    fn gen_ser_accessor(&self, variable_path: VariablePath) -> Tokens {
-        let type_accessor = self.inner.gen_ser_accessor(variable_path.to_owned());
+    fn gen_ser_accessor(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
+        let type_accessor = self
+            .inner
+            .gen_ser_accessor(self.inner_path(&variable_path), ident_case);
         quote! {
-            if ($variable_path !== undefined) {
+            if (!is_none($variable_path)) {
                 s.serialize_number(U32_BYTES, false, 1);
                 $type_accessor
             } else {
@@ -20,25 +26,57 @@ impl JsTypeGenerateable for OptionalMeta {
         }
     }
 
-    fn gen_des_accessor(&self, field_accessor: FieldAccessor) -> Tokens {
-        let inner_accessor = self.inner.gen_des_accessor(FieldAccessor::None);
-        quote!($(field_accessor)(d.deserialize_number(U32_BYTES, false) === 0) ? undefined : $inner_accessor)
+    fn gen_des_accessor(
+        &self,
+        field_accessor: FieldAccessor,
+        ident_case: IdentifierCase,
+    ) -> Tokens {
+        let mut inner_accessor = self.inner.gen_des_accessor(FieldAccessor::None, ident_case);
+        if self.is_nested() {
+            inner_accessor = quote!({ $WRAPPED_VALUE: $inner_accessor });
+        }
+        quote!($(field_accessor)(d.deserialize_number(U32_BYTES, false) === 0) ? NONE : $inner_accessor)
     }
 
-    fn gen_ty_check(&self, variable_path: VariablePath) -> Tokens {
-        let available_check = AvailableCheck::from_variable_path(variable_path.to_owned());
-        let inner_type_check = self.inner.gen_ty_check(variable_path.to_owned());
-        match &available_check {
-            AvailableCheck::Object(_, _) => {
-                quote!((($(available_check.to_owned()) && ($(variable_path.to_owned()) !== undefined && $inner_type_check) || $variable_path === undefined) || !($available_check)))
-            }
-            AvailableCheck::None => {
-                quote!(($(variable_path.to_owned()) !== undefined && $inner_type_check) || $variable_path === undefined)
-            }
+    fn gen_ty_check(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
+        let mut inner_type_check = self
+            .inner
+            .gen_ty_check(self.inner_path(&variable_path), ident_case);
+        if self.is_nested() {
+            inner_type_check =
+                quote!((typeof $(variable_path.clone()) === "object" && $inner_type_check));
         }
+        quote!((is_none($variable_path) || $inner_type_check))
+    }
+
+    fn gen_size_hint(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
+        let inner_size_hint = self
+            .inner
+            .gen_size_hint(self.inner_path(&variable_path), ident_case);
+        quote!((1 + (is_none($variable_path) ? 0 : $inner_size_hint)))
     }
 
     fn gen_ts_type(&self) -> Tokens {
-        quote!($(self.inner.gen_ts_type()) | undefined)
+        if self.is_nested() {
+            quote!({ $WRAPPED_VALUE: $(self.inner.gen_ts_type()) } | none)
+        } else {
+            quote!($(self.inner.gen_ts_type()) | none)
+        }
+    }
+}
+
+impl OptionalMeta {
+    fn is_nested(&self) -> bool {
+        matches!(self.inner.as_ref(), ValueType::Optional(_))
+    }
+
+    fn inner_path(&self, variable_path: &VariablePath) -> VariablePath {
+        if self.is_nested() {
+            variable_path
+                .to_owned()
+                .modify_push(VariableAccess::Field(WRAPPED_VALUE.into()))
+        } else {
+            variable_path.to_owned()
+        }
     }
 }
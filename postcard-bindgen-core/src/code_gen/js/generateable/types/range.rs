@@ -2,7 +2,7 @@ use genco::{prelude::js::Tokens, quote};
 
 use crate::{
     code_gen::{
-        js::{FieldAccessor, VariableAccess, VariablePath},
+        js::{FieldAccessor, IdentifierCase, VariableAccess, VariablePath},
         utils::{JoinType, TokensIterExt},
     },
     type_info::RangeMeta,
@@ -11,32 +11,61 @@ use crate::{
 use super::JsTypeGenerateable;
 
 impl JsTypeGenerateable for RangeMeta {
-    fn gen_ser_accessor(&self, variable_path: VariablePath) -> Tokens {
+    fn gen_ser_accessor(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
         let start_path = variable_path
             .to_owned()
             .modify_push(VariableAccess::Field("start".into()));
         let stop_path = variable_path.modify_push(VariableAccess::Field("end".into()));
 
-        let start_accessor = self.bounds_type.gen_ser_accessor(start_path);
-        let stop_accessor = self.bounds_type.gen_ser_accessor(stop_path);
+        let start_accessor = self.bounds_type.gen_ser_accessor(start_path, ident_case);
+        let stop_accessor = self.bounds_type.gen_ser_accessor(stop_path, ident_case);
 
         [start_accessor, stop_accessor]
             .into_iter()
             .join_with([JoinType::Semicolon, JoinType::LineBreak])
     }
 
-    fn gen_des_accessor(&self, field_accessor: FieldAccessor) -> Tokens {
-        let field_des = self.bounds_type.gen_des_accessor(FieldAccessor::None);
+    fn gen_des_accessor(
+        &self,
+        field_accessor: FieldAccessor,
+        ident_case: IdentifierCase,
+    ) -> Tokens {
+        let field_des = self
+            .bounds_type
+            .gen_des_accessor(FieldAccessor::None, ident_case);
         quote! {
             $field_accessor{
-                end: $(field_des.to_owned()),
-                start: $field_des
+                start: $(field_des.to_owned()),
+                end: $field_des
             }
         }
     }
 
-    fn gen_ty_check(&self, variable_path: VariablePath) -> Tokens {
-        quote!(typeof $(variable_path.to_owned()) === "object" && "start" in $(variable_path.to_owned()) && "end" in $variable_path)
+    fn gen_ty_check(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
+        let start_path = variable_path
+            .to_owned()
+            .modify_push(VariableAccess::Field("start".into()));
+        let stop_path = variable_path
+            .to_owned()
+            .modify_push(VariableAccess::Field("end".into()));
+
+        [
+            quote!(typeof $(variable_path.to_owned()) === "object"),
+            quote!($(variable_path.to_owned()) !== null),
+            self.bounds_type.gen_ty_check(start_path, ident_case),
+            self.bounds_type.gen_ty_check(stop_path, ident_case),
+        ]
+        .into_iter()
+        .join_logic_and()
+    }
+
+    fn gen_size_hint(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
+        let start_path = variable_path
+            .to_owned()
+            .modify_push(VariableAccess::Field("start".into()));
+        let stop_path = variable_path.modify_push(VariableAccess::Field("end".into()));
+
+        quote!(($(self.bounds_type.gen_size_hint(start_path, ident_case)) + $(self.bounds_type.gen_size_hint(stop_path, ident_case))))
     }
 
     fn gen_ts_type(&self) -> Tokens {
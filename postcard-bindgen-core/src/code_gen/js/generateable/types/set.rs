@@ -0,0 +1,56 @@
+use genco::{prelude::js::Tokens, quote};
+
+use crate::{
+    code_gen::js::{FieldAccessor, IdentifierCase, VariablePath, JS_OBJECT_VARIABLE},
+    type_info::{SetMeta, ValueType},
+};
+
+use super::JsTypeGenerateable;
+
+// Sets are encoded like sequences. The serializer accepts any iterable, so only the
+// deserialized array has to be turned into a `Set`.
+impl JsTypeGenerateable for SetMeta {
+    fn gen_ser_accessor(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
+        self.as_sequence()
+            .gen_ser_accessor(variable_path, ident_case)
+    }
+
+    fn gen_des_accessor(
+        &self,
+        field_accessor: FieldAccessor,
+        ident_case: IdentifierCase,
+    ) -> Tokens {
+        let sequence_accessor = self
+            .as_sequence()
+            .gen_des_accessor(FieldAccessor::None, ident_case);
+        quote!($(field_accessor)new Set($sequence_accessor))
+    }
+
+    fn gen_ty_check(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
+        let item_ty_check = self
+            .items_type
+            .gen_ty_check(VariablePath::default(), ident_case);
+        quote!($(variable_path.clone()) instanceof Set && [...$variable_path].every(($JS_OBJECT_VARIABLE) => $item_ty_check))
+    }
+
+    fn gen_size_hint(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
+        let item_bytes = match self.items_type.as_ref() {
+            ValueType::Number(number_meta) => Some(number_meta.max_encoded_len()),
+            ValueType::Bool(_) => Some(1),
+            _ => None,
+        };
+        match item_bytes {
+            Some(item_bytes) => quote!((5 + $variable_path.size * $item_bytes)),
+            None => {
+                let item_size_hint = self
+                    .items_type
+                    .gen_size_hint(VariablePath::default(), ident_case);
+                quote!((5 + [...$variable_path].reduce((acc, $JS_OBJECT_VARIABLE) => acc + $item_size_hint, 0)))
+            }
+        }
+    }
+
+    fn gen_ts_type(&self) -> Tokens {
+        quote!(Set<$(self.items_type.gen_ts_type())>)
+    }
+}
@@ -1,23 +1,55 @@
 use genco::{prelude::js::Tokens, quote};
 
 use crate::{
-    code_gen::js::{FieldAccessor, VariablePath},
-    type_info::StringMeta,
+    code_gen::{
+        js::{FieldAccessor, IdentifierCase, VariablePath},
+        utils::SEMVER_PATTERN,
+    },
+    type_info::{StringFormat, StringMeta},
 };
 
 use super::JsTypeGenerateable;
 
 impl JsTypeGenerateable for StringMeta {
-    fn gen_ser_accessor(&self, variable_path: VariablePath) -> Tokens {
+    fn gen_ser_accessor(&self, variable_path: VariablePath, _ident_case: IdentifierCase) -> Tokens {
         quote!(s.serialize_string($variable_path))
     }
 
-    fn gen_des_accessor(&self, field_accessor: FieldAccessor) -> Tokens {
+    fn gen_des_accessor(
+        &self,
+        field_accessor: FieldAccessor,
+        _ident_case: IdentifierCase,
+    ) -> Tokens {
         quote!($(field_accessor)d.deserialize_string())
     }
 
-    fn gen_ty_check(&self, variable_path: VariablePath) -> Tokens {
-        quote!(typeof $variable_path === "string")
+    fn gen_ty_check(&self, variable_path: VariablePath, _ident_case: IdentifierCase) -> Tokens {
+        if self.char {
+            // A single unicode scalar value, which may be two utf-16 code units.
+            quote!(typeof $(variable_path.clone()) === "string" && is_well_formed_string($(variable_path.clone())) && [...$variable_path].length === 1)
+        } else if let Some(capacity) = self.capacity {
+            // The capacity is in bytes of utf-8, not in utf-16 code units.
+            quote!(typeof $(variable_path.clone()) === "string" && is_well_formed_string($(variable_path.clone())) && new TextEncoder().encode($variable_path).length <= $capacity)
+        } else if let Some(format) = self.format {
+            let format_check = match format {
+                StringFormat::Url => quote!(URL.canParse($(variable_path.clone()))),
+                StringFormat::SemVer => {
+                    quote!($(format!("/{SEMVER_PATTERN}/")).test($(variable_path.clone())))
+                }
+            };
+            quote!(typeof $(variable_path.clone()) === "string" && is_well_formed_string($variable_path) && $format_check)
+        } else {
+            quote!(typeof $(variable_path.clone()) === "string" && is_well_formed_string($variable_path))
+        }
+    }
+
+    fn gen_size_hint(&self, variable_path: VariablePath, _ident_case: IdentifierCase) -> Tokens {
+        if self.char {
+            // The length prefix and at most four bytes of utf-8.
+            return quote!(5);
+        }
+        // A single utf-16 code unit takes at most three bytes in utf-8.
+        quote!((5 + $variable_path.length * 3))
     }
 
     fn gen_ts_type(&self) -> Tokens {
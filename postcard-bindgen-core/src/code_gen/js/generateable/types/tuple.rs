@@ -2,7 +2,7 @@ use genco::{lang::js::Tokens, quote};
 
 use crate::{
     code_gen::{
-        js::{FieldAccessor, VariableAccess, VariablePath},
+        js::{FieldAccessor, IdentifierCase, VariableAccess, VariablePath},
         utils::{JoinType, TokensIterExt},
     },
     type_info::TupleMeta,
@@ -11,7 +11,7 @@ use crate::{
 use super::JsTypeGenerateable;
 
 impl JsTypeGenerateable for TupleMeta {
-    fn gen_ser_accessor(&self, variable_path: VariablePath) -> Tokens {
+    fn gen_ser_accessor(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
         self.items_types
             .iter()
             .enumerate()
@@ -20,16 +20,21 @@ impl JsTypeGenerateable for TupleMeta {
                     variable_path
                         .clone()
                         .modify_push(VariableAccess::Indexed(i)),
+                    ident_case,
                 )
             })
             .join_with([JoinType::Semicolon, JoinType::LineBreak])
     }
 
-    fn gen_des_accessor(&self, field_accessor: FieldAccessor) -> Tokens {
+    fn gen_des_accessor(
+        &self,
+        field_accessor: FieldAccessor,
+        ident_case: IdentifierCase,
+    ) -> Tokens {
         let inner_type_accessors = self
             .items_types
             .iter()
-            .map(|v| v.gen_des_accessor(FieldAccessor::None))
+            .map(|v| v.gen_des_accessor(FieldAccessor::None, ident_case))
             .join_with([JoinType::Comma, JoinType::LineBreak]);
         quote! {
             $field_accessor[
@@ -38,7 +43,7 @@ impl JsTypeGenerateable for TupleMeta {
         }
     }
 
-    fn gen_ty_check(&self, variable_path: VariablePath) -> Tokens {
+    fn gen_ty_check(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
         let type_checks = self
             .items_types
             .iter()
@@ -48,12 +53,34 @@ impl JsTypeGenerateable for TupleMeta {
                     variable_path
                         .clone()
                         .modify_push(VariableAccess::Indexed(i)),
+                    ident_case,
                 )
             })
             .join_logic_and();
         quote!(Array.isArray($(variable_path.clone())) && $variable_path.length === $(self.items_types.len()) && $type_checks)
     }
 
+    fn gen_size_hint(&self, variable_path: VariablePath, ident_case: IdentifierCase) -> Tokens {
+        let body = self
+            .items_types
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                v.gen_size_hint(
+                    variable_path
+                        .to_owned()
+                        .modify_push(VariableAccess::Indexed(i)),
+                    ident_case,
+                )
+            })
+            .join_with_plus();
+        if body.is_empty() {
+            quote!(0)
+        } else {
+            quote!(($body))
+        }
+    }
+
     fn gen_ts_type(&self) -> Tokens {
         let type_checks = self
             .items_types
@@ -0,0 +1,39 @@
+use genco::{prelude::js::Tokens, quote};
+
+use crate::{
+    code_gen::js::{FieldAccessor, IdentifierCase, VariablePath},
+    type_info::UnitMeta,
+};
+
+use super::JsTypeGenerateable;
+
+// `()` is encoded as nothing and represented by `null`.
+impl JsTypeGenerateable for UnitMeta {
+    fn gen_ser_accessor(
+        &self,
+        _variable_path: VariablePath,
+        _ident_case: IdentifierCase,
+    ) -> Tokens {
+        Tokens::new()
+    }
+
+    fn gen_des_accessor(
+        &self,
+        field_accessor: FieldAccessor,
+        _ident_case: IdentifierCase,
+    ) -> Tokens {
+        quote!($(field_accessor)null)
+    }
+
+    fn gen_ty_check(&self, variable_path: VariablePath, _ident_case: IdentifierCase) -> Tokens {
+        quote!($variable_path === null)
+    }
+
+    fn gen_size_hint(&self, _variable_path: VariablePath, _ident_case: IdentifierCase) -> Tokens {
+        quote!(0)
+    }
+
+    fn gen_ts_type(&self) -> Tokens {
+        quote!(null)
+    }
+}
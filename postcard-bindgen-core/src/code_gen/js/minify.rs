@@ -0,0 +1,347 @@
+use genco::quote;
+
+use crate::{
+    code_gen::utils::{ContainerIdentifierBuilder, IdentifierCase},
+    registry::Container,
+};
+
+use super::Tokens;
+
+/// Constants of the runtime, referenced by their plain name.
+const CONSTANTS: &[&str] = &[
+    "BITS_PER_BYTE",
+    "BITS_PER_VARINT_BYTE",
+    "U8_BYTES",
+    "U16_BYTES",
+    "U32_BYTES",
+    "U64_BYTES",
+    "U128_BYTES",
+];
+
+/// Internal functions and classes, referenced by their plain name like the constants.
+/// `DeserializationError` is exported, so it keeps its name.
+const FUNCTIONS: &[&str] = &[
+    "de_zig_zag_signed",
+    "zig_zag",
+    "varint_max",
+    "max_of_last_byte",
+    "to_number_if_safe",
+    "varint",
+    "NONE",
+    "is_none",
+    "is_well_formed_string",
+    "to_array",
+    "is_array_of",
+    "Serializer",
+    "LengthCounter",
+    "Deserializer",
+    "check_bounds",
+    "check_integer_type",
+    "check_deserialized",
+    "deserialize_type",
+    "deep_equal",
+];
+
+/// Prefixes of the functions generated per type, followed by the type identifier.
+const TYPE_FUNCTION_PREFIXES: &[&str] =
+    &["is_", "serialize_", "size_hint_", "deserialize_", "sample_"];
+
+/// Fields and methods of `Serializer` and `Deserializer`.
+const MEMBERS: &[&str] = &[
+    "bytes",
+    "length",
+    "index",
+    "finish",
+    "reserve",
+    "push",
+    "push_n",
+    "serialize_bool",
+    "serialize_number",
+    "serialize_number_float",
+    "serialize_number_array",
+    "serialize_number_float_array",
+    "serialize_string",
+    "serialize_bytes",
+    "serialize_array",
+    "serialize_string_key_map",
+    "serialize_map",
+    "at_end",
+    "pop_next",
+    "pop_n",
+    "get_uint8",
+    "take_varint",
+    "try_take",
+    "deserialize_bool",
+    "deserialize_number",
+    "deserialize_number_float",
+    "deserialize_number_array",
+    "deserialize_number_float_array",
+    "deserialize_string",
+    "deserialize_bytes",
+    "deserialize_array",
+    "deserialize_string_key_map",
+    "deserialize_map",
+];
+
+/// Variables holding a `Serializer` or `Deserializer` in the generated code.
+const RECEIVERS: &[&str] = &["s", "d", "this"];
+
+/// Lines which are copied as they are, the codec snippets are user code.
+const VERBATIM_LINE_PREFIX: &str = "const CODECS";
+
+/// Minifies generated javascript.
+///
+/// The indentation, empty lines and the whitespace between tokens are removed and the
+/// runtime constants, the internal functions, the functions of each type and the members of
+/// the serializer and deserializer are renamed to short identifiers. These start with `$`,
+/// which is not allowed in rust identifiers, so they can't clash with names of the bindings.
+/// The short names only depend on the containers, so modules minified with the same
+/// containers still import each other.
+/// Line breaks are kept, the generated code relies on automatic semicolon insertion.
+pub fn minify(tokens: Tokens, containers: &[Container], ident_case: IdentifierCase) -> Tokens {
+    let source = tokens
+        .to_file_string()
+        .expect("generated javascript is valid");
+    let type_functions = type_function_names(containers, ident_case);
+    let minified = minify_source(&source, &type_functions);
+    quote!($minified)
+}
+
+fn type_function_names(containers: &[Container], ident_case: IdentifierCase) -> Vec<String> {
+    containers
+        .iter()
+        .flat_map(|container| {
+            let ident = ContainerIdentifierBuilder::from(container)
+                .ident_case(ident_case)
+                .build();
+            TYPE_FUNCTION_PREFIXES
+                .iter()
+                .map(move |prefix| format!("{prefix}{ident}"))
+        })
+        .collect()
+}
+
+fn minify_source(source: &str, type_functions: &[String]) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut in_class = false;
+
+    for line in source.lines().map(str::trim) {
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with(VERBATIM_LINE_PREFIX) {
+            out.push_str(line);
+        } else {
+            minify_line(line, in_class, type_functions, &mut out);
+        }
+        out.push('\n');
+
+        if line.starts_with("class ") {
+            in_class = true;
+        } else if line == "}" {
+            in_class = false;
+        }
+    }
+
+    out
+}
+
+fn minify_line(line: &str, in_class: bool, type_functions: &[String], out: &mut String) {
+    let chars = line.char_indices().collect::<Vec<_>>();
+    // last tokens before the current position, an identifier or a punctuation character
+    let mut prev: [Option<&str>; 3] = [None, None, None];
+    let mut i = 0;
+
+    while i < chars.len() {
+        let (start, c) = chars[i];
+
+        if c == '"' || c == '\'' || c == '`' {
+            let mut end = i + 1;
+            while end < chars.len() && chars[end].1 != c {
+                if chars[end].1 == '\\' {
+                    end += 1;
+                }
+                end += 1;
+            }
+            let end = chars.get(end + 1).map_or(line.len(), |(pos, _)| *pos);
+            out.push_str(&line[start..end]);
+            i = chars.partition_point(|(pos, _)| *pos < end);
+            prev = [prev[1], prev[2], Some("\"")];
+            continue;
+        }
+
+        if c == '/' && chars.get(i + 1).is_some_and(|(_, c)| *c == '/') {
+            out.push_str(&line[start..]);
+            return;
+        }
+
+        if is_ident_start(c) || c.is_ascii_digit() {
+            let end = chars[i..]
+                .iter()
+                .find(|(_, c)| !is_ident_char(*c))
+                .map_or(line.len(), |(pos, _)| *pos);
+            let ident = &line[start..end];
+            let rest = line[end..].trim_start();
+
+            let renamed = if c.is_ascii_digit() {
+                None
+            } else if is_member_access(prev) || (in_class && start == 0 && is_assignment(rest)) {
+                short_name(MEMBERS, ident, CONSTANTS.len())
+            } else if prev[2] != Some(".") && !is_object_key(prev[2], rest) {
+                short_name(CONSTANTS, ident, 0)
+                    .or_else(|| short_name(FUNCTIONS, ident, CONSTANTS.len() + MEMBERS.len()))
+                    .or_else(|| {
+                        let offset = CONSTANTS.len() + MEMBERS.len() + FUNCTIONS.len();
+                        short_name(type_functions, ident, offset)
+                    })
+            } else {
+                None
+            };
+            out.push_str(renamed.as_deref().unwrap_or(ident));
+
+            i = chars.partition_point(|(pos, _)| *pos < end);
+            prev = [prev[1], prev[2], Some(ident)];
+            continue;
+        }
+
+        if c.is_whitespace() {
+            let next = chars[i..].iter().find(|(_, c)| !c.is_whitespace());
+            if let (Some(last), Some((_, next))) = (out.chars().last(), next) {
+                if needs_space(last, *next) {
+                    out.push(' ');
+                }
+            }
+            i += 1;
+            continue;
+        }
+
+        out.push(c);
+        prev = [prev[1], prev[2], Some(&line[start..start + c.len_utf8()])];
+        i += 1;
+    }
+}
+
+/// Whether the tokens ending with `last` and starting with `next` would merge without a space,
+/// like `return value` or `a - -b`.
+fn needs_space(last: char, next: char) -> bool {
+    (is_ident_char(last) && is_ident_char(next)) || (last == next && matches!(last, '+' | '-'))
+}
+
+/// `prev` is `<receiver>.`, the receiver itself must not be a property.
+fn is_member_access(prev: [Option<&str>; 3]) -> bool {
+    matches!(prev, [before, Some(receiver), Some(".")] if before != Some(".") && RECEIVERS.contains(&receiver))
+}
+
+fn is_assignment(rest: &str) -> bool {
+    rest.starts_with('=') && !rest.starts_with("==") && !rest.starts_with("=>")
+}
+
+fn is_object_key(prev: Option<&str>, rest: &str) -> bool {
+    (rest.starts_with(':') || rest.starts_with("?:")) && prev != Some("?")
+}
+
+fn short_name(names: &[impl AsRef<str>], ident: &str, offset: usize) -> Option<String> {
+    const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    names
+        .iter()
+        .position(|name| name.as_ref() == ident)
+        .map(|index| {
+            let mut index = index + offset;
+            let mut name = String::from("$");
+            loop {
+                name.push(LETTERS[index % LETTERS.len()] as char);
+                index /= LETTERS.len();
+                if index == 0 {
+                    break name;
+                }
+            }
+        })
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_' || c == '$'
+}
+
+pub fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '$'
+}
+
+#[cfg(test)]
+mod test {
+    use super::minify_source;
+
+    #[test]
+    fn test_minify_renames_runtime_names() {
+        let source = "\
+const BITS_PER_BYTE = 8, U8_BYTES = 1
+
+class Serializer {
+    constructor(capacity) { this.bytes = new Uint8Array(capacity); this.length = 0 }
+    push = (byte) => { this.bytes[this.length++] = byte }
+}
+
+function serialize_A(s, v) {
+    s.serialize_number(U8_BYTES, false, v.length);
+    s.serialize_string(\"U8_BYTES s.push\");
+}
+";
+        let expected = "\
+const $a=8,$c=1
+class $Z{
+constructor(capacity){this.$h=new Uint8Array(capacity);this.$i=0}
+$m=(byte)=>{this.$h[this.$i++]=byte}
+}
+function serialize_A(s,v){
+s.$p($c,false,v.length);
+s.$t(\"U8_BYTES s.push\");
+}
+";
+        assert_eq!(minify_source(source, &[]), expected);
+    }
+
+    #[test]
+    fn test_minify_keeps_binding_names() {
+        let source = "\
+function deserialize_A(d) {
+    return { U8_BYTES: d.deserialize_number(U8_BYTES, false), s: v.s.push, bytes: v.d.bytes };
+}
+const CODECS = { \"date\": { serialize: (d) => d.length } }
+";
+        let expected = "\
+function deserialize_A(d){
+return{U8_BYTES:d.$F($c,false),s:v.s.push,bytes:v.d.bytes};
+}
+const CODECS = { \"date\": { serialize: (d) => d.length } }
+";
+        assert_eq!(minify_source(source, &[]), expected);
+    }
+
+    #[test]
+    fn test_minify_renames_type_functions() {
+        let source = "\
+function is_A(v) {
+    return typeof v.a === \"number\" && v.a - -1 > 0 && v.is_A === undefined
+}
+
+function serialize(type, value) {
+    if (is_A(value)) {
+        serialize_A(new Serializer(), value);
+    }
+}
+";
+        let expected = "\
+function $hb(v){
+return typeof v.a===\"number\"&&v.a- -1>0&&v.is_A===undefined
+}
+function serialize(type,value){
+if($hb(value)){
+$ib(new $Z(),value);
+}
+}
+";
+        let type_functions = ["is_A".to_owned(), "serialize_A".to_owned()];
+        assert_eq!(minify_source(source, &type_functions), expected);
+    }
+}
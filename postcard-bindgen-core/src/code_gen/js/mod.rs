@@ -1,38 +1,59 @@
+mod codec;
 mod des;
 mod general;
 mod generateable;
+mod minify;
+mod self_test;
 mod ser;
+mod split;
 mod type_checks;
 
 use core::borrow::Borrow;
+use std::{collections::HashMap, io};
 
-use des::{gen_des_functions, gen_deserialize_func, gen_deserializer_code};
+use codec::assert_codecs_registered;
+
+use des::{gen_check_deserialized, gen_des_functions, gen_deserialize_func, gen_deserializer_code};
 use genco::{
     prelude::js::JavaScript,
-    quote_in,
+    quote, quote_in,
     tokens::{quoted, FormatInto},
 };
-use general::gen_util;
-use generateable::gen_ts_typings;
-use ser::{gen_ser_functions, gen_serialize_func, gen_serializer_code};
+use general::{gen_known_types, gen_util};
+use generateable::{gen_ts_type_decls, gen_ts_typings};
+use minify::minify;
+use self_test::gen_self_test;
+use ser::{
+    gen_encoded_length_func, gen_ser_functions, gen_serialize_any_func, gen_serialize_func,
+    gen_serializer_code, gen_size_hint_functions,
+};
+use split::gen_split_exports;
 use type_checks::gen_type_checks;
 
-use crate::{registry::ContainerCollection, ExportFile, Exports};
+use crate::{
+    json_ir,
+    registry::{BindingType, Container, ContainerCollection},
+    ExportFile, Exports,
+};
+
+use super::{
+    export_registry::ExportMode,
+    utils::{ContainerFullQualifiedTypeBuilder, ContainerIdentifierBuilder, TokensIterExt},
+};
 
-use super::{export_registry::ExportMode, utils::TokensIterExt};
+pub use super::utils::IdentifierCase;
+pub use codec::Codec;
 
 const JS_ENUM_VARIANT_KEY: &str = "tag";
 const JS_ENUM_VARIANT_VALUE: &str = "value";
 const JS_OBJECT_VARIABLE: &str = "v";
 const JS_LOGIC_AND: &str = "&&";
-const JS_LOGIC_OR: &str = "||";
 
 type Tokens = genco::Tokens<JavaScript>;
 
 type VariablePath = super::variable_path::VariablePath<JavaScript>;
 type VariableAccess = super::variable_path::VariableAccess;
 type FieldAccessor<'a> = super::field_accessor::FieldAccessor<'a>;
-type AvailableCheck = super::available_check::AvailableCheck<JavaScript>;
 type Function = super::function::Function<JavaScript>;
 type FunctionArg = super::function::FunctionArg<JavaScript>;
 type ExportRegistry = super::export_registry::ExportRegistry<JavaScript>;
@@ -57,6 +78,72 @@ pub struct GenerationSettings {
     type_script_types: bool,
     module_structure: bool,
     esm_module: bool,
+    split_modules: bool,
+    optional_none: OptionalNone,
+    self_test: bool,
+    iterable_inputs: bool,
+    pointer_width: PointerWidth,
+    minify: bool,
+    ambiguous_types: AmbiguousTypes,
+    ident_case: IdentifierCase,
+    validate_deserialized: bool,
+    codecs: Vec<(String, Codec)>,
+}
+
+/// Representation of [`Option::None`] in javascript.
+///
+/// This controls which values are treated as absent at serialization and runtime type checks
+/// and which value is produced at deserialization.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OptionalNone {
+    /// `None` is `undefined`.
+    #[default]
+    Undefined,
+    /// `None` is `null`, which is what `JSON.parse` produces for absent values.
+    Null,
+    /// Both `undefined` and `null` are accepted as `None`. Deserialization produces `undefined`.
+    Either,
+}
+
+/// Handling of types the runtime type checks can't tell apart.
+///
+/// Two types with the same fields, or enums with the same variants, pass each other's
+/// `is_*` type checks, so `serializeAny` can't pick one of them. Fields referencing other
+/// types are only the same if they reference the same type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AmbiguousTypes {
+    /// Ambiguous types are not reported.
+    #[default]
+    Allow,
+    /// Ambiguous types are returned as warnings in [`ExportMeta::warnings`] by [`generate`]
+    /// and the other generate functions.
+    /// The build script helper `postcard_bindgen::build::write_bindings` prints them as
+    /// `cargo:warning`.
+    Warn,
+    /// Generating bindings panics if types are ambiguous.
+    Deny,
+}
+
+/// Width of `isize` and `usize` on the target which encodes or decodes the data.
+///
+/// 64 bit values may exceed [`Number.MAX_SAFE_INTEGER`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Number/MAX_SAFE_INTEGER),
+/// these are deserialized as `BigInt` then.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PointerWidth {
+    /// 32 bit targets, e.g. most microcontrollers. The values are always a `number`.
+    Bits32,
+    /// 64 bit targets.
+    #[default]
+    Bits64,
+}
+
+impl PointerWidth {
+    pub(crate) fn bytes(self) -> usize {
+        match self {
+            PointerWidth::Bits32 => 4,
+            PointerWidth::Bits64 => 8,
+        }
+    }
 }
 
 impl GenerationSettings {
@@ -69,6 +156,16 @@ impl GenerationSettings {
             type_script_types: true,
             module_structure: true,
             esm_module: true,
+            split_modules: false,
+            optional_none: OptionalNone::Undefined,
+            self_test: false,
+            iterable_inputs: false,
+            pointer_width: PointerWidth::Bits64,
+            minify: false,
+            ambiguous_types: AmbiguousTypes::Allow,
+            ident_case: IdentifierCase::ScreamingSnake,
+            validate_deserialized: false,
+            codecs: Vec::new(),
         }
     }
 
@@ -97,6 +194,9 @@ impl GenerationSettings {
     /// Enabling or disabling of runtime type checks code generation.
     ///
     /// Disabling this should lead to a speed increase at serialization.
+    ///
+    /// With serialization and runtime type checks enabled, a `serializeAny(value)` function is
+    /// generated, which picks the type to serialize by running all type checks.
     pub fn runtime_type_checks(mut self, enabled: bool) -> Self {
         self.runtime_type_checks = enabled;
         self
@@ -117,17 +217,131 @@ impl GenerationSettings {
 
     /// Enabling or disabling ESM (as opposed to cjs) output
     ///
-    /// Enabling will change the way the `serialize` and `desrialize`
+    /// Enabling will change the way the `serialize` and `deserialize`
     /// functions are exported to bring them in line with ESM standards/importers.
     /// The package.json file also gets `"type": "module"` added,
     /// so package managers/bundlers importing it know it's ESM.
     ///
-    ///
     /// Disabling this will use the default `module.exports`-style export (cjs)
     pub fn esm_module(mut self, enabled: bool) -> Self {
         self.esm_module = enabled;
         self
     }
+
+    /// Enabling or disabling of one module per type.
+    ///
+    /// Instead of a single `index.js`, the package holds a `runtime.js`, a module for each type
+    /// in `types/` and an `index.js` with the functions taking the type name. A type module
+    /// exports `serialize(value)`, `encodedLength(value)`, `deserialize(bytes)` and
+    /// `deserializeLenient(bytes)` of its type, the index re-exports each of them as namespace
+    /// named by the type identifier, e.g. `POINT.serialize(value)`. Bundlers drop the modules
+    /// of the types which are not imported, so large bindings only cost what is used.
+    ///
+    /// The modules are always ES modules, the ESM option is ignored. The typescript
+    /// declarations only cover the index.
+    pub fn split_modules(mut self, enabled: bool) -> Self {
+        self.split_modules = enabled;
+        self
+    }
+
+    /// Sets the javascript value which represents [`Option::None`].
+    ///
+    /// Defaults to [`OptionalNone::Undefined`].
+    pub fn optional_none(mut self, optional_none: OptionalNone) -> Self {
+        self.optional_none = optional_none;
+        self
+    }
+
+    /// Enabling or disabling of the `__selfTest()` function code generation.
+    ///
+    /// The function round trips a sample value of each type through serialization and
+    /// deserialization and throws on a mismatch. This can be run in a test suite to catch
+    /// regressions when the bindings change. It's only generated if serialization and
+    /// deserialization are enabled.
+    pub fn self_test(mut self, enabled: bool) -> Self {
+        self.self_test = enabled;
+        self
+    }
+
+    /// Enabling or disabling of iterable inputs at the runtime type checks.
+    ///
+    /// Serialization accepts any iterable like a `Set`, a generator or a typed array where a
+    /// `Vec` is expected and converts it into an array. The runtime type checks only accept
+    /// arrays unless this is enabled. The items of other iterables are not checked then, as
+    /// checking would consume generators. Deserialization always produces arrays.
+    pub fn iterable_inputs(mut self, enabled: bool) -> Self {
+        self.iterable_inputs = enabled;
+        self
+    }
+
+    /// Sets the width of `isize` and `usize`, which has to match the target the data is
+    /// exchanged with. Serialization and the runtime type checks reject values not fitting
+    /// into this width.
+    ///
+    /// Defaults to [`PointerWidth::Bits64`].
+    pub fn pointer_width(mut self, pointer_width: PointerWidth) -> Self {
+        self.pointer_width = pointer_width;
+        self
+    }
+
+    /// Enabling or disabling of minified javascript output.
+    ///
+    /// By default the generated code is formatted to be readable for debugging. Minifying
+    /// removes the indentation and the whitespace between tokens and renames the internal
+    /// constants, helper functions and the members of the serializer and deserializer to
+    /// short names, which reduces the bundle size. The exported functions and the typescript
+    /// declarations keep their names, so code using the bindings works with both outputs.
+    /// Line breaks are kept, as the generated code relies on automatic semicolon insertion.
+    pub fn minify(mut self, enabled: bool) -> Self {
+        self.minify = enabled;
+        self
+    }
+
+    /// Sets how types are handled which the runtime type checks can't tell apart.
+    ///
+    /// Defaults to [`AmbiguousTypes::Allow`].
+    pub fn ambiguous_types(mut self, ambiguous_types: AmbiguousTypes) -> Self {
+        self.ambiguous_types = ambiguous_types;
+        self
+    }
+
+    /// Sets the casing of the type names in the identifiers of the generated helper
+    /// functions like `serialize_*` and `is_*`, which show up in stack traces.
+    ///
+    /// Defaults to [`IdentifierCase::ScreamingSnake`].
+    pub fn ident_case(mut self, ident_case: IdentifierCase) -> Self {
+        self.ident_case = ident_case;
+        self
+    }
+
+    /// Enabling or disabling of the validation of deserialized values.
+    ///
+    /// `deserialize` and `deserializeLenient` run the runtime type check of the requested
+    /// type on the decoded value and throw a `DeserializationError` of kind `invalid_value`
+    /// if it doesn't pass. This catches byte streams which decode to values the bindings
+    /// would not accept for serialization, at the cost of checking every value twice. Only
+    /// applied if runtime type checks are enabled.
+    pub fn validate_deserialized(mut self, enabled: bool) -> Self {
+        self.validate_deserialized = enabled;
+        self
+    }
+
+    /// Registers a [`Codec`] under the given name.
+    ///
+    /// Struct fields registered with this codec name are exposed in javascript as the value
+    /// the codec produces. Generating bindings panics if a field uses a codec which is not
+    /// registered.
+    pub fn codec(mut self, name: impl Into<String>, codec: Codec) -> Self {
+        self.codecs.push((name.into(), codec));
+        self
+    }
+}
+
+impl GenerationSettings {
+    /// Deserialized values are only validated if the type checks are generated.
+    fn validates_deserialized(&self) -> bool {
+        self.runtime_type_checks && self.validate_deserialized
+    }
 }
 
 impl Default for GenerationSettings {
@@ -139,16 +353,102 @@ impl Default for GenerationSettings {
             type_script_types: false,
             module_structure: true,
             esm_module: false,
+            split_modules: false,
+            optional_none: OptionalNone::Undefined,
+            self_test: false,
+            iterable_inputs: false,
+            pointer_width: PointerWidth::Bits64,
+            minify: false,
+            ambiguous_types: AmbiguousTypes::Allow,
+            ident_case: IdentifierCase::ScreamingSnake,
+            validate_deserialized: false,
+            codecs: Vec::new(),
         }
     }
 }
 
+/// Panics if two containers generate the same javascript identifier.
+///
+/// The per type functions like `serialize_*` share one namespace, so one type would
+/// silently use the functions of the other one.
+fn assert_unique_identifiers(
+    containers: impl Iterator<Item = Container>,
+    ident_case: IdentifierCase,
+) {
+    let mut identifiers = HashMap::new();
+    for container in containers {
+        let identifier = ContainerIdentifierBuilder::from(&container)
+            .ident_case(ident_case)
+            .build();
+        let full_qualified = ContainerFullQualifiedTypeBuilder::from(&container).build();
+        if let Some(other) = identifiers.insert(identifier.clone(), full_qualified.clone()) {
+            panic!(
+                "`{other}` and `{full_qualified}` both generate the javascript identifier `{identifier}`, rename one of the types"
+            );
+        }
+    }
+}
+
+/// Reports groups of containers with the same structure, returns the warnings if they are
+/// not denied.
+fn check_ambiguous_types(
+    containers: impl Iterator<Item = Container>,
+    ambiguous_types: AmbiguousTypes,
+) -> Vec<String> {
+    if ambiguous_types == AmbiguousTypes::Allow {
+        return Vec::new();
+    }
+
+    let mut groups: Vec<(BindingType, Vec<String>)> = Vec::new();
+    for container in containers {
+        let full_qualified = ContainerFullQualifiedTypeBuilder::from(&container).build();
+        match groups.iter_mut().find(|(ty, _)| *ty == container.r#type) {
+            Some((_, names)) => names.push(full_qualified),
+            None => groups.push((container.r#type, vec![full_qualified])),
+        }
+    }
+
+    let mut warnings = Vec::new();
+    for (_, names) in groups.into_iter().filter(|(_, names)| names.len() > 1) {
+        let names = names
+            .iter()
+            .map(|name| format!("`{name}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let message = format!(
+            "{names} have the same structure, the runtime type checks can't tell them apart"
+        );
+        match ambiguous_types {
+            AmbiguousTypes::Deny => panic!("{message}"),
+            _ => warnings.push(message),
+        }
+    }
+    warnings
+}
+
 /// Metadata for JS export
 ///
 /// Contains information about the exported JS package needed to
 /// complete the full npm_package (e.g. if it's an ESM module or not)
 pub struct ExportMeta {
     pub esm_module: bool,
+    /// The files are the modules of [`GenerationSettings::split_modules`], named by their
+    /// path in the package.
+    pub split_modules: bool,
+    /// The types the runtime type checks can't tell apart, if
+    /// [`GenerationSettings::ambiguous_types`] is set to [`AmbiguousTypes::Warn`].
+    pub warnings: Vec<String>,
+}
+
+impl ExportMeta {
+    /// Metadata of the outputs which are always a single ES module.
+    fn single_esm_module(warnings: Vec<String>) -> Self {
+        Self {
+            esm_module: true,
+            split_modules: false,
+            warnings,
+        }
+    }
 }
 
 pub fn generate(
@@ -157,21 +457,117 @@ pub fn generate(
 ) -> (Exports<JavaScript>, ExportMeta) {
     let gen_settings = gen_settings.borrow();
 
+    let warnings = prepare_containers(&mut containers, gen_settings);
+
+    let exports = if gen_settings.split_modules {
+        gen_split_exports(&containers, gen_settings)
+    } else if gen_settings.esm_module {
+        gen_exports(&containers, gen_settings, ExportMode::Esm)
+    } else {
+        gen_exports(&containers, gen_settings, ExportMode::Cjs)
+    };
+
+    // Create metadata about export
+    let export_metadata = ExportMeta {
+        esm_module: gen_settings.esm_module || gen_settings.split_modules,
+        split_modules: gen_settings.split_modules,
+        warnings,
+    };
+
+    (exports, export_metadata)
+}
+
+/// Generates the bindings from a JSON document written by
+/// [`ContainerCollection::to_json`], e.g. a schema file checked in next to the bindings.
+///
+/// Fails if the document can't be read or isn't a valid description of the types, see
+/// [`json_ir::from_json`].
+pub fn generate_js_from_ir(
+    reader: impl io::Read,
+    gen_settings: impl Borrow<GenerationSettings>,
+) -> io::Result<(Exports<JavaScript>, ExportMeta)> {
+    Ok(generate(json_ir::from_json(reader)?, gen_settings))
+}
+
+/// Generates the bindings once as CommonJS and once as ES module, returned in this order.
+///
+/// The ESM and split module options of [`GenerationSettings`] are ignored. The files of both
+/// exports only differ in the way the functions are exported, so the bindings can be packaged
+/// for node, bundlers and browsers at once. The [`ExportMeta`] describes the ES module.
+pub fn generate_dual(
+    mut containers: ContainerCollection,
+    gen_settings: impl Borrow<GenerationSettings>,
+) -> (Exports<JavaScript>, Exports<JavaScript>, ExportMeta) {
+    let gen_settings = gen_settings.borrow();
+
+    let warnings = prepare_containers(&mut containers, gen_settings);
+
+    (
+        gen_exports(&containers, gen_settings, ExportMode::Cjs),
+        gen_exports(&containers, gen_settings, ExportMode::Esm),
+        ExportMeta::single_esm_module(warnings),
+    )
+}
+
+/// Generates the bindings as a single ES module, which runs in browsers and deno as well as
+/// in node.
+///
+/// The generated code only relies on the language builtins like `Uint8Array` and `BigInt`
+/// and on the `TextEncoder` and `TextDecoder` web APIs, no node globals like `Buffer` or
+/// `module.exports`. The ESM and split module options of [`GenerationSettings`] are ignored.
+pub fn generate_web(
+    mut containers: ContainerCollection,
+    gen_settings: impl Borrow<GenerationSettings>,
+) -> (Exports<JavaScript>, ExportMeta) {
+    let gen_settings = gen_settings.borrow();
+
+    let warnings = prepare_containers(&mut containers, gen_settings);
+
+    (
+        gen_exports(&containers, gen_settings, ExportMode::Esm),
+        ExportMeta::single_esm_module(warnings),
+    )
+}
+
+/// Checks the containers against the settings and resolves the settings which change the
+/// containers themselves. Returns the warnings of the checks.
+fn prepare_containers(
+    containers: &mut ContainerCollection,
+    gen_settings: &GenerationSettings,
+) -> Vec<String> {
+    assert_codecs_registered(containers.all_containers(), &gen_settings.codecs);
+
     if !gen_settings.module_structure {
         containers.flatten();
     }
 
-    let export_mode = if gen_settings.esm_module {
-        ExportMode::Esm
-    } else {
-        ExportMode::Cjs
-    };
+    assert_unique_identifiers(containers.all_containers(), gen_settings.ident_case);
+    let warnings = check_ambiguous_types(containers.all_containers(), gen_settings.ambiguous_types);
+
+    containers.resolve_pointer_width(gen_settings.pointer_width.bytes());
+    warnings
+}
 
+fn gen_exports(
+    containers: &ContainerCollection,
+    gen_settings: &GenerationSettings,
+    export_mode: ExportMode,
+) -> Exports<JavaScript> {
     let mut export_files = Vec::new();
 
     export_files.push(ExportFile {
         content_type: "util".to_owned(),
-        content: gen_util(),
+        content: gen_util(gen_settings),
+    });
+
+    let mut export_registry = ExportRegistry::new(export_mode.clone());
+    let mut tokens = gen_known_types(containers.all_containers(), false, &mut export_registry);
+    tokens.line();
+    tokens.append(export_registry);
+
+    export_files.push(ExportFile {
+        content_type: "known_types".to_owned(),
+        content: tokens,
     });
 
     if gen_settings.ser {
@@ -182,7 +578,15 @@ pub fn generate(
 
         let mut tokens = Tokens::new();
 
-        tokens.append(gen_ser_functions(containers.all_containers()));
+        tokens.append(gen_ser_functions(
+            containers.all_containers(),
+            gen_settings.ident_case,
+        ));
+        tokens.line();
+        tokens.append(gen_size_hint_functions(
+            containers.all_containers(),
+            gen_settings.ident_case,
+        ));
         tokens.line();
 
         let mut export_registry = ExportRegistry::new(export_mode.clone());
@@ -190,9 +594,29 @@ pub fn generate(
         tokens.append(gen_serialize_func(
             containers.all_containers(),
             gen_settings.runtime_type_checks,
+            false,
+            gen_settings.ident_case,
+            &mut export_registry,
+        ));
+        tokens.line();
+        tokens.append(gen_encoded_length_func(
+            containers.all_containers(),
+            gen_settings.runtime_type_checks,
+            false,
+            gen_settings.ident_case,
             &mut export_registry,
         ));
 
+        if gen_settings.runtime_type_checks {
+            tokens.line();
+            tokens.append(gen_serialize_any_func(
+                containers.all_containers(),
+                false,
+                gen_settings.ident_case,
+                &mut export_registry,
+            ));
+        }
+
         tokens.line();
         tokens.append(export_registry);
 
@@ -203,6 +627,8 @@ pub fn generate(
     }
 
     if gen_settings.des {
+        let validate_deserialized = gen_settings.validates_deserialized();
+
         export_files.push(ExportFile {
             content_type: "deserializer".to_owned(),
             content: gen_deserializer_code(),
@@ -210,13 +636,23 @@ pub fn generate(
 
         let mut tokens = Tokens::new();
 
-        tokens.append(gen_des_functions(containers.all_containers()));
+        tokens.append(gen_des_functions(
+            containers.all_containers(),
+            gen_settings.ident_case,
+        ));
         tokens.line();
 
-        let mut export_registry = ExportRegistry::new(export_mode);
+        let mut export_registry = ExportRegistry::new(export_mode.clone());
 
+        if validate_deserialized {
+            tokens.append(gen_check_deserialized());
+            tokens.line();
+        }
         tokens.append(gen_deserialize_func(
             containers.all_containers(),
+            false,
+            validate_deserialized,
+            gen_settings.ident_case,
             &mut export_registry,
         ));
         tokens.line();
@@ -229,32 +665,204 @@ pub fn generate(
         });
     }
 
+    if gen_settings.ser && gen_settings.des && gen_settings.self_test {
+        let mut export_registry = ExportRegistry::new(export_mode);
+
+        let mut tokens = gen_self_test(
+            containers.all_containers(),
+            false,
+            gen_settings.ident_case,
+            &mut export_registry,
+        );
+        tokens.line();
+        tokens.append(export_registry);
+
+        export_files.push(ExportFile {
+            content_type: "self_test".to_owned(),
+            content: tokens,
+        });
+    }
+
     if gen_settings.runtime_type_checks {
         export_files.push(ExportFile {
             content_type: "runtime_checks".to_owned(),
-            content: gen_type_checks(containers.all_containers()),
+            content: gen_type_checks(containers.all_containers(), gen_settings.ident_case),
         });
     }
 
     if gen_settings.type_script_types {
-        let ts = gen_ts_typings(&containers);
+        let ts = gen_ts_typings(containers, gen_settings);
         export_files.push(ExportFile {
             content_type: "ts".to_owned(),
             content: ts,
         });
     }
 
-    // Create metadata about export
-    let export_metadata = ExportMeta {
-        esm_module: gen_settings.esm_module,
+    if gen_settings.minify {
+        let containers = containers.all_containers().collect::<Vec<_>>();
+        for file in export_files.iter_mut().filter(|f| f.content_type != "ts") {
+            file.content = minify(
+                std::mem::take(&mut file.content),
+                &containers,
+                gen_settings.ident_case,
+            );
+        }
+    }
+
+    Exports {
+        files: export_files,
+    }
+}
+
+/// Generates a single self-contained typescript module.
+///
+/// The module holds the type declarations, the runtime and the typed `serialize` and
+/// `deserialize` functions, which are exported with `export` syntax. Structs are object
+/// types, enums unions discriminated by their `tag` and the functions are generic over the
+/// type name, so passing a value of the wrong shape fails at compile time. The serialized
+/// bytes are returned as `Uint8Array`.
+///
+/// The runtime itself is untyped, so the module is excluded from type checking with
+/// `// @ts-nocheck`. The exported types and function signatures are still checked at the
+/// call sites of the importing modules.
+///
+/// The ESM and typescript options of [`GenerationSettings`] are ignored, as both are
+/// always needed for a typescript module, and so is the split module option.
+pub fn generate_ts(
+    containers: ContainerCollection,
+    gen_settings: impl Borrow<GenerationSettings>,
+) -> (Tokens, ExportMeta) {
+    gen_ts_module(containers, gen_settings.borrow(), false)
+}
+
+/// Generates a [`generate_ts`] module for deno, which additionally disables the deno linter
+/// for the module.
+pub fn generate_deno(
+    containers: ContainerCollection,
+    gen_settings: impl Borrow<GenerationSettings>,
+) -> (Tokens, ExportMeta) {
+    gen_ts_module(containers, gen_settings.borrow(), true)
+}
+
+fn gen_ts_module(
+    mut containers: ContainerCollection,
+    gen_settings: &GenerationSettings,
+    deno: bool,
+) -> (Tokens, ExportMeta) {
+    let warnings = prepare_containers(&mut containers, gen_settings);
+
+    let mut export_registry = ExportRegistry::new(ExportMode::Esm);
+
+    let mut tokens = quote! {
+        $("// @ts-nocheck")
+        $(if deno {
+            $("// deno-lint-ignore-file")
+        })
+
+        $(gen_ts_type_decls(&containers, gen_settings))
+
+        $(gen_util(gen_settings))
+
+        $(gen_known_types(containers.all_containers(), true, &mut export_registry))
     };
 
-    (
-        Exports {
-            files: export_files,
-        },
-        export_metadata,
-    )
+    if gen_settings.ser {
+        tokens.line();
+        tokens.append(gen_serializer_code());
+    }
+
+    if gen_settings.des {
+        tokens.line();
+        tokens.append(gen_deserializer_code());
+    }
+
+    if gen_settings.runtime_type_checks {
+        tokens.line();
+        tokens.append(gen_type_checks(
+            containers.all_containers(),
+            gen_settings.ident_case,
+        ));
+    }
+
+    if gen_settings.ser {
+        tokens.line();
+        tokens.append(gen_ser_functions(
+            containers.all_containers(),
+            gen_settings.ident_case,
+        ));
+        tokens.line();
+        tokens.append(gen_size_hint_functions(
+            containers.all_containers(),
+            gen_settings.ident_case,
+        ));
+        tokens.line();
+        tokens.append(gen_serialize_func(
+            containers.all_containers(),
+            gen_settings.runtime_type_checks,
+            true,
+            gen_settings.ident_case,
+            &mut export_registry,
+        ));
+        tokens.line();
+        tokens.append(gen_encoded_length_func(
+            containers.all_containers(),
+            gen_settings.runtime_type_checks,
+            true,
+            gen_settings.ident_case,
+            &mut export_registry,
+        ));
+
+        if gen_settings.runtime_type_checks {
+            tokens.line();
+            tokens.append(gen_serialize_any_func(
+                containers.all_containers(),
+                true,
+                gen_settings.ident_case,
+                &mut export_registry,
+            ));
+        }
+    }
+
+    if gen_settings.des {
+        tokens.line();
+        tokens.append(gen_des_functions(
+            containers.all_containers(),
+            gen_settings.ident_case,
+        ));
+        tokens.line();
+        if gen_settings.validates_deserialized() {
+            tokens.append(gen_check_deserialized());
+            tokens.line();
+        }
+        tokens.append(gen_deserialize_func(
+            containers.all_containers(),
+            true,
+            gen_settings.validates_deserialized(),
+            gen_settings.ident_case,
+            &mut export_registry,
+        ));
+    }
+
+    if gen_settings.ser && gen_settings.des && gen_settings.self_test {
+        tokens.line();
+        tokens.append(gen_self_test(
+            containers.all_containers(),
+            true,
+            gen_settings.ident_case,
+            &mut export_registry,
+        ));
+    }
+
+    tokens.line();
+    tokens.append(export_registry);
+
+    let tokens = if gen_settings.minify {
+        let containers = containers.all_containers().collect::<Vec<_>>();
+        minify(tokens, &containers, gen_settings.ident_case)
+    } else {
+        tokens
+    };
+    (tokens, ExportMeta::single_esm_module(warnings))
 }
 
 impl<I, F> TokensIterExt<JavaScript, F> for I
@@ -263,7 +871,31 @@ where
     F: FormatInto<JavaScript>,
 {
     const LOGICAL_AND: &'static str = JS_LOGIC_AND;
-    const LOGICAL_OR: &'static str = JS_LOGIC_OR;
+}
+
+/// Key of an object property, which is quoted if the name is no identifier, e.g. for a field
+/// renamed with `#[serde(rename_all = "kebab-case")]`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PropertyKey<'a>(pub &'a str);
+
+impl PropertyKey<'_> {
+    fn is_identifier(&self) -> bool {
+        let mut chars = self.0.chars();
+        chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+    }
+}
+
+impl FormatInto<JavaScript> for PropertyKey<'_> {
+    fn format_into(self, tokens: &mut Tokens) {
+        if self.is_identifier() {
+            quote_in!(*tokens => $(self.0))
+        } else {
+            quote_in!(*tokens => $(quoted(self.0)))
+        }
+    }
 }
 
 impl FormatInto<JavaScript> for FieldAccessor<'_> {
@@ -271,7 +903,7 @@ impl FormatInto<JavaScript> for FieldAccessor<'_> {
         quote_in! { *tokens =>
             $(match self {
                 Self::Array | Self::None => (),
-                Self::Object(n) => $n:$[' '],
+                Self::Object(n) => $(PropertyKey(n)):$[' '],
             })
         }
     }
@@ -299,18 +931,11 @@ impl FormatInto<JavaScript> for VariableAccess {
         quote_in! { *tokens =>
             $(match self {
                 Self::Indexed(index) => [$index],
-                Self::Field(name) => .$name,
-            })
-        }
-    }
-}
-
-impl FormatInto<JavaScript> for AvailableCheck {
-    fn format_into(self, tokens: &mut Tokens) {
-        quote_in! { *tokens =>
-            $(match self {
-                AvailableCheck::Object(path, name) => $(quoted(name)) in $path,
-                AvailableCheck::None => ()
+                Self::Field(name) => $(if PropertyKey(&name).is_identifier() {
+                    .$name
+                } else {
+                    [$(quoted(name))]
+                }),
             })
         }
     }
@@ -319,7 +944,7 @@ impl FormatInto<JavaScript> for AvailableCheck {
 impl FormatInto<JavaScript> for FunctionArg {
     fn format_into(self, tokens: &mut Tokens) {
         quote_in! { *tokens =>
-            $(self.name)
+            $(self.name)$(if let Some(r#type) = self.r#type => :$[' ']$r#type)
         }
     }
 }
@@ -327,7 +952,7 @@ impl FormatInto<JavaScript> for FunctionArg {
 impl FormatInto<JavaScript> for Function {
     fn format_into(self, tokens: &mut Tokens) {
         quote_in! { *tokens =>
-            function $(self.name)($(for arg in self.args join (, ) => $arg)) {
+            function $(self.name)($(for arg in self.args join (, ) => $arg))$(if let Some(return_type) = self.return_type => :$[' ']$return_type) {
                 $(self.body)
             }
         }
@@ -339,13 +964,13 @@ impl FormatInto<JavaScript> for ExportRegistry {
         match self.export_mode {
             ExportMode::Cjs => {
                 quote_in! { *tokens =>
-                    $(for export in self.exports join () => exports.$(&export) = $export)
+                    $(for export in self.exports join ($['\r']) => exports.$(&export) = $export)
                 }
             }
             ExportMode::Esm => {
                 quote_in! { *tokens =>
                     export {
-                        $(for export in self.exports join (,) => $export)
+                        $(for export in self.exports join (,$['\r']) => $export)
                     };
                 }
             }
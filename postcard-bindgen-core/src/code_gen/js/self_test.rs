@@ -0,0 +1,223 @@
+use genco::{
+    lang::JavaScript,
+    quote,
+    tokens::{quoted, FormatInto},
+};
+
+use crate::{
+    code_gen::{
+        js::{codec, Function, PropertyKey, Tokens, JS_ENUM_VARIANT_KEY, JS_ENUM_VARIANT_VALUE},
+        utils::{
+            ContainerFullQualifiedTypeBuilder, ContainerIdentifierBuilder, IdentifierCase,
+            JoinType, TokensIterExt,
+        },
+    },
+    function_args,
+    registry::{BindingType, Container, EnumVariantType, StructField},
+    type_info::{ArrayFormat, MapMeta, NumberMeta, StringFormat, StringMeta, ValueType},
+};
+
+use super::ExportRegistry;
+
+/// Generates a `__selfTest()` function, which serializes a sample value of each type,
+/// deserializes it again and throws if the result differs from the sample.
+///
+/// The samples are built from minimal defaults, e.g. `0` for numbers, `""` for strings,
+/// empty arrays or the first variant of an enum.
+pub fn gen_self_test(
+    containers: impl Iterator<Item = Container>,
+    type_script: bool,
+    ident_case: IdentifierCase,
+    export_registry: &mut ExportRegistry,
+) -> Tokens {
+    // Enums without variants have no value which could be sampled.
+    let containers = containers
+        .filter(|container| {
+            !matches!(&container.r#type, BindingType::Enum(enum_type) if enum_type.variants.is_empty())
+        })
+        .collect::<Vec<_>>();
+
+    let sample_functions = containers
+        .iter()
+        .map(|container| gen_sample_function(container, ident_case))
+        .join_with_empty_line();
+
+    let cases = containers
+        .iter()
+        .map(|container| {
+            let full_qualified = ContainerFullQualifiedTypeBuilder::from(container).build();
+            let container_ident = ContainerIdentifierBuilder::from(container)
+                .ident_case(ident_case)
+                .build();
+            quote!([$(quoted(full_qualified)), sample_$container_ident()])
+        })
+        .join_with([JoinType::Comma, JoinType::LineBreak]);
+
+    let body = quote! {
+        [
+            $cases
+        ].forEach(([type, value]) => {
+            const bytes = serialize(type, value);
+            if (!deep_equal(deserialize(type, bytes), value)) {
+                throw "self test failed for type " + type;
+            }
+        });
+    };
+
+    export_registry.push("__selfTest");
+
+    let self_test = if type_script {
+        Function::new("__selfTest", (), body, "void")
+    } else {
+        Function::new_untyped("__selfTest", (), body)
+    };
+
+    quote! {
+        $sample_functions
+
+        $(gen_deep_equal())
+
+        $self_test
+    }
+}
+
+fn gen_deep_equal() -> impl FormatInto<JavaScript> {
+    Function::new_untyped(
+        "deep_equal",
+        function_args!["a", "b"],
+        quote! {
+            if (a === b) {
+                return true;
+            }
+            if ((Array.isArray(a) || ArrayBuffer.isView(a)) && (Array.isArray(b) || ArrayBuffer.isView(b))) {
+                return a.length === b.length && Array.from(a).every((v, i) => deep_equal(v, b[i]));
+            }
+            if (a instanceof Map && b instanceof Map) {
+                return a.size === b.size && [...a].every(([k, v]) => b.has(k) && deep_equal(v, b.get(k)));
+            }
+            if (typeof a === "object" && typeof b === "object" && a !== null && b !== null) {
+                const keys = Object.keys(a);
+                return keys.length === Object.keys(b).length && keys.every((k) => k in b && deep_equal(a[k], b[k]));
+            }
+            return false;
+        },
+    )
+}
+
+fn gen_sample_function(
+    container: &Container,
+    ident_case: IdentifierCase,
+) -> impl FormatInto<JavaScript> {
+    let container_ident = ContainerIdentifierBuilder::from(container)
+        .ident_case(ident_case)
+        .build();
+    let sample = match &container.r#type {
+        BindingType::Struct(struct_type) => gen_sample_fields(&struct_type.fields, ident_case),
+        BindingType::TupleStruct(tuple_struct_type) => {
+            gen_sample_indexed(&tuple_struct_type.fields, ident_case)
+        }
+        BindingType::UnitStruct(_) => quote!({}),
+        BindingType::Enum(enum_type) => {
+            let variant = &enum_type.variants[0];
+            let variant_name = quoted(&variant.name);
+            match (&enum_type.tag, &variant.inner_type) {
+                // the fields of internally tagged variants are next to the tag
+                (Some(tag), EnumVariantType::Empty) => {
+                    quote!({ $(PropertyKey(tag)): $variant_name })
+                }
+                (Some(tag), EnumVariantType::Tuple(fields)) => {
+                    quote!({ $(PropertyKey(tag)): $variant_name, ...$(gen_sample(&fields[0], ident_case)) })
+                }
+                (Some(tag), EnumVariantType::NewType(fields)) => {
+                    quote!({ $(PropertyKey(tag)): $variant_name, ...$(gen_sample_fields(fields, ident_case)) })
+                }
+                (None, EnumVariantType::Empty) => quote!({ $JS_ENUM_VARIANT_KEY: $variant_name }),
+                (None, EnumVariantType::Tuple(fields)) => {
+                    let value = match fields.len() {
+                        1 => gen_sample(&fields[0], ident_case),
+                        _ => gen_sample_indexed(fields, ident_case),
+                    };
+                    quote!({ $JS_ENUM_VARIANT_KEY: $variant_name, $JS_ENUM_VARIANT_VALUE: $value })
+                }
+                (None, EnumVariantType::NewType(fields)) => {
+                    quote!({ $JS_ENUM_VARIANT_KEY: $variant_name, $JS_ENUM_VARIANT_VALUE: $(gen_sample_fields(fields, ident_case)) })
+                }
+            }
+        }
+    };
+
+    Function::new_untyped(quote!(sample_$container_ident), (), quote!(return $sample;))
+}
+
+fn gen_sample_fields(fields: &[StructField], ident_case: IdentifierCase) -> Tokens {
+    let fields = fields
+        .iter()
+        .map(|field| {
+            // Skipped fields are not encoded, deserializing them yields the default.
+            let sample = if field.skip {
+                let default = field.default.as_deref().unwrap_or("undefined");
+                quote!($default)
+            } else if let Some(codec) = &field.codec {
+                codec::gen_decoded_value(codec, gen_sample(&field.v_type, ident_case))
+            } else {
+                gen_sample(&field.v_type, ident_case)
+            };
+            quote!($(PropertyKey(&field.name)): $sample)
+        })
+        .join_with_comma();
+    quote!({ $fields })
+}
+
+fn gen_sample_indexed(fields: &[ValueType], ident_case: IdentifierCase) -> Tokens {
+    let fields = fields
+        .iter()
+        .map(|field| gen_sample(field, ident_case))
+        .join_with_comma();
+    quote!([$fields])
+}
+
+fn gen_sample(value_type: &ValueType, ident_case: IdentifierCase) -> Tokens {
+    match value_type {
+        ValueType::Number(NumberMeta::Integer { non_zero: true, .. }) => quote!(1),
+        ValueType::Number(_) => quote!(0),
+        ValueType::Bool(_) => quote!(false),
+        ValueType::Unit(_) => quote!(null),
+        ValueType::String(StringMeta { char: true, .. }) => quote!("a"),
+        // the shortest strings the type checks of the formats accept
+        ValueType::String(StringMeta {
+            format: Some(StringFormat::Url),
+            ..
+        }) => quote!("http://a/"),
+        ValueType::String(StringMeta {
+            format: Some(StringFormat::SemVer),
+            ..
+        }) => quote!("0.0.0"),
+        ValueType::String(_) => quote!(""),
+        ValueType::Optional(_) => quote!(NONE),
+        ValueType::Array(array_meta) => match array_meta
+            .length
+            .or(array_meta.format.map(ArrayFormat::length))
+        {
+            Some(length) => {
+                quote!(Array.from({ length: $length }, () => $(gen_sample(&array_meta.items_type, ident_case))))
+            }
+            None => quote!([]),
+        },
+        ValueType::Tuple(tuple_meta) => gen_sample_indexed(&tuple_meta.items_types, ident_case),
+        ValueType::Range(range_meta) => {
+            let bound = gen_sample(&range_meta.bounds_type, ident_case);
+            quote!({ start: $(bound.clone()), end: $bound })
+        }
+        ValueType::Map(MapMeta { key_type, .. }) => match key_type.as_ref() {
+            ValueType::String(_) => quote!({}),
+            _ => quote!(new Map()),
+        },
+        ValueType::Set(_) => quote!(new Set()),
+        ValueType::Object(object_meta) => {
+            let container_ident = ContainerIdentifierBuilder::from(object_meta)
+                .ident_case(ident_case)
+                .build();
+            quote!(sample_$container_ident())
+        }
+    }
+}
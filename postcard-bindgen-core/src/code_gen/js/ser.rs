@@ -9,7 +9,10 @@ use crate::{
         js::{
             generateable::container::BindingTypeGenerateable, Function, Tokens, JS_OBJECT_VARIABLE,
         },
-        utils::{ContainerFullQualifiedTypeBuilder, ContainerIdentifierBuilder, TokensIterExt},
+        utils::{
+            ContainerFullQualifiedTypeBuilder, ContainerIdentifierBuilder, IdentifierCase,
+            JoinType, TokensIterExt,
+        },
     },
     function_args,
     registry::Container,
@@ -20,30 +23,78 @@ use super::{Case, DefaultCase, ExportRegistry, SwitchCase};
 pub fn gen_serializer_code() -> Tokens {
     quote! {
         class Serializer {
-            constructor() { this.bytes = [] }
-            finish = () => this.bytes
-            push_n = (bytes) => bytes.forEach((byte) => this.bytes.push(byte))
+            constructor(capacity) { this.bytes = new Uint8Array(capacity === undefined ? 0 : capacity); this.length = 0 }
+            finish = () => this.bytes.subarray(0, this.length)
+            reserve = (n) => { if (this.length + n > this.bytes.length) { const bytes = new Uint8Array(Math.max(this.bytes.length * 2, this.length + n)); bytes.set(this.bytes.subarray(0, this.length)); this.bytes = bytes } }
+            push = (byte) => { this.reserve(1); this.bytes[this.length++] = byte }
+            push_n = (bytes) => { this.reserve(bytes.length); this.bytes.set(bytes, this.length); this.length += bytes.length }
             serialize_bool = (value) => this.serialize_number(U8_BYTES, false, value ? 1 : 0)
-            serialize_number = (n_bytes, signed, value) => { if (n_bytes === U8_BYTES) { this.bytes.push(value) } else if (n_bytes === U16_BYTES || n_bytes === U32_BYTES || n_bytes === U64_BYTES || n_bytes === U128_BYTES) { const value_b = BigInt(value), buffer = signed ? varint(n_bytes, zig_zag(n_bytes, value_b)) : varint(n_bytes, value_b); this.push_n(buffer) } else { throw "byte count not supported" } }
+            serialize_number = (n_bytes, signed, value) => { if (n_bytes === U8_BYTES) { this.push(signed ? value & 0xFF : value) } else if (n_bytes === U16_BYTES || n_bytes === U32_BYTES || n_bytes === U64_BYTES || n_bytes === U128_BYTES) { const value_b = BigInt(value), buffer = signed ? varint(n_bytes, zig_zag(n_bytes, value_b)) : varint(n_bytes, value_b); this.push_n(buffer) } else { throw "byte count not supported" } }
             serialize_number_float = (n_bytes, value) => { const b_buffer = new ArrayBuffer(n_bytes), b_view = new DataView(b_buffer); if (n_bytes === U32_BYTES) { b_view.setFloat32(0, value, true) } else if (n_bytes === U64_BYTES) { b_view.setFloat64(0, value, true) } else { throw "byte count not supported" } this.push_n(new Uint8Array(b_buffer)) }
-            serialize_string = (str) => { this.push_n(varint(U32_BYTES, str.length)); const bytes = []; for (const c of str) { bytes.push(c.charCodeAt(0)) } this.push_n(bytes) }
-            serialize_array = (ser, array, len) => { if (len == undefined) this.push_n(varint(U32_BYTES, array.length)); array.slice(0, len != undefined ? len : array.length).forEach((v) => ser(this, v)) }
+            serialize_number_array = (n_bytes, signed, array_in, len) => { const array = to_array(array_in), n = len != undefined ? len : array.length; if (len == undefined) this.push_n(varint(U32_BYTES, n)); if (n_bytes > U32_BYTES) { for (let i = 0; i < n; i++) { this.serialize_number(n_bytes, signed, array[i]) } return } for (let i = 0; i < n; i++) { let value = Number(array[i]); if (n_bytes === U8_BYTES) { this.push(signed ? value & 0xFF : value); continue } if (signed) { value = ((value << 1) ^ (value >> 31)) >>> 0 } while (value >= 0x80) { this.push((value & 0x7F) | 0x80); value >>>= 7 } this.push(value) } }
+            serialize_number_float_array = (n_bytes, array_in, len) => { const array = to_array(array_in), n = len != undefined ? len : array.length; if (len == undefined) this.push_n(varint(U32_BYTES, n)); const b_view = new DataView(new ArrayBuffer(n * n_bytes)); for (let i = 0; i < n; i++) { if (n_bytes === U32_BYTES) { b_view.setFloat32(i * n_bytes, array[i], true) } else if (n_bytes === U64_BYTES) { b_view.setFloat64(i * n_bytes, array[i], true) } else { throw "byte count not supported" } } this.push_n(new Uint8Array(b_view.buffer)) }
+            serialize_string = (str) => { if (!is_well_formed_string(str)) { throw "string contains lone surrogates" } const bytes = new TextEncoder().encode(str); this.push_n(varint(U32_BYTES, bytes.length)); this.push_n(bytes) }
+            serialize_bytes = (bytes_in) => { const bytes = to_array(bytes_in); this.push_n(varint(U32_BYTES, bytes.length)); this.push_n(bytes) }
+            serialize_array = (ser, array_in, len) => { const array = to_array(array_in); if (len == undefined) this.push_n(varint(U32_BYTES, array.length)); array.slice(0, len != undefined ? len : array.length).forEach((v) => ser(this, v)) }
             serialize_string_key_map = (ser, obj) => { const entries = Object.entries(obj); this.push_n(varint(U32_BYTES, entries.length)); entries.forEach(([i, v]) => { this.serialize_string(i); ser(this, v) }) }
             serialize_map = (ser, map) => { this.push_n(varint(U32_BYTES, map.size)); map.forEach((v, k) => ser(this, k, v)) }
         }
 
+        class LengthCounter extends Serializer {
+            reserve = (n) => {}
+            push = (byte) => { this.length++ }
+            push_n = (bytes) => { this.length += bytes.length }
+        }
+
     }
 }
 
-pub fn gen_ser_functions(bindings: impl Iterator<Item = Container>) -> Tokens {
+pub fn gen_ser_functions(
+    bindings: impl Iterator<Item = Container>,
+    ident_case: IdentifierCase,
+) -> Tokens {
+    bindings
+        .map(|container| gen_ser_function_for_type(container, ident_case))
+        .join_with_empty_line()
+}
+
+/// Generates a `size_hint_*` function per type, returning an upper bound of the encoded length.
+///
+/// `serialize` reserves that many bytes up front, so the buffer does not need to grow while
+/// encoding a valid value.
+pub fn gen_size_hint_functions(
+    bindings: impl Iterator<Item = Container>,
+    ident_case: IdentifierCase,
+) -> Tokens {
     bindings
-        .map(gen_ser_function_for_type)
+        .map(|container| gen_size_hint_function_for_type(container, ident_case))
         .join_with_empty_line()
 }
 
-fn gen_ser_function_for_type(container: Container) -> impl FormatInto<JavaScript> {
-    let container_ident = ContainerIdentifierBuilder::from(&container).build();
-    let ser_body = container.r#type.gen_ser_body();
+pub fn gen_size_hint_function_for_type(
+    container: Container,
+    ident_case: IdentifierCase,
+) -> impl FormatInto<JavaScript> {
+    let container_ident = ContainerIdentifierBuilder::from(&container)
+        .ident_case(ident_case)
+        .build();
+    let size_hint_body = container.r#type.gen_size_hint_body(ident_case);
+
+    Function::new_untyped(
+        quote!(size_hint_$container_ident),
+        function_args![JS_OBJECT_VARIABLE],
+        size_hint_body,
+    )
+}
+
+pub fn gen_ser_function_for_type(
+    container: Container,
+    ident_case: IdentifierCase,
+) -> impl FormatInto<JavaScript> {
+    let container_ident = ContainerIdentifierBuilder::from(&container)
+        .ident_case(ident_case)
+        .build();
+    let ser_body = container.r#type.gen_ser_body(ident_case);
 
     Function::new_untyped(
         quote!(serialize_$container_ident),
@@ -55,46 +106,205 @@ fn gen_ser_function_for_type(container: Container) -> impl FormatInto<JavaScript
 pub fn gen_serialize_func(
     defines: impl Iterator<Item = Container>,
     runtime_type_checks: bool,
+    type_script: bool,
+    ident_case: IdentifierCase,
     export_registry: &mut ExportRegistry,
 ) -> impl FormatInto<JavaScript> {
     let mut switch_case = SwitchCase::new("type");
-    switch_case.extend_cases(defines.map(|d| gen_ser_case(d, runtime_type_checks)));
+    switch_case
+        .extend_cases(defines.map(|d| gen_ser_case(d, runtime_type_checks, true, ident_case)));
     switch_case.default_case(DefaultCase::new_without_break(
         quote!(throw "type not implemented";),
     ));
 
     export_registry.push("serialize");
 
-    Function::new_untyped(
+    let body = quote! {
+        if (!(typeof type === "string")) {
+            throw "type must be a string";
+        }
+        const s = new Serializer();
+        $switch_case
+        return s.finish();
+    };
+
+    if type_script {
+        Function::new(
+            "serialize<T extends Type>",
+            function_args![("type", "T"), ("value", "ValueType<T>")],
+            body,
+            "Uint8Array",
+        )
+    } else {
+        Function::new_untyped("serialize", function_args!["type", "value"], body)
+    }
+}
+
+/// Generates `encodedLength(type, value)`, which returns the exact length of the bytes
+/// `serialize` produces for the value.
+///
+/// The value is serialized into a `LengthCounter`, which only counts the bytes.
+pub fn gen_encoded_length_func(
+    defines: impl Iterator<Item = Container>,
+    runtime_type_checks: bool,
+    type_script: bool,
+    ident_case: IdentifierCase,
+    export_registry: &mut ExportRegistry,
+) -> impl FormatInto<JavaScript> {
+    let mut switch_case = SwitchCase::new("type");
+    switch_case
+        .extend_cases(defines.map(|d| gen_ser_case(d, runtime_type_checks, false, ident_case)));
+    switch_case.default_case(DefaultCase::new_without_break(
+        quote!(throw "type not implemented";),
+    ));
+
+    export_registry.push("encodedLength");
+
+    let body = quote! {
+        if (!(typeof type === "string")) {
+            throw "type must be a string";
+        }
+        const s = new LengthCounter();
+        $switch_case
+        return s.length;
+    };
+
+    if type_script {
+        Function::new(
+            "encodedLength<T extends Type>",
+            function_args![("type", "T"), ("value", "ValueType<T>")],
+            body,
+            "number",
+        )
+    } else {
+        Function::new_untyped("encodedLength", function_args!["type", "value"], body)
+    }
+}
+
+/// Serializes the value with the only type whose runtime type check accepts it.
+///
+/// The checks run in registration order. Values matching no type or more than one type are
+/// rejected, so structurally identical types never lead to a silently wrong guess.
+pub fn gen_serialize_any_func(
+    defines: impl Iterator<Item = Container>,
+    type_script: bool,
+    ident_case: IdentifierCase,
+    export_registry: &mut ExportRegistry,
+) -> impl FormatInto<JavaScript> {
+    let candidates = defines
+        .map(|container| {
+            let full_qualified = ContainerFullQualifiedTypeBuilder::from(&container).build();
+            let container_ident = ContainerIdentifierBuilder::from(&container)
+                .ident_case(ident_case)
+                .build();
+            let ident = container_ident.as_str();
+            quote!([$(quoted(full_qualified)), is_$ident, serialize_$ident, size_hint_$ident])
+        })
+        .join_with([JoinType::Comma, JoinType::LineBreak]);
+
+    export_registry.push("serializeAny");
+
+    let body = quote! {
+        const matches = [
+            $candidates
+        ].filter(([, is]) => is(value));
+        if (matches.length === 0) {
+            throw "value matches no type";
+        }
+        if (matches.length > 1) {
+            throw "value is ambiguous, matches types " + matches.map(([type]) => type).join(", ");
+        }
+        const s = new Serializer(matches[0][3](value));
+        matches[0][2](s, value);
+        return s.finish();
+    };
+
+    if type_script {
+        Function::new(
+            "serializeAny",
+            function_args![("value", "ValueType<Type>")],
+            body,
+            "Uint8Array",
+        )
+    } else {
+        Function::new_untyped("serializeAny", function_args!["value"], body)
+    }
+}
+
+/// Generates `serialize(value)` and `encodedLength(value)` for a single type, which behave
+/// like the functions taking the type name.
+pub fn gen_serialize_type_funcs(
+    container: Container,
+    runtime_type_checks: bool,
+    ident_case: IdentifierCase,
+    export_registry: &mut ExportRegistry,
+) -> Tokens {
+    let serialize = Function::new_untyped(
         "serialize",
-        function_args!["type", "value"],
+        function_args!["value"],
         quote! {
-            if (!(typeof type === "string")) {
-                throw "type must be a string";
-            }
             const s = new Serializer();
-            $switch_case
+            $(gen_ser_statements(&container, runtime_type_checks, true, ident_case))
             return s.finish();
         },
-    )
+    );
+    let encoded_length = Function::new_untyped(
+        "encodedLength",
+        function_args!["value"],
+        quote! {
+            const s = new LengthCounter();
+            $(gen_ser_statements(&container, runtime_type_checks, false, ident_case))
+            return s.length;
+        },
+    );
+
+    export_registry.push("serialize");
+    export_registry.push("encodedLength");
+
+    quote! {
+        $serialize
+
+        $encoded_length
+    }
 }
 
-fn gen_ser_case(container: Container, runtime_type_checks: bool) -> Case {
+fn gen_ser_case(
+    container: Container,
+    runtime_type_checks: bool,
+    reserve: bool,
+    ident_case: IdentifierCase,
+) -> Case {
     let full_qualified = ContainerFullQualifiedTypeBuilder::from(&container).build();
-    let container_ident = ContainerIdentifierBuilder::from(&container).build();
-    let body = if runtime_type_checks {
+    let body = gen_ser_statements(&container, runtime_type_checks, reserve, ident_case);
+
+    Case::new(quoted(full_qualified), body)
+}
+
+/// Serializes `value` into `s`, which has to be in scope.
+fn gen_ser_statements(
+    container: &Container,
+    runtime_type_checks: bool,
+    reserve: bool,
+    ident_case: IdentifierCase,
+) -> Tokens {
+    let container_ident = ContainerIdentifierBuilder::from(container)
+        .ident_case(ident_case)
+        .build();
+    let serialize = quote! {
+        $(if reserve {
+            s.reserve(size_hint_$(container_ident.as_str())(value));
+        })
+        serialize_$(container_ident.as_str())(s, value);
+    };
+    if runtime_type_checks {
         quote! {
             if (is_$(container_ident.as_str())(value)) {
-                serialize_$(container_ident)(s, value);
+                $serialize
             } else {
                 throw "value has wrong format";
             }
         }
     } else {
-        quote! {
-            serialize_$(container_ident)(s, value);
-        }
-    };
-
-    Case::new(quoted(full_qualified), body)
+        serialize
+    }
 }
@@ -0,0 +1,355 @@
+use std::collections::HashSet;
+
+use genco::{lang::JavaScript, quote, tokens::quoted};
+
+use crate::{
+    code_gen::{
+        export_registry::ExportMode,
+        utils::{ContainerIdentifierBuilder, TokensIterExt},
+    },
+    registry::{Container, ContainerCollection},
+    ExportFile, Exports,
+};
+
+use super::{
+    codec::JS_CODECS,
+    des::{
+        gen_check_deserialized, gen_des_function_for_type, gen_deserialize_func,
+        gen_deserialize_type_funcs, gen_deserializer_code,
+    },
+    general::{gen_known_types, gen_util},
+    generateable::gen_ts_typings,
+    minify::{is_ident_char, minify},
+    self_test::gen_self_test,
+    ser::{
+        gen_encoded_length_func, gen_ser_function_for_type, gen_serialize_any_func,
+        gen_serialize_func, gen_serialize_type_funcs, gen_serializer_code,
+        gen_size_hint_function_for_type,
+    },
+    type_checks::{gen_check_helpers, gen_type_check},
+    ExportRegistry, GenerationSettings, Tokens,
+};
+
+/// Top level names of [`gen_util`].
+const UTIL_NAMES: &[&str] = &[
+    "BITS_PER_BYTE",
+    "BITS_PER_VARINT_BYTE",
+    "U8_BYTES",
+    "U16_BYTES",
+    "U32_BYTES",
+    "U64_BYTES",
+    "U128_BYTES",
+    "de_zig_zag_signed",
+    "zig_zag",
+    "varint_max",
+    "max_of_last_byte",
+    "to_number_if_safe",
+    "varint",
+    "NONE",
+    "is_none",
+    "is_well_formed_string",
+    "to_array",
+    "is_array_of",
+];
+
+const RUNTIME_MODULE: &str = "runtime";
+const INDEX_MODULE: &str = "index";
+const TYPES_DIR: &str = "types";
+
+/// Generates the bindings as ES modules, one per type.
+///
+/// The files are named by their path relative to the package root without extension:
+/// `runtime` holds the serializer, the deserializer and the helpers, `types/<ident>` the
+/// functions of a single type and `index` the functions taking the type name, which
+/// re-exports each type module as namespace named by the type identifier. Each module only
+/// imports what it uses, so bundlers drop the types a program never touches.
+pub fn gen_split_exports(
+    containers: &ContainerCollection,
+    gen_settings: &GenerationSettings,
+) -> Exports<JavaScript> {
+    let runtime_names = runtime_names(gen_settings);
+    let types = containers
+        .all_containers()
+        .map(|container| {
+            let ident = ContainerIdentifierBuilder::from(&container)
+                .ident_case(gen_settings.ident_case)
+                .build();
+            let functions = type_function_names(&ident, gen_settings);
+            (container, ident, functions)
+        })
+        .collect::<Vec<_>>();
+
+    let mut files = vec![ExportFile {
+        content_type: RUNTIME_MODULE.to_owned(),
+        content: gen_runtime_module(gen_settings, &runtime_names),
+    }];
+
+    for (container, ident, _) in &types {
+        let module = gen_type_module(container.clone(), ident, gen_settings);
+        let runtime_import = (format!("../{RUNTIME_MODULE}.js"), runtime_names.as_slice());
+        let type_imports = types
+            .iter()
+            .filter(|(_, other, _)| other != ident)
+            .map(|(_, other, functions)| (format!("./{other}.js"), functions.as_slice()));
+        let imports = gen_imports(&module, [runtime_import].into_iter().chain(type_imports));
+
+        files.push(ExportFile {
+            content_type: format!("{TYPES_DIR}/{ident}"),
+            content: quote! {
+                $imports
+
+                $module
+            },
+        });
+    }
+
+    let index = gen_index_module(containers, gen_settings);
+    let runtime_import = (format!("./{RUNTIME_MODULE}.js"), runtime_names.as_slice());
+    let type_imports = types
+        .iter()
+        .map(|(_, ident, functions)| (format!("./{TYPES_DIR}/{ident}.js"), functions.as_slice()));
+    let imports = gen_imports(&index, [runtime_import].into_iter().chain(type_imports));
+    let namespaces = types.iter().map(|(_, ident, _)| {
+        quote!(export * as $ident from $(quoted(format!("./{TYPES_DIR}/{ident}.js")));)
+    });
+
+    files.push(ExportFile {
+        content_type: INDEX_MODULE.to_owned(),
+        content: quote! {
+            $imports
+
+            $index
+
+            $(for namespace in namespaces join ($['\r']) => $namespace)
+        },
+    });
+
+    if gen_settings.minify {
+        let containers = types
+            .iter()
+            .map(|(container, _, _)| container.clone())
+            .collect::<Vec<_>>();
+        for file in files.iter_mut() {
+            file.content = minify(
+                std::mem::take(&mut file.content),
+                &containers,
+                gen_settings.ident_case,
+            );
+        }
+    }
+
+    if gen_settings.type_script_types {
+        files.push(ExportFile {
+            content_type: "ts".to_owned(),
+            content: gen_ts_typings(containers, gen_settings),
+        });
+    }
+
+    Exports { files }
+}
+
+fn runtime_names(gen_settings: &GenerationSettings) -> Vec<String> {
+    let mut names = UTIL_NAMES.to_vec();
+    if !gen_settings.codecs.is_empty() {
+        names.push(JS_CODECS);
+    }
+    if gen_settings.ser {
+        names.extend(["Serializer", "LengthCounter"]);
+    }
+    if gen_settings.des {
+        names.extend(["DeserializationError", "Deserializer"]);
+        if gen_settings.validates_deserialized() {
+            names.push("check_deserialized");
+        }
+    }
+    if gen_settings.runtime_type_checks {
+        names.extend(["check_bounds", "check_integer_type"]);
+    }
+    names.into_iter().map(str::to_owned).collect()
+}
+
+/// The functions of a type module, which other modules may use.
+fn type_function_names(ident: &str, gen_settings: &GenerationSettings) -> Vec<String> {
+    let mut names = Vec::new();
+    if gen_settings.runtime_type_checks {
+        names.push(format!("is_{ident}"));
+    }
+    if gen_settings.ser {
+        names.push(format!("serialize_{ident}"));
+        names.push(format!("size_hint_{ident}"));
+    }
+    if gen_settings.des {
+        names.push(format!("deserialize_{ident}"));
+    }
+    names
+}
+
+fn gen_runtime_module(gen_settings: &GenerationSettings, runtime_names: &[String]) -> Tokens {
+    let mut parts = vec![gen_util(gen_settings)];
+    if gen_settings.ser {
+        parts.push(gen_serializer_code());
+    }
+    if gen_settings.des {
+        parts.push(gen_deserializer_code());
+        if gen_settings.validates_deserialized() {
+            parts.push(gen_check_deserialized());
+        }
+    }
+    if gen_settings.runtime_type_checks {
+        parts.push(gen_check_helpers());
+    }
+
+    let mut export_registry = ExportRegistry::new(ExportMode::Esm);
+    for name in runtime_names {
+        export_registry.push(name);
+    }
+
+    let mut tokens = parts.into_iter().join_with_empty_line();
+    tokens.line();
+    tokens.append(export_registry);
+    tokens
+}
+
+fn gen_type_module(container: Container, ident: &str, gen_settings: &GenerationSettings) -> Tokens {
+    let ident_case = gen_settings.ident_case;
+    let mut export_registry = ExportRegistry::new(ExportMode::Esm);
+    let mut parts = Vec::new();
+
+    for name in type_function_names(ident, gen_settings) {
+        export_registry.push(name);
+    }
+
+    if gen_settings.runtime_type_checks {
+        parts.push(quote!($(gen_type_check(container.clone(), ident_case))));
+    }
+
+    if gen_settings.ser {
+        parts.push(quote!($(gen_ser_function_for_type(container.clone(), ident_case))));
+        parts.push(quote!($(gen_size_hint_function_for_type(container.clone(), ident_case))));
+        parts.push(gen_serialize_type_funcs(
+            container.clone(),
+            gen_settings.runtime_type_checks,
+            ident_case,
+            &mut export_registry,
+        ));
+    }
+
+    if gen_settings.des {
+        parts.push(quote!($(gen_des_function_for_type(container.clone(), ident_case))));
+        parts.push(gen_deserialize_type_funcs(
+            container,
+            gen_settings.validates_deserialized(),
+            ident_case,
+            &mut export_registry,
+        ));
+    }
+
+    let mut tokens = parts.into_iter().join_with_empty_line();
+    tokens.line();
+    tokens.append(export_registry);
+    tokens
+}
+
+fn gen_index_module(containers: &ContainerCollection, gen_settings: &GenerationSettings) -> Tokens {
+    let ident_case = gen_settings.ident_case;
+    let mut export_registry = ExportRegistry::new(ExportMode::Esm);
+    let mut parts = vec![gen_known_types(
+        containers.all_containers(),
+        false,
+        &mut export_registry,
+    )];
+
+    if gen_settings.ser {
+        parts.push(quote!($(gen_serialize_func(
+            containers.all_containers(),
+            gen_settings.runtime_type_checks,
+            false,
+            ident_case,
+            &mut export_registry,
+        ))));
+        parts.push(quote!($(gen_encoded_length_func(
+            containers.all_containers(),
+            gen_settings.runtime_type_checks,
+            false,
+            ident_case,
+            &mut export_registry,
+        ))));
+        if gen_settings.runtime_type_checks {
+            parts.push(quote!($(gen_serialize_any_func(
+                containers.all_containers(),
+                false,
+                ident_case,
+                &mut export_registry,
+            ))));
+        }
+    }
+
+    if gen_settings.des {
+        parts.push(quote!($(gen_deserialize_func(
+            containers.all_containers(),
+            false,
+            gen_settings.validates_deserialized(),
+            ident_case,
+            &mut export_registry,
+        ))));
+    }
+
+    if gen_settings.ser && gen_settings.des && gen_settings.self_test {
+        parts.push(gen_self_test(
+            containers.all_containers(),
+            false,
+            ident_case,
+            &mut export_registry,
+        ));
+    }
+
+    let mut tokens = parts.into_iter().join_with_empty_line();
+    tokens.line();
+    tokens.append(export_registry);
+    tokens
+}
+
+/// Imports the names the module uses from the given modules, which are pairs of the module
+/// path and the names the module exports.
+fn gen_imports<'a>(
+    module: &Tokens,
+    modules: impl Iterator<Item = (String, &'a [String])>,
+) -> Tokens {
+    let source = module
+        .to_file_string()
+        .expect("generated javascript is valid");
+    let used = used_identifiers(&source);
+
+    modules
+        .filter_map(|(path, names)| {
+            let names = names
+                .iter()
+                .filter(|name| used.contains(name.as_str()))
+                .collect::<Vec<_>>();
+            (!names.is_empty()).then(
+                || quote!(import { $(for name in names join (, ) => $name) } from $(quoted(path));),
+            )
+        })
+        .join_with_line_breaks()
+}
+
+/// Identifiers of the source which are not accessed as member of another value.
+fn used_identifiers(source: &str) -> HashSet<&str> {
+    let mut used = HashSet::new();
+    let mut start = None;
+
+    for (pos, c) in source.char_indices().chain([(source.len(), ' ')]) {
+        match (is_ident_char(c), start) {
+            (true, None) => start = Some(pos),
+            (false, Some(ident_start)) => {
+                if !source[..ident_start].ends_with('.') {
+                    used.insert(&source[ident_start..pos]);
+                }
+                start = None;
+            }
+            _ => (),
+        }
+    }
+
+    used
+}
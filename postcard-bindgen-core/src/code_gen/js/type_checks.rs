@@ -7,7 +7,7 @@ use genco::{
 use crate::{
     code_gen::{
         function::Function,
-        utils::{break_long_logical_lines, ContainerIdentifierBuilder, TokensIterExt},
+        utils::{ContainerIdentifierBuilder, IdentifierCase, TokensIterExt},
     },
     function_args,
     registry::Container,
@@ -15,9 +15,21 @@ use crate::{
 
 use super::{generateable::container::BindingTypeGenerateable, JS_OBJECT_VARIABLE};
 
-pub fn gen_type_checks(bindings: impl Iterator<Item = Container>) -> Tokens {
-    let body = bindings.map(gen_type_check).join_with_empty_line();
+pub fn gen_type_checks(
+    bindings: impl Iterator<Item = Container>,
+    ident_case: IdentifierCase,
+) -> Tokens {
+    let body = bindings
+        .map(|container| gen_type_check(container, ident_case))
+        .join_with_empty_line();
 
+    [gen_check_helpers(), body]
+        .into_iter()
+        .join_with_empty_line()
+}
+
+/// Generates the integer range checks the type checks of all types share.
+pub fn gen_check_helpers() -> Tokens {
     let check_function = Function::new_untyped(
         "check_bounds",
         function_args![JS_OBJECT_VARIABLE, "n_bytes", "signed"],
@@ -37,20 +49,25 @@ pub fn gen_type_checks(bindings: impl Iterator<Item = Container>) -> Tokens {
         function_args![JS_OBJECT_VARIABLE, "n_bytes", "signed"],
         quote! {
             return (
-                typeof $JS_OBJECT_VARIABLE === "number" ||
+                (typeof $JS_OBJECT_VARIABLE === "number" && Number.isInteger($JS_OBJECT_VARIABLE)) ||
                 typeof $JS_OBJECT_VARIABLE === "bigint"
-            ) && Number.isInteger($JS_OBJECT_VARIABLE) && check_bounds($JS_OBJECT_VARIABLE, n_bytes, signed)
+            ) && check_bounds($JS_OBJECT_VARIABLE, n_bytes, signed)
         },
     );
 
-    [quote!($check_function), quote!($check_number), body]
+    [quote!($check_function), quote!($check_number)]
         .into_iter()
         .join_with_empty_line()
 }
 
-pub fn gen_type_check(container: Container) -> impl FormatInto<JavaScript> {
-    let container_ident = ContainerIdentifierBuilder::from(&container).build();
-    let body = break_long_logical_lines(container.r#type.gen_ty_check_body());
+pub fn gen_type_check(
+    container: Container,
+    ident_case: IdentifierCase,
+) -> impl FormatInto<JavaScript> {
+    let container_ident = ContainerIdentifierBuilder::from(&container)
+        .ident_case(ident_case)
+        .build();
+    let body = container.r#type.gen_ty_check_body(ident_case);
 
     Function::new_untyped(
         quote!(is_$container_ident),
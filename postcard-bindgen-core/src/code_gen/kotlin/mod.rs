@@ -0,0 +1,537 @@
+mod runtime;
+
+use core::borrow::Borrow;
+use std::collections::BTreeSet;
+
+use convert_case::{Case, Casing};
+use genco::{lang::java::Java, quote, quote_in};
+
+use runtime::gen_runtime;
+
+use super::backend::{generate_with, LanguageBackend};
+use crate::{
+    registry::{
+        BindingType, Container, ContainerCollection, EnumType, EnumVariantType, StructField,
+        StructType, TupleStructType,
+    },
+    type_info::{ArrayMeta, NumberMeta, ValueType},
+    Exports,
+};
+
+pub use super::settings::{GenerationSettings, PointerWidth};
+
+type Tokens = genco::lang::java::Tokens;
+type ExportFile = crate::ExportFile<Java>;
+
+const KOTLIN_KEYWORDS: &[&str] = &[
+    "as",
+    "break",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    "while",
+];
+
+/// Generates a Kotlin source file, exported as `source`.
+///
+/// The file is placed in the package `module_name`. Structs become data classes with
+/// camel case properties, unit structs objects and enums sealed classes with a subclass
+/// per variant. Serializing and deserializing is done with
+///
+/// ```kotlin
+/// fun serialize(value: Type): ByteArray
+/// inline fun <reified T : Any> deserialize(bytes: ByteArray): T
+/// ```
+///
+/// which throw a `PostcardException` if a value is out of range or the input is invalid.
+///
+/// Unsigned integers map to the unsigned Kotlin types, 128 bit integers to `BigInteger`,
+/// vectors and arrays to `List` and tuples to `Pair` or `Triple`. Generating panics for
+/// tuples of other lengths.
+pub fn generate(
+    containers: ContainerCollection,
+    gen_settings: impl Borrow<GenerationSettings>,
+    module_name: String,
+) -> Exports<Java> {
+    let backend = KotlinBackend {
+        gen_settings: gen_settings.borrow(),
+        module_name: module_name.to_case(Case::Snake),
+        containers: Vec::new(),
+        types: Vec::new(),
+        ser_functions: Tokens::new(),
+        des_functions: Tokens::new(),
+    };
+    generate_with(backend, containers)
+}
+
+struct KotlinBackend<'a> {
+    gen_settings: &'a GenerationSettings,
+    module_name: String,
+    containers: Vec<Container>,
+    types: Vec<Tokens>,
+    ser_functions: Tokens,
+    des_functions: Tokens,
+}
+
+impl KotlinBackend<'_> {
+    fn push_type(&mut self, container: &Container) {
+        self.types.push(gen_type(container, &self.module_name));
+        if self.gen_settings.ser {
+            self.ser_functions.line();
+            self.ser_functions.append(gen_ser_function(container));
+        }
+        if self.gen_settings.des {
+            self.des_functions.line();
+            self.des_functions.append(gen_des_function(container));
+        }
+        self.containers.push(container.clone());
+    }
+}
+
+impl LanguageBackend for KotlinBackend<'_> {
+    type Lang = Java;
+
+    fn prepare(&mut self, containers: &mut ContainerCollection) {
+        containers.assert_no_internally_tagged_enums("Kotlin");
+        containers.flatten();
+        containers.resolve_pointer_width(self.gen_settings.pointer_width.bytes());
+        containers.sets_as_sequences();
+        containers.remove_unit_fields();
+
+        let mut names = BTreeSet::new();
+        for container in containers.all_containers() {
+            assert!(
+                names.insert(container.name.clone()),
+                "the Kotlin bindings hold all types in one package, but `{}` is defined twice",
+                container.name
+            );
+        }
+    }
+
+    fn visit_struct(&mut self, container: &Container, _: &StructType) {
+        self.push_type(container);
+    }
+
+    fn visit_tuple_struct(&mut self, container: &Container, _: &TupleStructType) {
+        self.push_type(container);
+    }
+
+    fn visit_enum(&mut self, container: &Container, _: &EnumType) {
+        self.push_type(container);
+    }
+
+    fn visit_unit_struct(&mut self, container: &Container) {
+        self.push_type(container);
+    }
+
+    fn finish(self) -> Exports<Java> {
+        let gen_settings = self.gen_settings;
+
+        let mut source = quote! {
+            package $(&self.module_name)
+
+            import java.math.BigInteger
+            $(if gen_settings.des => import kotlin.reflect.KClass)
+
+            $(gen_runtime())
+
+            $(for ty in self.types join ($['\n']) => $ty)
+        };
+
+        if gen_settings.ser {
+            source.append(self.ser_functions);
+            source.line();
+            source.append(gen_serialize_functions(&self.containers));
+        }
+
+        if gen_settings.des {
+            source.append(self.des_functions);
+            source.line();
+            source.append(gen_deserialize_function(&self.containers));
+        }
+
+        Exports {
+            files: vec![ExportFile {
+                content_type: "source".to_owned(),
+                content: source,
+            }],
+        }
+    }
+}
+
+/// Fields of a struct or an enum variant.
+enum Fields<'a> {
+    Named(&'a [StructField]),
+    Unnamed(&'a [ValueType]),
+    Unit,
+}
+
+impl Fields<'_> {
+    fn members(&self) -> Vec<(String, &ValueType)> {
+        match self {
+            Fields::Named(fields) => fields
+                .iter()
+                .filter(|field| !field.skip)
+                .map(|field| (kotlin_ident(&field.name), &field.v_type))
+                .collect(),
+            Fields::Unnamed(fields) => fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| (format!("_{index}"), field))
+                .collect(),
+            Fields::Unit => Vec::new(),
+        }
+    }
+}
+
+impl<'a> From<&'a EnumVariantType> for Fields<'a> {
+    fn from(variant_type: &'a EnumVariantType) -> Self {
+        match variant_type {
+            EnumVariantType::Empty => Fields::Unit,
+            EnumVariantType::Tuple(fields) => Fields::Unnamed(fields),
+            EnumVariantType::NewType(fields) => Fields::Named(fields),
+        }
+    }
+}
+
+fn gen_type(container: &Container, package: &str) -> Tokens {
+    match &container.r#type {
+        BindingType::Struct(struct_type) => gen_class(
+            &container.name,
+            &Fields::Named(&struct_type.fields),
+            "",
+            None,
+        ),
+        BindingType::TupleStruct(tuple_struct) => gen_class(
+            &container.name,
+            &Fields::Unnamed(&tuple_struct.fields),
+            "",
+            None,
+        ),
+        BindingType::UnitStruct(_) => gen_class(&container.name, &Fields::Unit, "", None),
+        BindingType::Enum(enum_type) => {
+            // the variants may shadow other types of the bindings
+            let qualifier = format!("{package}.");
+            let variants = enum_type.variants.iter().map(|variant| {
+                gen_class(
+                    &variant.name,
+                    &Fields::from(&variant.inner_type),
+                    &qualifier,
+                    Some(&container.name),
+                )
+            });
+            quote! {
+                sealed class $(&container.name) {
+                    $(for variant in variants => $variant$['\r'])
+                }
+            }
+        }
+    }
+}
+
+fn gen_class(name: &str, fields: &Fields, qualifier: &str, parent: Option<&str>) -> Tokens {
+    let members = fields.members();
+    let parent = parent.map(|parent| format!(" : {parent}()"));
+    if members.is_empty() {
+        return quote!(object $name$parent);
+    }
+    quote! {
+        data class $name(
+            $(for (member, v_type) in members => val $member: $(kotlin_type(v_type, qualifier)),$['\r'])
+        )$parent
+    }
+}
+
+fn gen_ser_function(container: &Container) -> Tokens {
+    let name = &container.name;
+    let mut body = Tokens::new();
+    match &container.r#type {
+        BindingType::Struct(struct_type) => {
+            gen_ser_fields(&mut body, &Fields::Named(&struct_type.fields))
+        }
+        BindingType::TupleStruct(tuple_struct) => {
+            gen_ser_fields(&mut body, &Fields::Unnamed(&tuple_struct.fields))
+        }
+        BindingType::UnitStruct(_) => (),
+        BindingType::Enum(enum_type) => {
+            let mut cases = Tokens::new();
+            for variant in enum_type.variants.iter() {
+                let fields = Fields::from(&variant.inner_type);
+                let mut fields_body = Tokens::new();
+                gen_ser_fields(&mut fields_body, &fields);
+                quote_in! { cases =>
+                    is $name.$(&variant.name) -> {
+                        s.u32($(variant.index)u)
+                        $fields_body
+                    }
+                }
+                cases.push();
+            }
+            quote_in! { body =>
+                when (v) {
+                    $cases
+                }
+            }
+        }
+    }
+
+    quote! {
+        internal fun serialize$name(s: Serializer, v: $name) {
+            $body
+        }
+    }
+}
+
+fn gen_ser_fields(tokens: &mut Tokens, fields: &Fields) {
+    for (member, v_type) in fields.members() {
+        tokens.append(gen_ser(v_type, quote!(v.$member), 0));
+        tokens.push();
+    }
+}
+
+/// Serializes `value`, lambdas are nested with `depth`.
+fn gen_ser(v_type: &ValueType, value: Tokens, depth: usize) -> Tokens {
+    let item = format!("x{depth}");
+    match v_type {
+        ValueType::Object(meta) => quote!(serialize$(&meta.name)(s, $value)),
+        ValueType::Optional(meta) => {
+            quote!(s.option($value) { $(&item) -> $(gen_ser(&meta.inner, quote!($(&item)), depth + 1)) })
+        }
+        ValueType::Array(ArrayMeta {
+            items_type,
+            length: Some(length),
+            ..
+        }) => {
+            quote!(s.array($value, $(*length)) { $(&item) -> $(gen_ser(items_type, quote!($(&item)), depth + 1)) })
+        }
+        ValueType::Array(ArrayMeta { items_type, .. }) => {
+            quote!(s.list($value) { $(&item) -> $(gen_ser(items_type, quote!($(&item)), depth + 1)) })
+        }
+        ValueType::Map(meta) => {
+            let key = format!("k{depth}");
+            quote!(s.map($value, { $(&key) -> $(gen_ser(&meta.key_type, quote!($(&key)), depth + 1)) }, { $(&item) -> $(gen_ser(&meta.value_type, quote!($(&item)), depth + 1)) }))
+        }
+        ValueType::Range(meta) => {
+            let start = gen_ser(&meta.bounds_type, quote!($(&item).start), depth + 1);
+            let end = gen_ser(&meta.bounds_type, quote!($(&item).end), depth + 1);
+            quote!($value.let { $(&item) -> $start; $end })
+        }
+        ValueType::Tuple(meta) => {
+            let items = tuple_accessors(meta.items_types.len())
+                .iter()
+                .zip(meta.items_types.iter())
+                .map(|(accessor, item_type)| {
+                    gen_ser(item_type, quote!($(&item).$(*accessor)), depth + 1)
+                })
+                .collect::<Vec<_>>();
+            quote!($value.let { $(&item) -> $(for item in items join (; ) => $item) })
+        }
+        _ => quote!(s.$(primitive_fn(v_type))($value)),
+    }
+}
+
+fn gen_des_function(container: &Container) -> Tokens {
+    let name = &container.name;
+    let body = match &container.r#type {
+        BindingType::Struct(struct_type) => {
+            gen_des_constructor(quote!($name), &Fields::Named(&struct_type.fields))
+        }
+        BindingType::TupleStruct(tuple_struct) => {
+            gen_des_constructor(quote!($name), &Fields::Unnamed(&tuple_struct.fields))
+        }
+        BindingType::UnitStruct(_) => quote!($name),
+        BindingType::Enum(enum_type) => {
+            let mut cases = Tokens::new();
+            for variant in enum_type.variants.iter() {
+                let constructor = gen_des_constructor(
+                    quote!($name.$(&variant.name)),
+                    &Fields::from(&variant.inner_type),
+                );
+                quote_in!(cases => $(variant.index)u -> $constructor);
+                cases.push();
+            }
+            quote! {
+                when (val tag = d.u32()) {
+                    $cases
+                    else -> throw PostcardException($(format!("\"unknown variant $tag of {name}\"")))
+                }
+            }
+        }
+    };
+
+    quote! {
+        internal fun deserialize$name(d: Deserializer): $name = $body
+    }
+}
+
+fn gen_des_constructor(name: Tokens, fields: &Fields) -> Tokens {
+    let members = fields.members();
+    if members.is_empty() {
+        return name;
+    }
+    quote! {
+        $name(
+            $(for (member, v_type) in members => $member = $(gen_des(v_type)),$['\r'])
+        )
+    }
+}
+
+fn gen_des(v_type: &ValueType) -> Tokens {
+    match v_type {
+        ValueType::Object(meta) => quote!(deserialize$(&meta.name)(d)),
+        ValueType::Optional(meta) => quote!(d.option { $(gen_des(&meta.inner)) }),
+        ValueType::Array(ArrayMeta {
+            items_type,
+            length: Some(length),
+            ..
+        }) => quote!(d.array($(*length)) { $(gen_des(items_type)) }),
+        ValueType::Array(ArrayMeta { items_type, .. }) => {
+            quote!(d.list { $(gen_des(items_type)) })
+        }
+        ValueType::Map(meta) => {
+            quote!(d.map({ $(gen_des(&meta.key_type)) }, { $(gen_des(&meta.value_type)) }))
+        }
+        ValueType::Range(meta) => {
+            quote!(PostcardRange($(gen_des(&meta.bounds_type)), $(gen_des(&meta.bounds_type))))
+        }
+        ValueType::Tuple(meta) => {
+            let tuple = tuple_type(meta.items_types.len());
+            quote!($tuple($(for item in meta.items_types.iter() join (, ) => $(gen_des(item)))))
+        }
+        _ => quote!(d.$(primitive_fn(v_type))()),
+    }
+}
+
+fn gen_serialize_functions(containers: &[Container]) -> Tokens {
+    let mut tokens = Tokens::new();
+    for container in containers.iter() {
+        let name = &container.name;
+        quote_in! { tokens =>
+            fun serialize(value: $name): ByteArray = Serializer().also { serialize$name(it, value) }.finish()
+        }
+        tokens.push();
+    }
+    tokens
+}
+
+fn gen_deserialize_function(containers: &[Container]) -> Tokens {
+    quote! {
+        inline fun <reified T : Any> deserialize(bytes: ByteArray): T = deserialize(T::class, bytes)
+
+        @Suppress("UNCHECKED_CAST")
+        fun <T : Any> deserialize(type: KClass<T>, bytes: ByteArray): T {
+            val d = Deserializer(bytes)
+            val value: Any = when (type) {
+                $(for container in containers.iter() => $(&container.name)::class -> deserialize$(&container.name)(d)$['\r'])
+                else -> throw IllegalArgumentException("no bindings for $type")
+            }
+            d.finish()
+            return value as T
+        }
+    }
+}
+
+fn tuple_type(len: usize) -> &'static str {
+    match len {
+        2 => "Pair",
+        3 => "Triple",
+        _ => panic!("tuples with {len} items are not supported by the Kotlin bindings, only pairs and triples"),
+    }
+}
+
+fn tuple_accessors(len: usize) -> &'static [&'static str] {
+    match tuple_type(len) {
+        "Pair" => &["first", "second"],
+        _ => &["first", "second", "third"],
+    }
+}
+
+/// Name of the `Serializer` and `Deserializer` function of a primitive.
+fn primitive_fn(v_type: &ValueType) -> String {
+    match v_type {
+        ValueType::Number(NumberMeta::Integer { bytes, signed, .. }) => {
+            format!("{}{}", if *signed { "i" } else { "u" }, bytes * 8)
+        }
+        ValueType::Number(NumberMeta::FloatingPoint { bytes }) => format!("f{}", bytes * 8),
+        ValueType::Bool(_) => "bool".to_owned(),
+        ValueType::String(_) => "string".to_owned(),
+        _ => unreachable!("not a primitive type"),
+    }
+}
+
+fn kotlin_type(v_type: &ValueType, qualifier: &str) -> String {
+    match v_type {
+        ValueType::Number(NumberMeta::Integer { bytes: 16, .. }) => "BigInteger".to_owned(),
+        ValueType::Number(NumberMeta::Integer { bytes, signed, .. }) => {
+            let name = match bytes {
+                1 => "Byte",
+                2 => "Short",
+                4 => "Int",
+                _ => "Long",
+            };
+            if *signed {
+                name.to_owned()
+            } else {
+                format!("U{name}")
+            }
+        }
+        ValueType::Number(NumberMeta::FloatingPoint { bytes: 4 }) => "Float".to_owned(),
+        ValueType::Number(NumberMeta::FloatingPoint { .. }) => "Double".to_owned(),
+        ValueType::Bool(_) => "Boolean".to_owned(),
+        ValueType::String(_) => "String".to_owned(),
+        ValueType::Object(meta) => format!("{qualifier}{}", meta.name),
+        ValueType::Optional(meta) => format!("{}?", kotlin_type(&meta.inner, qualifier)),
+        ValueType::Range(meta) => format!(
+            "PostcardRange<{}>",
+            kotlin_type(&meta.bounds_type, qualifier)
+        ),
+        ValueType::Array(meta) => format!("List<{}>", kotlin_type(&meta.items_type, qualifier)),
+        ValueType::Set(_) => unreachable!("sets are replaced by sequences"),
+        ValueType::Unit(_) => panic!("`()` is only supported as field by the Kotlin bindings"),
+        ValueType::Map(meta) => format!(
+            "Map<{}, {}>",
+            kotlin_type(&meta.key_type, qualifier),
+            kotlin_type(&meta.value_type, qualifier)
+        ),
+        ValueType::Tuple(meta) => format!(
+            "{}<{}>",
+            tuple_type(meta.items_types.len()),
+            meta.items_types
+                .iter()
+                .map(|item| kotlin_type(item, qualifier))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn kotlin_ident(name: &str) -> String {
+    let name = name.to_case(Case::Camel);
+    if KOTLIN_KEYWORDS.contains(&name.as_str()) {
+        format!("`{name}`")
+    } else {
+        name
+    }
+}
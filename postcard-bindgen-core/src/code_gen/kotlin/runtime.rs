@@ -0,0 +1,267 @@
+use genco::quote;
+
+use super::Tokens;
+
+/// The serializer and deserializer of the primitive and collection types, shared by the
+/// generated functions.
+pub fn gen_runtime() -> Tokens {
+    quote!($(RUNTIME))
+}
+
+const RUNTIME: &str = r#"class PostcardException(message: String) : Exception(message)
+
+/** rust `Range` and `RangeInclusive` */
+data class PostcardRange<T>(val start: T, val end: T)
+
+class Serializer {
+    private var bytes = ByteArray(64)
+    private var length = 0
+
+    fun push(byte: Int) {
+        if (length == bytes.size) {
+            bytes = bytes.copyOf(bytes.size * 2)
+        }
+        bytes[length++] = byte.toByte()
+    }
+
+    fun varint(value: ULong) {
+        var v = value
+        while (v >= 0x80u) {
+            push((v and 0x7Fu).toInt() or 0x80)
+            v = v shr 7
+        }
+        push(v.toInt())
+    }
+
+    fun zigzag(value: Long) = varint(((value shl 1) xor (value shr 63)).toULong())
+
+    fun len(n: Int) = varint(n.toULong())
+
+    fun bool(v: Boolean) = push(if (v) 1 else 0)
+    fun u8(v: UByte) = push(v.toInt())
+    fun i8(v: Byte) = push(v.toInt())
+    fun u16(v: UShort) = varint(v.toULong())
+    fun u32(v: UInt) = varint(v.toULong())
+    fun u64(v: ULong) = varint(v)
+    fun i16(v: Short) = zigzag(v.toLong())
+    fun i32(v: Int) = zigzag(v.toLong())
+    fun i64(v: Long) = zigzag(v)
+
+    fun u128(v: BigInteger) {
+        if (v.signum() < 0 || v.bitLength() > 128) {
+            throw PostcardException("$v is out of range of u128")
+        }
+        var rest = v
+        while (rest >= BYTE_LIMIT) {
+            push(rest.toInt() and 0x7F or 0x80)
+            rest = rest.shiftRight(7)
+        }
+        push(rest.toInt())
+    }
+
+    fun i128(v: BigInteger) {
+        if (v.bitLength() > 127) {
+            throw PostcardException("$v is out of range of i128")
+        }
+        u128(if (v.signum() < 0) v.negate().shiftLeft(1).subtract(BigInteger.ONE) else v.shiftLeft(1))
+    }
+
+    fun f32(v: Float) {
+        val bits = v.toRawBits()
+        for (i in 0 until 4) {
+            push(bits ushr (8 * i))
+        }
+    }
+
+    fun f64(v: Double) {
+        val bits = v.toRawBits()
+        for (i in 0 until 8) {
+            push((bits ushr (8 * i)).toInt())
+        }
+    }
+
+    fun string(v: String) {
+        val utf8 = v.encodeToByteArray()
+        len(utf8.size)
+        for (byte in utf8) {
+            push(byte.toInt())
+        }
+    }
+
+    fun <T> option(v: T?, item: (T) -> Unit) {
+        if (v == null) {
+            push(0)
+        } else {
+            push(1)
+            item(v)
+        }
+    }
+
+    fun <T> list(v: List<T>, item: (T) -> Unit) {
+        len(v.size)
+        v.forEach(item)
+    }
+
+    fun <T> array(v: List<T>, size: Int, item: (T) -> Unit) {
+        if (v.size != size) {
+            throw PostcardException("expected $size items, got ${v.size}")
+        }
+        v.forEach(item)
+    }
+
+    fun <K, V> map(v: Map<K, V>, key: (K) -> Unit, value: (V) -> Unit) {
+        len(v.size)
+        for ((k, x) in v) {
+            key(k)
+            value(x)
+        }
+    }
+
+    fun finish(): ByteArray = bytes.copyOf(length)
+
+    private companion object {
+        val BYTE_LIMIT: BigInteger = BigInteger.valueOf(0x80)
+    }
+}
+
+class Deserializer(private val bytes: ByteArray) {
+    private var index = 0
+
+    fun pop(): Int {
+        if (index >= bytes.size) {
+            throw PostcardException("unexpected end of input")
+        }
+        return bytes[index++].toInt() and 0xFF
+    }
+
+    /** rejects varints longer than `maxBytes` and values exceeding 64 bits */
+    fun varint(maxBytes: Int): ULong {
+        var out = 0UL
+        for (i in 0 until maxBytes) {
+            val byte = pop()
+            if (i == 9 && byte > 1) {
+                throw PostcardException("varint exceeds 64 bits")
+            }
+            out = out or ((byte and 0x7F).toULong() shl (7 * i))
+            if ((byte and 0x80) == 0) {
+                return out
+            }
+        }
+        throw PostcardException("varint exceeds $maxBytes bytes")
+    }
+
+    fun zigzag(maxBytes: Int): Long {
+        val raw = varint(maxBytes).toLong()
+        return (raw ushr 1) xor -(raw and 1L)
+    }
+
+    private fun unsigned(maxBytes: Int, max: ULong): ULong {
+        val v = varint(maxBytes)
+        if (v > max) {
+            throw PostcardException("$v is out of range")
+        }
+        return v
+    }
+
+    private fun signed(maxBytes: Int, min: Long, max: Long): Long {
+        val v = zigzag(maxBytes)
+        if (v < min || v > max) {
+            throw PostcardException("$v is out of range")
+        }
+        return v
+    }
+
+    fun len(): Int = unsigned(10, Int.MAX_VALUE.toULong()).toInt()
+
+    fun bool(): Boolean = when (pop()) {
+        0 -> false
+        1 -> true
+        else -> throw PostcardException("invalid bool")
+    }
+
+    fun u8(): UByte = pop().toUByte()
+    fun i8(): Byte = pop().toByte()
+    fun u16(): UShort = unsigned(3, UShort.MAX_VALUE.toULong()).toUShort()
+    fun u32(): UInt = unsigned(5, UInt.MAX_VALUE.toULong()).toUInt()
+    fun u64(): ULong = varint(10)
+    fun i16(): Short = signed(3, Short.MIN_VALUE.toLong(), Short.MAX_VALUE.toLong()).toShort()
+    fun i32(): Int = signed(5, Int.MIN_VALUE.toLong(), Int.MAX_VALUE.toLong()).toInt()
+    fun i64(): Long = zigzag(10)
+
+    fun u128(): BigInteger {
+        var out = BigInteger.ZERO
+        for (i in 0 until 19) {
+            val byte = pop()
+            out = out.or(BigInteger.valueOf((byte and 0x7F).toLong()).shiftLeft(7 * i))
+            if ((byte and 0x80) == 0) {
+                if (out.bitLength() > 128) {
+                    throw PostcardException("varint exceeds 128 bits")
+                }
+                return out
+            }
+        }
+        throw PostcardException("varint exceeds 19 bytes")
+    }
+
+    fun i128(): BigInteger {
+        val raw = u128()
+        return if (raw.testBit(0)) raw.shiftRight(1).add(BigInteger.ONE).negate() else raw.shiftRight(1)
+    }
+
+    fun f32(): Float {
+        var bits = 0
+        for (i in 0 until 4) {
+            bits = bits or (pop() shl (8 * i))
+        }
+        return Float.fromBits(bits)
+    }
+
+    fun f64(): Double {
+        var bits = 0L
+        for (i in 0 until 8) {
+            bits = bits or (pop().toLong() shl (8 * i))
+        }
+        return Double.fromBits(bits)
+    }
+
+    fun string(): String {
+        val n = len()
+        if (bytes.size - index < n) {
+            throw PostcardException("unexpected end of input")
+        }
+        val start = index
+        index += n
+        try {
+            return bytes.decodeToString(start, start + n, throwOnInvalidSequence = true)
+        } catch (e: CharacterCodingException) {
+            throw PostcardException("invalid utf-8")
+        }
+    }
+
+    fun <T> option(item: () -> T): T? = if (bool()) item() else null
+
+    fun <T> list(item: () -> T): List<T> {
+        val n = len()
+        val out = ArrayList<T>(minOf(n, bytes.size - index))
+        repeat(n) { out.add(item()) }
+        return out
+    }
+
+    fun <T> array(size: Int, item: () -> T): List<T> = List(size) { item() }
+
+    fun <K, V> map(key: () -> K, value: () -> V): Map<K, V> {
+        val n = len()
+        val out = LinkedHashMap<K, V>()
+        repeat(n) {
+            val k = key()
+            out[k] = value()
+        }
+        return out
+    }
+
+    fun finish() {
+        if (index != bytes.size) {
+            throw PostcardException("${bytes.size - index} trailing bytes")
+        }
+    }
+}"#;
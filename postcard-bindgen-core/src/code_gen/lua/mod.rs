@@ -0,0 +1,458 @@
+mod runtime;
+
+use core::borrow::Borrow;
+
+use genco::{quote, quote_in};
+
+use runtime::{gen_des_runtime, gen_runtime, gen_ser_runtime};
+
+use super::backend::{generate_with, LanguageBackend};
+use crate::{
+    registry::{
+        BindingType, Container, ContainerCollection, EnumType, EnumVariantType, StructField,
+        StructType, TupleStructType,
+    },
+    type_info::{ArrayMeta, NumberMeta, ValueType},
+    Exports,
+};
+
+pub use super::settings::{GenerationSettings, PointerWidth};
+
+type Tokens = genco::Tokens<()>;
+type ExportFile = crate::ExportFile<()>;
+
+const LUA_KEYWORDS: &[&str] = &[
+    "and", "break", "do", "else", "elseif", "end", "false", "for", "function", "goto", "if", "in",
+    "local", "nil", "not", "or", "repeat", "return", "then", "true", "until", "while",
+];
+
+/// Generates a Lua module, exported as `source`.
+///
+/// The module runs on Lua 5.1 to 5.4 without any libraries and returns a table with the
+/// functions
+///
+/// ```lua
+/// serialize(type_name, value) -- returns the bytes as string
+/// deserialize(type_name, bytes)
+/// ```
+///
+/// which raise an error if a value is out of range or the input is invalid.
+///
+/// Values are plain tables laid out like the JavaScript values: structs are keyed by the
+/// field names, tuple structs, tuples and vectors are sequences and enums are tables
+/// holding the variant name as `tag` and the fields as `value`. `None` is `nil`. Before
+/// Lua 5.3, 64 bit integers above 2^53 lose precision. Generating panics for 128 bit
+/// integers and optionals, which can't be stored in a sequence or a table.
+pub fn generate(
+    containers: ContainerCollection,
+    gen_settings: impl Borrow<GenerationSettings>,
+) -> Exports<()> {
+    let backend = LuaBackend {
+        gen_settings: gen_settings.borrow(),
+        encoders: Tokens::new(),
+        decoders: Tokens::new(),
+    };
+    generate_with(backend, containers)
+}
+
+struct LuaBackend<'a> {
+    gen_settings: &'a GenerationSettings,
+    encoders: Tokens,
+    decoders: Tokens,
+}
+
+impl LuaBackend<'_> {
+    fn push_type(&mut self, container: &Container) {
+        if self.gen_settings.ser {
+            self.encoders.line();
+            self.encoders.append(gen_encoder(container));
+        }
+        if self.gen_settings.des {
+            self.decoders.line();
+            self.decoders.append(gen_decoder(container));
+        }
+    }
+}
+
+impl LanguageBackend for LuaBackend<'_> {
+    type Lang = ();
+
+    fn prepare(&mut self, containers: &mut ContainerCollection) {
+        containers.assert_no_internally_tagged_enums("Lua");
+        containers.flatten();
+        containers.resolve_pointer_width(self.gen_settings.pointer_width.bytes());
+        containers.sets_as_sequences();
+        containers.remove_unit_fields();
+
+        for container in containers.all_containers() {
+            for (_, v_type) in container_fields(&container) {
+                assert_supported(v_type, false);
+            }
+        }
+    }
+
+    fn visit_struct(&mut self, container: &Container, _: &StructType) {
+        self.push_type(container);
+    }
+
+    fn visit_tuple_struct(&mut self, container: &Container, _: &TupleStructType) {
+        self.push_type(container);
+    }
+
+    fn visit_enum(&mut self, container: &Container, _: &EnumType) {
+        self.push_type(container);
+    }
+
+    fn visit_unit_struct(&mut self, container: &Container) {
+        self.push_type(container);
+    }
+
+    fn finish(self) -> Exports<()> {
+        let mut source = gen_runtime();
+
+        if self.gen_settings.ser {
+            source.line();
+            source.append(gen_ser_runtime());
+            source.line();
+            quote_in!(source => local encoders = {});
+            source.append(self.encoders);
+            source.line();
+            source.append(quote! {
+                function M.serialize(type_name, value)
+                    local encode = encoders[type_name]
+                    if encode == nil then
+                        fail("no bindings for " .. tostring(type_name))
+                    end
+                    local w = new_writer()
+                    encode(w, value)
+                    return w:finish()
+                end
+            });
+        }
+
+        if self.gen_settings.des {
+            source.line();
+            source.append(gen_des_runtime());
+            source.line();
+            quote_in!(source => local decoders = {});
+            source.append(self.decoders);
+            source.line();
+            source.append(quote! {
+                function M.deserialize(type_name, bytes)
+                    local decode = decoders[type_name]
+                    if decode == nil then
+                        fail("no bindings for " .. tostring(type_name))
+                    end
+                    local r = new_reader(bytes)
+                    local value = decode(r)
+                    r:finish()
+                    return value
+                end
+            });
+        }
+
+        source.line();
+        quote_in!(source => return M);
+
+        Exports {
+            files: vec![ExportFile {
+                content_type: "source".to_owned(),
+                content: source,
+            }],
+        }
+    }
+}
+
+/// Fields of a struct or an enum variant.
+enum Fields<'a> {
+    Named(&'a [StructField]),
+    Unnamed(&'a [ValueType]),
+    Unit,
+}
+
+impl<'a> Fields<'a> {
+    /// The keys of the fields in the table of the value.
+    fn members(&self) -> Vec<(Key, &'a ValueType)> {
+        match self {
+            Fields::Named(fields) => fields
+                .iter()
+                .filter(|field| !field.skip)
+                .map(|field| (Key::Name(field.name.to_string()), &field.v_type))
+                .collect(),
+            Fields::Unnamed(fields) => fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| (Key::Index(index + 1), field))
+                .collect(),
+            Fields::Unit => Vec::new(),
+        }
+    }
+}
+
+impl<'a> From<&'a EnumVariantType> for Fields<'a> {
+    fn from(variant_type: &'a EnumVariantType) -> Self {
+        match variant_type {
+            EnumVariantType::Empty => Fields::Unit,
+            EnumVariantType::Tuple(fields) => Fields::Unnamed(fields),
+            EnumVariantType::NewType(fields) => Fields::Named(fields),
+        }
+    }
+}
+
+/// Key of a field in a Lua table.
+enum Key {
+    Name(String),
+    Index(usize),
+}
+
+impl Key {
+    /// Accesses the field in the table `table`.
+    fn of(&self, table: &str) -> String {
+        match self {
+            Key::Name(name) if is_identifier(name) => format!("{table}.{name}"),
+            Key::Name(name) => format!("{table}[\"{name}\"]"),
+            Key::Index(index) => format!("{table}[{index}]"),
+        }
+    }
+}
+
+fn struct_fields(container: &Container) -> Option<Fields<'_>> {
+    match &container.r#type {
+        BindingType::Struct(struct_type) => Some(Fields::Named(&struct_type.fields)),
+        BindingType::TupleStruct(tuple_struct) => Some(Fields::Unnamed(&tuple_struct.fields)),
+        BindingType::UnitStruct(_) => Some(Fields::Unit),
+        BindingType::Enum(_) => None,
+    }
+}
+
+/// Fields of all variants for enums.
+fn container_fields(container: &Container) -> Vec<(Key, &ValueType)> {
+    match &container.r#type {
+        BindingType::Enum(enum_type) => enum_type
+            .variants
+            .iter()
+            .flat_map(|variant| Fields::from(&variant.inner_type).members())
+            .collect(),
+        _ => struct_fields(container).unwrap().members(),
+    }
+}
+
+/// Panics for types which can't be represented, `in_table` is set for items of sequences
+/// and maps, which can't be `nil`.
+fn assert_supported(v_type: &ValueType, in_table: bool) {
+    match v_type {
+        ValueType::Number(NumberMeta::Integer { bytes: 16, .. }) => {
+            panic!("128 bit integers are not supported by the Lua bindings")
+        }
+        ValueType::Unit(_) => panic!("`()` is only supported as field by the Lua bindings"),
+        ValueType::Optional(meta) => {
+            assert!(
+                !in_table,
+                "optionals are not supported by the Lua bindings in vectors, arrays, maps and other optionals"
+            );
+            assert_supported(&meta.inner, true);
+        }
+        ValueType::Range(meta) => assert_supported(&meta.bounds_type, true),
+        ValueType::Array(meta) => assert_supported(&meta.items_type, true),
+        ValueType::Map(meta) => {
+            assert_supported(&meta.key_type, true);
+            assert_supported(&meta.value_type, true);
+        }
+        ValueType::Tuple(meta) => {
+            // the items are accessed by index, so they may be `nil`
+            for item in meta.items_types.iter() {
+                assert_supported(item, false);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Key of the enum variant with a single unnamed field, which is the value itself.
+fn is_newtype(fields: &Fields) -> bool {
+    matches!(fields, Fields::Unnamed(fields) if fields.len() == 1)
+}
+
+fn gen_encoder(container: &Container) -> Tokens {
+    let mut body = Tokens::new();
+    match &container.r#type {
+        BindingType::Enum(enum_type) => {
+            body.append("local tag = v.tag");
+            body.push();
+            for (index, variant) in enum_type.variants.iter().enumerate() {
+                let fields = Fields::from(&variant.inner_type);
+                let keyword = if index == 0 { "if" } else { "elseif" };
+                let mut fields_body = Tokens::new();
+                if is_newtype(&fields) {
+                    let (_, v_type) = &fields.members()[0];
+                    fields_body.append(gen_encode(v_type, "v.value", 0));
+                } else {
+                    gen_encode_fields(&mut fields_body, &fields, "v.value");
+                }
+                quote_in! { body =>
+                    $keyword tag == $(format!("\"{}\"", variant.name)) then
+                        w:u32($(variant.index))
+                        $fields_body
+                };
+                body.push();
+            }
+            quote_in! { body =>
+                else
+                    fail($(format!("\"unknown variant \" .. tostring(tag) .. \" of {}\"", container.name)))
+                end
+            };
+        }
+        _ => gen_encode_fields(&mut body, &struct_fields(container).unwrap(), "v"),
+    }
+
+    let signature = format!(
+        "function {}(w, v)",
+        Key::Name(container.name.to_string()).of("encoders")
+    );
+    if body.is_empty() {
+        return quote!($signature end);
+    }
+    quote! {
+        $signature
+            $body
+        end
+    }
+}
+
+fn gen_encode_fields(tokens: &mut Tokens, fields: &Fields, table: &str) {
+    for (key, v_type) in fields.members() {
+        tokens.append(gen_encode(v_type, &key.of(table), 0));
+        tokens.push();
+    }
+}
+
+/// Encodes `value`, closures are nested with `depth`.
+fn gen_encode(v_type: &ValueType, value: &str, depth: usize) -> Tokens {
+    let item = format!("x{depth}");
+    let closure = |v_type: &ValueType, item: &str| quote!(function($item) $(gen_encode(v_type, item, depth + 1)) end);
+    match v_type {
+        ValueType::Object(meta) => {
+            quote!($(Key::Name(meta.name.to_string()).of("encoders"))(w, $value))
+        }
+        ValueType::Optional(meta) => quote!(w:option($value, $(closure(&meta.inner, &item)))),
+        ValueType::Array(ArrayMeta {
+            items_type,
+            length: Some(length),
+            ..
+        }) => quote!(w:array($value, $(*length), $(closure(items_type, &item)))),
+        ValueType::Array(ArrayMeta { items_type, .. }) => {
+            quote!(w:list($value, $(closure(items_type, &item))))
+        }
+        ValueType::Map(meta) => {
+            let key = format!("k{depth}");
+            quote!(w:map($value, $(closure(&meta.key_type, &key)), $(closure(&meta.value_type, &item))))
+        }
+        ValueType::Range(meta) => quote!(w:range($value, $(closure(&meta.bounds_type, &item)))),
+        ValueType::Tuple(meta) => {
+            quote!(w:tuple($value, $(for item_type in meta.items_types.iter() join (, ) => $(closure(item_type, &item)))))
+        }
+        _ => quote!(w:$(primitive_fn(v_type))($value)),
+    }
+}
+
+fn gen_decoder(container: &Container) -> Tokens {
+    let mut body = Tokens::new();
+    match &container.r#type {
+        BindingType::Enum(enum_type) => {
+            body.append("local tag = r:u32()");
+            body.push();
+            for (index, variant) in enum_type.variants.iter().enumerate() {
+                let fields = Fields::from(&variant.inner_type);
+                let keyword = if index == 0 { "if" } else { "elseif" };
+                let tag = format!("tag = \"{}\"", variant.name);
+                let mut case = Tokens::new();
+                match fields {
+                    Fields::Unit => quote_in!(case => return { $tag }),
+                    _ if is_newtype(&fields) => {
+                        let (_, v_type) = &fields.members()[0];
+                        quote_in!(case => return { $tag, value = $(gen_decode(v_type)) });
+                    }
+                    _ => quote_in! { case =>
+                        $(gen_decode_table("value", &fields))
+                        return { $tag, value = value }
+                    },
+                }
+                quote_in! { body =>
+                    $keyword tag == $(variant.index) then
+                        $case
+                };
+                body.push();
+            }
+            quote_in! { body =>
+                end
+                fail($(format!("\"unknown variant \" .. tag .. \" of {}\"", container.name)))
+            };
+        }
+        _ => quote_in! { body =>
+            $(gen_decode_table("v", &struct_fields(container).unwrap()))
+            return v
+        },
+    }
+
+    quote! {
+        $(format!("function {}(r)", Key::Name(container.name.to_string()).of("decoders")))
+            $body
+        end
+    }
+}
+
+/// Decodes `fields` into the new table `table`, one statement per field, as the order
+/// table constructors are evaluated in is undefined.
+fn gen_decode_table(table: &str, fields: &Fields) -> Tokens {
+    let mut tokens = quote!(local $table = {});
+    for (key, v_type) in fields.members() {
+        tokens.push();
+        quote_in!(tokens => $(key.of(table)) = $(gen_decode(v_type)));
+    }
+    tokens
+}
+
+fn gen_decode(v_type: &ValueType) -> Tokens {
+    let closure = |v_type: &ValueType| quote!(function() return $(gen_decode(v_type)) end);
+    match v_type {
+        ValueType::Object(meta) => {
+            quote!($(Key::Name(meta.name.to_string()).of("decoders"))(r))
+        }
+        ValueType::Optional(meta) => quote!(r:option($(closure(&meta.inner)))),
+        ValueType::Array(ArrayMeta {
+            items_type,
+            length: Some(length),
+            ..
+        }) => quote!(r:array($(*length), $(closure(items_type)))),
+        ValueType::Array(ArrayMeta { items_type, .. }) => {
+            quote!(r:list($(closure(items_type))))
+        }
+        ValueType::Map(meta) => {
+            quote!(r:map($(closure(&meta.key_type)), $(closure(&meta.value_type))))
+        }
+        ValueType::Range(meta) => quote!(r:range($(closure(&meta.bounds_type)))),
+        ValueType::Tuple(meta) => {
+            quote!(r:tuple($(for item_type in meta.items_types.iter() join (, ) => $(closure(item_type)))))
+        }
+        _ => quote!(r:$(primitive_fn(v_type))()),
+    }
+}
+
+/// Name of the `Writer` and `Reader` method of a primitive.
+fn primitive_fn(v_type: &ValueType) -> String {
+    match v_type {
+        ValueType::Number(NumberMeta::Integer { bytes, signed, .. }) => {
+            format!("{}{}", if *signed { "i" } else { "u" }, bytes * 8)
+        }
+        ValueType::Number(NumberMeta::FloatingPoint { bytes }) => format!("f{}", bytes * 8),
+        ValueType::Bool(_) => "bool".to_owned(),
+        ValueType::String(_) => "string".to_owned(),
+        _ => unreachable!("not a primitive type"),
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    !LUA_KEYWORDS.contains(&name)
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+}
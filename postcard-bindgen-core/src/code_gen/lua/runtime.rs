@@ -0,0 +1,412 @@
+use genco::quote;
+
+use super::Tokens;
+
+/// Helpers shared by the encode and decode functions.
+pub fn gen_runtime() -> Tokens {
+    quote!($(RUNTIME))
+}
+
+pub fn gen_ser_runtime() -> Tokens {
+    quote!($(SER_RUNTIME))
+}
+
+pub fn gen_des_runtime() -> Tokens {
+    quote!($(DES_RUNTIME))
+}
+
+const RUNTIME: &str = r#"local M = {}
+
+-- Lua 5.3 and later have 64 bit integers and bitwise operators, older versions only doubles
+local shr
+local INT_MIN, INT_MAX = -2 ^ 53, 2 ^ 53
+if math.type then
+    shr = load("return function(v, n) return v >> n end")()
+    INT_MIN, INT_MAX = math.mininteger, math.maxinteger
+else
+    shr = function(v, n)
+        return math.floor(v / 2 ^ n)
+    end
+end
+
+local function fail(message)
+    error("postcard: " .. message, 0)
+end"#;
+
+const SER_RUNTIME: &str = r#"local Writer = {}
+Writer.__index = Writer
+
+local function new_writer()
+    return setmetatable({ parts = {} }, Writer)
+end
+
+local function integer(v, min, max)
+    if type(v) ~= "number" or v ~= math.floor(v) or v < min or v > max then
+        fail(tostring(v) .. " is out of range")
+    end
+    return math.tointeger and math.tointeger(v) or v
+end
+
+function Writer:raw(s)
+    self.parts[#self.parts + 1] = s
+end
+
+function Writer:push(b)
+    self:raw(string.char(b))
+end
+
+-- negative values are taken as the bit pattern of an unsigned 64 bit integer
+function Writer:varint(v)
+    while v >= 0x80 or v < 0 do
+        self:push(v % 0x80 + 0x80)
+        v = shr(v, 7)
+    end
+    self:push(v)
+end
+
+function Writer:zigzag(v)
+    if v >= 0 then
+        self:varint(v * 2)
+    else
+        self:varint(-v * 2 - 1)
+    end
+end
+
+function Writer:len(n)
+    self:varint(n)
+end
+
+function Writer:bool(v)
+    if type(v) ~= "boolean" then
+        fail(tostring(v) .. " is not a boolean")
+    end
+    self:push(v and 1 or 0)
+end
+
+function Writer:u8(v) self:push(integer(v, 0, 0xFF)) end
+function Writer:i8(v) self:push(integer(v, -0x80, 0x7F) % 0x100) end
+function Writer:u16(v) self:varint(integer(v, 0, 0xFFFF)) end
+function Writer:u32(v) self:varint(integer(v, 0, 0xFFFFFFFF)) end
+function Writer:u64(v) self:varint(integer(v, math.type and INT_MIN or 0, INT_MAX)) end
+function Writer:i16(v) self:zigzag(integer(v, -0x8000, 0x7FFF)) end
+function Writer:i32(v) self:zigzag(integer(v, -0x80000000, 0x7FFFFFFF)) end
+function Writer:i64(v) self:zigzag(integer(v, INT_MIN, INT_MAX)) end
+
+if string.pack then
+    function Writer:f32(v) self:raw(string.pack("<f", v)) end
+    function Writer:f64(v) self:raw(string.pack("<d", v)) end
+else
+    -- Lua 5.1 and 5.2 can't pack floats, so the IEEE 754 bits are assembled by hand
+    local function encode_float(v, mantissa_bits, exponent_bits)
+        local bias = 2 ^ (exponent_bits - 1) - 1
+        local max_exponent = 2 ^ exponent_bits - 1
+        local sign = 0
+        if v < 0 or (v == 0 and 1 / v < 0) then
+            sign = 1
+            v = -v
+        end
+        local mantissa, exponent = 0, 0
+        if v ~= v then
+            mantissa, exponent = 2 ^ (mantissa_bits - 1), max_exponent
+        elseif v == math.huge then
+            exponent = max_exponent
+        elseif v ~= 0 then
+            local m, e = math.frexp(v)
+            exponent = e + bias - 1
+            if exponent <= 0 then
+                mantissa = math.floor(v / 2 ^ (1 - bias - mantissa_bits) + 0.5)
+                exponent = 0
+            else
+                mantissa = math.floor((m * 2 - 1) * 2 ^ mantissa_bits + 0.5)
+            end
+            if mantissa == 2 ^ mantissa_bits then
+                mantissa, exponent = 0, exponent + 1
+            end
+            if exponent >= max_exponent then
+                mantissa, exponent = 0, max_exponent
+            end
+        end
+
+        local high = sign * 2 ^ exponent_bits + exponent
+        local out, bits = {}, mantissa_bits
+        for i = 1, (mantissa_bits + exponent_bits + 1) / 8 do
+            if bits >= 8 then
+                out[i] = mantissa % 0x100
+                mantissa = (mantissa - out[i]) / 0x100
+                bits = bits - 8
+            else
+                local scale = 2 ^ (8 - bits)
+                local low = high % scale
+                out[i] = mantissa + low * 2 ^ bits
+                high = (high - low) / scale
+                mantissa, bits = 0, 0
+            end
+        end
+        return string.char((table.unpack or unpack)(out))
+    end
+
+    function Writer:f32(v) self:raw(encode_float(v, 23, 8)) end
+    function Writer:f64(v) self:raw(encode_float(v, 52, 11)) end
+end
+
+function Writer:string(v)
+    if type(v) ~= "string" then
+        fail(tostring(v) .. " is not a string")
+    end
+    self:len(#v)
+    self:raw(v)
+end
+
+function Writer:option(v, item)
+    if v == nil then
+        self:push(0)
+    else
+        self:push(1)
+        item(v)
+    end
+end
+
+function Writer:list(v, item)
+    self:len(#v)
+    for i = 1, #v do
+        item(v[i])
+    end
+end
+
+function Writer:array(v, n, item)
+    if #v ~= n then
+        fail("expected " .. n .. " items, got " .. #v)
+    end
+    for i = 1, n do
+        item(v[i])
+    end
+end
+
+function Writer:map(v, key, value)
+    local n = 0
+    for _ in pairs(v) do
+        n = n + 1
+    end
+    self:len(n)
+    for k, x in pairs(v) do
+        key(k)
+        value(x)
+    end
+end
+
+function Writer:range(v, item)
+    item(v.start)
+    item(v["end"])
+end
+
+function Writer:tuple(v, ...)
+    local items = { ... }
+    for i = 1, #items do
+        items[i](v[i])
+    end
+end
+
+function Writer:finish()
+    return table.concat(self.parts)
+end"#;
+
+const DES_RUNTIME: &str = r#"local Reader = {}
+Reader.__index = Reader
+
+local function new_reader(data)
+    if type(data) ~= "string" then
+        fail("expected the bytes as string, got " .. type(data))
+    end
+    return setmetatable({ data = data, pos = 1 }, Reader)
+end
+
+function Reader:pop()
+    local b = string.byte(self.data, self.pos)
+    if b == nil then
+        fail("unexpected end of input")
+    end
+    self.pos = self.pos + 1
+    return b
+end
+
+function Reader:bytes(n)
+    local last = self.pos + n - 1
+    if last > #self.data then
+        fail("unexpected end of input")
+    end
+    local s = string.sub(self.data, self.pos, last)
+    self.pos = last + 1
+    return s
+end
+
+-- rejects varints longer than max_bytes and values exceeding 64 bits
+function Reader:varint(max_bytes)
+    local out, scale = 0, 1
+    for i = 1, max_bytes do
+        local b = self:pop()
+        if i == 10 and b > 1 then
+            fail("varint exceeds 64 bits")
+        end
+        out = out + b % 0x80 * scale
+        if b < 0x80 then
+            return out
+        end
+        scale = scale * 0x80
+    end
+    fail("varint exceeds " .. max_bytes .. " bytes")
+end
+
+function Reader:zigzag(max_bytes)
+    local raw = self:varint(max_bytes)
+    if raw % 2 == 0 then
+        return shr(raw, 1)
+    end
+    return -shr(raw, 1) - 1
+end
+
+function Reader:unsigned(max_bytes, max)
+    local v = self:varint(max_bytes)
+    if v < 0 or v > max then
+        fail(tostring(v) .. " is out of range")
+    end
+    return v
+end
+
+function Reader:signed(max_bytes, min, max)
+    local v = self:zigzag(max_bytes)
+    if v < min or v > max then
+        fail(tostring(v) .. " is out of range")
+    end
+    return v
+end
+
+function Reader:len()
+    return self:unsigned(10, INT_MAX)
+end
+
+function Reader:bool()
+    local b = self:pop()
+    if b > 1 then
+        fail("invalid bool")
+    end
+    return b == 1
+end
+
+function Reader:u8() return self:pop() end
+function Reader:u16() return self:unsigned(3, 0xFFFF) end
+function Reader:u32() return self:unsigned(5, 0xFFFFFFFF) end
+function Reader:u64() return self:varint(10) end
+function Reader:i16() return self:signed(3, -0x8000, 0x7FFF) end
+function Reader:i32() return self:signed(5, -0x80000000, 0x7FFFFFFF) end
+function Reader:i64() return self:zigzag(10) end
+
+function Reader:i8()
+    local b = self:pop()
+    if b >= 0x80 then
+        return b - 0x100
+    end
+    return b
+end
+
+if string.unpack then
+    function Reader:f32() return (string.unpack("<f", self:bytes(4))) end
+    function Reader:f64() return (string.unpack("<d", self:bytes(8))) end
+else
+    -- Lua 5.1 and 5.2 can't unpack floats, so the IEEE 754 bits are decoded by hand
+    local function decode_float(s, mantissa_bits, exponent_bits)
+        local mantissa, exponent = 0, 0
+        local mantissa_scale, exponent_scale, bits = 1, 1, mantissa_bits
+        for i = 1, #s do
+            local b = string.byte(s, i)
+            if bits >= 8 then
+                mantissa = mantissa + b * mantissa_scale
+                mantissa_scale = mantissa_scale * 0x100
+                bits = bits - 8
+            else
+                local low = b % 2 ^ bits
+                mantissa = mantissa + low * mantissa_scale
+                exponent = exponent + (b - low) / 2 ^ bits * exponent_scale
+                exponent_scale = exponent_scale * 2 ^ (8 - bits)
+                bits = 0
+            end
+        end
+
+        local sign = 1
+        if exponent >= 2 ^ exponent_bits then
+            sign = -1
+            exponent = exponent - 2 ^ exponent_bits
+        end
+        local bias = 2 ^ (exponent_bits - 1) - 1
+        if exponent == 2 ^ exponent_bits - 1 then
+            if mantissa == 0 then
+                return sign * math.huge
+            end
+            return 0 / 0
+        elseif exponent == 0 then
+            return sign * mantissa * 2 ^ (1 - bias - mantissa_bits)
+        end
+        return sign * (1 + mantissa / 2 ^ mantissa_bits) * 2 ^ (exponent - bias)
+    end
+
+    function Reader:f32() return decode_float(self:bytes(4), 23, 8) end
+    function Reader:f64() return decode_float(self:bytes(8), 52, 11) end
+end
+
+function Reader:string()
+    local s = self:bytes(self:len())
+    if utf8 and not utf8.len(s) then
+        fail("invalid utf-8")
+    end
+    return s
+end
+
+function Reader:option(item)
+    if self:bool() then
+        return item()
+    end
+    return nil
+end
+
+function Reader:list(item)
+    local out = {}
+    for i = 1, self:len() do
+        out[i] = item()
+    end
+    return out
+end
+
+function Reader:array(n, item)
+    local out = {}
+    for i = 1, n do
+        out[i] = item()
+    end
+    return out
+end
+
+function Reader:map(key, value)
+    local out = {}
+    for _ = 1, self:len() do
+        local k = key()
+        out[k] = value()
+    end
+    return out
+end
+
+function Reader:range(item)
+    local start = item()
+    return { start = start, ["end"] = item() }
+end
+
+function Reader:tuple(...)
+    local items, out = { ... }, {}
+    for i = 1, #items do
+        out[i] = items[i]()
+    end
+    return out
+end
+
+function Reader:finish()
+    if self.pos <= #self.data then
+        fail(#self.data - self.pos + 1 .. " trailing bytes")
+    end
+end"#;
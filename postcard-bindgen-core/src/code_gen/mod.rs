@@ -1,4 +1,3 @@
-mod available_check;
 mod export_registry;
 mod field_accessor;
 mod function;
@@ -7,8 +6,19 @@ mod switch_case;
 mod utils;
 mod variable_path;
 
+pub mod backend;
+pub mod c;
+pub mod cpp;
+pub mod csharp;
+pub mod dart;
+pub mod go;
+pub mod java;
 pub mod js;
+pub mod kotlin;
+pub mod lua;
 pub mod python;
+pub mod settings;
+pub mod swift;
 
 use crate::type_info::NumberMeta;
 
@@ -33,4 +43,13 @@ impl NumberMeta {
             _ => unreachable!(),
         }
     }
+
+    /// Upper bound of the encoded length in bytes, integers wider than one byte are varints.
+    pub(crate) fn max_encoded_len(&self) -> usize {
+        match self {
+            NumberMeta::Integer { bytes: 1, .. } => 1,
+            NumberMeta::Integer { bytes, .. } => (bytes * 8).div_ceil(7),
+            NumberMeta::FloatingPoint { bytes } => *bytes,
+        }
+    }
 }
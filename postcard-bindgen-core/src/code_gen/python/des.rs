@@ -55,7 +55,8 @@ pub fn gen_deserializer_code() -> Tokens {
 
             def deserialize_number(self, n_bytes, signed):
                 if n_bytes == U8_BYTES:
-                    return self.get_uint8()
+                    byte = self.get_uint8()
+                    return byte - 0x100 if signed and byte > 0x7F else byte
                 elif n_bytes in {U16_BYTES, U32_BYTES, U64_BYTES, U128_BYTES}:
                     val = self.try_take(n_bytes)
                     return to_number_if_safe(de_zig_zag_signed(val) if signed else val)
@@ -74,7 +75,7 @@ pub fn gen_deserializer_code() -> Tokens {
             def deserialize_string(self):
                 str_len = self.try_take(U32_BYTES)
                 str_bytes = self.pop_n(str_len)
-                return "".join(chr(b) for b in str_bytes)
+                return bytes(str_bytes).decode("utf-8")
 
             def deserialize_array(self, des, length = None):
                 return [des(self) for _ in range(self.try_take(U32_BYTES) if length is None else length)]
@@ -92,9 +93,9 @@ pub fn gen_des_functions(bindings: impl Iterator<Item = Container>) -> Tokens {
 
 fn gen_des_function_for_type(container: Container) -> Tokens {
     let container_ident =
-        ContainerIdentifierBuilder::new(container.path.clone().into_buf(), container.name).build();
+        ContainerIdentifierBuilder::new(container.path.clone().into_buf(), &container.name).build();
     let fully_qualified =
-        ContainerFullQualifiedTypeBuilder::new(container.path.clone().into_buf(), container.name)
+        ContainerFullQualifiedTypeBuilder::new(container.path.clone().into_buf(), &container.name)
             .build();
     let des_body = container.r#type.gen_des_body((&container).into());
     quote! {
@@ -106,7 +107,7 @@ fn gen_des_function_for_type(container: Container) -> Tokens {
 pub fn gen_deserialize_func(containers: impl Iterator<Item = Container> + Clone) -> Tokens {
     let all_bindings = containers
         .clone()
-        .map(|d| ContainerFullQualifiedTypeBuilder::new(d.path.clone().into_buf(), d.name).build())
+        .map(|d| ContainerFullQualifiedTypeBuilder::new(d.path.clone().into_buf(), &d.name).build())
         .collect::<Vec<_>>();
 
     let mut obj_type_types = all_bindings.iter().map(|d| quote!($d));
@@ -137,10 +138,10 @@ pub fn gen_deserialize_func(containers: impl Iterator<Item = Container> + Clone)
 
 fn gen_des_case(container: Container) -> (Tokens, Tokens) {
     let fully_qualified =
-        ContainerFullQualifiedTypeBuilder::new(container.path.clone().into_buf(), container.name)
+        ContainerFullQualifiedTypeBuilder::new(container.path.clone().into_buf(), &container.name)
             .build();
     let container_ident =
-        ContainerIdentifierBuilder::new(container.path.clone().into_buf(), container.name).build();
+        ContainerIdentifierBuilder::new(container.path.clone().into_buf(), &container.name).build();
     (
         quote!(obj_type is $fully_qualified),
         quote!(return cast(T, deserialize_$container_ident(d))),
@@ -3,6 +3,7 @@ use genco::quote;
 use super::Tokens;
 
 pub fn gen_util() -> Tokens {
+    // python's floor division `//` would start a comment in the macro, so it's avoided
     quote! {
         BITS_PER_BYTE = 8
         BITS_PER_VARINT_BYTE = 7
@@ -19,7 +20,7 @@ pub fn gen_util() -> Tokens {
             return (n << 1) ^ (n >> (n_bytes * BITS_PER_BYTE - 1))
 
         def varint_max(n_bytes):
-            return (n_bytes * BITS_PER_BYTE + (BITS_PER_BYTE - 1)) // BITS_PER_VARINT_BYTE
+            return divmod(n_bytes * BITS_PER_BYTE + (BITS_PER_BYTE - 1), BITS_PER_VARINT_BYTE)[0]
 
         def max_of_last_byte(n_bytes):
             return (1 << (n_bytes * BITS_PER_BYTE) % 7) - 1
@@ -42,7 +43,7 @@ pub fn gen_util() -> Tokens {
             max_val = 2 ** (n_bytes * BITS_PER_BYTE)
             value_b = int(value)
             if signed:
-                bounds = max_val // 2
+                bounds = max_val >> 1
                 return -bounds <= value_b < bounds
             else:
                 return 0 <= value_b < max_val
@@ -15,12 +15,16 @@ use crate::{
 
 use super::{BindingTypeGenerateable, ContainerInfo};
 
+// An enum without variants is uninhabited, no value of it can be encoded or decoded.
 impl BindingTypeGenerateable for EnumType {
     fn gen_ser_body(&self, container_info: ContainerInfo<'_>) -> Tokens {
+        if self.variants.is_empty() {
+            return gen_uninhabited_error();
+        }
         self.variants
             .iter()
             .map(|v| {
-                let variant_name = quote!($(container_info.name.as_ref())_$(v.name));
+                let variant_name = quote!($(container_info.name.as_ref())_$(&v.name));
 
                 let ser_fields = [quote!(s.serialize_number(U32_BYTES, False, $(v.index)))]
                     .into_iter()
@@ -28,10 +32,11 @@ impl BindingTypeGenerateable for EnumType {
                         EnumVariantType::Empty => quote!(),
                         EnumVariantType::NewType(fields) => fields
                             .iter()
+                            .filter(|f| !f.skip)
                             .map(|f| {
                                 f.v_type.gen_ser_accessor(
                                     VariablePath::default()
-                                        .modify_push(VariableAccess::Field(f.name.to_owned())),
+                                        .modify_push(VariableAccess::Field(f.name.to_string())),
                                 )
                             })
                             .join_with_line_breaks(),
@@ -60,6 +65,9 @@ impl BindingTypeGenerateable for EnumType {
     }
 
     fn gen_des_body(&self, container_info: ContainerInfo<'_>) -> Tokens {
+        if self.variants.is_empty() {
+            return gen_uninhabited_error();
+        }
         let fully_qualified = ContainerFullQualifiedTypeBuilder::from(&container_info).build();
         let switch = self
             .variants
@@ -67,16 +75,20 @@ impl BindingTypeGenerateable for EnumType {
             .map(|v| {
                 let constructor_args = match &v.inner_type {
                     EnumVariantType::Empty => quote!(),
-                    EnumVariantType::NewType(fields) => fields.iter().map(
-                        |f| quote!($(f.name) = $(f.v_type.gen_des_accessor(FieldAccessor::None))),
-                    ).join_with_comma(),
+                    EnumVariantType::NewType(fields) => fields.iter().map(|f| {
+                        if f.skip {
+                            quote!($(&f.name) = None)
+                        } else {
+                            quote!($(&f.name) = $(f.v_type.gen_des_accessor(FieldAccessor::None)))
+                        }
+                    }).join_with_comma(),
                     EnumVariantType::Tuple(fields) => fields.iter().map(
                         |f| quote!($(f.gen_des_accessor(FieldAccessor::None))),
                     ).join_with_comma(),
                 };
                 (
                     Some(quote!(variant_index == $(v.index))),
-                    quote!(return $(&fully_qualified)_$(v.name)($constructor_args)),
+                    quote!(return $(&fully_qualified)_$(&v.name)($constructor_args)),
                 )
             })
             .chain([(
@@ -92,6 +104,9 @@ impl BindingTypeGenerateable for EnumType {
     }
 
     fn gen_ty_check_body(&self, container_info: ContainerInfo<'_>) -> Tokens {
+        if self.variants.is_empty() {
+            return gen_uninhabited_error();
+        }
         let fully_qualified = ContainerFullQualifiedTypeBuilder::from(&container_info).build();
         let assert_funcs = self
             .variants
@@ -101,10 +116,11 @@ impl BindingTypeGenerateable for EnumType {
                     EnumVariantType::Empty => quote!(pass),
                     EnumVariantType::NewType(fields) => fields
                         .iter()
+                        .filter(|f| !f.skip)
                         .map(|f| {
                             f.v_type.gen_ty_check(
                                 VariablePath::default()
-                                    .modify_push(VariableAccess::Field(v.name.to_owned())),
+                                    .modify_push(VariableAccess::Field(f.name.to_string())),
                             )
                         })
                         .join_with_line_breaks(),
@@ -119,7 +135,7 @@ impl BindingTypeGenerateable for EnumType {
                         .join_with_line_breaks(),
                 };
                 quote! {
-                    def assert_$(v.name)($PYTHON_OBJECT_VARIABLE):
+                    def assert_$(&v.name)($PYTHON_OBJECT_VARIABLE):
                         $body
 
                 }
@@ -130,10 +146,10 @@ impl BindingTypeGenerateable for EnumType {
             .variants
             .iter()
             .map(|v| {
-                let variant_name = quote!($(&fully_qualified)_$(v.name));
+                let variant_name = quote!($(&fully_qualified)_$(&v.name));
                 (
                     Some(quote!(isinstance($PYTHON_OBJECT_VARIABLE, $variant_name))),
-                    quote!(assert_$(v.name)($PYTHON_OBJECT_VARIABLE)),
+                    quote!(assert_$(&v.name)($PYTHON_OBJECT_VARIABLE)),
                 )
             })
             .chain([(
@@ -154,6 +170,13 @@ impl BindingTypeGenerateable for EnumType {
         container_info: ContainerInfo<'_>,
         import_registry: &mut ImportRegistry,
     ) -> Tokens {
+        if self.variants.is_empty() {
+            return quote! {
+                class $(container_info.name):
+                    pass
+            };
+        }
+
         let variants = self
             .variants
             .iter()
@@ -169,6 +192,10 @@ impl BindingTypeGenerateable for EnumType {
     }
 }
 
+fn gen_uninhabited_error() -> Tokens {
+    quote!(raise TypeError("uninhabited enum"))
+}
+
 fn gen_variant_typings(
     enum_name: impl AsRef<str>,
     variant: impl AsRef<EnumVariant>,
@@ -177,7 +204,7 @@ fn gen_variant_typings(
     let enum_name = enum_name.as_ref();
     let variant = variant.as_ref();
 
-    let variant_name = quote!($(enum_name)_$(variant.name));
+    let variant_name = quote!($(enum_name)_$(&variant.name));
 
     match &variant.inner_type {
         EnumVariantType::Empty => quote! {
@@ -187,7 +214,17 @@ fn gen_variant_typings(
         EnumVariantType::NewType(fields) => {
             let fields = fields
                 .iter()
-                .map(|f| quote!($(f.name): $(f.v_type.gen_typings(import_registry))))
+                .map(|f| {
+                    if f.skip {
+                        import_registry.push(
+                            Package::Extern("typing".into()),
+                            ImportItem::Single("Optional".into()),
+                        );
+                        quote!($(&f.name): Optional[$(f.v_type.gen_typings(import_registry))])
+                    } else {
+                        quote!($(&f.name): $(f.v_type.gen_typings(import_registry)))
+                    }
+                })
                 .join_with_line_breaks();
 
             import_registry.push(
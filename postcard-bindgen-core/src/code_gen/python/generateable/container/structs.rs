@@ -18,9 +18,11 @@ impl BindingTypeGenerateable for StructType {
     fn gen_ser_body(&self, _container_info: ContainerInfo<'_>) -> Tokens {
         self.fields
             .iter()
+            .filter(|field| !field.skip)
             .map(|field| {
                 field.v_type.gen_ser_accessor(
-                    VariablePath::default().modify_push(VariableAccess::Field(field.name.into())),
+                    VariablePath::default()
+                        .modify_push(VariableAccess::Field(field.name.to_string())),
                 )
             })
             .join_with_line_breaks()
@@ -32,9 +34,13 @@ impl BindingTypeGenerateable for StructType {
             .fields
             .iter()
             .map(|field| {
-                field
-                    .v_type
-                    .gen_des_accessor(FieldAccessor::Object(field.name))
+                if field.skip {
+                    quote!($(FieldAccessor::Object(&field.name))None)
+                } else {
+                    field
+                        .v_type
+                        .gen_des_accessor(FieldAccessor::Object(&field.name))
+                }
             })
             .join_with_comma();
         quote!(return $fully_qualified($body))
@@ -47,11 +53,12 @@ impl BindingTypeGenerateable for StructType {
         let field_checks = self
             .fields
             .iter()
+            .filter(|field| !field.skip)
             .map(|field| {
                 field.v_type.gen_ty_check(
                     variable_path
                         .to_owned()
-                        .modify_push(VariableAccess::Field(field.name.into())),
+                        .modify_push(VariableAccess::Field(field.name.to_string())),
                 )
             })
             .join_with_line_breaks();
@@ -72,7 +79,17 @@ impl BindingTypeGenerateable for StructType {
         let body = self
             .fields
             .iter()
-            .map(|field| quote!($(field.name): $(field.v_type.gen_typings(import_registry))))
+            .map(|field| {
+                if field.skip {
+                    import_registry.push(
+                        Package::Extern("typing".into()),
+                        ImportItem::Single("Optional".into()),
+                    );
+                    quote!($(&field.name): Optional[$(field.v_type.gen_typings(import_registry))])
+                } else {
+                    quote!($(&field.name): $(field.v_type.gen_typings(import_registry)))
+                }
+            })
             .join_with_line_breaks();
         import_registry.push(
             Package::Extern("dataclasses".into()),
@@ -1,14 +1,11 @@
-use std::{
-    borrow::Cow,
-    path::{Path, PathBuf},
-};
+use std::path::{Path, PathBuf};
 
 use container::BindingTypeGenerateable;
 use convert_case::{Case, Casing};
 use genco::{quote, quote_in, tokens::quoted};
 
 use crate::{
-    code_gen::{python::ImportRegistry, utils::TokensIterExt},
+    code_gen::{import_registry::Package, python::ImportRegistry, utils::TokensIterExt},
     registry::{BindingType, Container, ContainerCollection, Module},
 };
 
@@ -44,7 +41,7 @@ fn generate_typings_for_mod<'a>(
     generate_package_name: String,
 ) {
     let container_exports = containers.clone().map(|f| {
-        let mut l = vec![Cow::from(f.name)];
+        let mut l = vec![f.name.clone()];
         if let BindingType::Enum(e) = f.r#type {
             l.extend(
                 e.variants
@@ -96,6 +93,12 @@ fn generate_typings_for_mod<'a>(
         let types = container
             .r#type
             .gen_typings_body((&container).into(), &mut import_registry);
+        // Other containers are imported at the end, when the classes of this file already
+        // exist. Types which reference each other, e.g. through a `Box`, would be circular
+        // imports otherwise.
+        let container_imports = import_registry.split_off(|package| {
+            matches!(package, Package::Intern(path) if path.parts().next().is_some_and(|p| p == "types"))
+        });
 
         files.push(ExportFile {
             content_type: path
@@ -106,6 +109,8 @@ fn generate_typings_for_mod<'a>(
                 $import_registry
 
                 $types
+
+                $container_imports
             },
         });
     }
@@ -5,7 +5,7 @@ use crate::{
         python::{FieldAccessor, ImportRegistry, Tokens, VariablePath, PYTHON_OBJECT_VARIABLE},
         utils::TokensIterExt,
     },
-    type_info::ArrayMeta,
+    type_info::{ArrayFormat, ArrayMeta},
 };
 
 use super::PythonTypeGenerateable;
@@ -35,7 +35,7 @@ impl PythonTypeGenerateable for ArrayMeta {
                 $(self.items_type.gen_ty_check(VariablePath::default()))
         };
         let item_ty_check = quote!([assert_$(variable_path.to_owned().into_string("_"))($PYTHON_OBJECT_VARIABLE) for $PYTHON_OBJECT_VARIABLE in $(variable_path.clone())]);
-        if let Some(len) = self.length {
+        if let Some(len) = self.length.or(self.format.map(ArrayFormat::length)) {
             [
                 quote!(assert isinstance($(variable_path.to_owned()), list), "{} is not a list".format($(variable_path.to_owned()))),
                 quote!(assert len($(variable_path.to_owned())) == $len, "{} has not a length of {}".format($(variable_path.to_owned()), $len)),
@@ -44,6 +44,15 @@ impl PythonTypeGenerateable for ArrayMeta {
             ]
             .into_iter()
             .join_with_line_breaks()
+        } else if let Some(capacity) = self.capacity {
+            [
+                quote!(assert isinstance($(variable_path.to_owned()), list), "{} is not a list".format($(variable_path.to_owned()))),
+                quote!(assert len($(variable_path.to_owned())) <= $capacity, "{} has more than {} elements".format($(variable_path.to_owned()), $capacity)),
+                assert_item_type_check_func,
+                quote!($item_ty_check),
+            ]
+            .into_iter()
+            .join_with_line_breaks()
         } else {
             [
                 quote!(assert isinstance($(variable_path.to_owned()), list), "{} is not a list".format($(variable_path.to_owned()))),
@@ -8,8 +8,10 @@ mod object;
 mod optional;
 mod python_type;
 mod range;
+mod set;
 mod string;
 mod tuple;
+mod unit;
 
 pub trait PythonTypeGenerateable {
     fn gen_ser_accessor(&self, variable_path: VariablePath) -> Tokens;
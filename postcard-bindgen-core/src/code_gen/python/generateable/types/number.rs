@@ -44,13 +44,17 @@ impl PythonTypeGenerateable for NumberMeta {
             NumberMeta::FloatingPoint { .. } => {
                 quote!(assert isinstance($(variable_path.to_owned()), float), "{} is not a float".format($variable_path))
             }
-            NumberMeta::Integer { signed, .. } => {
+            NumberMeta::Integer {
+                signed, non_zero, ..
+            } => {
                 let signed = bool_to_python_bool(*signed);
+                let non_zero_check = non_zero.then(|| quote!(assert $(variable_path.to_owned()) != 0, "{} is zero".format($(variable_path.to_owned()))));
                 [
                     quote!(assert isinstance($(variable_path.to_owned()), int), "{} is not an int".format($(variable_path.to_owned()))),
                     quote!(assert check_bounds($byte_amount_str, $signed, $(variable_path.to_owned())), "{} does not fit into an {}".format($variable_path, $byte_amount_str))
                 ]
                 .into_iter()
+                .chain(non_zero_check)
                 .join_with_line_breaks()
             }
         }
@@ -61,7 +65,7 @@ impl PythonTypeGenerateable for NumberMeta {
             NumberMeta::FloatingPoint { .. } => {
                 quote!(float)
             }
-            NumberMeta::Integer { bytes, signed } => {
+            NumberMeta::Integer { bytes, signed, .. } => {
                 let int_type = rust_int_to_python_type(*bytes, *signed);
                 import_registry.push(
                     Package::Intern("basic_types".into()),
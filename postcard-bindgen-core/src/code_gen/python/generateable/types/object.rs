@@ -1,5 +1,5 @@
 use convert_case::{Case, Casing};
-use genco::quote;
+use genco::{quote, tokens::quoted};
 
 use crate::{
     code_gen::{
@@ -15,19 +15,19 @@ use super::PythonTypeGenerateable;
 impl PythonTypeGenerateable for ObjectMeta {
     fn gen_ser_accessor(&self, variable_path: VariablePath) -> Tokens {
         let container_ident =
-            ContainerIdentifierBuilder::new(self.path.clone().into_buf(), self.name).build();
+            ContainerIdentifierBuilder::new(self.path.clone().into_buf(), &self.name).build();
         quote!(serialize_$container_ident(s, $variable_path))
     }
 
     fn gen_des_accessor(&self, field_accessor: FieldAccessor) -> Tokens {
         let container_ident =
-            ContainerIdentifierBuilder::new(self.path.clone().into_buf(), self.name).build();
+            ContainerIdentifierBuilder::new(self.path.clone().into_buf(), &self.name).build();
         quote!($(field_accessor)deserialize_$container_ident(d))
     }
 
     fn gen_ty_check(&self, variable_path: VariablePath) -> Tokens {
         let container_ident =
-            ContainerIdentifierBuilder::new(self.path.clone().into_buf(), self.name).build();
+            ContainerIdentifierBuilder::new(self.path.clone().into_buf(), &self.name).build();
         quote!(assert_$container_ident($variable_path))
     }
 
@@ -41,17 +41,18 @@ impl PythonTypeGenerateable for ObjectMeta {
 
         import_path.push_front("types");
 
-        container_path.push(self.name);
+        container_path.push(self.name.clone());
         let type_alias = format!("_{}", String::from(container_path.into_path("_")));
 
         import_registry.push(
             Package::Intern(import_path),
             ImportItem::Aliased {
-                item_name: self.name.into(),
+                item_name: self.name.clone(),
                 alias: type_alias.clone().into(),
             },
         );
 
-        quote!($type_alias)
+        // quoted, the import is placed after the classes, see `generate_typings_for_mod`
+        quote!($(quoted(type_alias)))
     }
 }
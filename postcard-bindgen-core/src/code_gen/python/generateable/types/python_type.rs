@@ -15,8 +15,10 @@ impl PythonTypeGenerateable for ValueType {
             Self::String(string_meta) => string_meta.gen_ser_accessor(variable_path),
             Self::Range(range_meta) => range_meta.gen_ser_accessor(variable_path),
             Self::Map(map_meta) => map_meta.gen_ser_accessor(variable_path),
+            Self::Set(set_meta) => set_meta.gen_ser_accessor(variable_path),
             Self::Tuple(tuple_meta) => tuple_meta.gen_ser_accessor(variable_path),
             Self::Bool(bool_meta) => bool_meta.gen_ser_accessor(variable_path),
+            Self::Unit(unit_meta) => unit_meta.gen_ser_accessor(variable_path),
         }
     }
 
@@ -29,8 +31,10 @@ impl PythonTypeGenerateable for ValueType {
             Self::String(string_meta) => string_meta.gen_des_accessor(field_accessor),
             Self::Range(range_meta) => range_meta.gen_des_accessor(field_accessor),
             Self::Map(map_meta) => map_meta.gen_des_accessor(field_accessor),
+            Self::Set(set_meta) => set_meta.gen_des_accessor(field_accessor),
             Self::Tuple(tuple_meta) => tuple_meta.gen_des_accessor(field_accessor),
             Self::Bool(bool_meta) => bool_meta.gen_des_accessor(field_accessor),
+            Self::Unit(unit_meta) => unit_meta.gen_des_accessor(field_accessor),
         }
     }
 
@@ -43,8 +47,10 @@ impl PythonTypeGenerateable for ValueType {
             Self::String(string_meta) => string_meta.gen_ty_check(variable_path),
             Self::Range(range_meta) => range_meta.gen_ty_check(variable_path),
             Self::Map(map_meta) => map_meta.gen_ty_check(variable_path),
+            Self::Set(set_meta) => set_meta.gen_ty_check(variable_path),
             Self::Tuple(tuple_meta) => tuple_meta.gen_ty_check(variable_path),
             Self::Bool(bool_meta) => bool_meta.gen_ty_check(variable_path),
+            Self::Unit(unit_meta) => unit_meta.gen_ty_check(variable_path),
         }
     }
 
@@ -57,8 +63,10 @@ impl PythonTypeGenerateable for ValueType {
             Self::String(string_meta) => string_meta.gen_typings(import_registry),
             Self::Range(range_meta) => range_meta.gen_typings(import_registry),
             Self::Map(map_meta) => map_meta.gen_typings(import_registry),
+            Self::Set(set_meta) => set_meta.gen_typings(import_registry),
             Self::Tuple(tuple_meta) => tuple_meta.gen_typings(import_registry),
             Self::Bool(bool_meta) => bool_meta.gen_typings(import_registry),
+            Self::Unit(unit_meta) => unit_meta.gen_typings(import_registry),
         }
     }
 }
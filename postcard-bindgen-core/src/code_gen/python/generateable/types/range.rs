@@ -15,7 +15,12 @@ impl PythonTypeGenerateable for RangeMeta {
         let start_path = variable_path
             .to_owned()
             .modify_push(VariableAccess::Field("start".to_owned()));
-        let stop_path = variable_path.modify_push(VariableAccess::Field("stop".to_owned()));
+        let mut stop_path = variable_path.modify_push(VariableAccess::Field("stop".to_owned()));
+        if self.inclusive {
+            // a `range` excludes its stop, the end of an inclusive range is the value before
+            let stop = quote!($stop_path).to_string().unwrap();
+            stop_path = VariablePath::new(format!("({stop} - 1)"));
+        }
 
         let start_accessor = self.bounds_type.gen_ser_accessor(start_path);
         let stop_accessor = self.bounds_type.gen_ser_accessor(stop_path);
@@ -27,7 +32,11 @@ impl PythonTypeGenerateable for RangeMeta {
 
     fn gen_des_accessor(&self, field_accessor: FieldAccessor) -> Tokens {
         let field_des = self.bounds_type.gen_des_accessor(FieldAccessor::None);
-        quote!($field_accessor range($(field_des.to_owned()), $field_des))
+        if self.inclusive {
+            quote!($field_accessor range($(field_des.to_owned()), $field_des + 1))
+        } else {
+            quote!($field_accessor range($(field_des.to_owned()), $field_des))
+        }
     }
 
     fn gen_ty_check(&self, variable_path: VariablePath) -> Tokens {
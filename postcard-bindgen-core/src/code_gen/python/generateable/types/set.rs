@@ -0,0 +1,45 @@
+use genco::quote;
+
+use crate::{
+    code_gen::{
+        python::{FieldAccessor, ImportRegistry, Tokens, VariablePath, PYTHON_OBJECT_VARIABLE},
+        utils::TokensIterExt,
+    },
+    type_info::SetMeta,
+};
+
+use super::PythonTypeGenerateable;
+
+// Sets are encoded like lists, the serializer indexes its input so the set is copied into one.
+impl PythonTypeGenerateable for SetMeta {
+    fn gen_ser_accessor(&self, variable_path: VariablePath) -> Tokens {
+        let inner_type_accessor = self.items_type.gen_ser_accessor(VariablePath::default());
+        quote!(s.serialize_array(lambda s, $PYTHON_OBJECT_VARIABLE: $inner_type_accessor, list($variable_path), None))
+    }
+
+    fn gen_des_accessor(&self, field_accessor: FieldAccessor) -> Tokens {
+        let inner_type_accessor = self.items_type.gen_des_accessor(FieldAccessor::Array);
+        quote!($(field_accessor)set(d.deserialize_array(lambda d: $inner_type_accessor, None)))
+    }
+
+    fn gen_ty_check(&self, variable_path: VariablePath) -> Tokens {
+        let assert_func_name = quote!(assert_$(variable_path.to_owned().into_string("_")));
+        let assert_item_type_check_func = quote! {
+            def $(&assert_func_name)($PYTHON_OBJECT_VARIABLE):
+                $(self.items_type.gen_ty_check(VariablePath::default()))
+        };
+        let item_ty_check = quote!([$assert_func_name($PYTHON_OBJECT_VARIABLE) for $PYTHON_OBJECT_VARIABLE in $(variable_path.to_owned())]);
+
+        [
+            quote!(assert isinstance($(variable_path.to_owned()), set), "{} is not a set".format($variable_path)),
+            assert_item_type_check_func,
+            item_ty_check,
+        ]
+        .into_iter()
+        .join_with_line_breaks()
+    }
+
+    fn gen_typings(&self, import_registry: &mut ImportRegistry) -> Tokens {
+        quote!(set[$(self.items_type.gen_typings(import_registry))])
+    }
+}
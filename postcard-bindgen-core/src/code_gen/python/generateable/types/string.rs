@@ -1,12 +1,19 @@
 use genco::quote;
 
 use crate::{
-    code_gen::python::{FieldAccessor, ImportRegistry, Tokens, VariablePath},
-    type_info::StringMeta,
+    code_gen::{
+        python::{FieldAccessor, ImportRegistry, Tokens, VariablePath},
+        utils::SEMVER_PATTERN,
+    },
+    type_info::{StringFormat, StringMeta},
 };
 
 use super::PythonTypeGenerateable;
 
+/// A scheme followed by anything without whitespace. Python has no URL parser which rejects
+/// invalid URLs, so only the scheme which makes it absolute is checked.
+const URL_PATTERN: &str = r"^[a-zA-Z][a-zA-Z0-9+.-]*:\S*$";
+
 impl PythonTypeGenerateable for StringMeta {
     fn gen_ser_accessor(&self, variable_path: VariablePath) -> Tokens {
         quote!(s.serialize_string($variable_path))
@@ -17,7 +24,19 @@ impl PythonTypeGenerateable for StringMeta {
     }
 
     fn gen_ty_check(&self, variable_path: VariablePath) -> Tokens {
-        quote!(assert isinstance($(variable_path.to_owned()), str), "{} is not a string".format($variable_path))
+        if self.char {
+            quote!(assert isinstance($(variable_path.to_owned()), str) and len($(variable_path.to_owned())) == 1, "{} is not a single character".format($variable_path))
+        } else if let Some(capacity) = self.capacity {
+            quote!(assert isinstance($(variable_path.to_owned()), str) and len($(variable_path.to_owned()).encode("utf-8")) <= $capacity, "{} is not a string of at most {} bytes".format($variable_path, $capacity))
+        } else if let Some(format) = self.format {
+            let (pattern, name) = match format {
+                StringFormat::Url => (URL_PATTERN, "a URL"),
+                StringFormat::SemVer => (SEMVER_PATTERN, "a semantic version"),
+            };
+            quote!(assert isinstance($(variable_path.to_owned()), str) and re.match($(format!("r\"{pattern}\"")), $(variable_path.to_owned())) is not None, $(format!("\"{{}} is not {name}\"")).format($variable_path))
+        } else {
+            quote!(assert isinstance($(variable_path.to_owned()), str), "{} is not a string".format($variable_path))
+        }
     }
 
     fn gen_typings(&self, _import_registry: &mut ImportRegistry) -> Tokens {
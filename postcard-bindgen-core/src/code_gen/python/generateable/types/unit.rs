@@ -0,0 +1,28 @@
+use genco::quote;
+
+use crate::{
+    code_gen::python::{FieldAccessor, ImportRegistry, Tokens, VariablePath},
+    type_info::UnitMeta,
+};
+
+use super::PythonTypeGenerateable;
+
+// `()` is encoded as nothing and represented by `None`. The serialization is an expression,
+// as it may be the body of a lambda.
+impl PythonTypeGenerateable for UnitMeta {
+    fn gen_ser_accessor(&self, _variable_path: VariablePath) -> Tokens {
+        quote!(None)
+    }
+
+    fn gen_des_accessor(&self, field_accessor: FieldAccessor) -> Tokens {
+        quote!($(field_accessor)None)
+    }
+
+    fn gen_ty_check(&self, variable_path: VariablePath) -> Tokens {
+        quote!(assert $(variable_path.to_owned()) is None, "{} is not None".format($variable_path))
+    }
+
+    fn gen_typings(&self, _import_registry: &mut ImportRegistry) -> Tokens {
+        quote!(None)
+    }
+}
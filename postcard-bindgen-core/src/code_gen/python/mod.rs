@@ -18,6 +18,8 @@ use crate::{
     code_gen::import_registry::ImportMode, path::PathBuf, registry::ContainerCollection, Exports,
 };
 
+pub use super::js::PointerWidth;
+
 use super::{
     import_registry::{ImportItem, Package},
     utils::{IfBranchedTemplate, TokensBranchedIterExt, TokensIterExt},
@@ -25,14 +27,12 @@ use super::{
 
 const PYTHON_OBJECT_VARIABLE: &str = "v";
 const PYTHON_LOGIC_AND: &str = "and";
-const PYTHON_LOGIC_OR: &str = "or";
 
 type Tokens = genco::lang::python::Tokens;
 
 type VariablePath = super::variable_path::VariablePath<Python>;
 type VariableAccess = super::variable_path::VariableAccess;
 type FieldAccessor<'a> = super::field_accessor::FieldAccessor<'a>;
-type AvailableCheck = super::available_check::AvailableCheck<Python>;
 type ImportRegistry = super::import_registry::ImportRegistry;
 type ExportFile = crate::ExportFile<Python>;
 type FunctionArg = super::function::FunctionArg<Python>;
@@ -53,6 +53,7 @@ pub struct GenerationSettings {
     des: bool,
     runtime_type_checks: bool,
     module_structure: bool,
+    pointer_width: PointerWidth,
 }
 
 impl GenerationSettings {
@@ -63,6 +64,7 @@ impl GenerationSettings {
             des: true,
             runtime_type_checks: true,
             module_structure: true,
+            pointer_width: PointerWidth::Bits64,
         }
     }
 
@@ -98,6 +100,15 @@ impl GenerationSettings {
         self.module_structure = enabled;
         self
     }
+
+    /// Sets the width of `isize` and `usize`, which has to match the target the data is
+    /// exchanged with. The runtime type checks reject values which don't fit into it.
+    ///
+    /// Defaults to [`PointerWidth::Bits64`].
+    pub fn pointer_width(mut self, pointer_width: PointerWidth) -> Self {
+        self.pointer_width = pointer_width;
+        self
+    }
 }
 
 impl Default for GenerationSettings {
@@ -107,6 +118,7 @@ impl Default for GenerationSettings {
             des: true,
             runtime_type_checks: false,
             module_structure: true,
+            pointer_width: PointerWidth::Bits64,
         }
     }
 }
@@ -119,9 +131,19 @@ pub fn generate(
     let generate_package_name = generate_package_name.to_case(Case::Snake);
     let gen_settings = gen_settings.borrow();
 
+    containers.assert_no_internally_tagged_enums("Python");
     if !gen_settings.module_structure {
         containers.flatten();
     }
+    containers.resolve_pointer_width(gen_settings.pointer_width.bytes());
+    // dataclass fields need identifiers, e.g. `sensor-id` becomes `sensor_id`
+    containers.rename_fields(|name| {
+        if is_identifier(&name) {
+            name
+        } else {
+            name.to_case(Case::Snake).into()
+        }
+    });
 
     let mut files = Vec::new();
 
@@ -141,6 +163,8 @@ pub fn generate(
         let type_checks = gen_type_checks(containers.all_containers());
 
         let type_checks = quote! {
+            import re
+
             from .util import *
             from .types import *
 
@@ -229,13 +253,20 @@ pub fn generate(
     Exports { files }
 }
 
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
 impl<I, F> TokensIterExt<Python, F> for I
 where
     I: Iterator<Item = F>,
     F: FormatInto<Python>,
 {
     const LOGICAL_AND: &'static str = PYTHON_LOGIC_AND;
-    const LOGICAL_OR: &'static str = PYTHON_LOGIC_OR;
 }
 
 pub(super) struct BranchedTemplate;
@@ -303,17 +334,6 @@ impl FormatInto<Python> for VariableAccess {
     }
 }
 
-impl FormatInto<Python> for AvailableCheck {
-    fn format_into(self, tokens: &mut Tokens) {
-        quote_in! { *tokens =>
-            $(match self {
-                AvailableCheck::Object(..) => (),
-                AvailableCheck::None => ()
-            })
-        }
-    }
-}
-
 impl FormatInto<Python> for ImportRegistry {
     fn format_into(self, tokens: &mut Tokens) {
         let (base_path, items) = self.into_items_sorted();
@@ -37,7 +37,7 @@ pub fn gen_serializer_code() -> Tokens {
 
             def serialize_number(self, n_bytes, signed, value):
                 if n_bytes == U8_BYTES:
-                    self.bytes.append(value)
+                    self.bytes.append(value & 0xFF if signed else value)
                 elif n_bytes in {U16_BYTES, U32_BYTES, U64_BYTES, U128_BYTES}:
                     value_b = int(value)
                     buffer = varint(n_bytes, zig_zag(n_bytes, value_b) if signed else value_b)
@@ -55,8 +55,9 @@ pub fn gen_serializer_code() -> Tokens {
                 self.push_n(b_buffer)
 
             def serialize_string(self, s):
-                self.push_n(varint(U32_BYTES, len(s)))
-                self.push_n([ord(c) for c in s])
+                encoded = s.encode("utf-8")
+                self.push_n(varint(U32_BYTES, len(encoded)))
+                self.push_n(encoded)
 
             def serialize_array(self, ser, array, length):
                 if length is None:
@@ -79,7 +80,7 @@ pub fn gen_ser_functions(bindings: impl Iterator<Item = Container>) -> Tokens {
 
 fn gen_ser_function_for_type(container: Container) -> impl FormatInto<Python> {
     let container_ident =
-        ContainerIdentifierBuilder::new(container.path.clone().into_buf(), container.name).build();
+        ContainerIdentifierBuilder::new(container.path.clone().into_buf(), &container.name).build();
     let body = container.r#type.gen_ser_body((&container).into());
 
     Function::new_untyped(
@@ -16,7 +16,7 @@ pub fn gen_type_checks(bindings: impl Iterator<Item = Container>) -> Tokens {
 
 fn gen_type_check(container: Container) -> Tokens {
     let container_ident =
-        ContainerIdentifierBuilder::new(container.path.clone().into_buf(), container.name).build();
+        ContainerIdentifierBuilder::new(container.path.clone().into_buf(), &container.name).build();
     let body = container.r#type.gen_ty_check_body((&container).into());
     quote! {
         def assert_$container_ident($PYTHON_OBJECT_VARIABLE):
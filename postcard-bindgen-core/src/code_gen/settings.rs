@@ -0,0 +1,61 @@
+pub use super::js::PointerWidth;
+
+/// Settings for bindings generation of the languages without further options, which are C,
+/// C++, Kotlin, Swift, Dart, C#, Go, Java and Lua.
+///
+/// This enables the possibility to enable or disable encoding and decoding. Less code will be
+/// generated if an option is off.
+///
+/// By default, only decoding is enabled. Encoding can be enabled by using [`GenerationSettings::serialization()`].
+/// Decoding can be disabled with [`GenerationSettings::deserialization()`].
+/// To enable all at once use [`GenerationSettings::enable_all()`].
+#[derive(Debug)]
+pub struct GenerationSettings {
+    pub(crate) ser: bool,
+    pub(crate) des: bool,
+    pub(crate) pointer_width: PointerWidth,
+}
+
+impl GenerationSettings {
+    /// Constructs [`GenerationSettings`] and enables all options at once.
+    pub fn enable_all() -> Self {
+        Self {
+            ser: true,
+            des: true,
+            pointer_width: PointerWidth::Bits64,
+        }
+    }
+
+    /// Enabling or disabling of the code which encodes values, e.g. the `serialize` functions.
+    pub fn serialization(mut self, enabled: bool) -> Self {
+        self.ser = enabled;
+        self
+    }
+
+    /// Enabling or disabling of the code which decodes values, e.g. the `deserialize`
+    /// functions.
+    pub fn deserialization(mut self, enabled: bool) -> Self {
+        self.des = enabled;
+        self
+    }
+
+    /// Sets the width of `isize` and `usize`, which has to match the target the data is
+    /// exchanged with. Languages with sized integers use the integer type of this width, the
+    /// others only differ in the range of values which is encoded.
+    ///
+    /// Defaults to [`PointerWidth::Bits64`].
+    pub fn pointer_width(mut self, pointer_width: PointerWidth) -> Self {
+        self.pointer_width = pointer_width;
+        self
+    }
+}
+
+impl Default for GenerationSettings {
+    fn default() -> Self {
+        Self {
+            ser: false,
+            des: true,
+            pointer_width: PointerWidth::Bits64,
+        }
+    }
+}
@@ -0,0 +1,498 @@
+mod runtime;
+
+use core::borrow::Borrow;
+use std::collections::BTreeSet;
+
+use convert_case::{Case, Casing};
+use genco::{lang::swift::Swift, quote, quote_in};
+
+use runtime::{gen_des_runtime, gen_runtime, gen_ser_runtime};
+
+use super::backend::{generate_with, LanguageBackend};
+use crate::{
+    registry::{
+        BindingType, Container, ContainerCollection, EnumType, EnumVariantType, StructField,
+        StructType, TupleStructType,
+    },
+    type_info::{ArrayMeta, NumberMeta, ValueType},
+    Exports,
+};
+
+pub use super::settings::{GenerationSettings, PointerWidth};
+
+type Tokens = genco::lang::swift::Tokens;
+type ExportFile = crate::ExportFile<Swift>;
+
+const SWIFT_KEYWORDS: &[&str] = &[
+    "Any",
+    "Self",
+    "as",
+    "associatedtype",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "continue",
+    "default",
+    "defer",
+    "deinit",
+    "do",
+    "else",
+    "enum",
+    "extension",
+    "fallthrough",
+    "false",
+    "fileprivate",
+    "for",
+    "func",
+    "guard",
+    "if",
+    "import",
+    "in",
+    "init",
+    "inout",
+    "internal",
+    "is",
+    "let",
+    "nil",
+    "open",
+    "operator",
+    "private",
+    "protocol",
+    "public",
+    "repeat",
+    "rethrows",
+    "return",
+    "self",
+    "static",
+    "struct",
+    "subscript",
+    "super",
+    "switch",
+    "throw",
+    "throws",
+    "true",
+    "try",
+    "typealias",
+    "var",
+    "where",
+    "while",
+];
+
+/// Generates a Swift source file, exported as `source`.
+///
+/// Structs become structs with camel case properties and enums enums with associated
+/// values, whose cases are named like the rust variants, matching the `tag` of the
+/// JavaScript bindings. Encoding and decoding is done with
+///
+/// ```swift
+/// func serialize<T: PostcardEncodable>(_ value: T) -> Data
+/// func deserialize<T: PostcardDecodable>(_ type: T.Type, from data: Data) throws -> T
+/// ```
+///
+/// where decoding throws a `PostcardError` if the input is invalid.
+///
+/// All types share one module. Generating panics for 128 bit integers, which have no
+/// standard Swift type before Swift 6, and for tuples with less than two items.
+pub fn generate(
+    containers: ContainerCollection,
+    gen_settings: impl Borrow<GenerationSettings>,
+) -> Exports<Swift> {
+    let backend = SwiftBackend {
+        gen_settings: gen_settings.borrow(),
+        types: Vec::new(),
+        encode_extensions: Tokens::new(),
+        decode_extensions: Tokens::new(),
+    };
+    generate_with(backend, containers)
+}
+
+struct SwiftBackend<'a> {
+    gen_settings: &'a GenerationSettings,
+    types: Vec<Tokens>,
+    encode_extensions: Tokens,
+    decode_extensions: Tokens,
+}
+
+impl SwiftBackend<'_> {
+    fn push_type(&mut self, container: &Container) {
+        self.types.push(gen_type(container));
+        if self.gen_settings.ser {
+            self.encode_extensions.line();
+            self.encode_extensions
+                .append(gen_encode_extension(container));
+        }
+        if self.gen_settings.des {
+            self.decode_extensions.line();
+            self.decode_extensions
+                .append(gen_decode_extension(container));
+        }
+    }
+}
+
+impl LanguageBackend for SwiftBackend<'_> {
+    type Lang = Swift;
+
+    fn prepare(&mut self, containers: &mut ContainerCollection) {
+        containers.assert_no_internally_tagged_enums("Swift");
+        containers.flatten();
+        containers.resolve_pointer_width(self.gen_settings.pointer_width.bytes());
+        containers.sets_as_sequences();
+        containers.remove_unit_fields();
+
+        let mut names = BTreeSet::new();
+        for container in containers.all_containers() {
+            assert!(
+                names.insert(container.name.clone()),
+                "the Swift bindings hold all types in one module, but `{}` is defined twice",
+                container.name
+            );
+        }
+    }
+
+    fn visit_struct(&mut self, container: &Container, _: &StructType) {
+        self.push_type(container);
+    }
+
+    fn visit_tuple_struct(&mut self, container: &Container, _: &TupleStructType) {
+        self.push_type(container);
+    }
+
+    fn visit_enum(&mut self, container: &Container, _: &EnumType) {
+        self.push_type(container);
+    }
+
+    fn visit_unit_struct(&mut self, container: &Container) {
+        self.push_type(container);
+    }
+
+    fn finish(self) -> Exports<Swift> {
+        let mut source = quote! {
+            import Foundation
+
+            $(gen_runtime())
+
+            $(for ty in self.types join ($['\n']) => $ty)
+        };
+
+        if self.gen_settings.ser {
+            source.line();
+            source.append(gen_ser_runtime());
+            source.append(self.encode_extensions);
+        }
+
+        if self.gen_settings.des {
+            source.line();
+            source.append(gen_des_runtime());
+            source.append(self.decode_extensions);
+        }
+
+        Exports {
+            files: vec![ExportFile {
+                content_type: "source".to_owned(),
+                content: source,
+            }],
+        }
+    }
+}
+
+/// Fields of a struct or an enum variant.
+enum Fields<'a> {
+    Named(&'a [StructField]),
+    Unnamed(&'a [ValueType]),
+    Unit,
+}
+
+impl<'a> Fields<'a> {
+    fn members(&self) -> Vec<(String, &'a ValueType)> {
+        match self {
+            Fields::Named(fields) => fields
+                .iter()
+                .filter(|field| !field.skip)
+                .map(|field| (swift_ident(&field.name.to_case(Case::Camel)), &field.v_type))
+                .collect(),
+            Fields::Unnamed(fields) => fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| (format!("_{index}"), field))
+                .collect(),
+            Fields::Unit => Vec::new(),
+        }
+    }
+}
+
+impl<'a> From<&'a EnumVariantType> for Fields<'a> {
+    fn from(variant_type: &'a EnumVariantType) -> Self {
+        match variant_type {
+            EnumVariantType::Empty => Fields::Unit,
+            EnumVariantType::Tuple(fields) => Fields::Unnamed(fields),
+            EnumVariantType::NewType(fields) => Fields::Named(fields),
+        }
+    }
+}
+
+fn struct_fields(container: &Container) -> Option<Fields<'_>> {
+    match &container.r#type {
+        BindingType::Struct(struct_type) => Some(Fields::Named(&struct_type.fields)),
+        BindingType::TupleStruct(tuple_struct) => Some(Fields::Unnamed(&tuple_struct.fields)),
+        BindingType::UnitStruct(_) => Some(Fields::Unit),
+        BindingType::Enum(_) => None,
+    }
+}
+
+fn gen_type(container: &Container) -> Tokens {
+    let name = &container.name;
+    match &container.r#type {
+        BindingType::Enum(enum_type) => {
+            let cases = enum_type.variants.iter().map(|variant| {
+                let fields = Fields::from(&variant.inner_type);
+                let members = fields.members();
+                let case_name = swift_ident(&variant.name);
+                match fields {
+                    Fields::Unit => quote!(case $case_name),
+                    Fields::Unnamed(_) => quote!(case $case_name($(for (_, v_type) in members join (, ) => $(swift_type(v_type))))),
+                    Fields::Named(_) => quote!(case $case_name($(for (member, v_type) in members join (, ) => $member: $(swift_type(v_type))))),
+                }
+            });
+            quote! {
+                enum $name {
+                    $(for case in cases => $case$['\r'])
+                }
+            }
+        }
+        _ => {
+            let members = struct_fields(container).unwrap().members();
+            if members.is_empty() {
+                return quote!(struct $name {});
+            }
+            quote! {
+                struct $name {
+                    $(for (member, v_type) in members => var $member: $(swift_type(v_type))$['\r'])
+                }
+            }
+        }
+    }
+}
+
+fn gen_encode_extension(container: &Container) -> Tokens {
+    let name = &container.name;
+    let mut body = Tokens::new();
+    match &container.r#type {
+        BindingType::Enum(enum_type) => {
+            let mut cases = Tokens::new();
+            for variant in enum_type.variants.iter() {
+                let members = Fields::from(&variant.inner_type).members();
+                let bindings = (0..members.len())
+                    .map(|index| format!("v{index}"))
+                    .collect::<Vec<_>>();
+                let case_name = swift_ident(&variant.name);
+                let pattern = if members.is_empty() {
+                    quote!(case .$case_name:)
+                } else {
+                    quote!(case let .$case_name($(for binding in bindings.iter() join (, ) => $binding)):)
+                };
+                quote_in! { cases =>
+                    $pattern
+                        w.u32($(variant.index))
+                        $(for ((_, v_type), binding) in members.iter().zip(bindings.iter()) => $(gen_encode(v_type, quote!($binding), 0))$['\r'])
+                }
+                cases.push();
+            }
+            quote_in! { body =>
+                switch self {
+                $cases
+                }
+            }
+        }
+        _ => {
+            for (member, v_type) in struct_fields(container).unwrap().members() {
+                body.append(gen_encode(v_type, quote!(self.$member), 0));
+                body.push();
+            }
+        }
+    }
+
+    quote! {
+        extension $name: PostcardEncodable {
+            func encode(to w: PostcardWriter) {
+                $body
+            }
+        }
+    }
+}
+
+/// Encodes `value`, closures are nested with `depth`.
+fn gen_encode(v_type: &ValueType, value: Tokens, depth: usize) -> Tokens {
+    let item = format!("x{depth}");
+    match v_type {
+        ValueType::Object(_) => quote!($value.encode(to: w)),
+        ValueType::Optional(meta) => {
+            quote!(w.option($value) { $(&item) in $(gen_encode(&meta.inner, quote!($(&item)), depth + 1)) })
+        }
+        ValueType::Array(ArrayMeta {
+            items_type,
+            length: Some(length),
+            ..
+        }) => {
+            quote!(w.array($value, count: $(*length)) { $(&item) in $(gen_encode(items_type, quote!($(&item)), depth + 1)) })
+        }
+        ValueType::Array(ArrayMeta { items_type, .. }) => {
+            quote!(w.list($value) { $(&item) in $(gen_encode(items_type, quote!($(&item)), depth + 1)) })
+        }
+        ValueType::Map(meta) => {
+            let key = format!("k{depth}");
+            quote!(w.map($value, { $(&key) in $(gen_encode(&meta.key_type, quote!($(&key)), depth + 1)) }, { $(&item) in $(gen_encode(&meta.value_type, quote!($(&item)), depth + 1)) }))
+        }
+        ValueType::Range(meta) => {
+            quote!(w.range($value) { $(&item) in $(gen_encode(&meta.bounds_type, quote!($(&item)), depth + 1)) })
+        }
+        ValueType::Tuple(meta) => {
+            let items = meta
+                .items_types
+                .iter()
+                .enumerate()
+                .map(|(index, item_type)| {
+                    gen_encode(item_type, quote!($(&item).$index), depth + 1)
+                });
+            quote!(w.tuple($value) { $(&item) in $(for item in items join (; ) => $item) })
+        }
+        _ => quote!(w.$(primitive_fn(v_type))($value)),
+    }
+}
+
+fn gen_decode_extension(container: &Container) -> Tokens {
+    let name = &container.name;
+    let mut body = Tokens::new();
+    match &container.r#type {
+        BindingType::Enum(enum_type) => {
+            let mut cases = Tokens::new();
+            for variant in enum_type.variants.iter() {
+                let fields = Fields::from(&variant.inner_type);
+                let members = fields.members();
+                let case_name = swift_ident(&variant.name);
+                let value = match fields {
+                    Fields::Unit => quote!(.$case_name),
+                    Fields::Unnamed(_) => {
+                        quote!(.$case_name($(for (_, v_type) in members join (, ) => $(gen_decode(v_type)))))
+                    }
+                    Fields::Named(_) => {
+                        quote!(.$case_name($(for (member, v_type) in members join (, ) => $member: $(gen_decode(v_type)))))
+                    }
+                };
+                quote_in! { cases =>
+                    case $(variant.index):
+                        self = $value
+                }
+                cases.push();
+            }
+            quote_in! { body =>
+                let tag = try r.u32()
+                switch tag {
+                $cases
+                default:
+                    throw PostcardError.invalidValue($(format!("\"unknown variant \\(tag) of {name}\"")))
+                }
+            }
+        }
+        _ => {
+            for (member, v_type) in struct_fields(container).unwrap().members() {
+                quote_in!(body => self.$member = $(gen_decode(v_type)));
+                body.push();
+            }
+        }
+    }
+
+    quote! {
+        extension $name: PostcardDecodable {
+            init(from r: PostcardReader) throws {
+                $body
+            }
+        }
+    }
+}
+
+fn gen_decode(v_type: &ValueType) -> Tokens {
+    match v_type {
+        // the type is inferred, as enum cases may shadow it
+        ValueType::Object(_) => quote!(try r.decode()),
+        ValueType::Optional(meta) => quote!(try r.option { $(gen_decode(&meta.inner)) }),
+        ValueType::Array(ArrayMeta {
+            items_type,
+            length: Some(length),
+            ..
+        }) => quote!(try r.array(count: $(*length)) { $(gen_decode(items_type)) }),
+        ValueType::Array(ArrayMeta { items_type, .. }) => {
+            quote!(try r.list { $(gen_decode(items_type)) })
+        }
+        ValueType::Map(meta) => {
+            quote!(try r.map({ $(gen_decode(&meta.key_type)) }, { $(gen_decode(&meta.value_type)) }))
+        }
+        ValueType::Range(meta) => quote!(try r.range { $(gen_decode(&meta.bounds_type)) }),
+        ValueType::Tuple(meta) => {
+            quote!(($(for item in meta.items_types.iter() join (, ) => $(gen_decode(item)))))
+        }
+        _ => quote!(try r.$(primitive_fn(v_type))()),
+    }
+}
+
+/// Name of the `PostcardWriter` and `PostcardReader` function of a primitive.
+fn primitive_fn(v_type: &ValueType) -> String {
+    match v_type {
+        ValueType::Number(NumberMeta::Integer { bytes, signed, .. }) => {
+            format!("{}{}", if *signed { "i" } else { "u" }, bytes * 8)
+        }
+        ValueType::Number(NumberMeta::FloatingPoint { bytes }) => format!("f{}", bytes * 8),
+        ValueType::Bool(_) => "bool".to_owned(),
+        ValueType::String(_) => "string".to_owned(),
+        _ => unreachable!("not a primitive type"),
+    }
+}
+
+fn swift_type(v_type: &ValueType) -> String {
+    match v_type {
+        ValueType::Number(NumberMeta::Integer { bytes, signed, .. }) => {
+            assert_ne!(
+                *bytes, 16,
+                "128 bit integers are not supported by the Swift bindings"
+            );
+            format!("{}Int{}", if *signed { "" } else { "U" }, bytes * 8)
+        }
+        ValueType::Number(NumberMeta::FloatingPoint { bytes: 4 }) => "Float".to_owned(),
+        ValueType::Number(NumberMeta::FloatingPoint { .. }) => "Double".to_owned(),
+        ValueType::Bool(_) => "Bool".to_owned(),
+        ValueType::String(_) => "String".to_owned(),
+        ValueType::Object(meta) => meta.name.to_string(),
+        ValueType::Optional(meta) => format!("{}?", swift_type(&meta.inner)),
+        ValueType::Range(meta) => format!("PostcardRange<{}>", swift_type(&meta.bounds_type)),
+        ValueType::Array(meta) => format!("[{}]", swift_type(&meta.items_type)),
+        ValueType::Set(_) => unreachable!("sets are replaced by sequences"),
+        ValueType::Unit(_) => panic!("`()` is only supported as field by the Swift bindings"),
+        ValueType::Map(meta) => format!(
+            "[{}: {}]",
+            swift_type(&meta.key_type),
+            swift_type(&meta.value_type)
+        ),
+        ValueType::Tuple(meta) => {
+            assert!(
+                meta.items_types.len() >= 2,
+                "tuples with less than two items are not supported by the Swift bindings"
+            );
+            format!(
+                "({})",
+                meta.items_types
+                    .iter()
+                    .map(swift_type)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }
+    }
+}
+
+fn swift_ident(name: &str) -> String {
+    if SWIFT_KEYWORDS.contains(&name) {
+        format!("`{name}`")
+    } else {
+        name.to_owned()
+    }
+}
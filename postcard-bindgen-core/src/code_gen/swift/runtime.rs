@@ -0,0 +1,292 @@
+use genco::quote;
+
+use super::Tokens;
+
+/// Types shared by the encode and decode functions.
+pub fn gen_runtime() -> Tokens {
+    quote!($(RUNTIME))
+}
+
+pub fn gen_ser_runtime() -> Tokens {
+    quote!($(SER_RUNTIME))
+}
+
+pub fn gen_des_runtime() -> Tokens {
+    quote!($(DES_RUNTIME))
+}
+
+const RUNTIME: &str = r#"enum PostcardError: Error {
+    case unexpectedEnd
+    case invalidValue(String)
+    case trailingBytes(Int)
+}
+
+/// rust `Range` and `RangeInclusive`
+struct PostcardRange<T> {
+    var start: T
+    var end: T
+}"#;
+
+const SER_RUNTIME: &str = r#"protocol PostcardEncodable {
+    func encode(to w: PostcardWriter)
+}
+
+func serialize<T: PostcardEncodable>(_ value: T) -> Data {
+    let w = PostcardWriter()
+    value.encode(to: w)
+    return w.data
+}
+
+final class PostcardWriter {
+    private(set) var data = Data()
+
+    func push(_ byte: UInt8) {
+        data.append(byte)
+    }
+
+    func varint(_ value: UInt64) {
+        var v = value
+        while v >= 0x80 {
+            push(UInt8(truncatingIfNeeded: v) | 0x80)
+            v >>= 7
+        }
+        push(UInt8(v))
+    }
+
+    func zigzag(_ value: Int64) {
+        varint(UInt64(bitPattern: (value << 1) ^ (value >> 63)))
+    }
+
+    func len(_ n: Int) {
+        varint(UInt64(n))
+    }
+
+    func bool(_ v: Bool) { push(v ? 1 : 0) }
+    func u8(_ v: UInt8) { push(v) }
+    func i8(_ v: Int8) { push(UInt8(bitPattern: v)) }
+    func u16(_ v: UInt16) { varint(UInt64(v)) }
+    func u32(_ v: UInt32) { varint(UInt64(v)) }
+    func u64(_ v: UInt64) { varint(v) }
+    func i16(_ v: Int16) { zigzag(Int64(v)) }
+    func i32(_ v: Int32) { zigzag(Int64(v)) }
+    func i64(_ v: Int64) { zigzag(v) }
+
+    func f32(_ v: Float) {
+        let bits = v.bitPattern
+        for i in 0..<4 {
+            push(UInt8(truncatingIfNeeded: bits >> (8 * i)))
+        }
+    }
+
+    func f64(_ v: Double) {
+        let bits = v.bitPattern
+        for i in 0..<8 {
+            push(UInt8(truncatingIfNeeded: bits >> (8 * i)))
+        }
+    }
+
+    func string(_ v: String) {
+        len(v.utf8.count)
+        data.append(contentsOf: v.utf8)
+    }
+
+    func option<T>(_ v: T?, _ item: (T) -> Void) {
+        if let v = v {
+            push(1)
+            item(v)
+        } else {
+            push(0)
+        }
+    }
+
+    func list<T>(_ v: [T], _ item: (T) -> Void) {
+        len(v.count)
+        v.forEach(item)
+    }
+
+    func array<T>(_ v: [T], count: Int, _ item: (T) -> Void) {
+        precondition(v.count == count, "expected \(count) items, got \(v.count)")
+        v.forEach(item)
+    }
+
+    func map<K, V>(_ v: [K: V], _ key: (K) -> Void, _ value: (V) -> Void) {
+        len(v.count)
+        for (k, x) in v {
+            key(k)
+            value(x)
+        }
+    }
+
+    func range<T>(_ v: PostcardRange<T>, _ item: (T) -> Void) {
+        item(v.start)
+        item(v.end)
+    }
+
+    func tuple<T>(_ v: T, _ items: (T) -> Void) {
+        items(v)
+    }
+}"#;
+
+const DES_RUNTIME: &str = r#"protocol PostcardDecodable {
+    init(from r: PostcardReader) throws
+}
+
+func deserialize<T: PostcardDecodable>(_ type: T.Type, from data: Data) throws -> T {
+    let r = PostcardReader(data)
+    let value = try T(from: r)
+    try r.finish()
+    return value
+}
+
+final class PostcardReader {
+    private let bytes: [UInt8]
+    private var index = 0
+
+    init(_ data: Data) {
+        bytes = [UInt8](data)
+    }
+
+    func pop() throws -> UInt8 {
+        guard index < bytes.count else {
+            throw PostcardError.unexpectedEnd
+        }
+        index += 1
+        return bytes[index - 1]
+    }
+
+    /// rejects varints longer than `maxBytes` and values exceeding 64 bits
+    func varint(maxBytes: Int) throws -> UInt64 {
+        var out: UInt64 = 0
+        for i in 0..<maxBytes {
+            let byte = try pop()
+            if i == 9 && byte > 1 {
+                throw PostcardError.invalidValue("varint exceeds 64 bits")
+            }
+            out |= UInt64(byte & 0x7F) << (7 * i)
+            if byte & 0x80 == 0 {
+                return out
+            }
+        }
+        throw PostcardError.invalidValue("varint exceeds \(maxBytes) bytes")
+    }
+
+    func zigzag(maxBytes: Int) throws -> Int64 {
+        let raw = try varint(maxBytes: maxBytes)
+        return Int64(bitPattern: raw >> 1) ^ -Int64(raw & 1)
+    }
+
+    private func unsigned<T: FixedWidthInteger>(maxBytes: Int) throws -> T {
+        let v = try varint(maxBytes: maxBytes)
+        guard let out = T(exactly: v) else {
+            throw PostcardError.invalidValue("\(v) is out of range of \(T.self)")
+        }
+        return out
+    }
+
+    private func signed<T: FixedWidthInteger>(maxBytes: Int) throws -> T {
+        let v = try zigzag(maxBytes: maxBytes)
+        guard let out = T(exactly: v) else {
+            throw PostcardError.invalidValue("\(v) is out of range of \(T.self)")
+        }
+        return out
+    }
+
+    func len() throws -> Int {
+        try unsigned(maxBytes: 10)
+    }
+
+    func bool() throws -> Bool {
+        switch try pop() {
+        case 0:
+            return false
+        case 1:
+            return true
+        default:
+            throw PostcardError.invalidValue("invalid bool")
+        }
+    }
+
+    func u8() throws -> UInt8 { try pop() }
+    func i8() throws -> Int8 { Int8(bitPattern: try pop()) }
+    func u16() throws -> UInt16 { try unsigned(maxBytes: 3) }
+    func u32() throws -> UInt32 { try unsigned(maxBytes: 5) }
+    func u64() throws -> UInt64 { try varint(maxBytes: 10) }
+    func i16() throws -> Int16 { try signed(maxBytes: 3) }
+    func i32() throws -> Int32 { try signed(maxBytes: 5) }
+    func i64() throws -> Int64 { try zigzag(maxBytes: 10) }
+
+    func f32() throws -> Float {
+        var bits: UInt32 = 0
+        for i in 0..<4 {
+            bits |= UInt32(try pop()) << (8 * i)
+        }
+        return Float(bitPattern: bits)
+    }
+
+    func f64() throws -> Double {
+        var bits: UInt64 = 0
+        for i in 0..<8 {
+            bits |= UInt64(try pop()) << (8 * i)
+        }
+        return Double(bitPattern: bits)
+    }
+
+    func string() throws -> String {
+        let n = try len()
+        guard bytes.count - index >= n else {
+            throw PostcardError.unexpectedEnd
+        }
+        let utf8 = bytes[index..<index + n]
+        index += n
+        guard let v = String(bytes: utf8, encoding: .utf8) else {
+            throw PostcardError.invalidValue("invalid utf-8")
+        }
+        return v
+    }
+
+    func option<T>(_ item: () throws -> T) throws -> T? {
+        if try bool() {
+            return try item()
+        }
+        return nil
+    }
+
+    func list<T>(_ item: () throws -> T) throws -> [T] {
+        let n = try len()
+        var out = [T]()
+        out.reserveCapacity(min(n, bytes.count - index))
+        for _ in 0..<n {
+            out.append(try item())
+        }
+        return out
+    }
+
+    func array<T>(count: Int, _ item: () throws -> T) throws -> [T] {
+        try (0..<count).map { _ in try item() }
+    }
+
+    func map<K: Hashable, V>(_ key: () throws -> K, _ value: () throws -> V) throws -> [K: V] {
+        let n = try len()
+        var out = [K: V]()
+        for _ in 0..<n {
+            let k = try key()
+            out[k] = try value()
+        }
+        return out
+    }
+
+    func decode<T: PostcardDecodable>() throws -> T {
+        try T(from: self)
+    }
+
+    func range<T>(_ item: () throws -> T) throws -> PostcardRange<T> {
+        let start = try item()
+        return PostcardRange(start: start, end: try item())
+    }
+
+    func finish() throws {
+        if index != bytes.count {
+            throw PostcardError.trailingBytes(bytes.count - index)
+        }
+    }
+}"#;
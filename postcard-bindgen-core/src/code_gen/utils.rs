@@ -14,6 +14,9 @@ use crate::{
     type_info::ObjectMeta,
 };
 
+/// Regular expression of a semantic version, from <https://semver.org>.
+pub(super) const SEMVER_PATTERN: &str = r"^(0|[1-9]\d*)\.(0|[1-9]\d*)\.(0|[1-9]\d*)(?:-((?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*)(?:\.(?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*))*))?(?:\+([0-9a-zA-Z-]+(?:\.[0-9a-zA-Z-]+)*))?$";
+
 pub fn break_long_logical_lines<L: Lang>(tokens: impl FormatInto<L>) -> Tokens<L> {
     let tokens = quote!($tokens);
     let mut result = Tokens::new();
@@ -32,17 +35,39 @@ pub fn break_long_logical_lines<L: Lang>(tokens: impl FormatInto<L>) -> Tokens<L
         }
     }
 
-    result.unindent();
+    if intend {
+        result.unindent();
+    }
     result
 }
 
+/// Casing of the type names in the identifiers of the generated helper functions, e.g. in
+/// `serialize_MY_STRUCT` or `is_myStruct`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IdentifierCase {
+    /// `MY_STRUCT`
+    #[default]
+    ScreamingSnake,
+    /// `my_struct`
+    Snake,
+    /// `myStruct`
+    Camel,
+    /// `MyStruct`
+    Pascal,
+}
+
 pub trait StrExt {
-    fn to_obj_identifier(&self) -> String;
+    fn to_obj_identifier(&self, ident_case: IdentifierCase) -> String;
 }
 
 impl StrExt for &str {
-    fn to_obj_identifier(&self) -> String {
-        self.to_case(Case::Snake).to_uppercase()
+    fn to_obj_identifier(&self, ident_case: IdentifierCase) -> String {
+        match ident_case {
+            IdentifierCase::ScreamingSnake => self.to_case(Case::Snake).to_uppercase(),
+            IdentifierCase::Snake => self.to_case(Case::Snake),
+            IdentifierCase::Camel => self.to_case(Case::Camel),
+            IdentifierCase::Pascal => self.to_case(Case::Pascal),
+        }
     }
 }
 
@@ -58,7 +83,6 @@ where
     F: FormatInto<L>,
     Self: Sized,
 {
-    const LOGICAL_OR: &'static str;
     const LOGICAL_AND: &'static str;
 
     fn join_with_line_breaks(mut self) -> Tokens<L> {
@@ -142,6 +166,10 @@ where
         quote!($(for part in self join ( : ) => $part))
     }
 
+    fn join_with_plus(self) -> Tokens<L> {
+        quote!($(for part in self join ( + ) => $part))
+    }
+
     fn join_with_vertical_line(self) -> Tokens<L> {
         quote!($(for part in self join ( | ) => $part))
     }
@@ -149,10 +177,6 @@ where
     fn join_logic_and(self) -> Tokens<L> {
         quote!($(for part in self join ( $(Self::LOGICAL_AND) ) => $part))
     }
-
-    fn join_logic_or(self) -> Tokens<L> {
-        quote!($(for part in self join ( $(Self::LOGICAL_OR) ) => $part))
-    }
 }
 
 pub(super) trait IfBranchedTemplate<L: Lang> {
@@ -208,31 +232,41 @@ where
 pub struct ContainerIdentifierBuilder<'a> {
     path: PathBuf<'a>,
     name: &'a str,
+    ident_case: IdentifierCase,
 }
 
 impl<'a> ContainerIdentifierBuilder<'a> {
     pub fn new(path: PathBuf<'a>, name: &'a str) -> Self {
-        Self { path, name }
+        Self {
+            path,
+            name,
+            ident_case: IdentifierCase::default(),
+        }
+    }
+
+    pub fn ident_case(mut self, ident_case: IdentifierCase) -> Self {
+        self.ident_case = ident_case;
+        self
     }
 
     pub fn build(mut self) -> String {
         // We will skip the first part of the path, as it is the crate name.
         self.path.pop_front();
-        self.path.push(self.name.to_obj_identifier());
+        self.path.push(self.name.to_obj_identifier(self.ident_case));
 
         self.path.into_path("_").to_string()
     }
 }
 
-impl From<&Container> for ContainerIdentifierBuilder<'_> {
-    fn from(container: &Container) -> Self {
-        Self::new(container.path.clone().into_buf(), container.name)
+impl<'a> From<&'a Container> for ContainerIdentifierBuilder<'a> {
+    fn from(container: &'a Container) -> Self {
+        Self::new(container.path.clone().into_buf(), &container.name)
     }
 }
 
-impl From<&ObjectMeta> for ContainerIdentifierBuilder<'_> {
-    fn from(meta: &ObjectMeta) -> Self {
-        Self::new(meta.path.clone().into_buf(), meta.name)
+impl<'a> From<&'a ObjectMeta> for ContainerIdentifierBuilder<'a> {
+    fn from(meta: &'a ObjectMeta) -> Self {
+        Self::new(meta.path.clone().into_buf(), &meta.name)
     }
 }
 
@@ -261,15 +295,15 @@ impl<'a> From<&'a ContainerInfo<'a>> for ContainerFullQualifiedTypeBuilder<'a> {
     }
 }
 
-impl From<&Container> for ContainerFullQualifiedTypeBuilder<'_> {
-    fn from(container: &Container) -> Self {
-        Self::new(container.path.clone().into_buf(), container.name)
+impl<'a> From<&'a Container> for ContainerFullQualifiedTypeBuilder<'a> {
+    fn from(container: &'a Container) -> Self {
+        Self::new(container.path.clone().into_buf(), &container.name)
     }
 }
 
-impl From<&ObjectMeta> for ContainerFullQualifiedTypeBuilder<'_> {
-    fn from(meta: &ObjectMeta) -> Self {
-        Self::new(meta.path.clone().into_buf(), meta.name)
+impl<'a> From<&'a ObjectMeta> for ContainerFullQualifiedTypeBuilder<'a> {
+    fn from(meta: &'a ObjectMeta) -> Self {
+        Self::new(meta.path.clone().into_buf(), &meta.name)
     }
 }
 
@@ -308,6 +342,33 @@ mod test {
         assert_eq!(builder.build(), "submodule_TEST".to_string());
     }
 
+    #[test]
+    fn test_container_identifier_builder_case() {
+        let path = || PathBuf::from_iter(["crate".into(), "submodule".into()]);
+
+        let identifiers = [
+            IdentifierCase::ScreamingSnake,
+            IdentifierCase::Snake,
+            IdentifierCase::Camel,
+            IdentifierCase::Pascal,
+        ]
+        .map(|ident_case| {
+            ContainerIdentifierBuilder::new(path(), "MyType")
+                .ident_case(ident_case)
+                .build()
+        });
+
+        assert_eq!(
+            identifiers,
+            [
+                "submodule_MY_TYPE",
+                "submodule_my_type",
+                "submodule_myType",
+                "submodule_MyType"
+            ]
+        );
+    }
+
     #[test]
     fn test_container_full_qualified_type_builder() {
         let container = ContainerInfo {
@@ -25,16 +25,6 @@ impl<L> VariablePath<L> {
         self
     }
 
-    pub fn pop(&mut self) -> (&mut Self, Option<VariableAccess>) {
-        let popped = self.parts.pop();
-        (self, popped)
-    }
-
-    pub fn modify_pop(mut self) -> (Self, Option<VariableAccess>) {
-        let (_, popped) = self.pop();
-        (self, popped)
-    }
-
     pub fn into_string(self, joined_by: &str) -> String {
         self.start_variable
             + joined_by
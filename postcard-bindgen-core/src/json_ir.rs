@@ -0,0 +1,805 @@
+//! Language neutral description of the registered types as JSON.
+//!
+//! The document has the format version and the containers in registration order:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "containers": [
+//!     {
+//!       "name": "Point",
+//!       "path": ["my_crate", "geometry"],
+//!       "kind": "struct",
+//!       "fields": [{ "name": "x", "type": { "kind": "integer", ... }, ... }]
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! The `kind` of a container is `struct` with named `fields`, `tuple_struct` with a list of
+//! field types, `unit_struct` or `enum` with `variants`, which have a `name`, the encoded
+//! `index` and a `kind` of `unit`, `tuple` or `struct` like the containers, and a `tag`, the
+//! field of `#[serde(tag = "...")]` or `null` for externally tagged enums. Named fields
+//! also have `skip`, `default`, `codec` and `alias`, which are `null` if not set, and
+//! `has_default`, whether the field is left out of encodings which end before it.
+//!
+//! The `kind` of a type is one of
+//! - `integer` with `bytes`, `signed`, `pointer_sized` and `non_zero`
+//! - `float` with `bytes`
+//! - `bool`, `unit`, `string` with `capacity` and `format` and `char`
+//! - `array` with `items`, `length`, which is `null` for vectors and slices, `capacity` and
+//!   `format`
+//! - `optional` with `inner`
+//! - `range` with `bounds` and `inclusive`, which is `true` for a `RangeInclusive`
+//! - `map` with `key` and `value`
+//! - `set` with `items`
+//! - `tuple` with `items`
+//! - `object` with the `name` and `path` of the referenced container
+//!
+//! `capacity` is the maximum length of heapless strings and vectors, `null` for others. The
+//! `format` of a string is `url`, `semver` or `null`, the one of an array `uuid` or `null`.
+//!
+//! Fields are only added within a version, so readers should ignore unknown fields. Other
+//! changes increase [`VERSION`].
+
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::{Display, Write};
+use std::io;
+
+use crate::{
+    path::Path,
+    registry::{
+        BindingType, BindingsRegistry, Container, ContainerCollection, EnumType, EnumVariant,
+        EnumVariantType, StructField, StructType, TupleStructType, UnitStructType,
+    },
+    type_info::{
+        ArrayFormat, ArrayMeta, BoolMeta, MapMeta, NumberMeta, ObjectMeta, OptionalMeta, RangeMeta,
+        SetMeta, StringFormat, StringMeta, TupleMeta, UnitMeta, ValueType,
+    },
+};
+
+/// Version of the JSON format, see the [module docs](self).
+pub const VERSION: u32 = 1;
+
+/// Describes the containers as JSON document.
+pub(crate) fn containers_to_json(containers: impl Iterator<Item = Container>) -> String {
+    let document = Json::object(vec![
+        ("version", Json::Number(VERSION as usize)),
+        (
+            "containers",
+            Json::Array(containers.map(|c| container_json(&c)).collect()),
+        ),
+    ]);
+
+    let mut out = String::new();
+    document.write(&mut out, 0);
+    out.push('\n');
+    out
+}
+
+/// Reads the containers from a JSON document written by [`ContainerCollection::to_json`].
+///
+/// Fails with [`io::ErrorKind::InvalidData`] if the document is malformed or has a newer
+/// [`VERSION`]. The names of the types and fields are leaked once, as the registry only holds
+/// `&'static str` names.
+pub fn from_json(mut reader: impl io::Read) -> io::Result<ContainerCollection> {
+    let mut source = String::new();
+    reader.read_to_string(&mut source)?;
+
+    let document = Parser::new(&source).parse_document()?;
+
+    let version = document.field("version")?.as_usize()?;
+    if version > VERSION as usize {
+        return Err(invalid(format!(
+            "version {version} is not supported, the latest version is {VERSION}"
+        )));
+    }
+
+    let mut registry = BindingsRegistry::default();
+    for container in document.field("containers")?.as_array()? {
+        register_container(&mut registry, container)?;
+    }
+    Ok(registry.into_entries())
+}
+
+fn register_container(registry: &mut BindingsRegistry, container: &Json) -> io::Result<()> {
+    let name = container.field("name")?.as_str()?.to_owned();
+    let path = path_from_json(container.field("path")?)?;
+
+    match container.field("kind")?.as_str()? {
+        "struct" => registry.register_struct_binding(
+            name,
+            path,
+            StructType {
+                fields: struct_fields_from_json(container.field("fields")?)?,
+            },
+        ),
+        "tuple_struct" => registry.register_tuple_struct_binding(
+            name,
+            path,
+            TupleStructType {
+                fields: tuple_fields_from_json(container.field("fields")?)?,
+            },
+        ),
+        "unit_struct" => registry.register_unit_struct_binding(name, path, UnitStructType),
+        "enum" => {
+            let variants = container
+                .field("variants")?
+                .as_array()?
+                .iter()
+                .map(|variant| {
+                    let inner_type = match variant.field("kind")?.as_str()? {
+                        "unit" => EnumVariantType::Empty,
+                        "tuple" => EnumVariantType::Tuple(tuple_fields_from_json(
+                            variant.field("fields")?,
+                        )?),
+                        "struct" => EnumVariantType::NewType(struct_fields_from_json(
+                            variant.field("fields")?,
+                        )?),
+                        kind => return Err(invalid(format!("unknown variant kind `{kind}`"))),
+                    };
+                    Ok(EnumVariant {
+                        index: variant.field("index")?.as_usize()?,
+                        name: variant.field("name")?.as_str()?.to_owned().into(),
+                        inner_type,
+                    })
+                })
+                .collect::<io::Result<_>>()?;
+            // documents written before internally tagged enums were supported have no tag
+            let tag = container
+                .optional_field("tag")?
+                .map(|s| s.to_owned().into());
+            registry.register_enum_binding(name, path, EnumType { variants, tag })
+        }
+        kind => return Err(invalid(format!("unknown container kind `{kind}`"))),
+    }
+
+    Ok(())
+}
+
+fn struct_fields_from_json(fields: &Json) -> io::Result<Vec<StructField>> {
+    fields
+        .as_array()?
+        .iter()
+        .map(|field| {
+            Ok(StructField {
+                name: field.field("name")?.as_str()?.to_owned().into(),
+                v_type: type_from_json(field.field("type")?)?,
+                skip: field.field("skip")?.as_bool()?,
+                default: field
+                    .optional_field("default")?
+                    .map(|s| s.to_owned().into()),
+                codec: field.optional_field("codec")?.map(|s| s.to_owned().into()),
+                alias: field.optional_field("alias")?.map(|s| s.to_owned().into()),
+                // documents written before the key was added have no such fields
+                has_default: field
+                    .field("has_default")
+                    .map_or(Ok(false), Json::as_bool)?,
+            })
+        })
+        .collect()
+}
+
+fn tuple_fields_from_json(fields: &Json) -> io::Result<Vec<ValueType>> {
+    fields.as_array()?.iter().map(type_from_json).collect()
+}
+
+fn path_from_json(path: &Json) -> io::Result<String> {
+    let parts = path
+        .as_array()?
+        .iter()
+        .map(Json::as_str)
+        .collect::<io::Result<Vec<_>>>()?;
+    Ok(parts.join("::"))
+}
+
+fn type_from_json(v_type: &Json) -> io::Result<ValueType> {
+    let boxed = |key| type_from_json(v_type.field(key)?).map(Box::new);
+    let capacity = || match v_type.field("capacity") {
+        Ok(Json::Null) | Err(_) => Ok(None),
+        Ok(capacity) => capacity.as_usize().map(Some),
+    };
+
+    Ok(match v_type.field("kind")?.as_str()? {
+        "integer" => {
+            let bytes = v_type.field("bytes")?.as_usize()?;
+            if ![1, 2, 4, 8, 16].contains(&bytes) {
+                return Err(invalid(format!("integers can't have {bytes} bytes")));
+            }
+            ValueType::Number(NumberMeta::Integer {
+                bytes,
+                signed: v_type.field("signed")?.as_bool()?,
+                pointer_sized: v_type.field("pointer_sized")?.as_bool()?,
+                // added later, missing in older documents
+                non_zero: match v_type.field("non_zero") {
+                    Ok(non_zero) => non_zero.as_bool()?,
+                    Err(_) => false,
+                },
+            })
+        }
+        "float" => {
+            let bytes = v_type.field("bytes")?.as_usize()?;
+            if ![4, 8].contains(&bytes) {
+                return Err(invalid(format!("floats can't have {bytes} bytes")));
+            }
+            ValueType::Number(NumberMeta::FloatingPoint { bytes })
+        }
+        "bool" => ValueType::Bool(BoolMeta),
+        "unit" => ValueType::Unit(UnitMeta),
+        "string" => ValueType::String(StringMeta {
+            char: false,
+            capacity: capacity()?,
+            // added later, missing in older documents
+            format: match v_type.field("format") {
+                Ok(Json::Null) | Err(_) => None,
+                Ok(format) => Some(match format.as_str()? {
+                    "url" => StringFormat::Url,
+                    "semver" => StringFormat::SemVer,
+                    format => return Err(invalid(format!("unknown string format `{format}`"))),
+                }),
+            },
+        }),
+        "char" => ValueType::String(StringMeta {
+            char: true,
+            capacity: None,
+            format: None,
+        }),
+        "array" => ValueType::Array(ArrayMeta {
+            items_type: boxed("items")?,
+            length: match v_type.field("length")? {
+                Json::Null => None,
+                length => Some(length.as_usize()?),
+            },
+            capacity: capacity()?,
+            // added later, missing in older documents
+            format: match v_type.field("format") {
+                Ok(Json::Null) | Err(_) => None,
+                Ok(format) => Some(match format.as_str()? {
+                    "uuid" => ArrayFormat::Uuid,
+                    format => return Err(invalid(format!("unknown array format `{format}`"))),
+                }),
+            },
+        }),
+        "optional" => ValueType::Optional(OptionalMeta {
+            inner: boxed("inner")?,
+        }),
+        "range" => ValueType::Range(RangeMeta {
+            bounds_type: boxed("bounds")?,
+            // added later, missing in older documents
+            inclusive: match v_type.field("inclusive") {
+                Ok(inclusive) => inclusive.as_bool()?,
+                Err(_) => false,
+            },
+        }),
+        "map" => ValueType::Map(MapMeta {
+            key_type: boxed("key")?,
+            value_type: boxed("value")?,
+        }),
+        "set" => ValueType::Set(SetMeta {
+            items_type: boxed("items")?,
+        }),
+        "tuple" => ValueType::Tuple(TupleMeta {
+            items_types: tuple_fields_from_json(v_type.field("items")?)?,
+        }),
+        "object" => ValueType::Object(ObjectMeta {
+            name: v_type.field("name")?.as_str()?.to_owned().into(),
+            path: Path::new(path_from_json(v_type.field("path")?)?, "::"),
+        }),
+        kind => return Err(invalid(format!("unknown type kind `{kind}`"))),
+    })
+}
+
+fn invalid(message: impl Display) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid JSON IR: {message}"),
+    )
+}
+
+fn container_json(container: &Container) -> Json {
+    let mut entries = vec![
+        ("name", Json::string(&container.name)),
+        ("path", path_json(&container.path)),
+    ];
+
+    match &container.r#type {
+        BindingType::Struct(struct_type) => {
+            entries.push(("kind", Json::string("struct")));
+            entries.push(("fields", struct_fields_json(&struct_type.fields)));
+        }
+        BindingType::TupleStruct(tuple_struct_type) => {
+            entries.push(("kind", Json::string("tuple_struct")));
+            entries.push(("fields", tuple_fields_json(&tuple_struct_type.fields)));
+        }
+        BindingType::UnitStruct(_) => entries.push(("kind", Json::string("unit_struct"))),
+        BindingType::Enum(enum_type) => {
+            let variants = enum_type.variants.iter().map(|variant| {
+                let mut entries = vec![
+                    ("name", Json::string(&variant.name)),
+                    ("index", Json::Number(variant.index)),
+                ];
+                match &variant.inner_type {
+                    EnumVariantType::Empty => entries.push(("kind", Json::string("unit"))),
+                    EnumVariantType::Tuple(fields) => {
+                        entries.push(("kind", Json::string("tuple")));
+                        entries.push(("fields", tuple_fields_json(fields)));
+                    }
+                    EnumVariantType::NewType(fields) => {
+                        entries.push(("kind", Json::string("struct")));
+                        entries.push(("fields", struct_fields_json(fields)));
+                    }
+                }
+                Json::object(entries)
+            });
+
+            entries.push(("kind", Json::string("enum")));
+            entries.push(("variants", Json::Array(variants.collect())));
+            entries.push((
+                "tag",
+                enum_type.tag.as_deref().map_or(Json::Null, Json::string),
+            ));
+        }
+    }
+
+    Json::object(entries)
+}
+
+fn struct_fields_json(fields: &[StructField]) -> Json {
+    Json::Array(
+        fields
+            .iter()
+            .map(|field| {
+                Json::object(vec![
+                    ("name", Json::string(&field.name)),
+                    ("type", type_json(&field.v_type)),
+                    ("skip", Json::Bool(field.skip)),
+                    (
+                        "default",
+                        field.default.as_deref().map_or(Json::Null, Json::string),
+                    ),
+                    (
+                        "codec",
+                        field.codec.as_deref().map_or(Json::Null, Json::string),
+                    ),
+                    (
+                        "alias",
+                        field.alias.as_deref().map_or(Json::Null, Json::string),
+                    ),
+                    ("has_default", Json::Bool(field.has_default)),
+                ])
+            })
+            .collect(),
+    )
+}
+
+fn tuple_fields_json(fields: &[ValueType]) -> Json {
+    Json::Array(fields.iter().map(type_json).collect())
+}
+
+fn path_json(path: &Path) -> Json {
+    Json::Array(
+        path.parts()
+            .filter(|part| !part.is_empty())
+            .map(Json::string)
+            .collect(),
+    )
+}
+
+fn type_json(v_type: &ValueType) -> Json {
+    let entries = match v_type {
+        ValueType::Number(NumberMeta::Integer {
+            bytes,
+            signed,
+            pointer_sized,
+            non_zero,
+        }) => vec![
+            ("kind", Json::string("integer")),
+            ("bytes", Json::Number(*bytes)),
+            ("signed", Json::Bool(*signed)),
+            ("pointer_sized", Json::Bool(*pointer_sized)),
+            ("non_zero", Json::Bool(*non_zero)),
+        ],
+        ValueType::Number(NumberMeta::FloatingPoint { bytes }) => vec![
+            ("kind", Json::string("float")),
+            ("bytes", Json::Number(*bytes)),
+        ],
+        ValueType::Bool(_) => vec![("kind", Json::string("bool"))],
+        ValueType::Unit(_) => vec![("kind", Json::string("unit"))],
+        ValueType::String(StringMeta { char: true, .. }) => vec![("kind", Json::string("char"))],
+        ValueType::String(meta) => vec![
+            ("kind", Json::string("string")),
+            ("capacity", meta.capacity.map_or(Json::Null, Json::Number)),
+            (
+                "format",
+                match meta.format {
+                    Some(StringFormat::Url) => Json::string("url"),
+                    Some(StringFormat::SemVer) => Json::string("semver"),
+                    None => Json::Null,
+                },
+            ),
+        ],
+        ValueType::Array(meta) => vec![
+            ("kind", Json::string("array")),
+            ("items", type_json(&meta.items_type)),
+            ("length", meta.length.map_or(Json::Null, Json::Number)),
+            ("capacity", meta.capacity.map_or(Json::Null, Json::Number)),
+            (
+                "format",
+                match meta.format {
+                    Some(ArrayFormat::Uuid) => Json::string("uuid"),
+                    None => Json::Null,
+                },
+            ),
+        ],
+        ValueType::Optional(meta) => vec![
+            ("kind", Json::string("optional")),
+            ("inner", type_json(&meta.inner)),
+        ],
+        ValueType::Range(meta) => vec![
+            ("kind", Json::string("range")),
+            ("bounds", type_json(&meta.bounds_type)),
+            ("inclusive", Json::Bool(meta.inclusive)),
+        ],
+        ValueType::Map(meta) => vec![
+            ("kind", Json::string("map")),
+            ("key", type_json(&meta.key_type)),
+            ("value", type_json(&meta.value_type)),
+        ],
+        ValueType::Set(meta) => vec![
+            ("kind", Json::string("set")),
+            ("items", type_json(&meta.items_type)),
+        ],
+        ValueType::Tuple(meta) => vec![
+            ("kind", Json::string("tuple")),
+            ("items", tuple_fields_json(&meta.items_types)),
+        ],
+        ValueType::Object(meta) => vec![
+            ("kind", Json::string("object")),
+            ("name", Json::string(&meta.name)),
+            ("path", path_json(&meta.path)),
+        ],
+    };
+
+    Json::object(entries)
+}
+
+enum Json {
+    Null,
+    Bool(bool),
+    Number(usize),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn string(value: &str) -> Json {
+        Json::String(value.to_string())
+    }
+
+    fn object(entries: Vec<(&str, Json)>) -> Json {
+        Json::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+    }
+
+    /// Writes the value with two spaces indentation per level. Arrays of scalars and empty
+    /// collections are kept on one line.
+    fn write(&self, out: &mut String, indent: usize) {
+        match self {
+            Json::Null => out.push_str("null"),
+            Json::Bool(value) => write!(out, "{value}").unwrap(),
+            Json::Number(value) => write!(out, "{value}").unwrap(),
+            Json::String(value) => write_string(out, value),
+            Json::Array(items) if items.is_empty() => out.push_str("[]"),
+            Json::Array(items) if items.iter().all(Json::is_scalar) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    item.write(out, indent);
+                }
+                out.push(']');
+            }
+            Json::Array(items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    out.push_str(if i > 0 { ",\n" } else { "\n" });
+                    push_indent(out, indent + 1);
+                    item.write(out, indent + 1);
+                }
+                out.push('\n');
+                push_indent(out, indent);
+                out.push(']');
+            }
+            Json::Object(entries) if entries.is_empty() => out.push_str("{}"),
+            Json::Object(entries) => {
+                out.push('{');
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    out.push_str(if i > 0 { ",\n" } else { "\n" });
+                    push_indent(out, indent + 1);
+                    write_string(out, key);
+                    out.push_str(": ");
+                    value.write(out, indent + 1);
+                }
+                out.push('\n');
+                push_indent(out, indent);
+                out.push('}');
+            }
+        }
+    }
+
+    fn is_scalar(&self) -> bool {
+        !matches!(self, Json::Array(_) | Json::Object(_))
+    }
+
+    fn field(&self, key: &str) -> io::Result<&Json> {
+        match self {
+            Json::Object(entries) => entries
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, value)| value)
+                .ok_or_else(|| invalid(format!("missing field `{key}`"))),
+            _ => Err(invalid(format!(
+                "expected an object with the field `{key}`"
+            ))),
+        }
+    }
+
+    /// A string field, which is `null` or missing if not set.
+    fn optional_field(&self, key: &str) -> io::Result<Option<&str>> {
+        match self.field(key) {
+            Ok(Json::Null) => Ok(None),
+            Ok(value) => value.as_str().map(Some),
+            Err(_) if matches!(self, Json::Object(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn as_str(&self) -> io::Result<&str> {
+        match self {
+            Json::String(value) => Ok(value),
+            _ => Err(invalid("expected a string")),
+        }
+    }
+
+    fn as_usize(&self) -> io::Result<usize> {
+        match self {
+            Json::Number(value) => Ok(*value),
+            _ => Err(invalid("expected an unsigned integer")),
+        }
+    }
+
+    fn as_bool(&self) -> io::Result<bool> {
+        match self {
+            Json::Bool(value) => Ok(*value),
+            _ => Err(invalid("expected a boolean")),
+        }
+    }
+
+    fn as_array(&self) -> io::Result<&[Json]> {
+        match self {
+            Json::Array(items) => Ok(items),
+            _ => Err(invalid("expected an array")),
+        }
+    }
+}
+
+fn push_indent(out: &mut String, indent: usize) {
+    for _ in 0..indent {
+        out.push_str("  ");
+    }
+}
+
+fn write_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Parses the subset of JSON the IR uses, numbers are unsigned integers only.
+struct Parser<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        Self { source, pos: 0 }
+    }
+
+    fn parse_document(mut self) -> io::Result<Json> {
+        let value = self.parse_value()?;
+        self.skip_whitespace();
+        if self.pos < self.source.len() {
+            return Err(self.error("trailing characters"));
+        }
+        Ok(value)
+    }
+
+    fn parse_value(&mut self) -> io::Result<Json> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.parse_object(),
+            Some('[') => self.parse_array(),
+            Some('"') => self.parse_string().map(Json::String),
+            Some('0'..='9') => self.parse_number(),
+            Some('n') => self.parse_keyword("null", Json::Null),
+            Some('t') => self.parse_keyword("true", Json::Bool(true)),
+            Some('f') => self.parse_keyword("false", Json::Bool(false)),
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    fn parse_object(&mut self) -> io::Result<Json> {
+        self.expect('{')?;
+        let mut entries = Vec::new();
+        self.skip_whitespace();
+        if self.eat('}') {
+            return Ok(Json::Object(entries));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            entries.push((key, self.parse_value()?));
+            self.skip_whitespace();
+            if self.eat('}') {
+                return Ok(Json::Object(entries));
+            }
+            self.expect(',')?;
+        }
+    }
+
+    fn parse_array(&mut self) -> io::Result<Json> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.eat(']') {
+            return Ok(Json::Array(items));
+        }
+        loop {
+            items.push(self.parse_value()?);
+            self.skip_whitespace();
+            if self.eat(']') {
+                return Ok(Json::Array(items));
+            }
+            self.expect(',')?;
+        }
+    }
+
+    fn parse_string(&mut self) -> io::Result<String> {
+        self.expect('"')?;
+        let mut value = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(value),
+                Some('\\') => match self.next() {
+                    Some('"') => value.push('"'),
+                    Some('\\') => value.push('\\'),
+                    Some('/') => value.push('/'),
+                    Some('b') => value.push('\u{8}'),
+                    Some('f') => value.push('\u{c}'),
+                    Some('n') => value.push('\n'),
+                    Some('r') => value.push('\r'),
+                    Some('t') => value.push('\t'),
+                    Some('u') => value.push(self.parse_unicode_escape()?),
+                    _ => return Err(self.error("invalid escape sequence")),
+                },
+                Some(c) if (c as u32) < 0x20 => {
+                    return Err(self.error("control character in string"))
+                }
+                Some(c) => value.push(c),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn parse_unicode_escape(&mut self) -> io::Result<char> {
+        let high = self.parse_hex4()?;
+        let code = if (0xd800..0xdc00).contains(&high) {
+            if !(self.eat('\\') && self.eat('u')) {
+                return Err(self.error("unpaired surrogate"));
+            }
+            let low = self.parse_hex4()?;
+            if !(0xdc00..0xe000).contains(&low) {
+                return Err(self.error("unpaired surrogate"));
+            }
+            0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn parse_hex4(&mut self) -> io::Result<u32> {
+        let digits = self
+            .source
+            .get(self.pos..self.pos + 4)
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        let value =
+            u32::from_str_radix(digits, 16).map_err(|_| self.error("invalid unicode escape"))?;
+        self.pos += 4;
+        Ok(value)
+    }
+
+    fn parse_number(&mut self) -> io::Result<Json> {
+        let start = self.pos;
+        while matches!(self.peek(), Some('0'..='9')) {
+            self.pos += 1;
+        }
+        if matches!(self.peek(), Some('.' | 'e' | 'E')) {
+            return Err(self.error("expected an unsigned integer"));
+        }
+        self.source[start..self.pos]
+            .parse()
+            .map(Json::Number)
+            .map_err(|_| self.error("number out of range"))
+    }
+
+    fn parse_keyword(&mut self, keyword: &str, value: Json) -> io::Result<Json> {
+        if self.source[self.pos..].starts_with(keyword) {
+            self.pos += keyword.len();
+            Ok(value)
+        } else {
+            Err(self.error("expected a value"))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.source[self.pos..].chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let matches = self.peek() == Some(c);
+        if matches {
+            self.pos += c.len_utf8();
+        }
+        matches
+    }
+
+    fn expect(&mut self, c: char) -> io::Result<()> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{c}`")))
+        }
+    }
+
+    fn error(&self, message: impl Display) -> io::Error {
+        invalid(format!("{message} at byte {}", self.pos))
+    }
+}
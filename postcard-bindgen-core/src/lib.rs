@@ -5,7 +5,13 @@ extern crate alloc;
 #[cfg(feature = "generating")]
 pub mod code_gen;
 #[cfg(feature = "generating")]
+pub mod json_ir;
+#[cfg(feature = "generating")]
 pub mod path;
+#[cfg(feature = "postcard-schema")]
+pub mod postcard_schema;
+#[cfg(feature = "serde-reflection")]
+pub mod reflection;
 #[cfg(feature = "generating")]
 pub mod registry;
 #[cfg(feature = "generating")]
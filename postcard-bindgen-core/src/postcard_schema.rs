@@ -0,0 +1,333 @@
+//! Registers types implementing postcard's experimental
+//! [`Schema`](postcard::experimental::schema::Schema) trait.
+//!
+//! The schema is what postcard itself reports for a type, so the bindings have the same
+//! layout as the encoder without a second derive. Structs, tuple structs, unit structs and
+//! enums become containers named like the type, other schemas become value types:
+//! - `char` is encoded as string
+//! - byte arrays are a vector of `u8`
+//! - newtype structs and newtype variants are tuple structs and variants with one field
+//! - arrays `[T; N]` are arrays with a fixed length
+//!
+//! The unit type `()` has no representation in the bindings and is rejected, as well as
+//! containers with a name which is no identifier, e.g. `Result<T, E>`.
+
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Display;
+use std::collections::BTreeMap;
+
+use postcard::experimental::schema::{NamedType, NamedValue, NamedVariant, SdmTy, Varint};
+
+use crate::{
+    path::Path,
+    registry::{
+        BindingsRegistry, EnumType, EnumVariant, EnumVariantType, StructField, StructType,
+        TupleStructType, UnitStructType,
+    },
+    type_info::{
+        ArrayMeta, BoolMeta, MapMeta, NumberMeta, ObjectMeta, OptionalMeta, StringMeta, TupleMeta,
+        UnitMeta, ValueType,
+    },
+};
+
+/// A schema which can't be expressed in the bindings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedSchema {
+    /// Name of the type which has the schema.
+    pub type_name: String,
+    /// The reason why the schema is not supported.
+    pub reason: String,
+}
+
+impl Display for UnsupportedSchema {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "the schema of `{}` {}", self.type_name, self.reason)
+    }
+}
+
+impl std::error::Error for UnsupportedSchema {}
+
+/// Builds the bindings registry from the schemas of the given types.
+///
+/// The types referenced by the schemas are registered as well, so only the top level types
+/// need to be passed. All containers are registered at the root module, as the schemas have
+/// no module path.
+///
+/// # Example
+/// ```
+/// # use postcard::experimental::schema::{NamedType, NamedValue, Schema, SdmTy};
+/// # use postcard_bindgen_core::postcard_schema::from_postcard_schemas;
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// // usually derived with `#[derive(Schema)]`
+/// impl Schema for Point {
+///     const SCHEMA: &'static NamedType = &NamedType {
+///         name: "Point",
+///         ty: &SdmTy::Struct(&[
+///             &NamedValue { name: "x", ty: i32::SCHEMA },
+///             &NamedValue { name: "y", ty: i32::SCHEMA },
+///         ]),
+///     };
+/// }
+///
+/// let bindings = from_postcard_schemas(&[Point::SCHEMA]).unwrap();
+/// ```
+pub fn from_postcard_schemas(
+    schemas: &[&'static NamedType],
+) -> Result<BindingsRegistry, UnsupportedSchema> {
+    let mut collector = Collector::default();
+    for schema in schemas {
+        if !is_container(schema.ty) {
+            return Err(unsupported(schema, "is no struct or enum"));
+        }
+        collector.value_type(schema)?;
+    }
+
+    let mut registry = BindingsRegistry::default();
+    for (name, container) in collector.containers {
+        match container {
+            Container::Struct(fields) => {
+                registry.register_struct_binding(name, "", StructType { fields })
+            }
+            Container::TupleStruct(fields) => {
+                registry.register_tuple_struct_binding(name, "", TupleStructType { fields })
+            }
+            Container::UnitStruct => {
+                registry.register_unit_struct_binding(name, "", UnitStructType)
+            }
+            Container::Enum(variants) => registry.register_enum_binding(
+                name,
+                "",
+                EnumType {
+                    variants,
+                    tag: None,
+                },
+            ),
+        }
+    }
+
+    Ok(registry)
+}
+
+enum Container {
+    Struct(Vec<StructField>),
+    TupleStruct(Vec<ValueType>),
+    UnitStruct,
+    Enum(Vec<EnumVariant>),
+}
+
+/// Collects the containers, each after the containers referenced by its fields.
+#[derive(Default)]
+struct Collector {
+    containers: Vec<(&'static str, Container)>,
+    /// Schemas of the visited containers by name, to detect different types with the same name.
+    visited: BTreeMap<&'static str, &'static NamedType>,
+}
+
+impl Collector {
+    fn value_type(&mut self, schema: &'static NamedType) -> Result<ValueType, UnsupportedSchema> {
+        let integer = |bytes, signed, pointer_sized| {
+            ValueType::Number(NumberMeta::Integer {
+                bytes,
+                signed,
+                pointer_sized,
+                non_zero: false,
+            })
+        };
+
+        Ok(match schema.ty {
+            SdmTy::Bool => ValueType::Bool(BoolMeta),
+            SdmTy::I8 => integer(1, true, false),
+            SdmTy::U8 => integer(1, false, false),
+            SdmTy::Varint(varint) => match varint {
+                Varint::I16 => integer(2, true, false),
+                Varint::I32 => integer(4, true, false),
+                Varint::I64 => integer(8, true, false),
+                Varint::I128 => integer(16, true, false),
+                Varint::U16 => integer(2, false, false),
+                Varint::U32 => integer(4, false, false),
+                Varint::U64 => integer(8, false, false),
+                Varint::U128 => integer(16, false, false),
+                Varint::Isize => integer(8, true, true),
+                Varint::Usize => integer(8, false, true),
+            },
+            SdmTy::F32 => ValueType::Number(NumberMeta::FloatingPoint { bytes: 4 }),
+            SdmTy::F64 => ValueType::Number(NumberMeta::FloatingPoint { bytes: 8 }),
+            SdmTy::Char => ValueType::String(StringMeta {
+                char: true,
+                capacity: None,
+                format: None,
+            }),
+            SdmTy::String => ValueType::String(StringMeta {
+                char: false,
+                capacity: None,
+                format: None,
+            }),
+            SdmTy::ByteArray => ValueType::Array(ArrayMeta {
+                items_type: Box::new(integer(1, false, false)),
+                length: None,
+                capacity: None,
+                format: None,
+            }),
+            SdmTy::Option(inner) => ValueType::Optional(OptionalMeta {
+                inner: Box::new(self.value_type(inner)?),
+            }),
+            SdmTy::Seq(items) => ValueType::Array(ArrayMeta {
+                items_type: Box::new(self.value_type(items)?),
+                length: None,
+                capacity: None,
+                format: None,
+            }),
+            SdmTy::Map { key, val } => ValueType::Map(MapMeta {
+                key_type: Box::new(self.value_type(key)?),
+                value_type: Box::new(self.value_type(val)?),
+            }),
+            // arrays are encoded like tuples, but all items have the same type
+            SdmTy::Tuple(items) if schema.name == "[T; N]" && !items.is_empty() => {
+                ValueType::Array(ArrayMeta {
+                    items_type: Box::new(self.value_type(items[0])?),
+                    length: Some(items.len()),
+                    capacity: None,
+                    format: None,
+                })
+            }
+            SdmTy::Unit => ValueType::Unit(UnitMeta),
+            SdmTy::Tuple(items) => ValueType::Tuple(TupleMeta {
+                items_types: self.value_types(items)?,
+            }),
+            ty if is_container(ty) => {
+                self.register_container(schema)?;
+                ValueType::Object(ObjectMeta {
+                    name: schema.name.into(),
+                    path: Path::new("", "::"),
+                })
+            }
+            _ => return Err(unsupported(schema, "has no representation in the bindings")),
+        })
+    }
+
+    fn value_types(
+        &mut self,
+        schemas: &[&'static NamedType],
+    ) -> Result<Vec<ValueType>, UnsupportedSchema> {
+        schemas
+            .iter()
+            .map(|schema| self.value_type(schema))
+            .collect()
+    }
+
+    fn fields(
+        &mut self,
+        fields: &[&'static NamedValue],
+    ) -> Result<Vec<StructField>, UnsupportedSchema> {
+        fields
+            .iter()
+            .map(|field| {
+                Ok(StructField {
+                    name: field.name.into(),
+                    v_type: self.value_type(field.ty)?,
+                    skip: false,
+                    default: None,
+                    codec: None,
+                    alias: None,
+                    has_default: false,
+                })
+            })
+            .collect()
+    }
+
+    fn register_container(&mut self, schema: &'static NamedType) -> Result<(), UnsupportedSchema> {
+        match self.visited.get(schema.name) {
+            Some(visited) if *visited == schema => return Ok(()),
+            Some(_) => {
+                return Err(unsupported(
+                    schema,
+                    "differs from another type with the same name",
+                ))
+            }
+            None => (),
+        }
+        if !is_identifier(schema.name) {
+            return Err(unsupported(schema, "has no name usable in the bindings"));
+        }
+
+        // marked as visited first, so recursive types don't recurse endlessly
+        self.visited.insert(schema.name, schema);
+
+        let container = match schema.ty {
+            SdmTy::Struct(fields) => Container::Struct(self.fields(fields)?),
+            SdmTy::NewtypeStruct(inner) => Container::TupleStruct(self.value_types(&[*inner])?),
+            SdmTy::TupleStruct(fields) => Container::TupleStruct(self.value_types(fields)?),
+            SdmTy::UnitStruct => Container::UnitStruct,
+            SdmTy::Enum(variants) => Container::Enum(
+                variants
+                    .iter()
+                    .enumerate()
+                    .map(|(index, variant)| self.variant(schema, index, variant))
+                    .collect::<Result<_, _>>()?,
+            ),
+            _ => unreachable!("only containers are registered"),
+        };
+
+        self.containers.push((schema.name, container));
+        Ok(())
+    }
+
+    fn variant(
+        &mut self,
+        schema: &'static NamedType,
+        index: usize,
+        variant: &NamedVariant,
+    ) -> Result<EnumVariant, UnsupportedSchema> {
+        let inner_type = match variant.ty {
+            SdmTy::UnitVariant => EnumVariantType::Empty,
+            SdmTy::NewtypeVariant(inner) => EnumVariantType::Tuple(self.value_types(&[*inner])?),
+            SdmTy::TupleVariant(fields) => EnumVariantType::Tuple(self.value_types(fields)?),
+            SdmTy::StructVariant(fields) => EnumVariantType::NewType(self.fields(fields)?),
+            _ => {
+                return Err(unsupported(
+                    schema,
+                    format!("has the variant `{}` with an unknown schema", variant.name),
+                ))
+            }
+        };
+
+        Ok(EnumVariant {
+            index,
+            name: variant.name.into(),
+            inner_type,
+        })
+    }
+}
+
+fn is_container(ty: &SdmTy) -> bool {
+    matches!(
+        ty,
+        SdmTy::Struct(_)
+            | SdmTy::NewtypeStruct(_)
+            | SdmTy::TupleStruct(_)
+            | SdmTy::UnitStruct
+            | SdmTy::Enum(_)
+    )
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn unsupported(schema: &NamedType, reason: impl ToString) -> UnsupportedSchema {
+    UnsupportedSchema {
+        type_name: schema.name.to_string(),
+        reason: reason.to_string(),
+    }
+}
@@ -0,0 +1,241 @@
+//! Registers types traced with [`serde_reflection`], for types which can't derive
+//! `PostcardBindings`, e.g. types of other crates or generated code.
+//!
+//! The traced formats map onto the postcard wire format as follows:
+//! - `char` is encoded as string
+//! - bytes are a vector of `u8`
+//! - newtype structs and newtype variants are tuple structs and variants with one field
+//! - `TupleArray` is an array with a fixed length
+//! - the unit type `()` is encoded as nothing, like the derive maps it
+//!
+//! Formats the tracer left unknown are rejected.
+
+use alloc::{boxed::Box, format, string::String, vec, vec::Vec};
+use core::fmt::Display;
+
+use serde_reflection::{ContainerFormat, Format, Named, Registry, VariantFormat};
+
+use crate::{
+    path::Path,
+    registry::{
+        BindingsRegistry, EnumType, EnumVariant, EnumVariantType, StructField, StructType,
+        TupleStructType, UnitStructType,
+    },
+    type_info::{
+        ArrayMeta, BoolMeta, MapMeta, NumberMeta, ObjectMeta, OptionalMeta, StringMeta, TupleMeta,
+        UnitMeta, ValueType,
+    },
+};
+
+/// A format of the traced registry which can't be expressed in the bindings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedFormat {
+    /// Name of the container which has the format.
+    pub container: String,
+    /// Description of the format.
+    pub format: String,
+}
+
+impl Display for UnsupportedFormat {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "`{}` has the format {}, which is not supported",
+            self.container, self.format
+        )
+    }
+}
+
+impl std::error::Error for UnsupportedFormat {}
+
+/// Builds the bindings registry from a registry traced by [`serde_reflection::Tracer`].
+///
+/// All containers are registered at the root module, as the traced names have no module path.
+/// The registry must be complete, i.e. the tracer finished with
+/// [`registry`](serde_reflection::Tracer::registry), otherwise unresolved formats are
+/// reported as unsupported.
+///
+/// # Example
+/// ```
+/// # use postcard_bindgen_core::reflection::from_serde_reflection;
+/// # use serde_reflection::{ContainerFormat, Format, Named, Registry};
+/// let mut registry = Registry::new();
+/// registry.insert(
+///     "Point".to_string(),
+///     ContainerFormat::Struct(vec![
+///         Named { name: "x".to_string(), value: Format::I32 },
+///         Named { name: "y".to_string(), value: Format::I32 },
+///     ]),
+/// );
+///
+/// let bindings = from_serde_reflection(&registry).unwrap();
+/// ```
+pub fn from_serde_reflection(registry: &Registry) -> Result<BindingsRegistry, UnsupportedFormat> {
+    let mut bindings = BindingsRegistry::default();
+
+    for (name, container_format) in registry {
+        let unsupported = |format: &dyn core::fmt::Debug| UnsupportedFormat {
+            container: name.clone(),
+            format: format!("{format:?}"),
+        };
+        let value_type = |format: &Format| value_type(format).map_err(|f| unsupported(&f));
+        let value_types = |formats: &[Format]| {
+            formats
+                .iter()
+                .map(value_type)
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let fields = |fields: &[Named<Format>]| {
+            fields
+                .iter()
+                .map(|field| {
+                    Ok(StructField {
+                        name: field.name.clone().into(),
+                        v_type: value_type(&field.value)?,
+                        skip: false,
+                        default: None,
+                        codec: None,
+                        alias: None,
+                        has_default: false,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let container_name = name.clone();
+        match container_format {
+            ContainerFormat::UnitStruct => {
+                bindings.register_unit_struct_binding(container_name, "", UnitStructType)
+            }
+            ContainerFormat::NewTypeStruct(format) => bindings.register_tuple_struct_binding(
+                container_name,
+                "",
+                TupleStructType {
+                    fields: vec![value_type(format)?],
+                },
+            ),
+            ContainerFormat::TupleStruct(formats) => bindings.register_tuple_struct_binding(
+                container_name,
+                "",
+                TupleStructType {
+                    fields: value_types(formats)?,
+                },
+            ),
+            ContainerFormat::Struct(named) => bindings.register_struct_binding(
+                container_name,
+                "",
+                StructType {
+                    fields: fields(named)?,
+                },
+            ),
+            ContainerFormat::Enum(variants) => {
+                let variants = variants
+                    .iter()
+                    .map(|(index, variant)| {
+                        let inner_type = match &variant.value {
+                            VariantFormat::Unit => EnumVariantType::Empty,
+                            VariantFormat::NewType(format) => {
+                                EnumVariantType::Tuple(vec![value_type(format)?])
+                            }
+                            VariantFormat::Tuple(formats) => {
+                                EnumVariantType::Tuple(value_types(formats)?)
+                            }
+                            VariantFormat::Struct(named) => {
+                                EnumVariantType::NewType(fields(named)?)
+                            }
+                            VariantFormat::Variable(_) => return Err(unsupported(&variant.value)),
+                        };
+                        Ok(EnumVariant {
+                            index: *index as usize,
+                            name: variant.name.clone().into(),
+                            inner_type,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                bindings.register_enum_binding(
+                    container_name,
+                    "",
+                    EnumType {
+                        variants,
+                        tag: None,
+                    },
+                )
+            }
+        }
+    }
+
+    Ok(bindings)
+}
+
+/// Maps the format onto a value type, returns the first format which has no value type.
+fn value_type(format: &Format) -> Result<ValueType, Format> {
+    let integer = |bytes, signed| {
+        ValueType::Number(NumberMeta::Integer {
+            bytes,
+            signed,
+            pointer_sized: false,
+            non_zero: false,
+        })
+    };
+    let boxed = |format| value_type(format).map(Box::new);
+
+    Ok(match format {
+        Format::TypeName(name) => ValueType::Object(ObjectMeta {
+            name: name.clone().into(),
+            path: Path::new("", "::"),
+        }),
+        Format::Bool => ValueType::Bool(BoolMeta),
+        Format::I8 => integer(1, true),
+        Format::I16 => integer(2, true),
+        Format::I32 => integer(4, true),
+        Format::I64 => integer(8, true),
+        Format::I128 => integer(16, true),
+        Format::U8 => integer(1, false),
+        Format::U16 => integer(2, false),
+        Format::U32 => integer(4, false),
+        Format::U64 => integer(8, false),
+        Format::U128 => integer(16, false),
+        Format::F32 => ValueType::Number(NumberMeta::FloatingPoint { bytes: 4 }),
+        Format::F64 => ValueType::Number(NumberMeta::FloatingPoint { bytes: 8 }),
+        Format::Char => ValueType::String(StringMeta {
+            char: true,
+            capacity: None,
+            format: None,
+        }),
+        Format::Str => ValueType::String(StringMeta {
+            char: false,
+            capacity: None,
+            format: None,
+        }),
+        Format::Bytes => ValueType::Array(ArrayMeta {
+            items_type: Box::new(integer(1, false)),
+            length: None,
+            capacity: None,
+            format: None,
+        }),
+        Format::Option(inner) => ValueType::Optional(OptionalMeta {
+            inner: boxed(inner)?,
+        }),
+        Format::Seq(items) => ValueType::Array(ArrayMeta {
+            items_type: boxed(items)?,
+            length: None,
+            capacity: None,
+            format: None,
+        }),
+        Format::Map { key, value } => ValueType::Map(MapMeta {
+            key_type: boxed(key)?,
+            value_type: boxed(value)?,
+        }),
+        Format::Tuple(items) => ValueType::Tuple(TupleMeta {
+            items_types: items.iter().map(value_type).collect::<Result<_, _>>()?,
+        }),
+        Format::TupleArray { content, size } => ValueType::Array(ArrayMeta {
+            items_type: boxed(content)?,
+            length: Some(*size),
+            capacity: None,
+            format: None,
+        }),
+        Format::Unit => ValueType::Unit(UnitMeta),
+        Format::Variable(_) => return Err(format.clone()),
+    })
+}
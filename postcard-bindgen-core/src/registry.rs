@@ -5,14 +5,15 @@ use alloc::vec::Vec;
 use tree_ds::prelude::{Node, NodeRemovalStrategy, Tree};
 
 use crate::{
+    json_ir,
     path::Path,
-    type_info::{GenJsBinding, ValueType},
+    type_info::{GenJsBinding, NumberMeta, ValueType},
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Container {
     pub path: Path<'static, 'static>,
-    pub name: &'static str,
+    pub name: Cow<'static, str>,
     pub r#type: BindingType,
 }
 
@@ -27,6 +28,95 @@ impl Container {
             _ => (),
         }
     }
+
+    /// The named fields of a struct or of the struct variants of an enum.
+    pub(crate) fn named_fields(&self) -> Vec<&StructField> {
+        match &self.r#type {
+            BindingType::Struct(ty) => ty.fields.iter().collect(),
+            BindingType::Enum(ty) => ty
+                .variants
+                .iter()
+                .filter_map(|variant| match &variant.inner_type {
+                    EnumVariantType::NewType(fields) => Some(fields.iter()),
+                    _ => None,
+                })
+                .flatten()
+                .collect(),
+            BindingType::TupleStruct(_) | BindingType::UnitStruct(_) => Vec::new(),
+        }
+    }
+
+    /// Calls `f` with the named fields of a struct or of the struct variants of an enum.
+    fn visit_named_fields_mut(&mut self, f: &mut impl FnMut(&mut StructField)) {
+        match &mut self.r#type {
+            BindingType::Struct(ty) => ty.fields.iter_mut().for_each(f),
+            BindingType::Enum(ty) => {
+                for variant in &mut ty.variants {
+                    if let EnumVariantType::NewType(fields) = &mut variant.inner_type {
+                        fields.iter_mut().for_each(&mut *f);
+                    }
+                }
+            }
+            BindingType::TupleStruct(_) | BindingType::UnitStruct(_) => {}
+        }
+    }
+
+    /// Calls `f` with all value types of the fields, including the nested ones.
+    pub(crate) fn visit_value_types_mut(&mut self, f: &mut impl FnMut(&mut ValueType)) {
+        match &mut self.r#type {
+            BindingType::Struct(ty) => {
+                for field in &mut ty.fields {
+                    field.v_type.visit_mut(f);
+                }
+            }
+            BindingType::TupleStruct(ty) => {
+                for field in &mut ty.fields {
+                    field.visit_mut(f);
+                }
+            }
+            BindingType::Enum(ty) => {
+                for variant in &mut ty.variants {
+                    match &mut variant.inner_type {
+                        EnumVariantType::NewType(fields) => {
+                            for field in fields {
+                                field.v_type.visit_mut(f);
+                            }
+                        }
+                        EnumVariantType::Tuple(fields) => {
+                            for field in fields {
+                                field.visit_mut(f);
+                            }
+                        }
+                        EnumVariantType::Empty => {}
+                    }
+                }
+            }
+            BindingType::UnitStruct(_) => {}
+        }
+    }
+
+    /// Removes the fields which are not encoded and have no javascript value, e.g.
+    /// `PhantomData`.
+    fn remove_phantom_fields(&mut self) {
+        match &mut self.r#type {
+            BindingType::Struct(ty) => ty.fields.retain(|field| !field.v_type.is_phantom()),
+            BindingType::TupleStruct(ty) => ty.fields.retain(|field| !field.is_phantom()),
+            BindingType::Enum(ty) => {
+                for variant in &mut ty.variants {
+                    match &mut variant.inner_type {
+                        EnumVariantType::NewType(fields) => {
+                            fields.retain(|field| !field.v_type.is_phantom())
+                        }
+                        EnumVariantType::Tuple(fields) => {
+                            fields.retain(|field| !field.is_phantom())
+                        }
+                        EnumVariantType::Empty => {}
+                    }
+                }
+            }
+            BindingType::UnitStruct(_) => {}
+        }
+    }
 }
 
 pub struct ContainerInfo<'a> {
@@ -37,7 +127,7 @@ pub struct ContainerInfo<'a> {
 impl From<&Container> for ContainerInfo<'_> {
     fn from(container: &Container) -> Self {
         Self {
-            name: Cow::Borrowed(container.name),
+            name: container.name.clone(),
             path: container.path.clone(),
         }
     }
@@ -53,8 +143,12 @@ pub enum BindingType {
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 // encoded into | variant index | (inner)
+// or, if internally tagged, into | variant name | (inner)
 pub struct EnumType {
     pub variants: Vec<EnumVariant>,
+    /// Name of the field holding the variant name, set by `#[serde(tag = "...")]`. The
+    /// variant is encoded by its name, followed by the fields of the variant.
+    pub tag: Option<Cow<'static, str>>,
 }
 
 impl EnumType {
@@ -62,31 +156,83 @@ impl EnumType {
         Self::default()
     }
 
-    // index is set based on order of variant registration
-    pub fn register_variant(&mut self, name: &'static str) {
+    /// An internally tagged enum, whose variants are distinguished by the field `tag`.
+    ///
+    /// The bindings decode the values Rust encodes, but Rust can't decode these enums with
+    /// postcard, their `Deserialize` implementation needs `deserialize_any`.
+    pub fn new_internally_tagged(tag: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            variants: Vec::new(),
+            tag: Some(tag.into()),
+        }
+    }
+
+    // index is set based on order of variant registration, continuing after the last registered index
+    pub fn register_variant(&mut self, name: impl Into<Cow<'static, str>>) {
+        self.register_variant_with_index(self.next_index(), name)
+    }
+
+    pub fn register_variant_tuple(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        fields: TupleFields,
+    ) {
+        self.register_variant_tuple_with_index(self.next_index(), name, fields)
+    }
+
+    pub fn register_unnamed_struct(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        fields: StructFields,
+    ) {
+        self.register_unnamed_struct_with_index(self.next_index(), name, fields)
+    }
+
+    /// Registers a unit variant with the explicit variant index used on the wire.
+    pub fn register_variant_with_index(
+        &mut self,
+        index: usize,
+        name: impl Into<Cow<'static, str>>,
+    ) {
         self.variants.push(EnumVariant {
-            index: self.variants.len(),
-            name,
+            index,
+            name: name.into(),
             inner_type: EnumVariantType::Empty,
         });
     }
 
-    pub fn register_variant_tuple(&mut self, name: &'static str, fields: TupleFields) {
+    /// Registers a tuple variant with the explicit variant index used on the wire.
+    pub fn register_variant_tuple_with_index(
+        &mut self,
+        index: usize,
+        name: impl Into<Cow<'static, str>>,
+        fields: TupleFields,
+    ) {
         self.variants.push(EnumVariant {
-            index: self.variants.len(),
-            name,
+            index,
+            name: name.into(),
             inner_type: EnumVariantType::Tuple(fields.into_inner()),
         });
     }
 
-    pub fn register_unnamed_struct(&mut self, name: &'static str, fields: StructFields) {
+    /// Registers a struct variant with the explicit variant index used on the wire.
+    pub fn register_unnamed_struct_with_index(
+        &mut self,
+        index: usize,
+        name: impl Into<Cow<'static, str>>,
+        fields: StructFields,
+    ) {
         self.variants.push(EnumVariant {
-            index: self.variants.len(),
-            name,
+            index,
+            name: name.into(),
             inner_type: EnumVariantType::NewType(fields.into_inner()),
         })
     }
 
+    fn next_index(&self) -> usize {
+        self.variants.last().map_or(0, |v| v.index + 1)
+    }
+
     fn flatten_paths(&mut self) {
         for variant in &mut self.variants {
             match &mut variant.inner_type {
@@ -109,7 +255,7 @@ impl EnumType {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EnumVariant {
     pub index: usize,
-    pub name: &'static str,
+    pub name: Cow<'static, str>,
     pub inner_type: EnumVariantType,
 }
 
@@ -123,7 +269,7 @@ impl AsRef<EnumVariant> for EnumVariant {
 pub enum EnumVariantType {
     Empty,
     Tuple(Vec<ValueType>),
-    // for unnamed structs create struct with custom name ( __EnumName_Struct1)
+    // fields of struct variants are generated inline in the functions of the enum
     NewType(Vec<StructField>),
 }
 
@@ -137,10 +283,100 @@ impl StructType {
         Self::default()
     }
 
-    pub fn register_field<T: GenJsBinding>(&mut self, name: &'static str) {
+    pub fn register_field<T: GenJsBinding>(&mut self, name: impl Into<Cow<'static, str>>) {
         self.fields.push(StructField {
-            name,
+            name: name.into(),
+            v_type: T::get_type(),
+            skip: false,
+            default: None,
+            codec: None,
+            alias: None,
+            has_default: false,
+        })
+    }
+
+    /// Registers a field which is converted by the named codec of the javascript generation
+    /// settings. The python generator uses the plain field type.
+    pub fn register_field_with_codec<T: GenJsBinding>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        codec: &'static str,
+    ) {
+        self.fields.push(StructField {
+            name: name.into(),
+            v_type: T::get_type(),
+            skip: false,
+            default: None,
+            codec: Some(codec.into()),
+            alias: None,
+            has_default: false,
+        })
+    }
+
+    /// Registers a field whose type is a type alias like `type NodeId = u32`. The typescript
+    /// declarations refer to the alias instead of the aliased type.
+    pub fn register_field_with_alias<T: GenJsBinding>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        alias: &'static str,
+    ) {
+        self.fields.push(StructField {
+            name: name.into(),
+            v_type: T::get_type(),
+            skip: false,
+            default: None,
+            codec: None,
+            alias: Some(alias.into()),
+            has_default: false,
+        })
+    }
+
+    /// Registers a field with `#[serde(default)]`, which is set to the given JavaScript
+    /// expression, or the default of the type, when deserializing bytes which end before it.
+    pub fn register_field_with_default<T: GenJsBinding>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        default: Option<&'static str>,
+    ) {
+        self.fields.push(StructField {
+            name: name.into(),
+            v_type: T::get_type(),
+            skip: false,
+            default: default.map(Into::into),
+            codec: None,
+            alias: None,
+            has_default: true,
+        })
+    }
+
+    /// Registers a field which is not part of the encoded bytes (e.g. `#[serde(skip)]`).
+    pub fn register_skipped_field<T: GenJsBinding>(&mut self, name: impl Into<Cow<'static, str>>) {
+        self.fields.push(StructField {
+            name: name.into(),
             v_type: T::get_type(),
+            skip: true,
+            default: None,
+            codec: None,
+            alias: None,
+            has_default: false,
+        })
+    }
+
+    /// Registers a skipped field which is set to the given JavaScript expression
+    /// when deserializing instead of `undefined`.
+    pub fn register_skipped_field_with_default<T: GenJsBinding>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        default: &'static str,
+    ) {
+        self.fields.push(StructField {
+            name: name.into(),
+            v_type: T::get_type(),
+            skip: true,
+            default: Some(default.into()),
+            codec: None,
+            alias: None,
+            has_default: false,
         })
     }
 
@@ -183,18 +419,113 @@ impl UnitStructType {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StructField {
-    pub name: &'static str,
+    pub name: Cow<'static, str>,
     pub v_type: ValueType,
+    // skipped fields are not encoded, they are only part of the object shape
+    pub skip: bool,
+    // expression the deserializer assigns to the field if it has no encoded value
+    pub default: Option<Cow<'static, str>>,
+    // name of the codec converting the encoded value into its javascript representation
+    pub codec: Option<Cow<'static, str>>,
+    // name of the type alias the field is declared with, the typescript declarations use it
+    pub alias: Option<Cow<'static, str>>,
+    // the field is left out of encodings which end before it, like a `#[serde(default)]` field
+    // of an older version of the type. The deserializer assigns `default` then or, if it's not
+    // set, the default of the type. Without a known default the field is always decoded.
+    pub has_default: bool,
 }
 
 #[derive(Debug, Default)]
 pub struct StructFields(Vec<StructField>);
 
 impl StructFields {
-    pub fn register_field<T: GenJsBinding>(&mut self, name: &'static str) {
+    pub fn register_field<T: GenJsBinding>(&mut self, name: impl Into<Cow<'static, str>>) {
         self.0.push(StructField {
-            name,
+            name: name.into(),
             v_type: T::get_type(),
+            skip: false,
+            default: None,
+            codec: None,
+            alias: None,
+            has_default: false,
+        })
+    }
+
+    /// Registers a field which is converted by the named codec of the javascript generation
+    /// settings. The python generator uses the plain field type.
+    pub fn register_field_with_codec<T: GenJsBinding>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        codec: &'static str,
+    ) {
+        self.0.push(StructField {
+            name: name.into(),
+            v_type: T::get_type(),
+            skip: false,
+            default: None,
+            codec: Some(codec.into()),
+            alias: None,
+            has_default: false,
+        })
+    }
+
+    pub fn register_field_with_alias<T: GenJsBinding>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        alias: &'static str,
+    ) {
+        self.0.push(StructField {
+            name: name.into(),
+            v_type: T::get_type(),
+            skip: false,
+            default: None,
+            codec: None,
+            alias: Some(alias.into()),
+            has_default: false,
+        })
+    }
+
+    pub fn register_field_with_default<T: GenJsBinding>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        default: Option<&'static str>,
+    ) {
+        self.0.push(StructField {
+            name: name.into(),
+            v_type: T::get_type(),
+            skip: false,
+            default: default.map(Into::into),
+            codec: None,
+            alias: None,
+            has_default: true,
+        })
+    }
+
+    pub fn register_skipped_field<T: GenJsBinding>(&mut self, name: impl Into<Cow<'static, str>>) {
+        self.0.push(StructField {
+            name: name.into(),
+            v_type: T::get_type(),
+            skip: true,
+            default: None,
+            codec: None,
+            alias: None,
+            has_default: false,
+        })
+    }
+
+    pub fn register_skipped_field_with_default<T: GenJsBinding>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        default: &'static str,
+    ) {
+        self.0.push(StructField {
+            name: name.into(),
+            v_type: T::get_type(),
+            skip: true,
+            default: Some(default.into()),
+            codec: None,
+            alias: None,
+            has_default: false,
         })
     }
 
@@ -288,6 +619,157 @@ impl ContainerCollection {
         }
     }
 
+    /// Sets the width in bytes of all pointer sized integers (`isize` and `usize`).
+    ///
+    /// The width depends on the target which encodes the data, it's 8 bytes by default.
+    pub fn resolve_pointer_width(&mut self, width: usize) {
+        self.for_each_container_mut(|container| {
+            container.visit_value_types_mut(&mut |ty| {
+                if let ValueType::Number(NumberMeta::Integer {
+                    bytes,
+                    pointer_sized: true,
+                    ..
+                }) = ty
+                {
+                    *bytes = width;
+                }
+            });
+        });
+    }
+
+    /// Replaces all sets by sequences of their elements, for languages without a set type.
+    ///
+    /// Sets are encoded like sequences, so the bindings stay compatible.
+    pub fn sets_as_sequences(&mut self) {
+        self.for_each_container_mut(|container| {
+            container.visit_value_types_mut(&mut |ty| {
+                if let ValueType::Set(meta) = ty {
+                    *ty = ValueType::Array(meta.as_sequence());
+                }
+            });
+        });
+    }
+
+    /// Renames the named fields of all types with `f`, for languages which can't use some
+    /// serde names, e.g. `sensor-id` of `#[serde(rename_all = "kebab-case")]`.
+    ///
+    /// Fields are encoded by their position, so the bindings stay compatible.
+    pub fn rename_fields(&mut self, f: impl Fn(Cow<'static, str>) -> Cow<'static, str>) {
+        self.for_each_container_mut(|container| {
+            container.visit_named_fields_mut(&mut |field| {
+                field.name = f(core::mem::take(&mut field.name))
+            });
+        });
+    }
+
+    /// Removes the fields of type `()`, for languages without a unit value.
+    ///
+    /// The fields are encoded as nothing, so the bindings stay compatible. Tuple structs and
+    /// variants left without fields become unit structs and variants.
+    pub fn remove_unit_fields(&mut self) {
+        let is_unit = |v_type: &ValueType| matches!(v_type, ValueType::Unit(_));
+        self.for_each_container_mut(|container| match &mut container.r#type {
+            BindingType::Struct(ty) => ty.fields.retain(|field| !is_unit(&field.v_type)),
+            BindingType::TupleStruct(ty) => {
+                ty.fields.retain(|field| !is_unit(field));
+                if ty.fields.is_empty() {
+                    container.r#type = BindingType::UnitStruct(UnitStructType);
+                }
+            }
+            BindingType::Enum(ty) => {
+                for variant in &mut ty.variants {
+                    if let EnumVariantType::Tuple(fields) = &mut variant.inner_type {
+                        fields.retain(|field| !is_unit(field));
+                        if fields.is_empty() {
+                            variant.inner_type = EnumVariantType::Empty;
+                        }
+                    }
+                }
+            }
+            BindingType::UnitStruct(_) => {}
+        });
+    }
+
+    /// Panics for internally tagged enums, which are encoded by the names of their variants.
+    /// Only the JavaScript bindings support them, `language` names the other bindings in the
+    /// message.
+    pub fn assert_no_internally_tagged_enums(&self, language: &str) {
+        for container in self.all_containers() {
+            if let BindingType::Enum(EnumType { tag: Some(_), .. }) = container.r#type {
+                panic!(
+                    "internally tagged enums like `{}` are not supported by the {language} bindings",
+                    container.name
+                );
+            }
+        }
+    }
+
+    /// Renames the types named `name` and all references to them, returns whether a type was
+    /// renamed.
+    pub fn rename(&mut self, name: &str, new_name: &str) -> bool {
+        let mut renamed = false;
+        self.for_each_container_mut(|container| {
+            if container.name == name {
+                container.name = new_name.to_owned().into();
+                renamed = true;
+            }
+            container.visit_value_types_mut(&mut |ty| match ty {
+                ValueType::Object(meta) if meta.name == name => {
+                    meta.name = new_name.to_owned().into()
+                }
+                _ => (),
+            });
+        });
+        renamed
+    }
+
+    /// Removes the types named `name`, returns whether a type was removed.
+    ///
+    /// Fails with the name of a type referring to it, as the bindings of that type need the
+    /// removed one. Nothing is removed in that case.
+    pub fn exclude(&mut self, name: &str) -> Result<bool, Cow<'static, str>> {
+        for mut container in self.all_containers().filter(|c| c.name != name) {
+            let mut refers = false;
+            container.visit_value_types_mut(&mut |ty| {
+                refers |= matches!(ty, ValueType::Object(meta) if meta.name == name)
+            });
+            if refers {
+                return Err(container.name);
+            }
+        }
+
+        let ids = self
+            .0
+            .get_nodes()
+            .iter()
+            .filter(|node| {
+                node.get_value()
+                    .unwrap()
+                    .container()
+                    .is_some_and(|c| c.name == name)
+            })
+            .map(Node::get_node_id)
+            .collect::<Vec<_>>();
+        for id in &ids {
+            self.0
+                .remove_node(id, NodeRemovalStrategy::RemoveNodeAndChildren)
+                .unwrap();
+        }
+
+        Ok(!ids.is_empty())
+    }
+
+    /// Calls `f` with each type of the collection, in all modules.
+    fn for_each_container_mut(&mut self, mut f: impl FnMut(&mut Container)) {
+        for node in self.0.get_nodes().iter() {
+            node.update_value(|v| {
+                if let Some(container) = v.as_mut().and_then(NodeType::container_mut) {
+                    f(container);
+                }
+            });
+        }
+    }
+
     pub fn all_containers(&self) -> impl Iterator<Item = Container> + Clone + '_ {
         self.0
             .get_nodes()
@@ -299,6 +781,11 @@ impl ContainerCollection {
         let root_node = self.0.get_root_node().unwrap().get_node_id();
         container_and_modules_per_mod(&self.0, &root_node)
     }
+    /// Describes all containers, their fields and types as JSON, see [`json_ir`](crate::json_ir)
+    /// for the format.
+    pub fn to_json(&self) -> String {
+        json_ir::containers_to_json(self.all_containers())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -438,52 +925,73 @@ pub struct BindingsRegistry(Tree<NodeId, NodeType>);
 impl BindingsRegistry {
     pub fn register_struct_binding(
         &mut self,
-        name: &'static str,
+        name: impl Into<Cow<'static, str>>,
         path: impl Into<Cow<'static, str>>,
         value: StructType,
     ) {
         self.insert_container(Container {
             path: Path::new(path, "::"),
-            name,
+            name: name.into(),
             r#type: BindingType::Struct(value),
         });
     }
 
     pub fn register_tuple_struct_binding(
         &mut self,
-        name: &'static str,
+        name: impl Into<Cow<'static, str>>,
         path: impl Into<Cow<'static, str>>,
         value: TupleStructType,
     ) {
         self.insert_container(Container {
             path: Path::new(path, "::"),
-            name,
+            name: name.into(),
             r#type: BindingType::TupleStruct(value),
         });
     }
 
+    /// Registers a bare tuple type like `(u8, String)` under the given name.
+    ///
+    /// The tuple is encoded like a tuple struct and represented as positional array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` is not a tuple type.
+    pub fn register_tuple_binding<T: GenJsBinding>(&mut self, name: impl Into<Cow<'static, str>>) {
+        let name = name.into();
+        let fields = match T::get_type() {
+            ValueType::Tuple(tuple_meta) => tuple_meta.items_types,
+            _ => panic!("`{name}` is registered as tuple binding but is not a tuple"),
+        };
+
+        self.insert_container(Container {
+            path: Path::new("", "::"),
+            name,
+            r#type: BindingType::TupleStruct(TupleStructType { fields }),
+        });
+    }
+
     pub fn register_unit_struct_binding(
         &mut self,
-        name: &'static str,
+        name: impl Into<Cow<'static, str>>,
         path: impl Into<Cow<'static, str>>,
         value: UnitStructType,
     ) {
         self.insert_container(Container {
             path: Path::new(path, "::"),
-            name,
+            name: name.into(),
             r#type: BindingType::UnitStruct(value),
         });
     }
 
     pub fn register_enum_binding(
         &mut self,
-        name: &'static str,
+        name: impl Into<Cow<'static, str>>,
         path: impl Into<Cow<'static, str>>,
         value: EnumType,
     ) {
         self.insert_container(Container {
             path: Path::new(path, "::"),
-            name,
+            name: name.into(),
             r#type: BindingType::Enum(value),
         });
     }
@@ -492,7 +1000,20 @@ impl BindingsRegistry {
         ContainerCollection(self.0)
     }
 
-    fn insert_container(&mut self, container: Container) {
+    /// Describes all registered containers, their fields and types as JSON, see
+    /// [`json_ir`](crate::json_ir) for the format.
+    pub fn to_json(&self) -> String {
+        json_ir::containers_to_json(
+            self.0
+                .get_nodes()
+                .iter()
+                .filter_map(|node| node.get_value().unwrap().container().cloned()),
+        )
+    }
+
+    fn insert_container(&mut self, mut container: Container) {
+        container.remove_phantom_fields();
+
         let mut node = self.0.get_root_node().unwrap();
         let node_id = {
             let container_path = &container.path;
@@ -577,11 +1098,129 @@ pub trait JsBindings {
     fn create_bindings(registry: &mut BindingsRegistry);
 }
 
+// `Result` is encoded like an enum with the variants `Ok` and `Err`. Like generic user types,
+// each instantiation is registered as its own type, e.g. `Result_u8_String`.
+impl<T: GenJsBinding, E: GenJsBinding> JsBindings for Result<T, E> {
+    fn create_bindings(registry: &mut BindingsRegistry) {
+        let mut ok = TupleFields::default();
+        ok.register_field::<T>();
+        let mut err = TupleFields::default();
+        err.register_field::<E>();
+
+        let mut ty = EnumType::new();
+        ty.register_variant_tuple("Ok", ok);
+        ty.register_variant_tuple("Err", err);
+
+        let ValueType::Object(meta) = Self::get_type() else {
+            unreachable!("results are objects")
+        };
+        registry.register_enum_binding(meta.name, "", ty);
+    }
+}
+
+// serde encodes `Either` like an enum with the variants `Left` and `Right`, each instantiation
+// is registered as its own type, e.g. `Either_u8_String`.
+#[cfg(feature = "either")]
+impl<L: GenJsBinding, R: GenJsBinding> JsBindings for either::Either<L, R> {
+    fn create_bindings(registry: &mut BindingsRegistry) {
+        let mut left = TupleFields::default();
+        left.register_field::<L>();
+        let mut right = TupleFields::default();
+        right.register_field::<R>();
+
+        let mut ty = EnumType::new();
+        ty.register_variant_tuple("Left", left);
+        ty.register_variant_tuple("Right", right);
+
+        let ValueType::Object(meta) = Self::get_type() else {
+            unreachable!("eithers are objects")
+        };
+        registry.register_enum_binding(meta.name, "", ty);
+    }
+}
+
+// Durations and points in time are encoded as structs of the seconds and the nanoseconds of
+// the last second, `SystemTime` relative to the unix epoch.
+fn register_time_binding(registry: &mut BindingsRegistry, name: &'static str) {
+    let mut ty = StructType::new();
+    ty.register_field::<u64>("secs");
+    ty.register_field::<u32>("nanos");
+    registry.register_struct_binding(name, "", ty);
+}
+
+impl JsBindings for core::time::Duration {
+    fn create_bindings(registry: &mut BindingsRegistry) {
+        register_time_binding(registry, "Duration");
+    }
+}
+
+impl JsBindings for std::time::SystemTime {
+    fn create_bindings(registry: &mut BindingsRegistry) {
+        register_time_binding(registry, "SystemTime");
+    }
+}
+
+// Binary formats encode IP addresses as enums of the versions and socket addresses as tuples of
+// the address and the port. The flow info and scope id of a `SocketAddrV6` are not encoded.
+impl JsBindings for std::net::IpAddr {
+    fn create_bindings(registry: &mut BindingsRegistry) {
+        let mut v4 = TupleFields::default();
+        v4.register_field::<std::net::Ipv4Addr>();
+        let mut v6 = TupleFields::default();
+        v6.register_field::<std::net::Ipv6Addr>();
+
+        let mut ty = EnumType::new();
+        ty.register_variant_tuple("V4", v4);
+        ty.register_variant_tuple("V6", v6);
+        registry.register_enum_binding("IpAddr", "", ty);
+    }
+}
+
+fn register_socket_addr_binding<Ip: GenJsBinding>(
+    registry: &mut BindingsRegistry,
+    name: &'static str,
+) {
+    let mut ty = StructType::new();
+    ty.register_field::<Ip>("ip");
+    ty.register_field::<u16>("port");
+    registry.register_struct_binding(name, "", ty);
+}
+
+impl JsBindings for std::net::SocketAddrV4 {
+    fn create_bindings(registry: &mut BindingsRegistry) {
+        register_socket_addr_binding::<std::net::Ipv4Addr>(registry, "SocketAddrV4");
+    }
+}
+
+impl JsBindings for std::net::SocketAddrV6 {
+    fn create_bindings(registry: &mut BindingsRegistry) {
+        register_socket_addr_binding::<std::net::Ipv6Addr>(registry, "SocketAddrV6");
+    }
+}
+
+// The variants refer to `SocketAddrV4` and `SocketAddrV6`, they have to be registered as well.
+impl JsBindings for std::net::SocketAddr {
+    fn create_bindings(registry: &mut BindingsRegistry) {
+        let mut v4 = TupleFields::default();
+        v4.register_field::<std::net::SocketAddrV4>();
+        let mut v6 = TupleFields::default();
+        v6.register_field::<std::net::SocketAddrV6>();
+
+        let mut ty = EnumType::new();
+        ty.register_variant_tuple("V4", v4);
+        ty.register_variant_tuple("V6", v6);
+        registry.register_enum_binding("SocketAddr", "", ty);
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::registry::{
-        BindingsRegistry, EnumType, JsBindings, StructFields, StructType, TupleFields,
-        TupleStructType,
+    use crate::{
+        registry::{
+            BindingType, BindingsRegistry, EnumType, JsBindings, StructFields, StructType,
+            TupleFields, TupleStructType,
+        },
+        type_info::GenJsBinding,
     };
 
     #[test]
@@ -630,6 +1269,30 @@ mod test {
         Test::create_bindings(&mut registry);
     }
 
+    #[test]
+    fn test_registry_tuple() {
+        let mut registry = BindingsRegistry::default();
+        registry.register_tuple_binding::<(u8, &str)>("Args");
+
+        let containers = registry.into_entries();
+        let container = containers.all_containers().next().unwrap();
+
+        assert_eq!(container.name, "Args");
+        assert_eq!(
+            container.r#type,
+            BindingType::TupleStruct(TupleStructType {
+                fields: vec![u8::get_type(), <&str>::get_type()]
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_registry_tuple_not_a_tuple() {
+        let mut registry = BindingsRegistry::default();
+        registry.register_tuple_binding::<u8>("Args");
+    }
+
     #[test]
     fn test_registry_enum() {
         #[allow(unused)]
@@ -661,4 +1324,16 @@ mod test {
         let mut registry = BindingsRegistry::default();
         Test::create_bindings(&mut registry);
     }
+
+    #[test]
+    fn test_enum_variant_indices() {
+        let mut ty = EnumType::new();
+
+        ty.register_variant("A");
+        ty.register_variant_with_index(3, "B");
+        ty.register_variant_tuple("C", TupleFields::default());
+
+        let indices = ty.variants.iter().map(|v| v.index).collect::<Vec<_>>();
+        assert_eq!(indices, [0, 3, 4]);
+    }
 }
@@ -1,4 +1,12 @@
-use alloc::{boxed::Box, vec, vec::Vec};
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::Display;
+use std::borrow::Cow;
 
 use crate::path::Path;
 
@@ -11,8 +19,10 @@ pub enum ValueType {
     Optional(OptionalMeta),
     Range(RangeMeta),
     Map(MapMeta),
+    Set(SetMeta),
     Tuple(TupleMeta),
     Bool(BoolMeta),
+    Unit(UnitMeta),
 }
 
 impl ValueType {
@@ -31,6 +41,9 @@ impl ValueType {
                 meta.key_type.flatten_paths();
                 meta.value_type.flatten_paths();
             }
+            ValueType::Set(meta) => {
+                meta.items_type.flatten_paths();
+            }
             ValueType::Tuple(meta) => {
                 for item in meta.items_types.iter_mut() {
                     item.flatten_paths();
@@ -41,6 +54,116 @@ impl ValueType {
     }
 }
 
+impl ValueType {
+    /// Whether the type is encoded as zero bytes and has no value, like `PhantomData`. In
+    /// contrast to that, `()` is a value.
+    pub(crate) fn is_phantom(&self) -> bool {
+        matches!(self, ValueType::Tuple(meta) if meta.items_types.is_empty())
+    }
+}
+
+impl ValueType {
+    /// Calls `f` with this value type and all value types nested in it.
+    pub(crate) fn visit_mut(&mut self, f: &mut impl FnMut(&mut ValueType)) {
+        f(self);
+        match self {
+            ValueType::Array(meta) => meta.items_type.visit_mut(f),
+            ValueType::Optional(meta) => meta.inner.visit_mut(f),
+            ValueType::Range(meta) => meta.bounds_type.visit_mut(f),
+            ValueType::Map(meta) => {
+                meta.key_type.visit_mut(f);
+                meta.value_type.visit_mut(f);
+            }
+            ValueType::Set(meta) => meta.items_type.visit_mut(f),
+            ValueType::Tuple(meta) => {
+                for item in meta.items_types.iter_mut() {
+                    item.visit_mut(f);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl ValueType {
+    /// Name of the type as part of a generated name, e.g. `u32`, `String` or `Vec_u8`.
+    pub(crate) fn name_part(&self) -> String {
+        match self {
+            ValueType::Number(NumberMeta::Integer {
+                bytes,
+                signed,
+                pointer_sized,
+                non_zero: true,
+            }) => {
+                let integer = ValueType::Number(NumberMeta::Integer {
+                    bytes: *bytes,
+                    signed: *signed,
+                    pointer_sized: *pointer_sized,
+                    non_zero: false,
+                });
+                format!("NonZero_{}", integer.name_part())
+            }
+            ValueType::Number(NumberMeta::Integer {
+                pointer_sized: true,
+                signed,
+                ..
+            }) => if *signed { "isize" } else { "usize" }.to_string(),
+            ValueType::Number(NumberMeta::Integer { bytes, signed, .. }) => {
+                format!("{}{}", if *signed { "i" } else { "u" }, bytes * 8)
+            }
+            ValueType::Number(NumberMeta::FloatingPoint { bytes }) => format!("f{}", bytes * 8),
+            ValueType::String(StringMeta { char: true, .. }) => "char".to_string(),
+            ValueType::String(_) => "String".to_string(),
+            ValueType::Bool(_) => "bool".to_string(),
+            ValueType::Unit(_) => "unit".to_string(),
+            ValueType::Object(meta) => meta.name.to_string(),
+            ValueType::Optional(meta) => format!("Option_{}", meta.inner.name_part()),
+            ValueType::Range(meta) => format!("Range_{}", meta.bounds_type.name_part()),
+            ValueType::Array(ArrayMeta {
+                items_type,
+                length: Some(length),
+                ..
+            }) => format!("Array{length}_{}", items_type.name_part()),
+            ValueType::Array(meta) => format!("Vec_{}", meta.items_type.name_part()),
+            ValueType::Map(meta) => format!(
+                "Map_{}_{}",
+                meta.key_type.name_part(),
+                meta.value_type.name_part()
+            ),
+            ValueType::Set(meta) => format!("Set_{}", meta.items_type.name_part()),
+            ValueType::Tuple(meta) => meta
+                .items_types
+                .iter()
+                .fold("Tuple".to_string(), |name, item| {
+                    format!("{name}_{}", item.name_part())
+                }),
+        }
+    }
+}
+
+/// Name of a generic type instantiated with the given type arguments, e.g. `Wrapper_u32` for
+/// `Wrapper<u32>`.
+///
+/// Each instantiation is registered as its own type, so the generated bindings need a distinct
+/// name per instantiation.
+pub fn monomorphized_name(name: &str, type_args: &[ValueType]) -> String {
+    monomorphized_name_with_consts(name, type_args, &[])
+}
+
+/// Like [`monomorphized_name`], with the values of const generics after the type arguments,
+/// e.g. `Frame_u8_16` for `Frame<u8, 16>`.
+pub fn monomorphized_name_with_consts(
+    name: &str,
+    type_args: &[ValueType],
+    const_args: &[&dyn Display],
+) -> String {
+    type_args
+        .iter()
+        .map(ValueType::name_part)
+        .chain(const_args.iter().map(ToString::to_string))
+        .fold(name.to_string(), |name, arg| format!("{name}_{arg}"))
+}
+
 impl AsRef<ValueType> for ValueType {
     fn as_ref(&self) -> &ValueType {
         self
@@ -53,9 +176,29 @@ pub struct MapMeta {
     pub(crate) value_type: Box<ValueType>,
 }
 
+/// Sets are encoded like sequences, the elements are unique but their order is not defined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetMeta {
+    pub(crate) items_type: Box<ValueType>,
+}
+
+impl SetMeta {
+    /// The sequence the set is encoded as.
+    pub(crate) fn as_sequence(&self) -> ArrayMeta {
+        ArrayMeta {
+            items_type: self.items_type.clone(),
+            length: None,
+            capacity: None,
+            format: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RangeMeta {
     pub(crate) bounds_type: Box<ValueType>,
+    /// A `RangeInclusive`, encoded like a `Range` but `end` is part of the range.
+    pub(crate) inclusive: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -65,8 +208,18 @@ pub struct OptionalMeta {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NumberMeta {
-    Integer { bytes: usize, signed: bool },
-    FloatingPoint { bytes: usize },
+    Integer {
+        bytes: usize,
+        signed: bool,
+        /// `isize` or `usize`, the width of these depends on the target which encodes the
+        /// data. `bytes` is replaced by the configured pointer width at generation.
+        pointer_sized: bool,
+        /// `NonZeroU8`, `NonZeroI32`, ..., encoded like the integer but never zero.
+        non_zero: bool,
+    },
+    FloatingPoint {
+        bytes: usize,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -74,14 +227,52 @@ pub struct ArrayMeta {
     // Boxed to avoid infinite recursion
     pub(crate) items_type: Box<ValueType>,
     pub(crate) length: Option<usize>,
+    /// The maximum length of a sequence with a fixed capacity, e.g. a `heapless::Vec` or an
+    /// `arrayvec::ArrayVec`.
+    pub(crate) capacity: Option<usize>,
+    /// The layout of sequences which represent a parsed type, checked at runtime.
+    pub(crate) format: Option<ArrayFormat>,
+}
+
+/// Layout of a sequence, e.g. of a type which is serialized as byte string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrayFormat {
+    /// The 16 bytes of a `uuid::Uuid`.
+    Uuid,
+}
+
+impl ArrayFormat {
+    /// The number of items a sequence of this format has.
+    pub(crate) fn length(self) -> usize {
+        match self {
+            Self::Uuid => 16,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StringMeta {
+    /// A `char`, encoded as string of a single unicode scalar value.
+    pub(crate) char: bool,
+    /// The maximum length in bytes of a string with a fixed capacity, e.g. a
+    /// `heapless::String`.
+    pub(crate) capacity: Option<usize>,
+    /// The syntax of strings which represent a parsed type, checked at runtime.
+    pub(crate) format: Option<StringFormat>,
 }
 
+/// Syntax of a string, e.g. of a type which is serialized as string.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct StringMeta {}
+pub enum StringFormat {
+    /// An absolute URL like `https://example.com/path`, e.g. a `url::Url`.
+    Url,
+    /// A semantic version like `1.2.3-beta.1`, e.g. a `semver::Version`.
+    SemVer,
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ObjectMeta {
-    pub name: &'static str,
+    pub name: Cow<'static, str>,
     pub path: Path<'static, 'static>,
 }
 
@@ -93,11 +284,26 @@ pub struct TupleMeta {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BoolMeta;
 
+/// The unit type `()`, encoded as nothing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnitMeta;
+
+#[diagnostic::on_unimplemented(
+    message = "no bindings can be generated for `{Self}`",
+    note = "trait objects like `Box<dyn Trait>` have no fixed wire format and can't be supported"
+)]
 pub trait GenJsBinding {
     fn get_type() -> ValueType;
 }
 
-impl<T: GenJsBinding> GenJsBinding for &mut T {
+// Borrowed fields like `&'a str` or `&'a [u8]` are encoded like the owned types.
+impl<T: GenJsBinding + ?Sized> GenJsBinding for &T {
+    fn get_type() -> ValueType {
+        T::get_type()
+    }
+}
+
+impl<T: GenJsBinding + ?Sized> GenJsBinding for &mut T {
     fn get_type() -> ValueType {
         T::get_type()
     }
@@ -105,11 +311,19 @@ impl<T: GenJsBinding> GenJsBinding for &mut T {
 
 macro_rules! impl_gen_js_binding_numbers_ints {
     ($ty:ty, $bytes:expr, $signed:ident) => {
+        impl_gen_js_binding_numbers_ints!($ty, $bytes, $signed, false);
+    };
+    ($ty:ty, $bytes:expr, $signed:ident, $pointer_sized:ident) => {
+        impl_gen_js_binding_numbers_ints!($ty, $bytes, $signed, $pointer_sized, false);
+    };
+    ($ty:ty, $bytes:expr, $signed:ident, $pointer_sized:ident, $non_zero:ident) => {
         impl GenJsBinding for $ty {
             fn get_type() -> ValueType {
                 ValueType::Number(NumberMeta::Integer {
                     bytes: $bytes,
                     signed: $signed,
+                    pointer_sized: $pointer_sized,
+                    non_zero: $non_zero,
                 })
             }
         }
@@ -131,16 +345,28 @@ impl_gen_js_binding_numbers_ints!(u16, 2, false);
 impl_gen_js_binding_numbers_ints!(u32, 4, false);
 impl_gen_js_binding_numbers_ints!(u64, 8, false);
 impl_gen_js_binding_numbers_ints!(u128, 16, false);
-// TODO check for operating system
-impl_gen_js_binding_numbers_ints!(usize, 4, false);
+impl_gen_js_binding_numbers_ints!(usize, 8, false, true);
 
 impl_gen_js_binding_numbers_ints!(i8, 1, true);
 impl_gen_js_binding_numbers_ints!(i16, 2, true);
 impl_gen_js_binding_numbers_ints!(i32, 4, true);
 impl_gen_js_binding_numbers_ints!(i64, 8, true);
 impl_gen_js_binding_numbers_ints!(i128, 16, true);
-// TODO check for operating system
-impl_gen_js_binding_numbers_ints!(isize, 4, true);
+impl_gen_js_binding_numbers_ints!(isize, 8, true, true);
+
+impl_gen_js_binding_numbers_ints!(core::num::NonZeroU8, 1, false, false, true);
+impl_gen_js_binding_numbers_ints!(core::num::NonZeroU16, 2, false, false, true);
+impl_gen_js_binding_numbers_ints!(core::num::NonZeroU32, 4, false, false, true);
+impl_gen_js_binding_numbers_ints!(core::num::NonZeroU64, 8, false, false, true);
+impl_gen_js_binding_numbers_ints!(core::num::NonZeroU128, 16, false, false, true);
+impl_gen_js_binding_numbers_ints!(core::num::NonZeroUsize, 8, false, true, true);
+
+impl_gen_js_binding_numbers_ints!(core::num::NonZeroI8, 1, true, false, true);
+impl_gen_js_binding_numbers_ints!(core::num::NonZeroI16, 2, true, false, true);
+impl_gen_js_binding_numbers_ints!(core::num::NonZeroI32, 4, true, false, true);
+impl_gen_js_binding_numbers_ints!(core::num::NonZeroI64, 8, true, false, true);
+impl_gen_js_binding_numbers_ints!(core::num::NonZeroI128, 16, true, false, true);
+impl_gen_js_binding_numbers_ints!(core::num::NonZeroIsize, 8, true, true, true);
 
 impl_gen_js_binding_numbers_floats!(f32, 4);
 impl_gen_js_binding_numbers_floats!(f64, 8);
@@ -153,47 +379,160 @@ impl<T: GenJsBinding> GenJsBinding for Option<T> {
     }
 }
 
-impl<T: GenJsBinding> GenJsBinding for &[T] {
+impl<T: GenJsBinding> GenJsBinding for [T] {
     fn get_type() -> ValueType {
         ValueType::Array(ArrayMeta {
             items_type: Box::new(T::get_type()),
             length: None,
+            capacity: None,
+            format: None,
         })
     }
 }
 
-impl<T: GenJsBinding> GenJsBinding for [T] {
+impl<T: GenJsBinding, const S: usize> GenJsBinding for [T; S] {
     fn get_type() -> ValueType {
         ValueType::Array(ArrayMeta {
             items_type: Box::new(T::get_type()),
-            length: None,
+            length: Some(S),
+            capacity: None,
+            format: None,
         })
     }
 }
 
-impl<T: GenJsBinding, const S: usize> GenJsBinding for [T; S] {
+impl GenJsBinding for str {
     fn get_type() -> ValueType {
-        ValueType::Array(ArrayMeta {
-            items_type: Box::new(T::get_type()),
-            length: Some(S),
+        ValueType::String(StringMeta {
+            char: false,
+            capacity: None,
+            format: None,
         })
     }
 }
 
-impl GenJsBinding for &str {
+impl<T: GenJsBinding> GenJsBinding for core::ops::Range<T> {
     fn get_type() -> ValueType {
-        ValueType::String(StringMeta {})
+        ValueType::Range(RangeMeta {
+            bounds_type: Box::new(T::get_type()),
+            inclusive: false,
+        })
     }
 }
 
-impl<T: GenJsBinding> GenJsBinding for core::ops::Range<T> {
+impl<T: GenJsBinding> GenJsBinding for core::ops::RangeInclusive<T> {
     fn get_type() -> ValueType {
         ValueType::Range(RangeMeta {
             bounds_type: Box::new(T::get_type()),
+            inclusive: true,
+        })
+    }
+}
+
+// Registered as an enum by its `JsBindings` implementation.
+impl<T: GenJsBinding, E: GenJsBinding> GenJsBinding for Result<T, E> {
+    fn get_type() -> ValueType {
+        ValueType::Object(ObjectMeta {
+            name: monomorphized_name("Result", &[T::get_type(), E::get_type()]).into(),
+            path: Path::new("", "::"),
+        })
+    }
+}
+
+// Registered as an enum like `Result`.
+#[cfg(feature = "either")]
+impl<L: GenJsBinding, R: GenJsBinding> GenJsBinding for either::Either<L, R> {
+    fn get_type() -> ValueType {
+        ValueType::Object(ObjectMeta {
+            name: monomorphized_name("Either", &[L::get_type(), R::get_type()]).into(),
+            path: Path::new("", "::"),
+        })
+    }
+}
+
+// Registered as structs by their `JsBindings` implementations.
+impl GenJsBinding for core::time::Duration {
+    fn get_type() -> ValueType {
+        ValueType::Object(ObjectMeta {
+            name: "Duration".into(),
+            path: Path::new("", "::"),
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl GenJsBinding for std::time::SystemTime {
+    fn get_type() -> ValueType {
+        ValueType::Object(ObjectMeta {
+            name: "SystemTime".into(),
+            path: Path::new("", "::"),
+        })
+    }
+}
+
+// serde serializes paths as strings and fails for paths which aren't valid UTF-8.
+#[cfg(feature = "std")]
+impl GenJsBinding for std::path::Path {
+    fn get_type() -> ValueType {
+        ValueType::String(StringMeta {
+            char: false,
+            capacity: None,
+            format: None,
         })
     }
 }
 
+#[cfg(feature = "std")]
+impl GenJsBinding for std::path::PathBuf {
+    fn get_type() -> ValueType {
+        std::path::Path::get_type()
+    }
+}
+
+// Binary formats encode the addresses as their octets, a fixed size array without length.
+#[cfg(feature = "std")]
+impl GenJsBinding for std::net::Ipv4Addr {
+    fn get_type() -> ValueType {
+        ValueType::Array(ArrayMeta {
+            items_type: Box::new(u8::get_type()),
+            length: Some(4),
+            capacity: None,
+            format: None,
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl GenJsBinding for std::net::Ipv6Addr {
+    fn get_type() -> ValueType {
+        ValueType::Array(ArrayMeta {
+            items_type: Box::new(u8::get_type()),
+            length: Some(16),
+            capacity: None,
+            format: None,
+        })
+    }
+}
+
+// Registered as enums and structs by their `JsBindings` implementations.
+macro_rules! net_impls {
+    ($($ty:ident),+) => {
+        $(
+            #[cfg(feature = "std")]
+            impl GenJsBinding for std::net::$ty {
+                fn get_type() -> ValueType {
+                    ValueType::Object(ObjectMeta {
+                        name: stringify!($ty).into(),
+                        path: Path::new("", "::"),
+                    })
+                }
+            }
+        )+
+    };
+}
+
+net_impls!(IpAddr, SocketAddr, SocketAddrV4, SocketAddrV6);
+
 macro_rules! tuple_impls {
     ($($($name:ident)+),+) => {
         $(
@@ -233,6 +572,32 @@ tuple_impls! {
     T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14
 }
 
+// Serialized as unit struct, which is encoded as nothing. Fields of this type are removed
+// when the container is registered.
+impl<T: ?Sized> GenJsBinding for core::marker::PhantomData<T> {
+    fn get_type() -> ValueType {
+        ValueType::Tuple(TupleMeta {
+            items_types: vec![],
+        })
+    }
+}
+
+impl GenJsBinding for char {
+    fn get_type() -> ValueType {
+        ValueType::String(StringMeta {
+            char: true,
+            capacity: None,
+            format: None,
+        })
+    }
+}
+
+impl GenJsBinding for () {
+    fn get_type() -> ValueType {
+        ValueType::Unit(UnitMeta)
+    }
+}
+
 impl GenJsBinding for bool {
     fn get_type() -> ValueType {
         ValueType::Bool(BoolMeta)
@@ -249,10 +614,23 @@ impl<K: GenJsBinding, V: GenJsBinding> GenJsBinding for alloc::collections::BTre
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<T: GenJsBinding> GenJsBinding for alloc::collections::BTreeSet<T> {
+    fn get_type() -> ValueType {
+        ValueType::Set(SetMeta {
+            items_type: Box::new(T::get_type()),
+        })
+    }
+}
+
 #[cfg(feature = "alloc")]
 impl GenJsBinding for alloc::string::String {
     fn get_type() -> ValueType {
-        ValueType::String(StringMeta {})
+        ValueType::String(StringMeta {
+            char: false,
+            capacity: None,
+            format: None,
+        })
     }
 }
 
@@ -262,26 +640,46 @@ impl<T: GenJsBinding> GenJsBinding for alloc::vec::Vec<T> {
         ValueType::Array(ArrayMeta {
             items_type: Box::new(T::get_type()),
             length: None,
+            capacity: None,
+            format: None,
         })
     }
 }
 
+// Smart pointers are serialized like the value they point to.
 #[cfg(feature = "alloc")]
-impl<T: GenJsBinding> GenJsBinding for alloc::rc::Rc<T> {
+impl<T: GenJsBinding + ?Sized> GenJsBinding for Box<T> {
     fn get_type() -> ValueType {
         T::get_type()
     }
 }
 
 #[cfg(feature = "alloc")]
-impl<T: GenJsBinding> GenJsBinding for alloc::sync::Arc<T> {
+impl<T: GenJsBinding + ?Sized> GenJsBinding for alloc::rc::Rc<T> {
     fn get_type() -> ValueType {
         T::get_type()
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<T: GenJsBinding + ?Sized> GenJsBinding for alloc::sync::Arc<T> {
+    fn get_type() -> ValueType {
+        T::get_type()
+    }
+}
+
+// Borrowed or owned, `Cow<str>` and `Cow<[u8]>` are encoded like `String` and `Vec<u8>`.
+#[cfg(feature = "alloc")]
+impl<T: GenJsBinding + alloc::borrow::ToOwned + ?Sized> GenJsBinding for alloc::borrow::Cow<'_, T> {
+    fn get_type() -> ValueType {
+        T::get_type()
+    }
+}
+
+// The hasher isn't part of the encoding, so maps with custom hashers (e.g. `FxHashMap`) are
+// supported as well.
 #[cfg(feature = "std")]
-impl<K: GenJsBinding, V: GenJsBinding> GenJsBinding for std::collections::HashMap<K, V> {
+impl<K: GenJsBinding, V: GenJsBinding, S> GenJsBinding for std::collections::HashMap<K, V, S> {
     fn get_type() -> ValueType {
         ValueType::Map(MapMeta {
             key_type: Box::new(K::get_type()),
@@ -290,6 +688,15 @@ impl<K: GenJsBinding, V: GenJsBinding> GenJsBinding for std::collections::HashMa
     }
 }
 
+#[cfg(feature = "std")]
+impl<T: GenJsBinding, S> GenJsBinding for std::collections::HashSet<T, S> {
+    fn get_type() -> ValueType {
+        ValueType::Set(SetMeta {
+            items_type: Box::new(T::get_type()),
+        })
+    }
+}
+
 #[cfg(feature = "std")]
 impl<T: GenJsBinding> GenJsBinding for std::sync::RwLock<T> {
     fn get_type() -> ValueType {
@@ -297,12 +704,106 @@ impl<T: GenJsBinding> GenJsBinding for std::sync::RwLock<T> {
     }
 }
 
+// chrono serializes dates and times as strings, e.g. `2024-01-31T12:00:00Z` for a
+// `DateTime<Utc>`, `2024-01-31` for a `NaiveDate` and `2024-01-31T12:00:00` for a
+// `NaiveDateTime`.
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> GenJsBinding for chrono::DateTime<Tz> {
+    fn get_type() -> ValueType {
+        ValueType::String(StringMeta {
+            char: false,
+            capacity: None,
+            format: None,
+        })
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl GenJsBinding for chrono::NaiveDate {
+    fn get_type() -> ValueType {
+        ValueType::String(StringMeta {
+            char: false,
+            capacity: None,
+            format: None,
+        })
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl GenJsBinding for chrono::NaiveDateTime {
+    fn get_type() -> ValueType {
+        ValueType::String(StringMeta {
+            char: false,
+            capacity: None,
+            format: None,
+        })
+    }
+}
+
+// Serialized as byte string of its 16 bytes by binary formats like postcard.
+#[cfg(feature = "uuid")]
+impl GenJsBinding for uuid::Uuid {
+    fn get_type() -> ValueType {
+        ValueType::Array(ArrayMeta {
+            items_type: Box::new(u8::get_type()),
+            length: None,
+            capacity: None,
+            format: Some(ArrayFormat::Uuid),
+        })
+    }
+}
+
+// Serialized as strings, which the runtime type checks validate.
+#[cfg(feature = "url")]
+impl GenJsBinding for url::Url {
+    fn get_type() -> ValueType {
+        ValueType::String(StringMeta {
+            char: false,
+            capacity: None,
+            format: Some(StringFormat::Url),
+        })
+    }
+}
+
+#[cfg(feature = "semver")]
+impl GenJsBinding for semver::Version {
+    fn get_type() -> ValueType {
+        ValueType::String(StringMeta {
+            char: false,
+            capacity: None,
+            format: Some(StringFormat::SemVer),
+        })
+    }
+}
+
+// Byte strings, encoded like `Vec<u8>`.
+#[cfg(feature = "serde_bytes")]
+impl GenJsBinding for serde_bytes::Bytes {
+    fn get_type() -> ValueType {
+        ValueType::Array(ArrayMeta {
+            items_type: Box::new(u8::get_type()),
+            length: None,
+            capacity: None,
+            format: None,
+        })
+    }
+}
+
+#[cfg(feature = "serde_bytes")]
+impl GenJsBinding for serde_bytes::ByteBuf {
+    fn get_type() -> ValueType {
+        serde_bytes::Bytes::get_type()
+    }
+}
+
 #[cfg(feature = "heapless")]
 impl<T: GenJsBinding, const N: usize> GenJsBinding for heapless::Vec<T, N> {
     fn get_type() -> ValueType {
         ValueType::Array(ArrayMeta {
             items_type: Box::new(T::get_type()),
             length: None,
+            capacity: Some(N),
+            format: None,
         })
     }
 }
@@ -310,7 +811,11 @@ impl<T: GenJsBinding, const N: usize> GenJsBinding for heapless::Vec<T, N> {
 #[cfg(feature = "heapless")]
 impl<const N: usize> GenJsBinding for heapless::String<N> {
     fn get_type() -> ValueType {
-        ValueType::String(StringMeta {})
+        ValueType::String(StringMeta {
+            char: false,
+            capacity: Some(N),
+            format: None,
+        })
     }
 }
 
@@ -325,3 +830,83 @@ impl<K: GenJsBinding, V: GenJsBinding, const N: usize> GenJsBinding
         })
     }
 }
+
+#[cfg(feature = "heapless")]
+impl<K: GenJsBinding, V: GenJsBinding, S, const N: usize> GenJsBinding
+    for heapless::IndexMap<K, V, S, N>
+{
+    fn get_type() -> ValueType {
+        ValueType::Map(MapMeta {
+            key_type: Box::new(K::get_type()),
+            value_type: Box::new(V::get_type()),
+        })
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<T: GenJsBinding, S, const N: usize> GenJsBinding for heapless::IndexSet<T, S, N> {
+    fn get_type() -> ValueType {
+        ValueType::Set(SetMeta {
+            items_type: Box::new(T::get_type()),
+        })
+    }
+}
+
+// Encoded like the std collections, in insertion order.
+#[cfg(feature = "indexmap")]
+impl<K: GenJsBinding, V: GenJsBinding, S> GenJsBinding for indexmap::IndexMap<K, V, S> {
+    fn get_type() -> ValueType {
+        ValueType::Map(MapMeta {
+            key_type: Box::new(K::get_type()),
+            value_type: Box::new(V::get_type()),
+        })
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<T: GenJsBinding, S> GenJsBinding for indexmap::IndexSet<T, S> {
+    fn get_type() -> ValueType {
+        ValueType::Set(SetMeta {
+            items_type: Box::new(T::get_type()),
+        })
+    }
+}
+
+// The inline capacity of a `SmallVec` doesn't limit its length, it spills onto the heap.
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> GenJsBinding for smallvec::SmallVec<A>
+where
+    A::Item: GenJsBinding,
+{
+    fn get_type() -> ValueType {
+        ValueType::Array(ArrayMeta {
+            items_type: Box::new(A::Item::get_type()),
+            length: None,
+            capacity: None,
+            format: None,
+        })
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<T: GenJsBinding, const N: usize> GenJsBinding for arrayvec::ArrayVec<T, N> {
+    fn get_type() -> ValueType {
+        ValueType::Array(ArrayMeta {
+            items_type: Box::new(T::get_type()),
+            length: None,
+            capacity: Some(N),
+            format: None,
+        })
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<const N: usize> GenJsBinding for arrayvec::ArrayString<N> {
+    fn get_type() -> ValueType {
+        ValueType::String(StringMeta {
+            char: false,
+            capacity: Some(N),
+            format: None,
+        })
+    }
+}
@@ -0,0 +1,47 @@
+mod registry;
+
+use postcard_bindgen_core::{
+    code_gen::cpp::{generate, GenerationSettings},
+    path::Path,
+    registry::{BindingsRegistry, StructType},
+    type_info::{GenJsBinding, ObjectMeta, ValueType},
+};
+
+use registry::init_registry;
+
+#[test]
+fn test_header() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all();
+    let exports = generate(containers, gen_settings, "test".to_owned());
+
+    let header_file = exports.file("header").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(header_file);
+}
+
+#[test]
+#[should_panic(expected = "`Node` contains itself by value")]
+fn test_recursion_by_value_panics() {
+    struct Node;
+    impl GenJsBinding for Node {
+        fn get_type() -> ValueType {
+            ValueType::Object(ObjectMeta {
+                name: "Node".into(),
+                path: Path::new("main_crate", "::"),
+            })
+        }
+    }
+
+    let mut registry = BindingsRegistry::default();
+    let mut struct_type = StructType::new();
+    struct_type.register_field::<Option<Node>>("next");
+    struct_type.register_field::<Vec<Node>>("children");
+    registry.register_struct_binding("Node", "main_crate", struct_type);
+
+    generate(
+        registry.into_entries(),
+        GenerationSettings::enable_all(),
+        "test".to_owned(),
+    );
+}
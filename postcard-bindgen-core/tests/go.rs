@@ -0,0 +1,16 @@
+mod registry;
+
+use postcard_bindgen_core::code_gen::go::{generate, GenerationSettings};
+
+use registry::init_registry;
+
+#[test]
+fn test_source() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all();
+    let exports = generate(containers, gen_settings, "test".to_owned());
+
+    let source_file = exports.file("source").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(source_file);
+}
@@ -1,6 +1,12 @@
 mod registry;
 
-use postcard_bindgen_core::code_gen::js::{generate, GenerationSettings};
+use postcard_bindgen_core::{
+    code_gen::js::{
+        generate, generate_deno, generate_dual, generate_ts, generate_web, AmbiguousTypes,
+        GenerationSettings, IdentifierCase,
+    },
+    registry::{BindingsRegistry, StructType},
+};
 
 use registry::init_registry;
 
@@ -63,6 +69,17 @@ fn test_ts_types() {
     insta::assert_snapshot!(ts_file);
 }
 
+#[test]
+fn test_ts_types_declare_enabled_functions() {
+    let gen_settings = GenerationSettings::default().type_script_types(true);
+    let (exports, _meta) = generate(init_registry().into_entries(), gen_settings);
+
+    let ts_file = exports.file("ts").unwrap().to_file_string().unwrap();
+    assert!(ts_file.contains("export function deserialize<T extends Type>"));
+    assert!(!ts_file.contains("export function serialize<T extends Type>"));
+    assert!(!ts_file.contains("encodedLength"));
+}
+
 #[test]
 fn test_ser_cjf_module() {
     let containers = init_registry().into_entries();
@@ -84,3 +101,152 @@ fn test_des_cjf_module() {
     let des_file = exports.file("des").unwrap().to_file_string().unwrap();
     insta::assert_snapshot!(des_file);
 }
+
+#[test]
+fn test_deno_module() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all();
+    let deno_file = generate_deno(containers, gen_settings)
+        .0
+        .to_file_string()
+        .unwrap();
+    insta::assert_snapshot!(deno_file);
+}
+
+#[test]
+fn test_ts_module() {
+    let gen_settings = GenerationSettings::enable_all();
+    let ts_file = generate_ts(init_registry().into_entries(), &gen_settings)
+        .0
+        .to_file_string()
+        .unwrap();
+    let deno_file = generate_deno(init_registry().into_entries(), &gen_settings)
+        .0
+        .to_file_string()
+        .unwrap();
+
+    // the deno module is the typescript module with the deno linter disabled
+    assert_eq!(
+        deno_file.replacen("// deno-lint-ignore-file\n", "", 1),
+        ts_file
+    );
+}
+
+#[test]
+fn test_self_test() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all().self_test(true);
+    let (exports, _meta) = generate(containers, gen_settings);
+
+    let self_test_file = exports.file("self_test").unwrap().to_file_string().unwrap();
+    insta::assert_snapshot!(self_test_file);
+}
+
+#[test]
+fn test_helper_names_match() {
+    const HELPER_PREFIXES: &[&str] =
+        &["serialize_", "deserialize_", "is_", "size_hint_", "sample_"];
+
+    for (ident_case, my_type) in [
+        (IdentifierCase::ScreamingSnake, "MY_TYPE"),
+        (IdentifierCase::Snake, "my_type"),
+        (IdentifierCase::Camel, "myType"),
+        (IdentifierCase::Pascal, "MyType"),
+    ] {
+        let mut registry = init_registry();
+        let mut struct_type = StructType::new();
+        struct_type.register_field::<u8>("a");
+        registry.register_struct_binding("MyType", "main_crate", struct_type);
+
+        let gen_settings = GenerationSettings::enable_all()
+            .self_test(true)
+            .ident_case(ident_case);
+        let source = generate_deno(registry.into_entries(), gen_settings)
+            .0
+            .to_file_string()
+            .unwrap();
+
+        // the type dispatch calls the helpers of the type
+        assert!(source.contains(&format!("is_{my_type}(value)")));
+        assert!(source.contains(&format!("serialize_{my_type}(s, value)")));
+        assert!(source.contains(&format!("return deserialize_{my_type}(d)")));
+
+        // every called helper of a type is defined, all names of the registry end with `Type`,
+        // which keeps out runtime functions like `is_none` and members like `s.serialize_array`
+        let mut rest = source.as_str();
+        while let Some(start) = rest.find(|c: char| c.is_ascii_alphabetic()) {
+            let preceded_by_dot = rest[..start].ends_with('.');
+            rest = &rest[start..];
+            let end = rest
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .unwrap_or(rest.len());
+            let (ident, after) = rest.split_at(end);
+            if !preceded_by_dot
+                && after.starts_with('(')
+                && HELPER_PREFIXES.iter().any(|p| ident.starts_with(p))
+                && ident.to_lowercase().ends_with("type")
+            {
+                assert!(
+                    source.contains(&format!("function {ident}(")),
+                    "`{ident}` is called but not defined ({ident_case:?})"
+                );
+            }
+            rest = after;
+        }
+    }
+}
+
+#[test]
+fn test_ambiguous_types_warn() {
+    let containers = || {
+        let mut registry = BindingsRegistry::default();
+        for name in ["Celsius", "Fahrenheit"] {
+            let mut struct_type = StructType::new();
+            struct_type.register_field::<f32>("value");
+            registry.register_struct_binding(name, "main_crate", struct_type);
+        }
+        registry.into_entries()
+    };
+    let warnings = ["`Celsius`, `Fahrenheit` have the same structure, the runtime type checks can't tell them apart"];
+
+    let gen_settings = GenerationSettings::enable_all().ambiguous_types(AmbiguousTypes::Warn);
+    let (_, meta) = generate(containers(), &gen_settings);
+    assert_eq!(meta.warnings, warnings);
+    let (_, _, meta) = generate_dual(containers(), &gen_settings);
+    assert_eq!(meta.warnings, warnings);
+    let (_, meta) = generate_web(containers(), &gen_settings);
+    assert_eq!(meta.warnings, warnings);
+    let (_, meta) = generate_deno(containers(), &gen_settings);
+    assert_eq!(meta.warnings, warnings);
+
+    let (_, meta) = generate(
+        init_registry().into_entries(),
+        GenerationSettings::enable_all(),
+    );
+    assert!(meta.warnings.is_empty());
+}
+
+#[test]
+fn test_split_modules() {
+    let containers = init_registry().into_entries();
+
+    let gen_settings = GenerationSettings::enable_all().split_modules(true);
+    let (exports, meta) = generate(containers, gen_settings);
+    assert!(meta.esm_module && meta.split_modules);
+
+    // every type module is re-exported by the index
+    let index_file = exports.file("index").unwrap().to_file_string().unwrap();
+    let type_modules = exports
+        .files
+        .iter()
+        .filter_map(|file| file.content_type.strip_prefix("types/"))
+        .collect::<Vec<_>>();
+    assert!(!type_modules.is_empty());
+    for ident in type_modules {
+        assert!(index_file.contains(&format!("export * as {ident} from \"./types/{ident}.js\";")));
+    }
+
+    insta::assert_snapshot!(index_file);
+}
@@ -0,0 +1,59 @@
+mod registry;
+
+use std::io::ErrorKind;
+
+use postcard_bindgen_core::{
+    code_gen::js::{generate, generate_js_from_ir, GenerationSettings},
+    json_ir::from_json,
+    Exports,
+};
+use registry::init_registry;
+
+#[test]
+fn test_registry_json() {
+    let registry = init_registry();
+    let json = registry.to_json();
+    assert_eq!(json, registry.into_entries().to_json());
+
+    insta::assert_snapshot!(json);
+}
+
+#[test]
+fn test_read_json() {
+    let json = init_registry().to_json();
+
+    let containers = from_json(json.as_bytes()).unwrap();
+    assert_eq!(containers.to_json(), json);
+
+    let settings = GenerationSettings::enable_all();
+    let (from_registry, _) = generate(init_registry().into_entries(), &settings);
+    let (from_ir, _) = generate_js_from_ir(json.as_bytes(), &settings).unwrap();
+    let files = |exports: Exports<_>| {
+        exports
+            .files
+            .into_iter()
+            .map(|file| (file.content_type, file.content.to_file_string().unwrap()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(files(from_ir), files(from_registry));
+}
+
+#[test]
+fn test_read_invalid_json() {
+    let error = |json: &str| from_json(json.as_bytes()).unwrap_err();
+
+    assert_eq!(error("{").kind(), ErrorKind::InvalidData);
+    assert_eq!(
+        error(r#"{ "version": 2, "containers": [] }"#).to_string(),
+        "invalid JSON IR: version 2 is not supported, the latest version is 1"
+    );
+    assert_eq!(
+        error(r#"{ "version": 1, "containers": [{ "name": "A", "path": [], "kind": "union" }] }"#)
+            .to_string(),
+        "invalid JSON IR: unknown container kind `union`"
+    );
+    assert_eq!(
+        error(r#"{ "version": 1, "containers": [] } ,"#).to_string(),
+        "invalid JSON IR: trailing characters at byte 35"
+    );
+}
@@ -17,7 +17,7 @@ macro_rules! dummy_struct {
                 impl GenJsBinding for $sname {
                     fn get_type() -> ValueType {
                         ValueType::Object(ObjectMeta {
-                            name: stringify!($name),
+                            name: stringify!($name).into(),
                             path: Path::new("main_crate", "::"),
                         })
                     }
@@ -0,0 +1,187 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use serde_derive_internals::{ast::Field, attr};
+use syn::LitStr;
+
+/// The `#[postcard_bindgen(...)]` attributes of a field.
+#[derive(Default)]
+struct FieldAttrs {
+    /// Set with `codec = "name"`.
+    codec: Option<LitStr>,
+    /// Set with `alias = "Name"`, or with `alias` to the name of the field type.
+    alias: Option<LitStr>,
+    /// Set with `default = "expression"`, the javascript value of `#[serde(default)]`.
+    default: Option<LitStr>,
+}
+
+fn field_attrs(field: &Field<'_>) -> syn::Result<FieldAttrs> {
+    let mut attrs = FieldAttrs::default();
+    for attr in field
+        .original
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("postcard_bindgen"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("codec") {
+                attrs.codec = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("alias") {
+                attrs.alias = Some(if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse()?
+                } else {
+                    type_alias_name(field.ty).ok_or_else(|| {
+                        meta.error(
+                            "`alias` needs a name if the field type is no type alias like `NodeId`",
+                        )
+                    })?
+                });
+                Ok(())
+            } else if meta.path.is_ident("default") {
+                attrs.default = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported postcard_bindgen attribute"))
+            }
+        })?;
+    }
+    if let (Some(codec), Some(_)) = (&attrs.codec, &attrs.alias) {
+        return Err(syn::Error::new_spanned(
+            codec,
+            "a field can't have a codec and an alias",
+        ));
+    }
+    Ok(attrs)
+}
+
+/// Name of a type without generic arguments, e.g. `NodeId` for `graph::NodeId`.
+fn type_alias_name(ty: &syn::Type) -> Option<LitStr> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    segment
+        .arguments
+        .is_none()
+        .then(|| LitStr::new(&segment.ident.to_string(), segment.ident.span()))
+}
+
+/// Type the field is registered with.
+///
+/// `#[serde(with = "serde_bytes")]` encodes a byte array like a byte vector, with its length.
+/// Other byte containers are encoded the same way with and without the attribute.
+pub fn field_type(field: &Field<'_>) -> TokenStream {
+    let ty = field.ty;
+    let serde_bytes = field.attrs.serialize_with().is_some_and(|path| {
+        let mut segments = path.path.segments.iter().rev();
+        segments.next().is_some_and(|s| s.ident == "serialize")
+            && segments.next().is_some_and(|s| s.ident == "serde_bytes")
+    });
+    if serde_bytes && matches!(ty, syn::Type::Array(_)) {
+        quote!(&[u8])
+    } else {
+        quote!(#ty)
+    }
+}
+
+/// Whether the field is part of the encoding, fields skipped at serialization, also with
+/// `#[serde(skip)]`, are left out by serde. `#[serde(skip_deserializing)]` alone only skips
+/// the field when reading, serde still writes it.
+pub fn is_encoded(field: &Field<'_>) -> bool {
+    !field.attrs.skip_serializing()
+}
+
+/// Registration of a named field on `target`, which is a `StructType` or `StructFields`.
+///
+/// `container_default` is the `#[serde(default)]` of the struct, which applies to all fields.
+pub fn register_named_field(
+    target: TokenStream,
+    field: &Field<'_>,
+    container_default: &attr::Default,
+) -> TokenStream {
+    // serde serializes flattened fields as map of unknown length, which postcard rejects. A
+    // nested struct has no framing, so without the attribute its fields have the same bytes.
+    if field.attrs.flatten() {
+        return syn::Error::new_spanned(
+            field.original,
+            "`#[serde(flatten)]` is not supported, postcard can't serialize flattened fields; \
+             without it the fields of the struct are encoded the same, but nested in javascript",
+        )
+        .to_compile_error();
+    }
+    // the field would only be part of the encoding for some of its values
+    if field.attrs.skip_serializing_if().is_some() {
+        return syn::Error::new_spanned(
+            field.original,
+            "`#[serde(skip_serializing_if = \"...\")]` is not supported, postcard can't tell if a skipped field follows",
+        )
+        .to_compile_error();
+    }
+
+    let ty = field_type(field);
+    let field_name = field.attrs.name().serialize_name();
+    let attrs = match field_attrs(field) {
+        Ok(attrs) => attrs,
+        Err(err) => return err.to_compile_error(),
+    };
+    // whether the field has a default and if it's the result of a function, whose value is
+    // unknown unless it's set, the field is always decoded then
+    let serde_default = match (field.attrs.default(), container_default) {
+        (attr::Default::None, attr::Default::None) => None,
+        (attr::Default::Path(_), _) | (attr::Default::None, attr::Default::Path(_)) => Some(true),
+        _ => Some(false),
+    }
+    .filter(|&is_path| !is_path || attrs.default.is_some());
+    match (serde_default, attrs) {
+        (
+            None,
+            FieldAttrs {
+                default: Some(default),
+                ..
+            },
+        ) => syn::Error::new_spanned(default, "`default` needs `#[serde(default)]`")
+            .to_compile_error(),
+        (
+            Some(_),
+            FieldAttrs {
+                codec: Some(attr), ..
+            }
+            | FieldAttrs {
+                alias: Some(attr), ..
+            },
+        ) => syn::Error::new_spanned(
+            attr,
+            "a field with a default can't have a codec or an alias",
+        )
+        .to_compile_error(),
+        (
+            Some(_),
+            FieldAttrs {
+                default: Some(default),
+                ..
+            },
+        ) => quote!(
+            #target.register_field_with_default::<#ty>(#field_name, Some(#default))
+        ),
+        (Some(_), _) => {
+            quote!(#target.register_field_with_default::<#ty>(#field_name, None))
+        }
+        (
+            None,
+            FieldAttrs {
+                codec: Some(codec), ..
+            },
+        ) => {
+            quote!(#target.register_field_with_codec::<#ty>(#field_name, #codec))
+        }
+        (
+            None,
+            FieldAttrs {
+                alias: Some(alias), ..
+            },
+        ) => {
+            quote!(#target.register_field_with_alias::<#ty>(#field_name, #alias))
+        }
+        (None, _) => quote!(#target.register_field::<#ty>(#field_name)),
+    }
+}
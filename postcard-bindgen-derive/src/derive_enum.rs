@@ -1,77 +1,128 @@
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::TokenStream;
 use quote::quote;
-use serde_derive_internals::ast::{self, Style};
+use serde_derive_internals::{
+    ast::{self, Style},
+    attr,
+};
 
-pub fn derive_enum<'a>(ident: Ident, variants: impl AsRef<[ast::Variant<'a>]>) -> TokenStream {
-    let enum_name = ident.to_string();
-    let body = variants.as_ref().iter().map(|variant| {
-        let variant_name = variant.attrs.name().serialize_name();
-        derive_variant_style(&variant.style, variant_name, &variant.fields)
-    });
+use crate::attrs::{field_type, is_encoded, register_named_field};
+
+pub fn derive_enum<'a>(
+    enum_name: TokenStream,
+    variants: impl AsRef<[ast::Variant<'a>]>,
+    tag: &attr::TagType,
+    original: &syn::DeriveInput,
+) -> TokenStream {
+    // an internally tagged enum is encoded by the name of its variant followed by the fields,
+    // the other representations are rejected, as postcard has no framing to tell them apart
+    let ty = match tag {
+        attr::TagType::External => quote!(_pb::__private::EnumType::new()),
+        attr::TagType::Internal { tag } => {
+            quote!(_pb::__private::EnumType::new_internally_tagged(#tag))
+        }
+        attr::TagType::Adjacent { .. } => {
+            return syn::Error::new_spanned(
+                original,
+                "adjacently tagged enums (`#[serde(tag = \"...\", content = \"...\")]`) are not supported, use `#[serde(tag = \"...\")]` instead",
+            )
+            .to_compile_error();
+        }
+        attr::TagType::None => {
+            return syn::Error::new_spanned(
+                original,
+                "`#[serde(untagged)]` is not supported, the bindings can't tell the variants apart",
+            )
+            .to_compile_error();
+        }
+    };
+    // serde uses the declaration position as variant index, also if variants in between are skipped
+    let body = variants
+        .as_ref()
+        .iter()
+        .enumerate()
+        .filter(|(_, variant)| !variant.attrs.skip_serializing())
+        .map(|(index, variant)| {
+            let variant_name = variant.attrs.name().serialize_name();
+            derive_variant_style(&variant.style, index, variant_name, &variant.fields)
+        });
     quote!(
-        let mut ty = _pb::__private::EnumType::new();
+        let mut ty = #ty;
         #(#body);*;
-        reg.register_enum_binding(#enum_name.into(), module_path!(), ty);
+        reg.register_enum_binding(#enum_name, module_path!(), ty);
     )
 }
 
 fn derive_variant_style<'a>(
     style: &Style,
+    index: usize,
     variant_name: impl AsRef<str>,
     fields: impl AsRef<[ast::Field<'a>]>,
 ) -> TokenStream {
     match style {
-        ast::Style::Struct => derive_struct_variant(variant_name, fields),
-        ast::Style::Newtype => derive_newtype_variant(variant_name, &fields.as_ref()[0]),
-        ast::Style::Tuple => derive_tuple_variant(variant_name, fields),
-        ast::Style::Unit => derive_unit_variant(variant_name),
+        ast::Style::Struct => derive_struct_variant(index, variant_name, fields),
+        ast::Style::Newtype => derive_newtype_variant(index, variant_name, &fields.as_ref()[0]),
+        ast::Style::Tuple => derive_tuple_variant(index, variant_name, fields),
+        ast::Style::Unit => derive_unit_variant(index, variant_name),
     }
 }
 
-fn derive_unit_variant(variant_name: impl AsRef<str>) -> TokenStream {
+fn derive_unit_variant(index: usize, variant_name: impl AsRef<str>) -> TokenStream {
     let variant_name = variant_name.as_ref();
-    quote!(ty.register_variant(#variant_name.into());)
+    quote!(ty.register_variant_with_index(#index, #variant_name);)
 }
 
-fn derive_newtype_variant(variant_name: impl AsRef<str>, field: &ast::Field<'_>) -> TokenStream {
+fn derive_newtype_variant(
+    index: usize,
+    variant_name: impl AsRef<str>,
+    field: &ast::Field<'_>,
+) -> TokenStream {
     let variant_name = variant_name.as_ref();
-    let ty = field.ty;
+    let ty = field_type(field);
     quote!(
         let mut fields = _pb::__private::TupleFields::default();
         fields.register_field::<#ty>();
-        ty.register_variant_tuple(#variant_name.into(), fields);
+        ty.register_variant_tuple_with_index(#index, #variant_name, fields);
     )
 }
 
 fn derive_struct_variant<'a>(
+    index: usize,
     variant_name: impl AsRef<str>,
     fields: impl AsRef<[ast::Field<'a>]>,
 ) -> TokenStream {
     let variant_name = variant_name.as_ref();
-    let body = fields.as_ref().iter().map(|field| {
-        let ty = field.ty;
-        let field_name = field.attrs.name().serialize_name();
-        quote!(fields.register_field::<#ty>(#field_name.into());)
-    });
+    let body = fields
+        .as_ref()
+        .iter()
+        .filter(|field| is_encoded(field))
+        .map(|field| {
+            let register = register_named_field(quote!(fields), field, &attr::Default::None);
+            quote!(#register;)
+        });
     quote!(
         let mut fields = _pb::__private::StructFields::default();
         #(#body);*;
-        ty.register_unnamed_struct(#variant_name.into(), fields);
+        ty.register_unnamed_struct_with_index(#index, #variant_name, fields);
     )
 }
 
 fn derive_tuple_variant<'a>(
+    index: usize,
     variant_name: impl AsRef<str>,
     fields: impl AsRef<[ast::Field<'a>]>,
 ) -> TokenStream {
     let variant_name = variant_name.as_ref();
-    let body = fields.as_ref().iter().map(|field| {
-        let ty = field.ty;
-        quote!(fields.register_field::<#ty>();)
-    });
+    let body = fields
+        .as_ref()
+        .iter()
+        .filter(|field| is_encoded(field))
+        .map(|field| {
+            let ty = field_type(field);
+            quote!(fields.register_field::<#ty>();)
+        });
     quote!(
         let mut fields = _pb::__private::TupleFields::default();
         #(#body);*;
-        ty.register_variant_tuple(#variant_name.into(), fields);
+        ty.register_variant_tuple_with_index(#index, #variant_name, fields);
     )
 }
@@ -1,57 +1,70 @@
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::TokenStream;
 use quote::quote;
-use serde_derive_internals::ast::{Field, Style};
+use serde_derive_internals::{
+    ast::{Field, Style},
+    attr,
+};
 
-pub fn derive_struct(style: Style, ident: Ident, fields: Vec<Field>) -> TokenStream {
+use crate::attrs::{field_type, is_encoded, register_named_field};
+
+pub fn derive_struct(
+    style: Style,
+    struct_name: TokenStream,
+    fields: Vec<Field>,
+    default: &attr::Default,
+) -> TokenStream {
     let fields = fields
         .into_iter()
-        .filter(|field| !(field.attrs.skip_serializing() || field.attrs.skip_deserializing()))
+        .filter(|field| is_encoded(field))
         .collect::<Vec<_>>();
-    let struct_name = ident.to_string();
-    derive_struct_style(style, struct_name, fields)
+    derive_struct_style(style, struct_name, fields, default)
 }
 
 fn derive_struct_style<'a>(
     style: Style,
-    struct_name: String,
+    struct_name: TokenStream,
     fields: impl AsRef<[Field<'a>]>,
+    default: &attr::Default,
 ) -> TokenStream {
     match style {
-        Style::Struct => derive_struct_type(struct_name, fields),
+        Style::Struct => derive_struct_type(struct_name, fields, default),
         Style::Tuple => derive_tuple_struct_type(struct_name, fields),
         Style::Unit => derive_unit_struct_type(struct_name),
         Style::Newtype => derive_tuple_struct_type(struct_name, fields),
     }
 }
 
-fn derive_unit_struct_type(name: String) -> TokenStream {
+fn derive_unit_struct_type(name: TokenStream) -> TokenStream {
     quote!(
         let mut ty = _pb::__private::UnitStructType::new();
-        reg.register_unit_struct_binding(#name.into(), module_path!(), ty);
+        reg.register_unit_struct_binding(#name, module_path!(), ty);
     )
 }
 
-fn derive_tuple_struct_type<'a>(name: String, fields: impl AsRef<[Field<'a>]>) -> TokenStream {
+fn derive_tuple_struct_type<'a>(name: TokenStream, fields: impl AsRef<[Field<'a>]>) -> TokenStream {
     let body = fields.as_ref().iter().map(|field| {
-        let ty = field.ty;
+        let ty = field_type(field);
         quote!(ty.register_field::<#ty>())
     });
     quote!(
         let mut ty = _pb::__private::TupleStructType::new();
         #(#body);*;
-        reg.register_tuple_struct_binding(#name.into(), module_path!(), ty);
+        reg.register_tuple_struct_binding(#name, module_path!(), ty);
     )
 }
 
-fn derive_struct_type<'a>(name: String, fields: impl AsRef<[Field<'a>]>) -> TokenStream {
-    let body = fields.as_ref().iter().map(|field| {
-        let ident_str = field.attrs.name().serialize_name();
-        let ty = field.ty;
-        quote!(ty.register_field::<#ty>(#ident_str.into()))
-    });
+fn derive_struct_type<'a>(
+    name: TokenStream,
+    fields: impl AsRef<[Field<'a>]>,
+    default: &attr::Default,
+) -> TokenStream {
+    let body = fields
+        .as_ref()
+        .iter()
+        .map(|field| register_named_field(quote!(ty), field, default));
     quote!(
         let mut ty = _pb::__private::StructType::new();
         #(#body);*;
-        reg.register_struct_binding(#name.into(), module_path!(), ty);
+        reg.register_struct_binding(#name, module_path!(), ty);
     )
 }
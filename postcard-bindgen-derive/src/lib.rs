@@ -3,12 +3,13 @@ use derive_struct::derive_struct;
 use proc_macro2::TokenStream;
 use quote::quote;
 use serde_derive_internals::{ast, Ctxt, Derive};
-use syn::DeriveInput;
+use syn::{parse_quote, DeriveInput};
 
+mod attrs;
 mod derive_enum;
 mod derive_struct;
 
-#[proc_macro_derive(PostcardBindings)]
+#[proc_macro_derive(PostcardBindings, attributes(postcard_bindgen))]
 pub fn postcard_bindings(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive_js_implementation(input).into()
 }
@@ -19,32 +20,74 @@ fn derive_js_implementation(input: proc_macro::TokenStream) -> TokenStream {
     let cx = Ctxt::new();
     let container = ast::Container::from_ast(&cx, &input, Derive::Serialize).unwrap();
 
+    let ident = container.ident;
+    // the name serde uses, e.g. set with `#[serde(rename = "...")]`
+    let container_name = container.attrs.name().serialize_name().to_owned();
+    if syn::parse_str::<syn::Ident>(&container_name).is_err() {
+        cx.check().unwrap();
+        return syn::Error::new_spanned(
+            ident,
+            format!("the type can't be renamed to `{container_name}`, which is no identifier"),
+        )
+        .to_compile_error();
+    }
+
+    // Each instantiation of a generic type is registered as its own type, named after its
+    // type arguments and the values of its const generics, e.g. `Wrapper_u32` or `Frame_16`.
+    let type_params = container
+        .generics
+        .type_params()
+        .map(|param| param.ident.to_owned())
+        .collect::<Vec<_>>();
+    let const_params = container
+        .generics
+        .const_params()
+        .map(|param| param.ident.to_owned())
+        .collect::<Vec<_>>();
+    let name = if type_params.is_empty() && const_params.is_empty() {
+        quote!(#container_name)
+    } else if const_params.is_empty() {
+        quote!(_pb::__private::monomorphized_name(#container_name, &[#(<#type_params as _pb::__private::GenJsBinding>::get_type()),*]))
+    } else {
+        quote!(_pb::__private::monomorphized_name_with_consts(#container_name, &[#(<#type_params as _pb::__private::GenJsBinding>::get_type()),*], &[#(&#const_params),*]))
+    };
+
+    let mut generics = container.generics.to_owned();
+    for param in &type_params {
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#param: _pb::__private::GenJsBinding));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
     let body = match container.data {
-        ast::Data::Enum(variants) => derive_enum(container.ident.to_owned(), variants),
+        ast::Data::Enum(variants) => derive_enum(
+            name.to_owned(),
+            variants,
+            container.attrs.tag(),
+            container.original,
+        ),
         ast::Data::Struct(style, fields) => {
-            derive_struct(style, container.ident.to_owned(), fields)
+            derive_struct(style, name.to_owned(), fields, container.attrs.default())
         }
     };
 
-    let ident = container.ident;
-    let generics = container.generics;
-    let container_name = ident.to_string();
-
     let expanded = if cfg!(feature = "expanding") {
         quote!(
             const _: () = {
                 #[allow(unused_extern_crates, clippy::useless_attribute)]
                 extern crate postcard_bindgen as _pb;
-                impl #generics _pb::__private::JsBindings for #ident #generics {
+                impl #impl_generics _pb::__private::JsBindings for #ident #ty_generics #where_clause {
                     fn create_bindings(reg: &mut _pb::__private::BindingsRegistry) {
                         #body
                     }
                 }
 
-                impl #generics _pb::__private::GenJsBinding for #ident #generics {
+                impl #impl_generics _pb::__private::GenJsBinding for #ident #ty_generics #where_clause {
                     fn get_type() -> _pb::__private::ValueType {
                         _pb::__private::ValueType::Object(_pb::__private::ObjectMeta {
-                            name: #container_name.into(),
+                            name: #name.into(),
                             path: _pb::__private::Path::new(module_path!(), "::"),
                         })
                     }
@@ -0,0 +1,106 @@
+//! Writes the JavaScript bindings from a build script.
+//!
+//! ```no_run
+//! // build.rs
+//! # mod types {
+//! #     #[derive(serde::Serialize, postcard_bindgen::PostcardBindings)]
+//! #     pub struct Message { pub id: u8 }
+//! # }
+//! use postcard_bindgen::{build::{write_bindings, Options}, generate_bindings};
+//!
+//! fn main() {
+//!     write_bindings("web/src/generated", generate_bindings!(types::Message), Options::default())
+//!         .unwrap();
+//! }
+//! ```
+//!
+//! The files are only written if their content changed, so tools watching them, e.g. a dev
+//! server, don't reload on every build.
+
+use std::{fs, io, path::Path};
+
+use postcard_bindgen_core::{
+    code_gen::js::{generate, GenerationSettings},
+    registry::ContainerCollection,
+};
+
+use crate::package::npm_package::js_module;
+
+/// Options of [`write_bindings`].
+#[derive(Debug)]
+pub struct Options {
+    name: String,
+    gen_settings: GenerationSettings,
+}
+
+impl Options {
+    /// Name of the written files without extension, `bindings` by default.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Settings of the generated code, [`GenerationSettings::enable_all`] by default.
+    ///
+    /// The split modules option is ignored, the bindings are always written as one module.
+    pub fn generation_settings(mut self, gen_settings: GenerationSettings) -> Self {
+        self.gen_settings = gen_settings;
+        self
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            name: "bindings".into(),
+            gen_settings: GenerationSettings::enable_all(),
+        }
+    }
+}
+
+/// Writes the bindings to `<out_dir>/<name>.js` and the type declarations to
+/// `<out_dir>/<name>.d.ts`, if enabled.
+///
+/// A file is only written if its content differs from the file on disk. Returns whether any
+/// file was written.
+///
+/// The warnings of the generation, e.g. of [`AmbiguousTypes::Warn`], are printed as
+/// `cargo:warning`, which cargo shows for build scripts.
+///
+/// [`AmbiguousTypes::Warn`]: crate::javascript::AmbiguousTypes::Warn
+pub fn write_bindings(
+    out_dir: impl AsRef<Path>,
+    tys: ContainerCollection,
+    options: Options,
+) -> io::Result<bool> {
+    let out_dir = out_dir.as_ref();
+    fs::create_dir_all(out_dir)?;
+
+    let (mut exports, export_meta) = generate(tys, options.gen_settings.split_modules(false));
+    for warning in export_meta.warnings {
+        println!("cargo:warning={warning}");
+    }
+
+    let mut changed = false;
+    if let Some(file) = exports.pop_file("ts") {
+        changed |= write_if_changed(
+            &out_dir.join(format!("{}.d.ts", options.name)),
+            &file.to_file_string().unwrap(),
+        )?;
+    }
+    changed |= write_if_changed(
+        &out_dir.join(format!("{}.js", options.name)),
+        &js_module(&mut exports).to_file_string().unwrap(),
+    )?;
+
+    Ok(changed)
+}
+
+fn write_if_changed(path: &Path, content: &str) -> io::Result<bool> {
+    match fs::read(path) {
+        Ok(current) if current == content.as_bytes() => Ok(false),
+        Ok(_) => fs::write(path, content).map(|_| true),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => fs::write(path, content).map(|_| true),
+        Err(e) => Err(e),
+    }
+}
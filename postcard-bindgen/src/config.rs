@@ -0,0 +1,361 @@
+//! Generation options read from a `postcard-bindgen.toml`.
+//!
+//! ```toml
+//! language = "javascript"
+//! name = "firmware-bindings"
+//! version = "1.2.0"
+//! module-format = "dual"
+//! type-checks = "typescript"
+//! exclude = ["DebugFrame"]
+//!
+//! [rename]
+//! Msg = "Message"
+//! ```
+//!
+//! All keys are optional:
+//! - `language`: `javascript` (default), `typescript`, `deno`, `web`, `python`, `c`, `cpp`,
+//!   `kotlin`, `swift`, `dart`, `csharp`, `go`, `java` or `lua`
+//! - `name`: name of the package, module or source file, `bindings` by default
+//! - `version`: version of the npm or pip package, `0.1.0` by default
+//! - `module-format`: `esm` (default), `commonjs` or `dual`, only used by `javascript`
+//! - `type-checks`: `all` (default), `typescript`, `runtime` or `none`, only used by
+//!   `javascript` and `python`
+//! - `serialization` and `deserialization`: whether the code is generated, both `true` by
+//!   default
+//! - `pointer-width`: width in bits of `isize` and `usize` on the target the data is
+//!   exchanged with, `32` or `64` (default)
+//! - `rename`: new names of types, by their Rust name
+//! - `exclude`: Rust names of types without bindings
+//!
+//! The command line tools additionally read where the bindings are written to and how the
+//! JSON document of the types is exported, relative paths are relative to the file:
+//!
+//! ```toml
+//! out-dir = "web/src/generated"
+//!
+//! [exporter]
+//! package = "firmware-types"
+//! example = "export"
+//! ```
+//!
+//! The exporter is run with `cargo run`, selected by `package`, `bin` or `example` and the
+//! enabled `features`. It has to print the document written by `to_json()` to stdout.
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use postcard_bindgen_core::registry::ContainerCollection;
+use serde::{Deserialize, Deserializer};
+
+use crate::{
+    c, cpp, csharp, dart, go, java, javascript, javascript::PointerWidth, kotlin, lua, python,
+    swift, PackageInfo, Version,
+};
+
+/// The generation options of a `postcard-bindgen.toml`, see the [module](self) for the keys.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub struct Config {
+    pub language: Language,
+    pub name: String,
+    #[serde(deserialize_with = "version")]
+    pub version: Version,
+    pub module_format: ModuleFormat,
+    pub type_checks: TypeChecks,
+    pub serialization: bool,
+    pub deserialization: bool,
+    #[serde(deserialize_with = "pointer_width")]
+    pub pointer_width: PointerWidth,
+    pub rename: BTreeMap<String, String>,
+    pub exclude: Vec<String>,
+    pub out_dir: Option<PathBuf>,
+    pub exporter: Option<Exporter>,
+}
+
+/// The crate target which prints the JSON document of the types.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub struct Exporter {
+    pub package: Option<String>,
+    pub bin: Option<String>,
+    pub example: Option<String>,
+    pub features: Vec<String>,
+}
+
+/// Language of the bindings and the way they are packaged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    /// npm package, see [`javascript::build_package`]
+    Javascript,
+    /// see [`javascript::build_ts_module`]
+    Typescript,
+    /// see [`javascript::build_deno_module`]
+    Deno,
+    /// see [`javascript::build_web_module`]
+    Web,
+    /// pip package, see [`python::build_package`]
+    Python,
+    C,
+    Cpp,
+    Kotlin,
+    Swift,
+    Dart,
+    Csharp,
+    Go,
+    Java,
+    Lua,
+}
+
+/// Module format of the npm package.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModuleFormat {
+    Esm,
+    Commonjs,
+    /// All formats at once, see [`javascript::build_dual_package`]
+    Dual,
+}
+
+/// Which type checks are generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TypeChecks {
+    None,
+    /// Only the typescript declarations, values are not checked at runtime.
+    Typescript,
+    /// Only the checks of the values at runtime.
+    Runtime,
+    All,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            language: Language::Javascript,
+            name: "bindings".into(),
+            version: Version::from_array([0, 1, 0]),
+            module_format: ModuleFormat::Esm,
+            type_checks: TypeChecks::All,
+            serialization: true,
+            deserialization: true,
+            pointer_width: PointerWidth::Bits64,
+            rename: BTreeMap::new(),
+            exclude: Vec::new(),
+            out_dir: None,
+            exporter: None,
+        }
+    }
+}
+
+impl Config {
+    /// Name of the configuration file.
+    pub const FILE_NAME: &'static str = "postcard-bindgen.toml";
+
+    /// Reads the configuration from a file.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidData`] if the file is no valid configuration.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_toml(&fs::read_to_string(path)?)
+    }
+
+    /// Parses the configuration.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidData`] if the document is no valid configuration.
+    pub fn from_toml(document: &str) -> io::Result<Self> {
+        toml::from_str(document).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Excludes and renames the types of the configuration.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidInput`] if a renamed or excluded type doesn't exist
+    /// or an excluded type is referenced by a type which is not excluded.
+    pub fn apply(&self, bindings: &mut ContainerCollection) -> io::Result<()> {
+        for name in &self.exclude {
+            match bindings.exclude(name) {
+                Ok(true) => (),
+                Ok(false) => return Err(invalid_input(format!("no type `{name}` to exclude"))),
+                Err(user) => {
+                    return Err(invalid_input(format!(
+                        "`{name}` can't be excluded, it's used by `{user}`"
+                    )))
+                }
+            }
+        }
+        for (name, new_name) in &self.rename {
+            if !bindings.rename(name, new_name) {
+                return Err(invalid_input(format!("no type `{name}` to rename")));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies the configuration to the bindings and writes them to `parent_dir` in the
+    /// configured language.
+    pub fn build(&self, parent_dir: &Path, mut bindings: ContainerCollection) -> io::Result<()> {
+        self.apply(&mut bindings)?;
+
+        let (ser, des) = (self.serialization, self.deserialization);
+        let pointer_width = self.pointer_width;
+        let name = self.name.as_str();
+        let package_info = || PackageInfo {
+            name: self.name.clone(),
+            version: self.version,
+        };
+        let (static_checks, runtime_checks) = match self.type_checks {
+            TypeChecks::None => (false, false),
+            TypeChecks::Typescript => (true, false),
+            TypeChecks::Runtime => (false, true),
+            TypeChecks::All => (true, true),
+        };
+        let js_settings = || {
+            javascript::GenerationSettings::enable_all()
+                .serialization(ser)
+                .deserialization(des)
+                .type_script_types(static_checks)
+                .runtime_type_checks(runtime_checks)
+                .pointer_width(pointer_width)
+        };
+
+        match self.language {
+            Language::Javascript => match self.module_format {
+                ModuleFormat::Dual => javascript::build_dual_package(
+                    parent_dir,
+                    package_info(),
+                    js_settings(),
+                    bindings,
+                ),
+                format => javascript::build_package(
+                    parent_dir,
+                    package_info(),
+                    js_settings().esm_module(format == ModuleFormat::Esm),
+                    bindings,
+                ),
+            },
+            Language::Typescript => {
+                javascript::build_ts_module(parent_dir, name, js_settings(), bindings)
+            }
+            Language::Deno => {
+                javascript::build_deno_module(parent_dir, name, js_settings(), bindings)
+            }
+            Language::Web => {
+                javascript::build_web_module(parent_dir, name, js_settings(), bindings)
+            }
+            Language::Python => python::build_package(
+                parent_dir,
+                package_info(),
+                python::GenerationSettings::enable_all()
+                    .serialization(ser)
+                    .deserialization(des)
+                    .runtime_type_checks(runtime_checks)
+                    .pointer_width(pointer_width),
+                bindings,
+            ),
+            Language::C => c::build_sources(
+                parent_dir,
+                name,
+                c::GenerationSettings::enable_all()
+                    .serialization(ser)
+                    .deserialization(des)
+                    .pointer_width(pointer_width),
+                bindings,
+            ),
+            Language::Cpp => cpp::build_header(
+                parent_dir,
+                name,
+                cpp::GenerationSettings::enable_all()
+                    .serialization(ser)
+                    .deserialization(des)
+                    .pointer_width(pointer_width),
+                bindings,
+            ),
+            Language::Kotlin => kotlin::build_source(
+                parent_dir,
+                name,
+                kotlin::GenerationSettings::enable_all()
+                    .serialization(ser)
+                    .deserialization(des)
+                    .pointer_width(pointer_width),
+                bindings,
+            ),
+            Language::Swift => swift::build_source(
+                parent_dir,
+                name,
+                swift::GenerationSettings::enable_all()
+                    .serialization(ser)
+                    .deserialization(des)
+                    .pointer_width(pointer_width),
+                bindings,
+            ),
+            Language::Dart => dart::build_library(
+                parent_dir,
+                name,
+                dart::GenerationSettings::enable_all()
+                    .serialization(ser)
+                    .deserialization(des)
+                    .pointer_width(pointer_width),
+                bindings,
+            ),
+            Language::Csharp => csharp::build_source(
+                parent_dir,
+                name,
+                csharp::GenerationSettings::enable_all()
+                    .serialization(ser)
+                    .deserialization(des)
+                    .pointer_width(pointer_width),
+                bindings,
+            ),
+            Language::Go => go::build_source(
+                parent_dir,
+                name,
+                go::GenerationSettings::enable_all()
+                    .serialization(ser)
+                    .deserialization(des)
+                    .pointer_width(pointer_width),
+                bindings,
+            ),
+            Language::Java => java::build_source(
+                parent_dir,
+                name,
+                java::GenerationSettings::enable_all()
+                    .serialization(ser)
+                    .deserialization(des)
+                    .pointer_width(pointer_width),
+                bindings,
+            ),
+            Language::Lua => lua::build_module(
+                parent_dir,
+                name,
+                lua::GenerationSettings::enable_all()
+                    .serialization(ser)
+                    .deserialization(des)
+                    .pointer_width(pointer_width),
+                bindings,
+            ),
+        }
+    }
+}
+
+fn version<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Version, D::Error> {
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(serde::de::Error::custom)
+}
+
+fn pointer_width<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PointerWidth, D::Error> {
+    match u8::deserialize(deserializer)? {
+        32 => Ok(PointerWidth::Bits32),
+        64 => Ok(PointerWidth::Bits64),
+        bits => Err(serde::de::Error::custom(format!(
+            "invalid pointer width {bits}, expected 32 or 64"
+        ))),
+    }
+}
+
+fn invalid_input(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
@@ -71,17 +71,127 @@ mod package;
 #[cfg(feature = "generating")]
 #[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
 pub mod javascript {
-    pub use super::package::npm_package::build_npm_package as build_package;
-    pub use postcard_bindgen_core::code_gen::js::GenerationSettings;
+    pub use super::package::deno_module::build_deno_module;
+    pub use super::package::npm_package::{
+        build_dual_npm_package as build_dual_package, build_npm_package as build_package,
+    };
+    pub use super::package::ts_module::build_ts_module;
+    pub use super::package::web_module::build_web_module;
+    pub use postcard_bindgen_core::code_gen::js::{
+        generate_js_from_ir, AmbiguousTypes, Codec, GenerationSettings, IdentifierCase,
+        OptionalNone, PointerWidth,
+    };
+}
+
+#[cfg(feature = "generating")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
+pub mod c {
+    pub use super::package::c_sources::build_c_sources as build_sources;
+    pub use postcard_bindgen_core::code_gen::c::{GenerationSettings, PointerWidth};
+}
+
+#[cfg(feature = "generating")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
+pub mod cpp {
+    pub use super::package::cpp_header::build_cpp_header as build_header;
+    pub use postcard_bindgen_core::code_gen::cpp::{GenerationSettings, PointerWidth};
+}
+
+#[cfg(feature = "generating")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
+pub mod kotlin {
+    pub use super::package::kotlin_source::build_kotlin_source as build_source;
+    pub use postcard_bindgen_core::code_gen::kotlin::{GenerationSettings, PointerWidth};
 }
 
 #[cfg(feature = "generating")]
 #[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
 pub mod python {
     pub use super::package::pip_module::build_pip_module as build_package;
-    pub use postcard_bindgen_core::code_gen::python::GenerationSettings;
+    pub use postcard_bindgen_core::code_gen::python::{GenerationSettings, PointerWidth};
+}
+
+#[cfg(feature = "generating")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
+pub mod swift {
+    pub use super::package::swift_source::build_swift_source as build_source;
+    pub use postcard_bindgen_core::code_gen::swift::{GenerationSettings, PointerWidth};
+}
+
+#[cfg(feature = "generating")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
+pub mod dart {
+    pub use super::package::dart_library::build_dart_library as build_library;
+    pub use postcard_bindgen_core::code_gen::dart::{GenerationSettings, PointerWidth};
+}
+
+#[cfg(feature = "generating")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
+pub mod csharp {
+    pub use super::package::csharp_source::build_csharp_source as build_source;
+    pub use postcard_bindgen_core::code_gen::csharp::{GenerationSettings, PointerWidth};
+}
+
+#[cfg(feature = "generating")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
+pub mod go {
+    pub use super::package::go_source::build_go_source as build_source;
+    pub use postcard_bindgen_core::code_gen::go::{GenerationSettings, PointerWidth};
+}
+
+#[cfg(feature = "generating")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
+pub mod java {
+    pub use super::package::java_source::build_java_source as build_source;
+    pub use postcard_bindgen_core::code_gen::java::{GenerationSettings, PointerWidth};
+}
+
+#[cfg(feature = "generating")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
+pub mod lua {
+    pub use super::package::lua_module::build_lua_module as build_module;
+    pub use postcard_bindgen_core::code_gen::lua::{GenerationSettings, PointerWidth};
+}
+
+/// Building blocks for generators of languages this crate doesn't ship.
+///
+/// A generator implements [`LanguageBackend`](backend::LanguageBackend) and runs with
+/// [`generate_with`](backend::generate_with) on the output of [`generate_bindings!`].
+#[cfg(feature = "generating")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
+pub mod backend {
+    pub use postcard_bindgen_core::{
+        code_gen::backend::{generate_with, LanguageBackend},
+        lang,
+        registry::{
+            BindingType, Container, ContainerCollection, EnumType, EnumVariant, EnumVariantType,
+            StructField, StructType, TupleStructType,
+        },
+        type_info::{ArrayMeta, NumberMeta, ObjectMeta, ValueType},
+        ExportFile, Exports,
+    };
 }
 
+#[cfg(feature = "generating")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
+pub mod build;
+
+#[cfg(feature = "config")]
+#[cfg_attr(docsrs, doc(cfg(feature = "config")))]
+pub mod config;
+
+#[cfg(feature = "generating")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
+pub use postcard_bindgen_core::json_ir;
+
+#[cfg(feature = "serde-reflection")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde-reflection")))]
+pub use postcard_bindgen_core::reflection;
+
+#[cfg(feature = "postcard-schema")]
+#[cfg_attr(docsrs, doc(cfg(feature = "postcard-schema")))]
+pub use postcard_bindgen_core::postcard_schema;
+
 #[cfg(feature = "generating")]
 #[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
 pub use package::{PackageInfo, Version, VersionFromStrError};
@@ -107,7 +217,9 @@ pub mod __private {
     pub use postcard_bindgen_core::{
         path::Path,
         registry::*,
-        type_info::{GenJsBinding, ObjectMeta, ValueType},
+        type_info::{
+            monomorphized_name, monomorphized_name_with_consts, GenJsBinding, ObjectMeta, ValueType,
+        },
     };
 }
 
@@ -129,16 +241,36 @@ pub mod __private {
 ///
 /// let bindings = generate_bindings!(Test);
 /// ```
+///
+/// Bare tuples can be registered under a name with `"<name>" => <tuple type>`. They are
+/// represented as positional arrays.
+///
+/// ```rust
+/// # use serde::Serialize;
+/// # use postcard_bindgen::{PostcardBindings, generate_bindings};
+/// # #[derive(Serialize, PostcardBindings)]
+/// # struct Test {
+/// #     field: u8
+/// # }
+/// let bindings = generate_bindings!(Test, "Args" => (u8, String));
+/// ```
 #[cfg(feature = "generating")]
 #[cfg_attr(docsrs, doc(cfg(feature = "generating")))]
 #[macro_export]
 macro_rules! generate_bindings {
-    ($( $x:ty ),*) => {
+    (@register $reg:ident;) => {};
+    (@register $reg:ident; $name:literal => $x:ty $(, $($rest:tt)*)?) => {
+        $reg.register_tuple_binding::<$x>($name);
+        postcard_bindgen::generate_bindings!(@register $reg; $($($rest)*)?);
+    };
+    (@register $reg:ident; $x:ty $(, $($rest:tt)*)?) => {
+        <$x as postcard_bindgen::__private::JsBindings>::create_bindings(&mut $reg);
+        postcard_bindgen::generate_bindings!(@register $reg; $($($rest)*)?);
+    };
+    ($($bindings:tt)*) => {
         {
             let mut reg = postcard_bindgen::__private::BindingsRegistry::default();
-            $(
-                <$x as postcard_bindgen::__private::JsBindings>::create_bindings(&mut reg);
-            )*
+            postcard_bindgen::generate_bindings!(@register reg; $($bindings)*);
             reg.into_entries()
         }
     };
@@ -0,0 +1,54 @@
+use core::borrow::Borrow;
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use postcard_bindgen_core::{
+    code_gen::c::{generate, GenerationSettings},
+    registry::ContainerCollection,
+};
+
+/// Builds a C header and source file.
+///
+/// The files are written to `<parent_dir>/<name>.h` and `<parent_dir>/<name>.c` and only
+/// depend on the C standard library.
+///
+/// # Example
+/// ```
+/// # use postcard_bindgen::{c::{build_sources, GenerationSettings}, PostcardBindings, generate_bindings};
+/// # use serde::Serialize;
+/// #[derive(Serialize, PostcardBindings)]
+/// struct Test {
+///     field: u8
+/// }
+///
+/// # fn main() {
+/// let parent_dir = std::env::temp_dir();
+///
+/// build_sources(parent_dir.as_path(), "bindings", GenerationSettings::enable_all(), generate_bindings!(Test));
+/// # }
+/// ```
+pub fn build_c_sources(
+    parent_dir: &Path,
+    name: &str,
+    gen_settings: impl Borrow<GenerationSettings>,
+    bindings: ContainerCollection,
+) -> io::Result<()> {
+    std::fs::create_dir_all(parent_dir)?;
+
+    let exports = generate(bindings, gen_settings, name.to_owned());
+
+    for (content_type, extension) in [("header", "h"), ("source", "c")] {
+        let path = parent_dir.join(format!("{name}.{extension}"));
+        let content = exports
+            .file(content_type)
+            .unwrap()
+            .to_file_string()
+            .unwrap();
+        File::create(path.as_path())?.write_all(content.as_bytes())?;
+    }
+
+    Ok(())
+}
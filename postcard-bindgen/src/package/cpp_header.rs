@@ -0,0 +1,47 @@
+use core::borrow::Borrow;
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use postcard_bindgen_core::{
+    code_gen::cpp::{generate, GenerationSettings},
+    registry::ContainerCollection,
+};
+
+/// Builds a header only C++ library.
+///
+/// The header is written to `<parent_dir>/<name>.hpp` and only depends on the C++17
+/// standard library.
+///
+/// # Example
+/// ```
+/// # use postcard_bindgen::{cpp::{build_header, GenerationSettings}, PostcardBindings, generate_bindings};
+/// # use serde::Serialize;
+/// #[derive(Serialize, PostcardBindings)]
+/// struct Test {
+///     field: u8
+/// }
+///
+/// # fn main() {
+/// let parent_dir = std::env::temp_dir();
+///
+/// build_header(parent_dir.as_path(), "bindings", GenerationSettings::enable_all(), generate_bindings!(Test));
+/// # }
+/// ```
+pub fn build_cpp_header(
+    parent_dir: &Path,
+    name: &str,
+    gen_settings: impl Borrow<GenerationSettings>,
+    bindings: ContainerCollection,
+) -> io::Result<()> {
+    std::fs::create_dir_all(parent_dir)?;
+
+    let exports = generate(bindings, gen_settings, name.to_owned());
+
+    let content = exports.file("header").unwrap().to_file_string().unwrap();
+    File::create(parent_dir.join(format!("{name}.hpp")))?.write_all(content.as_bytes())?;
+
+    Ok(())
+}
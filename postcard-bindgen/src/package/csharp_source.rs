@@ -0,0 +1,47 @@
+use core::borrow::Borrow;
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use postcard_bindgen_core::{
+    code_gen::csharp::{generate, GenerationSettings},
+    registry::ContainerCollection,
+};
+
+/// Builds a C# source file.
+///
+/// The file is written to `<parent_dir>/<name>.cs` and declares the namespace `name` in
+/// pascal case. It needs .NET 7 or later and no other packages.
+///
+/// # Example
+/// ```
+/// # use postcard_bindgen::{csharp::{build_source, GenerationSettings}, PostcardBindings, generate_bindings};
+/// # use serde::Serialize;
+/// #[derive(Serialize, PostcardBindings)]
+/// struct Test {
+///     field: u8
+/// }
+///
+/// # fn main() {
+/// let parent_dir = std::env::temp_dir();
+///
+/// build_source(parent_dir.as_path(), "bindings", GenerationSettings::enable_all(), generate_bindings!(Test));
+/// # }
+/// ```
+pub fn build_csharp_source(
+    parent_dir: &Path,
+    name: &str,
+    gen_settings: impl Borrow<GenerationSettings>,
+    bindings: ContainerCollection,
+) -> io::Result<()> {
+    std::fs::create_dir_all(parent_dir)?;
+
+    let exports = generate(bindings, gen_settings, name.to_owned());
+
+    let content = exports.file("source").unwrap().to_file_string().unwrap();
+    File::create(parent_dir.join(format!("{name}.cs")))?.write_all(content.as_bytes())?;
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use core::borrow::Borrow;
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use postcard_bindgen_core::{
+    code_gen::dart::{generate, GenerationSettings},
+    registry::ContainerCollection,
+};
+
+/// Builds a Dart library.
+///
+/// The file is written to `<parent_dir>/<name>.dart` and only imports `dart:convert` and
+/// `dart:typed_data`, so it can be used in Flutter apps.
+///
+/// # Example
+/// ```
+/// # use postcard_bindgen::{dart::{build_library, GenerationSettings}, PostcardBindings, generate_bindings};
+/// # use serde::Serialize;
+/// #[derive(Serialize, PostcardBindings)]
+/// struct Test {
+///     field: u8
+/// }
+///
+/// # fn main() {
+/// let parent_dir = std::env::temp_dir();
+///
+/// build_library(parent_dir.as_path(), "bindings", GenerationSettings::enable_all(), generate_bindings!(Test));
+/// # }
+/// ```
+pub fn build_dart_library(
+    parent_dir: &Path,
+    name: &str,
+    gen_settings: impl Borrow<GenerationSettings>,
+    bindings: ContainerCollection,
+) -> io::Result<()> {
+    std::fs::create_dir_all(parent_dir)?;
+
+    let exports = generate(bindings, gen_settings);
+
+    let content = exports.file("source").unwrap().to_file_string().unwrap();
+    File::create(parent_dir.join(format!("{name}.dart")))?.write_all(content.as_bytes())?;
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use core::borrow::Borrow;
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use postcard_bindgen_core::{
+    code_gen::js::{generate_deno, GenerationSettings},
+    registry::ContainerCollection,
+};
+
+/// Builds a single self-contained typescript module for deno.
+///
+/// The module is written to `<parent_dir>/<module_name>.ts` and can be imported
+/// without any further files.
+///
+/// # Example
+/// ```
+/// # use postcard_bindgen::{javascript::{build_deno_module, GenerationSettings}, PostcardBindings, generate_bindings};
+/// # use serde::Serialize;
+/// #[derive(Serialize, PostcardBindings)]
+/// struct Test {
+///     field: u8
+/// }
+///
+/// # fn main() {
+/// let parent_dir = std::env::temp_dir();
+///
+/// build_deno_module(parent_dir.as_path(), "bindings", GenerationSettings::enable_all(), generate_bindings!(Test));
+/// # }
+/// ```
+pub fn build_deno_module(
+    parent_dir: &Path,
+    module_name: &str,
+    gen_settings: impl Borrow<GenerationSettings>,
+    bindings: ContainerCollection,
+) -> io::Result<()> {
+    std::fs::create_dir_all(parent_dir)?;
+
+    let (tokens, _) = generate_deno(bindings, gen_settings);
+
+    let module_path = parent_dir.join(format!("{module_name}.ts"));
+    File::create(module_path.as_path())?.write_all(tokens.to_file_string().unwrap().as_bytes())?;
+
+    Ok(())
+}
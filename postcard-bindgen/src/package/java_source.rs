@@ -0,0 +1,49 @@
+use core::borrow::Borrow;
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use postcard_bindgen_core::{
+    code_gen::java::{class_name, generate, GenerationSettings},
+    registry::ContainerCollection,
+};
+
+/// Builds a Java source file.
+///
+/// The file is written to `<parent_dir>/<Name>.java`, where `Name` is `name` in pascal
+/// case, and declares the package `name`, lowercased without separators. It only depends
+/// on the Java standard library, records and sealed interfaces require Java 17.
+///
+/// # Example
+/// ```
+/// # use postcard_bindgen::{java::{build_source, GenerationSettings}, PostcardBindings, generate_bindings};
+/// # use serde::Serialize;
+/// #[derive(Serialize, PostcardBindings)]
+/// struct Test {
+///     field: u8
+/// }
+///
+/// # fn main() {
+/// let parent_dir = std::env::temp_dir().join("bindings");
+///
+/// build_source(parent_dir.as_path(), "bindings", GenerationSettings::enable_all(), generate_bindings!(Test));
+/// # }
+/// ```
+pub fn build_java_source(
+    parent_dir: &Path,
+    name: &str,
+    gen_settings: impl Borrow<GenerationSettings>,
+    bindings: ContainerCollection,
+) -> io::Result<()> {
+    std::fs::create_dir_all(parent_dir)?;
+
+    let exports = generate(bindings, gen_settings, name.to_owned());
+
+    let content = exports.file("source").unwrap().to_file_string().unwrap();
+    File::create(parent_dir.join(format!("{}.java", class_name(name))))?
+        .write_all(content.as_bytes())?;
+
+    Ok(())
+}
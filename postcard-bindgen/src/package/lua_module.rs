@@ -0,0 +1,48 @@
+use core::borrow::Borrow;
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use postcard_bindgen_core::{
+    code_gen::lua::{generate, GenerationSettings},
+    registry::ContainerCollection,
+};
+
+/// Builds a Lua module.
+///
+/// The module is written to `<parent_dir>/<name>.lua`, so it can be loaded with
+/// `require("<name>")` if `parent_dir` is on the `package.path`. It runs on Lua 5.1 to 5.4
+/// and LuaJIT without any libraries.
+///
+/// # Example
+/// ```
+/// # use postcard_bindgen::{lua::{build_module, GenerationSettings}, PostcardBindings, generate_bindings};
+/// # use serde::Serialize;
+/// #[derive(Serialize, PostcardBindings)]
+/// struct Test {
+///     field: u8
+/// }
+///
+/// # fn main() {
+/// let parent_dir = std::env::temp_dir();
+///
+/// build_module(parent_dir.as_path(), "bindings", GenerationSettings::enable_all(), generate_bindings!(Test));
+/// # }
+/// ```
+pub fn build_lua_module(
+    parent_dir: &Path,
+    name: &str,
+    gen_settings: impl Borrow<GenerationSettings>,
+    bindings: ContainerCollection,
+) -> io::Result<()> {
+    std::fs::create_dir_all(parent_dir)?;
+
+    let exports = generate(bindings, gen_settings);
+
+    let content = exports.file("source").unwrap().to_file_string().unwrap();
+    File::create(parent_dir.join(format!("{name}.lua")))?.write_all(content.as_bytes())?;
+
+    Ok(())
+}
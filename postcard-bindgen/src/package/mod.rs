@@ -1,5 +1,17 @@
+pub mod c_sources;
+pub mod cpp_header;
+pub mod csharp_source;
+pub mod dart_library;
+pub mod deno_module;
+pub mod go_source;
+pub mod java_source;
+pub mod kotlin_source;
+pub mod lua_module;
 pub mod npm_package;
 pub mod pip_module;
+pub mod swift_source;
+pub mod ts_module;
+pub mod web_module;
 
 use std::{
     error::Error,
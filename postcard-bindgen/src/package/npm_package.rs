@@ -6,15 +6,23 @@ use std::{
 };
 
 use postcard_bindgen_core::{
-    code_gen::js::{generate, GenerationSettings},
-    lang::js::Tokens,
+    code_gen::js::{generate, generate_dual, GenerationSettings},
+    lang::js::{JavaScript, Tokens},
     registry::ContainerCollection,
+    Exports,
 };
 
 use super::{PackageInfo, Version};
 
 /// Builds a npm package from created language binding strings.
 ///
+/// The package holds the bindings in `index.js`, the type declarations in `index.d.ts` if
+/// enabled and a `package.json` with an `exports` map, so it can be published or installed
+/// from its path as is. With [`GenerationSettings::split_modules`], the bindings are split
+/// into `runtime.js`, a module per type in `types/` and the `index.js` importing them.
+///
+/// Fails with [`io::ErrorKind::InvalidInput`] if the name is not a valid npm package name.
+///
 /// # Example
 /// ```
 /// # use postcard_bindgen::{javascript::{build_package, GenerationSettings}, PackageInfo, PostcardBindings, generate_bindings};
@@ -40,6 +48,8 @@ pub fn build_npm_package(
     gen_settings: impl Borrow<GenerationSettings>,
     bindings: ContainerCollection,
 ) -> io::Result<()> {
+    check_package_name(&package_info.name)?;
+
     let mut dir = parent_dir.to_path_buf();
     dir.push(package_info.name.as_str());
 
@@ -52,20 +62,118 @@ pub fn build_npm_package(
         &package_info.version,
         exports.file("ts").is_some(),
         export_meta.esm_module,
+        export_meta.split_modules,
     );
 
     let mut package_json_path = dir.to_owned();
     package_json_path.push("package.json");
     File::create(package_json_path.as_path())?.write_all(package_json.as_bytes())?;
 
-    let js_export_path = dir.join("index.js");
-    let js_tokens = [
+    if let Some(file) = exports.pop_file("ts") {
+        let ts_export_path = dir.join("index.d.ts");
+        File::create(ts_export_path.as_path())?
+            .write_all(file.to_file_string().unwrap().as_bytes())?;
+    }
+
+    if export_meta.split_modules {
+        for file in exports.files {
+            let path = dir.join(format!("{}.js", file.content_type));
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            File::create(path)?.write_all(file.content.to_file_string().unwrap().as_bytes())?;
+        }
+    } else {
+        let js_export_path = dir.join("index.js");
+        let js_tokens = js_module(&mut exports);
+
+        File::create(js_export_path.as_path())?
+            .write_all(js_tokens.to_file_string().unwrap().as_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// Builds a npm package holding the bindings in every module format.
+///
+/// The package contains `index.cjs` for `require`, `index.mjs` for `import` and
+/// `index.umd.js` for `<script>` tags and AMD loaders, which all hold the same bindings.
+/// The `exports` map of the `package.json` points node and bundlers to the right file.
+/// Loaded by a `<script>` tag, the functions are placed in a global object named after the
+/// package in camel case, e.g. `testPackage` for `@scope/test-package`.
+///
+/// The ESM option of [`GenerationSettings`] is ignored.
+///
+/// Fails with [`io::ErrorKind::InvalidInput`] if the name is not a valid npm package name.
+///
+/// # Example
+/// ```
+/// # use postcard_bindgen::{javascript::{build_dual_package, GenerationSettings}, PackageInfo, PostcardBindings, generate_bindings};
+/// # use serde::Serialize;
+/// #[derive(Serialize, PostcardBindings)]
+/// struct Test {
+///     field: u8
+/// }
+///
+/// # fn main() {
+/// let parent_dir = std::env::temp_dir();
+/// let package_info = PackageInfo {
+///     name: "test-package".into(),
+///     version: "0.1.0".try_into().unwrap()
+/// };
+///
+/// build_dual_package(parent_dir.as_path(), package_info, GenerationSettings::enable_all(), generate_bindings!(Test));
+/// # }
+/// ```
+pub fn build_dual_npm_package(
+    parent_dir: &Path,
+    package_info: PackageInfo,
+    gen_settings: impl Borrow<GenerationSettings>,
+    bindings: ContainerCollection,
+) -> io::Result<()> {
+    check_package_name(&package_info.name)?;
+
+    let dir = parent_dir.join(package_info.name.as_str());
+
+    std::fs::create_dir_all(&dir)?;
+
+    let (mut cjs_exports, mut esm_exports, _) = generate_dual(bindings, gen_settings);
+    let ts_types_enabled = esm_exports.file("ts").is_some();
+
+    let package_json = dual_package_file_src(
+        package_info.name.as_str(),
+        &package_info.version,
+        ts_types_enabled,
+    );
+    File::create(dir.join("package.json"))?.write_all(package_json.as_bytes())?;
+
+    let cjs = js_module(&mut cjs_exports).to_file_string().unwrap();
+    let esm = js_module(&mut esm_exports).to_file_string().unwrap();
+    let umd = umd_module(&cjs, &global_name(&package_info.name));
+
+    File::create(dir.join("index.cjs"))?.write_all(cjs.as_bytes())?;
+    File::create(dir.join("index.mjs"))?.write_all(esm.as_bytes())?;
+    File::create(dir.join("index.umd.js"))?.write_all(umd.as_bytes())?;
+
+    if let Some(file) = esm_exports.file("ts") {
+        File::create(dir.join("index.d.ts"))?
+            .write_all(file.to_file_string().unwrap().as_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// Joins the generated javascript files into a single module.
+pub(crate) fn js_module(exports: &mut Exports<JavaScript>) -> Tokens {
+    [
         "util",
+        "known_types",
         "serializer",
         "deserializer",
         "runtime_checks",
         "ser",
         "des",
+        "self_test",
     ]
     .into_iter()
     .filter_map(|t| exports.pop_file(t))
@@ -73,18 +181,105 @@ pub fn build_npm_package(
         current.append(content.clone());
         current.line();
         current
-    });
+    })
+}
 
-    File::create(js_export_path.as_path())?
-        .write_all(js_tokens.to_file_string().unwrap().as_bytes())?;
+/// Wraps the CommonJS module `cjs`, which assigns its functions to `exports`.
+fn umd_module(cjs: &str, global_name: &str) -> String {
+    let body = cjs
+        .lines()
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!("    {line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!(
+        "\
+(function (root, factory) {{
+    if (typeof define === \"function\" && define.amd) {{
+        define([\"exports\"], factory);
+    }} else if (typeof exports === \"object\" && typeof module === \"object\") {{
+        factory(exports);
+    }} else {{
+        factory((root.{global_name} = {{}}));
+    }}
+}})(typeof globalThis !== \"undefined\" ? globalThis : this, function (exports) {{
+{body}
+}});
+"
+    )
+}
 
-    if let Some(file) = exports.file("ts") {
-        let ts_export_path = dir.join("index.d.ts");
-        File::create(ts_export_path.as_path())?
-            .write_all(file.to_file_string().unwrap().as_bytes())?;
+/// The package name without scope in camel case, e.g. `testPackage` for
+/// `@scope/test-package`.
+fn global_name(package_name: &str) -> String {
+    let name = package_name.rsplit('/').next().unwrap_or(package_name);
+    let mut out = String::new();
+    for (index, word) in name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .enumerate()
+    {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            if index == 0 {
+                out.push(first.to_ascii_lowercase());
+            } else {
+                out.push(first.to_ascii_uppercase());
+            }
+            out.extend(chars);
+        }
+    }
+    if out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert(0, '_');
+    }
+    out
+}
+
+/// Checks the rules npm applies to new package names: at most 214 characters, lowercase,
+/// url safe and not starting with a dot or an underscore, optionally prefixed by a scope.
+fn check_package_name(package_name: &str) -> io::Result<()> {
+    let invalid = |reason: &str| {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid npm package name \"{package_name}\": {reason}"),
+        ))
+    };
+
+    if package_name.len() > 214 {
+        return invalid("longer than 214 characters");
     }
 
-    Ok(())
+    let name = match package_name.strip_prefix('@') {
+        Some(scoped) => match scoped.split_once('/') {
+            Some((scope, name)) if !scope.is_empty() => {
+                if !scope.chars().all(is_url_safe) {
+                    return invalid("scope contains characters which are not url safe");
+                }
+                name
+            }
+            _ => return invalid("scope must be followed by a name, e.g. @scope/name"),
+        },
+        None => package_name,
+    };
+
+    if name.is_empty() {
+        invalid("name is empty")
+    } else if name.starts_with(['.', '_']) {
+        invalid("name starts with a dot or an underscore")
+    } else if !name.chars().all(is_url_safe) {
+        invalid("only lowercase letters, digits and -._~ are allowed")
+    } else {
+        Ok(())
+    }
+}
+
+fn is_url_safe(c: char) -> bool {
+    c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '.' | '_' | '~')
 }
 
 fn package_file_src(
@@ -92,14 +287,81 @@ fn package_file_src(
     package_version: &Version,
     ts_types_enabled: bool,
     esm_module: bool,
+    split_modules: bool,
+) -> String {
+    let (types, export_types, types_file) = if ts_types_enabled {
+        (
+            ",\n    \"types\": \"index.d.ts\"",
+            "\n            \"types\": \"./index.d.ts\",",
+            ", \"index.d.ts\"",
+        )
+    } else {
+        ("", "", "")
+    };
+    let (module_type, condition) = if esm_module {
+        (",\n    \"type\": \"module\"", "import")
+    } else {
+        ("", "require")
+    };
+    // the type modules can be imported on their own, e.g. `<package>/types/POINT`
+    let (type_modules, side_effects, files) = if split_modules {
+        (
+            ",\n        \"./types/*\": \"./types/*.js\"",
+            ",\n    \"sideEffects\": false",
+            "\"index.js\", \"runtime.js\", \"types\"",
+        )
+    } else {
+        ("", "", "\"index.js\"")
+    };
+    format!("\
+{{
+    \"name\": \"{}\",
+    \"description\": \"Auto generated bindings for postcard format serializing and deserializing javascript to and from bytes.\",
+    \"version\": \"{}\",
+    \"main\": \"index.js\"{types}{module_type},
+    \"exports\": {{
+        \".\": {{{export_types}
+            \"{condition}\": \"./index.js\",
+            \"default\": \"./index.js\"
+        }}{type_modules}
+    }}{side_effects},
+    \"files\": [{files}{types_file}]
+}}",
+        package_name.as_ref(), package_version
+    )
+}
+
+fn dual_package_file_src(
+    package_name: impl AsRef<str>,
+    package_version: &Version,
+    ts_types_enabled: bool,
 ) -> String {
+    let (types, export_types, files) = if ts_types_enabled {
+        (
+            ",\n    \"types\": \"index.d.ts\"",
+            "\n            \"types\": \"./index.d.ts\",",
+            ", \"index.d.ts\"",
+        )
+    } else {
+        ("", "", "")
+    };
     format!("\
 {{
     \"name\": \"{}\",
     \"description\": \"Auto generated bindings for postcard format serializing and deserializing javascript to and from bytes.\",
     \"version\": \"{}\",
-    \"main\": \"index.js\"{}{}
+    \"main\": \"index.cjs\",
+    \"module\": \"index.mjs\",
+    \"unpkg\": \"index.umd.js\"{types},
+    \"exports\": {{
+        \".\": {{{export_types}
+            \"import\": \"./index.mjs\",
+            \"require\": \"./index.cjs\",
+            \"default\": \"./index.umd.js\"
+        }}
+    }},
+    \"files\": [\"index.cjs\", \"index.mjs\", \"index.umd.js\"{files}]
 }}",
-        package_name.as_ref(), package_version, if ts_types_enabled { ",\n\t\"types\": \"index.d.ts\"" } else { "" }, if esm_module { ",\n\t\"type\": \"module\"" } else { "" }
+        package_name.as_ref(), package_version
     )
 }
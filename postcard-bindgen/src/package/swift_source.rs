@@ -0,0 +1,46 @@
+use core::borrow::Borrow;
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use postcard_bindgen_core::{
+    code_gen::swift::{generate, GenerationSettings},
+    registry::ContainerCollection,
+};
+
+/// Builds a Swift source file.
+///
+/// The file is written to `<parent_dir>/<name>.swift` and only depends on `Foundation`.
+///
+/// # Example
+/// ```
+/// # use postcard_bindgen::{swift::{build_source, GenerationSettings}, PostcardBindings, generate_bindings};
+/// # use serde::Serialize;
+/// #[derive(Serialize, PostcardBindings)]
+/// struct Test {
+///     field: u8
+/// }
+///
+/// # fn main() {
+/// let parent_dir = std::env::temp_dir();
+///
+/// build_source(parent_dir.as_path(), "Bindings", GenerationSettings::enable_all(), generate_bindings!(Test));
+/// # }
+/// ```
+pub fn build_swift_source(
+    parent_dir: &Path,
+    name: &str,
+    gen_settings: impl Borrow<GenerationSettings>,
+    bindings: ContainerCollection,
+) -> io::Result<()> {
+    std::fs::create_dir_all(parent_dir)?;
+
+    let exports = generate(bindings, gen_settings);
+
+    let content = exports.file("source").unwrap().to_file_string().unwrap();
+    File::create(parent_dir.join(format!("{name}.swift")))?.write_all(content.as_bytes())?;
+
+    Ok(())
+}
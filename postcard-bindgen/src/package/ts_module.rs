@@ -0,0 +1,47 @@
+use core::borrow::Borrow;
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use postcard_bindgen_core::{
+    code_gen::js::{generate_ts, GenerationSettings},
+    registry::ContainerCollection,
+};
+
+/// Builds a single self-contained typescript module.
+///
+/// The module is written to `<parent_dir>/<module_name>.ts` and can be imported
+/// without any further files, e.g. by a bundler or the typescript compiler.
+///
+/// # Example
+/// ```
+/// # use postcard_bindgen::{javascript::{build_ts_module, GenerationSettings}, PostcardBindings, generate_bindings};
+/// # use serde::Serialize;
+/// #[derive(Serialize, PostcardBindings)]
+/// struct Test {
+///     field: u8
+/// }
+///
+/// # fn main() {
+/// let parent_dir = std::env::temp_dir();
+///
+/// build_ts_module(parent_dir.as_path(), "bindings", GenerationSettings::enable_all(), generate_bindings!(Test));
+/// # }
+/// ```
+pub fn build_ts_module(
+    parent_dir: &Path,
+    module_name: &str,
+    gen_settings: impl Borrow<GenerationSettings>,
+    bindings: ContainerCollection,
+) -> io::Result<()> {
+    std::fs::create_dir_all(parent_dir)?;
+
+    let (tokens, _) = generate_ts(bindings, gen_settings);
+
+    let module_path = parent_dir.join(format!("{module_name}.ts"));
+    File::create(module_path.as_path())?.write_all(tokens.to_file_string().unwrap().as_bytes())?;
+
+    Ok(())
+}
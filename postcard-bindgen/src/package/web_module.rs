@@ -0,0 +1,58 @@
+use core::borrow::Borrow;
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use postcard_bindgen_core::{
+    code_gen::js::{generate_web, GenerationSettings},
+    registry::ContainerCollection,
+};
+
+use super::npm_package::js_module;
+
+/// Builds a single javascript module for browsers and deno.
+///
+/// The module is written to `<parent_dir>/<module_name>.js` and only uses web APIs, so it
+/// can be loaded by `<script type="module">` or imported by deno without a bundler. With
+/// typescript types enabled, the declarations are written to `<module_name>.d.ts` next to
+/// it, which deno picks up through the `@ts-self-types` comment of the module.
+///
+/// # Example
+/// ```
+/// # use postcard_bindgen::{javascript::{build_web_module, GenerationSettings}, PostcardBindings, generate_bindings};
+/// # use serde::Serialize;
+/// #[derive(Serialize, PostcardBindings)]
+/// struct Test {
+///     field: u8
+/// }
+///
+/// # fn main() {
+/// let parent_dir = std::env::temp_dir();
+///
+/// build_web_module(parent_dir.as_path(), "bindings", GenerationSettings::enable_all(), generate_bindings!(Test));
+/// # }
+/// ```
+pub fn build_web_module(
+    parent_dir: &Path,
+    module_name: &str,
+    gen_settings: impl Borrow<GenerationSettings>,
+    bindings: ContainerCollection,
+) -> io::Result<()> {
+    std::fs::create_dir_all(parent_dir)?;
+
+    let (mut exports, _) = generate_web(bindings, gen_settings);
+
+    let mut module = String::new();
+    if let Some(file) = exports.pop_file("ts") {
+        File::create(parent_dir.join(format!("{module_name}.d.ts")))?
+            .write_all(file.to_file_string().unwrap().as_bytes())?;
+        module.push_str(&format!("// @ts-self-types=\"./{module_name}.d.ts\"\n\n"));
+    }
+    module.push_str(&js_module(&mut exports).to_file_string().unwrap());
+
+    File::create(parent_dir.join(format!("{module_name}.js")))?.write_all(module.as_bytes())?;
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use postcard_bindgen::{
+    build::{write_bindings, Options},
+    generate_bindings,
+    javascript::GenerationSettings,
+    PostcardBindings,
+};
+use serde::Serialize;
+
+#[derive(Serialize, PostcardBindings)]
+struct A {
+    a: u8,
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct B(u16);
+
+#[test]
+fn test_write_bindings_only_on_change() {
+    let out_dir = std::env::temp_dir().join("postcard-bindgen-build-script");
+    let _ = std::fs::remove_dir_all(&out_dir);
+
+    let options = || Options::default().name("messages");
+    assert!(write_bindings(&out_dir, generate_bindings!(A), options()).unwrap());
+    assert!(out_dir.join("messages.js").is_file());
+    assert!(out_dir.join("messages.d.ts").is_file());
+
+    let modified = || {
+        std::fs::metadata(out_dir.join("messages.js"))
+            .unwrap()
+            .modified()
+            .unwrap()
+    };
+    let first = modified();
+    assert!(!write_bindings(&out_dir, generate_bindings!(A), options()).unwrap());
+    assert_eq!(modified(), first);
+
+    assert!(write_bindings(&out_dir, generate_bindings!(A, B), options()).unwrap());
+    let js = std::fs::read_to_string(out_dir.join("messages.js")).unwrap();
+    assert!(js.contains("\"B\""));
+}
+
+#[test]
+fn test_write_bindings_without_types() {
+    let out_dir = std::env::temp_dir().join("postcard-bindgen-build-script-js");
+    let _ = std::fs::remove_dir_all(&out_dir);
+
+    let settings = GenerationSettings::enable_all().type_script_types(false);
+    let options = Options::default().generation_settings(settings);
+    assert!(write_bindings(&out_dir, generate_bindings!(A), options).unwrap());
+    assert!(out_dir.join("bindings.js").is_file());
+    assert!(!out_dir.join("bindings.d.ts").exists());
+}
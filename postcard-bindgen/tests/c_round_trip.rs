@@ -0,0 +1,291 @@
+mod common;
+
+use std::{
+    collections::BTreeMap,
+    ops::Range,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use common::{build_bindings, byte_list, printed_bytes, run};
+use postcard_bindgen::{
+    c::{build_sources, GenerationSettings},
+    generate_bindings, PostcardBindings,
+};
+use serde::Serialize;
+
+fn build_c_sources(
+    name: &str,
+    bindings: postcard_bindgen::__private::ContainerCollection,
+) -> PathBuf {
+    build_bindings("c", name, bindings, |dir, bindings| {
+        build_sources(dir, "bindings", GenerationSettings::enable_all(), bindings)
+    })
+}
+
+/// Compiles and runs the program together with the generated sources.
+fn run_c(dir: &Path, main: &str) -> String {
+    let main_path = dir.join("main.c");
+    let binary_path = dir.join("main");
+    std::fs::write(
+        main_path.as_path(),
+        format!("#include <stdio.h>\n#include \"bindings.h\"\n\n{main}"),
+    )
+    .unwrap();
+
+    run(Command::new("cc")
+        .args(["-std=c99", "-Wall", "-Wextra", "-pedantic", "-Werror", "-o"])
+        .arg(&binary_path)
+        .arg(&main_path)
+        .arg(dir.join("bindings.c")));
+    run(&mut Command::new(&binary_path))
+}
+
+fn c_bytes(bytes: &[u8]) -> String {
+    format!("{{{}}}", byte_list(bytes))
+}
+
+const PRINT_BYTES: &str = "static void print_bytes(const uint8_t *bytes, size_t len) {
+    for (size_t i = 0; i < len; i++) {
+        printf(i == 0 ? \"%u\" : \" %u\", bytes[i]);
+    }
+    printf(\"\\n\");
+}
+";
+
+#[derive(Serialize, PostcardBindings)]
+struct Inner {
+    id: u16,
+    name: String,
+}
+
+#[derive(Serialize, PostcardBindings)]
+enum Instruction {
+    Stop,
+    Move(i32, i32),
+    Rename { to: String },
+    Batch(Vec<Inner>),
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Message {
+    seq: u64,
+    temp: f32,
+    ratio: f64,
+    flag: bool,
+    level: i8,
+    offset: i64,
+    data: Vec<u8>,
+    inner: Inner,
+    maybe: Option<u32>,
+    fixed: [u16; 3],
+    pair: (u8, String),
+    range: Range<u32>,
+    table: BTreeMap<u8, String>,
+    instruction: Instruction,
+    instructions: Vec<Instruction>,
+}
+
+fn message() -> Message {
+    Message {
+        seq: u64::MAX,
+        temp: -1.5,
+        ratio: 0.1,
+        flag: true,
+        level: -128,
+        offset: i64::MIN,
+        data: vec![1, 2, 255],
+        inner: Inner {
+            id: 300,
+            name: "grüße".into(),
+        },
+        maybe: Some(70_000),
+        fixed: [1, 2, 65_535],
+        pair: (7, "seven".into()),
+        range: 3..9,
+        table: BTreeMap::from([(1, "one".into()), (2, "two".into())]),
+        instruction: Instruction::Rename { to: "x".into() },
+        instructions: vec![
+            Instruction::Stop,
+            Instruction::Move(-5, 70_000),
+            Instruction::Batch(vec![Inner {
+                id: 1,
+                name: "a".into(),
+            }]),
+        ],
+    }
+}
+
+#[test]
+fn test_decode_and_encode_round_trip() {
+    let dir = build_c_sources(
+        "round-trip",
+        generate_bindings!(Inner, Instruction, Message),
+    );
+    let bytes = postcard::to_allocvec(&message()).unwrap();
+
+    let main = format!(
+        "{PRINT_BYTES}
+int main(void) {{
+    const uint8_t input[] = {};
+    uint8_t scratch[512], output[512];
+    size_t consumed, written;
+    Message message;
+    if (!Message_decode(&message, input, sizeof(input), scratch, sizeof(scratch), &consumed)) {{
+        return 1;
+    }}
+    printf(\"%zu %.*s %u\\n\", consumed, (int)message.inner.name.len, message.inner.name.ptr, message.instructions.items[2].value.Batch.items[0].id);
+    if (!Message_encode(&message, output, sizeof(output), &written)) {{
+        return 1;
+    }}
+    print_bytes(output, written);
+    return 0;
+}}",
+        c_bytes(&bytes)
+    );
+    let output = run_c(&dir, &main);
+    let mut lines = output.lines();
+
+    assert_eq!(lines.next().unwrap(), format!("{} grüße 1", bytes.len()));
+    assert_eq!(lines.next().unwrap(), printed_bytes(&bytes));
+}
+
+#[test]
+fn test_encode_matches_postcard() {
+    let dir = build_c_sources("encode", generate_bindings!(Inner, Instruction));
+
+    let values = [
+        Instruction::Stop,
+        Instruction::Move(-5, i32::MAX),
+        Instruction::Rename { to: "to".into() },
+        Instruction::Batch(vec![
+            Inner {
+                id: 1,
+                name: "a".into(),
+            },
+            Inner {
+                id: 2,
+                name: "".into(),
+            },
+        ]),
+    ];
+
+    let main = format!(
+        "{PRINT_BYTES}
+int main(void) {{
+    const Inner batch[] = {{ {{ 1, {{ \"a\", 1 }} }}, {{ 2, {{ \"\", 0 }} }} }};
+    Instruction values[4];
+    values[0].tag = Instruction_Stop;
+    values[1].tag = Instruction_Move;
+    values[1].value.Move._0 = -5;
+    values[1].value.Move._1 = INT32_MAX;
+    values[2].tag = Instruction_Rename;
+    values[2].value.Rename.to.ptr = \"to\";
+    values[2].value.Rename.to.len = 2;
+    values[3].tag = Instruction_Batch;
+    values[3].value.Batch.items = batch;
+    values[3].value.Batch.len = 2;
+    for (size_t i = 0; i < 4; i++) {{
+        uint8_t output[64];
+        size_t written;
+        if (!Instruction_encode(&values[i], output, sizeof(output), &written)) {{
+            return 1;
+        }}
+        print_bytes(output, written);
+    }}
+    return 0;
+}}"
+    );
+    let output = run_c(&dir, &main);
+
+    for (line, value) in output.lines().zip(values) {
+        assert_eq!(line, printed_bytes(&postcard::to_allocvec(&value).unwrap()));
+    }
+}
+
+#[test]
+fn test_decode_rejects_invalid_input() {
+    let dir = build_c_sources("invalid", generate_bindings!(Inner, Instruction));
+    let bytes = postcard::to_allocvec(&Instruction::Batch(vec![Inner {
+        id: 1,
+        name: "a".into(),
+    }]))
+    .unwrap();
+
+    let main = format!(
+        "int main(void) {{
+    const uint8_t input[] = {};
+    const uint8_t invalid_utf8[] = {{2, 1, 0x80}};
+    const uint8_t unknown_variant[] = {{4}};
+    uint8_t scratch[64];
+    Instruction value;
+    printf(\"%d\\n\", Instruction_decode(&value, input, sizeof(input), scratch, sizeof(scratch), NULL));
+    printf(\"%d\\n\", Instruction_decode(&value, input, sizeof(input) - 1, scratch, sizeof(scratch), NULL));
+    printf(\"%d\\n\", Instruction_decode(&value, input, sizeof(input), scratch, 1, NULL));
+    printf(\"%d\\n\", Instruction_decode(&value, invalid_utf8, sizeof(invalid_utf8), scratch, sizeof(scratch), NULL));
+    printf(\"%d\\n\", Instruction_decode(&value, unknown_variant, sizeof(unknown_variant), scratch, sizeof(scratch), NULL));
+    return 0;
+}}",
+        c_bytes(&bytes)
+    );
+    let output = run_c(&dir, &main);
+
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        ["1", "0", "0", "0", "0"]
+    );
+}
+
+#[test]
+fn test_unit_fields_are_left_out() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Ack {
+        id: u8,
+        done: (),
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    enum Status {
+        Done(()),
+        Code((), u8),
+    }
+
+    let dir = build_c_sources("unit", generate_bindings!(Ack, Status));
+    let values = [
+        postcard::to_allocvec(&Ack { id: 3, done: () }).unwrap(),
+        postcard::to_allocvec(&Status::Done(())).unwrap(),
+        postcard::to_allocvec(&Status::Code((), 7)).unwrap(),
+    ];
+
+    let main = format!(
+        "{PRINT_BYTES}
+int main(void) {{
+    uint8_t output[16];
+    size_t written;
+    Ack ack = {{ 3 }};
+    Status done, code;
+    done.tag = Status_Done;
+    code.tag = Status_Code;
+    code.value.Code = 7;
+    if (!Ack_encode(&ack, output, sizeof(output), &written)) {{
+        return 1;
+    }}
+    print_bytes(output, written);
+    if (!Status_encode(&done, output, sizeof(output), &written)) {{
+        return 1;
+    }}
+    print_bytes(output, written);
+    if (!Status_encode(&code, output, sizeof(output), &written)) {{
+        return 1;
+    }}
+    print_bytes(output, written);
+    return 0;
+}}"
+    );
+    let output = run_c(&dir, &main);
+
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        values.map(|bytes| printed_bytes(&bytes))
+    );
+}
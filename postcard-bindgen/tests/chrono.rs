@@ -0,0 +1,94 @@
+use std::{io::ErrorKind, process::Command};
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use postcard_bindgen::{
+    generate_bindings,
+    javascript::{build_package, Codec, GenerationSettings},
+    PackageInfo, PostcardBindings,
+};
+use serde::Serialize;
+
+#[derive(Serialize, PostcardBindings)]
+struct Log {
+    at: DateTime<Utc>,
+    day: NaiveDate,
+    local: NaiveDateTime,
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct DatedLog {
+    #[postcard_bindgen(codec = "date_time")]
+    at: DateTime<Utc>,
+    #[postcard_bindgen(codec = "naive_date")]
+    day: NaiveDate,
+    #[postcard_bindgen(codec = "naive_date_time")]
+    local: NaiveDateTime,
+}
+
+#[test]
+fn test_chrono_round_trip() {
+    let at = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+    let log = Log {
+        at,
+        day: at.date_naive(),
+        local: at.naive_utc(),
+    };
+    let bytes = postcard::to_allocvec(&log).unwrap();
+    let dated_bytes = postcard::to_allocvec(&DatedLog {
+        at: log.at,
+        day: log.day,
+        local: log.local,
+    })
+    .unwrap();
+    assert_eq!(bytes, dated_bytes);
+
+    let parent_dir = std::env::temp_dir().join("postcard-bindgen-chrono");
+    build_package(
+        &parent_dir,
+        PackageInfo {
+            name: "chrono".into(),
+            version: "0.1.0".try_into().unwrap(),
+        },
+        GenerationSettings::enable_all()
+            .esm_module(false)
+            .codec("date_time", Codec::date_time())
+            .codec("naive_date", Codec::naive_date())
+            .codec("naive_date_time", Codec::naive_date_time()),
+        generate_bindings!(Log, DatedLog),
+    )
+    .unwrap();
+
+    let script = format!(
+        "const p = require({package:?});
+        console.log(JSON.stringify(p.deserialize(\"Log\", {bytes})));
+        const dated = p.deserialize(\"DatedLog\", {bytes});
+        console.log(dated.at instanceof Date, dated.at.getTime(), dated.day.getTime(), dated.local.getTime());
+        const decoded = Array.from(p.serialize(\"DatedLog\", dated));
+        console.log(JSON.stringify(p.deserialize(\"Log\", decoded)));",
+        package = parent_dir.join("chrono").join("index.js"),
+        bytes = format!("{bytes:?}"),
+    );
+    let output = match Command::new("node").arg("-e").arg(script).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            eprintln!("node not found, skipping chrono test");
+            return;
+        }
+        Err(e) => panic!("failed to run node: {e}"),
+    };
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            "{\"at\":\"2023-11-14T22:13:20Z\",\"day\":\"2023-11-14\",\"local\":\"2023-11-14T22:13:20\"}",
+            "true 1700000000000 1699920000000 1700000000000",
+            "{\"at\":\"2023-11-14T22:13:20.000Z\",\"day\":\"2023-11-14\",\"local\":\"2023-11-14T22:13:20.000\"}",
+        ]
+    );
+}
@@ -0,0 +1,61 @@
+//! Helpers of the round trip tests of the languages whose bindings are compiled or run by an
+//! external toolchain.
+//!
+//! The tests fail if the toolchain is missing. The ones needing a toolchain which is not
+//! installed on most machines, Kotlin, Swift, Dart, .NET, Go and Lua, are ignored and run with
+//! `cargo test -- --ignored`, like CI does after installing them.
+
+// each test uses a part of the helpers
+#![allow(dead_code)]
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use postcard_bindgen::__private::ContainerCollection;
+
+/// Writes the bindings with `build` into a directory of the test named `name`.
+pub fn build_bindings(
+    language: &str,
+    name: &str,
+    bindings: ContainerCollection,
+    build: impl FnOnce(&Path, ContainerCollection) -> io::Result<()>,
+) -> PathBuf {
+    let dir = std::env::temp_dir()
+        .join(format!("postcard-bindgen-{language}-round-trip"))
+        .join(name);
+    build(dir.as_path(), bindings).unwrap();
+    dir
+}
+
+/// Runs the command and returns what it printed to stdout.
+///
+/// Panics if the program is not installed or fails, with the output it printed.
+pub fn run(command: &mut Command) -> String {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let output = command
+        .output()
+        .unwrap_or_else(|e| panic!("failed to run `{program}`, is it installed? {e}"));
+    assert!(
+        output.status.success(),
+        "`{program}` failed with {}\n{}{}",
+        output.status,
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap().trim().to_owned()
+}
+
+/// The bytes separated by commas, for the array literals of the languages.
+pub fn byte_list(bytes: &[u8]) -> String {
+    let bytes = bytes.iter().map(u8::to_string).collect::<Vec<_>>();
+    bytes.join(", ")
+}
+
+/// The bytes separated by spaces, as the programs of the tests print them.
+pub fn printed_bytes(bytes: &[u8]) -> String {
+    let bytes = bytes.iter().map(u8::to_string).collect::<Vec<_>>();
+    bytes.join(" ")
+}
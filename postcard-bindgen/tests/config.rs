@@ -0,0 +1,115 @@
+use std::io::ErrorKind;
+
+use postcard_bindgen::{
+    config::{Config, Language, ModuleFormat, TypeChecks},
+    generate_bindings,
+    javascript::PointerWidth,
+    PostcardBindings, Version,
+};
+use serde::Serialize;
+
+#[derive(Serialize, PostcardBindings)]
+struct Msg {
+    id: u8,
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Frame(Msg);
+
+#[derive(Serialize, PostcardBindings)]
+struct DebugFrame(u32);
+
+#[test]
+fn test_parse_config() {
+    let config = Config::from_toml(
+        r#"
+        language = "python"
+        name = "firmware"
+        version = "1.2.0"
+        module-format = "dual"
+        type-checks = "runtime"
+        serialization = false
+        pointer-width = 32
+        exclude = ["DebugFrame"]
+
+        [rename]
+        Msg = "Message"
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(config.language, Language::Python);
+    assert_eq!(config.name, "firmware");
+    assert_eq!(config.version, Version::from_array([1, 2, 0]));
+    assert_eq!(config.module_format, ModuleFormat::Dual);
+    assert_eq!(config.type_checks, TypeChecks::Runtime);
+    assert!(!config.serialization);
+    assert!(config.deserialization);
+    assert_eq!(config.pointer_width, PointerWidth::Bits32);
+    assert_eq!(config.exclude, ["DebugFrame"]);
+    assert_eq!(config.rename["Msg"], "Message");
+}
+
+#[test]
+fn test_empty_config_is_default() {
+    let config = Config::from_toml("").unwrap();
+
+    assert_eq!(config.language, Language::Javascript);
+    assert_eq!(config.name, "bindings");
+    assert_eq!(config.module_format, ModuleFormat::Esm);
+    assert_eq!(config.type_checks, TypeChecks::All);
+    assert_eq!(config.pointer_width, PointerWidth::Bits64);
+}
+
+#[test]
+fn test_invalid_config() {
+    for document in [
+        "languages = \"go\"",
+        "language = \"cobol\"",
+        "version = \"1.0\"",
+        "pointer-width = 16",
+    ] {
+        let err = Config::from_toml(document).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData, "{document}");
+    }
+}
+
+#[test]
+fn test_rename_and_exclude() {
+    let config = Config::from_toml(
+        r#"
+        language = "lua"
+        exclude = ["DebugFrame"]
+        rename = { Msg = "Message" }
+        "#,
+    )
+    .unwrap();
+
+    let dir = std::env::temp_dir().join("postcard-bindgen-config");
+    config
+        .build(&dir, generate_bindings!(Msg, Frame, DebugFrame))
+        .unwrap();
+
+    let source = std::fs::read_to_string(dir.join("bindings.lua")).unwrap();
+    assert!(source.contains("encoders.Message("));
+    assert!(source.contains("encoders.Frame("));
+    assert!(!source.contains("Msg"));
+    assert!(!source.contains("DebugFrame"));
+}
+
+#[test]
+fn test_exclude_used_type() {
+    let mut bindings = generate_bindings!(Msg, Frame);
+
+    let config = Config::from_toml("exclude = [\"Msg\"]").unwrap();
+    let err = config.apply(&mut bindings).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert_eq!(
+        err.to_string(),
+        "`Msg` can't be excluded, it's used by `Frame`"
+    );
+
+    let config = Config::from_toml("rename = { Unknown = \"Other\" }").unwrap();
+    let err = config.apply(&mut bindings).unwrap_err();
+    assert_eq!(err.to_string(), "no type `Unknown` to rename");
+}
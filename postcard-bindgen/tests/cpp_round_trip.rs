@@ -0,0 +1,241 @@
+mod common;
+
+use std::{
+    collections::BTreeMap,
+    ops::Range,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use common::{build_bindings, byte_list, printed_bytes, run};
+use postcard_bindgen::{
+    cpp::{build_header, GenerationSettings},
+    generate_bindings, PostcardBindings,
+};
+use serde::Serialize;
+
+fn build_cpp_header(
+    name: &str,
+    bindings: postcard_bindgen::__private::ContainerCollection,
+) -> PathBuf {
+    build_bindings("cpp", name, bindings, |dir, bindings| {
+        build_header(dir, "bindings", GenerationSettings::enable_all(), bindings)
+    })
+}
+
+/// Compiles and runs the program together with the generated header.
+fn run_cpp(dir: &Path, main: &str) -> String {
+    let main_path = dir.join("main.cpp");
+    let binary_path = dir.join("main");
+    std::fs::write(
+        main_path.as_path(),
+        format!("#include <cstdio>\n#include \"bindings.hpp\"\n\n{main}"),
+    )
+    .unwrap();
+
+    run(Command::new("c++")
+        .args([
+            "-std=c++17",
+            "-Wall",
+            "-Wextra",
+            "-pedantic",
+            "-Werror",
+            "-o",
+        ])
+        .arg(&binary_path)
+        .arg(&main_path));
+    run(&mut Command::new(&binary_path))
+}
+
+fn cpp_bytes(bytes: &[u8]) -> String {
+    format!("{{{}}}", byte_list(bytes))
+}
+
+const PRINT_BYTES: &str = "static void print_bytes(const std::vector<std::uint8_t>& bytes) {
+    for (std::size_t i = 0; i < bytes.size(); i++) {
+        std::printf(i == 0 ? \"%u\" : \" %u\", bytes[i]);
+    }
+    std::printf(\"\\n\");
+}
+";
+
+#[derive(Serialize, PostcardBindings)]
+struct Inner {
+    id: u16,
+    name: String,
+}
+
+#[derive(Serialize, PostcardBindings)]
+enum Instruction {
+    Stop,
+    Move(i32, i32),
+    Rename { to: String },
+    Batch(Vec<Inner>),
+    Inner(Inner),
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Message {
+    seq: u64,
+    temp: f32,
+    ratio: f64,
+    flag: bool,
+    level: i8,
+    offset: i64,
+    data: Vec<u8>,
+    inner: Inner,
+    maybe: Option<u32>,
+    fixed: [u16; 3],
+    pair: (u8, String),
+    range: Range<u32>,
+    table: BTreeMap<u8, String>,
+    flags: Vec<bool>,
+    instruction: Instruction,
+    instructions: Vec<Instruction>,
+}
+
+fn message() -> Message {
+    Message {
+        seq: u64::MAX,
+        temp: -1.5,
+        ratio: 0.1,
+        flag: true,
+        level: -128,
+        offset: i64::MIN,
+        data: vec![1, 2, 255],
+        inner: Inner {
+            id: 300,
+            name: "grüße".into(),
+        },
+        maybe: Some(70_000),
+        fixed: [1, 2, 65_535],
+        pair: (7, "seven".into()),
+        range: 3..9,
+        table: BTreeMap::from([(1, "one".into()), (2, "two".into())]),
+        flags: vec![true, false],
+        instruction: Instruction::Rename { to: "x".into() },
+        instructions: vec![
+            Instruction::Stop,
+            Instruction::Move(-5, 70_000),
+            Instruction::Batch(vec![Inner {
+                id: 1,
+                name: "a".into(),
+            }]),
+            Instruction::Inner(Inner {
+                id: 2,
+                name: "b".into(),
+            }),
+        ],
+    }
+}
+
+#[test]
+fn test_decode_and_encode_round_trip() {
+    let dir = build_cpp_header(
+        "round-trip",
+        generate_bindings!(Inner, Instruction, Message),
+    );
+    let bytes = postcard::to_allocvec(&message()).unwrap();
+
+    let main = format!(
+        "{PRINT_BYTES}
+int main() {{
+    const std::vector<std::uint8_t> input = {};
+    auto message = postcard::from_bytes<bindings::Message>(input);
+    if (!message) {{
+        return 1;
+    }}
+    const auto& batch = std::get<bindings::Instruction::Batch>(message->instructions[2].value);
+    std::printf(\"%s %u\\n\", message->inner.name.c_str(), batch._0[0].id);
+    print_bytes(postcard::to_bytes(*message));
+    return 0;
+}}",
+        cpp_bytes(&bytes)
+    );
+    let output = run_cpp(&dir, &main);
+    let mut lines = output.lines();
+
+    assert_eq!(lines.next().unwrap(), "grüße 1");
+    assert_eq!(lines.next().unwrap(), printed_bytes(&bytes));
+}
+
+#[test]
+fn test_encode_matches_postcard() {
+    let dir = build_cpp_header("encode", generate_bindings!(Inner, Instruction));
+
+    let values = [
+        Instruction::Stop,
+        Instruction::Move(-5, i32::MAX),
+        Instruction::Rename { to: "to".into() },
+        Instruction::Batch(vec![
+            Inner {
+                id: 1,
+                name: "a".into(),
+            },
+            Inner {
+                id: 2,
+                name: "".into(),
+            },
+        ]),
+        Instruction::Inner(Inner {
+            id: 3,
+            name: "c".into(),
+        }),
+    ];
+
+    let main = format!(
+        "{PRINT_BYTES}
+int main() {{
+    using bindings::Instruction;
+    const std::vector<Instruction> values = {{
+        {{Instruction::Stop{{}}}},
+        {{Instruction::Move{{-5, INT32_MAX}}}},
+        {{Instruction::Rename{{\"to\"}}}},
+        {{Instruction::Batch{{{{{{1, \"a\"}}, {{2, \"\"}}}}}}}},
+        {{Instruction::Inner{{{{3, \"c\"}}}}}},
+    }};
+    for (const auto& value : values) {{
+        print_bytes(postcard::to_bytes(value));
+    }}
+    return 0;
+}}"
+    );
+    let output = run_cpp(&dir, &main);
+
+    for (line, value) in output.lines().zip(values) {
+        assert_eq!(line, printed_bytes(&postcard::to_allocvec(&value).unwrap()));
+    }
+}
+
+#[test]
+fn test_decode_rejects_invalid_input() {
+    let dir = build_cpp_header("invalid", generate_bindings!(Inner, Instruction));
+    let bytes = postcard::to_allocvec(&Instruction::Batch(vec![Inner {
+        id: 1,
+        name: "a".into(),
+    }]))
+    .unwrap();
+
+    let main = format!(
+        "int main() {{
+    using bindings::Instruction;
+    std::vector<std::uint8_t> input = {};
+    const std::vector<std::uint8_t> invalid_utf8 = {{2, 1, 0x80}};
+    const std::vector<std::uint8_t> unknown_variant = {{5}};
+    std::printf(\"%d\\n\", postcard::from_bytes<Instruction>(input).has_value());
+    std::printf(\"%d\\n\", postcard::from_bytes<Instruction>(input.data(), input.size() - 1).has_value());
+    input.push_back(0);
+    std::printf(\"%d\\n\", postcard::from_bytes<Instruction>(input).has_value());
+    std::printf(\"%d\\n\", postcard::from_bytes<Instruction>(invalid_utf8).has_value());
+    std::printf(\"%d\\n\", postcard::from_bytes<Instruction>(unknown_variant).has_value());
+    return 0;
+}}",
+        cpp_bytes(&bytes)
+    );
+    let output = run_cpp(&dir, &main);
+
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        ["1", "0", "0", "0", "0"]
+    );
+}
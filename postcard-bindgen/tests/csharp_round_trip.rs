@@ -0,0 +1,174 @@
+mod common;
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use common::{build_bindings, byte_list, printed_bytes, run};
+use postcard_bindgen::{
+    csharp::{build_source, GenerationSettings},
+    generate_bindings, PostcardBindings,
+};
+use serde::Serialize;
+
+const PROJECT: &str = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <OutputType>Exe</OutputType>
+    <TargetFramework>net8.0</TargetFramework>
+    <Nullable>enable</Nullable>
+    <TreatWarningsAsErrors>true</TreatWarningsAsErrors>
+  </PropertyGroup>
+</Project>
+"#;
+
+fn build_csharp_source(
+    name: &str,
+    bindings: postcard_bindgen::__private::ContainerCollection,
+) -> PathBuf {
+    build_bindings("csharp", name, bindings, |dir, bindings| {
+        build_source(dir, "bindings", GenerationSettings::enable_all(), bindings)
+    })
+}
+
+/// Builds and runs a console project with the top level statements of `main` and the
+/// generated source.
+fn run_csharp(dir: &Path, main: &str) -> String {
+    std::fs::write(dir.join("RoundTrip.csproj"), PROJECT).unwrap();
+    std::fs::write(
+        dir.join("Program.cs"),
+        format!(
+            "using System;\n\
+             using Bindings;\n\n\
+             {main}\n\n\
+             static void PrintBytes(byte[] bytes) => Console.WriteLine(string.Join(\" \", bytes));\n"
+        ),
+    )
+    .unwrap();
+
+    run(Command::new("dotnet").args(["run", "--project"]).arg(dir))
+}
+
+fn csharp_bytes(bytes: &[u8]) -> String {
+    format!("new byte[] {{ {} }}", byte_list(bytes))
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Inner {
+    id: u16,
+    name: String,
+}
+
+#[derive(Serialize, PostcardBindings)]
+enum Instruction {
+    Stop,
+    Move(i32, i32),
+    Rename { to: String },
+    Inner(Inner),
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Message {
+    seq: u64,
+    temp: f32,
+    level: i8,
+    big: u128,
+    data: Vec<u8>,
+    inner: Inner,
+    maybe: Option<u32>,
+    fixed: [u16; 3],
+    pair: (u8, String),
+    table: BTreeMap<u8, String>,
+    instructions: Vec<Instruction>,
+}
+
+#[test]
+#[ignore = "needs dotnet, see tests/common"]
+fn test_decode_and_encode_round_trip() {
+    let dir = build_csharp_source(
+        "round-trip",
+        generate_bindings!(Inner, Instruction, Message),
+    );
+    let message = Message {
+        seq: u64::MAX,
+        temp: -1.5,
+        level: -128,
+        big: u128::MAX,
+        data: vec![1, 2, 255],
+        inner: Inner {
+            id: 300,
+            name: "grüße".into(),
+        },
+        maybe: Some(70_000),
+        fixed: [1, 2, 65_535],
+        pair: (7, "seven".into()),
+        table: BTreeMap::from([(1, "one".into()), (2, "two".into())]),
+        instructions: vec![
+            Instruction::Stop,
+            Instruction::Move(-5, 70_000),
+            Instruction::Rename { to: "x".into() },
+            Instruction::Inner(Inner {
+                id: 1,
+                name: "a".into(),
+            }),
+        ],
+    };
+    let bytes = postcard::to_allocvec(&message).unwrap();
+
+    let main = format!(
+        "Console.OutputEncoding = System.Text.Encoding.UTF8;
+var message = Postcard.Deserialize<Message>({});
+Console.WriteLine($\"{{message.Inner.Name}} {{message.Big}} {{message.Instructions[1]}}\");
+PrintBytes(Postcard.Serialize(message));",
+        csharp_bytes(&bytes)
+    );
+    let output = run_csharp(&dir, &main);
+    let mut lines = output.lines();
+
+    assert_eq!(
+        lines.next().unwrap(),
+        format!("grüße {} Move {{ Field0 = -5, Field1 = 70000 }}", u128::MAX)
+    );
+    assert_eq!(lines.next().unwrap(), printed_bytes(&bytes));
+}
+
+#[test]
+#[ignore = "needs dotnet, see tests/common"]
+fn test_decode_rejects_invalid_input() {
+    let dir = build_csharp_source("invalid", generate_bindings!(Inner, Instruction));
+    let bytes = postcard::to_allocvec(&Instruction::Inner(Inner {
+        id: 1,
+        name: "a".into(),
+    }))
+    .unwrap();
+
+    let main = format!(
+        "var input = {};
+Console.WriteLine(Rejects(input));
+Console.WriteLine(Rejects(input[..^1]));
+Console.WriteLine(Rejects([.. input, 0]));
+Console.WriteLine(Rejects(new byte[] {{ 2, 1, 0x80 }}));
+Console.WriteLine(Rejects(new byte[] {{ 4 }}));
+
+static bool Rejects(byte[] bytes)
+{{
+    try
+    {{
+        Postcard.Deserialize<Instruction>(bytes);
+        return false;
+    }}
+    catch (PostcardException)
+    {{
+        return true;
+    }}
+}}",
+        csharp_bytes(&bytes)
+    );
+    let output = run_csharp(&dir, &main);
+
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        ["False", "True", "True", "True", "True"]
+    );
+}
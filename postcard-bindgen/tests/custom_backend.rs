@@ -0,0 +1,88 @@
+use genco::{quote_in, Tokens};
+use postcard_bindgen::{
+    backend::{
+        generate_with, Container, ContainerCollection, EnumType, ExportFile, Exports,
+        LanguageBackend, StructType, TupleStructType,
+    },
+    generate_bindings, PostcardBindings,
+};
+use serde::Serialize;
+
+#[derive(Serialize, PostcardBindings)]
+struct A {
+    a: u8,
+    b: Vec<u16>,
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct B(u8, A);
+
+#[allow(dead_code)]
+#[derive(Serialize, PostcardBindings)]
+enum C {
+    A,
+    B(B),
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct D;
+
+/// Describes the shape of each type in one line.
+#[derive(Default)]
+struct Outline {
+    prepared: bool,
+    lines: Tokens<()>,
+}
+
+impl LanguageBackend for Outline {
+    type Lang = ();
+
+    fn prepare(&mut self, containers: &mut ContainerCollection) {
+        containers.flatten();
+        self.prepared = true;
+    }
+
+    fn visit_struct(&mut self, container: &Container, struct_type: &StructType) {
+        let fields = struct_type.fields.iter().map(|field| &field.name);
+        quote_in!(self.lines => struct $(&container.name) { $(for field in fields join (, ) => $field) }$['\r']);
+    }
+
+    fn visit_tuple_struct(&mut self, container: &Container, tuple_struct_type: &TupleStructType) {
+        quote_in!(self.lines => tuple $(&container.name) with $(tuple_struct_type.fields.len()) fields$['\r']);
+    }
+
+    fn visit_enum(&mut self, container: &Container, enum_type: &EnumType) {
+        let variants = enum_type.variants.iter().map(|variant| &variant.name);
+        quote_in!(self.lines => enum $(&container.name) { $(for variant in variants join ( | ) => $variant) }$['\r']);
+    }
+
+    fn visit_unit_struct(&mut self, container: &Container) {
+        quote_in!(self.lines => unit $(&container.name)$['\r']);
+    }
+
+    fn finish(self) -> Exports<()> {
+        assert!(self.prepared);
+        Exports {
+            files: vec![ExportFile {
+                content_type: "outline".to_owned(),
+                content: self.lines,
+            }],
+        }
+    }
+}
+
+#[test]
+fn test_custom_backend_visits_all_types() {
+    let exports = generate_with(Outline::default(), generate_bindings!(A, B, C, D));
+
+    let outline = exports.file("outline").unwrap().to_file_string().unwrap();
+    assert_eq!(
+        outline.lines().collect::<Vec<_>>(),
+        [
+            "struct A { a, b }",
+            "tuple B with 2 fields",
+            "enum C { A | B }",
+            "unit D",
+        ]
+    );
+}
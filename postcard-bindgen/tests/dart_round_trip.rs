@@ -0,0 +1,163 @@
+mod common;
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use common::{build_bindings, byte_list, printed_bytes, run};
+use postcard_bindgen::{
+    dart::{build_library, GenerationSettings},
+    generate_bindings, PostcardBindings,
+};
+use serde::Serialize;
+
+fn build_dart_library(
+    name: &str,
+    bindings: postcard_bindgen::__private::ContainerCollection,
+) -> PathBuf {
+    build_bindings("dart", name, bindings, |dir, bindings| {
+        build_library(dir, "bindings", GenerationSettings::enable_all(), bindings)
+    })
+}
+
+/// Runs the `main` function together with the generated library.
+fn run_dart(dir: &Path, main: &str) -> String {
+    let main_path = dir.join("main.dart");
+    std::fs::write(
+        main_path.as_path(),
+        format!(
+            "import 'dart:typed_data';\n\n\
+             import 'bindings.dart';\n\n\
+             void printBytes(Uint8List bytes) => print(bytes.join(' '));\n\n\
+             {main}"
+        ),
+    )
+    .unwrap();
+
+    run(Command::new("dart").arg("run").arg(&main_path))
+}
+
+fn dart_bytes(bytes: &[u8]) -> String {
+    format!("Uint8List.fromList([{}])", byte_list(bytes))
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Inner {
+    id: u16,
+    name: String,
+}
+
+#[derive(Serialize, PostcardBindings)]
+enum Instruction {
+    Stop,
+    Move(i32, i32),
+    Rename { to: String },
+    Inner(Inner),
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Message {
+    seq: u64,
+    temp: f32,
+    level: i8,
+    big: u128,
+    data: Vec<u8>,
+    inner: Inner,
+    maybe: Option<u32>,
+    fixed: [u16; 3],
+    pair: (u8, String),
+    table: BTreeMap<u8, String>,
+    instructions: Vec<Instruction>,
+}
+
+#[test]
+#[ignore = "needs dart, see tests/common"]
+fn test_decode_and_encode_round_trip() {
+    let dir = build_dart_library(
+        "round-trip",
+        generate_bindings!(Inner, Instruction, Message),
+    );
+    let message = Message {
+        seq: u64::MAX,
+        temp: -1.5,
+        level: -128,
+        big: u128::MAX,
+        data: vec![1, 2, 255],
+        inner: Inner {
+            id: 300,
+            name: "grüße".into(),
+        },
+        maybe: Some(70_000),
+        fixed: [1, 2, 65_535],
+        pair: (7, "seven".into()),
+        table: BTreeMap::from([(1, "one".into()), (2, "two".into())]),
+        instructions: vec![
+            Instruction::Stop,
+            Instruction::Move(-5, 70_000),
+            Instruction::Rename { to: "x".into() },
+            Instruction::Inner(Inner {
+                id: 1,
+                name: "a".into(),
+            }),
+        ],
+    };
+    let bytes = postcard::to_allocvec(&message).unwrap();
+
+    let main = format!(
+        "void main() {{
+  final message = deserialize({}, Message.decode);
+  final move = message.instructions[1] as InstructionMove;
+  print('${{message.inner.name}} ${{message.big}} ${{move.field0}} ${{move.field1}}');
+  printBytes(serialize(message));
+}}",
+        dart_bytes(&bytes)
+    );
+    let output = run_dart(&dir, &main);
+    let mut lines = output.lines();
+
+    assert_eq!(
+        lines.next().unwrap(),
+        format!("grüße {} -5 70000", u128::MAX)
+    );
+    assert_eq!(lines.next().unwrap(), printed_bytes(&bytes));
+}
+
+#[test]
+#[ignore = "needs dart, see tests/common"]
+fn test_decode_rejects_invalid_input() {
+    let dir = build_dart_library("invalid", generate_bindings!(Inner, Instruction));
+    let bytes = postcard::to_allocvec(&Instruction::Inner(Inner {
+        id: 1,
+        name: "a".into(),
+    }))
+    .unwrap();
+
+    let main = format!(
+        "bool rejects(Uint8List bytes) {{
+  try {{
+    deserialize(bytes, Instruction.decode);
+    return false;
+  }} on PostcardException {{
+    return true;
+  }}
+}}
+
+void main() {{
+  final input = {};
+  print(rejects(input));
+  print(rejects(Uint8List.sublistView(input, 0, input.length - 1)));
+  print(rejects(Uint8List.fromList([...input, 0])));
+  print(rejects(Uint8List.fromList([2, 1, 0x80])));
+  print(rejects(Uint8List.fromList([4])));
+}}",
+        dart_bytes(&bytes)
+    );
+    let output = run_dart(&dir, &main);
+
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        ["false", "true", "true", "true", "true"]
+    );
+}
@@ -0,0 +1,58 @@
+use std::{io::ErrorKind, process::Command};
+
+use postcard_bindgen::{
+    generate_bindings,
+    javascript::{build_deno_module, GenerationSettings},
+    PostcardBindings,
+};
+use serde::Serialize;
+
+#[test]
+fn test_deno_module_type_checks() {
+    #[allow(dead_code)]
+    #[derive(Serialize, PostcardBindings)]
+    struct A {
+        a: u8,
+        b: Vec<u16>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Serialize, PostcardBindings)]
+    enum B {
+        A,
+        B(A),
+    }
+
+    let dir = std::env::temp_dir().join("postcard-bindgen-deno");
+    build_deno_module(
+        dir.as_path(),
+        "bindings",
+        GenerationSettings::enable_all(),
+        generate_bindings!(A, B),
+    )
+    .unwrap();
+
+    let main_path = dir.join("main.ts");
+    std::fs::write(
+        main_path.as_path(),
+        "import { deserialize, serialize, type B } from \"./bindings.ts\";
+        const bytes: Uint8Array = serialize(\"B\", { tag: \"B\", value: { a: 1, b: [2, 3] } });
+        const value: B = deserialize(\"B\", bytes);
+        console.log(JSON.stringify(value));",
+    )
+    .unwrap();
+
+    let output = match Command::new("deno").arg("check").arg(&main_path).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            eprintln!("deno not found, skipping deno check");
+            return;
+        }
+        Err(e) => panic!("failed to run deno: {e}"),
+    };
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
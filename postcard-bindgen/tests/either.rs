@@ -0,0 +1,68 @@
+use std::{io::ErrorKind, process::Command};
+
+use either::Either;
+use postcard_bindgen::{
+    generate_bindings,
+    javascript::{build_package, GenerationSettings},
+    PackageInfo, PostcardBindings,
+};
+use serde::Serialize;
+
+#[derive(Serialize, PostcardBindings)]
+struct Lookup {
+    key: Either<u16, String>,
+    hits: Vec<Either<u16, String>>,
+}
+
+#[test]
+fn test_either_round_trip() {
+    let lookup = Lookup {
+        key: Either::Right("name".into()),
+        hits: vec![Either::Left(300), Either::Right("a".into())],
+    };
+    let bytes = postcard::to_allocvec(&lookup).unwrap();
+
+    let parent_dir = std::env::temp_dir().join("postcard-bindgen-either");
+    build_package(
+        &parent_dir,
+        PackageInfo {
+            name: "either".into(),
+            version: "0.1.0".try_into().unwrap(),
+        },
+        GenerationSettings::enable_all().esm_module(false),
+        generate_bindings!(Lookup, Either<u16, String>),
+    )
+    .unwrap();
+
+    let script = format!(
+        "const p = require({package:?});
+        const lookup = p.deserialize(\"Lookup\", {bytes:?});
+        console.log(JSON.stringify(lookup));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Lookup\", lookup))));
+        try {{ p.serialize(\"Lookup\", {{ ...lookup, key: {{ tag: \"Left\", value: \"a\" }} }}) }} catch (e) {{ console.log(\"rejected\") }}",
+        package = parent_dir.join("either").join("index.js"),
+    );
+    let output = match Command::new("node").arg("-e").arg(script).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            eprintln!("node not found, skipping either test");
+            return;
+        }
+        Err(e) => panic!("failed to run node: {e}"),
+    };
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            "{\"key\":{\"tag\":\"Right\",\"value\":\"name\"},\"hits\":[{\"tag\":\"Left\",\"value\":300},{\"tag\":\"Right\",\"value\":\"a\"}]}",
+            &format!("{bytes:?}").replace(' ', ""),
+            "rejected",
+        ]
+    );
+}
@@ -0,0 +1,166 @@
+mod common;
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use common::{build_bindings, byte_list, printed_bytes, run};
+use postcard_bindgen::{
+    generate_bindings,
+    go::{build_source, GenerationSettings},
+    PostcardBindings,
+};
+use serde::Serialize;
+
+fn build_go_source(
+    name: &str,
+    bindings: postcard_bindgen::__private::ContainerCollection,
+) -> PathBuf {
+    // the bindings are placed in the main package, next to the main function
+    build_bindings("go", name, bindings, |dir, bindings| {
+        build_source(dir, "main", GenerationSettings::enable_all(), bindings)
+    })
+}
+
+/// Runs the `main` function together with the generated source.
+fn run_go(dir: &Path, main: &str) -> String {
+    std::fs::write(dir.join("go.mod"), "module roundtrip\n\ngo 1.21\n").unwrap();
+    std::fs::write(
+        dir.join("run.go"),
+        format!(
+            "package main\n\n\
+             import (\n    \"fmt\"\n    \"strings\"\n)\n\n\
+             func printBytes(bytes []byte, err error) {{\n    \
+             if err != nil {{\n        panic(err)\n    }}\n    \
+             fmt.Println(strings.Trim(fmt.Sprint(bytes), \"[]\"))\n}}\n\n\
+             {main}\n"
+        ),
+    )
+    .unwrap();
+
+    run(Command::new("go").args(["run", "."]).current_dir(dir))
+}
+
+fn go_bytes(bytes: &[u8]) -> String {
+    format!("[]byte{{{}}}", byte_list(bytes))
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Inner {
+    id: u16,
+    name: String,
+}
+
+#[derive(Serialize, PostcardBindings)]
+enum Instruction {
+    Stop,
+    Move(i32, i32),
+    Rename { to: String },
+    Inner(Inner),
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Message {
+    seq: u64,
+    temp: f32,
+    level: i8,
+    big: u128,
+    data: Vec<u8>,
+    inner: Inner,
+    maybe: Option<u32>,
+    fixed: [u16; 3],
+    pair: (u8, String),
+    // go maps are iterated in random order, so only a single entry round trips byte for byte
+    table: BTreeMap<u8, String>,
+    instructions: Vec<Instruction>,
+}
+
+#[test]
+#[ignore = "needs go, see tests/common"]
+fn test_decode_and_encode_round_trip() {
+    let dir = build_go_source(
+        "round-trip",
+        generate_bindings!(Inner, Instruction, Message),
+    );
+    let message = Message {
+        seq: u64::MAX,
+        temp: -1.5,
+        level: -128,
+        big: u128::MAX,
+        data: vec![1, 2, 255],
+        inner: Inner {
+            id: 300,
+            name: "grüße".into(),
+        },
+        maybe: Some(70_000),
+        fixed: [1, 2, 65_535],
+        pair: (7, "seven".into()),
+        table: BTreeMap::from([(1, "one".into())]),
+        instructions: vec![
+            Instruction::Stop,
+            Instruction::Move(-5, 70_000),
+            Instruction::Rename { to: "x".into() },
+            Instruction::Inner(Inner {
+                id: 1,
+                name: "a".into(),
+            }),
+        ],
+    };
+    let bytes = postcard::to_allocvec(&message).unwrap();
+
+    let main = format!(
+        "func main() {{
+    message, err := DecodeMessage({})
+    if err != nil {{
+        panic(err)
+    }}
+    fmt.Println(message.Inner.Name, message.Big, message.Instructions[1])
+    printBytes(EncodeMessage(message))
+}}",
+        go_bytes(&bytes)
+    );
+    let output = run_go(&dir, &main);
+    let mut lines = output.lines();
+
+    assert_eq!(
+        lines.next().unwrap(),
+        format!("grüße {} {{-5 70000}}", u128::MAX)
+    );
+    assert_eq!(lines.next().unwrap(), printed_bytes(&bytes));
+}
+
+#[test]
+#[ignore = "needs go, see tests/common"]
+fn test_decode_rejects_invalid_input() {
+    let dir = build_go_source("invalid", generate_bindings!(Inner, Instruction));
+    let bytes = postcard::to_allocvec(&Instruction::Inner(Inner {
+        id: 1,
+        name: "a".into(),
+    }))
+    .unwrap();
+
+    let main = format!(
+        "func rejects(bytes []byte) bool {{
+    _, err := DecodeInstruction(bytes)
+    return err != nil
+}}
+
+func main() {{
+    input := {}
+    fmt.Println(rejects(input))
+    fmt.Println(rejects(input[:len(input)-1]))
+    fmt.Println(rejects(append(append([]byte{{}}, input...), 0)))
+    fmt.Println(rejects([]byte{{2, 1, 0x80}}))
+    fmt.Println(rejects([]byte{{4}}))
+}}",
+        go_bytes(&bytes)
+    );
+    let output = run_go(&dir, &main);
+
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        ["false", "true", "true", "true", "true"]
+    );
+}
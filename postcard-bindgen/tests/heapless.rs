@@ -0,0 +1,126 @@
+use std::{io::ErrorKind, process::Command};
+
+use heapless::{String, Vec};
+use postcard_bindgen::{
+    generate_bindings,
+    javascript::{build_package, GenerationSettings},
+    PackageInfo, PostcardBindings,
+};
+use serde::Serialize;
+
+#[derive(Serialize, PostcardBindings)]
+struct Packet {
+    name: String<8>,
+    payload: Vec<u8, 4>,
+}
+
+#[test]
+fn test_heapless_capacity_checks() {
+    let packet = Packet {
+        name: String::try_from("sensor").unwrap(),
+        payload: Vec::from_slice(&[1, 2, 3, 4]).unwrap(),
+    };
+    let bytes = postcard::to_allocvec(&packet).unwrap();
+
+    let parent_dir = std::env::temp_dir().join("postcard-bindgen-heapless");
+    build_package(
+        &parent_dir,
+        PackageInfo {
+            name: "heapless".into(),
+            version: "0.1.0".try_into().unwrap(),
+        },
+        GenerationSettings::enable_all().esm_module(false),
+        generate_bindings!(Packet),
+    )
+    .unwrap();
+
+    let script = format!(
+        "const p = require({package:?});
+        const check = (packet) => {{ try {{ p.serialize(\"Packet\", packet); return true }} catch (e) {{ return false }} }};
+        const packet = p.deserialize(\"Packet\", {bytes:?});
+        console.log(packet.name, JSON.stringify(Array.from(packet.payload)));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Packet\", packet))));
+        console.log(check({{ ...packet, payload: [1, 2, 3, 4, 5] }}));
+        console.log(check({{ ...packet, name: \"12345678\" }}), check({{ ...packet, name: \"123456789\" }}));
+        console.log(check({{ ...packet, name: \"\u{e9}\u{e9}\u{e9}\u{e9}\" }}), check({{ ...packet, name: \"\u{e9}\u{e9}\u{e9}\u{e9}\u{e9}\" }}));",
+        package = parent_dir.join("heapless").join("index.js"),
+    );
+    let output = match Command::new("node").arg("-e").arg(script).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            eprintln!("node not found, skipping heapless test");
+            return;
+        }
+        Err(e) => panic!("failed to run node: {e}"),
+    };
+    assert!(
+        output.status.success(),
+        "{}",
+        std::string::String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = std::string::String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        output.lines().collect::<std::vec::Vec<_>>(),
+        [
+            "sensor [1,2,3,4]",
+            &format!("{bytes:?}").replace(' ', ""),
+            "false",
+            "true false",
+            "true false",
+        ]
+    );
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Buffer<T, const N: usize> {
+    data: Vec<T, N>,
+}
+
+#[test]
+fn test_const_generic_instantiations() {
+    let buffer = Buffer::<u8, 2> {
+        data: Vec::from_slice(&[1, 2]).unwrap(),
+    };
+    let bytes = postcard::to_allocvec(&buffer).unwrap();
+
+    let parent_dir = std::env::temp_dir().join("postcard-bindgen-heapless");
+    build_package(
+        &parent_dir,
+        PackageInfo {
+            name: "const-generics".into(),
+            version: "0.1.0".try_into().unwrap(),
+        },
+        GenerationSettings::enable_all().esm_module(false),
+        generate_bindings!(Buffer<u8, 2>, Buffer<u8, 4>),
+    )
+    .unwrap();
+
+    let script = format!(
+        "const p = require({package:?});
+        const check = (name, data) => {{ try {{ p.serialize(name, {{ data }}); return true }} catch (e) {{ return false }} }};
+        console.log(JSON.stringify(p.TYPES));
+        console.log(JSON.stringify(Array.from(p.deserialize(\"Buffer_u8_2\", {bytes:?}).data)));
+        console.log(check(\"Buffer_u8_2\", [1, 2, 3]), check(\"Buffer_u8_4\", [1, 2, 3]));",
+        package = parent_dir.join("const-generics").join("index.js"),
+    );
+    let output = match Command::new("node").arg("-e").arg(script).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            eprintln!("node not found, skipping heapless test");
+            return;
+        }
+        Err(e) => panic!("failed to run node: {e}"),
+    };
+    assert!(
+        output.status.success(),
+        "{}",
+        std::string::String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = std::string::String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        output.lines().collect::<std::vec::Vec<_>>(),
+        ["[\"Buffer_u8_2\",\"Buffer_u8_4\"]", "[1,2]", "false true"]
+    );
+}
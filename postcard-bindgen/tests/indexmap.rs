@@ -0,0 +1,70 @@
+use std::{io::ErrorKind, process::Command};
+
+use indexmap::{IndexMap, IndexSet};
+use postcard_bindgen::{
+    generate_bindings,
+    javascript::{build_package, GenerationSettings},
+    PackageInfo, PostcardBindings,
+};
+use serde::Serialize;
+
+#[derive(Serialize, PostcardBindings)]
+struct Routes {
+    hops: IndexMap<u16, u8>,
+    names: IndexMap<String, u8>,
+    seen: IndexSet<u16>,
+}
+
+#[test]
+fn test_indexmap_keeps_order() {
+    let routes = Routes {
+        hops: IndexMap::from([(300, 2), (7, 1), (120, 3)]),
+        names: IndexMap::from([("zeta".into(), 1), ("alpha".into(), 2)]),
+        seen: IndexSet::from([9, 3, 400]),
+    };
+    let bytes = postcard::to_allocvec(&routes).unwrap();
+
+    let parent_dir = std::env::temp_dir().join("postcard-bindgen-indexmap");
+    build_package(
+        &parent_dir,
+        PackageInfo {
+            name: "indexmap".into(),
+            version: "0.1.0".try_into().unwrap(),
+        },
+        GenerationSettings::enable_all().esm_module(false),
+        generate_bindings!(Routes),
+    )
+    .unwrap();
+
+    let script = format!(
+        "const p = require({package:?});
+        const routes = p.deserialize(\"Routes\", {bytes:?});
+        console.log(routes.hops instanceof Map, JSON.stringify([...routes.hops]));
+        console.log(JSON.stringify(routes.names), routes.seen instanceof Set, JSON.stringify([...routes.seen]));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Routes\", routes))));",
+        package = parent_dir.join("indexmap").join("index.js"),
+    );
+    let output = match Command::new("node").arg("-e").arg(script).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            eprintln!("node not found, skipping indexmap test");
+            return;
+        }
+        Err(e) => panic!("failed to run node: {e}"),
+    };
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            "true [[300,2],[7,1],[120,3]]",
+            "{\"zeta\":1,\"alpha\":2} true [9,3,400]",
+            &format!("{bytes:?}").replace(' ', ""),
+        ]
+    );
+}
@@ -0,0 +1,185 @@
+mod common;
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use common::{build_bindings, printed_bytes, run};
+use postcard_bindgen::{
+    generate_bindings,
+    java::{build_source, GenerationSettings},
+    PostcardBindings,
+};
+use serde::Serialize;
+
+fn build_java_source(
+    name: &str,
+    bindings: postcard_bindgen::__private::ContainerCollection,
+) -> PathBuf {
+    build_bindings("java", name, bindings, |dir, bindings| {
+        build_source(dir, "bindings", GenerationSettings::enable_all(), bindings)
+    })
+}
+
+/// Compiles and runs the `main` method together with the generated source.
+fn run_java(dir: &Path, main: &str) -> String {
+    std::fs::write(
+        dir.join("Main.java"),
+        format!(
+            "package bindings;\n\n\
+             import java.io.PrintStream;\n\
+             import java.nio.charset.StandardCharsets;\n\
+             import java.util.Arrays;\n\n\
+             public class Main {{\n\
+             // the default encoding of System.out depends on the locale\n\
+             static final PrintStream out = new PrintStream(System.out, true, StandardCharsets.UTF_8);\n\n\
+             static String bytes(byte[] bytes) {{\n    \
+             StringBuilder text = new StringBuilder();\n    \
+             for (byte b : bytes) {{\n        \
+             text.append(text.length() == 0 ? \"\" : \" \").append(b & 0xFF);\n    }}\n    \
+             return text.toString();\n}}\n\n\
+             {main}\n}}\n"
+        ),
+    )
+    .unwrap();
+
+    run(Command::new("javac")
+        .args([
+            "-Xlint:all",
+            "-Werror",
+            "-d",
+            "out",
+            "Bindings.java",
+            "Main.java",
+        ])
+        .current_dir(dir));
+    run(Command::new("java")
+        .args(["-cp", "out", "bindings.Main"])
+        .current_dir(dir))
+}
+
+fn java_bytes(bytes: &[u8]) -> String {
+    let bytes = bytes
+        .iter()
+        .map(|byte| format!("(byte) {byte}"))
+        .collect::<Vec<_>>();
+    format!("new byte[] {{{}}}", bytes.join(", "))
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Inner {
+    id: u16,
+    name: String,
+}
+
+#[derive(Serialize, PostcardBindings)]
+enum Instruction {
+    Stop,
+    Move(i32, i32),
+    Rename { to: String },
+    Inner(Inner),
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Message {
+    seq: u64,
+    temp: f32,
+    level: i8,
+    big: u128,
+    data: Vec<u8>,
+    inner: Inner,
+    maybe: Option<u32>,
+    fixed: [u16; 3],
+    pair: (u8, String),
+    table: BTreeMap<u8, String>,
+    instructions: Vec<Instruction>,
+}
+
+#[test]
+fn test_decode_and_encode_round_trip() {
+    let dir = build_java_source(
+        "round-trip",
+        generate_bindings!(Inner, Instruction, Message),
+    );
+    let message = Message {
+        seq: u64::MAX,
+        temp: -1.5,
+        level: -128,
+        big: u128::MAX,
+        data: vec![1, 2, 255],
+        inner: Inner {
+            id: 300,
+            name: "grüße".into(),
+        },
+        maybe: Some(70_000),
+        fixed: [1, 2, 65_535],
+        pair: (7, "seven".into()),
+        table: BTreeMap::from([(1, "one".into()), (2, "two".into())]),
+        instructions: vec![
+            Instruction::Stop,
+            Instruction::Move(-5, 70_000),
+            Instruction::Rename { to: "x".into() },
+            Instruction::Inner(Inner {
+                id: 1,
+                name: "a".into(),
+            }),
+        ],
+    };
+    let bytes = postcard::to_allocvec(&message).unwrap();
+
+    let main = format!(
+        "public static void main(String[] args) {{
+    Bindings.Message message = Bindings.deserialize({}, Bindings.Message::decode);
+    out.println(message.inner().name() + \" \" + message.big() + \" \" + message.instructions().get(1));
+    out.println(bytes(Bindings.serialize(message)));
+}}",
+        java_bytes(&bytes)
+    );
+    let output = run_java(&dir, &main);
+    let mut lines = output.lines();
+
+    assert_eq!(
+        lines.next().unwrap(),
+        format!("grüße {} Move[item0=-5, item1=70000]", u128::MAX)
+    );
+    assert_eq!(lines.next().unwrap(), printed_bytes(&bytes));
+}
+
+#[test]
+fn test_decode_rejects_invalid_input() {
+    let dir = build_java_source("invalid", generate_bindings!(Inner, Instruction));
+    let bytes = postcard::to_allocvec(&Instruction::Inner(Inner {
+        id: 1,
+        name: "a".into(),
+    }))
+    .unwrap();
+
+    let main = format!(
+        "static boolean rejects(byte[] bytes) {{
+    try {{
+        Bindings.deserialize(bytes, Bindings.Instruction::decode);
+        return false;
+    }} catch (Bindings.PostcardException e) {{
+        return true;
+    }}
+}}
+
+public static void main(String[] args) {{
+    byte[] input = {};
+    out.println(rejects(input));
+    out.println(rejects(Arrays.copyOf(input, input.length - 1)));
+    out.println(rejects(Arrays.copyOf(input, input.length + 1)));
+    out.println(rejects(new byte[] {{2, 1, (byte) 0x80}}));
+    out.println(rejects(new byte[] {{4}}));
+}}",
+        java_bytes(&bytes)
+    );
+    let output = run_java(&dir, &main);
+
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        ["false", "true", "true", "true", "true"]
+    );
+}
@@ -0,0 +1,137 @@
+use std::{io::ErrorKind, process::Command};
+
+use postcard_bindgen::{
+    generate_bindings,
+    javascript::{build_package, Codec, GenerationSettings},
+    json_ir, PackageInfo, PostcardBindings,
+};
+use serde::Serialize;
+
+mod geometry {
+    use super::*;
+
+    #[derive(Serialize, PostcardBindings)]
+    pub struct Point {
+        pub x: i32,
+        pub y: i32,
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Serialize, PostcardBindings)]
+enum Shape {
+    Empty,
+    Line(geometry::Point, geometry::Point),
+    Circle {
+        center: geometry::Point,
+        #[postcard_bindgen(codec = "radius")]
+        radius: u64,
+    },
+}
+
+#[test]
+fn test_json_ir_is_valid_json() {
+    let json = generate_bindings!(geometry::Point, Shape).to_json();
+
+    let script = "const ir = JSON.parse(require(\"fs\").readFileSync(0, \"utf8\"));
+        console.log(ir.version);
+        for (const c of ir.containers) console.log(c.path.join(\"::\"), c.name, c.kind);
+        const circle = ir.containers[1].variants[2];
+        console.log(circle.fields[0].type.name, circle.fields[1].codec);";
+
+    let mut child = match Command::new("node")
+        .arg("-e")
+        .arg(script)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            eprintln!("node not found, skipping json ir test");
+            return;
+        }
+        Err(e) => panic!("failed to run node: {e}"),
+    };
+    std::io::Write::write_all(&mut child.stdin.take().unwrap(), json.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            "1",
+            "json_ir::geometry Point struct",
+            "json_ir Shape enum",
+            "Point radius",
+        ]
+    );
+}
+
+#[test]
+fn test_package_from_json_ir() {
+    let dir = std::env::temp_dir().join("postcard-bindgen-json-ir");
+    std::fs::create_dir_all(&dir).unwrap();
+    let schema_path = dir.join("schema.json");
+    std::fs::write(
+        &schema_path,
+        generate_bindings!(geometry::Point, Shape).to_json(),
+    )
+    .unwrap();
+
+    // only the schema file is needed from here on
+    let schema = std::fs::File::open(&schema_path).unwrap();
+    build_package(
+        dir.as_path(),
+        PackageInfo {
+            name: "from-ir".into(),
+            version: "0.1.0".try_into().unwrap(),
+        },
+        GenerationSettings::enable_all().esm_module(false).codec(
+            "radius",
+            Codec::new(
+                "(r) => BigInt(r)",
+                "(r) => Number(r)",
+                "(r) => typeof r === \"number\"",
+            ),
+        ),
+        json_ir::from_json(schema).unwrap(),
+    )
+    .unwrap();
+
+    let value = Shape::Circle {
+        center: geometry::Point { x: -1, y: 2 },
+        radius: 300,
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+
+    let script = format!(
+        "const p = require({path:?});
+        const bytes = p.serialize(\"Shape\", {{ tag: \"Circle\", value: {{ center: {{ x: -1, y: 2 }}, radius: 300 }} }});
+        console.log(JSON.stringify(Array.from(bytes)));",
+        path = dir.join("from-ir"),
+    );
+    let output = match Command::new("node").arg("-e").arg(script).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            eprintln!("node not found, skipping json ir package test");
+            return;
+        }
+        Err(e) => panic!("failed to run node: {e}"),
+    };
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap().trim(),
+        format!("{bytes:?}").replace(' ', "")
+    );
+}
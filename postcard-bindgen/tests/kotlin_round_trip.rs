@@ -0,0 +1,169 @@
+mod common;
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use common::{build_bindings, byte_list, printed_bytes, run};
+use postcard_bindgen::{
+    generate_bindings,
+    kotlin::{build_source, GenerationSettings},
+    PostcardBindings,
+};
+use serde::Serialize;
+
+fn build_kotlin_source(
+    name: &str,
+    bindings: postcard_bindgen::__private::ContainerCollection,
+) -> PathBuf {
+    build_bindings("kotlin", name, bindings, |dir, bindings| {
+        build_source(dir, "bindings", GenerationSettings::enable_all(), bindings)
+    })
+}
+
+/// Compiles and runs the `main` function together with the generated source.
+fn run_kotlin(dir: &Path, main: &str) -> String {
+    let main_path = dir.join("main.kt");
+    let jar_path = dir.join("main.jar");
+    std::fs::write(
+        main_path.as_path(),
+        format!(
+            "import bindings.*\n\n\
+             fun printBytes(bytes: ByteArray) = println(bytes.joinToString(\" \") {{ (it.toInt() and 0xFF).toString() }})\n\n\
+             {main}"
+        ),
+    )
+    .unwrap();
+
+    run(Command::new("kotlinc")
+        .arg(&main_path)
+        .arg(dir.join("bindings.kt"))
+        .args(["-include-runtime", "-d"])
+        .arg(&jar_path));
+    run(Command::new("java").arg("-jar").arg(&jar_path))
+}
+
+fn kotlin_bytes(bytes: &[u8]) -> String {
+    format!(
+        "intArrayOf({}).map {{ it.toByte() }}.toByteArray()",
+        byte_list(bytes)
+    )
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Inner {
+    id: u16,
+    name: String,
+}
+
+#[derive(Serialize, PostcardBindings)]
+enum Instruction {
+    Stop,
+    Move(i32, i32),
+    Rename { to: String },
+    Inner(Inner),
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Message {
+    seq: u64,
+    temp: f32,
+    level: i8,
+    big: u128,
+    data: Vec<u8>,
+    inner: Inner,
+    maybe: Option<u32>,
+    fixed: [u16; 3],
+    pair: (u8, String),
+    table: BTreeMap<u8, String>,
+    instructions: Vec<Instruction>,
+}
+
+#[test]
+#[ignore = "needs kotlinc and java, see tests/common"]
+fn test_decode_and_encode_round_trip() {
+    let dir = build_kotlin_source(
+        "round-trip",
+        generate_bindings!(Inner, Instruction, Message),
+    );
+    let message = Message {
+        seq: u64::MAX,
+        temp: -1.5,
+        level: -128,
+        big: u128::MAX,
+        data: vec![1, 2, 255],
+        inner: Inner {
+            id: 300,
+            name: "grüße".into(),
+        },
+        maybe: Some(70_000),
+        fixed: [1, 2, 65_535],
+        pair: (7, "seven".into()),
+        table: BTreeMap::from([(1, "one".into()), (2, "two".into())]),
+        instructions: vec![
+            Instruction::Stop,
+            Instruction::Move(-5, 70_000),
+            Instruction::Rename { to: "x".into() },
+            Instruction::Inner(Inner {
+                id: 1,
+                name: "a".into(),
+            }),
+        ],
+    };
+    let bytes = postcard::to_allocvec(&message).unwrap();
+
+    let main = format!(
+        "fun main() {{
+    val message = deserialize<Message>({})
+    println(\"${{message.inner.name}} ${{message.big}} ${{message.instructions[1]}}\")
+    printBytes(serialize(message))
+}}",
+        kotlin_bytes(&bytes)
+    );
+    let output = run_kotlin(&dir, &main);
+    let mut lines = output.lines();
+
+    assert_eq!(
+        lines.next().unwrap(),
+        format!("grüße {} Move(_0=-5, _1=70000)", u128::MAX)
+    );
+    assert_eq!(lines.next().unwrap(), printed_bytes(&bytes));
+}
+
+#[test]
+#[ignore = "needs kotlinc and java, see tests/common"]
+fn test_decode_rejects_invalid_input() {
+    let dir = build_kotlin_source("invalid", generate_bindings!(Inner, Instruction));
+    let bytes = postcard::to_allocvec(&Instruction::Inner(Inner {
+        id: 1,
+        name: "a".into(),
+    }))
+    .unwrap();
+
+    let main = format!(
+        "fun rejects(bytes: ByteArray) = try {{
+    deserialize<Instruction>(bytes)
+    false
+}} catch (e: PostcardException) {{
+    true
+}}
+
+fun main() {{
+    val input = {}
+    println(rejects(input))
+    println(rejects(input.copyOf(input.size - 1)))
+    println(rejects(input + 0.toByte()))
+    println(rejects(byteArrayOf(2, 1, 0x80.toByte())))
+    println(rejects(byteArrayOf(4)))
+}}",
+        kotlin_bytes(&bytes)
+    );
+    let output = run_kotlin(&dir, &main);
+
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        ["false", "true", "true", "true", "true"]
+    );
+}
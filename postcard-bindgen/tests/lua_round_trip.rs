@@ -0,0 +1,162 @@
+mod common;
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use common::{build_bindings, printed_bytes, run};
+use postcard_bindgen::{
+    generate_bindings,
+    lua::{build_module, GenerationSettings},
+    PostcardBindings,
+};
+use serde::Serialize;
+
+fn build_lua_module(
+    name: &str,
+    bindings: postcard_bindgen::__private::ContainerCollection,
+) -> PathBuf {
+    build_bindings("lua", name, bindings, |dir, bindings| {
+        build_module(dir, "bindings", GenerationSettings::enable_all(), bindings)
+    })
+}
+
+/// Runs `script` next to the generated module, which is loaded as `bindings`.
+fn run_lua(dir: &Path, script: &str) -> String {
+    std::fs::write(
+        dir.join("run.lua"),
+        format!(
+            "package.path = \"./?.lua;\" .. package.path\n\
+             local bindings = require(\"bindings\")\n\n\
+             local function print_bytes(bytes)\n    \
+             local out = {{}}\n    \
+             for i = 1, #bytes do\n        \
+             out[i] = string.byte(bytes, i)\n    \
+             end\n    \
+             print(table.concat(out, \" \"))\n\
+             end\n\n\
+             {script}\n"
+        ),
+    )
+    .unwrap();
+
+    run(Command::new("lua").arg("run.lua").current_dir(dir))
+}
+
+/// A lua string literal holding `bytes`.
+fn lua_bytes(bytes: &[u8]) -> String {
+    let bytes = bytes
+        .iter()
+        .map(|byte| format!("\\{byte}"))
+        .collect::<String>();
+    format!("\"{bytes}\"")
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Inner {
+    id: u16,
+    name: String,
+}
+
+#[derive(Serialize, PostcardBindings)]
+enum Instruction {
+    Stop,
+    Move(i32, i32),
+    Rename { to: String },
+    Inner(Inner),
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Message {
+    // exact on lua versions before 5.3, which store numbers as doubles
+    seq: u64,
+    temp: f32,
+    level: i8,
+    data: Vec<u8>,
+    inner: Inner,
+    maybe: Option<u32>,
+    fixed: [u16; 3],
+    pair: (u8, String),
+    // lua tables are iterated in any order, so only a single entry round trips byte for byte
+    table: BTreeMap<u8, String>,
+    instructions: Vec<Instruction>,
+}
+
+#[test]
+#[ignore = "needs lua, see tests/common"]
+fn test_decode_and_encode_round_trip() {
+    let dir = build_lua_module(
+        "round-trip",
+        generate_bindings!(Inner, Instruction, Message),
+    );
+    let message = Message {
+        seq: 1 << 40,
+        temp: -1.5,
+        level: -128,
+        data: vec![1, 2, 255],
+        inner: Inner {
+            id: 300,
+            name: "grüße".into(),
+        },
+        maybe: Some(70_000),
+        fixed: [1, 2, 65_535],
+        pair: (7, "seven".into()),
+        table: BTreeMap::from([(1, "one".into())]),
+        instructions: vec![
+            Instruction::Stop,
+            Instruction::Move(-5, 70_000),
+            Instruction::Rename { to: "x".into() },
+            Instruction::Inner(Inner {
+                id: 1,
+                name: "a".into(),
+            }),
+        ],
+    };
+    let bytes = postcard::to_allocvec(&message).unwrap();
+
+    let script = format!(
+        "local message = bindings.deserialize(\"Message\", {})
+print(message.inner.name .. \" \" .. message.seq .. \" \" .. message.instructions[2].value[2])
+print_bytes(bindings.serialize(\"Message\", message))",
+        lua_bytes(&bytes)
+    );
+    let output = run_lua(&dir, &script);
+    let mut lines = output.lines();
+
+    assert_eq!(lines.next().unwrap(), format!("grüße {} 70000", 1u64 << 40));
+    assert_eq!(lines.next().unwrap(), printed_bytes(&bytes));
+}
+
+#[test]
+#[ignore = "needs lua, see tests/common"]
+fn test_decode_rejects_invalid_input() {
+    let dir = build_lua_module("invalid", generate_bindings!(Inner, Instruction));
+    let bytes = postcard::to_allocvec(&Instruction::Inner(Inner {
+        id: 1,
+        name: "a".into(),
+    }))
+    .unwrap();
+
+    // strings are only checked for valid utf-8 since lua 5.3, so an overlong tag is used
+    let script = format!(
+        "local function rejects(bytes)
+    return not pcall(bindings.deserialize, \"Instruction\", bytes)
+end
+
+local input = {}
+print(rejects(input))
+print(rejects(string.sub(input, 1, -2)))
+print(rejects(input .. \"\\0\"))
+print(rejects(\"\\128\\128\\128\\128\\128\\1\"))
+print(rejects(\"\\4\"))",
+        lua_bytes(&bytes)
+    );
+    let output = run_lua(&dir, &script);
+
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        ["false", "true", "true", "true", "true"]
+    );
+}
@@ -0,0 +1,456 @@
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use postcard_bindgen::{
+    generate_bindings,
+    python::{build_package, GenerationSettings, PointerWidth},
+    PackageInfo, PostcardBindings,
+};
+use serde::Serialize;
+
+fn build_py_package(
+    name: &str,
+    bindings: postcard_bindgen::__private::ContainerCollection,
+) -> PathBuf {
+    build_py_package_with_settings(name, GenerationSettings::enable_all(), bindings)
+}
+
+fn build_py_package_with_settings(
+    name: &str,
+    gen_settings: GenerationSettings,
+    bindings: postcard_bindgen::__private::ContainerCollection,
+) -> PathBuf {
+    let parent_dir = std::env::temp_dir().join("postcard-bindgen-python-round-trip");
+    build_package(
+        parent_dir.as_path(),
+        PackageInfo {
+            name: name.into(),
+            version: "0.1.0".try_into().unwrap(),
+        },
+        gen_settings,
+        bindings,
+    )
+    .unwrap();
+    parent_dir.join(name)
+}
+
+/// Runs the script with python, the generated package is available as `p`.
+///
+/// Returns [`None`] if python is not installed, so the tests can be skipped.
+fn run_py(package_dir: &Path, script: &str) -> Option<String> {
+    let module_name = package_dir
+        .file_name()
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .replace('-', "_");
+    let script = format!("import {module_name} as p\n{script}");
+    let output = match Command::new("python3")
+        .arg("-c")
+        .arg(script)
+        .env("PYTHONPATH", package_dir.join("src"))
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            eprintln!("python3 not found, skipping python round trip test");
+            return None;
+        }
+        Err(e) => panic!("failed to run python3: {e}"),
+    };
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Some(String::from_utf8(output.stdout).unwrap().trim().to_owned())
+}
+
+fn py_bytes(bytes: &[u8]) -> String {
+    format!("{bytes:?}")
+}
+
+#[test]
+fn test_numbers_round_trip() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Numbers {
+        a: i8,
+        b: i16,
+        c: i32,
+        d: u64,
+        e: i64,
+        f: f32,
+    }
+
+    let package_dir = build_py_package("py-numbers", generate_bindings!(Numbers));
+
+    let values = [
+        Numbers {
+            a: i8::MIN,
+            b: i16::MIN,
+            c: i32::MIN,
+            d: u64::MAX,
+            e: i64::MIN,
+            f: 1.5,
+        },
+        Numbers {
+            a: -1,
+            b: -300,
+            c: 123_456,
+            d: 0,
+            e: -1,
+            f: -0.25,
+        },
+    ];
+
+    for value in values {
+        let expected = postcard::to_allocvec(&value).unwrap();
+        let script = format!(
+            "v = p.Numbers(a={}, b={}, c={}, d={}, e={}, f={:?})\n\
+             b = p.serialize(v)\n\
+             assert p.deserialize(p.Numbers, b) == v\n\
+             print(list(b))",
+            value.a, value.b, value.c, value.d, value.e, value.f
+        );
+        let Some(out) = run_py(&package_dir, &script) else {
+            return;
+        };
+        assert_eq!(out, py_bytes(&expected));
+    }
+}
+
+#[test]
+fn test_strings_and_arrays_round_trip() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Collections {
+        name: String,
+        values: Vec<u32>,
+        fixed: [u8; 3],
+        maybe: Option<u16>,
+    }
+
+    let package_dir = build_py_package("py-collections", generate_bindings!(Collections));
+
+    let value = Collections {
+        name: "grüße".into(),
+        values: vec![1, 300, u32::MAX],
+        fixed: [1, 2, 3],
+        maybe: Some(1000),
+    };
+    let expected = postcard::to_allocvec(&value).unwrap();
+
+    let Some(out) = run_py(
+        &package_dir,
+        "v = p.Collections(name='grüße', values=[1, 300, 4294967295], fixed=[1, 2, 3], maybe=1000)\n\
+         b = p.serialize(v)\n\
+         assert p.deserialize(p.Collections, b) == v\n\
+         print(list(b))",
+    ) else {
+        return;
+    };
+    assert_eq!(out, py_bytes(&expected));
+}
+
+#[test]
+fn test_enums_round_trip() {
+    #[derive(Serialize, PostcardBindings)]
+    enum Message {
+        Ping,
+        Value(i32),
+        Move { x: u8, y: i64 },
+    }
+
+    let package_dir = build_py_package("py-enums", generate_bindings!(Message));
+
+    let cases = [
+        (Message::Ping, "p.Message_Ping()"),
+        (Message::Value(-70_000), "p.Message_Value(-70000)"),
+        (
+            Message::Move { x: 200, y: -5 },
+            "p.Message_Move(x=200, y=-5)",
+        ),
+    ];
+
+    for (value, py_value) in cases {
+        let expected = postcard::to_allocvec(&value).unwrap();
+        let script = format!(
+            "v = {py_value}\n\
+             b = p.serialize(v)\n\
+             assert type(p.deserialize(p.Message, b)) is type(v)\n\
+             assert p.serialize(p.deserialize(p.Message, b)) == b\n\
+             print(list(b))"
+        );
+        let Some(out) = run_py(&package_dir, &script) else {
+            return;
+        };
+        assert_eq!(out, py_bytes(&expected));
+    }
+}
+
+#[test]
+fn test_nested_and_tuple_structs_round_trip() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Point(i16, i16);
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Marker;
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Path {
+        points: Vec<Point>,
+        closed: bool,
+        marker: Marker,
+    }
+
+    let package_dir = build_py_package("py-nested", generate_bindings!(Point, Marker, Path));
+
+    let value = Path {
+        points: vec![Point(1, -2), Point(-300, 400)],
+        closed: true,
+        marker: Marker,
+    };
+    let expected = postcard::to_allocvec(&value).unwrap();
+
+    let Some(out) = run_py(
+        &package_dir,
+        "v = p.Path(points=[p.Point(1, -2), p.Point(-300, 400)], closed=True, marker=p.Marker())\n\
+         b = p.serialize(v)\n\
+         assert p.deserialize(p.Path, b) == v\n\
+         print(list(b))",
+    ) else {
+        return;
+    };
+    assert_eq!(out, py_bytes(&expected));
+}
+
+#[test]
+fn test_recursive_types_round_trip() {
+    #[derive(Serialize, PostcardBindings)]
+    enum Expr {
+        Lit(u8),
+        Neg(Box<Expr>),
+        Block(Vec<Stmt>),
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Stmt {
+        expr: Box<Expr>,
+        next: Option<Box<Stmt>>,
+    }
+
+    let package_dir = build_py_package("py-recursive", generate_bindings!(Expr, Stmt));
+
+    let value = Expr::Block(vec![Stmt {
+        expr: Box::new(Expr::Neg(Box::new(Expr::Lit(1)))),
+        next: Some(Box::new(Stmt {
+            expr: Box::new(Expr::Lit(2)),
+            next: None,
+        })),
+    }]);
+    let expected = postcard::to_allocvec(&value).unwrap();
+
+    let script = "v = p.Expr_Block([p.Stmt(expr=p.Expr_Neg(p.Expr_Lit(1)), next=p.Stmt(expr=p.Expr_Lit(2), next=None))])\n\
+                  b = p.serialize(v)\n\
+                  assert p.serialize(p.deserialize(p.Expr, b)) == b\n\
+                  print(list(b))";
+    let Some(out) = run_py(&package_dir, script) else {
+        return;
+    };
+    assert_eq!(out, py_bytes(&expected));
+}
+
+#[test]
+fn test_field_names_without_identifiers_round_trip() {
+    #[derive(Serialize, PostcardBindings)]
+    #[serde(rename_all = "kebab-case")]
+    struct Config {
+        sensor_id: u8,
+        sample_rate: u16,
+    }
+
+    let package_dir = build_py_package("py-field-names", generate_bindings!(Config));
+
+    let value = Config {
+        sensor_id: 3,
+        sample_rate: 100,
+    };
+    let expected = postcard::to_allocvec(&value).unwrap();
+
+    let script = "b = p.serialize(p.Config(sensor_id=3, sample_rate=100))\n\
+                  assert p.deserialize(p.Config, b).sample_rate == 100\n\
+                  print(list(b))";
+    let Some(out) = run_py(&package_dir, script) else {
+        return;
+    };
+    assert_eq!(out, py_bytes(&expected));
+}
+
+#[test]
+fn test_generic_instantiations_round_trip() {
+    #[derive(Serialize, PostcardBindings)]
+    enum Status {
+        Ok,
+        Err(u8),
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Msg<T> {
+        payload: T,
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    enum Reply<T> {
+        Single(T),
+        Many(Vec<T>),
+    }
+
+    let package_dir = build_py_package(
+        "py-generics",
+        generate_bindings!(Status, Msg<u8>, Msg<Status>, Reply<Msg<Status>>),
+    );
+
+    let many = Reply::Many(vec![
+        Msg {
+            payload: Status::Err(3),
+        },
+        Msg {
+            payload: Status::Ok,
+        },
+    ]);
+    let single = Reply::Single(Msg {
+        payload: Status::Ok,
+    });
+
+    let script = "v = p.Reply_Msg_Status_Many([p.Msg_Status(payload=p.Status_Err(3)), p.Msg_Status(payload=p.Status_Ok())])\n\
+                  b = p.serialize(v)\n\
+                  assert type(p.deserialize(p.Reply_Msg_Status, b)) is type(v)\n\
+                  assert list(p.serialize(p.Msg_u8(payload=200))) == [200]\n\
+                  print(list(b))\n\
+                  print(list(p.serialize(p.Reply_Msg_Status_Single(p.Msg_Status(payload=p.Status_Ok())))))";
+    let Some(out) = run_py(&package_dir, script) else {
+        return;
+    };
+    assert_eq!(
+        out.lines().collect::<Vec<_>>(),
+        [
+            py_bytes(&postcard::to_allocvec(&many).unwrap()),
+            py_bytes(&postcard::to_allocvec(&single).unwrap()),
+        ]
+    );
+}
+
+#[test]
+fn test_ranges_round_trip() {
+    use core::ops::{Range, RangeInclusive};
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Window {
+        span: Range<u32>,
+        limits: RangeInclusive<i16>,
+    }
+
+    let package_dir = build_py_package("py-ranges", generate_bindings!(Window));
+
+    let expected = postcard::to_allocvec(&Window {
+        span: 3..300,
+        limits: -5..=5,
+    })
+    .unwrap();
+    let script = "v = p.Window(span=range(3, 300), limits=range(-5, 6))\n\
+         b = p.serialize(v)\n\
+         assert p.deserialize(p.Window, b) == v\n\
+         print(list(b))";
+    let Some(out) = run_py(&package_dir, script) else {
+        return;
+    };
+    assert_eq!(out, py_bytes(&expected));
+}
+
+#[test]
+fn test_pointer_width() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Sizes {
+        len: usize,
+        offset: isize,
+    }
+
+    let package_dir = build_py_package_with_settings(
+        "py-pointer-width",
+        GenerationSettings::enable_all().pointer_width(PointerWidth::Bits32),
+        generate_bindings!(Sizes),
+    );
+
+    let value = Sizes {
+        len: 4_000_000_000,
+        offset: -5,
+    };
+    let expected = postcard::to_allocvec(&value).unwrap();
+    let script = "v = p.Sizes(len=4000000000, offset=-5)\n\
+         b = p.serialize(v)\n\
+         assert p.deserialize(p.Sizes, b) == v\n\
+         print(list(b))\n\
+         for v in [p.Sizes(len=2**32, offset=0), p.Sizes(len=0, offset=-2**31 - 1)]:\n    \
+             try:\n        \
+                 p.serialize(v)\n        \
+                 print(\"accepted\")\n    \
+             except AssertionError:\n        \
+                 print(\"rejected\")\n\
+         try:\n    \
+             p.deserialize(p.Sizes, bytes([128, 128, 128, 128, 16, 0]))\n\
+         except Exception:\n    \
+             print(\"rejected\")";
+    let Some(out) = run_py(&package_dir, script) else {
+        return;
+    };
+    assert_eq!(
+        out.lines().collect::<Vec<_>>(),
+        [
+            py_bytes(&expected).as_str(),
+            "rejected",
+            "rejected",
+            "rejected"
+        ]
+    );
+}
+
+#[test]
+fn test_unit_values_round_trip() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Ack {
+        id: u8,
+        done: (),
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    enum Response {
+        Empty((), u8),
+        Acks(Vec<Ack>),
+    }
+
+    let package_dir = build_py_package("py-unit", generate_bindings!(Ack, Response));
+
+    let cases = [
+        (Response::Empty((), 7), "p.Response_Empty(None, 7)"),
+        (
+            Response::Acks(vec![Ack { id: 1, done: () }]),
+            "p.Response_Acks([p.Ack(id=1, done=None)])",
+        ),
+    ];
+
+    for (value, py_value) in cases {
+        let expected = postcard::to_allocvec(&value).unwrap();
+        let script = format!(
+            "v = {py_value}\n\
+             b = p.serialize(v)\n\
+             assert p.deserialize(p.Response, b) == v\n\
+             print(list(b))"
+        );
+        let Some(out) = run_py(&package_dir, &script) else {
+            return;
+        };
+        assert_eq!(out, py_bytes(&expected));
+    }
+}
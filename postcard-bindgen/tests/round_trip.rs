@@ -0,0 +1,3103 @@
+use std::{
+    borrow::Cow,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use postcard_bindgen::{
+    generate_bindings,
+    javascript::{
+        build_dual_package, build_package, AmbiguousTypes, Codec, GenerationSettings,
+        IdentifierCase, OptionalNone, PointerWidth,
+    },
+    PackageInfo, PostcardBindings,
+};
+use serde::{Deserialize, Serialize};
+
+fn build_js_package(
+    name: &str,
+    bindings: postcard_bindgen::__private::ContainerCollection,
+) -> PathBuf {
+    build_js_package_with_settings(name, GenerationSettings::enable_all(), bindings)
+}
+
+fn build_js_package_with_settings(
+    name: &str,
+    gen_settings: GenerationSettings,
+    bindings: postcard_bindgen::__private::ContainerCollection,
+) -> PathBuf {
+    let parent_dir = std::env::temp_dir().join("postcard-bindgen-round-trip");
+    build_package(
+        parent_dir.as_path(),
+        PackageInfo {
+            name: name.into(),
+            version: "0.1.0".try_into().unwrap(),
+        },
+        gen_settings.esm_module(false),
+        bindings,
+    )
+    .unwrap();
+    parent_dir.join(name)
+}
+
+/// Runs the script with node, the generated package is available as `p`.
+///
+/// Returns [`None`] if node is not installed, so the tests can be skipped.
+fn run_js(package_dir: &Path, script: &str) -> Option<String> {
+    let script = format!(
+        "const p = require({:?});\n{}",
+        package_dir.join("index.js"),
+        script
+    );
+    let output = match Command::new("node").arg("-e").arg(script).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            eprintln!("node not found, skipping js round trip test");
+            return None;
+        }
+        Err(e) => panic!("failed to run node: {e}"),
+    };
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Some(String::from_utf8(output.stdout).unwrap().trim().to_owned())
+}
+
+fn js_bytes(bytes: &[u8]) -> String {
+    format!("{bytes:?}")
+}
+
+#[test]
+fn test_signed_numbers_round_trip() {
+    #[derive(Serialize, PostcardBindings)]
+    struct SignedNumbers {
+        a: i8,
+        b: i16,
+        c: i32,
+    }
+
+    let package_dir = build_js_package("signed-numbers", generate_bindings!(SignedNumbers));
+
+    let values = [
+        SignedNumbers {
+            a: i8::MIN,
+            b: i16::MIN,
+            c: i32::MIN,
+        },
+        SignedNumbers {
+            a: -1,
+            b: -1,
+            c: -1,
+        },
+        SignedNumbers { a: 0, b: 0, c: 0 },
+        SignedNumbers {
+            a: i8::MAX,
+            b: i16::MAX,
+            c: i32::MAX,
+        },
+    ];
+
+    for value in values {
+        let bytes = postcard::to_allocvec(&value).unwrap();
+        let object = format!("{{ a: {}, b: {}, c: {} }}", value.a, value.b, value.c);
+
+        let script = format!(
+            "const bytes = p.serialize(\"SignedNumbers\", {object});
+            console.log(JSON.stringify(Array.from(bytes)));
+            console.log(JSON.stringify(p.deserialize(\"SignedNumbers\", {})));",
+            js_bytes(&bytes)
+        );
+        let Some(output) = run_js(&package_dir, &script) else {
+            return;
+        };
+        let mut lines = output.lines();
+
+        assert_eq!(lines.next().unwrap(), js_bytes(&bytes).replace(' ', ""));
+        assert_eq!(
+            lines.next().unwrap(),
+            format!("{{\"a\":{},\"b\":{},\"c\":{}}}", value.a, value.b, value.c)
+        );
+    }
+}
+
+#[test]
+fn test_negative_one_encodes_as_single_byte() {
+    assert_eq!(postcard::to_allocvec(&-1i32).unwrap(), [0x01]);
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Value(i32);
+
+    let package_dir = build_js_package("negative-one", generate_bindings!(Value));
+
+    let Some(output) = run_js(
+        &package_dir,
+        "console.log(JSON.stringify(Array.from(p.serialize(\"Value\", [-1]))))",
+    ) else {
+        return;
+    };
+    assert_eq!(output, "[1]");
+}
+
+#[test]
+fn test_number_arrays_round_trip() {
+    #[derive(Serialize, PostcardBindings)]
+    struct NumberArrays {
+        a: Vec<u8>,
+        b: Vec<i16>,
+        c: Vec<u32>,
+        d: [i32; 3],
+        e: Vec<u64>,
+        f: Vec<f32>,
+        g: [f64; 2],
+    }
+
+    let package_dir = build_js_package("number-arrays", generate_bindings!(NumberArrays));
+
+    let value = NumberArrays {
+        a: vec![0, 1, 255],
+        b: vec![i16::MIN, -1, 0, i16::MAX],
+        c: vec![0, 127, 128, u32::MAX],
+        d: [i32::MIN, -1, i32::MAX],
+        e: vec![0, 1 << 40],
+        f: vec![1.5, -0.25],
+        g: [0.1, -1e300],
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+
+    let script = format!(
+        "const value = {{ a: {:?}, b: {:?}, c: {:?}, d: {:?}, e: {:?}, f: {:?}, g: {:?} }};
+        console.log(JSON.stringify(Array.from(p.serialize(\"NumberArrays\", value))));
+        const des = p.deserialize(\"NumberArrays\", {});
+        const plain = Object.fromEntries(Object.entries(des).map(([k, v]) => [k, Array.from(v)]));
+        console.log(JSON.stringify(plain) === JSON.stringify(value));",
+        value.a,
+        value.b,
+        value.c,
+        value.d,
+        value.e,
+        value.f,
+        value.g,
+        js_bytes(&bytes)
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    let mut lines = output.lines();
+
+    assert_eq!(lines.next().unwrap(), js_bytes(&bytes).replace(' ', ""));
+    assert_eq!(lines.next().unwrap(), "true");
+}
+
+#[test]
+fn test_fixed_number_arrays_as_typed_arrays() {
+    #[derive(Serialize, PostcardBindings)]
+    struct TypedArrays {
+        a: [f64; 3],
+        b: [u16; 2],
+        c: [u64; 2],
+    }
+
+    let package_dir = build_js_package("typed-arrays", generate_bindings!(TypedArrays));
+
+    let value = TypedArrays {
+        a: [0.5, -2.0, 1e10],
+        b: [0, u16::MAX],
+        c: [0, 1],
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+
+    let script = format!(
+        "const des = p.deserialize(\"TypedArrays\", {});
+        console.log(des.a instanceof Float64Array, des.b instanceof Uint16Array, Array.isArray(des.c));
+        console.log(JSON.stringify(Array.from(p.serialize(\"TypedArrays\", des))));",
+        js_bytes(&bytes)
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    let mut lines = output.lines();
+
+    assert_eq!(lines.next().unwrap(), "true true true");
+    assert_eq!(lines.next().unwrap(), js_bytes(&bytes).replace(' ', ""));
+}
+
+#[test]
+fn test_enum_skipped_variant_round_trip() {
+    #[allow(dead_code)]
+    #[derive(Serialize, PostcardBindings)]
+    enum Skipped {
+        A,
+        #[serde(skip)]
+        B,
+        C(u8),
+    }
+
+    let package_dir = build_js_package("skipped-variant", generate_bindings!(Skipped));
+
+    let bytes = postcard::to_allocvec(&Skipped::C(5)).unwrap();
+    assert_eq!(bytes, [2, 5]);
+
+    let script = format!(
+        "console.log(JSON.stringify(Array.from(p.serialize(\"Skipped\", {{ tag: \"C\", value: 5 }}))));
+        console.log(JSON.stringify(p.deserialize(\"Skipped\", {})));",
+        js_bytes(&bytes)
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    let mut lines = output.lines();
+
+    assert_eq!(lines.next().unwrap(), js_bytes(&bytes).replace(' ', ""));
+    assert_eq!(lines.next().unwrap(), "{\"tag\":\"C\",\"value\":5}");
+}
+
+#[test]
+fn test_skipped_fields_round_trip() {
+    #[allow(dead_code)]
+    #[derive(Serialize, PostcardBindings)]
+    struct Reading {
+        #[serde(skip)]
+        cached: u32,
+        value: u8,
+        #[serde(skip_serializing)]
+        raw: Vec<u8>,
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    enum Event {
+        Measured {
+            reading: Reading,
+            #[serde(skip)]
+            received_at: u64,
+        },
+        Moved(i8, #[serde(skip)] u8, i8),
+    }
+
+    let package_dir = build_js_package("skipped-fields", generate_bindings!(Reading, Event));
+
+    let measured = Event::Measured {
+        reading: Reading {
+            cached: 1,
+            value: 7,
+            raw: vec![1, 2],
+        },
+        received_at: 2,
+    };
+    let moved = Event::Moved(-1, 9, 2);
+    let (measured, moved) = (
+        postcard::to_allocvec(&measured).unwrap(),
+        postcard::to_allocvec(&moved).unwrap(),
+    );
+    assert_eq!(measured, [0, 7]);
+
+    let script = format!(
+        "console.log(JSON.stringify(p.deserialize(\"Event\", {})));
+        console.log(JSON.stringify(p.deserialize(\"Event\", {})));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Event\", {{ tag: \"Measured\", value: {{ reading: {{ value: 7 }} }} }}))));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Event\", {{ tag: \"Moved\", value: [-1, 2] }}))));",
+        js_bytes(&measured),
+        js_bytes(&moved)
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            "{\"tag\":\"Measured\",\"value\":{\"reading\":{\"value\":7}}}".into(),
+            "{\"tag\":\"Moved\",\"value\":[-1,2]}".into(),
+            js_bytes(&measured).replace(' ', ""),
+            js_bytes(&moved).replace(' ', ""),
+        ]
+    );
+}
+
+#[test]
+fn test_skip_deserializing_field_round_trip() {
+    // serde still serializes the field, it's only left out when reading
+    #[derive(Serialize, PostcardBindings)]
+    struct Sample {
+        #[serde(skip_deserializing)]
+        version: u8,
+        value: u16,
+    }
+
+    let package_dir = build_js_package("skip-deserializing", generate_bindings!(Sample));
+
+    let bytes = postcard::to_allocvec(&Sample {
+        version: 3,
+        value: 300,
+    })
+    .unwrap();
+    assert_eq!(bytes, [3, 172, 2]);
+
+    let script = format!(
+        "const sample = p.deserialize(\"Sample\", {});
+        console.log(JSON.stringify(sample));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Sample\", sample))));",
+        js_bytes(&bytes)
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            "{\"version\":3,\"value\":300}".into(),
+            js_bytes(&bytes).replace(' ', ""),
+        ]
+    );
+}
+
+#[test]
+fn test_serde_default_fields() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Settings {
+        id: u8,
+        #[serde(default)]
+        retries: u8,
+        #[serde(default)]
+        label: Option<String>,
+        #[serde(default = "default_levels")]
+        #[postcard_bindgen(default = "[1, 2]")]
+        levels: Vec<u16>,
+    }
+
+    // only `Deserialize` would call them
+    #[allow(dead_code)]
+    fn default_levels() -> Vec<u16> {
+        vec![1, 2]
+    }
+    #[allow(dead_code)]
+    fn default_scale() -> u8 {
+        10
+    }
+
+    #[derive(Default, Serialize, PostcardBindings)]
+    #[serde(default)]
+    struct Limits {
+        min: i16,
+        names: Vec<String>,
+    }
+
+    // defaults which are unknown to the bindings, so the fields are always decoded
+    #[derive(Default, Serialize, PostcardBindings)]
+    #[serde(default)]
+    struct Window {
+        limits: Limits,
+        mask: [bool; 2],
+        #[serde(default = "default_scale")]
+        scale: u8,
+    }
+
+    let package_dir = build_js_package(
+        "serde-default",
+        generate_bindings!(Settings, Limits, Window),
+    );
+
+    // an older version of the type which ends before the fields with defaults
+    let old = postcard::to_allocvec(&(3u8,)).unwrap();
+    let new = postcard::to_allocvec(&Settings {
+        id: 3,
+        retries: 5,
+        label: Some("a".into()),
+        levels: vec![4],
+    })
+    .unwrap();
+
+    let window = postcard::to_allocvec(&Window {
+        limits: Limits {
+            min: -1,
+            names: vec![],
+        },
+        mask: [true, false],
+        scale: 3,
+    })
+    .unwrap();
+
+    let script = format!(
+        "console.log(JSON.stringify(p.deserialize(\"Settings\", {})));
+        console.log(JSON.stringify(p.deserialize(\"Settings\", {})));
+        console.log(JSON.stringify(p.deserialize(\"Limits\", [])));
+        console.log(JSON.stringify(p.deserialize(\"Window\", {})));
+        try {{ p.deserialize(\"Window\", {}); console.log(\"accepted\") }} catch {{ console.log(\"rejected\") }}",
+        js_bytes(&old),
+        js_bytes(&new),
+        js_bytes(&window),
+        js_bytes(&window[..window.len() - 1]),
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            "{\"id\":3,\"retries\":0,\"levels\":[1,2]}",
+            "{\"id\":3,\"retries\":5,\"label\":\"a\",\"levels\":[4]}",
+            "{\"min\":0,\"names\":[]}",
+            "{\"limits\":{\"min\":-1,\"names\":[]},\"mask\":[true,false],\"scale\":3}",
+            "rejected",
+        ]
+    );
+}
+
+#[test]
+fn test_nested_struct_instead_of_flatten() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Header {
+        version: u8,
+        flags: u16,
+    }
+
+    // `#[serde(flatten)]` can't be encoded by postcard, a nested struct has the bytes of
+    // inlined fields
+    #[derive(Serialize, PostcardBindings)]
+    struct Packet {
+        header: Header,
+        len: u32,
+    }
+
+    let package_dir = build_js_package("nested-header", generate_bindings!(Header, Packet));
+
+    let packet = Packet {
+        header: Header {
+            version: 1,
+            flags: 300,
+        },
+        len: 7,
+    };
+    let bytes = postcard::to_allocvec(&packet).unwrap();
+    assert_eq!(bytes, postcard::to_allocvec(&(1u8, 300u16, 7u32)).unwrap());
+
+    let script = format!(
+        "console.log(JSON.stringify(p.deserialize(\"Packet\", {})));",
+        js_bytes(&bytes)
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.trim(),
+        "{\"header\":{\"version\":1,\"flags\":300},\"len\":7}"
+    );
+}
+
+#[test]
+fn test_internally_tagged_enum() {
+    #[derive(Serialize, Deserialize, PostcardBindings)]
+    struct Name {
+        name: String,
+    }
+
+    #[derive(Serialize, Deserialize, PostcardBindings)]
+    #[serde(tag = "type")]
+    enum Event {
+        Started,
+        Moved { x: i16, y: i16 },
+        Renamed(Name),
+    }
+
+    let package_dir = build_js_package("internally-tagged", generate_bindings!(Name, Event));
+
+    let events = [
+        Event::Started,
+        Event::Moved { x: -3, y: 400 },
+        Event::Renamed(Name {
+            name: "probe".into(),
+        }),
+    ];
+    // the variant is encoded by its name instead of its index
+    let bytes = events
+        .iter()
+        .map(|event| postcard::to_allocvec(event).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(bytes[0], postcard::to_allocvec("Started").unwrap());
+    // serde deserializes the enum with `deserialize_any`, which postcard doesn't implement, so
+    // only the bindings decode the bytes
+    assert_eq!(
+        postcard::from_bytes::<Event>(&bytes[1]).err(),
+        Some(postcard::Error::WontImplement)
+    );
+
+    let script = format!(
+        "for (const bytes of {bytes:?}) {{
+            const event = p.deserialize(\"Event\", bytes);
+            console.log(JSON.stringify(event));
+            console.log(JSON.stringify(Array.from(p.serialize(\"Event\", event))));
+        }}
+        const check = (value) => {{ try {{ p.serialize(\"Event\", value); return true }} catch {{ return false }} }};
+        console.log(JSON.stringify([
+            check({{ type: \"Moved\", x: 1, y: 2 }}),
+            check({{ type: \"Moved\", x: 1 }}),
+            check({{ type: \"Renamed\", name: 1 }}),
+            check({{ type: \"Stopped\" }}),
+            check({{ tag: \"Started\" }}),
+        ]));"
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    let json = [
+        "{\"type\":\"Started\"}",
+        "{\"type\":\"Moved\",\"x\":-3,\"y\":400}",
+        "{\"type\":\"Renamed\",\"name\":\"probe\"}",
+    ];
+    let mut expected = json
+        .iter()
+        .zip(&bytes)
+        .flat_map(|(json, bytes)| [json.to_string(), js_bytes(bytes).replace(' ', "")])
+        .collect::<Vec<_>>();
+    expected.push("[true,false,false,false,false]".into());
+    assert_eq!(output.lines().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn test_enum_payload_type_checks() {
+    #[allow(dead_code)]
+    #[derive(Serialize, PostcardBindings)]
+    enum Payload {
+        A,
+        B(u8, u16),
+        C { a: u8 },
+    }
+
+    let package_dir = build_js_package("enum-payload", generate_bindings!(Payload));
+
+    let script = "const check = (value) => { try { p.serialize(\"Payload\", value); return true } catch { return false } };
+        console.log(JSON.stringify([
+            check({ tag: \"A\" }),
+            check({ tag: \"A\", value: 1 }),
+            check({ tag: \"B\", value: [1, 2] }),
+            check({ tag: \"B\", value: [1] }),
+            check({ tag: \"B\", value: [1, \"2\"] }),
+            check({ tag: \"C\", value: { a: 1 } }),
+            check({ tag: \"C\", value: { b: 1 } }),
+            check({ tag: \"C\" }),
+            check({ tag: \"D\" }),
+        ]));";
+    let Some(output) = run_js(&package_dir, script) else {
+        return;
+    };
+
+    assert_eq!(
+        output,
+        "[true,false,true,false,false,true,false,false,false]"
+    );
+}
+
+#[test]
+fn test_serialize_any() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Point {
+        x: u8,
+        y: u8,
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Name {
+        name: String,
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Coordinate {
+        x: u8,
+        y: u8,
+    }
+
+    let package_dir = build_js_package("serialize-any", generate_bindings!(Point, Name));
+    let ambiguous_package_dir = build_js_package(
+        "serialize-any-ambiguous",
+        generate_bindings!(Point, Name, Coordinate),
+    );
+
+    let bytes = postcard::to_allocvec(&Name { name: "a".into() }).unwrap();
+
+    let script = "const tryAny = (value) => { try { return JSON.stringify(Array.from(p.serializeAny(value))) } catch (e) { return e } };
+        console.log(tryAny({ name: \"a\" }));
+        console.log(tryAny({ x: 1, y: 2 }));
+        console.log(tryAny({ z: 1 }));";
+    let Some(output) = run_js(&package_dir, script) else {
+        return;
+    };
+    let mut lines = output.lines();
+
+    assert_eq!(lines.next().unwrap(), js_bytes(&bytes).replace(' ', ""));
+    assert_eq!(lines.next().unwrap(), "[1,2]");
+    assert_eq!(lines.next().unwrap(), "value matches no type");
+
+    let Some(output) = run_js(&ambiguous_package_dir, script) else {
+        return;
+    };
+    let mut lines = output.lines();
+
+    assert_eq!(lines.next().unwrap(), js_bytes(&bytes).replace(' ', ""));
+    assert_eq!(
+        lines.next().unwrap(),
+        "value is ambiguous, matches types Point, Coordinate"
+    );
+}
+
+#[test]
+fn test_nested_enum_round_trip() {
+    #[allow(dead_code)]
+    #[derive(Serialize, PostcardBindings)]
+    enum Inner {
+        A,
+        B(u8),
+        C { c: u16 },
+    }
+
+    #[allow(dead_code)]
+    #[derive(Serialize, PostcardBindings)]
+    enum Outer {
+        Empty,
+        Wrap(Inner),
+        Pair(Inner, Inner),
+    }
+
+    let package_dir = build_js_package("nested-enum", generate_bindings!(Inner, Outer));
+
+    let values = [
+        (
+            Outer::Wrap(Inner::A),
+            "{\"tag\":\"Wrap\",\"value\":{\"tag\":\"A\"}}",
+        ),
+        (
+            Outer::Wrap(Inner::B(7)),
+            "{\"tag\":\"Wrap\",\"value\":{\"tag\":\"B\",\"value\":7}}",
+        ),
+        (
+            Outer::Pair(Inner::C { c: 300 }, Inner::B(1)),
+            "{\"tag\":\"Pair\",\"value\":[{\"tag\":\"C\",\"value\":{\"c\":300}},{\"tag\":\"B\",\"value\":1}]}",
+        ),
+    ];
+
+    for (value, json) in values {
+        let bytes = postcard::to_allocvec(&value).unwrap();
+
+        let script = format!(
+            "console.log(JSON.stringify(Array.from(p.serialize(\"Outer\", {json}))));
+            console.log(JSON.stringify(p.deserialize(\"Outer\", {})));",
+            js_bytes(&bytes)
+        );
+        let Some(output) = run_js(&package_dir, &script) else {
+            return;
+        };
+        let mut lines = output.lines();
+
+        assert_eq!(lines.next().unwrap(), js_bytes(&bytes).replace(' ', ""));
+        assert_eq!(lines.next().unwrap(), json);
+    }
+}
+
+#[test]
+fn test_strings_are_utf8() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Text {
+        text: String,
+    }
+
+    let package_dir = build_js_package("utf8-strings", generate_bindings!(Text));
+
+    let value = Text {
+        text: "h\u{e9}llo \u{1f600}".into(),
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+
+    let script = format!(
+        "console.log(JSON.stringify(Array.from(p.serialize(\"Text\", {{ text: {:?} }}))));
+        console.log(p.deserialize(\"Text\", {}).text === {:?});
+        try {{ p.serialize(\"Text\", {{ text: \"a\\uD800b\" }}); console.log(\"accepted\") }} catch (e) {{ console.log(e) }}",
+        value.text,
+        js_bytes(&bytes),
+        value.text
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    let mut lines = output.lines();
+
+    assert_eq!(lines.next().unwrap(), js_bytes(&bytes).replace(' ', ""));
+    assert_eq!(lines.next().unwrap(), "true");
+    assert_eq!(lines.next().unwrap(), "value has wrong format");
+}
+
+#[test]
+fn test_optional_none_representation() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Optional {
+        a: Option<u8>,
+    }
+
+    let script = "const check = (value) => { try { return JSON.stringify(Array.from(p.serialize(\"Optional\", value))) } catch (e) { return e } };
+        console.log(check({ a: undefined }), check({ a: null }), check({ a: 5 }));
+        console.log(p.deserialize(\"Optional\", [0]).a);";
+
+    let cases = [
+        (
+            OptionalNone::Undefined,
+            "[0] value has wrong format [1,5]",
+            "undefined",
+        ),
+        (
+            OptionalNone::Null,
+            "value has wrong format [0] [1,5]",
+            "null",
+        ),
+        (OptionalNone::Either, "[0] [0] [1,5]", "undefined"),
+    ];
+
+    for (optional_none, serialized, deserialized) in cases {
+        let package_dir = build_js_package_with_settings(
+            &format!("optional-none-{optional_none:?}").to_lowercase(),
+            GenerationSettings::enable_all().optional_none(optional_none),
+            generate_bindings!(Optional),
+        );
+        let Some(output) = run_js(&package_dir, script) else {
+            return;
+        };
+        let mut lines = output.lines();
+
+        assert_eq!(lines.next().unwrap(), serialized);
+        assert_eq!(lines.next().unwrap(), deserialized);
+    }
+}
+
+#[test]
+fn test_decoded_optionals_keep_their_keys() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Reading {
+        a: Option<u8>,
+        b: u16,
+        c: Option<String>,
+    }
+
+    let package_dir = build_js_package_with_settings(
+        "optional-keys",
+        GenerationSettings::enable_all().optional_none(OptionalNone::Null),
+        generate_bindings!(Reading),
+    );
+
+    let some = postcard::to_allocvec(&Reading {
+        a: Some(1),
+        b: 2,
+        c: Some("c".into()),
+    })
+    .unwrap();
+    let none = postcard::to_allocvec(&Reading {
+        a: None,
+        b: 2,
+        c: None,
+    })
+    .unwrap();
+    let script = format!(
+        "const some = p.deserialize(\"Reading\", {}), none = p.deserialize(\"Reading\", {});
+        console.log(Object.keys(some).join(), Object.keys(none).join());
+        console.log(JSON.stringify(none));",
+        js_bytes(&some),
+        js_bytes(&none)
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    let mut lines = output.lines();
+
+    // the keys of absent optionals are set, so the shape of the objects doesn't differ
+    assert_eq!(lines.next().unwrap(), "a,b,c a,b,c");
+    assert_eq!(lines.next().unwrap(), "{\"a\":null,\"b\":2,\"c\":null}");
+}
+
+#[test]
+fn test_self_test_passes() {
+    #[allow(dead_code)]
+    #[derive(Serialize, PostcardBindings)]
+    enum Kind {
+        A(u8, String),
+        B,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Serialize, PostcardBindings)]
+    struct Sample {
+        a: u64,
+        b: Option<i8>,
+        c: [f32; 4],
+        d: Vec<Kind>,
+        e: std::collections::HashMap<String, bool>,
+        f: std::collections::HashMap<u8, u8>,
+        g: (u16, Kind),
+        h: core::ops::Range<u32>,
+    }
+
+    let package_dir = build_js_package_with_settings(
+        "self-test",
+        GenerationSettings::enable_all().self_test(true),
+        generate_bindings!(Kind, Sample),
+    );
+
+    let Some(output) = run_js(&package_dir, "p.__selfTest(); console.log(\"ok\")") else {
+        return;
+    };
+    assert_eq!(output, "ok");
+}
+
+#[test]
+fn test_ranges() {
+    use core::ops::{Range, RangeInclusive};
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Window {
+        span: Range<u32>,
+        limits: RangeInclusive<i16>,
+    }
+
+    let package_dir = build_js_package("ranges", generate_bindings!(Window));
+
+    let value = Window {
+        span: 3..300,
+        limits: -5..=5,
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+    let script = format!(
+        "const check = (value) => {{ try {{ p.serialize(\"Window\", value); return true }} catch {{ return false }} }};
+        const window = p.deserialize(\"Window\", {bytes});
+        console.log(JSON.stringify(window));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Window\", window))));
+        console.log(JSON.stringify([
+            check({{ ...window, span: {{ start: \"3\", end: 300 }} }}),
+            check({{ ...window, limits: {{ start: -5, end: 40000 }} }}),
+            check({{ ...window, span: null }}),
+        ]));",
+        bytes = js_bytes(&bytes),
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            "{\"span\":{\"start\":3,\"end\":300},\"limits\":{\"start\":-5,\"end\":5}}".into(),
+            js_bytes(&bytes).replace(' ', ""),
+            "[false,false,false]".into(),
+        ]
+    );
+}
+
+#[test]
+fn test_smart_pointers_are_transparent() {
+    use std::{rc::Rc, sync::Arc};
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Inner {
+        a: u8,
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Pointers {
+        a: Box<Inner>,
+        b: Rc<String>,
+        c: Arc<[u16]>,
+        d: Box<str>,
+        e: Option<Box<u32>>,
+    }
+
+    let package_dir = build_js_package("smart-pointers", generate_bindings!(Inner, Pointers));
+
+    let value = Pointers {
+        a: Box::new(Inner { a: 1 }),
+        b: Rc::new("b".into()),
+        c: Arc::from([2, 300]),
+        d: "d".into(),
+        e: Some(Box::new(4)),
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+    let json = "{\"a\":{\"a\":1},\"b\":\"b\",\"c\":[2,300],\"d\":\"d\",\"e\":4}";
+
+    let script = format!(
+        "console.log(JSON.stringify(Array.from(p.serialize(\"Pointers\", {json}))));
+        console.log(JSON.stringify(p.deserialize(\"Pointers\", {})));",
+        js_bytes(&bytes)
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    let mut lines = output.lines();
+
+    assert_eq!(lines.next().unwrap(), js_bytes(&bytes).replace(' ', ""));
+    assert_eq!(lines.next().unwrap(), json);
+}
+
+#[test]
+fn test_top_level_tuple_round_trip() {
+    let package_dir = build_js_package(
+        "top-level-tuple",
+        generate_bindings!("Args" => (u8, String, Option<u16>)),
+    );
+
+    let bytes = postcard::to_allocvec(&(7u8, "seven", Some(700u16))).unwrap();
+
+    let script = format!(
+        "const check = (value) => {{ try {{ p.serialize(\"Args\", value); return true }} catch {{ return false }} }};
+        console.log(JSON.stringify(Array.from(p.serialize(\"Args\", [7, \"seven\", 700]))));
+        console.log(JSON.stringify(p.deserialize(\"Args\", {})));
+        console.log(check([7, \"seven\"]), check([7, 7, 7]), check({{ 0: 7, 1: \"seven\", 2: 700 }}));",
+        js_bytes(&bytes)
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    let mut lines = output.lines();
+
+    assert_eq!(lines.next().unwrap(), js_bytes(&bytes).replace(' ', ""));
+    assert_eq!(lines.next().unwrap(), "[7,\"seven\",700]");
+    assert_eq!(lines.next().unwrap(), "false false false");
+}
+
+#[test]
+fn test_deserialize_rejects_trailing_bytes() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Message {
+        a: u8,
+    }
+
+    let package_dir = build_js_package("trailing-bytes", generate_bindings!(Message));
+
+    let script = "try { p.deserialize(\"Message\", [1, 2]) } catch (e) { console.log(e instanceof p.DeserializationError, e.kind) }
+        console.log(JSON.stringify(p.deserializeLenient(\"Message\", [1, 2])));
+        console.log(JSON.stringify(p.deserialize(\"Message\", [1])));";
+    let Some(output) = run_js(&package_dir, script) else {
+        return;
+    };
+    let mut lines = output.lines();
+
+    assert_eq!(lines.next().unwrap(), "true trailing_bytes");
+    assert_eq!(lines.next().unwrap(), "{\"a\":1}");
+    assert_eq!(lines.next().unwrap(), "{\"a\":1}");
+}
+
+#[test]
+fn test_validate_deserialized() {
+    use std::num::NonZeroU8;
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Reading {
+        id: u8,
+        value: NonZeroU8,
+    }
+
+    let bindings = || generate_bindings!(Reading);
+    let valid = postcard::to_allocvec(&Reading {
+        id: 1,
+        value: NonZeroU8::new(3).unwrap(),
+    })
+    .unwrap();
+    // the same layout with a zero, which no `Reading` encodes to
+    let zero = postcard::to_allocvec(&(1u8, 0u8)).unwrap();
+
+    let script = format!(
+        "for (const bytes of [{}, {}]) {{
+            try {{ console.log(JSON.stringify(p.deserialize(\"Reading\", bytes))) }} catch (e) {{ console.log(e instanceof p.DeserializationError, e.kind) }}
+        }}",
+        js_bytes(&valid),
+        js_bytes(&zero)
+    );
+
+    let package_dir = build_js_package_with_settings(
+        "validate-deserialized",
+        GenerationSettings::enable_all().validate_deserialized(true),
+        bindings(),
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    let mut lines = output.lines();
+    assert_eq!(lines.next().unwrap(), "{\"id\":1,\"value\":3}");
+    assert_eq!(lines.next().unwrap(), "true invalid_value");
+
+    let package_dir = build_js_package_with_settings(
+        "not-validate-deserialized",
+        GenerationSettings::enable_all(),
+        bindings(),
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    let mut lines = output.lines();
+    assert_eq!(lines.next().unwrap(), "{\"id\":1,\"value\":3}");
+    assert_eq!(lines.next().unwrap(), "{\"id\":1,\"value\":0}");
+}
+
+#[test]
+fn test_serializer_is_presized() {
+    #[derive(Serialize, PostcardBindings)]
+    enum Kind {
+        A,
+        B(u16),
+        C { x: f32 },
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Item {
+        id: u32,
+        name: String,
+        values: Vec<i64>,
+        kind: Option<Kind>,
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Catalog {
+        items: Vec<Item>,
+        counts: [u16; 4],
+    }
+
+    let package_dir = build_js_package("presized", generate_bindings!(Kind, Item, Catalog));
+
+    let value = Catalog {
+        items: (0..500)
+            .map(|i| Item {
+                id: i * 1000,
+                name: format!("item {i} \u{fc}\u{1F600}"),
+                values: vec![i as i64, -(i as i64), i as i64 * 100_000],
+                kind: match i % 4 {
+                    0 => None,
+                    1 => Some(Kind::A),
+                    2 => Some(Kind::B(i as u16)),
+                    _ => Some(Kind::C { x: i as f32 / 4.0 }),
+                },
+            })
+            .collect(),
+        counts: [1, 200, 30_000, u16::MAX],
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+
+    // Counts the buffers allocated by size, growing the buffer would allocate again.
+    let script = format!(
+        "let allocations = 0;
+        globalThis.Uint8Array = class extends Uint8Array {{ constructor(...args) {{ super(...args); if (typeof args[0] === \"number\") allocations++ }} }};
+        const kind = (i) => [undefined, {{ tag: \"A\" }}, {{ tag: \"B\", value: i }}, {{ tag: \"C\", value: {{ x: i / 4 }} }}][i % 4];
+        const items = Array.from({{ length: 500 }}, (_, i) => ({{ id: i * 1000, name: `item ${{i}} \\u00fc\\u{{1F600}}`, values: [i, -i, i * 100000], kind: kind(i) }}));
+        const bytes = p.serialize(\"Catalog\", {{ items, counts: [1, 200, 30000, 65535] }});
+        console.log(JSON.stringify(Array.from(bytes)) === JSON.stringify({}));
+        console.log(allocations);"
+        , js_bytes(&bytes).replace(' ', "")
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    let mut lines = output.lines();
+
+    assert_eq!(lines.next().unwrap(), "true");
+    // The empty initial buffer and the reservation for the size hint.
+    assert_eq!(lines.next().unwrap(), "2");
+}
+
+#[test]
+fn test_empty_enum_throws() {
+    #[derive(Serialize, PostcardBindings)]
+    enum Never {}
+
+    let package_dir = build_js_package("empty-enum", generate_bindings!(Never));
+    let unchecked_dir = build_js_package_with_settings(
+        "empty-enum-unchecked",
+        GenerationSettings::enable_all().runtime_type_checks(false),
+        generate_bindings!(Never),
+    );
+
+    let script = format!(
+        "const u = require({:?});
+        const check = (f) => {{ try {{ f(); return \"accepted\" }} catch (e) {{ return e }} }};
+        console.log(check(() => p.serialize(\"Never\", {{ tag: \"A\" }})));
+        console.log(check(() => u.serialize(\"Never\", {{ tag: \"A\" }})));
+        console.log(check(() => p.deserialize(\"Never\", [0])));",
+        unchecked_dir.join("index.js")
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    let mut lines = output.lines();
+
+    assert_eq!(lines.next().unwrap(), "value has wrong format");
+    assert_eq!(lines.next().unwrap(), "uninhabited enum");
+    assert_eq!(lines.next().unwrap(), "uninhabited enum");
+}
+
+#[test]
+fn test_field_codecs() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Event {
+        #[postcard_bindgen(codec = "date")]
+        timestamp_ms: u64,
+        name: String,
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    enum Log {
+        Entry {
+            #[postcard_bindgen(codec = "date")]
+            at: u64,
+        },
+    }
+
+    let date = Codec::new(
+        "(date) => date.getTime()",
+        "(ms) => new Date(Number(ms))",
+        "(date) => date instanceof Date",
+    )
+    .ts_type("Date");
+    let package_dir = build_js_package_with_settings(
+        "field-codecs",
+        GenerationSettings::enable_all()
+            .self_test(true)
+            .codec("date", date),
+        generate_bindings!(Event, Log),
+    );
+
+    let event = Event {
+        timestamp_ms: 1_700_000_000_000,
+        name: "launch".into(),
+    };
+    let event_bytes = postcard::to_allocvec(&event).unwrap();
+    let log_bytes = postcard::to_allocvec(&Log::Entry { at: 42 }).unwrap();
+
+    let script = format!(
+        "const check = (value) => {{ try {{ p.serialize(\"Event\", value); return true }} catch {{ return false }} }};
+        console.log(JSON.stringify(Array.from(p.serialize(\"Event\", {{ timestamp_ms: new Date(1700000000000), name: \"launch\" }}))));
+        const event = p.deserialize(\"Event\", {});
+        console.log(event.timestamp_ms instanceof Date, event.timestamp_ms.getTime(), event.name);
+        console.log(JSON.stringify(Array.from(p.serialize(\"Log\", {{ tag: \"Entry\", value: {{ at: new Date(42) }} }}))));
+        console.log(p.deserialize(\"Log\", {}).value.at.getTime());
+        console.log(check({{ timestamp_ms: 1700000000000, name: \"launch\" }}));
+        p.__selfTest();",
+        js_bytes(&event_bytes),
+        js_bytes(&log_bytes)
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    let mut lines = output.lines();
+
+    assert_eq!(
+        lines.next().unwrap(),
+        js_bytes(&event_bytes).replace(' ', "")
+    );
+    assert_eq!(lines.next().unwrap(), "true 1700000000000 launch");
+    assert_eq!(lines.next().unwrap(), js_bytes(&log_bytes).replace(' ', ""));
+    assert_eq!(lines.next().unwrap(), "42");
+    assert_eq!(lines.next().unwrap(), "false");
+}
+
+#[test]
+#[should_panic(expected = "codec `date` of field `timestamp_ms` in `Event` is not registered")]
+fn test_unregistered_field_codec() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Event {
+        #[postcard_bindgen(codec = "date")]
+        timestamp_ms: u64,
+    }
+
+    build_js_package("unregistered-codec", generate_bindings!(Event));
+}
+
+#[test]
+fn test_serde_renames() {
+    #[derive(Serialize, PostcardBindings)]
+    #[serde(rename = "Reading", rename_all = "camelCase")]
+    struct SensorReading {
+        sensor_id: u8,
+        #[serde(rename = "v")]
+        value: i16,
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    #[serde(rename = "Cmd", rename_all = "snake_case")]
+    enum Command {
+        SetLevel(u8),
+        #[serde(rename = "off")]
+        TurnOff,
+        Report {
+            #[serde(rename = "r")]
+            reading: SensorReading,
+        },
+    }
+
+    let package_dir = build_js_package("serde-renames", generate_bindings!(SensorReading, Command));
+
+    let reading = SensorReading {
+        sensor_id: 3,
+        value: -2,
+    };
+    let command = Command::Report { reading };
+    let bytes = postcard::to_allocvec(&command).unwrap();
+
+    let script = format!(
+        "console.log(JSON.stringify(p.TYPES));
+        console.log(JSON.stringify(p.deserialize(\"Cmd\", {})));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Cmd\", {{ tag: \"off\" }}))));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Cmd\", {{ tag: \"set_level\", value: 7 }}))));",
+        js_bytes(&bytes)
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            "[\"Reading\",\"Cmd\"]".into(),
+            "{\"tag\":\"report\",\"value\":{\"r\":{\"sensorId\":3,\"v\":-2}}}".into(),
+            js_bytes(&postcard::to_allocvec(&Command::TurnOff).unwrap()).replace(' ', ""),
+            js_bytes(&postcard::to_allocvec(&Command::SetLevel(7)).unwrap()).replace(' ', ""),
+        ]
+    );
+}
+
+#[test]
+fn test_field_names_without_identifiers() {
+    #[derive(Serialize, PostcardBindings)]
+    #[serde(rename_all = "kebab-case")]
+    struct Config {
+        sensor_id: u8,
+        sample_rate: Option<u16>,
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    #[serde(rename_all_fields = "kebab-case")]
+    enum Event {
+        Configured { new_config: Config },
+    }
+
+    let package_dir = build_js_package(
+        "field-names-without-identifiers",
+        generate_bindings!(Config, Event),
+    );
+
+    let event = Event::Configured {
+        new_config: Config {
+            sensor_id: 3,
+            sample_rate: Some(100),
+        },
+    };
+    let bytes = postcard::to_allocvec(&event).unwrap();
+
+    let script = format!(
+        "const event = p.deserialize(\"Event\", {});
+        console.log(JSON.stringify(event));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Event\", event))));",
+        js_bytes(&bytes)
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            "{\"tag\":\"Configured\",\"value\":{\"new-config\":{\"sensor-id\":3,\"sample-rate\":100}}}".into(),
+            js_bytes(&bytes).replace(' ', ""),
+        ]
+    );
+}
+
+#[test]
+fn test_same_struct_variants_in_different_enums() {
+    #[derive(Serialize, PostcardBindings)]
+    enum First {
+        Data { a: u8 },
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    enum Second {
+        Data { a: String },
+    }
+
+    let package_dir = build_js_package("same-struct-variants", generate_bindings!(First, Second));
+
+    let first = postcard::to_allocvec(&First::Data { a: 1 }).unwrap();
+    let second = postcard::to_allocvec(&Second::Data { a: "b".into() }).unwrap();
+
+    let script = format!(
+        "console.log(JSON.stringify(Array.from(p.serialize(\"First\", {{ tag: \"Data\", value: {{ a: 1 }} }}))));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Second\", {{ tag: \"Data\", value: {{ a: \"b\" }} }}))));
+        console.log(JSON.stringify(p.deserialize(\"First\", {})), JSON.stringify(p.deserialize(\"Second\", {})));",
+        js_bytes(&first),
+        js_bytes(&second)
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    let mut lines = output.lines();
+
+    assert_eq!(lines.next().unwrap(), js_bytes(&first).replace(' ', ""));
+    assert_eq!(lines.next().unwrap(), js_bytes(&second).replace(' ', ""));
+    assert_eq!(
+        lines.next().unwrap(),
+        "{\"tag\":\"Data\",\"value\":{\"a\":1}} {\"tag\":\"Data\",\"value\":{\"a\":\"b\"}}"
+    );
+}
+
+#[test]
+#[should_panic(expected = "`Same` and `Same` both generate the javascript identifier `SAME`")]
+fn test_colliding_identifiers() {
+    mod a {
+        #[derive(serde::Serialize, postcard_bindgen::PostcardBindings)]
+        pub struct Same {
+            pub a: u8,
+        }
+    }
+
+    mod b {
+        #[derive(serde::Serialize, postcard_bindgen::PostcardBindings)]
+        pub struct Same {
+            pub b: u8,
+        }
+    }
+
+    build_js_package_with_settings(
+        "colliding-identifiers",
+        GenerationSettings::enable_all().module_structure(false),
+        generate_bindings!(a::Same, b::Same),
+    );
+}
+
+#[test]
+fn test_iterable_array_inputs() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Items {
+        numbers: Vec<u16>,
+        names: Vec<String>,
+        fixed: [u8; 2],
+    }
+
+    let value = Items {
+        numbers: vec![1, 300],
+        names: vec!["a".into(), "b".into()],
+        fixed: [7, 8],
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+
+    let script = format!(
+        "function* names() {{ yield \"a\"; yield \"b\" }}
+        const check = (value) => {{ try {{ return JSON.stringify(Array.from(p.serialize(\"Items\", value))) === {expected:?} }} catch (e) {{ return e }} }};
+        console.log(check({{ numbers: new Set([1, 300]), names: names(), fixed: new Uint8Array([7, 8]) }}));
+        console.log(check({{ numbers: new Uint16Array([1, 300]), names: new Set([\"a\", \"b\"]), fixed: [7, 8] }}));
+        const items = p.deserialize(\"Items\", {expected});
+        console.log(JSON.stringify({{ ...items, fixed: Array.from(items.fixed) }}));",
+        expected = js_bytes(&bytes).replace(' ', "")
+    );
+
+    let strict_dir = build_js_package("iterable-inputs-strict", generate_bindings!(Items));
+    let Some(output) = run_js(&strict_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            "value has wrong format",
+            "value has wrong format",
+            "{\"numbers\":[1,300],\"names\":[\"a\",\"b\"],\"fixed\":[7,8]}"
+        ]
+    );
+
+    for (name, gen_settings) in [
+        (
+            "iterable-inputs",
+            GenerationSettings::enable_all().iterable_inputs(true),
+        ),
+        (
+            "iterable-inputs-unchecked",
+            GenerationSettings::enable_all().runtime_type_checks(false),
+        ),
+    ] {
+        let package_dir =
+            build_js_package_with_settings(name, gen_settings, generate_bindings!(Items));
+        let output = run_js(&package_dir, &script).unwrap();
+        assert_eq!(
+            output.lines().collect::<Vec<_>>(),
+            [
+                "true",
+                "true",
+                "{\"numbers\":[1,300],\"names\":[\"a\",\"b\"],\"fixed\":[7,8]}"
+            ]
+        );
+    }
+}
+
+#[test]
+fn test_pointer_width() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Sizes {
+        len: usize,
+        offset: isize,
+    }
+
+    let small = postcard::to_allocvec(&Sizes { len: 7, offset: -5 }).unwrap();
+    let large = postcard::to_allocvec(&Sizes {
+        len: 1 << 40,
+        offset: -5,
+    })
+    .unwrap();
+    let max = postcard::to_allocvec(&Sizes {
+        len: usize::MAX,
+        offset: isize::MIN,
+    })
+    .unwrap();
+
+    let script = format!(
+        "const ser = (value) => {{ try {{ return JSON.stringify(Array.from(p.serialize(\"Sizes\", value))) }} catch (e) {{ return e }} }};
+        console.log(ser({{ len: 7, offset: -5 }}) === {small:?});
+        console.log(ser({{ len: 2 ** 40, offset: -5 }}) === {large:?});
+        const sizes = p.deserialize(\"Sizes\", {max});
+        console.log(typeof sizes.len, typeof sizes.offset);",
+        small = js_bytes(&small).replace(' ', ""),
+        large = js_bytes(&large).replace(' ', ""),
+        max = js_bytes(&max),
+    );
+
+    let package_dir = build_js_package("pointer-width-64", generate_bindings!(Sizes));
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        ["true", "true", "bigint bigint"]
+    );
+
+    let package_dir = build_js_package_with_settings(
+        "pointer-width-32",
+        GenerationSettings::enable_all().pointer_width(PointerWidth::Bits32),
+        generate_bindings!(Sizes),
+    );
+    let script = format!(
+        "const ser = (value) => {{ try {{ return JSON.stringify(Array.from(p.serialize(\"Sizes\", value))) }} catch (e) {{ return e }} }};
+        console.log(ser({{ len: 7, offset: -5 }}) === {small:?});
+        console.log(ser({{ len: 2 ** 40, offset: -5 }}));",
+        small = js_bytes(&small).replace(' ', ""),
+    );
+    let output = run_js(&package_dir, &script).unwrap();
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        ["true", "value has wrong format"]
+    );
+}
+
+#[test]
+fn test_minified_output() {
+    #[derive(Serialize, PostcardBindings)]
+    enum Shape {
+        Point,
+        Circle(f64),
+        Rect { width: u32, height: u32 },
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Scene {
+        name: String,
+        shapes: Vec<Shape>,
+        bytes: Vec<u8>,
+        length: Option<i64>,
+        s: std::collections::HashMap<String, u16>,
+    }
+
+    let value = Scene {
+        name: "scene".into(),
+        shapes: vec![
+            Shape::Point,
+            Shape::Circle(1.5),
+            Shape::Rect {
+                width: 300,
+                height: 4,
+            },
+        ],
+        bytes: vec![1, 2, 3],
+        length: Some(-70_000),
+        s: [("a".to_owned(), 500)].into(),
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+
+    let readable_dir = build_js_package_with_settings(
+        "minified-readable",
+        GenerationSettings::enable_all().self_test(true),
+        generate_bindings!(Shape, Scene),
+    );
+    let minified_dir = build_js_package_with_settings(
+        "minified",
+        GenerationSettings::enable_all()
+            .self_test(true)
+            .minify(true),
+        generate_bindings!(Shape, Scene),
+    );
+
+    let readable = std::fs::read_to_string(readable_dir.join("index.js")).unwrap();
+    let minified = std::fs::read_to_string(minified_dir.join("index.js")).unwrap();
+    assert!(minified.len() < readable.len());
+    assert!(!minified.contains("U32_BYTES") && !minified.contains("deserialize_number"));
+    assert!(!minified.contains("serialize_SCENE") && !minified.contains("Deserializer"));
+    assert!(!minified.contains("    ") && !minified.contains(" = "));
+    // the exported names are kept
+    for name in [
+        "serialize",
+        "deserialize",
+        "DeserializationError",
+        "__selfTest",
+    ] {
+        assert!(
+            minified.contains(&format!("exports.{name}={name}")),
+            "{name}"
+        );
+    }
+
+    let script = format!(
+        "const value = {{ name: \"scene\", shapes: [{{ tag: \"Point\" }}, {{ tag: \"Circle\", value: 1.5 }}, {{ tag: \"Rect\", value: {{ width: 300, height: 4 }} }}], bytes: [1, 2, 3], length: -70000, s: {{ a: 500 }} }};
+        console.log(JSON.stringify(Array.from(p.serialize(\"Scene\", value))));
+        console.log(JSON.stringify(p.deserialize(\"Scene\", {expected})));
+        p.__selfTest();",
+        expected = js_bytes(&bytes),
+    );
+    let Some(readable_output) = run_js(&readable_dir, &script) else {
+        return;
+    };
+    let minified_output = run_js(&minified_dir, &script).unwrap();
+    assert_eq!(minified_output, readable_output);
+    assert_eq!(
+        minified_output.lines().next().unwrap(),
+        js_bytes(&bytes).replace(' ', "")
+    );
+}
+
+#[test]
+#[should_panic(expected = "`Celsius`, `Fahrenheit` have the same structure")]
+fn test_ambiguous_types() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Celsius {
+        value: f32,
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Fahrenheit {
+        value: f32,
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Kelvin {
+        value: f64,
+    }
+
+    build_js_package_with_settings(
+        "ambiguous-types",
+        GenerationSettings::enable_all().ambiguous_types(AmbiguousTypes::Deny),
+        generate_bindings!(Celsius, Fahrenheit, Kelvin),
+    );
+}
+
+#[test]
+fn test_known_types() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Point {
+        x: u8,
+    }
+
+    mod shapes {
+        #[derive(serde::Serialize, postcard_bindgen::PostcardBindings)]
+        pub struct Shape {
+            pub sides: u8,
+        }
+    }
+
+    for (name, gen_settings) in [
+        ("known-types", GenerationSettings::enable_all()),
+        ("known-types-des-only", GenerationSettings::default()),
+    ] {
+        let package_dir = build_js_package_with_settings(
+            name,
+            gen_settings,
+            generate_bindings!(Point, shapes::Shape),
+        );
+        let Some(output) = run_js(
+            &package_dir,
+            "console.log(JSON.stringify(p.TYPES), Object.isFrozen(p.TYPES));
+            console.log(p.isKnownType(\"Point\"), p.isKnownType(\"shapes.Shape\"), p.isKnownType(\"Line\"));",
+        ) else {
+            return;
+        };
+        assert_eq!(
+            output.lines().collect::<Vec<_>>(),
+            ["[\"Point\",\"shapes.Shape\"] true", "true true false"]
+        );
+    }
+}
+
+#[test]
+fn test_nested_arrays_and_optionals() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Nested {
+        a: Vec<Option<u32>>,
+        b: Option<Vec<u32>>,
+        c: Vec<Option<Vec<u8>>>,
+        d: Vec<(u8, Option<u8>)>,
+        e: std::collections::BTreeMap<u8, Option<u8>>,
+    }
+
+    let package_dir = build_js_package_with_settings(
+        "nested-arrays-and-optionals",
+        GenerationSettings::enable_all().self_test(true),
+        generate_bindings!(Nested),
+    );
+
+    let values = [
+        (
+            Nested {
+                a: vec![Some(1), None, Some(70_000)],
+                b: Some(vec![2, 300]),
+                c: vec![None, Some(vec![1, 2]), Some(vec![])],
+                d: vec![(1, None), (2, Some(3))],
+                e: [(1, None), (2, Some(3))].into(),
+            },
+            "{ a: [1, undefined, 70000], b: [2, 300], c: [undefined, [1, 2], []], d: [[1, undefined], [2, 3]], e: new Map([[1, undefined], [2, 3]]) }",
+        ),
+        (
+            Nested {
+                a: vec![],
+                b: None,
+                c: vec![None],
+                d: vec![],
+                e: Default::default(),
+            },
+            "{ a: [], b: undefined, c: [undefined], d: [], e: new Map() }",
+        ),
+    ];
+
+    for (value, js_value) in values {
+        let bytes = postcard::to_allocvec(&value).unwrap();
+        let script = format!(
+            "const value = {js_value};
+            console.log(JSON.stringify(Array.from(p.serialize(\"Nested\", value))));
+            const deserialized = p.deserialize(\"Nested\", {bytes});
+            console.log(JSON.stringify(Array.from(p.serialize(\"Nested\", deserialized))));
+            p.__selfTest();",
+            bytes = js_bytes(&bytes),
+        );
+        let Some(output) = run_js(&package_dir, &script) else {
+            return;
+        };
+        let expected = js_bytes(&bytes).replace(' ', "");
+        let mut lines = output.lines();
+        assert_eq!(lines.next().unwrap(), expected);
+        assert_eq!(lines.next().unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_encoded_length() {
+    #[derive(Serialize, PostcardBindings)]
+    enum Payload {
+        Empty,
+        Values(Vec<u64>),
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Frame {
+        id: u32,
+        offset: i64,
+        name: String,
+        payload: Payload,
+        checksum: Option<u16>,
+    }
+
+    for (name, gen_settings) in [
+        ("encoded-length", GenerationSettings::enable_all()),
+        (
+            "encoded-length-minified",
+            GenerationSettings::enable_all().minify(true),
+        ),
+    ] {
+        let package_dir =
+            build_js_package_with_settings(name, gen_settings, generate_bindings!(Payload, Frame));
+
+        let values = [
+            (
+                Frame {
+                    id: 0,
+                    offset: 0,
+                    name: String::new(),
+                    payload: Payload::Empty,
+                    checksum: None,
+                },
+                "{ id: 0, offset: 0n, name: \"\", payload: { tag: \"Empty\" }, checksum: undefined }",
+            ),
+            (
+                Frame {
+                    id: u32::MAX,
+                    offset: i64::MIN,
+                    name: "grüße 🦀".into(),
+                    payload: Payload::Values(vec![1, 300, u64::MAX]),
+                    checksum: Some(0xffff),
+                },
+                "{ id: 4294967295, offset: -9223372036854775808n, name: \"grüße 🦀\", payload: { tag: \"Values\", value: [1n, 300n, 18446744073709551615n] }, checksum: 65535 }",
+            ),
+        ];
+
+        for (value, js_value) in values {
+            let expected = postcard::to_allocvec(&value).unwrap().len();
+            let script = format!(
+                "const value = {js_value};
+                console.log(p.encodedLength(\"Frame\", value), p.serialize(\"Frame\", value).length);"
+            );
+            let Some(output) = run_js(&package_dir, &script) else {
+                return;
+            };
+            assert_eq!(output, format!("{expected} {expected}"));
+        }
+    }
+}
+
+#[test]
+fn test_identifier_case() {
+    mod shapes {
+        #[derive(serde::Serialize, postcard_bindgen::PostcardBindings)]
+        pub enum ShapeKind {
+            Circle(u32),
+            Square { side: u16 },
+        }
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    struct DrawCommand {
+        kinds: Vec<shapes::ShapeKind>,
+        label: Option<String>,
+    }
+
+    let value = DrawCommand {
+        kinds: vec![
+            shapes::ShapeKind::Circle(300),
+            shapes::ShapeKind::Square { side: 7 },
+        ],
+        label: Some("a".into()),
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+
+    for (name, ident_case, helper) in [
+        (
+            "identifier-case-screaming-snake",
+            IdentifierCase::ScreamingSnake,
+            "serialize_shapes_SHAPE_KIND",
+        ),
+        (
+            "identifier-case-snake",
+            IdentifierCase::Snake,
+            "serialize_shapes_shape_kind",
+        ),
+        (
+            "identifier-case-camel",
+            IdentifierCase::Camel,
+            "serialize_shapes_shapeKind",
+        ),
+        (
+            "identifier-case-pascal",
+            IdentifierCase::Pascal,
+            "serialize_shapes_ShapeKind",
+        ),
+    ] {
+        let package_dir = build_js_package_with_settings(
+            name,
+            GenerationSettings::enable_all()
+                .self_test(true)
+                .ident_case(ident_case),
+            generate_bindings!(shapes::ShapeKind, DrawCommand),
+        );
+
+        let source = std::fs::read_to_string(package_dir.join("index.js")).unwrap();
+        assert!(source.contains(&format!("function {helper}(")), "{name}");
+
+        let script = format!(
+            "const value = {{ kinds: [{{ tag: \"Circle\", value: 300 }}, {{ tag: \"Square\", value: {{ side: 7 }} }}], label: \"a\" }};
+            console.log(JSON.stringify(Array.from(p.serialize(\"DrawCommand\", value))));
+            console.log(JSON.stringify(p.deserialize(\"DrawCommand\", {bytes})) === JSON.stringify(value));
+            p.__selfTest();",
+            bytes = js_bytes(&bytes),
+        );
+        let Some(output) = run_js(&package_dir, &script) else {
+            return;
+        };
+        let mut lines = output.lines();
+        assert_eq!(lines.next().unwrap(), js_bytes(&bytes).replace(' ', ""));
+        assert_eq!(lines.next().unwrap(), "true");
+    }
+}
+
+#[test]
+fn test_generic_instantiations() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Wrapper<T> {
+        inner: T,
+        count: u8,
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    enum Either<L, R> {
+        Left(L),
+        Right(R),
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Holder {
+        number: Wrapper<u32>,
+        text: Wrapper<String>,
+        either: Either<Vec<u8>, Option<bool>>,
+    }
+
+    let package_dir = build_js_package_with_settings(
+        "generic-instantiations",
+        GenerationSettings::enable_all().self_test(true),
+        generate_bindings!(
+            Wrapper<u32>,
+            Wrapper<String>,
+            Either<Vec<u8>, Option<bool>>,
+            Holder
+        ),
+    );
+
+    let value = Holder {
+        number: Wrapper {
+            inner: 300,
+            count: 1,
+        },
+        text: Wrapper {
+            inner: "a".into(),
+            count: 2,
+        },
+        either: Either::Right(Some(true)),
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+    let left = Either::<Vec<u8>, Option<bool>>::Left(vec![1, 2]);
+
+    let script = format!(
+        "console.log(JSON.stringify(p.TYPES));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Wrapper_u32\", {{ inner: 300, count: 1 }}))));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Wrapper_String\", {{ inner: \"a\", count: 2 }}))));
+        console.log(JSON.stringify(p.deserialize(\"Holder\", {bytes})));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Either_Vec_u8_Option_bool\", {{ tag: \"Left\", value: [1, 2] }}))));
+        p.__selfTest();",
+        bytes = js_bytes(&bytes),
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    let mut lines = output.lines();
+    assert_eq!(
+        lines.next().unwrap(),
+        "[\"Wrapper_u32\",\"Wrapper_String\",\"Either_Vec_u8_Option_bool\",\"Holder\"]"
+    );
+    assert_eq!(
+        lines.next().unwrap(),
+        js_bytes(&postcard::to_allocvec(&value.number).unwrap()).replace(' ', "")
+    );
+    assert_eq!(
+        lines.next().unwrap(),
+        js_bytes(&postcard::to_allocvec(&value.text).unwrap()).replace(' ', "")
+    );
+    assert_eq!(
+        lines.next().unwrap(),
+        "{\"number\":{\"inner\":300,\"count\":1},\"text\":{\"inner\":\"a\",\"count\":2},\"either\":{\"tag\":\"Right\",\"value\":true}}"
+    );
+    assert_eq!(
+        lines.next().unwrap(),
+        js_bytes(&postcard::to_allocvec(&left).unwrap()).replace(' ', "")
+    );
+}
+
+#[test]
+fn test_dual_package_module_formats() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Point {
+        x: i16,
+        label: String,
+    }
+
+    let parent_dir = std::env::temp_dir().join("postcard-bindgen-round-trip");
+    build_dual_package(
+        parent_dir.as_path(),
+        PackageInfo {
+            name: "dual-package".into(),
+            version: "0.1.0".try_into().unwrap(),
+        },
+        GenerationSettings::enable_all(),
+        generate_bindings!(Point),
+    )
+    .unwrap();
+    let package_dir = parent_dir.join("dual-package");
+
+    let value = Point {
+        x: -300,
+        label: "ä".into(),
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+
+    // the umd bundle is run like a <script> tag, in a context without `module` and `exports`
+    let script = format!(
+        "const fs = require(\"fs\");
+        const vm = require(\"vm\");
+        const url = require(\"url\");
+        const dir = {dir:?};
+        const cjs = require(dir);
+        const context = {{ TextEncoder, TextDecoder }};
+        vm.runInNewContext(fs.readFileSync(dir + \"/index.umd.js\", \"utf8\"), context);
+        import(url.pathToFileURL(dir + \"/index.mjs\")).then((esm) => {{
+            for (const p of [cjs, esm, context.dualPackage]) {{
+                console.log(JSON.stringify(Array.from(p.serialize(\"Point\", {{ x: -300, label: \"ä\" }}))));
+                console.log(JSON.stringify(p.deserialize(\"Point\", {bytes})));
+            }}
+        }});",
+        dir = package_dir.to_str().unwrap(),
+        bytes = js_bytes(&bytes),
+    );
+    let output = match Command::new("node").arg("-e").arg(script).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            eprintln!("node not found, skipping js round trip test");
+            return;
+        }
+        Err(e) => panic!("failed to run node: {e}"),
+    };
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = String::from_utf8(output.stdout).unwrap();
+    let lines = output.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 6);
+    for pair in lines.chunks(2) {
+        assert_eq!(pair[0], js_bytes(&bytes).replace(' ', ""));
+        assert_eq!(pair[1], "{\"x\":-300,\"label\":\"ä\"}");
+    }
+}
+
+#[test]
+fn test_package_resolved_by_name() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Point {
+        x: i16,
+        y: i16,
+    }
+
+    // node resolves bare specifiers from `node_modules` through the `exports` map
+    let root_dir = std::env::temp_dir().join("postcard-bindgen-resolve");
+    let node_modules = root_dir.join("node_modules");
+    for (name, esm_module) in [("esm-package", true), ("cjs-package", false)] {
+        build_package(
+            node_modules.as_path(),
+            PackageInfo {
+                name: name.into(),
+                version: "0.1.0".try_into().unwrap(),
+            },
+            GenerationSettings::enable_all().esm_module(esm_module),
+            generate_bindings!(Point),
+        )
+        .unwrap();
+    }
+
+    let script = "const cjs = require(\"cjs-package\");
+        import(\"esm-package\").then((esm) => {
+            for (const p of [cjs, esm]) {
+                console.log(JSON.stringify(Array.from(p.serialize(\"Point\", { x: -1, y: 300 }))));
+            }
+        });";
+    let output = match Command::new("node")
+        .arg("-e")
+        .arg(script)
+        .current_dir(&root_dir)
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            eprintln!("node not found, skipping js round trip test");
+            return;
+        }
+        Err(e) => panic!("failed to run node: {e}"),
+    };
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let bytes = postcard::to_allocvec(&Point { x: -1, y: 300 }).unwrap();
+    let expected = js_bytes(&bytes).replace(' ', "");
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(output.lines().collect::<Vec<_>>(), [&expected, &expected]);
+}
+
+#[test]
+fn test_package_rejects_invalid_names() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Point {
+        x: i16,
+    }
+
+    let parent_dir = std::env::temp_dir().join("postcard-bindgen-invalid-names");
+    for name in [
+        "",
+        "Upper",
+        "_private",
+        ".hidden",
+        "with space",
+        "@scope",
+        "@/name",
+    ] {
+        let err = build_package(
+            parent_dir.as_path(),
+            PackageInfo {
+                name: name.into(),
+                version: "0.1.0".try_into().unwrap(),
+            },
+            GenerationSettings::enable_all(),
+            generate_bindings!(Point),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput, "{name}");
+    }
+
+    build_package(
+        parent_dir.as_path(),
+        PackageInfo {
+            name: "@scope/valid-name".into(),
+            version: "0.1.0".try_into().unwrap(),
+        },
+        GenerationSettings::enable_all(),
+        generate_bindings!(Point),
+    )
+    .unwrap();
+    assert!(parent_dir.join("@scope/valid-name/package.json").exists());
+}
+
+#[test]
+fn test_split_modules_round_trip() {
+    #[derive(Serialize, PostcardBindings)]
+    enum Shape {
+        Circle(Point),
+        Polygon(Vec<Point>),
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Point {
+        x: i16,
+        y: i16,
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Drawing {
+        name: String,
+        shapes: Vec<Shape>,
+    }
+
+    let value = Drawing {
+        name: "ä".into(),
+        shapes: vec![
+            Shape::Circle(Point { x: -1, y: 2 }),
+            Shape::Polygon(vec![Point { x: 300, y: 0 }]),
+        ],
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+    let expected = js_bytes(&bytes).replace(' ', "");
+
+    let root_dir = std::env::temp_dir().join("postcard-bindgen-split");
+    for (name, minify) in [("split-package", false), ("split-package-min", true)] {
+        build_package(
+            root_dir.join("node_modules").as_path(),
+            PackageInfo {
+                name: name.into(),
+                version: "0.1.0".try_into().unwrap(),
+            },
+            GenerationSettings::enable_all()
+                .split_modules(true)
+                .validate_deserialized(true)
+                .self_test(true)
+                .minify(minify),
+            generate_bindings!(Shape, Point, Drawing),
+        )
+        .unwrap();
+
+        // the type module is imported on its own, without the index
+        let script = format!(
+            "const value = {{ name: \"ä\", shapes: [
+                {{ tag: \"Circle\", value: {{ x: -1, y: 2 }} }},
+                {{ tag: \"Polygon\", value: [{{ x: 300, y: 0 }}] }},
+            ] }};
+            Promise.all([import(\"{name}\"), import(\"{name}/types/DRAWING\")]).then(([p, drawing]) => {{
+                p.__selfTest();
+                console.log(JSON.stringify(Array.from(p.serialize(\"Drawing\", value))));
+                console.log(JSON.stringify(Array.from(drawing.serialize(value))));
+                console.log(JSON.stringify(Array.from(p.DRAWING.serialize(value))));
+                console.log(drawing.encodedLength(value));
+                console.log(JSON.stringify(drawing.deserialize({bytes})) === JSON.stringify(value));
+                console.log(JSON.stringify(p.deserialize(\"Drawing\", {bytes})) === JSON.stringify(value));
+                try {{ drawing.deserialize([...{bytes}, 0]) }} catch (e) {{ console.log(e.kind) }}
+                try {{ drawing.serialize({{ name: 1, shapes: [] }}) }} catch (e) {{ console.log(e) }}
+            }});",
+            bytes = js_bytes(&bytes),
+        );
+        let output = match Command::new("node")
+            .arg("-e")
+            .arg(script)
+            .current_dir(&root_dir)
+            .output()
+        {
+            Ok(output) => output,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                eprintln!("node not found, skipping js round trip test");
+                return;
+            }
+            Err(e) => panic!("failed to run node: {e}"),
+        };
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let output = String::from_utf8(output.stdout).unwrap();
+        assert_eq!(
+            output.lines().collect::<Vec<_>>(),
+            [
+                expected.as_str(),
+                &expected,
+                &expected,
+                &bytes.len().to_string(),
+                "true",
+                "true",
+                "trailing_bytes",
+                "value has wrong format"
+            ],
+            "{name}"
+        );
+    }
+
+    let package_dir = root_dir.join("node_modules").join("split-package");
+    for file in [
+        "runtime.js",
+        "types/SHAPE.js",
+        "types/POINT.js",
+        "types/DRAWING.js",
+    ] {
+        assert!(package_dir.join(file).exists(), "{file}");
+    }
+}
+
+#[test]
+fn test_maps_with_custom_hasher() {
+    use std::{
+        collections::{hash_map::DefaultHasher, HashMap},
+        hash::BuildHasherDefault,
+    };
+
+    type CustomMap<K, V> = HashMap<K, V, BuildHasherDefault<DefaultHasher>>;
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Maps {
+        a: CustomMap<u8, u16>,
+        b: CustomMap<String, bool>,
+    }
+
+    let package_dir = build_js_package("maps-with-custom-hasher", generate_bindings!(Maps));
+
+    let value = Maps {
+        a: [(1, 300)].into_iter().collect(),
+        b: [("x".to_owned(), true)].into_iter().collect(),
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+    let script = format!(
+        "console.log(JSON.stringify(Array.from(p.serialize(\"Maps\", {{ a: new Map([[1, 300]]), b: {{ x: true }} }}))));
+        const deserialized = p.deserialize(\"Maps\", {bytes});
+        console.log(deserialized.a.get(1), deserialized.b.x);",
+        bytes = js_bytes(&bytes),
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [js_bytes(&bytes).replace(' ', ""), "300 true".into()]
+    );
+}
+
+#[test]
+fn test_sets() {
+    use std::collections::{BTreeSet, HashSet};
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Sets {
+        a: BTreeSet<u16>,
+        b: HashSet<String>,
+        c: BTreeSet<(u8, bool)>,
+    }
+
+    let package_dir = build_js_package("sets", generate_bindings!(Sets));
+
+    let value = Sets {
+        a: [1, 300].into_iter().collect(),
+        b: ["x".to_owned()].into_iter().collect(),
+        c: [(1, true), (2, false)].into_iter().collect(),
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+    let script = format!(
+        "const check = (value) => {{ try {{ p.serialize(\"Sets\", value); return true }} catch {{ return false }} }};
+        console.log(JSON.stringify(Array.from(p.serialize(\"Sets\", {{ a: new Set([1, 300]), b: new Set([\"x\"]), c: new Set([[1, true], [2, false]]) }}))));
+        const deserialized = p.deserialize(\"Sets\", {bytes});
+        console.log(deserialized.a instanceof Set, [...deserialized.a].join(), [...deserialized.b].join(), JSON.stringify([...deserialized.c]));
+        console.log(JSON.stringify([
+            check({{ a: new Set([70000]), b: new Set(), c: new Set() }}),
+            check({{ a: [1], b: new Set(), c: new Set() }}),
+            check({{ a: new Set(), b: new Set([1]), c: new Set() }}),
+        ]));",
+        bytes = js_bytes(&bytes),
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            js_bytes(&bytes).replace(' ', ""),
+            "true 1,300 x [[1,true],[2,false]]".into(),
+            "[false,false,false]".into(),
+        ]
+    );
+}
+
+#[test]
+fn test_fixed_arrays_have_no_length_prefix() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Fixed {
+        names: [String; 2],
+        pairs: [(u8, bool); 2],
+    }
+
+    let package_dir = build_js_package("fixed-arrays", generate_bindings!(Fixed));
+
+    let value = Fixed {
+        names: ["a".into(), "bc".into()],
+        pairs: [(1, true), (2, false)],
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+    assert_eq!(bytes, [1, b'a', 2, b'b', b'c', 1, 1, 2, 0]);
+
+    let script = format!(
+        "const check = (value) => {{ try {{ p.serialize(\"Fixed\", value); return true }} catch {{ return false }} }};
+        console.log(JSON.stringify(Array.from(p.serialize(\"Fixed\", {{ names: [\"a\", \"bc\"], pairs: [[1, true], [2, false]] }}))));
+        console.log(JSON.stringify(p.deserialize(\"Fixed\", {bytes})));
+        console.log(JSON.stringify([
+            check({{ names: [\"a\"], pairs: [[1, true], [2, false]] }}),
+            check({{ names: [\"a\", \"b\", \"c\"], pairs: [[1, true], [2, false]] }}),
+            check({{ names: [\"a\", \"b\"], pairs: [[1, true]] }}),
+        ]));",
+        bytes = js_bytes(&bytes),
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            js_bytes(&bytes).replace(' ', ""),
+            "{\"names\":[\"a\",\"bc\"],\"pairs\":[[1,true],[2,false]]}".into(),
+            "[false,false,false]".into(),
+        ]
+    );
+}
+
+#[test]
+fn test_tuple_fields() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Marker {
+        pos: (f32, f32),
+        label: (u8, (bool, String)),
+        range: Option<(u8, u16)>,
+    }
+
+    let package_dir = build_js_package("tuple-fields", generate_bindings!(Marker));
+
+    let value = Marker {
+        pos: (0.5, -1.0),
+        label: (3, (true, "x".into())),
+        range: Some((1, 300)),
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+    let script = format!(
+        "const check = (value) => {{ try {{ p.serialize(\"Marker\", value); return true }} catch {{ return false }} }};
+        const marker = {{ pos: [0.5, -1], label: [3, [true, \"x\"]], range: [1, 300] }};
+        console.log(JSON.stringify(Array.from(p.serialize(\"Marker\", marker))));
+        console.log(JSON.stringify(p.deserialize(\"Marker\", {bytes})));
+        console.log(JSON.stringify([
+            check({{ ...marker, pos: [0.5] }}),
+            check({{ ...marker, pos: [0.5, -1, 2] }}),
+            check({{ ...marker, label: [3, [1, \"x\"]] }}),
+            check({{ ...marker, range: [1, 70000] }}),
+            check({{ ...marker, range: undefined }}),
+        ]));",
+        bytes = js_bytes(&bytes),
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            js_bytes(&bytes).replace(' ', ""),
+            "{\"pos\":[0.5,-1],\"label\":[3,[true,\"x\"]],\"range\":[1,300]}".into(),
+            "[false,false,false,false,true]".into(),
+        ]
+    );
+}
+
+#[test]
+fn test_nested_optionals() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Patch {
+        name: Option<Option<String>>,
+        items: Option<Vec<u16>>,
+        pair: Option<(u8, Option<u16>)>,
+    }
+
+    let package_dir = build_js_package("nested-optionals", generate_bindings!(Patch));
+
+    let values = [
+        Patch {
+            name: None,
+            items: None,
+            pair: None,
+        },
+        Patch {
+            name: Some(None),
+            items: Some(vec![]),
+            pair: Some((1, None)),
+        },
+        Patch {
+            name: Some(Some("x".into())),
+            items: Some(vec![1, 2]),
+            pair: Some((1, Some(300))),
+        },
+    ];
+    let script = values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            let bytes = js_bytes(&postcard::to_allocvec(value).unwrap());
+            format!(
+                "const value{i} = p.deserialize(\"Patch\", {bytes});
+                console.log(JSON.stringify(value{i}), JSON.stringify(Array.from(p.serialize(\"Patch\", value{i}))) === {expected:?});",
+                expected = bytes.replace(' ', ""),
+            )
+        })
+        .chain([
+            "const check = (value) => { try { p.serialize(\"Patch\", value); return true } catch { return false } };
+            console.log(JSON.stringify([check({ name: \"x\" }), check({ name: { value: 1 } })]));"
+                .into(),
+        ])
+        .collect::<Vec<_>>()
+        .join("\n");
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            "{} true",
+            "{\"name\":{},\"items\":[],\"pair\":[1,null]} true",
+            "{\"name\":{\"value\":\"x\"},\"items\":[1,2],\"pair\":[1,300]} true",
+            "[false,false]",
+        ]
+    );
+}
+
+#[test]
+fn test_result_fields() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Response {
+        id: u8,
+        result: Result<u16, String>,
+    }
+
+    let package_dir = build_js_package(
+        "result-fields",
+        generate_bindings!(Response, Result<u16, String>),
+    );
+
+    let ok = postcard::to_allocvec(&Response {
+        id: 1,
+        result: Ok(300),
+    })
+    .unwrap();
+    let err = postcard::to_allocvec(&Response {
+        id: 2,
+        result: Err("failed".into()),
+    })
+    .unwrap();
+    let script = format!(
+        "const check = (value) => {{ try {{ p.serialize(\"Response\", value); return true }} catch {{ return false }} }};
+        console.log(JSON.stringify(Array.from(p.serialize(\"Response\", {{ id: 1, result: {{ tag: \"Ok\", value: 300 }} }}))));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Response\", {{ id: 2, result: {{ tag: \"Err\", value: \"failed\" }} }}))));
+        console.log(JSON.stringify(p.deserialize(\"Response\", {ok})), JSON.stringify(p.deserialize(\"Response\", {err})));
+        console.log(JSON.stringify([
+            check({{ id: 1, result: {{ tag: \"Ok\", value: \"a\" }} }}),
+            check({{ id: 1, result: {{ tag: \"Err\", value: 1 }} }}),
+            check({{ id: 1, result: {{ tag: \"None\" }} }}),
+        ]));",
+        ok = js_bytes(&ok),
+        err = js_bytes(&err),
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            js_bytes(&ok).replace(' ', ""),
+            js_bytes(&err).replace(' ', ""),
+            "{\"id\":1,\"result\":{\"tag\":\"Ok\",\"value\":300}} {\"id\":2,\"result\":{\"tag\":\"Err\",\"value\":\"failed\"}}".into(),
+            "[false,false,false]".into(),
+        ]
+    );
+}
+
+#[test]
+fn test_char_fields() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Key {
+        ascii: char,
+        wide: char,
+        all: Vec<char>,
+    }
+
+    let package_dir = build_js_package("char-fields", generate_bindings!(Key));
+
+    let value = Key {
+        ascii: 'a',
+        wide: '🦀',
+        all: vec!['ä', 'b'],
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+    let script = format!(
+        "const check = (value) => {{ try {{ p.serialize(\"Key\", value); return true }} catch {{ return false }} }};
+        const key = {{ ascii: \"a\", wide: \"🦀\", all: [\"ä\", \"b\"] }};
+        console.log(JSON.stringify(Array.from(p.serialize(\"Key\", key))));
+        console.log(JSON.stringify(p.deserialize(\"Key\", {bytes})) === JSON.stringify(key));
+        console.log(JSON.stringify([
+            check({{ ...key, ascii: \"\" }}),
+            check({{ ...key, ascii: \"ab\" }}),
+            check({{ ...key, wide: \"\\uD83E\" }}),
+            check({{ ...key, all: [\"ab\"] }}),
+        ]));",
+        bytes = js_bytes(&bytes),
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            js_bytes(&bytes).replace(' ', ""),
+            "true".into(),
+            "[false,false,false,false]".into(),
+        ]
+    );
+}
+
+#[test]
+fn test_128_bit_integers() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Wide {
+        max: u128,
+        min: i128,
+        small: i128,
+    }
+
+    let package_dir = build_js_package("128-bit-integers", generate_bindings!(Wide));
+
+    let value = Wide {
+        max: u128::MAX,
+        min: i128::MIN,
+        small: -3,
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+    let script = format!(
+        "const check = (value) => {{ try {{ p.serialize(\"Wide\", value); return true }} catch {{ return false }} }};
+        const wide = {{ max: {max}n, min: {min}n, small: -3 }};
+        console.log(JSON.stringify(Array.from(p.serialize(\"Wide\", wide))));
+        const des = p.deserialize(\"Wide\", {bytes});
+        console.log(des.max === wide.max, des.min === wide.min, des.small === -3);
+        console.log(JSON.stringify([
+            check({{ ...wide, max: {max}n + 1n }}),
+            check({{ ...wide, max: -1 }}),
+            check({{ ...wide, min: {min}n - 1n }}),
+            check({{ ...wide, small: 0.5 }}),
+        ]));",
+        max = u128::MAX,
+        min = i128::MIN,
+        bytes = js_bytes(&bytes),
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            js_bytes(&bytes).replace(' ', ""),
+            "true true true".into(),
+            "[false,false,false,false]".into(),
+        ]
+    );
+}
+
+#[test]
+fn test_floats() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Floats {
+        single: f32,
+        double: f64,
+        extremes: [f64; 3],
+    }
+
+    let package_dir = build_js_package("floats", generate_bindings!(Floats));
+
+    let value = Floats {
+        single: 0.1,
+        double: -1e300,
+        extremes: [-0.0, f64::MAX, f64::MIN_POSITIVE],
+    };
+    let special = Floats {
+        single: f32::NAN,
+        double: f64::INFINITY,
+        extremes: [f64::NEG_INFINITY, 0.0, 1.0],
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+    let special_bytes = postcard::to_allocvec(&special).unwrap();
+    let script = format!(
+        "const check = (value) => {{ try {{ p.serialize(\"Floats\", value); return true }} catch {{ return false }} }};
+        const floats = {{ single: 0.1, double: -1e300, extremes: [-0, Number.MAX_VALUE, 2.2250738585072014e-308] }};
+        console.log(JSON.stringify(Array.from(p.serialize(\"Floats\", floats))));
+        const des = p.deserialize(\"Floats\", {bytes});
+        console.log(des.single === Math.fround(0.1), des.double, Object.is(des.extremes[0], -0), des.extremes[1] === Number.MAX_VALUE);
+        const special = p.deserialize(\"Floats\", {special_bytes});
+        console.log(special.single, special.double, special.extremes[0]);
+        console.log(JSON.stringify(Array.from(p.serialize(\"Floats\", special))));
+        console.log(JSON.stringify([check({{ ...floats, single: \"1\" }}), check({{ ...floats, double: NaN }})]));",
+        bytes = js_bytes(&bytes),
+        special_bytes = js_bytes(&special_bytes),
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            js_bytes(&bytes).replace(' ', ""),
+            "true -1e+300 true true".into(),
+            "NaN Infinity -Infinity".into(),
+            js_bytes(&special_bytes).replace(' ', ""),
+            "[false,true]".into(),
+        ]
+    );
+}
+
+#[test]
+fn test_non_zero_integers() {
+    use std::num::{NonZeroI64, NonZeroU8};
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Ids {
+        small: NonZeroU8,
+        wide: NonZeroI64,
+        optional: Option<NonZeroU8>,
+    }
+
+    let package_dir = build_js_package("non-zero-integers", generate_bindings!(Ids));
+
+    let value = Ids {
+        small: NonZeroU8::new(7).unwrap(),
+        wide: NonZeroI64::new(i64::MIN).unwrap(),
+        optional: None,
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+    let script = format!(
+        "const check = (value) => {{ try {{ p.serialize(\"Ids\", value); return true }} catch {{ return false }} }};
+        const ids = {{ small: 7, wide: -9223372036854775808n }};
+        console.log(JSON.stringify(Array.from(p.serialize(\"Ids\", ids))));
+        const des = p.deserialize(\"Ids\", {bytes});
+        console.log(des.small, des.wide === ids.wide, des.optional);
+        console.log(JSON.stringify([check({{ ...ids, small: 0 }}), check({{ ...ids, wide: 0n }}), check({{ ...ids, optional: 0 }}), check({{ ...ids, optional: 1 }})]));",
+        bytes = js_bytes(&bytes),
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            js_bytes(&bytes).replace(' ', ""),
+            "7 true undefined".into(),
+            "[false,false,false,true]".into(),
+        ]
+    );
+}
+
+#[test]
+fn test_time_fields() {
+    use std::time::{Duration, SystemTime};
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Telemetry {
+        uptime: Duration,
+        timestamp: SystemTime,
+    }
+
+    let package_dir = build_js_package(
+        "time-fields",
+        generate_bindings!(Telemetry, Duration, SystemTime),
+    );
+
+    let value = Telemetry {
+        uptime: Duration::new(90, 500),
+        timestamp: SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 1),
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+    let script = format!(
+        "const telemetry = {{ uptime: {{ secs: 90, nanos: 500 }}, timestamp: {{ secs: 1700000000, nanos: 1 }} }};
+        console.log(JSON.stringify(Array.from(p.serialize(\"Telemetry\", telemetry))));
+        console.log(JSON.stringify(p.deserialize(\"Telemetry\", {bytes})) === JSON.stringify(telemetry));",
+        bytes = js_bytes(&bytes),
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [js_bytes(&bytes).replace(' ', ""), "true".into()]
+    );
+}
+
+#[test]
+fn test_path_fields() {
+    use std::path::{Path, PathBuf};
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Export<'a> {
+        source: &'a Path,
+        target: PathBuf,
+    }
+
+    let package_dir = build_js_package("path-fields", generate_bindings!(Export));
+
+    let value = Export {
+        source: Path::new("data/raw.bin"),
+        target: PathBuf::from("/tmp/out ä.json"),
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+    let script = format!(
+        "const value = p.deserialize(\"Export\", {bytes});
+        console.log(value.source, value.target);
+        console.log(JSON.stringify(Array.from(p.serialize(\"Export\", value))));",
+        bytes = js_bytes(&bytes),
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            "data/raw.bin /tmp/out ä.json".into(),
+            js_bytes(&bytes).replace(' ', ""),
+        ]
+    );
+}
+
+#[test]
+fn test_network_addresses() {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Peer {
+        gateway: Ipv4Addr,
+        addr: IpAddr,
+        socket: SocketAddr,
+        #[postcard_bindgen(codec = "ipv4")]
+        gateway_str: Ipv4Addr,
+        #[postcard_bindgen(codec = "ipv6")]
+        link_local: Ipv6Addr,
+        #[postcard_bindgen(codec = "ip")]
+        addr_str: IpAddr,
+        #[postcard_bindgen(codec = "socket")]
+        socket_str: SocketAddr,
+    }
+
+    let package_dir = build_js_package_with_settings(
+        "network-addresses",
+        GenerationSettings::enable_all()
+            .codec("ipv4", Codec::ipv4_addr())
+            .codec("ipv6", Codec::ipv6_addr())
+            .codec("ip", Codec::ip_addr())
+            .codec("socket", Codec::socket_addr()),
+        generate_bindings!(Peer, IpAddr, SocketAddr, SocketAddrV4, SocketAddrV6),
+    );
+
+    let ipv6 = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+    let value = Peer {
+        gateway: Ipv4Addr::new(192, 168, 0, 1),
+        addr: IpAddr::V6(ipv6),
+        socket: SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 8080)),
+        gateway_str: Ipv4Addr::new(10, 0, 0, 254),
+        link_local: Ipv6Addr::new(0xfe80, 0, 0, 0, 0x1ff, 0xfe23, 0x4567, 0x890a),
+        addr_str: IpAddr::V6(ipv6),
+        socket_str: SocketAddr::V6(SocketAddrV6::new(ipv6, 443, 0, 0)),
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+    let script = format!(
+        "const peer = p.deserialize(\"Peer\", {bytes});
+        console.log(JSON.stringify(Array.from(peer.gateway)), peer.addr.tag, peer.socket.value.port);
+        console.log(peer.gateway_str, peer.link_local, peer.addr_str, peer.socket_str);
+        console.log(JSON.stringify(Array.from(p.serialize(\"Peer\", peer))));
+        const other = {{ ...peer, link_local: \"::\", addr_str: \"127.0.0.1\", socket_str: \"[::ffff:0:1]:1\" }};
+        console.log(JSON.stringify(p.deserialize(\"Peer\", p.serialize(\"Peer\", other))) === JSON.stringify(other));
+        const check = (value) => {{ try {{ p.serialize(\"Peer\", value); return true }} catch {{ return false }} }};
+        console.log(JSON.stringify([
+            check({{ ...peer, gateway_str: \"256.0.0.1\" }}),
+            check({{ ...peer, link_local: \"1::2::3\" }}),
+            check({{ ...peer, socket_str: \"127.0.0.1\" }}),
+        ]));",
+        bytes = js_bytes(&bytes),
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            "[192,168,0,1] V6 8080",
+            "10.0.0.254 fe80::1ff:fe23:4567:890a 2001:db8::1 [2001:db8::1]:443",
+            &js_bytes(&bytes).replace(' ', ""),
+            "true",
+            "[false,false,false]",
+        ]
+    );
+}
+
+#[test]
+fn test_byte_vectors() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Blob {
+        data: Vec<u8>,
+        signed: Vec<i8>,
+    }
+
+    let package_dir = build_js_package("byte-vectors", generate_bindings!(Blob));
+
+    let value = Blob {
+        data: vec![0, 127, 128, 255],
+        signed: vec![-1],
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+    let script = format!(
+        "const check = (value) => {{ try {{ p.serialize(\"Blob\", value); return true }} catch {{ return false }} }};
+        const blob = p.deserialize(\"Blob\", {bytes});
+        console.log(blob.data instanceof Uint8Array, Array.isArray(blob.signed));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Blob\", blob))));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Blob\", {{ data: [0, 127, 128, 255], signed: [-1] }}))));
+        console.log(JSON.stringify([
+            check({{ ...blob, data: new Uint8Array(0) }}),
+            check({{ ...blob, data: [256] }}),
+            check({{ ...blob, data: new Int8Array(1) }}),
+        ]));",
+        bytes = js_bytes(&bytes),
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            "true true".into(),
+            js_bytes(&bytes).replace(' ', ""),
+            js_bytes(&bytes).replace(' ', ""),
+            "[true,false,false]".into(),
+        ]
+    );
+}
+
+#[test]
+fn test_cow_fields() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Frame<'a> {
+        name: Cow<'a, str>,
+        payload: Cow<'a, [u8]>,
+    }
+
+    let package_dir = build_js_package("cow-fields", generate_bindings!(Frame));
+
+    let borrowed = Frame {
+        name: Cow::Borrowed("sensor"),
+        payload: Cow::Borrowed(&[1, 2, 3]),
+    };
+    let owned = Frame {
+        name: Cow::Owned("sensor".into()),
+        payload: Cow::Owned(vec![1, 2, 3]),
+    };
+    let bytes = postcard::to_allocvec(&borrowed).unwrap();
+    assert_eq!(bytes, postcard::to_allocvec(&owned).unwrap());
+    let script = format!(
+        "const frame = p.deserialize(\"Frame\", {bytes});
+        console.log(frame.name, frame.payload instanceof Uint8Array);
+        console.log(JSON.stringify(Array.from(p.serialize(\"Frame\", frame))));",
+        bytes = js_bytes(&bytes),
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        ["sensor true".into(), js_bytes(&bytes).replace(' ', "")]
+    );
+}
+
+#[test]
+fn test_borrowed_fields() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Inner {
+        id: u8,
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Record<'a, T> {
+        name: &'a str,
+        raw: &'a [u8],
+        inner: &'a Inner,
+        tags: Vec<&'a str>,
+        note: Option<&'a str>,
+        payload: &'a T,
+    }
+
+    let package_dir = build_js_package("borrowed-fields", generate_bindings!(Inner, Record<u16>));
+
+    let data = [1, 2, 3];
+    let record = Record {
+        name: "sensor",
+        raw: &data,
+        inner: &Inner { id: 7 },
+        tags: vec!["a", "b"],
+        note: None,
+        payload: &300u16,
+    };
+    let bytes = postcard::to_allocvec(&record).unwrap();
+
+    let script = format!(
+        "const record = p.deserialize(\"Record_u16\", {bytes});
+        console.log(record.name, record.raw instanceof Uint8Array, JSON.stringify(record.tags), record.inner.id, record.payload);
+        console.log(JSON.stringify(Array.from(p.serialize(\"Record_u16\", record))));",
+        bytes = js_bytes(&bytes),
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            "sensor true [\"a\",\"b\"] 7 300".into(),
+            js_bytes(&bytes).replace(' ', "")
+        ]
+    );
+}
+
+#[test]
+fn test_boxed_recursive_type() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Node {
+        value: u8,
+        next: Option<Box<Node>>,
+    }
+
+    let package_dir = build_js_package("boxed-recursive-type", generate_bindings!(Node));
+
+    let value = Node {
+        value: 1,
+        next: Some(Box::new(Node {
+            value: 2,
+            next: None,
+        })),
+    };
+    let bytes = postcard::to_allocvec(&value).unwrap();
+    let json = "{\"value\":1,\"next\":{\"value\":2}}";
+
+    let script = format!(
+        "console.log(JSON.stringify(Array.from(p.serialize(\"Node\", {json}))));
+        console.log(JSON.stringify(p.deserialize(\"Node\", {})));",
+        js_bytes(&bytes)
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [js_bytes(&bytes).replace(' ', ""), json.into()]
+    );
+}
+
+#[test]
+fn test_mutually_recursive_types() {
+    #[derive(Serialize, PostcardBindings)]
+    enum Expr {
+        Lit(u8),
+        Neg(Box<Expr>),
+        Add(Box<Expr>, Box<Expr>),
+        Block(Vec<Stmt>),
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Stmt {
+        expr: Box<Expr>,
+        next: Option<Box<Stmt>>,
+    }
+
+    let package_dir = build_js_package("mutually-recursive-types", generate_bindings!(Expr, Stmt));
+
+    let value = Expr::Add(
+        Box::new(Expr::Neg(Box::new(Expr::Lit(1)))),
+        Box::new(Expr::Block(vec![Stmt {
+            expr: Box::new(Expr::Lit(2)),
+            next: Some(Box::new(Stmt {
+                expr: Box::new(Expr::Block(Vec::new())),
+                next: None,
+            })),
+        }])),
+    );
+    let bytes = postcard::to_allocvec(&value).unwrap();
+    let json = "{\"tag\":\"Add\",\"value\":[{\"tag\":\"Neg\",\"value\":{\"tag\":\"Lit\",\"value\":1}},{\"tag\":\"Block\",\"value\":[{\"expr\":{\"tag\":\"Lit\",\"value\":2},\"next\":{\"expr\":{\"tag\":\"Block\",\"value\":[]}}}]}]}";
+
+    let script = format!(
+        "console.log(JSON.stringify(Array.from(p.serialize(\"Expr\", {json}))));
+        console.log(JSON.stringify(p.deserialize(\"Expr\", {})));
+        try {{ p.serialize(\"Stmt\", {{ expr: {{ tag: \"Neg\", value: {{ tag: \"Lit\", value: 256 }} }} }}) }} catch (e) {{ console.log(\"rejected\") }}",
+        js_bytes(&bytes)
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            js_bytes(&bytes).replace(' ', ""),
+            json.into(),
+            "rejected".into()
+        ]
+    );
+}
+
+#[test]
+fn test_phantom_data_fields() {
+    use std::marker::PhantomData;
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Handle {
+        id: u8,
+        marker: PhantomData<String>,
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    struct Id(PhantomData<u32>, u16);
+
+    #[derive(Serialize, PostcardBindings)]
+    enum Event {
+        Opened(Handle, PhantomData<u8>),
+        Closed { id: Id, marker: PhantomData<u8> },
+    }
+
+    let package_dir =
+        build_js_package("phantom-data-fields", generate_bindings!(Handle, Id, Event));
+
+    let value = (
+        Event::Opened(
+            Handle {
+                id: 1,
+                marker: PhantomData,
+            },
+            PhantomData,
+        ),
+        Event::Closed {
+            id: Id(PhantomData, 300),
+            marker: PhantomData,
+        },
+    );
+    let opened = postcard::to_allocvec(&value.0).unwrap();
+    let closed = postcard::to_allocvec(&value.1).unwrap();
+    let script = format!(
+        "console.log(JSON.stringify(p.deserialize(\"Event\", {opened})));
+        console.log(JSON.stringify(p.deserialize(\"Event\", {closed})));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Event\", {{ tag: \"Opened\", value: {{ id: 1 }} }}))));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Event\", {{ tag: \"Closed\", value: {{ id: [300] }} }}))));",
+        opened = js_bytes(&opened),
+        closed = js_bytes(&closed),
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            "{\"tag\":\"Opened\",\"value\":{\"id\":1}}".into(),
+            "{\"tag\":\"Closed\",\"value\":{\"id\":[300]}}".into(),
+            js_bytes(&opened).replace(' ', ""),
+            js_bytes(&closed).replace(' ', ""),
+        ]
+    );
+}
+
+#[test]
+fn test_unit_values() {
+    #[derive(Serialize, PostcardBindings)]
+    struct Ack {
+        id: u8,
+        done: (),
+    }
+
+    #[derive(Serialize, PostcardBindings)]
+    enum Response {
+        Empty((), u8),
+        Acks(Vec<Ack>),
+    }
+
+    let package_dir = build_js_package(
+        "unit-values",
+        generate_bindings!(Ack, Response, Result<(), u8>),
+    );
+
+    let empty = postcard::to_allocvec(&Response::Empty((), 7)).unwrap();
+    let acks = postcard::to_allocvec(&Response::Acks(vec![Ack { id: 1, done: () }])).unwrap();
+    let ok = postcard::to_allocvec(&Ok::<(), u8>(())).unwrap();
+    let script = format!(
+        "const check = (value) => {{ try {{ p.serialize(\"Ack\", value); return true }} catch {{ return false }} }};
+        console.log(JSON.stringify(p.deserialize(\"Response\", {empty})));
+        console.log(JSON.stringify(p.deserialize(\"Response\", {acks})));
+        console.log(JSON.stringify(p.deserialize(\"Result_unit_u8\", {ok})));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Response\", {{ tag: \"Empty\", value: [null, 7] }}))));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Response\", {{ tag: \"Acks\", value: [{{ id: 1, done: null }}] }}))));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Result_unit_u8\", {{ tag: \"Ok\", value: null }}))));
+        console.log(check({{ id: 1, done: null }}), check({{ id: 1, done: 0 }}));",
+        empty = js_bytes(&empty),
+        acks = js_bytes(&acks),
+        ok = js_bytes(&ok),
+    );
+    let Some(output) = run_js(&package_dir, &script) else {
+        return;
+    };
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            "{\"tag\":\"Empty\",\"value\":[null,7]}".into(),
+            "{\"tag\":\"Acks\",\"value\":[{\"id\":1,\"done\":null}]}".into(),
+            "{\"tag\":\"Ok\",\"value\":null}".into(),
+            js_bytes(&empty).replace(' ', ""),
+            js_bytes(&acks).replace(' ', ""),
+            js_bytes(&ok).replace(' ', ""),
+            "true false".into(),
+        ]
+    );
+}
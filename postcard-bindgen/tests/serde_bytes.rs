@@ -0,0 +1,84 @@
+use std::{io::ErrorKind, process::Command};
+
+use postcard_bindgen::{
+    generate_bindings,
+    javascript::{build_package, GenerationSettings},
+    PackageInfo, PostcardBindings,
+};
+use serde::Serialize;
+use serde_bytes::{ByteBuf, Bytes};
+
+#[derive(Serialize, PostcardBindings)]
+struct Frame<'a> {
+    header: [u8; 2],
+    #[serde(with = "serde_bytes")]
+    key: [u8; 4],
+    #[serde(with = "serde_bytes")]
+    payload: Vec<u8>,
+    owned: ByteBuf,
+    borrowed: &'a Bytes,
+}
+
+#[derive(Serialize, PostcardBindings)]
+enum Message {
+    Key(#[serde(with = "serde_bytes")] [u8; 2]),
+}
+
+#[test]
+fn test_serde_bytes_round_trip() {
+    let frame = Frame {
+        header: [0xca, 0xfe],
+        key: [1, 2, 3, 4],
+        payload: vec![5, 6],
+        owned: ByteBuf::from(vec![7]),
+        borrowed: Bytes::new(&[8, 9]),
+    };
+    let frame_bytes = postcard::to_allocvec(&frame).unwrap();
+    // only the byte array with the attribute gets a length
+    assert_eq!(&frame_bytes[..3], [0xca, 0xfe, 4]);
+    let message_bytes = postcard::to_allocvec(&Message::Key([1, 2])).unwrap();
+
+    let parent_dir = std::env::temp_dir().join("postcard-bindgen-serde-bytes");
+    build_package(
+        &parent_dir,
+        PackageInfo {
+            name: "serde-bytes".into(),
+            version: "0.1.0".try_into().unwrap(),
+        },
+        GenerationSettings::enable_all().esm_module(false),
+        generate_bindings!(Frame, Message),
+    )
+    .unwrap();
+
+    let script = format!(
+        "const p = require({package:?});
+        const frame = p.deserialize(\"Frame\", {frame_bytes:?});
+        console.log([frame.key, frame.payload, frame.owned, frame.borrowed].every((b) => b instanceof Uint8Array));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Frame\", frame))));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Message\", p.deserialize(\"Message\", {message_bytes:?})))));",
+        package = parent_dir.join("serde-bytes").join("index.js"),
+    );
+    let output = match Command::new("node").arg("-e").arg(script).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            eprintln!("node not found, skipping serde_bytes test");
+            return;
+        }
+        Err(e) => panic!("failed to run node: {e}"),
+    };
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            "true".into(),
+            format!("{frame_bytes:?}").replace(' ', ""),
+            format!("{message_bytes:?}").replace(' ', ""),
+        ]
+    );
+}
@@ -0,0 +1,127 @@
+use std::{io::ErrorKind, process::Command};
+
+use postcard_bindgen::{
+    javascript::{build_package, GenerationSettings},
+    reflection::from_serde_reflection,
+    PackageInfo,
+};
+use serde::{Deserialize, Serialize};
+use serde_reflection::{ContainerFormat, Format, Registry, Tracer, TracerConfig};
+
+// the types only derive serde, like types of other crates
+#[derive(Serialize, Deserialize)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Meters(u16);
+
+#[allow(dead_code)]
+#[derive(Serialize, Deserialize)]
+enum Shape {
+    Empty,
+    Circle(Point, Meters),
+    Label {
+        text: String,
+        letter: char,
+        data: Vec<u8>,
+        corners: [u8; 2],
+        tag: Option<u64>,
+    },
+}
+
+#[test]
+fn test_traced_types_round_trip() {
+    let mut tracer = Tracer::new(TracerConfig::default());
+    tracer.trace_simple_type::<Shape>().unwrap();
+    let registry = tracer.registry().unwrap();
+
+    let parent_dir = std::env::temp_dir().join("postcard-bindgen-reflection");
+    build_package(
+        parent_dir.as_path(),
+        PackageInfo {
+            name: "reflection".into(),
+            version: "0.1.0".try_into().unwrap(),
+        },
+        GenerationSettings::enable_all().esm_module(false),
+        from_serde_reflection(&registry).unwrap().into_entries(),
+    )
+    .unwrap();
+
+    let circle = postcard::to_allocvec(&Shape::Circle(Point { x: -3, y: 4 }, Meters(500))).unwrap();
+    let label = postcard::to_allocvec(&Shape::Label {
+        text: "ab".into(),
+        letter: 'ä',
+        data: vec![1, 2],
+        corners: [3, 4],
+        tag: Some(5),
+    })
+    .unwrap();
+
+    let script = format!(
+        "const p = require({path:?});
+        const circle = {{ tag: \"Circle\", value: [{{ x: -3, y: 4 }}, [500]] }};
+        const label = {{ tag: \"Label\", value: {{ text: \"ab\", letter: \"ä\", data: [1, 2], corners: [3, 4], tag: 5n }} }};
+        console.log(JSON.stringify(Array.from(p.serialize(\"Shape\", circle))));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Shape\", label))));
+        console.log(p.deserialize(\"Shape\", new Uint8Array({label:?})).value.letter);",
+        path = parent_dir.join("reflection"),
+    );
+    let output = match Command::new("node").arg("-e").arg(script).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            eprintln!("node not found, skipping serde reflection test");
+            return;
+        }
+        Err(e) => panic!("failed to run node: {e}"),
+    };
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            format!("{circle:?}").replace(' ', ""),
+            format!("{label:?}").replace(' ', ""),
+            "ä".into(),
+        ]
+    );
+}
+
+#[test]
+fn test_unit_field() {
+    #[derive(Serialize, Deserialize)]
+    struct WithUnit {
+        a: u8,
+        b: (),
+    }
+
+    let mut tracer = Tracer::new(TracerConfig::default());
+    tracer.trace_simple_type::<WithUnit>().unwrap();
+    let registry = tracer.registry().unwrap();
+
+    let json = from_serde_reflection(&registry).unwrap().to_json();
+    let json = json.split_whitespace().collect::<String>();
+    assert!(
+        json.contains(r#"{"name":"b","type":{"kind":"unit"}"#),
+        "{json}"
+    );
+}
+
+#[test]
+fn test_unknown_format_is_unsupported() {
+    // formats stay unknown if the tracer never saw a value of them
+    let registry = Registry::from([(
+        "Unknown".to_owned(),
+        ContainerFormat::NewTypeStruct(Box::new(Format::unknown())),
+    )]);
+
+    let err = from_serde_reflection(&registry).unwrap_err();
+    assert_eq!(err.container, "Unknown");
+}
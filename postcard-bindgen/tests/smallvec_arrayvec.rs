@@ -0,0 +1,74 @@
+use std::{io::ErrorKind, process::Command};
+
+use arrayvec::{ArrayString, ArrayVec};
+use postcard_bindgen::{
+    generate_bindings,
+    javascript::{build_package, GenerationSettings},
+    PackageInfo, PostcardBindings,
+};
+use serde::Serialize;
+use smallvec::{smallvec, SmallVec};
+
+#[derive(Serialize, PostcardBindings)]
+struct Reading {
+    label: ArrayString<4>,
+    samples: ArrayVec<u16, 2>,
+    history: SmallVec<[u16; 2]>,
+}
+
+#[test]
+fn test_smallvec_and_arrayvec() {
+    let reading = Reading {
+        label: ArrayString::from("temp").unwrap(),
+        samples: ArrayVec::from([300, 301]),
+        history: smallvec![1, 2, 3],
+    };
+    let bytes = postcard::to_allocvec(&reading).unwrap();
+
+    let parent_dir = std::env::temp_dir().join("postcard-bindgen-smallvec-arrayvec");
+    build_package(
+        &parent_dir,
+        PackageInfo {
+            name: "smallvec-arrayvec".into(),
+            version: "0.1.0".try_into().unwrap(),
+        },
+        GenerationSettings::enable_all().esm_module(false),
+        generate_bindings!(Reading),
+    )
+    .unwrap();
+
+    let script = format!(
+        "const p = require({package:?});
+        const check = (reading) => {{ try {{ p.serialize(\"Reading\", reading); return true }} catch (e) {{ return false }} }};
+        const reading = p.deserialize(\"Reading\", {bytes:?});
+        console.log(reading.label, JSON.stringify(reading.samples), JSON.stringify(reading.history));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Reading\", reading))));
+        console.log(check({{ ...reading, samples: [1, 2, 3] }}), check({{ ...reading, label: \"humid\" }}));
+        console.log(check({{ ...reading, history: [1, 2, 3, 4, 5] }}));",
+        package = parent_dir.join("smallvec-arrayvec").join("index.js"),
+    );
+    let output = match Command::new("node").arg("-e").arg(script).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            eprintln!("node not found, skipping smallvec and arrayvec test");
+            return;
+        }
+        Err(e) => panic!("failed to run node: {e}"),
+    };
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            "temp [300,301] [1,2,3]",
+            &format!("{bytes:?}").replace(' ', ""),
+            "false false",
+            "true",
+        ]
+    );
+}
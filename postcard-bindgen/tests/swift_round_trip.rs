@@ -0,0 +1,165 @@
+mod common;
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use common::{build_bindings, byte_list, printed_bytes, run};
+use postcard_bindgen::{
+    generate_bindings,
+    swift::{build_source, GenerationSettings},
+    PostcardBindings,
+};
+use serde::Serialize;
+
+fn build_swift_source(
+    name: &str,
+    bindings: postcard_bindgen::__private::ContainerCollection,
+) -> PathBuf {
+    build_bindings("swift", name, bindings, |dir, bindings| {
+        build_source(dir, "bindings", GenerationSettings::enable_all(), bindings)
+    })
+}
+
+/// Compiles and runs the top level code of `main` together with the generated source.
+fn run_swift(dir: &Path, main: &str) -> String {
+    let main_path = dir.join("main.swift");
+    let exe_path = dir.join("main");
+    std::fs::write(
+        main_path.as_path(),
+        format!(
+            "import Foundation\n\n\
+             func printBytes(_ bytes: Data) {{ print(bytes.map {{ String($0) }}.joined(separator: \" \")) }}\n\n\
+             {main}"
+        ),
+    )
+    .unwrap();
+
+    run(Command::new("swiftc")
+        .arg(&main_path)
+        .arg(dir.join("bindings.swift"))
+        .arg("-o")
+        .arg(&exe_path));
+    run(&mut Command::new(&exe_path))
+}
+
+fn swift_bytes(bytes: &[u8]) -> String {
+    format!("Data([{}] as [UInt8])", byte_list(bytes))
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Inner {
+    id: u16,
+    name: String,
+}
+
+#[derive(Serialize, PostcardBindings)]
+enum Instruction {
+    Stop,
+    Move(i32, i32),
+    Rename { to: String },
+    Inner(Inner),
+}
+
+#[derive(Serialize, PostcardBindings)]
+struct Message {
+    seq: u64,
+    temp: f32,
+    level: i8,
+    data: Vec<u8>,
+    inner: Inner,
+    maybe: Option<u32>,
+    fixed: [u16; 3],
+    pair: (u8, String),
+    // swift dictionaries are unordered, so only a single entry round trips byte for byte
+    table: BTreeMap<u8, String>,
+    instructions: Vec<Instruction>,
+}
+
+#[test]
+#[ignore = "needs swiftc, see tests/common"]
+fn test_decode_and_encode_round_trip() {
+    let dir = build_swift_source(
+        "round-trip",
+        generate_bindings!(Inner, Instruction, Message),
+    );
+    let message = Message {
+        seq: u64::MAX,
+        temp: -1.5,
+        level: -128,
+        data: vec![1, 2, 255],
+        inner: Inner {
+            id: 300,
+            name: "grüße".into(),
+        },
+        maybe: Some(70_000),
+        fixed: [1, 2, 65_535],
+        pair: (7, "seven".into()),
+        table: BTreeMap::from([(1, "one".into())]),
+        instructions: vec![
+            Instruction::Stop,
+            Instruction::Move(-5, 70_000),
+            Instruction::Rename { to: "x".into() },
+            Instruction::Inner(Inner {
+                id: 1,
+                name: "a".into(),
+            }),
+        ],
+    };
+    let bytes = postcard::to_allocvec(&message).unwrap();
+
+    let main = format!(
+        "let message = try! deserialize(Message.self, from: {})
+if case let .Move(x, y) = message.instructions[1] {{
+    print(\"\\(message.inner.name) \\(message.seq) \\(x) \\(y)\")
+}}
+printBytes(serialize(message))",
+        swift_bytes(&bytes)
+    );
+    let output = run_swift(&dir, &main);
+    let mut lines = output.lines();
+
+    assert_eq!(
+        lines.next().unwrap(),
+        format!("grüße {} -5 70000", u64::MAX)
+    );
+    assert_eq!(lines.next().unwrap(), printed_bytes(&bytes));
+}
+
+#[test]
+#[ignore = "needs swiftc, see tests/common"]
+fn test_decode_rejects_invalid_input() {
+    let dir = build_swift_source("invalid", generate_bindings!(Inner, Instruction));
+    let bytes = postcard::to_allocvec(&Instruction::Inner(Inner {
+        id: 1,
+        name: "a".into(),
+    }))
+    .unwrap();
+
+    let main = format!(
+        "func rejects(_ bytes: Data) -> Bool {{
+    do {{
+        _ = try deserialize(Instruction.self, from: bytes)
+        return false
+    }} catch {{
+        return true
+    }}
+}}
+
+let input = {}
+print(rejects(input))
+print(rejects(input.dropLast()))
+print(rejects(input + Data([0])))
+print(rejects(Data([2, 1, 0x80])))
+print(rejects(Data([4])))",
+        swift_bytes(&bytes)
+    );
+    let output = run_swift(&dir, &main);
+
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        ["false", "true", "true", "true", "true"]
+    );
+}
@@ -0,0 +1,104 @@
+use std::{io::ErrorKind, process::Command};
+
+use postcard_bindgen::{
+    generate_bindings,
+    javascript::{build_ts_module, GenerationSettings},
+    PostcardBindings,
+};
+use serde::Serialize;
+
+#[test]
+fn test_type_alias_declarations() {
+    type NodeId = u32;
+
+    #[allow(dead_code)]
+    #[derive(Serialize, PostcardBindings)]
+    struct Edge {
+        #[postcard_bindgen(alias)]
+        from: NodeId,
+        #[postcard_bindgen(alias)]
+        to: NodeId,
+        #[postcard_bindgen(alias = "Label")]
+        label: Option<String>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Serialize, PostcardBindings)]
+    enum Change {
+        Removed {
+            #[postcard_bindgen(alias)]
+            node: NodeId,
+        },
+    }
+
+    let dir = std::env::temp_dir().join("postcard-bindgen-ts-alias");
+    build_ts_module(
+        dir.as_path(),
+        "bindings",
+        GenerationSettings::enable_all(),
+        generate_bindings!(Edge, Change),
+    )
+    .unwrap();
+
+    let module = std::fs::read_to_string(dir.join("bindings.ts")).unwrap();
+    assert!(module.contains("export type Label = string | none"));
+    assert!(module.contains("export type NodeId = u32"));
+    assert!(module.contains("export type Edge = { from: NodeId, to: NodeId, label: Label }"));
+    assert!(module.contains("{ tag: \"Removed\", value: { node: NodeId } }"));
+}
+
+#[test]
+fn test_ts_module_type_checks() {
+    #[allow(dead_code)]
+    #[derive(Serialize, PostcardBindings)]
+    struct A {
+        a: u8,
+        b: Vec<u16>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Serialize, PostcardBindings)]
+    enum B {
+        A,
+        B(A),
+    }
+
+    let dir = std::env::temp_dir().join("postcard-bindgen-ts");
+    build_ts_module(
+        dir.as_path(),
+        "bindings",
+        GenerationSettings::enable_all(),
+        generate_bindings!(A, B),
+    )
+    .unwrap();
+
+    let main_path = dir.join("main.ts");
+    std::fs::write(
+        main_path.as_path(),
+        "import { deserialize, serialize, type B } from \"./bindings\";
+        const bytes: Uint8Array = serialize(\"B\", { tag: \"B\", value: { a: 1, b: [2, 3] } });
+        const value: B = deserialize(\"B\", bytes);
+        console.log(JSON.stringify(value));",
+    )
+    .unwrap();
+
+    let output = match Command::new("tsc")
+        .args([
+            "--noEmit", "--strict", "--target", "es2020", "--module", "es2020",
+        ])
+        .arg(&main_path)
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            eprintln!("tsc not found, skipping typescript check");
+            return;
+        }
+        Err(e) => panic!("failed to run tsc: {e}"),
+    };
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
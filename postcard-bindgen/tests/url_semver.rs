@@ -0,0 +1,107 @@
+use std::{io::ErrorKind, process::Command};
+
+use postcard_bindgen::{
+    generate_bindings,
+    javascript::{build_package, GenerationSettings},
+    PackageInfo, PostcardBindings,
+};
+use serde::Serialize;
+
+#[derive(Serialize, PostcardBindings)]
+struct Release {
+    version: semver::Version,
+    download: url::Url,
+}
+
+#[test]
+fn test_url_semver_round_trip() {
+    let release = Release {
+        version: "1.2.3-beta.1+build.5".parse().unwrap(),
+        download: "https://example.com/firmware.bin?board=a".parse().unwrap(),
+    };
+    let bytes = postcard::to_allocvec(&release).unwrap();
+
+    let parent_dir = std::env::temp_dir().join("postcard-bindgen-url-semver");
+    build_package(
+        &parent_dir,
+        PackageInfo {
+            name: "url-semver".into(),
+            version: "0.1.0".try_into().unwrap(),
+        },
+        GenerationSettings::enable_all().esm_module(false),
+        generate_bindings!(Release),
+    )
+    .unwrap();
+
+    let script = format!(
+        "const p = require({package:?});
+        const release = p.deserialize(\"Release\", {bytes:?});
+        console.log(JSON.stringify(release));
+        console.log(JSON.stringify(Array.from(p.serialize(\"Release\", release))));
+        for (const invalid of [{{ version: \"1.2\" }}, {{ version: \"01.2.3\" }}, {{ download: \"example.com\" }}]) {{
+            try {{ p.serialize(\"Release\", {{ ...release, ...invalid }}); console.log(\"accepted\") }} catch (e) {{ console.log(\"rejected\") }}
+        }}",
+        package = parent_dir.join("url-semver").join("index.js"),
+    );
+    let output = match Command::new("node").arg("-e").arg(script).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            eprintln!("node not found, skipping url and semver test");
+            return;
+        }
+        Err(e) => panic!("failed to run node: {e}"),
+    };
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            "{\"version\":\"1.2.3-beta.1+build.5\",\"download\":\"https://example.com/firmware.bin?board=a\"}",
+            &format!("{bytes:?}").replace(' ', ""),
+            "rejected",
+            "rejected",
+            "rejected",
+        ]
+    );
+}
+
+#[test]
+fn test_url_semver_self_test() {
+    let parent_dir = std::env::temp_dir().join("postcard-bindgen-url-semver");
+    build_package(
+        &parent_dir,
+        PackageInfo {
+            name: "url-semver-self-test".into(),
+            version: "0.1.0".try_into().unwrap(),
+        },
+        GenerationSettings::enable_all()
+            .esm_module(false)
+            .self_test(true),
+        generate_bindings!(Release),
+    )
+    .unwrap();
+
+    let script = format!(
+        "require({package:?}).__selfTest(); console.log(\"ok\")",
+        package = parent_dir.join("url-semver-self-test").join("index.js"),
+    );
+    let output = match Command::new("node").arg("-e").arg(script).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            eprintln!("node not found, skipping url and semver self test");
+            return;
+        }
+        Err(e) => panic!("failed to run node: {e}"),
+    };
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8(output.stdout).unwrap().trim(), "ok");
+}
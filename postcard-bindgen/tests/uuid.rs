@@ -0,0 +1,79 @@
+use std::{io::ErrorKind, process::Command};
+
+use postcard_bindgen::{
+    generate_bindings,
+    javascript::{build_package, Codec, GenerationSettings},
+    PackageInfo, PostcardBindings,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Serialize, PostcardBindings)]
+struct Device {
+    raw: Uuid,
+    #[postcard_bindgen(codec = "uuid_bytes")]
+    bytes: Uuid,
+    #[postcard_bindgen(codec = "uuid")]
+    id: Uuid,
+}
+
+#[test]
+fn test_uuid_round_trip() {
+    let id = Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+    let bytes = postcard::to_allocvec(&Device {
+        raw: id,
+        bytes: id,
+        id,
+    })
+    .unwrap();
+    assert_eq!(bytes.len(), 3 * 17);
+
+    let parent_dir = std::env::temp_dir().join("postcard-bindgen-uuid");
+    build_package(
+        &parent_dir,
+        PackageInfo {
+            name: "uuid".into(),
+            version: "0.1.0".try_into().unwrap(),
+        },
+        GenerationSettings::enable_all()
+            .esm_module(false)
+            .codec("uuid", Codec::uuid())
+            .codec("uuid_bytes", Codec::uuid_bytes()),
+        generate_bindings!(Device),
+    )
+    .unwrap();
+
+    let script = format!(
+        "const p = require({package:?});
+        const device = p.deserialize(\"Device\", {bytes:?});
+        console.log(device.id, device.raw.length, device.bytes instanceof Uint8Array);
+        console.log(JSON.stringify(Array.from(p.serialize(\"Device\", device))));
+        try {{ p.serialize(\"Device\", {{ ...device, id: \"not-a-uuid\" }}) }} catch (e) {{ console.log(\"rejected\") }}
+        try {{ p.serialize(\"Device\", {{ ...device, raw: device.raw.slice(1) }}) }} catch (e) {{ console.log(\"rejected\") }}",
+        package = parent_dir.join("uuid").join("index.js"),
+    );
+    let output = match Command::new("node").arg("-e").arg(script).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            eprintln!("node not found, skipping uuid test");
+            return;
+        }
+        Err(e) => panic!("failed to run node: {e}"),
+    };
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [
+            "67e55044-10b1-426f-9247-bb680e5fe0c8 16 true",
+            &format!("{bytes:?}").replace(' ', ""),
+            "rejected",
+            "rejected",
+        ]
+    );
+}
@@ -0,0 +1,125 @@
+use std::{io::ErrorKind, process::Command};
+
+use postcard_bindgen::{
+    generate_bindings,
+    javascript::{build_web_module, GenerationSettings},
+    PostcardBindings,
+};
+use serde::Serialize;
+
+#[derive(Serialize, PostcardBindings)]
+struct A {
+    a: u8,
+    b: Vec<u16>,
+    c: String,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize, PostcardBindings)]
+enum B {
+    A,
+    B(A),
+}
+
+#[test]
+fn test_web_module_without_node_globals() {
+    let dir = std::env::temp_dir().join("postcard-bindgen-web");
+    build_web_module(
+        dir.as_path(),
+        "bindings",
+        GenerationSettings::enable_all(),
+        generate_bindings!(A, B),
+    )
+    .unwrap();
+
+    let value = B::B(A {
+        a: 1,
+        b: vec![2, 300],
+        c: "ä".into(),
+    });
+    let bytes = postcard::to_allocvec(&value).unwrap();
+
+    // the context only has the language builtins and the web apis the module may use, so
+    // `Buffer`, `require` or `module` throw a `ReferenceError`
+    let script = format!(
+        "const vm = require(\"vm\");
+        const source = require(\"fs\").readFileSync({path:?}, \"utf8\");
+        const context = vm.createContext({{ TextEncoder, TextDecoder }});
+        const module = new vm.SourceTextModule(source, {{ context }});
+        module.link(() => {{ throw \"no imports expected\" }})
+            .then(() => module.evaluate())
+            .then(() => {{
+                const p = module.namespace;
+                const value = {{ tag: \"B\", value: {{ a: 1, b: [2, 300], c: \"ä\" }} }};
+                console.log(JSON.stringify(Array.from(p.serialize(\"B\", value))));
+                console.log(JSON.stringify(p.deserialize(\"B\", {bytes:?})) === JSON.stringify(value));
+            }})
+            .catch((e) => {{ console.error(e); process.exit(1) }});",
+        path = dir.join("bindings.js"),
+    );
+    let output = match Command::new("node")
+        .arg("--experimental-vm-modules")
+        .arg("--no-warnings")
+        .arg("-e")
+        .arg(script)
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            eprintln!("node not found, skipping web module test");
+            return;
+        }
+        Err(e) => panic!("failed to run node: {e}"),
+    };
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        [format!("{bytes:?}").replace(' ', ""), "true".into()]
+    );
+}
+
+#[test]
+fn test_web_module_type_checks_with_deno() {
+    let dir = std::env::temp_dir().join("postcard-bindgen-web-deno");
+    build_web_module(
+        dir.as_path(),
+        "bindings",
+        GenerationSettings::enable_all(),
+        generate_bindings!(A, B),
+    )
+    .unwrap();
+
+    let source = std::fs::read_to_string(dir.join("bindings.js")).unwrap();
+    assert!(source.starts_with("// @ts-self-types=\"./bindings.d.ts\""));
+    assert!(dir.join("bindings.d.ts").exists());
+
+    let main_path = dir.join("main.ts");
+    std::fs::write(
+        main_path.as_path(),
+        "import { deserialize, serialize, type B } from \"./bindings.js\";
+        const bytes: Uint8Array = serialize(\"B\", { tag: \"B\", value: { a: 1, b: [2, 3], c: \"\" } });
+        const value: B = deserialize(\"B\", bytes);
+        console.log(JSON.stringify(value));",
+    )
+    .unwrap();
+
+    let output = match Command::new("deno").arg("check").arg(&main_path).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            eprintln!("deno not found, skipping deno check");
+            return;
+        }
+        Err(e) => panic!("failed to run deno: {e}"),
+    };
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}